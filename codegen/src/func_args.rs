@@ -0,0 +1,96 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, DataStruct, DeriveInput, Fields, GenericArgument, PathArguments, Type};
+
+/// Is this type an `Option<T>`?
+fn is_option_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => match path.path.segments.last() {
+            Some(seg) if seg.ident == "Option" => matches!(
+                seg.arguments,
+                PathArguments::AngleBracketed(ref args)
+                    if args.args.len() == 1 && matches!(args.args[0], GenericArgument::Type(..))
+            ),
+            _ => false,
+        },
+        Type::Group(group) => is_option_type(&group.elem),
+        Type::Paren(paren) => is_option_type(&paren.elem),
+        _ => false,
+    }
+}
+
+/// Derive the `FuncArgs` trait for a struct.
+pub fn derive_func_args_impl(input: DeriveInput) -> TokenStream {
+    let type_name = input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(ref f),
+            ..
+        }) => f.named.iter().collect::<Vec<_>>(),
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(ref f),
+            ..
+        }) => f.unnamed.iter().collect::<Vec<_>>(),
+        Data::Struct(DataStruct {
+            fields: Fields::Unit,
+            ..
+        }) => Vec::new(),
+
+        // enum ...
+        Data::Enum(_) => {
+            return syn::Error::new(Span::call_site(), "enums are not supported")
+                .into_compile_error()
+        }
+
+        // union ...
+        Data::Union(_) => {
+            return syn::Error::new(Span::call_site(), "unions are not supported")
+                .into_compile_error()
+        }
+    };
+
+    let mut args = Vec::new();
+    let mut optional = false;
+
+    for (i, field) in fields.into_iter().enumerate() {
+        let field_name = if let Some(ref field_name) = field.ident {
+            quote! { #field_name }
+        } else {
+            let index = proc_macro2::Literal::usize_unsuffixed(i);
+            quote! { #index }
+        };
+
+        if is_option_type(&field.ty) {
+            // Optional arguments stop at the first `None`
+            optional = true;
+            args.push(quote! {
+                match self.#field_name {
+                    Some(value) => args.extend(Some(rhai::Dynamic::from(value))),
+                    None => return,
+                }
+            });
+        } else if optional {
+            return syn::Error::new_spanned(
+                &field.ty,
+                "non-optional arguments cannot follow optional arguments",
+            )
+            .into_compile_error();
+        } else {
+            args.push(quote! {
+                args.extend(Some(rhai::Dynamic::from(self.#field_name)));
+            });
+        }
+    }
+
+    quote! {
+        impl #impl_generics rhai::FuncArgs for #type_name #type_generics #where_clause {
+            #[inline]
+            #[allow(unused_variables)]
+            fn parse<ARGS: Extend<rhai::Dynamic>>(self, args: &mut ARGS) {
+                #(#args)*
+            }
+        }
+    }
+}
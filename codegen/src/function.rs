@@ -70,6 +70,27 @@ pub fn flatten_type_groups(ty: &syn::Type) -> &syn::Type {
     }
 }
 
+/// If the type is `Option<T>`, return `T`.
+pub fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match flatten_type_groups(ty) {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return None,
+    };
+    let seg = path.segments.last()?;
+    if seg.ident != "Option" {
+        return None;
+    }
+    match seg.arguments {
+        syn::PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => {
+            match args.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 pub fn print_type(ty: &syn::Type) -> String {
     ty.to_token_stream()
         .to_string()
@@ -89,6 +110,7 @@ pub struct ExportedFnParams {
     pub pure: Option<Span>,
     pub volatile: Option<Span>,
     pub skip: bool,
+    pub tests: Vec<String>,
     pub special: FnSpecialAccess,
     pub namespace: FnNamespaceAccess,
     pub span: Option<Span>,
@@ -129,6 +151,7 @@ impl ExportedParams for ExportedFnParams {
         let mut pure = None;
         let mut volatile = None;
         let mut skip = false;
+        let mut tests = Vec::new();
         let mut namespace = FnNamespaceAccess::Unset;
         let mut special = FnSpecialAccess::None;
         for attr in attrs {
@@ -138,7 +161,7 @@ impl ExportedParams for ExportedFnParams {
                 span: item_span,
             } = attr;
             match (key.to_string().as_ref(), value) {
-                ("get", None) | ("set", None) | ("name", None) => {
+                ("get", None) | ("set", None) | ("name", None) | ("test", None) => {
                     return Err(syn::Error::new(key.span(), "requires value"))
                 }
                 ("name", Some(s)) if s.value() == FN_IDX_GET => {
@@ -172,6 +195,7 @@ impl ExportedParams for ExportedFnParams {
                     ))
                 }
                 ("name", Some(s)) => name.push(s.value()),
+                ("test", Some(s)) => tests.push(s.value()),
 
                 ("index_get", Some(s))
                 | ("index_set", Some(s))
@@ -256,6 +280,7 @@ impl ExportedParams for ExportedFnParams {
             pure,
             volatile,
             skip,
+            tests,
             special,
             namespace,
             span: Some(span),
@@ -272,6 +297,7 @@ pub struct ExportedFn {
     mut_receiver: bool,
     params: ExportedFnParams,
     cfg_attrs: Vec<syn::Attribute>,
+    optional_as_unit: bool,
     #[cfg(feature = "metadata")]
     comments: Vec<String>,
 }
@@ -397,6 +423,7 @@ impl Parse for ExportedFn {
             mut_receiver,
             params: <_>::default(),
             cfg_attrs,
+            optional_as_unit: false,
             #[cfg(feature = "metadata")]
             comments: Vec::new(),
         })
@@ -428,6 +455,10 @@ impl ExportedFn {
         self.params.skip
     }
 
+    pub fn set_optional_as_unit(&mut self, optional_as_unit: bool) {
+        self.optional_as_unit = optional_as_unit;
+    }
+
     pub fn pass_context(&self) -> bool {
         self.pass_context
     }
@@ -744,6 +775,12 @@ impl ExportedFn {
                     #[cfg(feature = "metadata")]
                     let arg_name = format!("{}: {}", pat.to_token_stream(), print_type(ty));
                     let arg_type = ty.as_ref();
+                    // With `optional_as_unit`, `Option<T>` parameters also accept `()` as `None`.
+                    let option_type = if self.optional_as_unit {
+                        option_inner_type(arg_type)
+                    } else {
+                        None
+                    };
                     let downcast_span = match flatten_type_groups(arg_type) {
                         syn::Type::Reference(syn::TypeReference {
                             mutability: None,
@@ -766,6 +803,23 @@ impl ExportedFn {
                                 mem::take(args[#i]).into_string().unwrap()
                             )
                         }
+                        _ if option_type.is_some() => {
+                            let inner_type = option_type.unwrap();
+                            is_string = false;
+                            is_ref = false;
+                            quote_spanned!(arg_type.span().resolved_at(Span::call_site()) =>
+                                match mem::take(args[#i]) {
+                                    v if v.is_unit() => None,
+                                    v => Some(v.try_cast_result::<#inner_type>().map_err(|v| {
+                                        EvalAltResult::ErrorMismatchDataType(
+                                            std::any::type_name::<#inner_type>().into(),
+                                            v.type_name().into(),
+                                            Position::NONE,
+                                        )
+                                    })?),
+                                }
+                            )
+                        }
                         _ => {
                             is_string = false;
                             is_ref = false;
@@ -783,7 +837,14 @@ impl ExportedFn {
                     );
                     #[cfg(feature = "metadata")]
                     input_type_names.push(arg_name);
-                    if !is_string {
+                    if option_type.is_some() {
+                        input_type_exprs.push(
+                            syn::parse2::<syn::Expr>(quote_spanned!(arg_type.span() =>
+                                TypeId::of::<Dynamic>()
+                            ))
+                            .unwrap(),
+                        );
+                    } else if !is_string {
                         input_type_exprs.push(
                             syn::parse2::<syn::Expr>(quote_spanned!(arg_type.span() =>
                                 TypeId::of::<#arg_type>()
@@ -824,7 +885,14 @@ impl ExportedFn {
             .map(|r| r.span())
             .unwrap_or_else(Span::call_site)
             .resolved_at(Span::call_site());
-        let return_expr = if self.params.return_raw.is_none() {
+        let return_option =
+            self.optional_as_unit && self.return_type().and_then(option_inner_type).is_some();
+        let return_expr = if return_option && self.params.return_raw.is_none() {
+            // With `optional_as_unit`, `Option<T>` return values map to `T` or `()`.
+            quote_spanned! { return_span =>
+                Ok(#sig_name(#(#unpack_exprs),*).map_or(Dynamic::UNIT, Dynamic::from))
+            }
+        } else if self.params.return_raw.is_none() {
             quote_spanned! { return_span =>
                 Ok(Dynamic::from(#sig_name(#(#unpack_exprs),*)))
             }
@@ -38,7 +38,10 @@ use syn::{parse_macro_input, spanned::Spanned, DeriveInput};
 
 mod attrs;
 mod custom_type;
+mod func_args;
 mod function;
+#[cfg(feature = "metadata")]
+mod metadata;
 mod module;
 mod register;
 mod rhai_module;
@@ -71,6 +74,18 @@ mod test;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// # Metadata Export
+///
+/// Under the `metadata` feature, `#[export_module(export_metadata)]` writes a JSON file describing
+/// all the functions (with parameters, return types and doc-comments), constants, custom types and
+/// sub-modules of the plugin module at compile time, so that documentation can be generated
+/// without instantiating an `Engine`.
+///
+/// The file is named after the module and placed in `OUT_DIR` if the crate has a build script,
+/// otherwise in `target/rhai-metadata` under the crate's root directory.
+/// Use `#[export_module(export_metadata = "path/to/file.json")]` to specify a path relative to
+/// the crate's root directory instead.
 #[proc_macro_attribute]
 pub fn export_module(args: TokenStream, input: TokenStream) -> TokenStream {
     let parsed_params = match crate::attrs::outer_item_attributes(args.into(), "export_module") {
@@ -81,6 +96,10 @@ pub fn export_module(args: TokenStream, input: TokenStream) -> TokenStream {
     if let Err(e) = module_def.set_params(parsed_params) {
         return e.to_compile_error().into();
     }
+    #[cfg(feature = "metadata")]
+    if let Err(e) = module_def.export_metadata() {
+        return e.to_compile_error().into();
+    }
 
     let tokens = module_def.generate();
     TokenStream::from(tokens)
@@ -119,6 +138,44 @@ pub fn exported_module(module_path: TokenStream) -> TokenStream {
     })
 }
 
+/// Macro to generate a Rhai `Module` from a _plugin module_ defined via [`#[export_module]`][macro@export_module],
+/// only once, returning a shared module.
+///
+/// The module is generated on first use and cached, so repeated calls (e.g. when constructing
+/// many `Engine` instances) simply clone the shared module.
+///
+/// Under the `sync` feature, the module is cached in a `static` and shared across all threads.
+/// Otherwise, it is cached once per thread.
+///
+/// # Usage
+///
+/// ```
+/// # use rhai::{Engine, Module, EvalAltResult};
+/// use rhai::plugin::*;
+///
+/// #[export_module]
+/// mod my_plugin_module {
+///     pub fn foo(x: i64) -> i64 { x * 2 }
+///     pub fn bar() -> i64 { 21 }
+/// }
+///
+/// # fn main() -> Result<(), Box<EvalAltResult>> {
+/// let mut engine = Engine::new();
+///
+/// engine.register_global_module(exported_static_module!(my_plugin_module));
+///
+/// assert_eq!(engine.eval::<i64>("foo(bar())")?, 42);
+/// # Ok(())
+/// # }
+/// ```
+#[proc_macro]
+pub fn exported_static_module(module_path: TokenStream) -> TokenStream {
+    let module_path = parse_macro_input!(module_path as syn::Path);
+    TokenStream::from(quote::quote! {
+        rhai::__exported_static_module!(#module_path::rhai_module_generate())
+    })
+}
+
 /// Macro to combine a _plugin module_ into an existing module.
 ///
 /// Functions and variables in the plugin module overrides any existing similarly-named
@@ -309,6 +366,47 @@ pub fn derive_custom_type(input: TokenStream) -> TokenStream {
     expanded.into()
 }
 
+/// Macro to implement the [`FuncArgs`][rhai::FuncArgs] trait, so that a struct can be passed as
+/// function call arguments to `Engine::call_fn`.
+///
+/// Each field is converted into a positional argument, in order.
+///
+/// Fields of type `Option<T>` are optional arguments and must be placed after all other fields.
+/// Optional arguments stop at the first `None` field, so that script functions with fewer
+/// parameters can be called.
+///
+/// # Usage
+///
+/// ```
+/// use rhai::{Engine, FuncArgs, Scope};
+///
+/// #[derive(FuncArgs)]
+/// struct Args {
+///     x: i64,
+///     y: i64,
+///     z: Option<i64>,
+/// }
+///
+/// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+/// let engine = Engine::new();
+/// let ast = engine.compile("fn calc(x, y) { x + y } fn calc(x, y, z) { x * y * z }")?;
+/// let mut scope = Scope::new();
+///
+/// let result = engine.call_fn::<i64>(&mut scope, &ast, "calc", Args { x: 2, y: 3, z: None })?;
+/// assert_eq!(result, 5);
+///
+/// let result = engine.call_fn::<i64>(&mut scope, &ast, "calc", Args { x: 2, y: 3, z: Some(4) })?;
+/// assert_eq!(result, 24);
+/// # Ok(())
+/// # }
+/// ```
+#[proc_macro_derive(FuncArgs)]
+pub fn derive_func_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let expanded = func_args::derive_func_args_impl(input);
+    expanded.into()
+}
+
 /// Macro to automatically expose a Rust function, type-def or use statement as `pub` when under the
 /// `internals` feature.
 ///
@@ -0,0 +1,224 @@
+//! Export of plugin module metadata to JSON files at compile time.
+
+use proc_macro2::Span;
+use quote::ToTokens;
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::function::{print_type, ExportedFn, FnNamespaceAccess};
+use crate::module::Module;
+use crate::rhai_module::{fn_namespace, ExportedType};
+
+/// Sub-directory (under `target`) holding metadata files when no `OUT_DIR` is available.
+const METADATA_DIR: &str = "rhai-metadata";
+
+/// A JSON value.
+#[derive(Clone)]
+enum Json {
+    Bool(bool),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    fn str(s: impl Into<String>) -> Self {
+        Self::Str(s.into())
+    }
+
+    fn strings(list: &[String]) -> Self {
+        Self::Array(list.iter().cloned().map(Self::Str).collect())
+    }
+
+    fn write_str(output: &mut String, s: &str) {
+        output.push('"');
+        for ch in s.chars() {
+            match ch {
+                '"' => output.push_str("\\\""),
+                '\\' => output.push_str("\\\\"),
+                '\n' => output.push_str("\\n"),
+                '\r' => output.push_str("\\r"),
+                '\t' => output.push_str("\\t"),
+                ch if ch.is_control() => write!(output, "\\u{:04x}", ch as u32).unwrap(),
+                ch => output.push(ch),
+            }
+        }
+        output.push('"');
+    }
+
+    fn write(&self, output: &mut String, indent: usize) {
+        let pad = "  ".repeat(indent + 1);
+
+        match self {
+            Self::Bool(b) => write!(output, "{b}").unwrap(),
+            Self::Str(s) => Self::write_str(output, s),
+            Self::Array(list) if list.is_empty() => output.push_str("[]"),
+            Self::Array(list) => {
+                output.push_str("[\n");
+                for (i, item) in list.iter().enumerate() {
+                    if i > 0 {
+                        output.push_str(",\n");
+                    }
+                    output.push_str(&pad);
+                    item.write(output, indent + 1);
+                }
+                write!(output, "\n{}]", "  ".repeat(indent)).unwrap();
+            }
+            Self::Object(fields) => {
+                output.push_str("{\n");
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        output.push_str(",\n");
+                    }
+                    output.push_str(&pad);
+                    Self::write_str(output, key);
+                    output.push_str(": ");
+                    value.write(output, indent + 1);
+                }
+                write!(output, "\n{}}}", "  ".repeat(indent)).unwrap();
+            }
+        }
+    }
+}
+
+/// Generate the metadata of a function, one entry for each name it is registered under.
+fn fn_metadata(function: &ExportedFn) -> impl Iterator<Item = Json> + '_ {
+    let params: Vec<_> = function
+        .arg_list()
+        .map(|arg| match arg {
+            syn::FnArg::Typed(syn::PatType { pat, ty, .. }) => Json::Object(vec![
+                ("name", Json::str(pat.to_token_stream().to_string())),
+                ("type", Json::str(print_type(ty))),
+            ]),
+            syn::FnArg::Receiver(..) => unreachable!("receivers are not allowed"),
+        })
+        .collect();
+
+    let return_type = function
+        .return_type()
+        .map_or_else(|| "()".to_string(), print_type);
+
+    function.exported_names().into_iter().map(move |name| {
+        let name = name.value();
+        let namespace = match fn_namespace(function, &name) {
+            FnNamespaceAccess::Global => "global",
+            _ => "internal",
+        };
+
+        Json::Object(vec![
+            ("name", Json::str(name)),
+            ("rustName", Json::str(function.name().to_string())),
+            ("namespace", Json::str(namespace)),
+            (
+                "pure",
+                Json::Bool(!function.mutable_receiver() || function.params().pure.is_some()),
+            ),
+            ("volatile", Json::Bool(function.params().volatile.is_some())),
+            (
+                "returnRaw",
+                Json::Bool(function.params().return_raw.is_some()),
+            ),
+            ("params", Json::Array(params.clone())),
+            ("returnType", Json::str(&return_type)),
+            ("docComments", Json::strings(function.comments())),
+        ])
+    })
+}
+
+/// Generate the metadata of a plugin module, including its exported sub-modules.
+fn module_metadata(module: &mut Module) -> syn::Result<Json> {
+    let doc = crate::attrs::doc_attributes(module.attrs())?;
+    let scope = module.scope().clone();
+
+    let mut functions = Vec::new();
+
+    for function in module.fns_mut() {
+        function.update_scope(&scope);
+        if !function.skipped() {
+            functions.extend(fn_metadata(function));
+        }
+    }
+
+    let constants = module.consts().iter().map(|c| Json::str(&c.name)).collect();
+
+    let types = module
+        .custom_types()
+        .iter()
+        .map(
+            |ExportedType {
+                 name,
+                 typ,
+                 comments,
+                 ..
+             }| {
+                Json::Object(vec![
+                    ("name", Json::str(name)),
+                    ("type", Json::str(print_type(typ))),
+                    ("docComments", Json::strings(comments)),
+                ])
+            },
+        )
+        .collect();
+
+    let mut modules = Vec::new();
+
+    for sub_module in module.sub_modules_mut() {
+        sub_module.update_scope(&scope);
+        if !sub_module.skipped() {
+            modules.push(module_metadata(sub_module)?);
+        }
+    }
+
+    Ok(Json::Object(vec![
+        ("name", Json::str(module.exported_name())),
+        ("docComments", Json::strings(&doc)),
+        ("constants", Json::Array(constants)),
+        ("types", Json::Array(types)),
+        ("functions", Json::Array(functions)),
+        ("modules", Json::Array(modules)),
+    ]))
+}
+
+/// Generate the metadata of a plugin module in JSON format.
+pub fn generate_json(module: &mut Module) -> syn::Result<String> {
+    let mut output = String::new();
+    module_metadata(module)?.write(&mut output, 0);
+    output.push('\n');
+    Ok(output)
+}
+
+/// Write the metadata of a plugin module in JSON format to a file.
+///
+/// If `path` is empty, the file is named after the module and placed in `OUT_DIR` (if the crate
+/// has a build script) or in `target/rhai-metadata` under the crate's root directory.
+/// Otherwise, `path` is relative to the crate's root directory.
+pub fn write_json(module: &mut Module, path: &str) -> syn::Result<()> {
+    let err = |msg: String| syn::Error::new(Span::call_site(), msg);
+
+    let root = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| err("cannot export metadata: CARGO_MANIFEST_DIR not set".to_string()))?;
+
+    let file = if !path.is_empty() {
+        root.join(path)
+    } else {
+        let dir = std::env::var_os("OUT_DIR")
+            .map_or_else(|| root.join("target").join(METADATA_DIR), PathBuf::from);
+        dir.join(format!("{}.json", module.exported_name()))
+    };
+
+    let json = generate_json(module)?;
+
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| err(format!("cannot create directory '{}': {e}", dir.display())))?;
+    }
+
+    std::fs::write(&file, json).map_err(|e| {
+        err(format!(
+            "cannot write metadata to '{}': {e}",
+            file.display()
+        ))
+    })
+}
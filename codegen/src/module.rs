@@ -13,6 +13,8 @@ pub struct ExportedModParams {
     pub name: String,
     skip: bool,
     pub scope: ExportScope,
+    pub export_metadata: Option<String>,
+    pub optional_as_unit: bool,
 }
 
 impl Parse for ExportedModParams {
@@ -39,6 +41,8 @@ impl ExportedParams for ExportedModParams {
         let mut name = String::new();
         let mut skip = false;
         let mut scope = None;
+        let mut export_metadata = None;
+        let mut optional_as_unit = false;
         for attr in attrs {
             let AttrItem { key, value, .. } = attr;
             match (key.to_string().as_ref(), value) {
@@ -65,6 +69,23 @@ impl ExportedParams for ExportedModParams {
                 ("export_all", Some(s)) => {
                     return Err(syn::Error::new(s.span(), "extraneous value"))
                 }
+
+                ("export_metadata", ..) if !cfg!(feature = "metadata") => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "'export_metadata' requires the 'metadata' feature",
+                    ))
+                }
+                ("export_metadata", ..) if export_metadata.is_some() => {
+                    return Err(syn::Error::new(key.span(), "duplicate 'export_metadata'"))
+                }
+                ("export_metadata", None) => export_metadata = Some(String::new()),
+                ("export_metadata", Some(s)) => export_metadata = Some(s.value()),
+
+                ("optional_as_unit", None) => optional_as_unit = true,
+                ("optional_as_unit", Some(s)) => {
+                    return Err(syn::Error::new(s.span(), "extraneous value"))
+                }
                 (attr, ..) => {
                     return Err(syn::Error::new(
                         key.span(),
@@ -76,7 +97,13 @@ impl ExportedParams for ExportedModParams {
 
         let scope = scope.unwrap_or_default();
 
-        Ok(ExportedModParams { name, skip, scope })
+        Ok(ExportedModParams {
+            name,
+            skip,
+            scope,
+            export_metadata,
+            optional_as_unit,
+        })
     }
 }
 
@@ -236,6 +263,23 @@ impl Module {
         self.params.skip
     }
 
+    #[allow(dead_code)]
+    pub fn scope(&self) -> &ExportScope {
+        &self.params.scope
+    }
+
+    /// Write metadata JSON files for this module and all sub-modules with the `export_metadata`
+    /// attribute.
+    #[cfg(feature = "metadata")]
+    pub fn export_metadata(&mut self) -> syn::Result<()> {
+        if let Some(path) = self.params.export_metadata.take() {
+            crate::metadata::write_json(self, &path)?;
+        }
+        self.sub_modules
+            .iter_mut()
+            .try_for_each(Module::export_metadata)
+    }
+
     pub fn generate(self) -> proc_macro2::TokenStream {
         match self.generate_inner() {
             Ok(tokens) => tokens,
@@ -267,6 +311,14 @@ impl Module {
         #[cfg(not(feature = "metadata"))]
         let mod_doc = String::new();
 
+        // `optional_as_unit` applies to all functions in this module and its sub-modules.
+        if params.optional_as_unit {
+            fns.iter_mut().for_each(|f| f.set_optional_as_unit(true));
+            sub_modules
+                .iter_mut()
+                .for_each(|m| m.params.optional_as_unit = true);
+        }
+
         if !params.skip {
             // Generate new module items.
             //
@@ -330,11 +382,21 @@ impl Module {
         &self.fns
     }
 
+    #[allow(dead_code)]
+    pub fn fns_mut(&mut self) -> &mut [ExportedFn] {
+        &mut self.fns
+    }
+
     #[allow(dead_code)]
     pub fn sub_modules(&self) -> &[Module] {
         &self.sub_modules
     }
 
+    #[allow(dead_code)]
+    pub fn sub_modules_mut(&mut self) -> &mut [Module] {
+        &mut self.sub_modules
+    }
+
     #[allow(dead_code)]
     pub fn content(&self) -> Option<&[syn::Item]> {
         match self.mod_all {
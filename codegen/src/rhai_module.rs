@@ -133,27 +133,21 @@ pub fn generate_body(
             .map(syn::Attribute::to_token_stream)
             .collect();
 
+        // Names in the same namespace are registered together as aliases of one function
+        let mut reg_groups: Vec<(FnNamespaceAccess, Vec<syn::LitStr>)> = Vec::new();
+
         for fn_literal in reg_names {
-            let mut namespace = FnNamespaceAccess::Internal;
-
-            match function.params().special {
-                FnSpecialAccess::None => (),
-                FnSpecialAccess::Index(..) | FnSpecialAccess::Property(..) => {
-                    let reg_name = fn_literal.value();
-                    if reg_name.starts_with(FN_GET)
-                        || reg_name.starts_with(FN_SET)
-                        || reg_name == FN_IDX_GET
-                        || reg_name == FN_IDX_SET
-                    {
-                        namespace = FnNamespaceAccess::Global;
-                    }
-                }
-            }
+            let namespace = fn_namespace(function, &fn_literal.value());
 
-            match function.params().namespace {
-                FnNamespaceAccess::Unset => (),
-                ns => namespace = ns,
+            match reg_groups.iter_mut().find(|(ns, ..)| *ns == namespace) {
+                Some((.., names)) => names.push(fn_literal),
+                None => reg_groups.push((namespace, vec![fn_literal])),
             }
+        }
+
+        for (namespace, names) in reg_groups {
+            let fn_literal = &names[0];
+            let aliases = &names[1..];
 
             let mut tokens = quote! {
                 #(#cfg_attrs)*
@@ -168,6 +162,10 @@ pub fn generate_body(
                 FnNamespaceAccess::Internal => (),
             }
 
+            if !aliases.is_empty() {
+                tokens.extend(quote! { .with_aliases([#(#aliases),*]) });
+            }
+
             #[cfg(feature = "metadata")]
             {
                 tokens.extend(quote! {
@@ -194,6 +192,19 @@ pub fn generate_body(
             set_fn_statements.push(syn::parse2::<syn::Stmt>(tokens).unwrap());
         }
 
+        for test in &function.params().tests {
+            let fn_literal = syn::LitStr::new(&function.exported_name(), Span::call_site());
+            let test_literal = syn::LitStr::new(test, Span::call_site());
+
+            set_fn_statements.push(
+                syn::parse2::<syn::Stmt>(quote! {
+                    #(#cfg_attrs)*
+                    _m.add_test(#fn_literal, #test_literal);
+                })
+                .unwrap(),
+            );
+        }
+
         gen_fn_tokens.push(quote! {
             #(#cfg_attrs)*
             #[allow(non_camel_case_types)]
@@ -255,6 +266,31 @@ pub fn generate_body(
     }
 }
 
+/// Determine the namespace of a function registered under a particular name.
+pub fn fn_namespace(function: &ExportedFn, reg_name: &str) -> FnNamespaceAccess {
+    let mut namespace = FnNamespaceAccess::Internal;
+
+    match function.params().special {
+        FnSpecialAccess::None => (),
+        FnSpecialAccess::Index(..) | FnSpecialAccess::Property(..) => {
+            if reg_name.starts_with(FN_GET)
+                || reg_name.starts_with(FN_SET)
+                || reg_name == FN_IDX_GET
+                || reg_name == FN_IDX_SET
+            {
+                namespace = FnNamespaceAccess::Global;
+            }
+        }
+    }
+
+    match function.params().namespace {
+        FnNamespaceAccess::Unset => (),
+        ns => namespace = ns,
+    }
+
+    namespace
+}
+
 pub fn check_rename_collisions(fns: &[ExportedFn]) -> Result<(), syn::Error> {
     fn make_key(name: impl ToString, item_fn: &ExportedFn) -> String {
         item_fn
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod func_args_tests {
+    use crate::test::assert_streams_eq;
+    use quote::quote;
+
+    #[test]
+    fn test_func_args_struct() {
+        let input = quote! {
+            #[derive(FuncArgs)]
+            pub struct Args {
+                pub x: INT,
+                y: String,
+                z: Option<bool>,
+            }
+        };
+
+        let result = crate::func_args::derive_func_args_impl(
+            syn::parse2::<syn::DeriveInput>(input).unwrap(),
+        );
+
+        let expected = quote! {
+            impl rhai::FuncArgs for Args {
+                #[inline]
+                #[allow(unused_variables)]
+                fn parse<ARGS: Extend<rhai::Dynamic>>(self, args: &mut ARGS) {
+                    args.extend(Some(rhai::Dynamic::from(self.x)));
+                    args.extend(Some(rhai::Dynamic::from(self.y)));
+                    match self.z {
+                        Some(value) => args.extend(Some(rhai::Dynamic::from(value))),
+                        None => return,
+                    }
+                }
+            }
+        };
+
+        assert_streams_eq(result, expected);
+    }
+
+    #[test]
+    fn test_func_args_tuple_struct() {
+        let input = quote! {
+            #[derive(FuncArgs)]
+            pub struct Args<T: Clone>(T, Option<INT>);
+        };
+
+        let result = crate::func_args::derive_func_args_impl(
+            syn::parse2::<syn::DeriveInput>(input).unwrap(),
+        );
+
+        let expected = quote! {
+            impl<T: Clone> rhai::FuncArgs for Args<T> {
+                #[inline]
+                #[allow(unused_variables)]
+                fn parse<ARGS: Extend<rhai::Dynamic>>(self, args: &mut ARGS) {
+                    args.extend(Some(rhai::Dynamic::from(self.0)));
+                    match self.1 {
+                        Some(value) => args.extend(Some(rhai::Dynamic::from(value))),
+                        None => return,
+                    }
+                }
+            }
+        };
+
+        assert_streams_eq(result, expected);
+    }
+
+    #[test]
+    fn test_func_args_optional_not_trailing() {
+        let input = quote! {
+            #[derive(FuncArgs)]
+            pub struct Args {
+                x: Option<INT>,
+                y: INT,
+            }
+        };
+
+        let result = crate::func_args::derive_func_args_impl(
+            syn::parse2::<syn::DeriveInput>(input).unwrap(),
+        );
+
+        assert!(result.to_string().contains("compile_error"));
+    }
+}
@@ -1,6 +1,7 @@
 use proc_macro2::TokenStream;
 
 mod custom_type;
+mod func_args;
 mod function;
 mod module;
 
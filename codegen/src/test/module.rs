@@ -271,6 +271,46 @@ mod module_tests {
         assert!(item_mod.fns().is_empty());
         assert!(item_mod.consts().is_empty());
     }
+
+    #[test]
+    #[cfg(feature = "metadata")]
+    fn export_metadata_module() {
+        let input_tokens: TokenStream = quote! {
+            /// Math functions.
+            pub mod math {
+                pub const PI: FLOAT = 3.14;
+
+                /// Add "one".
+                pub fn add_one(x: INT) -> INT {
+                    x + 1
+                }
+                #[rhai_fn(get = "len", pure)]
+                pub fn length(v: &mut Vector) -> INT {
+                    v.len()
+                }
+                fn private_fn() {}
+
+                pub mod inner {
+                    pub fn hello() {}
+                }
+            }
+        };
+
+        let mut item_mod = syn::parse2::<Module>(input_tokens).unwrap();
+        let json = crate::metadata::generate_json(&mut item_mod).unwrap();
+
+        assert!(json.starts_with(
+            "{\n  \"name\": \"math\",\n  \"docComments\": [\n    \"/// Math functions.\"\n  ],"
+        ));
+        assert!(json.contains("\"constants\": [\n    \"PI\"\n  ]"));
+        assert!(json.contains("\"name\": \"add_one\""));
+        assert!(json.contains("\"/// Add \\\"one\\\".\""));
+        assert!(json.contains("\"name\": \"get$len\",\n      \"rustName\": \"length\",\n      \"namespace\": \"global\",\n      \"pure\": true,"));
+        assert!(json.contains("\"name\": \"v\",\n          \"type\": \"&mut Vector\""));
+        assert!(json.contains("\"name\": \"hello\",\n          \"rustName\": \"hello\""));
+        assert!(json.contains("\"returnType\": \"()\""));
+        assert!(!json.contains("private_fn"));
+    }
 }
 
 #[cfg(test)]
@@ -778,11 +818,7 @@ mod generate_tests {
                 #[doc(hidden)]
                 #[inline(always)]
                 pub fn rhai_generate_into_module(_m: &mut Module, _flatten: bool) {
-                    FuncRegistration::new("add").with_params_info(add_together_token::PARAM_NAMES)
-                        .set_into_module_raw(_m, &add_together_token::param_types(), add_together_token().into());
-                    FuncRegistration::new("+").with_params_info(add_together_token::PARAM_NAMES)
-                        .set_into_module_raw(_m, &add_together_token::param_types(), add_together_token().into());
-                    FuncRegistration::new("add_together").with_params_info(add_together_token::PARAM_NAMES)
+                    FuncRegistration::new("add").with_aliases(["+", "add_together"]).with_params_info(add_together_token::PARAM_NAMES)
                         .set_into_module_raw(_m, &add_together_token::param_types(), add_together_token().into());
                 }
                 #[allow(non_camel_case_types)]
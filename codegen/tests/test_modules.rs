@@ -418,3 +418,120 @@ fn export_all_test() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+pub mod optional_as_unit {
+    use rhai::plugin::*;
+    use rhai::INT;
+
+    #[derive(Clone)]
+    pub struct Process {
+        pub exit_code: Option<INT>,
+    }
+
+    #[export_module(optional_as_unit)]
+    pub mod process {
+        use super::{Process, INT};
+
+        pub fn new_process() -> Process {
+            Process { exit_code: None }
+        }
+
+        #[rhai_fn(get = "exit_code", pure)]
+        pub fn get_exit_code(process: &mut Process) -> Option<INT> {
+            process.exit_code
+        }
+
+        #[rhai_fn(set = "exit_code")]
+        pub fn set_exit_code(process: &mut Process, exit_code: Option<INT>) {
+            process.exit_code = exit_code;
+        }
+
+        pub mod inner {
+            use super::INT;
+
+            pub fn halve(x: INT) -> Option<INT> {
+                if x % 2 == 0 {
+                    Some(x / 2)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn optional_as_unit_test() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    let m = rhai::exported_module!(crate::optional_as_unit::process);
+    engine.register_global_module(m.into());
+
+    assert!(engine.eval::<bool>("let p = new_process(); p.exit_code == ()")?);
+    assert_eq!(
+        engine.eval::<INT>("let p = new_process(); p.exit_code = 42; p.exit_code")?,
+        42
+    );
+    assert!(engine.eval::<bool>(
+        "let p = new_process(); p.exit_code = 42; p.exit_code = (); p.exit_code == ()"
+    )?);
+    assert!(matches!(
+        *engine
+            .run(r#"let p = new_process(); p.exit_code = "hello";"#)
+            .unwrap_err(),
+        EvalAltResult::ErrorMismatchDataType(..)
+    ));
+
+    let m = rhai::exported_module!(crate::optional_as_unit::process::inner);
+    engine.register_static_module("inner", m.into());
+
+    assert_eq!(engine.eval::<INT>("inner::halve(42)")?, 21);
+    assert!(engine.eval::<bool>("inner::halve(41) == ()")?);
+
+    Ok(())
+}
+
+pub mod embedded_tests {
+    use rhai::plugin::*;
+
+    #[export_module]
+    pub mod math {
+        use rhai::INT;
+
+        #[rhai_fn(test = "assert(double(2) == 4);", test = "assert(double(-1) == -2);")]
+        pub fn double(x: INT) -> INT {
+            x * 2
+        }
+
+        #[rhai_fn(name = "square", test = r#"assert(square(3) == 10, "bad square");"#)]
+        pub fn sqr(x: INT) -> INT {
+            x * x
+        }
+
+        pub mod inner {
+            use rhai::INT;
+
+            #[rhai_fn(test = "assert(triple(2) == 6);")]
+            pub fn triple(x: INT) -> INT {
+                x * 3
+            }
+        }
+    }
+}
+
+#[test]
+fn embedded_tests_test() {
+    let engine = Engine::new();
+    let m = rhai::exported_module!(crate::embedded_tests::math);
+
+    assert_eq!(m.iter_tests().count(), 3);
+
+    let failures = engine.run_module_tests(&m).unwrap_err();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].fn_name, "square");
+    assert_eq!(failures[0].path, "");
+    assert_eq!(failures[0].position().line(), Some(1));
+    assert!(failures[0].to_string().contains("bad square"));
+
+    let m = rhai::exported_module!(crate::embedded_tests::math::inner);
+    assert_eq!(engine.run_module_tests(&m).unwrap(), 1);
+}
@@ -5,7 +5,7 @@ fn main() {
     panic!("This example does not run under 'unchecked'.");
 }
 
-use rhai::{Dynamic, Engine};
+use rhai::{Dynamic, Engine, ProgressControl};
 
 #[cfg(feature = "sync")]
 use std::sync::Mutex;
@@ -22,10 +22,10 @@ fn main() {
         // Create Engine
         let mut engine = Engine::new();
 
-        engine.on_progress(move |_ops| {
+        engine.on_progress(move |_info| {
             #[cfg(feature = "sync")]
-            if _ops % 5 != 0 {
-                return None;
+            if _info.operations % 5 != 0 {
+                return ProgressControl::Continue;
             }
 
             #[cfg(feature = "sync")]
@@ -42,11 +42,11 @@ fn main() {
                         }
                         "resume" => {
                             println!("[Thread] Resuming script...");
-                            return None;
+                            return ProgressControl::Continue;
                         }
                         "stop" => {
                             println!("[Thread] Stopping script...");
-                            return Some(Dynamic::UNIT);
+                            return ProgressControl::Abort(Dynamic::UNIT);
                         }
                         cmd if paused => {
                             println!("[Thread] I don't understand '{cmd}'!");
@@ -54,11 +54,11 @@ fn main() {
                         }
                         _ => {
                             println!("[Thread] I don't understand '{cmd}'!");
-                            return None;
+                            return ProgressControl::Continue;
                         }
                     },
                     Err(_) if paused => (),
-                    Err(_) => return None,
+                    Err(_) => return ProgressControl::Continue,
                 }
 
                 std::thread::sleep(std::time::Duration::from_millis(100));
@@ -0,0 +1,215 @@
+//! An embeddable REPL with line editing, completion, multi-line input, a step debugger and
+//! timing/operations reporting, built entirely on public library APIs.
+
+use rhai::debugger::{DebuggerCommand, DebuggerEvent};
+use rhai::{Dynamic, Engine, EvalAltResult, Scope};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Context, Editor, Helper};
+use std::io::{stdin, stdout, Write};
+use std::time::Instant;
+
+/// Line editor helper providing completion and multi-line input.
+struct ReplHelper {
+    /// Engine used for completion and detection of incomplete input.
+    engine: Engine,
+    /// Copy of the REPL scope, for completing variable names.
+    scope: Scope<'static>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let completions = self.engine.completions(line, pos, Some(&self.scope));
+        Ok((completions.start, completions.candidates))
+    }
+}
+
+impl Validator for ReplHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        let (.., script) = split_command(ctx.input());
+
+        if self.engine.is_script_incomplete(script) {
+            Ok(ValidationResult::Incomplete)
+        } else {
+            Ok(ValidationResult::Valid(None))
+        }
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// Split the input into a REPL command (if any) and the script.
+fn split_command(input: &str) -> (Option<&str>, &str) {
+    match input.trim_start().strip_prefix(':') {
+        Some(cmd) => {
+            let (cmd, script) = cmd.split_once(char::is_whitespace).unwrap_or((cmd, ""));
+            (Some(cmd), script)
+        }
+        None => (None, input),
+    }
+}
+
+/// Print help text.
+fn print_help() {
+    println!(":help           => print this help");
+    println!(":quit           => quit");
+    println!(":scope          => print all variables in the scope");
+    println!(":time <script>  => evaluate a script and print the time taken");
+    println!(":ops <script>   => evaluate a script and print the number of operations performed");
+    println!(":debug <script> => step through a script in the debugger");
+    println!();
+    println!("Press Tab to complete, and Enter on an incomplete script to continue on a new line.");
+    println!();
+}
+
+/// Print an error together with the line of the script it occurs in.
+fn print_error(script: &str, err: &EvalAltResult) {
+    let pos = err.position();
+
+    if let Some(line) = pos.line().and_then(|n| script.lines().nth(n - 1)) {
+        println!("{line}");
+        println!("{0:>1$} {err}", "^", pos.position().unwrap_or(1));
+    } else {
+        println!("{err}");
+    }
+}
+
+/// Create an [`Engine`] that steps through a script in the debugger.
+fn debugging_engine(engine: &Engine, script: &str) -> Engine {
+    let lines: Vec<String> = script.lines().map(String::from).collect();
+    let mut engine = engine.fork();
+
+    engine.register_debugger(
+        |_, debugger| debugger,
+        move |_, event, _, _, pos| {
+            match event {
+                DebuggerEvent::Start => return Ok(DebuggerCommand::StepInto),
+                DebuggerEvent::End => return Ok(DebuggerCommand::Continue),
+                DebuggerEvent::FunctionExitWithValue(value) => {
+                    println!("<= {value:?}");
+                }
+                _ => (),
+            }
+
+            if let Some(line) = pos.line().and_then(|n| lines.get(n - 1)) {
+                println!("{pos}: {line}");
+            }
+
+            loop {
+                print!("debug [Enter=step, n=next, o=over, c=continue, q=quit]> ");
+                stdout().flush().unwrap();
+
+                let mut command = String::new();
+                stdin().read_line(&mut command).unwrap();
+
+                match command.trim() {
+                    "" | "s" => break Ok(DebuggerCommand::StepInto),
+                    "n" => break Ok(DebuggerCommand::Next),
+                    "o" => break Ok(DebuggerCommand::StepOver),
+                    "c" => break Ok(DebuggerCommand::Continue),
+                    "q" => break Err(EvalAltResult::ErrorTerminated(Dynamic::UNIT, pos).into()),
+                    _ => println!("Unknown command"),
+                }
+            }
+        },
+    );
+
+    engine
+}
+
+fn main() {
+    println!("Rhai REPL example");
+    println!("=================");
+    print_help();
+
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    let mut rl = Editor::<ReplHelper, DefaultHistory>::new().unwrap();
+    rl.set_helper(Some(ReplHelper {
+        engine: engine.fork(),
+        scope: Scope::new(),
+    }));
+
+    loop {
+        let input = match rl.readline("rhai> ") {
+            Ok(input) => input,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+            Err(err) => {
+                eprintln!("Error: {err:?}");
+                break;
+            }
+        };
+
+        if input.trim().is_empty() {
+            continue;
+        }
+
+        rl.add_history_entry(input.as_str()).unwrap();
+
+        let (command, script) = split_command(&input);
+
+        let result = match command {
+            None => engine.eval_with_scope::<Dynamic>(&mut scope, script),
+            Some("help") => {
+                print_help();
+                continue;
+            }
+            Some("quit") => break,
+            Some("scope") => {
+                println!("{scope}");
+                continue;
+            }
+            Some("time") => {
+                let start = Instant::now();
+                let result = engine.eval_with_scope::<Dynamic>(&mut scope, script);
+                println!("Time taken: {:?}", start.elapsed());
+                result
+            }
+            Some("ops") => engine
+                .eval_full_with_scope(&mut scope, script)
+                .map(|output| {
+                    println!("Operations performed: {}", output.num_operations);
+                    output.value
+                }),
+            Some("debug") => {
+                debugging_engine(&engine, script).eval_with_scope::<Dynamic>(&mut scope, script)
+            }
+            Some(cmd) => {
+                println!("Unknown command: ':{cmd}'");
+                continue;
+            }
+        };
+
+        match result {
+            Ok(value) if value.is_unit() => (),
+            Ok(value) => println!("=> {value:?}"),
+            Err(err) if matches!(*err, EvalAltResult::ErrorTerminated(..)) => {
+                println!("Terminated.")
+            }
+            Err(err) => print_error(script, &err),
+        }
+
+        // Keep completion of variable names up to date
+        rl.helper_mut().unwrap().scope = scope.clone();
+
+        println!();
+    }
+}
@@ -92,7 +92,11 @@ fuzz_target!(|ctx: Ctx| {
 
     // We need fuzzing to be fast, so we'll stop executing after 1s.
     let start = Instant::now();
-    engine.on_progress(move |_| (start.elapsed().as_millis() > 1000).then_some(Dynamic::UNIT));
+    engine.on_progress(move |_| {
+        (start.elapsed().as_millis() > 1000)
+            .then_some(Dynamic::UNIT)
+            .into()
+    });
 
     let engine = engine;
 
@@ -0,0 +1,343 @@
+//! Module that defines the native function allow-list API of [`Engine`].
+
+use crate::ast::{ASTNode, Expr, FnCallExpr, Stmt};
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+use crate::engine::{FN_IDX_GET, FN_IDX_SET};
+use crate::engine::{
+    KEYWORD_DEBUG, KEYWORD_EVAL, KEYWORD_FN_PTR, KEYWORD_FN_PTR_CALL, KEYWORD_FN_PTR_CURRY,
+    KEYWORD_IS_DEF_VAR, KEYWORD_PRINT, KEYWORD_TYPE_OF,
+};
+use crate::func::builtin::{get_builtin_binary_op_fn, get_builtin_op_assignment_fn};
+use crate::func::{FnCallArgs, RhaiFunc};
+use crate::packages::string_basic::{FUNC_TO_DEBUG, FUNC_TO_STRING};
+use crate::tokenizer::Token;
+use crate::{Engine, Identifier, Module, Position, AST};
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Functions handled directly by the [`Engine`] without calling any native Rust function.
+const INTRINSIC_FUNCTIONS: &[&str] = &[
+    KEYWORD_TYPE_OF,
+    KEYWORD_FN_PTR,
+    KEYWORD_FN_PTR_CURRY,
+    KEYWORD_IS_DEF_VAR,
+    "is_def_fn",
+    "is_shared",
+];
+
+/// List of native Rust functions that an [`AST`] can possibly call, as built by
+/// [`Engine::build_fn_allow_list`].
+///
+/// Calls are found via static analysis, including implicit calls such as property getters and
+/// setters, indexers, op-assignments and string interpolation.
+///
+/// Call sites whose target cannot be determined statically (e.g. `FnPtr::call`, `eval`, custom
+/// syntax, or calls to functions in imported modules) are recorded as _dynamic_ calls.
+///
+/// Set via [`Engine::set_fn_allow_list`] to lock evaluation to this set of functions.
+#[derive(Debug, Clone, Default)]
+pub struct FnAllowList {
+    /// Names and numbers of parameters of native functions that can be called.
+    calls: BTreeSet<(Identifier, usize)>,
+    /// Native functions that can be called, keyed by their identifiers.
+    ///
+    /// The functions are kept so that their identifiers are never reused.
+    functions: BTreeMap<usize, RhaiFunc>,
+    /// Call sites with targets that cannot be determined statically.
+    dynamic_calls: Vec<(Identifier, Position)>,
+}
+
+impl FnAllowList {
+    /// Create a new empty [`FnAllowList`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            calls: BTreeSet::new(),
+            functions: BTreeMap::new(),
+            dynamic_calls: Vec::new(),
+        }
+    }
+    /// Is the list empty?
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+    /// Number of native function names (with numbers of parameters) in the list.
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+    /// Iterate the names and numbers of parameters of native functions in the list.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.calls.iter().map(|(name, n)| (name.as_str(), *n))
+    }
+    /// Does the list contain a native function with a particular name and number of parameters?
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, name: &str, num_params: usize) -> bool {
+        self.calls.contains(&(name.into(), num_params))
+    }
+    /// Iterate the call sites whose targets cannot be determined statically, with the names of
+    /// the functions called.
+    #[inline]
+    pub fn iter_dynamic_calls(&self) -> impl Iterator<Item = (&str, Position)> {
+        self.dynamic_calls
+            .iter()
+            .map(|(name, pos)| (name.as_str(), *pos))
+    }
+    /// Are there call sites whose targets cannot be determined statically?
+    #[inline(always)]
+    #[must_use]
+    pub fn has_dynamic_calls(&self) -> bool {
+        !self.dynamic_calls.is_empty()
+    }
+    /// Add all native functions with a particular name and number of parameters currently
+    /// registered with an [`Engine`] to the list.
+    ///
+    /// This is useful for allowing functions that are only called indirectly by other native
+    /// functions (e.g. `to_string` on the elements of an array).
+    ///
+    /// Returns `true` if any such function is found.
+    pub fn allow(&mut self, engine: &Engine, name: impl AsRef<str>, num_params: usize) -> bool {
+        let name = name.as_ref();
+        let mut found = false;
+
+        let mut add_from = |module: &Module| {
+            module
+                .iter_fn()
+                .filter(|(f, m)| f.is_native() && m.num_params == num_params && m.name == name)
+                .for_each(|(f, ..)| {
+                    self.functions.insert(f.native_fn_id().unwrap(), f.clone());
+                    found = true;
+                });
+        };
+
+        add_from(&engine.type_operators);
+        engine.global_modules.iter().for_each(|m| add_from(m));
+
+        #[cfg(not(feature = "no_module"))]
+        {
+            fn add_sub_modules(module: &Module, add_from: &mut impl FnMut(&Module)) {
+                for (.., m) in module.iter_sub_modules_raw() {
+                    add_from(m);
+                    add_sub_modules(m, add_from);
+                }
+            }
+
+            for m in engine.global_sub_modules.values() {
+                add_from(m);
+                add_sub_modules(m, &mut add_from);
+            }
+        }
+
+        if found {
+            self.calls.insert((name.into(), num_params));
+        }
+
+        found
+    }
+    /// Add an operator to the list, even if it is only implemented by built-in functions.
+    fn allow_operator(&mut self, engine: &Engine, op: &str, num_params: usize) {
+        self.allow(engine, op, num_params);
+        self.calls.insert((op.into(), num_params));
+    }
+    /// Is a call to a resolved native function allowed?
+    ///
+    /// Built-in operators are always allowed if the operator itself is in the list.
+    #[must_use]
+    pub(crate) fn is_allowed(
+        &self,
+        name: &str,
+        op_token: Option<&Token>,
+        func: &RhaiFunc,
+        args: &FnCallArgs,
+    ) -> bool {
+        if !self.contains(name, args.len()) {
+            return false;
+        }
+        if func
+            .native_fn_id()
+            .map_or(true, |id| self.functions.contains_key(&id))
+        {
+            return true;
+        }
+
+        match op_token {
+            Some(token) if args.len() == 2 && token.is_op_assignment() => {
+                get_builtin_op_assignment_fn(token, args[0], args[1]).is_some()
+            }
+            Some(token) if args.len() == 2 => {
+                get_builtin_binary_op_fn(token, args[0], args[1]).is_some()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Engine {
+    /// Build a list of all native Rust functions that an [`AST`] can possibly call, via static
+    /// analysis of the [`AST`] (including all functions defined within it).
+    ///
+    /// Only functions currently registered with the [`Engine`] (including packages and static
+    /// modules) are considered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let ast = engine.compile("let x = [1, 2, 3]; x.len() + abs(-42)")?;
+    ///
+    /// let list = engine.build_fn_allow_list(&ast);
+    ///
+    /// assert!(list.contains("len", 1));
+    /// assert!(list.contains("abs", 1));
+    /// assert!(!list.contains("print", 1));
+    /// assert!(!list.has_dynamic_calls());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn build_fn_allow_list(&self, ast: &AST) -> FnAllowList {
+        let mut list = FnAllowList::new();
+
+        #[cfg(not(feature = "no_function"))]
+        let is_script_fn = |name: &str| ast.iter_fn_def().any(|f| f.name == name);
+        #[cfg(feature = "no_function")]
+        let is_script_fn = |_: &str| false;
+
+        let add_call = |list: &mut FnAllowList, x: &FnCallExpr, num_params: usize, pos| {
+            let is_dynamic = match x.name.as_str() {
+                KEYWORD_FN_PTR_CALL | KEYWORD_EVAL => true,
+                #[cfg(not(feature = "no_module"))]
+                _ if x.is_qualified() => !self.global_sub_modules.contains_key(x.namespace.root()),
+                _ => false,
+            };
+
+            let found = if x.is_operator_call() {
+                list.allow_operator(self, &x.name, num_params);
+                true
+            } else {
+                list.allow(self, &x.name, num_params)
+            };
+
+            match x.name.as_str() {
+                KEYWORD_PRINT => {
+                    list.allow(self, FUNC_TO_STRING, 1);
+                }
+                KEYWORD_DEBUG => {
+                    list.allow(self, FUNC_TO_DEBUG, 1);
+                }
+                _ => (),
+            }
+
+            #[cfg(not(feature = "no_module"))]
+            let is_qualified = x.is_qualified();
+            #[cfg(feature = "no_module")]
+            let is_qualified = false;
+
+            if is_dynamic
+                || (!found
+                    && !is_qualified
+                    && !is_script_fn(&x.name)
+                    && !INTRINSIC_FUNCTIONS.contains(&x.name.as_str()))
+            {
+                list.dynamic_calls.push((x.name.as_str().into(), pos));
+            }
+        };
+
+        ast._walk(&mut |path: &[ASTNode]| {
+            match path.last().unwrap() {
+                ASTNode::Stmt(Stmt::FnCall(x, pos)) | ASTNode::Expr(Expr::FnCall(x, pos)) => {
+                    add_call(&mut list, x, x.args.len(), *pos);
+                }
+                ASTNode::Expr(Expr::MethodCall(x, pos)) => {
+                    add_call(&mut list, x, x.args.len() + 1, *pos);
+                }
+                ASTNode::Stmt(Stmt::Assignment(x)) => {
+                    if let Some((.., op_assign, _, op, _)) = x.0.get_op_assignment_info() {
+                        list.allow_operator(self, op_assign.literal_syntax(), 2);
+                        list.allow_operator(self, op.literal_syntax(), 2);
+                    }
+                }
+                ASTNode::Expr(Expr::Property(x, ..)) => {
+                    list.allow(self, &x.0 .0, 1);
+                    list.allow(self, &x.1 .0, 2);
+                    #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+                    list.allow(self, FN_IDX_GET, 2);
+                    #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+                    list.allow(self, FN_IDX_SET, 3);
+                }
+                #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+                ASTNode::Expr(Expr::Index(..)) => {
+                    list.allow(self, FN_IDX_GET, 2);
+                    list.allow(self, FN_IDX_SET, 3);
+                }
+                ASTNode::Expr(Expr::InterpolatedString(..)) => {
+                    list.allow(self, FUNC_TO_STRING, 1);
+                }
+                #[cfg(not(feature = "no_custom_syntax"))]
+                ASTNode::Expr(Expr::Custom(x, pos)) => {
+                    let name = x.tokens.first().map_or("", |s| s.as_str());
+                    list.dynamic_calls.push((name.into(), *pos));
+                }
+                _ => (),
+            }
+            true
+        });
+
+        list
+    }
+    /// Lock evaluation to a list of native Rust functions, typically built via
+    /// [`build_fn_allow_list`][Engine::build_fn_allow_list].
+    ///
+    /// Calls to native functions (including those made indirectly via function pointers) that
+    /// are not in the list raise [`ErrorFunctionNotFound`][crate::EvalAltResult::ErrorFunctionNotFound],
+    /// even if a function with a matching name is registered with the [`Engine`] afterwards or
+    /// provided by an imported module.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// let ast = engine.compile("abs(-42)")?;
+    ///
+    /// engine.set_fn_allow_list(engine.build_fn_allow_list(&ast));
+    ///
+    /// assert_eq!(engine.eval_ast::<i64>(&ast)?, 42);
+    ///
+    /// // 'sign' is not in the list
+    /// assert!(engine.eval::<i64>("sign(-42)").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn set_fn_allow_list(&mut self, list: FnAllowList) -> &mut Self {
+        self.fn_allow_list = Some(list.into());
+        self
+    }
+    /// Remove the list of native Rust functions that evaluation is locked to, if any.
+    #[inline(always)]
+    pub fn clear_fn_allow_list(&mut self) -> &mut Self {
+        self.fn_allow_list = None;
+        self
+    }
+    /// The list of native Rust functions that evaluation is locked to, if any.
+    #[inline(always)]
+    #[must_use]
+    pub fn fn_allow_list(&self) -> Option<&FnAllowList> {
+        self.fn_allow_list.as_deref()
+    }
+}
@@ -0,0 +1,68 @@
+//! Module that defines the integer arithmetic mode API of [`Engine`].
+#![cfg(not(feature = "unchecked"))]
+
+use crate::Engine;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Behavior of integer arithmetic on overflow.
+///
+/// Not available under `unchecked`.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Hash)]
+#[non_exhaustive]
+pub enum ArithmeticMode {
+    /// Overflow raises an arithmetic error.
+    #[default]
+    Checked,
+    /// Overflow wraps around at the boundary of the integer type (e.g. `i64::MAX + 1` is
+    /// `i64::MIN`).
+    Wrapping,
+    /// Overflow saturates at the numeric bounds of the integer type (e.g. `i64::MAX + 1` is
+    /// `i64::MAX`).
+    Saturating,
+}
+
+impl Engine {
+    /// Set how integer arithmetic behaves on overflow.
+    ///
+    /// This affects the built-in operators as well as the standard arithmetic functions
+    /// (`+`, `-`, `*`, `/`, `%`, `**`, negation and `abs`) for all integer types.
+    ///
+    /// Division by zero and raising to a negative power are always errors.
+    ///
+    /// Not available under `unchecked`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{ArithmeticMode, Engine, Scope, INT};
+    ///
+    /// let mut engine = Engine::new();
+    /// let mut scope = Scope::new();
+    /// scope.push("x", INT::MAX);
+    ///
+    /// assert!(engine.eval_with_scope::<INT>(&mut scope, "x + 1").is_err());
+    ///
+    /// engine.set_arithmetic_mode(ArithmeticMode::Wrapping);
+    /// assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x + 1")?, INT::MIN);
+    ///
+    /// engine.set_arithmetic_mode(ArithmeticMode::Saturating);
+    /// assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x + 1")?, INT::MAX);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn set_arithmetic_mode(&mut self, mode: ArithmeticMode) -> &mut Self {
+        self.arithmetic_mode = mode;
+        self
+    }
+    /// How integer arithmetic behaves on overflow.
+    ///
+    /// Not available under `unchecked`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn arithmetic_mode(&self) -> ArithmeticMode {
+        self.arithmetic_mode
+    }
+}
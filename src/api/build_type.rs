@@ -1,10 +1,14 @@
 //! Trait to build a custom type for use with [`Engine`].
-use crate::func::SendSync;
+use crate::func::call::BuilderUpdated;
+use crate::func::{FnCallArgs, RhaiFunc, SendSync};
 use crate::module::FuncMetadata;
 use crate::packages::string_basic::{FUNC_TO_DEBUG, FUNC_TO_STRING};
+use crate::tokenizer::is_valid_identifier;
 use crate::types::dynamic::Variant;
-use crate::{Engine, FuncRegistration, Identifier, RhaiNativeFunc, StaticVec};
-use std::marker::PhantomData;
+use crate::{
+    Dynamic, Engine, FnArgsVec, FuncRegistration, Identifier, RhaiNativeFunc, Shared, StaticVec,
+};
+use std::{any::TypeId, marker::PhantomData};
 
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
@@ -105,6 +109,9 @@ pub struct TypeBuilder<'a, T: Variant + Clone> {
     engine: &'a mut Engine,
     /// Keep the latest registered function(s) in cache to add additional metadata.
     hashes: StaticVec<u64>,
+    /// Constants and static functions grouped under the type.
+    #[cfg(not(feature = "no_module"))]
+    statics: crate::Module,
     _marker: PhantomData<T>,
 }
 
@@ -115,6 +122,8 @@ impl<'a, T: Variant + Clone> TypeBuilder<'a, T> {
         Self {
             engine,
             hashes: StaticVec::new_const(),
+            #[cfg(not(feature = "no_module"))]
+            statics: crate::Module::new(),
             _marker: PhantomData,
         }
     }
@@ -186,6 +195,145 @@ impl<T: Variant + Clone> TypeBuilder<'_, T> {
         self.hashes.push(*hash);
         self
     }
+    /// Register a builder method, i.e. a method that takes the object by value and returns the
+    /// updated object (e.g. `fn with_name(self, name: &str) -> Self`).
+    ///
+    /// The method is called as an in-place update of the object, without cloning it:
+    ///
+    /// * On a variable (or property etc.), the variable is updated and the call returns `()`.
+    ///   Subsequent method calls in the same dot chain continue with the updated variable, so
+    ///   `cfg.with_name("x").with_port(80)` updates `cfg` twice.
+    ///
+    /// * On a temporary value (e.g. `new_config().with_name("x")`), the call returns the updated
+    ///   object.
+    ///
+    /// Builder methods cannot be called on constants.
+    ///
+    /// Returns an error if the name is not a valid identifier, or if the first parameter of the
+    /// method is not the object type taken by value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_object"))]
+    /// # {
+    /// use rhai::{CustomType, Engine, TypeBuilder};
+    ///
+    /// #[derive(Debug, Clone, Default)]
+    /// struct Config {
+    ///     name: String,
+    ///     port: i64,
+    /// }
+    ///
+    /// impl Config {
+    ///     fn with_name(mut self, name: &str) -> Self {
+    ///         self.name = name.into();
+    ///         self
+    ///     }
+    ///     fn with_port(mut self, port: i64) -> Self {
+    ///         self.port = port;
+    ///         self
+    ///     }
+    /// }
+    ///
+    /// impl CustomType for Config {
+    ///     fn build(mut builder: TypeBuilder<Self>) {
+    ///         builder
+    ///             .with_fn("new_config", Self::default)
+    ///             .with_fn("port", |cfg: &mut Self| cfg.port);
+    ///         builder
+    ///             .with_builder_fn("with_name", Self::with_name)
+    ///             .and_then(|b| b.with_builder_fn("with_port", Self::with_port))
+    ///             .expect("valid builder methods");
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// let mut engine = Engine::new();
+    /// engine.build_type::<Config>();
+    ///
+    /// let cfg = engine.eval::<Config>(r#"new_config().with_name("x").with_port(80)"#)?;
+    /// assert_eq!(cfg.port, 80);
+    ///
+    /// let port = engine.eval::<i64>(r#"
+    ///     let cfg = new_config();
+    ///     cfg.with_name("x").with_port(42);
+    ///     cfg.port()
+    /// "#)?;
+    /// assert_eq!(port, 42);
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub fn with_builder_fn<A: 'static, const N: usize, const X: bool, FUNC>(
+        &mut self,
+        name: impl Into<Identifier>,
+        method: FUNC,
+    ) -> Result<&mut Self, String>
+    where
+        FUNC: RhaiNativeFunc<A, N, X, T, false> + SendSync + 'static,
+    {
+        let name = name.into();
+
+        if !is_valid_identifier(&name) {
+            return Err(format!(
+                "builder method '{name}' must have a valid identifier as name"
+            ));
+        }
+        if FUNC::param_types().first() != Some(&TypeId::of::<T>()) {
+            return Err(format!(
+                "builder method '{name}' must take the object as its first parameter"
+            ));
+        }
+
+        let (inner, has_context) = match method.into_rhai_function(false, false) {
+            RhaiFunc::Pure {
+                func, has_context, ..
+            } => (func, has_context),
+            _ => {
+                return Err(format!(
+                    "builder method '{name}' must take the object by value"
+                ))
+            }
+        };
+
+        let func = RhaiFunc::Method {
+            func: Shared::new(move |ctx, args: &mut FnCallArgs| {
+                // Take the object out of the first argument and pass it by value
+                let mut obj = args[0].write_lock::<Dynamic>().unwrap().take();
+                let value = {
+                    let rest = args[1..].iter_mut().map(|a| &mut **a);
+                    let fn_args = &mut std::iter::once(&mut obj)
+                        .chain(rest)
+                        .collect::<FnArgsVec<_>>();
+                    inner(ctx, fn_args)?
+                };
+                // Put the updated object back in place
+                *args[0].write_lock::<Dynamic>().unwrap() = value;
+                Ok(Dynamic::from(BuilderUpdated))
+            }),
+            has_context,
+            is_pure: false,
+            is_volatile: false,
+        };
+
+        let reg = FuncRegistration::new(name).in_global_namespace();
+
+        #[cfg(feature = "metadata")]
+        let reg = {
+            let param_type_names = FUNC::param_names()
+                .iter()
+                .map(|ty| format!("_: {}", self.engine.format_param_type(ty)))
+                .collect::<FnArgsVec<_>>();
+            reg.with_params_info(param_type_names)
+        };
+
+        let module = self.engine.global_namespace_mut();
+        let FuncMetadata { hash, .. } = reg.set_into_module_raw(module, FUNC::param_types(), func);
+        self.hashes.clear();
+        self.hashes.push(*hash);
+        Ok(self)
+    }
 
     /// _(metadata)_ Add comments to the last registered function.
     /// Available under the `metadata` feature only.
@@ -200,10 +348,114 @@ impl<T: Variant + Clone> TypeBuilder<'_, T> {
             }
         }
 
+        #[cfg(not(feature = "no_module"))]
+        for &hash in &self.hashes {
+            if let Some(f) = self.statics.get_fn_metadata_mut(hash) {
+                f.comments = comments.iter().map(|&s| s.into()).collect();
+            }
+        }
+
         self
     }
 }
 
+#[cfg(not(feature = "no_module"))]
+impl<T: Variant + Clone> TypeBuilder<'_, T> {
+    /// Register a constant grouped under the type, accessible as `Type::NAME` in scripts.
+    ///
+    /// The type's namespace is named after its pretty-print name (see
+    /// [`with_name`][TypeBuilder::with_name]).
+    ///
+    /// Not available under `no_module`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{CustomType, Engine, TypeBuilder};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Color(i64);
+    ///
+    /// impl CustomType for Color {
+    ///     fn build(mut builder: TypeBuilder<Self>) {
+    ///         builder
+    ///             .with_name("Color")
+    ///             .with_constant("MAX", 255_i64)
+    ///             .with_static_fn("parse", |s: &str| Color(s.parse().unwrap_or(0)))
+    ///             .with_fn("value", |c: &mut Color| c.0);
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// let mut engine = Engine::new();
+    /// engine.build_type::<Color>();
+    ///
+    /// assert_eq!(engine.eval::<i64>("Color::MAX - Color::parse(\"42\").value()")?, 213);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn with_constant(
+        &mut self,
+        name: impl Into<Identifier>,
+        value: impl Variant + Clone,
+    ) -> &mut Self {
+        self.statics.set_var(name, value);
+        self
+    }
+
+    /// Register a static function grouped under the type, callable as `Type::name(...)` in
+    /// scripts, similar to an associated function in Rust.
+    ///
+    /// The type's namespace is named after its pretty-print name (see
+    /// [`with_name`][TypeBuilder::with_name]).
+    ///
+    /// Not available under `no_module`.
+    #[inline(always)]
+    pub fn with_static_fn<
+        A: 'static,
+        const N: usize,
+        const X: bool,
+        R: Variant + Clone,
+        const F: bool,
+    >(
+        &mut self,
+        name: impl AsRef<str> + Into<Identifier>,
+        func: impl RhaiNativeFunc<A, N, X, R, F> + SendSync + 'static,
+    ) -> &mut Self {
+        let FuncMetadata { hash, .. } =
+            FuncRegistration::new(name).set_into_module(&mut self.statics, func);
+        self.hashes.clear();
+        self.hashes.push(*hash);
+        self
+    }
+}
+
+#[cfg(not(feature = "no_module"))]
+impl<T: Variant + Clone> Drop for TypeBuilder<'_, T> {
+    fn drop(&mut self) {
+        if self.statics.is_empty() {
+            return;
+        }
+
+        // Name the namespace after the pretty-print name of the type,
+        // or the Rust type name without its path and generics
+        let name = self.engine.map_type_name(std::any::type_name::<T>());
+        let name = name.split('<').next().unwrap_or(name);
+        let name: Identifier = name.rsplit("::").next().unwrap_or(name).trim().into();
+
+        let mut statics = std::mem::take(&mut self.statics);
+
+        if let Some(existing) = self.engine.global_sub_modules.remove(&name) {
+            let mut module = crate::func::shared_take_or_clone(existing);
+            module.merge(&statics);
+            statics = module;
+        }
+
+        self.engine.register_static_module(name, statics.into());
+    }
+}
+
 impl<T> TypeBuilder<'_, T>
 where
     T: Variant + Clone + IntoIterator,
@@ -286,6 +538,60 @@ impl<T: Variant + Clone> TypeBuilder<'_, T> {
 
         self
     }
+
+    /// Register a callback to resolve properties of this type that are not found, i.e. with
+    /// neither a getter nor an index getter taking the property name.
+    ///
+    /// This allows a custom type to implement dynamic properties (e.g. a proxy object) without
+    /// affecting other types.
+    ///
+    /// The callback is called with the object and the property name. To indicate that the
+    /// property does not exist, return [`EvalAltResult::ErrorPropertyNotFound`][crate::EvalAltResult::ErrorPropertyNotFound].
+    ///
+    /// Only property reads are resolved; property assignments still require a setter.
+    ///
+    /// Not available under `no_object`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{CustomType, Dynamic, Engine, EvalAltResult, Position, TypeBuilder};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Env;
+    ///
+    /// impl CustomType for Env {
+    ///     fn build(mut builder: TypeBuilder<Self>) {
+    ///         builder.on_missing_property(|_, prop| match prop {
+    ///             "home" => Ok(Dynamic::from("/home/rhai")),
+    ///             _ => Err(EvalAltResult::ErrorPropertyNotFound(prop.into(), Position::NONE).into()),
+    ///         });
+    ///     }
+    /// }
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.build_type::<Env>();
+    /// engine.register_fn("env", || Env);
+    ///
+    /// assert_eq!(engine.eval::<String>("env().home")?, "/home/rhai");
+    /// assert!(engine.eval::<String>("env().user").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn on_missing_property(
+        &mut self,
+        callback: impl Fn(&mut T, &str) -> crate::RhaiResult + SendSync + 'static,
+    ) -> &mut Self {
+        self.engine.missing_property.insert(
+            std::any::TypeId::of::<T>(),
+            crate::Shared::new(move |obj, prop| {
+                callback(&mut obj.write_lock::<T>().unwrap(), prop)
+            }),
+        );
+        self
+    }
 }
 
 #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
@@ -363,3 +669,89 @@ impl<T: Variant + Clone> TypeBuilder<'_, T> {
         self
     }
 }
+
+/// Macro to register multiple properties of a custom type in bulk with a [`TypeBuilder`].
+///
+/// Each entry takes a property name, a getter function and an optional setter function.
+/// Doc-comments on each entry are recorded as metadata for the property functions under the
+/// `metadata` feature.
+///
+/// Not available under `no_object`.
+///
+/// # Example
+///
+/// ```
+/// use rhai::{with_fields, CustomType, Engine, TypeBuilder};
+///
+/// #[derive(Debug, Clone)]
+/// struct Order {
+///     id: i64,
+///     qty: i64,
+///     note: String,
+/// }
+///
+/// impl CustomType for Order {
+///     fn build(mut builder: TypeBuilder<Self>) {
+///         builder
+///             .with_name("Order")
+///             .with_fn("new_order", |id: i64| Order { id, qty: 0, note: String::new() });
+///
+///         with_fields!(builder,
+///             /// Order ID (read-only).
+///             "id" => (|o: &mut Self| o.id),
+///             /// Quantity ordered.
+///             "qty" => (|o: &mut Self| o.qty, |o: &mut Self, v: i64| o.qty = v),
+///             /// Free-form note.
+///             "note" => (|o: &mut Self| o.note.clone(), |o: &mut Self, v: String| o.note = v),
+///         );
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+/// let mut engine = Engine::new();
+/// engine.build_type::<Order>();
+///
+/// assert_eq!(engine.eval::<i64>("let o = new_order(42); o.qty = 2; o.id + o.qty")?, 44);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(not(feature = "no_object"))]
+#[macro_export]
+macro_rules! with_fields {
+    (@field $builder:ident [$($doc:literal)*] $name:expr, $get:expr) => {
+        $crate::__with_field_comments!($builder.with_get($name, $get), [$($doc)*]);
+    };
+    (@field $builder:ident [$($doc:literal)*] $name:expr, $get:expr, $set:expr) => {
+        $crate::__with_field_comments!($builder.with_get_set($name, $get, $set), [$($doc)*]);
+    };
+    ($builder:expr, $($(#[doc = $doc:literal])* $name:literal => ($get:expr $(, $set:expr)? $(,)?)),* $(,)?) => {{
+        let builder = &mut $builder;
+        $( $crate::with_fields!(@field builder [$($doc)*] $name, $get $(, $set)?); )*
+    }};
+}
+
+/// Add doc-comments to the property functions just registered by `with_fields!`.
+///
+/// This macro should not be used directly.
+#[cfg(not(feature = "no_object"))]
+#[cfg(feature = "metadata")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_field_comments {
+    ($builder:expr, [$($doc:literal)*]) => {
+        $builder.and_comments(&[$(concat!("///", $doc)),*])
+    };
+}
+
+/// Doc-comments are ignored without the `metadata` feature.
+///
+/// This macro should not be used directly.
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "metadata"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_field_comments {
+    ($builder:expr, [$($doc:literal)*]) => {
+        $builder
+    };
+}
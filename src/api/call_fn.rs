@@ -4,8 +4,8 @@
 use crate::eval::{Caches, GlobalRuntimeState};
 use crate::types::dynamic::Variant;
 use crate::{
-    Dynamic, Engine, FnArgsVec, FuncArgs, Position, RhaiResult, RhaiResultOf, Scope, StaticVec,
-    AST, ERR,
+    Dynamic, Engine, FnArgsVec, FuncArgs, Identifier, Position, RhaiResult, RhaiResultOf, Scope,
+    StaticVec, AST, ERR,
 };
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
@@ -23,6 +23,13 @@ pub struct CallFnOptions<'t> {
     pub eval_ast: bool,
     /// Rewind the [`Scope`] after the function call? Default `true`.
     pub rewind_scope: bool,
+    /// Arguments passed by name, matched against the parameter names of the script-defined
+    /// function after all positional arguments. Default empty.
+    pub named_args: StaticVec<(Identifier, Dynamic)>,
+    /// Default values for parameters not passed positionally or by name. Default empty.
+    ///
+    /// Parameters without an argument or a default value receive `()`.
+    pub default_args: StaticVec<(Identifier, Dynamic)>,
 }
 
 impl Default for CallFnOptions<'_> {
@@ -42,6 +49,8 @@ impl<'a> CallFnOptions<'a> {
             tag: None,
             eval_ast: true,
             rewind_scope: true,
+            named_args: StaticVec::new_const(),
+            default_args: StaticVec::new_const(),
         }
     }
     /// Bind to the `this` pointer.
@@ -72,6 +81,40 @@ impl<'a> CallFnOptions<'a> {
         self.rewind_scope = value;
         self
     }
+    /// Pass an argument by name, replacing any argument previously passed under the same name.
+    ///
+    /// Named arguments are matched against the names of the parameters of the script-defined
+    /// function that are not already taken by positional arguments.
+    #[inline]
+    #[must_use]
+    pub fn with_named_arg(
+        mut self,
+        name: impl Into<Identifier>,
+        value: impl Variant + Clone,
+    ) -> Self {
+        set_arg(&mut self.named_args, name.into(), Dynamic::from(value));
+        self
+    }
+    /// Set the default value for a parameter, used when no argument is passed to it either
+    /// positionally or by name.
+    #[inline]
+    #[must_use]
+    pub fn with_default_arg(
+        mut self,
+        name: impl Into<Identifier>,
+        value: impl Variant + Clone,
+    ) -> Self {
+        set_arg(&mut self.default_args, name.into(), Dynamic::from(value));
+        self
+    }
+}
+
+/// Set an argument in a list of name-value pairs, replacing any value under the same name.
+fn set_arg(args: &mut StaticVec<(Identifier, Dynamic)>, name: Identifier, value: Dynamic) {
+    match args.iter_mut().find(|(n, ..)| *n == name) {
+        Some((.., v)) => *v = value,
+        None => args.push((name, value)),
+    }
 }
 
 impl Engine {
@@ -161,23 +204,65 @@ impl Engine {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// # Named Arguments
+    ///
+    /// Arguments can also be passed by name via [`CallFnOptions::with_named_arg`], after any
+    /// positional arguments. Parameters not passed receive the value set via
+    /// [`CallFnOptions::with_default_arg`], otherwise their default values in the script (if
+    /// they are trailing parameters), or `()` if there is none.
+    ///
+    /// If the function is overloaded, the version with the fewest parameters that has all the
+    /// named parameters is called.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Scope, CallFnOptions};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let ast = engine.compile("
+    ///     fn configure(name, size, color, visible) {
+    ///         `${name}: ${size} ${color} ${visible}`
+    ///     }
+    /// ")?;
+    ///
+    /// let options = CallFnOptions::new()
+    ///                     .with_named_arg("visible", true)
+    ///                     .with_default_arg("size", 10_i64);
+    ///
+    /// let result = engine.call_fn_with_options::<String>(
+    ///                     options, &mut Scope::new(), &ast, "configure", ( "box", )
+    ///                 )?;
+    ///
+    /// assert_eq!(result, "box: 10  true");
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline(always)]
     pub fn call_fn_with_options<T: Variant + Clone>(
         &self,
-        options: CallFnOptions,
+        mut options: CallFnOptions,
         scope: &mut Scope,
         ast: &AST,
         name: impl AsRef<str>,
         args: impl FuncArgs,
     ) -> RhaiResultOf<T> {
+        let name = name.as_ref();
         let mut arg_values = StaticVec::new_const();
         args.parse(&mut arg_values);
 
+        if !options.named_args.is_empty() || !options.default_args.is_empty() {
+            let named_args = mem::take(&mut options.named_args);
+            let default_args = mem::take(&mut options.default_args);
+            Self::arrange_named_args(ast, name, &mut arg_values, named_args, &default_args)?;
+        }
+
         self._call_fn(
             options,
             scope,
             ast,
-            name.as_ref(),
+            name,
             arg_values.as_mut(),
             &mut self.new_global_runtime_state(),
             &mut Caches::new(),
@@ -194,6 +279,74 @@ impl Engine {
             })
         })
     }
+    /// Arrange positional and named arguments (plus default values) into the full list of
+    /// arguments of a script-defined function in an [`AST`].
+    fn arrange_named_args(
+        ast: &AST,
+        name: &str,
+        arg_values: &mut StaticVec<Dynamic>,
+        mut named_args: StaticVec<(Identifier, Dynamic)>,
+        default_args: &[(Identifier, Dynamic)],
+    ) -> RhaiResultOf<()> {
+        let num_positional = arg_values.len();
+
+        // Parameters that can be passed by name, excluding the rest parameter (if any)
+        fn named_params(
+            fn_def: &crate::ast::ScriptFuncDef,
+            num_positional: usize,
+        ) -> &[crate::ImmutableString] {
+            let num_fixed = fn_def.params.len() - usize::from(fn_def.is_variadic);
+            fn_def.params.get(num_positional..num_fixed).unwrap_or(&[])
+        }
+
+        // Find the function with the fewest parameters that has all the named parameters
+        let fn_def = ast
+            .shared_lib()
+            .iter_script_fn()
+            .filter(|&(.., fn_name, num_params, _)| fn_name == name && num_params >= num_positional)
+            .filter(|(.., fn_def)| {
+                named_args
+                    .iter()
+                    .all(|(n, ..)| named_params(fn_def, num_positional).iter().any(|p| p == n))
+            })
+            .min_by_key(|&(.., num_params, _)| num_params)
+            .map(|(.., fn_def)| fn_def)
+            .ok_or_else(|| {
+                let params = named_args
+                    .iter()
+                    .map(|(n, ..)| n.as_str())
+                    .collect::<StaticVec<_>>()
+                    .join(", ");
+                ERR::ErrorFunctionNotFound(format!("{name} (named: {params})"), Position::NONE)
+            })?;
+
+        let mut values = named_params(fn_def, num_positional)
+            .iter()
+            .map(|param| {
+                named_args
+                    .iter_mut()
+                    .find(|(n, ..)| n.as_str() == param.as_str())
+                    .map(|(.., v)| mem::take(v))
+                    .or_else(|| {
+                        default_args
+                            .iter()
+                            .find(|(n, ..)| n.as_str() == param.as_str())
+                            .map(|(.., v)| v.clone())
+                    })
+            })
+            .collect::<StaticVec<_>>();
+
+        // Leave trailing parameters not passed to their default values in the script, if any
+        let num_required = fn_def.min_num_args().saturating_sub(num_positional);
+
+        while values.len() > num_required && values.last().map_or(false, Option::is_none) {
+            values.pop();
+        }
+
+        arg_values.extend(values.into_iter().map(|v| v.unwrap_or(Dynamic::UNIT)));
+
+        Ok(())
+    }
     /// Call a script function defined in an [`AST`] with multiple [`Dynamic`] arguments.
     ///
     /// # Arguments
@@ -218,7 +371,14 @@ impl Engine {
 
         let orig_source = mem::replace(&mut global.source, ast.source_raw().cloned());
 
+        if let Some(state) = scope.shared_host_state() {
+            global.host_state = Some(state.clone());
+        }
+
         let orig_lib_len = global.lib.len();
+        if let Some(ref prelude) = self.prelude {
+            global.lib.push(prelude.clone());
+        }
         global.lib.push(ast.shared_lib().clone());
 
         let orig_tag = options.tag.map(|v| mem::replace(&mut global.tag, v));
@@ -230,6 +390,7 @@ impl Engine {
             std::mem::replace(&mut global.embedded_module_resolver, ast.resolver.clone());
 
         let rewind_scope = options.rewind_scope;
+        let undo_point = global.undo_log.len();
 
         defer! { global => move |g| {
             #[cfg(not(feature = "no_module"))]
@@ -259,8 +420,12 @@ impl Engine {
             #[cfg(not(feature = "no_closure"))]
             crate::func::ensure_no_data_race(name, args, false)?;
 
+            let arg_types = args
+                .iter()
+                .map(|a| self.map_type_name(Dynamic::type_name(a)));
+
             ast.shared_lib()
-                .get_script_fn(name, args.len())
+                .get_script_fn_by_types(name, arg_types)
                 .map_or_else(
                     || Err(ERR::ErrorFunctionNotFound(name.into(), Position::NONE).into()),
                     |fn_def| {
@@ -290,9 +455,14 @@ impl Engine {
             self.dbg(global, caches, scope, this_ptr, node)?;
         }
 
-        result.map_err(|err| match *err {
-            ERR::ErrorInFunctionCall(fn_name, _, inner_err, _) if fn_name == name => inner_err,
-            _ => err,
+        result.map_err(|err| {
+            // Undo changes made during this call
+            global.undo_log.rollback_to(undo_point);
+
+            match *err {
+                ERR::ErrorInFunctionCall(fn_name, _, inner_err, _) if fn_name == name => inner_err,
+                _ => err,
+            }
         })
     }
 }
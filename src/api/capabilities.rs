@@ -0,0 +1,195 @@
+//! Module that defines the capability introspection API of [`Engine`].
+
+use crate::Engine;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Get the names of all Cargo features of Rhai which are turned on.
+#[inline]
+pub(crate) fn enabled_features() -> impl Iterator<Item = &'static str> {
+    FEATURES
+        .iter()
+        .filter(|&&(_, on)| on)
+        .map(|&(name, _)| name)
+}
+
+/// All Cargo features of Rhai which affect the capabilities of an [`Engine`],
+/// together with whether each is turned on.
+const FEATURES: &[(&str, bool)] = &[
+    ("std", cfg!(feature = "std")),
+    ("no_std", cfg!(feature = "no_std")),
+    ("sync", cfg!(feature = "sync")),
+    ("parallel", cfg!(feature = "parallel")),
+    ("decimal", cfg!(feature = "decimal")),
+    ("serde", cfg!(feature = "serde")),
+    ("unicode-xid-ident", cfg!(feature = "unicode-xid-ident")),
+    ("metadata", cfg!(feature = "metadata")),
+    ("internals", cfg!(feature = "internals")),
+    ("debugging", cfg!(feature = "debugging")),
+    ("catch_panics", cfg!(feature = "catch_panics")),
+    ("stack_guard", cfg!(feature = "stack_guard")),
+    ("f32_float", cfg!(feature = "f32_float")),
+    ("only_i32", cfg!(feature = "only_i32")),
+    ("only_i64", cfg!(feature = "only_i64")),
+    ("no_float", cfg!(feature = "no_float")),
+    ("no_index", cfg!(feature = "no_index")),
+    ("no_object", cfg!(feature = "no_object")),
+    ("no_time", cfg!(feature = "no_time")),
+    ("no_function", cfg!(feature = "no_function")),
+    ("no_closure", cfg!(feature = "no_closure")),
+    ("no_module", cfg!(feature = "no_module")),
+    ("no_custom_syntax", cfg!(feature = "no_custom_syntax")),
+    ("unchecked", cfg!(feature = "unchecked")),
+    ("no_position", cfg!(feature = "no_position")),
+    ("no_optimize", cfg!(feature = "no_optimize")),
+];
+
+/// Description of a [module][crate::Module] (typically a package) loaded into the global
+/// namespace of an [`Engine`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct GlobalModuleInfo {
+    /// Id of the module, if any.
+    pub id: Option<String>,
+    /// Is the module part of the standard library?
+    pub is_standard: bool,
+    /// Number of functions in the module.
+    pub num_functions: usize,
+}
+
+/// Structured description of the capabilities of an [`Engine`], as returned by
+/// [`Engine::capabilities`].
+///
+/// This is useful for validating deployment configurations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct EngineCapabilities {
+    /// Version of Rhai.
+    pub version: &'static str,
+    /// Cargo features of Rhai that are turned on (e.g. `no_float`, `unchecked`).
+    pub features: Vec<&'static str>,
+    /// Limits in effect, keyed by name (e.g. `max_operations`), with zero meaning unlimited.
+    ///
+    /// Always empty under `unchecked`.
+    pub limits: Vec<(&'static str, u64)>,
+    /// Modules loaded into the global namespace, including packages, in order of registration.
+    ///
+    /// The first module is the global namespace of the [`Engine`] itself, containing
+    /// functions registered directly into the [`Engine`].
+    pub global_modules: Vec<GlobalModuleInfo>,
+    /// Names of static modules registered via
+    /// [`register_static_module`][Engine::register_static_module].
+    ///
+    /// Always empty under `no_module`.
+    pub static_modules: Vec<String>,
+    /// Is a module resolver set?
+    ///
+    /// Always `false` under `no_module`.
+    pub has_module_resolver: bool,
+    /// Is a variable resolver set via [`on_var`][Engine::on_var]?
+    pub has_variable_resolver: bool,
+    /// Is a prelude set via [`set_prelude`][Engine::set_prelude]?
+    pub has_prelude: bool,
+    /// Is [strict variables mode][Engine::set_strict_variables] turned on?
+    pub strict_variables: bool,
+    /// Is [deterministic mode][Engine::set_deterministic] turned on?
+    pub deterministic: bool,
+}
+
+impl Engine {
+    /// Get a structured description of the capabilities of this [`Engine`], including the
+    /// Cargo features turned on, limits, packages and resolvers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    /// # #[cfg(not(feature = "unchecked"))]
+    /// engine.set_max_operations(1000);
+    ///
+    /// let caps = engine.capabilities();
+    ///
+    /// assert_eq!(caps.features.contains(&"no_float"), cfg!(feature = "no_float"));
+    /// assert!(caps.global_modules.iter().any(|m| m.is_standard));
+    /// # #[cfg(not(feature = "unchecked"))]
+    /// assert!(caps.limits.contains(&("max_operations", 1000)));
+    /// ```
+    #[must_use]
+    pub fn capabilities(&self) -> EngineCapabilities {
+        let features = enabled_features().collect();
+
+        #[cfg(not(feature = "unchecked"))]
+        let limits = {
+            let mut limits = vec![
+                ("max_operations", self.max_operations()),
+                ("max_variables", self.max_variables() as u64),
+                ("max_open_handles", self.max_open_handles() as u64),
+                ("max_expr_depth", self.max_expr_depth() as u64),
+                ("max_string_size", self.max_string_size() as u64),
+                ("max_custom_data_size", self.max_custom_data_size() as u64),
+            ];
+            #[cfg(not(feature = "no_function"))]
+            limits.extend([
+                ("max_call_levels", self.max_call_levels() as u64),
+                ("max_functions", self.max_functions() as u64),
+                (
+                    "max_function_expr_depth",
+                    self.max_function_expr_depth() as u64,
+                ),
+            ]);
+            #[cfg(not(feature = "no_closure"))]
+            limits.extend([
+                ("max_closure_captures", self.max_closure_captures() as u64),
+                ("max_shared_values", self.max_shared_values() as u64),
+            ]);
+            #[cfg(not(feature = "no_module"))]
+            limits.push(("max_modules", self.max_modules() as u64));
+            #[cfg(not(feature = "no_index"))]
+            limits.push(("max_array_size", self.max_array_size() as u64));
+            #[cfg(not(feature = "no_object"))]
+            limits.push(("max_map_size", self.max_map_size() as u64));
+            #[cfg(feature = "stack_guard")]
+            limits.push(("min_stack_remaining", self.min_stack_remaining() as u64));
+            limits
+        };
+        #[cfg(feature = "unchecked")]
+        let limits = Vec::new();
+
+        let global_modules = self
+            .global_modules
+            .iter()
+            .map(|m| GlobalModuleInfo {
+                id: m.id().map(Into::into),
+                is_standard: m.is_standard_lib(),
+                num_functions: m.count().1,
+            })
+            .collect();
+
+        #[cfg(not(feature = "no_module"))]
+        let static_modules = self
+            .global_sub_modules
+            .keys()
+            .map(|name| name.to_string())
+            .collect();
+        #[cfg(feature = "no_module")]
+        let static_modules = Vec::new();
+
+        EngineCapabilities {
+            version: env!("CARGO_PKG_VERSION"),
+            features,
+            limits,
+            global_modules,
+            static_modules,
+            #[cfg(not(feature = "no_module"))]
+            has_module_resolver: self.module_resolver.is_some(),
+            #[cfg(feature = "no_module")]
+            has_module_resolver: false,
+            has_variable_resolver: self.resolve_var.is_some(),
+            has_prelude: self.has_prelude(),
+            strict_variables: self.strict_variables(),
+            deterministic: self.deterministic(),
+        }
+    }
+}
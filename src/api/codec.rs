@@ -0,0 +1,233 @@
+//! Module that defines the custom type codec API of [`Engine`].
+#![cfg(not(feature = "no_object"))]
+
+use crate::func::native::{OnDecodeTypeCallback, OnEncodeTypeCallback};
+use crate::func::SendSync;
+use crate::types::dynamic::{Union, Variant};
+use crate::{Dynamic, Engine, Identifier, Map, Position, RhaiResult, RhaiResultOf, Scope, ERR};
+use std::any::type_name;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Name of the property holding the type name of an encoded custom type.
+pub const CODEC_TYPE_PROPERTY: &str = "$type";
+/// Name of the property holding the encoded value of a custom type.
+pub const CODEC_VALUE_PROPERTY: &str = "$value";
+
+/// A codec for a custom type, registered via [`Engine::register_type_codec`].
+pub(crate) struct TypeCodec {
+    /// Name of the custom type, used to tag its encoded values.
+    pub name: Identifier,
+    /// Function to encode a value of the custom type.
+    pub encode: Box<OnEncodeTypeCallback>,
+    /// Function to decode a value of the custom type.
+    pub decode: Box<OnDecodeTypeCallback>,
+}
+
+impl Engine {
+    /// Register a codec for a custom type, so that [`Dynamic`] values containing it can be
+    /// converted via [`encode_dynamic`][Engine::encode_dynamic] into values made up only of
+    /// standard types (e.g. to serialize them with MessagePack or bincode), and back again via
+    /// [`decode_dynamic`][Engine::decode_dynamic].
+    ///
+    /// The `encode` function converts a value of the custom type into a value that can be
+    /// serialized, while the `decode` function does the reverse.
+    ///
+    /// An encoded custom type is represented by an object map with two properties:
+    /// `$type` holding the Rust type name and `$value` holding the value returned by `encode`.
+    ///
+    /// Not available under `no_object`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Dynamic, Engine};
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Point(i64, i64);
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_type_codec::<Point>(
+    ///     |p| Ok(format!("{},{}", p.0, p.1).into()),
+    ///     |v| {
+    ///         let s = v.into_string()?;
+    ///         let (x, y) = s.split_once(',').ok_or("invalid point")?;
+    ///         Ok(Point(x.parse().unwrap(), y.parse().unwrap()))
+    ///     },
+    /// );
+    ///
+    /// let value = Dynamic::from(Point(1, 2));
+    ///
+    /// let encoded = engine.encode_dynamic(&value)?;
+    /// assert!(encoded.is_map());
+    ///
+    /// let decoded = engine.decode_dynamic(encoded)?;
+    /// assert_eq!(decoded.cast::<Point>(), Point(1, 2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn register_type_codec<T: Variant + Clone>(
+        &mut self,
+        encode: impl Fn(&T) -> RhaiResultOf<Dynamic> + SendSync + 'static,
+        decode: impl Fn(Dynamic) -> RhaiResultOf<T> + SendSync + 'static,
+    ) -> &mut Self {
+        let codec = TypeCodec {
+            name: type_name::<T>().into(),
+            encode: Box::new(move |value| {
+                value
+                    .read_lock::<T>()
+                    .map_or(Ok(Dynamic::UNIT), |v| encode(&v))
+            }),
+            decode: Box::new(move |value| decode(value).map(Dynamic::from)),
+        };
+        self.type_codecs
+            .insert(std::any::TypeId::of::<T>(), codec.into());
+        self
+    }
+    /// Convert a [`Dynamic`] value into a value made up only of standard types, encoding all
+    /// custom types within it (including inside arrays and object maps) with the codecs
+    /// registered via [`register_type_codec`][Engine::register_type_codec].
+    ///
+    /// Shared values are flattened.
+    ///
+    /// Not available under `no_object`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a custom type with no registered codec is encountered,
+    /// or if a codec fails.
+    pub fn encode_dynamic(&self, value: &Dynamic) -> RhaiResult {
+        #[cfg(not(feature = "no_closure"))]
+        if value.is_shared() {
+            return self.encode_dynamic(&value.flatten_clone());
+        }
+
+        match value.0 {
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(ref arr, ..) => arr
+                .iter()
+                .map(|v| self.encode_dynamic(v))
+                .collect::<RhaiResultOf<crate::Array>>()
+                .map(Into::into),
+            Union::Map(ref map, ..) => map
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), self.encode_dynamic(v)?)))
+                .collect::<RhaiResultOf<Map>>()
+                .map(Into::into),
+            Union::Variant(..) => {
+                let codec = self.type_codecs.get(&value.type_id()).ok_or_else(|| {
+                    ERR::ErrorRuntime(
+                        format!(
+                            "No codec registered for type {}",
+                            self.map_type_name(value.type_name())
+                        )
+                        .into(),
+                        Position::NONE,
+                    )
+                })?;
+
+                let encoded = self.encode_dynamic(&(codec.encode)(value)?)?;
+
+                let mut map = Map::new();
+                map.insert(CODEC_TYPE_PROPERTY.into(), codec.name.as_str().into());
+                map.insert(CODEC_VALUE_PROPERTY.into(), encoded);
+                Ok(map.into())
+            }
+            _ => Ok(value.clone()),
+        }
+    }
+    /// Convert a value produced by [`encode_dynamic`][Engine::encode_dynamic] back into a
+    /// [`Dynamic`] value, decoding all custom types within it with the codecs registered via
+    /// [`register_type_codec`][Engine::register_type_codec].
+    ///
+    /// Not available under `no_object`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if an encoded custom type has no registered codec,
+    /// or if a codec fails.
+    pub fn decode_dynamic(&self, value: Dynamic) -> RhaiResult {
+        let value = value.flatten();
+
+        match value.0 {
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(arr, ..) => (*arr)
+                .into_iter()
+                .map(|v| self.decode_dynamic(v))
+                .collect::<RhaiResultOf<crate::Array>>()
+                .map(Into::into),
+            Union::Map(mut map, ..) => {
+                let name = match map.get(CODEC_TYPE_PROPERTY) {
+                    Some(tag) if map.len() == 2 && map.contains_key(CODEC_VALUE_PROPERTY) => {
+                        tag.clone().into_immutable_string().ok()
+                    }
+                    _ => None,
+                };
+
+                let Some(name) = name else {
+                    return (*map)
+                        .into_iter()
+                        .map(|(k, v)| Ok((k, self.decode_dynamic(v)?)))
+                        .collect::<RhaiResultOf<Map>>()
+                        .map(Into::into);
+                };
+
+                let codec = self
+                    .type_codecs
+                    .values()
+                    .find(|codec| codec.name == name.as_str())
+                    .ok_or_else(|| {
+                        ERR::ErrorRuntime(
+                            format!("No codec registered for type {name}").into(),
+                            Position::NONE,
+                        )
+                    })?;
+
+                let encoded = map.remove(CODEC_VALUE_PROPERTY).unwrap();
+                (codec.decode)(self.decode_dynamic(encoded)?)
+            }
+            union => Ok(Dynamic(union)),
+        }
+    }
+    /// Encode all the variables in a [`Scope`] via [`encode_dynamic`][Engine::encode_dynamic],
+    /// keeping their names and constant-ness.
+    ///
+    /// Not available under `no_object`.
+    pub fn encode_scope(&self, scope: &Scope) -> RhaiResultOf<Scope<'static>> {
+        let mut encoded = Scope::with_capacity(scope.len());
+
+        for (name, is_constant, value) in scope.iter_inner() {
+            let value = self.encode_dynamic(value)?;
+
+            if is_constant {
+                encoded.push_constant_dynamic(name, value);
+            } else {
+                encoded.push_dynamic(name, value);
+            }
+        }
+
+        Ok(encoded)
+    }
+    /// Decode all the variables in a [`Scope`] produced by [`encode_scope`][Engine::encode_scope]
+    /// via [`decode_dynamic`][Engine::decode_dynamic], keeping their names and constant-ness.
+    ///
+    /// Not available under `no_object`.
+    pub fn decode_scope(&self, scope: &Scope) -> RhaiResultOf<Scope<'static>> {
+        let mut decoded = Scope::with_capacity(scope.len());
+
+        for (name, is_constant, value) in scope.iter_inner() {
+            let value = self.decode_dynamic(value.clone())?;
+
+            if is_constant {
+                decoded.push_constant_dynamic(name, value);
+            } else {
+                decoded.push_dynamic(name, value);
+            }
+        }
+
+        Ok(decoded)
+    }
+}
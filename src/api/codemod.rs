@@ -0,0 +1,444 @@
+//! Module that defines the script migration (codemod) API of [`Engine`].
+#![cfg(not(feature = "no_position"))]
+
+use crate::parser::ParseResult;
+use crate::tokenizer::Token;
+use crate::{Engine, Identifier, Position};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{iter::once, ops::Range};
+
+/// A rewrite rule in a [`Codemod`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Rule {
+    /// Rename a function: old name, new name.
+    Rename(Identifier, Identifier),
+    /// Reorder the arguments of a function: name, source index of each new argument.
+    Reorder(Identifier, Vec<usize>),
+}
+
+/// A set of rewrite rules for migrating scripts to a changed API, applied via
+/// [`Engine::apply_codemod`].
+///
+/// All rules are matched against the original script, so a function can be both renamed and have
+/// its arguments reordered by the same [`Codemod`].
+///
+/// Not available under `no_position`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Codemod {
+    /// Rewrite rules, in order of registration.
+    rules: Vec<Rule>,
+}
+
+impl Codemod {
+    /// Create a new [`Codemod`] with no rewrite rules.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+    /// Rename a function.
+    ///
+    /// All calls to the function (including method calls) and all script-defined functions of
+    /// the same name are renamed.
+    ///
+    /// Namespace-qualified calls (e.g. `foo::bar(42)`) and function names stored in strings
+    /// (e.g. `Fn("bar")`) are not touched.
+    #[inline]
+    #[must_use]
+    pub fn rename_fn(mut self, from: impl Into<Identifier>, to: impl Into<Identifier>) -> Self {
+        self.rules.push(Rule::Rename(from.into(), to.into()));
+        self
+    }
+    /// Reorder the arguments of all calls to a function with a particular number of arguments.
+    ///
+    /// `order` lists, for each argument position in the rewritten call, the position of the
+    /// argument in the original call. For example, `[1, 0]` swaps the two arguments of a call.
+    ///
+    /// Only normal function calls are rewritten. Method calls, namespace-qualified calls and
+    /// calls with a different number of arguments are not touched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order` is not a permutation of `0..order.len()`.
+    #[must_use]
+    pub fn reorder_args(
+        mut self,
+        name: impl Into<Identifier>,
+        order: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        let order = order.into_iter().collect::<Vec<_>>();
+
+        let mut seen = vec![false; order.len()];
+        for &index in &order {
+            assert!(
+                index < order.len() && !seen[index],
+                "argument order {:?} is not a permutation",
+                order
+            );
+            seen[index] = true;
+        }
+
+        self.rules.push(Rule::Reorder(name.into(), order));
+        self
+    }
+    /// Is this [`Codemod`] empty (i.e. without rewrite rules)?
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// A change made to a script by a [`Codemod`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CodemodChange {
+    /// Position of the change in the original script.
+    pub position: Position,
+    /// Description of the change.
+    pub description: String,
+}
+
+/// A script file patched by a [`Codemod`], as returned by
+/// [`Engine::apply_codemod_to_dir`].
+///
+/// Not available under `no_std` or `WASM`.
+#[cfg(not(feature = "no_std"))]
+#[cfg(any(not(target_family = "wasm"), not(target_os = "unknown")))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CodemodFile {
+    /// Path of the script file.
+    pub path: std::path::PathBuf,
+    /// Patched script.
+    pub source: String,
+    /// Changes made to the script.
+    pub changes: Vec<CodemodChange>,
+}
+
+/// An edit to a range of bytes in a script.
+enum Edit {
+    /// Replace a range with new text.
+    Replace(Range<usize>, Identifier),
+    /// Permute ranges: ranges of the arguments, source index of each new argument.
+    Permute(Vec<Range<usize>>, Vec<usize>),
+}
+
+impl Edit {
+    /// Get the range of bytes covered by this edit.
+    fn range(&self) -> Range<usize> {
+        match self {
+            Self::Replace(range, ..) => range.clone(),
+            Self::Permute(slots, ..) => slots[0].start..slots[slots.len() - 1].end,
+        }
+    }
+}
+
+/// Render a range of a script with edits applied.
+///
+/// Edits must be sorted by start, with outer edits before inner edits.
+/// Edits nested inside permuted arguments are applied recursively.
+fn render(script: &str, edits: &[Edit], range: Range<usize>) -> String {
+    let mut output = String::with_capacity(range.len());
+    let mut cursor = range.start;
+
+    for edit in edits {
+        let r = edit.range();
+
+        if r.start < cursor || r.end > range.end {
+            continue;
+        }
+
+        output.push_str(&script[cursor..r.start]);
+
+        match edit {
+            Edit::Replace(.., text) => output.push_str(text),
+            Edit::Permute(slots, order) => {
+                for (i, &index) in order.iter().enumerate() {
+                    if i > 0 {
+                        output.push_str(&script[slots[i - 1].end..slots[i].start]);
+                    }
+                    output.push_str(&render(script, edits, slots[index].clone()));
+                }
+            }
+        }
+
+        cursor = r.end;
+    }
+
+    output.push_str(&script[cursor..range.end]);
+    output
+}
+
+impl Engine {
+    /// Apply a [`Codemod`] to a script, returning the patched script together with a report of
+    /// the changes made.
+    ///
+    /// The script must compile, otherwise a parse error is returned.
+    /// Formatting and comments are preserved.
+    ///
+    /// Not available under `no_position`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Codemod, Engine};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let codemod = Codemod::new()
+    ///     .rename_fn("old_clamp", "clamp")
+    ///     .reorder_args("old_clamp", [2, 0, 1]);
+    ///
+    /// let (script, changes) = engine.apply_codemod(&codemod, "
+    ///     let x = old_clamp(0, 10, value);  // value, lo, hi
+    /// ")?;
+    ///
+    /// assert_eq!(script, "
+    ///     let x = clamp(value, 0, 10);  // value, lo, hi
+    /// ");
+    /// assert_eq!(changes.len(), 2);
+    /// assert_eq!(changes[0].description, "renamed function 'old_clamp' to 'clamp'");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_codemod(
+        &self,
+        codemod: &Codemod,
+        script: impl AsRef<str>,
+    ) -> ParseResult<(String, Vec<CodemodChange>)> {
+        let script = script.as_ref();
+
+        self.compile(script)?;
+
+        let scripts = [script];
+        let (stream, _) = self.lex(&scripts);
+        let tokens = stream
+            .take_while(|(token, ..)| !matches!(token, Token::EOF))
+            .collect::<Vec<_>>();
+
+        let line_starts = once(0)
+            .chain(script.match_indices('\n').map(|(i, ..)| i + 1))
+            .collect::<Vec<_>>();
+
+        // Map a position to a byte offset in the script
+        let offset_of = |pos: Position| {
+            let start = line_starts[pos.line().unwrap() - 1];
+            let col = pos.position().unwrap_or(1) - 1;
+
+            script[start..]
+                .char_indices()
+                .nth(col)
+                .map_or(script.len(), |(i, ..)| start + i)
+        };
+
+        let mut edits = Vec::new();
+        let mut changes = Vec::new();
+
+        for (i, (token, pos)) in tokens.iter().enumerate() {
+            let name = match token {
+                Token::Identifier(name) => name.as_str(),
+                _ => continue,
+            };
+
+            let prev = i.checked_sub(1).map(|j| &tokens[j].0);
+            let next = tokens.get(i + 1).map(|(token, ..)| token);
+
+            if matches!(prev, Some(Token::DoubleColon))
+                || !matches!(next, Some(Token::LeftParen | Token::Unit))
+            {
+                continue;
+            }
+
+            #[cfg(not(feature = "no_function"))]
+            let is_def = matches!(prev, Some(Token::Fn));
+            #[cfg(feature = "no_function")]
+            let is_def = false;
+            #[cfg(not(feature = "no_object"))]
+            let is_method_call = matches!(prev, Some(Token::Period | Token::Elvis));
+            #[cfg(feature = "no_object")]
+            let is_method_call = false;
+
+            for rule in &codemod.rules {
+                match rule {
+                    Rule::Rename(from, to) if from == name => {
+                        let start = offset_of(*pos);
+                        edits.push(Edit::Replace(start..start + name.len(), to.clone()));
+
+                        let kind = if is_def {
+                            "function definition"
+                        } else {
+                            "function"
+                        };
+                        changes.push(CodemodChange {
+                            position: *pos,
+                            description: format!("renamed {kind} '{from}' to '{to}'"),
+                        });
+                    }
+                    Rule::Reorder(target, order)
+                        if target == name
+                            && !is_def
+                            && !is_method_call
+                            && matches!(next, Some(Token::LeftParen)) =>
+                    {
+                        let mut slots = Vec::new();
+                        let mut arg_start = offset_of(tokens[i + 1].1) + 1;
+                        let mut depth = 0_usize;
+                        let mut at_arg_start = true;
+                        let mut in_closure_params = false;
+
+                        for (token, pos) in &tokens[i + 2..] {
+                            match token {
+                                Token::LeftParen
+                                | Token::LeftBracket
+                                | Token::LeftBrace
+                                | Token::MapStart => depth += 1,
+                                #[cfg(not(feature = "no_index"))]
+                                Token::QuestionBracket => depth += 1,
+                                Token::RightParen | Token::RightBracket | Token::RightBrace
+                                    if depth > 0 =>
+                                {
+                                    depth -= 1
+                                }
+                                // Commas in closure parameters do not separate arguments
+                                Token::Pipe
+                                    if depth == 0 && (at_arg_start || in_closure_params) =>
+                                {
+                                    in_closure_params = !in_closure_params
+                                }
+                                Token::Comma | Token::RightParen
+                                    if depth == 0 && !in_closure_params =>
+                                {
+                                    let end = offset_of(*pos);
+                                    let arg = &script[arg_start..end];
+                                    let start = arg_start + (arg.len() - arg.trim_start().len());
+                                    slots.push(start..arg_start + arg.trim_end().len());
+
+                                    if matches!(token, Token::RightParen) {
+                                        break;
+                                    }
+
+                                    arg_start = end + 1;
+                                    at_arg_start = true;
+                                    continue;
+                                }
+                                _ => (),
+                            }
+                            at_arg_start = false;
+                        }
+
+                        // Skip trailing comma
+                        if slots.last().map_or(false, Range::is_empty) {
+                            slots.pop();
+                        }
+
+                        if slots.len() == order.len() && slots.len() > 1 {
+                            edits.push(Edit::Permute(slots, order.clone()));
+
+                            changes.push(CodemodChange {
+                                position: *pos,
+                                description: format!("reordered arguments of '{target}'"),
+                            });
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        edits.sort_by(|a, b| {
+            let (a, b) = (a.range(), b.range());
+            a.start.cmp(&b.start).then(b.end.cmp(&a.end))
+        });
+
+        Ok((render(script, &edits, 0..script.len()), changes))
+    }
+    /// Apply a [`Codemod`] to all script files with a particular extension (e.g. `rhai`) under a
+    /// directory, recursively, returning the patched scripts together with the changes made.
+    ///
+    /// Files are not written back. Only files that are changed are returned, in order of path.
+    ///
+    /// A leading shebang line (i.e. one starting with `#!`) is preserved.
+    ///
+    /// Not available under `no_std`, `no_position` or `WASM`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorSystem`][crate::EvalAltResult::ErrorSystem] if the directory or a script
+    /// file cannot be read, and [`ErrorParsing`][crate::EvalAltResult::ErrorParsing] if a script
+    /// file fails to compile.
+    #[cfg(not(feature = "no_std"))]
+    #[cfg(any(not(target_family = "wasm"), not(target_os = "unknown")))]
+    pub fn apply_codemod_to_dir(
+        &self,
+        codemod: &Codemod,
+        dir: impl AsRef<std::path::Path>,
+        extension: &str,
+    ) -> crate::RhaiResultOf<Vec<CodemodFile>> {
+        use crate::ERR;
+        use std::fs;
+
+        let mut pending = vec![dir.as_ref().to_path_buf()];
+        let mut paths = Vec::new();
+
+        while let Some(dir) = pending.pop() {
+            let entries = fs::read_dir(&dir).map_err(|err| {
+                ERR::ErrorSystem(
+                    format!("Cannot read directory '{}'", dir.to_string_lossy()),
+                    err.into(),
+                )
+            })?;
+
+            for entry in entries {
+                let path = entry
+                    .map_err(|err| {
+                        ERR::ErrorSystem(
+                            format!("Cannot read directory '{}'", dir.to_string_lossy()),
+                            err.into(),
+                        )
+                    })?
+                    .path();
+
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().map_or(false, |ext| ext == extension) {
+                    paths.push(path);
+                }
+            }
+        }
+
+        paths.sort();
+
+        let mut files = Vec::new();
+
+        for path in paths {
+            let contents = fs::read_to_string(&path).map_err(|err| {
+                ERR::ErrorSystem(
+                    format!("Cannot read script file '{}'", path.to_string_lossy()),
+                    err.into(),
+                )
+            })?;
+
+            // Keep the shebang but leave the new-line so that line numbers stay the same
+            let (shebang, script) = match contents.find('\n') {
+                Some(n) if contents.starts_with("#!") => contents.split_at(n),
+                None if contents.starts_with("#!") => (contents.as_str(), ""),
+                _ => ("", contents.as_str()),
+            };
+
+            let (patched, changes) = self.apply_codemod(codemod, script)?;
+
+            if !changes.is_empty() {
+                files.push(CodemodFile {
+                    path,
+                    source: format!("{shebang}{patched}"),
+                    changes,
+                });
+            }
+        }
+
+        Ok(files)
+    }
+}
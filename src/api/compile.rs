@@ -1,10 +1,115 @@
 //! Module that defines the public compilation API of [`Engine`].
 
+use crate::eval::Caches;
+use crate::func::ExprFnArgs;
 use crate::parser::{ParseResult, ParseState};
-use crate::{Engine, Scope, AST};
+use crate::types::dynamic::Variant;
+use crate::{
+    Dynamic, Engine, Locked, ParseError, Position, RhaiResultOf, Scope, StaticVec, AST, ERR,
+};
+use std::any::{type_name, TypeId};
+use std::fmt;
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 
+/// Results of compiling a batch of scripts via [`Engine::compile_batch_with_scope`].
+///
+/// Each script is compiled separately, in order, into its own [`AST`].
+///
+/// The [`Display`][fmt::Display] implementation lists the errors of all scripts that fail to
+/// compile, one per line, each tagged with the index of the script in the batch.
+#[derive(Debug, Clone)]
+pub struct BatchCompilation {
+    /// Compilation result of each script in the batch.
+    results: Vec<ParseResult<AST>>,
+}
+
+impl fmt::Display for BatchCompilation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (index, err)) in self.errors().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "script #{index}: {err}")?;
+        }
+        Ok(())
+    }
+}
+
+impl BatchCompilation {
+    /// Number of scripts in the batch.
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+    /// Is the batch empty?
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+    /// Did all scripts in the batch compile successfully?
+    #[inline]
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.results.iter().all(Result::is_ok)
+    }
+    /// Number of scripts in the batch that fail to compile.
+    #[inline]
+    #[must_use]
+    pub fn num_errors(&self) -> usize {
+        self.results.iter().filter(|r| r.is_err()).count()
+    }
+    /// Get the compilation result of each script, in order.
+    #[inline(always)]
+    pub fn results(&self) -> &[ParseResult<AST>] {
+        &self.results
+    }
+    /// Get the compilation result of each script, in order.
+    #[inline(always)]
+    #[must_use]
+    pub fn into_results(self) -> Vec<ParseResult<AST>> {
+        self.results
+    }
+    /// Get an iterator over the [`AST`]'s of all scripts that compile successfully,
+    /// together with the index of each script in the batch.
+    #[inline]
+    pub fn asts(&self) -> impl Iterator<Item = (usize, &AST)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| r.as_ref().ok().map(|ast| (i, ast)))
+    }
+    /// Get an iterator over the errors of all scripts that fail to compile,
+    /// together with the index of each script in the batch.
+    #[inline]
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &ParseError)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| r.as_ref().err().map(|err| (i, err)))
+    }
+    /// Get the [`AST`]'s of all scripts if all of them compile successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of all scripts that fail to compile, together with the index of each
+    /// script in the batch.
+    pub fn into_asts(self) -> Result<Vec<AST>, Vec<(usize, ParseError)>> {
+        if self.is_ok() {
+            Ok(self.results.into_iter().map(Result::unwrap).collect())
+        } else {
+            Err(self
+                .results
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, r)| r.err().map(|err| (i, err)))
+                .collect())
+        }
+    }
+}
+
 impl Engine {
     /// Compile a string into an [`AST`], which can be used later for evaluation.
     ///
@@ -29,6 +134,45 @@ impl Engine {
     pub fn compile(&self, script: impl AsRef<str>) -> ParseResult<AST> {
         self.compile_with_scope(&Scope::new(), script)
     }
+    /// Is a script _incomplete_, i.e. does it fail to compile only because it ends prematurely
+    /// (e.g. with an unclosed block or a dangling operator), so that more input may complete it?
+    ///
+    /// This is useful for interactive tools, such as a REPL, to decide whether to read more lines
+    /// before evaluating the input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// assert!(engine.is_script_incomplete("if x > 0 {"));
+    /// assert!(engine.is_script_incomplete("let y = 40 +"));
+    /// assert!(!engine.is_script_incomplete("let y = 40 + 2;"));
+    ///
+    /// // Errors before the end of the script cannot be fixed by more input
+    /// assert!(!engine.is_script_incomplete("let = 42; if x > 0 {"));
+    /// ```
+    #[must_use]
+    pub fn is_script_incomplete(&self, script: &str) -> bool {
+        let err = match self.compile(script) {
+            Ok(..) => return false,
+            Err(err) => err,
+        };
+
+        if *err.err_type() == crate::ParseErrorType::UnexpectedEOF {
+            return true;
+        }
+
+        // Errors reported at the end of the script are caused by missing input
+        let scripts = [script];
+        let (mut stream, ..) = self.lex_raw(&scripts, None);
+
+        stream
+            .find(|(token, ..)| *token == crate::tokenizer::Token::EOF)
+            .map_or(false, |(.., pos)| !pos.is_none() && pos == err.position())
+    }
     /// Compile a string into an [`AST`] using own scope, which can be used later for evaluation.
     ///
     /// ## Constants Propagation
@@ -93,6 +237,7 @@ impl Engine {
         use std::collections::BTreeSet;
 
         fn collect_imports(
+            engine: &Engine,
             ast: &AST,
             resolver: &StaticModuleResolver,
             imports: &mut BTreeSet<crate::Identifier>,
@@ -100,11 +245,14 @@ impl Engine {
             ast._walk(&mut |path| match path.last().unwrap() {
                 // Collect all `import` statements with a string constant path
                 ASTNode::Stmt(Stmt::Import(x, ..)) => match x.0 {
-                    Expr::StringConstant(ref s, ..)
+                    Expr::StringConstant(ref s, pos) => {
+                        engine.record_import(ast.source(), s, pos);
+
                         if !resolver.contains_path(s)
-                            && (imports.is_empty() || !imports.contains(s.as_str())) =>
-                    {
-                        imports.insert(s.clone().into());
+                            && (imports.is_empty() || !imports.contains(s.as_str()))
+                        {
+                            imports.insert(s.clone().into());
+                        }
                         true
                     }
                     _ => true,
@@ -118,7 +266,7 @@ impl Engine {
         let mut resolver = StaticModuleResolver::new();
         let mut imports = BTreeSet::new();
 
-        collect_imports(&ast, &resolver, &mut imports);
+        collect_imports(self, &ast, &resolver, &mut imports);
 
         if !imports.is_empty() {
             while let Some(path) = imports.pop_first() {
@@ -128,7 +276,9 @@ impl Engine {
                     .module_resolver()
                     .resolve_ast(self, None, &path, crate::Position::NONE)
                 {
-                    Some(Ok(module_ast)) => collect_imports(&module_ast, &resolver, &mut imports),
+                    Some(Ok(module_ast)) => {
+                        collect_imports(self, &module_ast, &resolver, &mut imports);
+                    }
                     Some(err) => return err,
                     None => (),
                 }
@@ -207,6 +357,56 @@ impl Engine {
             self.optimization_level,
         )
     }
+    /// Compile a batch of scripts, each into its own [`AST`], using own scope.
+    ///
+    /// Unlike [`compile_scripts_with_scope`][Engine::compile_scripts_with_scope], the scripts are
+    /// _not_ joined together. A script that fails to compile does not stop the rest of the batch
+    /// from being compiled, and the errors of all scripts can be reported together.
+    ///
+    /// This is faster than compiling many small scripts (e.g. rules) one by one, as strings
+    /// interned while compiling one script are reused for the others.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let batch = engine.compile_batch_with_scope(&Scope::new(), &[
+    ///                 "x > 40",
+    ///                 "x < ",
+    ///                 "x == 42",
+    ///                 "let = 1;"
+    /// ]);
+    ///
+    /// assert_eq!(batch.len(), 4);
+    /// assert_eq!(batch.num_errors(), 2);
+    /// assert_eq!(batch.asts().map(|(i, _)| i).collect::<Vec<_>>(), [0, 2]);
+    /// assert_eq!(batch.errors().map(|(i, _)| i).collect::<Vec<_>>(), [1, 3]);
+    ///
+    /// assert!(batch.to_string().starts_with("script #1: "));
+    /// ```
+    pub fn compile_batch_with_scope<S: AsRef<str>>(
+        &self,
+        scope: &Scope,
+        scripts: impl AsRef<[S]>,
+    ) -> BatchCompilation {
+        let results = scripts
+            .as_ref()
+            .iter()
+            .map(|script| {
+                self.compile_scripts_with_scope_raw(
+                    Some(scope),
+                    [script],
+                    #[cfg(not(feature = "no_optimize"))]
+                    self.optimization_level,
+                )
+            })
+            .collect();
+
+        BatchCompilation { results }
+    }
     /// Join a list of strings and compile into an [`AST`] using own scope at a specific optimization level.
     ///
     /// ## Constants Propagation
@@ -312,4 +512,119 @@ impl Engine {
             self.optimization_level,
         )
     }
+    /// Compile a string containing an expression into a Rust closure taking the values of
+    /// named parameters.
+    ///
+    /// The expression is compiled only once. Each parameter is a variable in a preallocated
+    /// [`Scope`], which is reused (together with the function resolution caches) across calls
+    /// to avoid setting up a new evaluation environment every time.
+    ///
+    /// The closure is re-entrant; a call made while another call is running (e.g. from within
+    /// a native function or from another thread under `sync`) simply sets up its own
+    /// environment.
+    ///
+    /// The closure's parameter types are specified as a tuple.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameter names does not match the number of parameter types.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// // 'calc' will have type Box<dyn Fn(i64, i64) -> Result<bool, Box<EvalAltResult>>>
+    /// let calc = engine.compile_expression_to_fn::<(i64, i64), bool>(
+    ///     &["x", "y"],        // parameter names in order
+    ///     "x * 2 + y < 42",   // the expression
+    /// )?;
+    ///
+    /// assert!(calc(10, 1)?);
+    /// assert!(!calc(20, 3)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn compile_expression_to_fn<'e, A: ExprFnArgs<'e, R>, R: Variant + Clone>(
+        &'e self,
+        params: &[&str],
+        script: impl AsRef<str>,
+    ) -> ParseResult<A::Output> {
+        assert_eq!(
+            params.len(),
+            A::NUM_PARAMS,
+            "the number of parameter names must match the number of parameter types"
+        );
+
+        let mut scope = Scope::with_capacity(params.len());
+        params.iter().for_each(|&name| {
+            scope.push_dynamic(name, Dynamic::UNIT);
+        });
+
+        let ast = self.compile_expression_with_scope(&scope, script)?;
+        let template = scope.clone();
+        let state = Locked::new((scope, Caches::new()));
+
+        Ok(A::wrap_fn(move |args| {
+            #[cfg(not(feature = "sync"))]
+            let guard = state.try_borrow_mut().ok();
+            #[cfg(feature = "sync")]
+            let guard = state.try_write().ok();
+
+            match guard {
+                Some(mut guard) => {
+                    let (scope, caches) = &mut *guard;
+                    self.eval_expr_fn(scope, caches, &ast, args)
+                }
+                // The preallocated environment is in use, so set up a new one
+                None => self.eval_expr_fn(&mut template.clone(), &mut Caches::new(), &ast, args),
+            }
+        }))
+    }
+    /// Evaluate an expression compiled via [`compile_expression_to_fn`][Engine::compile_expression_to_fn]
+    /// with the parameters in a preallocated [`Scope`].
+    fn eval_expr_fn<T: Variant + Clone>(
+        &self,
+        scope: &mut Scope,
+        caches: &mut Caches,
+        ast: &AST,
+        args: StaticVec<Dynamic>,
+    ) -> RhaiResultOf<T> {
+        let num_params = args.len();
+
+        for (index, value) in args.into_iter().enumerate() {
+            *scope.get_mut_by_index(index) = value;
+        }
+
+        let global = &mut self.new_global_runtime_state();
+        let result = self.eval_ast_with_scope_raw(global, caches, scope, ast);
+
+        // Keep the environment clean for the next call
+        scope.rewind(num_params);
+        (0..num_params).for_each(|index| *scope.get_mut_by_index(index) = Dynamic::UNIT);
+
+        let result = result?;
+
+        // Bail out early if the return type needs no cast
+        if TypeId::of::<T>() == TypeId::of::<Dynamic>() {
+            return Ok(reify! { result => T });
+        }
+
+        result.try_cast_result::<T>().map_err(|v| {
+            let typename = match type_name::<T>() {
+                typ if typ.contains("::") => self.map_type_name(typ),
+                typ => typ,
+            };
+
+            ERR::ErrorMismatchOutputType(
+                typename.into(),
+                self.map_type_name(v.type_name()).into(),
+                Position::NONE,
+            )
+            .into()
+        })
+    }
 }
@@ -0,0 +1,164 @@
+//! Module that defines the code completion API of [`Engine`].
+#![cfg(feature = "metadata")]
+#![cfg(not(feature = "no_position"))]
+
+use super::highlight::TokenCategory;
+use crate::tokenizer::{is_valid_identifier, Token};
+use crate::{Engine, FnAccess, Scope};
+use std::collections::BTreeSet;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Completions for the identifier under the cursor, as returned by [`Engine::completions`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Completions {
+    /// Byte offset of the start of the text to be replaced by a completion.
+    pub start: usize,
+    /// Candidates starting with the text to be replaced, sorted alphabetically.
+    pub candidates: Vec<String>,
+}
+
+impl Engine {
+    /// Get completions for the identifier (or namespace-qualified path) under the cursor.
+    ///
+    /// `offset` is the byte offset of the cursor within the script.
+    ///
+    /// Candidates include keywords, variables in the [`Scope`] (if any), variables and functions
+    /// defined in the script before the cursor, and functions registered with this [`Engine`]
+    /// (including packages, static modules and the prelude). For a namespace-qualified path
+    /// (e.g. `foo::ba`), only sub-modules and functions of that module are candidates. After a
+    /// `.`, property names of registered getters are also candidates.
+    ///
+    /// The script need not compile (e.g. because it is being edited).
+    ///
+    /// Not available under `no_position`. Exported under the `metadata` feature only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn("zz_double", |x: i64| x * 2);
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.push("zz_answer", 42_i64);
+    ///
+    /// let script = "let zz_local = 1; zz_";
+    ///
+    /// let completions = engine.completions(script, script.len(), Some(&scope));
+    ///
+    /// assert_eq!(completions.start, 18);
+    /// assert_eq!(completions.candidates, ["zz_answer", "zz_double", "zz_local"]);
+    /// ```
+    #[must_use]
+    pub fn completions(&self, script: &str, offset: usize, scope: Option<&Scope>) -> Completions {
+        let tokens = self.tokenize_classified(script);
+        let text = |i: usize| &script[tokens[i].span.clone()];
+        let is_ident = |i: usize| tokens[i].category == TokenCategory::Identifier;
+
+        // Tokens starting before the cursor, with the word being typed (if any) at the end
+        let end = tokens.iter().take_while(|t| t.span.start < offset).count();
+
+        let (start, prefix, mut before) = match end.checked_sub(1) {
+            Some(i) if is_word(tokens[i].category) && offset <= tokens[i].span.end => {
+                let start = tokens[i].span.start;
+                (start, &script[start..offset], i)
+            }
+            _ => (offset, "", end),
+        };
+
+        // Namespace path, if any
+        let mut path = Vec::new();
+
+        while before >= 2 && text(before - 1) == "::" && is_ident(before - 2) {
+            path.insert(0, text(before - 2));
+            before -= 2;
+        }
+
+        let mut candidates = BTreeSet::new();
+
+        let mut add = |name: &str| {
+            if name.starts_with(prefix) && is_valid_identifier(name) {
+                candidates.insert(name.to_string());
+            }
+        };
+
+        if path.is_empty() {
+            Token::iter_standard_keywords()
+                .filter(|&k| !self.is_symbol_disabled(k))
+                .for_each(&mut add);
+
+            if let Some(scope) = scope {
+                scope.iter_raw().for_each(|(name, ..)| add(name));
+            }
+
+            // Variables and functions defined in the script before the cursor
+            for i in 1..before {
+                if is_ident(i) && matches!(text(i - 1), "let" | "const" | "fn") {
+                    add(text(i));
+                }
+            }
+
+            #[cfg(not(feature = "no_module"))]
+            self.global_sub_modules.keys().for_each(|name| add(name));
+
+            let is_property = before >= 1 && matches!(text(before - 1), "." | "?.");
+
+            let mut add_fn = |name: &str, access: FnAccess| {
+                if access == FnAccess::Private {
+                    return;
+                }
+                match name.strip_prefix(crate::engine::FN_GET) {
+                    Some(prop) if is_property => add(prop),
+                    Some(..) => (),
+                    None => add(name),
+                }
+            };
+
+            for m in &self.global_modules {
+                m.iter_fn().for_each(|(.., f)| add_fn(&f.name, f.access));
+            }
+
+            #[cfg(not(feature = "no_module"))]
+            for m in self.global_sub_modules.values() {
+                super::signature_help::add_global_fns(m, &mut |_, f| add_fn(&f.name, f.access));
+            }
+
+            if let Some(ref prelude) = self.prelude {
+                prelude
+                    .iter_fn()
+                    .for_each(|(.., f)| add_fn(&f.name, f.access));
+            }
+        } else {
+            #[cfg(not(feature = "no_module"))]
+            if let Some(m) = self.global_sub_modules.get(path[0]).and_then(|m| {
+                path[1..]
+                    .iter()
+                    .try_fold(&**m, |m, &name| m.get_sub_module(name))
+            }) {
+                m.iter_sub_modules().for_each(|(name, ..)| add(name));
+                m.iter_fn()
+                    .filter(|(.., f)| f.access != FnAccess::Private)
+                    .for_each(|(.., f)| add(&f.name));
+            }
+        }
+
+        Completions {
+            start,
+            candidates: candidates.into_iter().collect(),
+        }
+    }
+}
+
+/// Can a token of this [category][TokenCategory] be completed?
+#[inline(always)]
+#[must_use]
+const fn is_word(category: TokenCategory) -> bool {
+    matches!(
+        category,
+        TokenCategory::Identifier | TokenCategory::Keyword | TokenCategory::Reserved
+    )
+}
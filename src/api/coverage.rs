@@ -0,0 +1,226 @@
+//! Module that defines the script coverage instrumentation API of [`Engine`].
+#![cfg(not(feature = "no_position"))]
+
+use crate::eval::GlobalRuntimeState;
+use crate::func::{locked_read, locked_write};
+use crate::{Engine, Identifier, Locked, Position, Shared, AST};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{collections::BTreeMap, fmt::Write};
+
+/// Coverage data collected during script evaluation.
+///
+/// Hit counts of statements and expressions are keyed by source (empty if none) and by
+/// position (line and column).
+///
+/// Not available under `no_position`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    /// Hit counts keyed by source and then by (line, column).
+    hits: BTreeMap<Identifier, BTreeMap<(usize, usize), u64>>,
+}
+
+impl Coverage {
+    /// Create a new, empty [`Coverage`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            hits: BTreeMap::new(),
+        }
+    }
+    /// Is this [`Coverage`] empty?
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hits.is_empty()
+    }
+    /// Clear all coverage data.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.hits.clear();
+    }
+    /// Record one hit at a position.
+    ///
+    /// Positions that are [`NONE`][Position::NONE] are ignored.
+    pub fn hit(&mut self, source: Option<&str>, pos: Position) {
+        if let (Some(line), Some(col)) = (pos.line(), pos.position()) {
+            let source = source.unwrap_or_default();
+
+            let positions = match self.hits.get_mut(source) {
+                Some(positions) => positions,
+                None => self.hits.entry(source.into()).or_default(),
+            };
+
+            *positions.entry((line, col)).or_insert(0) += 1;
+        }
+    }
+    /// Register all statements and expressions in an [`AST`] (including function bodies) with
+    /// zero hits, so that nodes never executed show up in the coverage report.
+    ///
+    /// The source of the [`AST`] (if any) is used as the source of all its nodes.
+    pub fn add_ast(&mut self, ast: &AST) {
+        let positions = self
+            .hits
+            .entry(ast.source().unwrap_or_default().into())
+            .or_default();
+
+        ast._walk(&mut |path| {
+            let pos = path.last().unwrap().position();
+
+            if let (Some(line), Some(col)) = (pos.line(), pos.position()) {
+                positions.entry((line, col)).or_insert(0);
+            }
+            true
+        });
+    }
+    /// Get the number of times the statement or expression at a position was executed.
+    ///
+    /// Returns [`None`] if nothing is recorded at the position.
+    #[must_use]
+    pub fn hit_count(&self, source: Option<&str>, pos: Position) -> Option<u64> {
+        let positions = self.hits.get(source.unwrap_or_default())?;
+        positions.get(&(pos.line()?, pos.position()?)).copied()
+    }
+    /// Get an iterator over all recorded positions in the form of
+    /// `(source, line, column, hit count)`.
+    ///
+    /// The source is empty if none.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize, usize, u64)> {
+        self.hits.iter().flat_map(|(source, positions)| {
+            positions
+                .iter()
+                .map(move |(&(line, col), &hits)| (source.as_str(), line, col, hits))
+        })
+    }
+    /// Get the hit counts of each line in a source, in order of line number.
+    ///
+    /// The hit count of a line is the maximum number of times any statement or expression
+    /// starting on that line was executed.
+    #[must_use]
+    pub fn line_hits(&self, source: Option<&str>) -> BTreeMap<usize, u64> {
+        let mut lines = BTreeMap::new();
+
+        if let Some(positions) = self.hits.get(source.unwrap_or_default()) {
+            for (&(line, _), &hits) in positions {
+                let count = lines.entry(line).or_insert(0);
+                *count = (*count).max(hits);
+            }
+        }
+
+        lines
+    }
+    /// Export the coverage data in [LCOV](https://github.com/linux-test-project/lcov) tracefile
+    /// format.
+    ///
+    /// Each source is exported as a separate record with line hit counts.
+    /// Nodes without a source are exported under the name `<script>`.
+    #[must_use]
+    pub fn to_lcov(&self) -> String {
+        let mut output = String::new();
+
+        for source in self.hits.keys() {
+            let lines = self.line_hits(Some(source));
+            let name = if source.is_empty() {
+                "<script>"
+            } else {
+                source
+            };
+
+            writeln!(output, "TN:").unwrap();
+            writeln!(output, "SF:{name}").unwrap();
+
+            for (line, hits) in &lines {
+                writeln!(output, "DA:{line},{hits}").unwrap();
+            }
+
+            let hit = lines.values().filter(|&&hits| hits > 0).count();
+            writeln!(output, "LH:{hit}").unwrap();
+            writeln!(output, "LF:{}", lines.len()).unwrap();
+            writeln!(output, "end_of_record").unwrap();
+        }
+
+        output
+    }
+}
+
+impl Engine {
+    /// Turn on/off the collection of script coverage data.
+    ///
+    /// When on, the number of times each statement and expression is executed is recorded.
+    /// Turning it on again clears all previously collected data.
+    ///
+    /// Not available under `no_position`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_collect_coverage(true);
+    ///
+    /// let ast = engine.compile("let x = 0;\nif x > 0 {\n    x = 42;\n}")?;
+    ///
+    /// engine.run_ast(&ast)?;
+    ///
+    /// let mut coverage = engine.coverage().unwrap();
+    /// coverage.add_ast(&ast);
+    ///
+    /// let lines = coverage.line_hits(None);
+    /// assert_eq!(lines[&1], 1);
+    /// assert_eq!(lines[&2], 1);
+    /// assert_eq!(lines[&3], 0);
+    ///
+    /// assert!(coverage.to_lcov().contains("DA:3,0"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_collect_coverage(&mut self, enable: bool) -> &mut Self {
+        self.coverage = if enable {
+            Some(Shared::new(Locked::new(Coverage::new())))
+        } else {
+            None
+        };
+        self
+    }
+    /// Is script coverage data being collected?
+    ///
+    /// Not available under `no_position`.
+    #[inline(always)]
+    #[must_use]
+    pub fn collect_coverage(&self) -> bool {
+        self.coverage.is_some()
+    }
+    /// Get a snapshot of the script coverage data collected so far.
+    ///
+    /// Returns [`None`] if coverage collection is not turned on.
+    ///
+    /// Not available under `no_position`.
+    #[must_use]
+    pub fn coverage(&self) -> Option<Coverage> {
+        self.coverage
+            .as_ref()
+            .and_then(|cov| locked_read(cov).map(|cov| cov.clone()))
+    }
+    /// Clear the script coverage data collected so far.
+    ///
+    /// Not available under `no_position`.
+    #[inline]
+    pub fn clear_coverage(&self) -> &Self {
+        if let Some(mut cov) = self.coverage.as_ref().and_then(|cov| locked_write(cov)) {
+            cov.clear();
+        }
+        self
+    }
+    /// Record one hit at a position if coverage collection is turned on.
+    #[inline(always)]
+    pub(crate) fn track_coverage(&self, global: &GlobalRuntimeState, pos: Position) {
+        if let Some(mut cov) = global.coverage.as_ref().and_then(|cov| locked_write(cov)) {
+            cov.hit(global.source(), pos);
+        }
+    }
+}
@@ -24,6 +24,8 @@ pub mod markers {
     pub const CUSTOM_SYNTAX_MARKER_FUNC: &str = "$func$";
     /// Special marker for matching an identifier.
     pub const CUSTOM_SYNTAX_MARKER_IDENT: &str = "$ident$";
+    /// Special marker for matching the name of a new variable declared by the custom syntax.
+    pub const CUSTOM_SYNTAX_MARKER_VAR: &str = "$var$";
     /// Special marker for matching a single symbol.
     pub const CUSTOM_SYNTAX_MARKER_SYMBOL: &str = "$symbol$";
     /// Special marker for matching a string literal.
@@ -37,6 +39,78 @@ pub mod markers {
     pub const CUSTOM_SYNTAX_MARKER_BOOL: &str = "$bool$";
     /// Special marker for identifying the custom syntax variant.
     pub const CUSTOM_SYNTAX_MARKER_SYNTAX_VARIANT: &str = "$$";
+    /// Types that can be required of an expression or statements block, in the form of
+    /// `$expr:type$` or `$block:type$`.
+    pub const CUSTOM_SYNTAX_TYPES: &[&str] = &[
+        "()", "int", "float", "decimal", "bool", "char", "string", "array", "blob", "map",
+    ];
+}
+
+/// Split a typed marker (e.g. `$expr:int$` or `$block:()$`) into the untyped marker and the
+/// required type.
+///
+/// Returns [`None`] if the symbol is not a typed marker.
+#[must_use]
+pub(crate) fn split_typed_marker(symbol: &str) -> Option<(&'static str, &str)> {
+    #[allow(clippy::wildcard_imports)]
+    use markers::*;
+
+    let (marker, ty) = symbol
+        .strip_suffix('$')?
+        .strip_prefix('$')?
+        .split_once(':')?;
+
+    let marker = match marker {
+        "expr" => CUSTOM_SYNTAX_MARKER_EXPR,
+        "block" => CUSTOM_SYNTAX_MARKER_BLOCK,
+        _ => return None,
+    };
+
+    CUSTOM_SYNTAX_TYPES.contains(&ty).then_some((marker, ty))
+}
+
+/// Determine the type (as named in [`CUSTOM_SYNTAX_TYPES`][markers::CUSTOM_SYNTAX_TYPES]) that
+/// an expression evaluates to, if it can be known without evaluating the expression.
+#[must_use]
+pub(crate) fn static_type_of(expr: &Expr) -> Option<&'static str> {
+    match expr {
+        Expr::Unit(..) => Some("()"),
+        Expr::IntegerConstant(..) => Some("int"),
+        #[cfg(not(feature = "no_float"))]
+        Expr::FloatConstant(..) => Some("float"),
+        Expr::BoolConstant(..) | Expr::And(..) | Expr::Or(..) => Some("bool"),
+        Expr::CharConstant(..) => Some("char"),
+        Expr::StringConstant(..) | Expr::InterpolatedString(..) => Some("string"),
+        #[cfg(not(feature = "no_index"))]
+        Expr::Array(..) => Some("array"),
+        #[cfg(not(feature = "no_object"))]
+        Expr::Map(..) => Some("map"),
+        Expr::DynamicConstant(x, ..) => match x {
+            _ if x.is_unit() => Some("()"),
+            _ if x.is_int() => Some("int"),
+            #[cfg(not(feature = "no_float"))]
+            _ if x.is_float() => Some("float"),
+            #[cfg(feature = "decimal")]
+            _ if x.is_decimal() => Some("decimal"),
+            _ if x.is_bool() => Some("bool"),
+            _ if x.is_char() => Some("char"),
+            _ if x.is_string() => Some("string"),
+            #[cfg(not(feature = "no_index"))]
+            _ if x.is_array() => Some("array"),
+            #[cfg(not(feature = "no_index"))]
+            _ if x.is_blob() => Some("blob"),
+            #[cfg(not(feature = "no_object"))]
+            _ if x.is_map() => Some("map"),
+            _ => None,
+        },
+        // A statements block evaluates to its last statement
+        Expr::Stmt(x) => match x.statements().last() {
+            None => Some("()"),
+            Some(crate::ast::Stmt::Expr(e)) => static_type_of(e),
+            Some(..) => None,
+        },
+        _ => None,
+    }
 }
 
 /// A general expression evaluation trait object.
@@ -186,6 +260,8 @@ pub struct CustomSyntax {
     pub func: Box<FnCustomSyntaxEval>,
     /// Any variables added/removed in the scope?
     pub scope_may_be_changed: bool,
+    /// Symbols of the custom syntax, if known (i.e. not registered via a raw parsing function).
+    pub symbols: Vec<ImmutableString>,
 }
 
 impl Engine {
@@ -204,6 +280,26 @@ impl Engine {
     /// * If `symbols` does not contain at least one valid token, then the custom syntax registration
     ///   is simply ignored.
     ///
+    /// ## Typed expressions and blocks
+    ///
+    /// `$expr:type$` and `$block:type$` match an expression or statements block that must evaluate
+    /// to a value of `type`, which is one of: `()`, `int`, `float`, `decimal`, `bool`, `char`,
+    /// `string`, `array`, `blob` or `map`.
+    ///
+    /// The type is checked at parse time whenever it can be determined (e.g. for literals), and
+    /// mismatches are reported as parse errors. Expressions whose type depends on runtime values
+    /// (e.g. variables or function calls) are accepted and must still be checked by `func`.
+    ///
+    /// ## Declared variables
+    ///
+    /// `$var$` matches the name of a new variable declared by this custom syntax. The variable is
+    /// visible to all statements following the custom syntax, but not to the custom syntax's own
+    /// expressions.
+    ///
+    /// After `func` returns, the variable is added to the current [`Scope`][crate::Scope]
+    /// automatically, with the value `func` pushed under its name (or `()` if none). Other
+    /// variables pushed by `func` are removed, so `scope_may_be_changed` can stay `false`.
+    ///
     /// ## Note on `scope_may_be_changed`
     ///
     /// If `scope_may_be_changed` is `true`, then _size_ of the current [`Scope`][crate::Scope]
@@ -245,6 +341,7 @@ impl Engine {
             let seg = match s {
                 // Markers not in first position
                 CUSTOM_SYNTAX_MARKER_IDENT
+                | CUSTOM_SYNTAX_MARKER_VAR
                 | CUSTOM_SYNTAX_MARKER_SYMBOL
                 | CUSTOM_SYNTAX_MARKER_EXPR
                 | CUSTOM_SYNTAX_MARKER_BLOCK
@@ -264,6 +361,9 @@ impl Engine {
                 #[cfg(not(feature = "no_float"))]
                 CUSTOM_SYNTAX_MARKER_FLOAT if !segments.is_empty() => s.into(),
 
+                // Typed markers not in first position
+                _ if !segments.is_empty() && split_typed_marker(s).is_some() => s.into(),
+
                 // Identifier not in first position
                 _ if !segments.is_empty() && is_valid_identifier(s) => s.into(),
 
@@ -328,9 +428,10 @@ impl Engine {
 
         // The first keyword/symbol is the discriminator
         let key = segments[0].clone();
+        let symbols = segments.clone();
 
         self.register_custom_syntax_with_state_raw(
-            key,
+            key.clone(),
             // Construct the parsing function
             move |stream, _, _| match stream.len() {
                 len if len >= segments.len() => Ok(None),
@@ -340,6 +441,14 @@ impl Engine {
             move |context, expressions, _| func(context, expressions),
         );
 
+        if let Some(syntax) = self
+            .custom_syntax
+            .get_mut(key.as_str())
+            .and_then(crate::Shared::get_mut)
+        {
+            syntax.symbols = symbols;
+        }
+
         Ok(self)
     }
     /// Register a custom syntax with the [`Engine`] with custom user-defined state.
@@ -364,8 +473,8 @@ impl Engine {
     /// `Fn(symbols: &[ImmutableString], look_ahead: &str, state: &mut Dynamic) -> Result<Option<ImmutableString>, ParseError>`
     ///
     /// where:
-    /// * `symbols`: a slice of symbols that have been parsed so far, possibly containing `$expr$` and/or `$block$`;
-    ///   `$ident$` and other literal markers are replaced by the actual text
+    /// * `symbols`: a slice of symbols that have been parsed so far, possibly containing `$expr$` and/or `$block$`
+    ///   (or their typed forms, e.g. `$expr:int$`); `$ident$`, `$var$` and other literal markers are replaced by the actual text
     /// * `look_ahead`: a string slice containing the next symbol that is about to be read
     /// * `state`: a [`Dynamic`] value that contains a user-defined state
     ///
@@ -389,6 +498,7 @@ impl Engine {
                 parse: Box::new(parse),
                 func: Box::new(func),
                 scope_may_be_changed,
+                symbols: Vec::new(),
             }
             .into(),
         );
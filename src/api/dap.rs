@@ -0,0 +1,647 @@
+//! Module that defines a [Debug Adapter Protocol](https://microsoft.github.io/debug-adapter-protocol/)
+//! server on top of the debugging interface.
+#![cfg(feature = "dap")]
+#![cfg(not(feature = "no_std"))]
+
+use crate::ast::ASTNode;
+use crate::debugger::{BreakPoint, DebuggerCommand, DebuggerEvent};
+use crate::func::{locked_write, Locked, SendSync};
+use crate::{Dynamic, Engine, EvalContext, Position, RhaiResultOf, Shared, ERR};
+use serde_json::{json, Map, Value};
+use std::convert::TryFrom;
+use std::io::{self, BufRead, Write};
+
+/// The only thread reported to the client.
+const THREAD_ID: i64 = 1;
+/// Variables reference of the local variables of the current frame.
+const LOCALS_REF: i64 = 1;
+/// Variables reference of the first expandable value (e.g. an array or object map).
+const FIRST_VALUE_REF: i64 = 2;
+
+/// A transport carrying [Debug Adapter Protocol](https://microsoft.github.io/debug-adapter-protocol/)
+/// messages between the [`Engine`] and a client (e.g. an IDE).
+///
+/// Exported under the `dap` feature only.
+pub trait DapTransport: SendSync {
+    /// Read the next message (a JSON string).
+    ///
+    /// Returns [`None`] if the client has disconnected.
+    fn read_message(&mut self) -> io::Result<Option<String>>;
+    /// Write a message (a JSON string).
+    fn write_message(&mut self, message: &str) -> io::Result<()>;
+}
+
+/// A [`DapTransport`] over a pair of byte streams (e.g. standard input/output or a TCP
+/// connection), with messages framed by `Content-Length` headers.
+///
+/// Exported under the `dap` feature only.
+#[derive(Debug)]
+pub struct DapStream<R: BufRead, W: Write> {
+    /// Stream of incoming messages.
+    reader: R,
+    /// Stream of outgoing messages.
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> DapStream<R, W> {
+    /// Create a new [`DapStream`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+}
+
+impl<R: BufRead + SendSync, W: Write + SendSync> DapTransport for DapStream<R, W> {
+    fn read_message(&mut self) -> io::Result<Option<String>> {
+        let mut content_length = None;
+
+        let len = loop {
+            let mut line = String::new();
+
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+
+            match line.trim_end().split_once(':') {
+                Some((name, value)) if name.trim().eq_ignore_ascii_case("Content-Length") => {
+                    let len = value
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                    content_length = Some(len);
+                }
+                Some(..) => (),
+                // A blank line terminates the headers
+                None if line.trim_end().is_empty() => {
+                    if let Some(len) = content_length {
+                        break len;
+                    }
+                }
+                None => return Err(io::Error::new(io::ErrorKind::InvalidData, line)),
+            }
+        };
+
+        let mut buf = vec![0; len];
+        self.reader.read_exact(&mut buf)?;
+
+        String::from_utf8(buf)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+    fn write_message(&mut self, message: &str) -> io::Result<()> {
+        write!(
+            self.writer,
+            "Content-Length: {}\r\n\r\n{message}",
+            message.len()
+        )?;
+        self.writer.flush()
+    }
+}
+
+/// State of a debugging session with a client.
+struct DapSession {
+    /// Transport to the client.
+    transport: Box<dyn DapTransport>,
+    /// Sequence number of the next outgoing message.
+    seq: i64,
+    /// Has the configuration been completed by the client?
+    configured: bool,
+    /// Has the client requested to stop at the beginning of the script?
+    stop_on_entry: bool,
+    /// Has the session ended?
+    ended: bool,
+    /// The last command given by the client.
+    command: DebuggerCommand,
+    /// Values that can be expanded by the client while stopped, indexed by variables
+    /// reference minus [`FIRST_VALUE_REF`].
+    values: Vec<Dynamic>,
+}
+
+impl DapSession {
+    /// Create a new [`DapSession`].
+    fn new(transport: Box<dyn DapTransport>) -> Self {
+        Self {
+            transport,
+            seq: 1,
+            configured: false,
+            stop_on_entry: false,
+            ended: false,
+            command: DebuggerCommand::Continue,
+            values: Vec::new(),
+        }
+    }
+
+    /// Send a message to the client.
+    fn send(&mut self, mut message: Value) -> RhaiResultOf<()> {
+        message["seq"] = self.seq.into();
+        self.seq += 1;
+
+        self.transport
+            .write_message(&message.to_string())
+            .map_err(|err| ERR::ErrorSystem("DAP transport".into(), Box::new(err)).into())
+    }
+    /// Receive a request from the client.
+    ///
+    /// Returns [`None`] if the client has disconnected.
+    fn recv(&mut self) -> RhaiResultOf<Option<Value>> {
+        let message = self
+            .transport
+            .read_message()
+            .map_err(|err| ERR::ErrorSystem("DAP transport".into(), Box::new(err)))?;
+
+        message
+            .map(|m| {
+                serde_json::from_str(&m)
+                    .map_err(|err| ERR::ErrorSystem("DAP message".into(), Box::new(err)).into())
+            })
+            .transpose()
+    }
+    /// Send a successful response to a request.
+    fn respond(&mut self, request: &Value, body: Value) -> RhaiResultOf<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": true,
+            "body": body,
+        }))
+    }
+    /// Send a failed response to a request.
+    fn respond_error(&mut self, request: &Value, message: impl Into<String>) -> RhaiResultOf<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": false,
+            "message": message.into(),
+        }))
+    }
+    /// Send an event.
+    fn event(&mut self, event: &str, body: Value) -> RhaiResultOf<()> {
+        self.send(json!({ "type": "event", "event": event, "body": body }))
+    }
+
+    /// Handle a debugger event.
+    fn on_debugger(
+        &mut self,
+        mut context: EvalContext,
+        event: DebuggerEvent,
+        _node: ASTNode,
+        src: Option<&str>,
+        pos: Position,
+    ) -> RhaiResultOf<DebuggerCommand> {
+        if self.ended {
+            return Ok(DebuggerCommand::Continue);
+        }
+
+        let reason = match event {
+            DebuggerEvent::Start if self.configured && self.stop_on_entry => "entry",
+            DebuggerEvent::Start if self.configured => return Ok(DebuggerCommand::Continue),
+            // Wait for the client to finish configuration
+            DebuggerEvent::Start => return self.serve(&mut context, src, pos),
+            DebuggerEvent::End => {
+                self.ended = true;
+                self.event("terminated", json!({}))?;
+                self.event("exited", json!({ "exitCode": 0 }))?;
+                return Ok(DebuggerCommand::Continue);
+            }
+            // Line break-points only stop at statements, not at every expression on the line
+            #[cfg(not(feature = "no_position"))]
+            DebuggerEvent::BreakPoint(n) if _node.is_expr() && is_line_break_point(&context, n) => {
+                return Ok(self.command)
+            }
+            DebuggerEvent::BreakPoint(..) => "breakpoint",
+            _ => "step",
+        };
+
+        self.stopped(reason)?;
+        self.serve(&mut context, src, pos)
+    }
+    /// Notify the client that execution has stopped.
+    fn stopped(&mut self, reason: &str) -> RhaiResultOf<()> {
+        self.values.clear();
+
+        self.event(
+            "stopped",
+            json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        )
+    }
+    /// Serve requests from the client until it resumes execution.
+    fn serve(
+        &mut self,
+        context: &mut EvalContext,
+        src: Option<&str>,
+        pos: Position,
+    ) -> RhaiResultOf<DebuggerCommand> {
+        loop {
+            let request = match self.recv()? {
+                Some(request) => request,
+                None => {
+                    self.ended = true;
+                    return Err(ERR::ErrorTerminated("DAP client disconnected".into(), pos).into());
+                }
+            };
+
+            if let Some(command) = self.handle(context, &request, src, pos)? {
+                self.command = command;
+                return Ok(command);
+            }
+        }
+    }
+    /// Handle a request from the client.
+    ///
+    /// Returns [`Some`] with the command to run if execution should resume.
+    fn handle(
+        &mut self,
+        context: &mut EvalContext,
+        request: &Value,
+        src: Option<&str>,
+        pos: Position,
+    ) -> RhaiResultOf<Option<DebuggerCommand>> {
+        let args = &request["arguments"];
+
+        let command = match request["command"].as_str().unwrap_or("") {
+            "initialize" => {
+                self.respond(
+                    request,
+                    json!({
+                        "supportsConfigurationDoneRequest": true,
+                        "supportsConditionalBreakpoints": true,
+                        "supportsFunctionBreakpoints": true,
+                        "supportsEvaluateForHovers": true,
+                    }),
+                )?;
+                self.event("initialized", json!({}))?;
+                return Ok(None);
+            }
+            "launch" | "attach" => {
+                self.stop_on_entry = args["stopOnEntry"].as_bool().unwrap_or(false);
+                self.respond(request, json!({}))?;
+                return Ok(None);
+            }
+            "configurationDone" => {
+                self.configured = true;
+                self.respond(request, json!({}))?;
+
+                if !self.stop_on_entry {
+                    return Ok(Some(DebuggerCommand::Continue));
+                }
+                self.stopped("entry")?;
+                return Ok(None);
+            }
+            "setBreakpoints" => {
+                let body = Self::set_break_points(context, args);
+                self.respond(request, body)?;
+                return Ok(None);
+            }
+            "setFunctionBreakpoints" => {
+                let body = Self::set_function_break_points(context, args);
+                self.respond(request, body)?;
+                return Ok(None);
+            }
+            "threads" => {
+                self.respond(
+                    request,
+                    json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }),
+                )?;
+                return Ok(None);
+            }
+            "stackTrace" => {
+                let frames = Self::stack_frames(context, src, pos);
+                let total = frames.len();
+                self.respond(
+                    request,
+                    json!({ "stackFrames": frames, "totalFrames": total }),
+                )?;
+                return Ok(None);
+            }
+            "scopes" => {
+                // Only the variables of the current frame are available
+                let scopes = if args["frameId"].as_i64().unwrap_or(0) == 0 {
+                    json!([{ "name": "Locals", "variablesReference": LOCALS_REF, "expensive": false }])
+                } else {
+                    json!([])
+                };
+                self.respond(request, json!({ "scopes": scopes }))?;
+                return Ok(None);
+            }
+            "variables" => {
+                let variables = self.variables(context, args["variablesReference"].as_i64());
+                self.respond(request, json!({ "variables": variables }))?;
+                return Ok(None);
+            }
+            "evaluate" => {
+                let expr = args["expression"].as_str().unwrap_or("");
+
+                match context.dbg_eval_expr(expr) {
+                    Ok(value) => {
+                        let value = context.engine().redact_value(None, &value);
+                        let body = json!({
+                            "result": format!("{value:?}"),
+                            "type": context.engine().map_type_name(value.type_name()),
+                            "variablesReference": self.add_value(&value),
+                        });
+                        self.respond(request, body)?;
+                    }
+                    Err(err) => self.respond_error(request, err.to_string())?,
+                }
+                return Ok(None);
+            }
+            "pause" => {
+                // Already stopped
+                self.respond(request, json!({}))?;
+                return Ok(None);
+            }
+            "disconnect" | "terminate" => {
+                self.ended = true;
+                self.respond(request, json!({}))?;
+                return Err(ERR::ErrorTerminated("DAP client disconnected".into(), pos).into());
+            }
+            "continue" => DebuggerCommand::Continue,
+            "next" => DebuggerCommand::Next,
+            "stepIn" => DebuggerCommand::StepInto,
+            "stepOut" => DebuggerCommand::StepOut,
+            cmd => {
+                self.respond_error(request, format!("Unsupported request: {cmd}"))?;
+                return Ok(None);
+            }
+        };
+
+        self.respond(request, json!({ "allThreadsContinued": true }))?;
+        Ok(Some(command))
+    }
+
+    /// Replace all break-points in a source file.
+    fn set_break_points(context: &mut EvalContext, args: &Value) -> Value {
+        let _source = args["source"]["path"]
+            .as_str()
+            .or_else(|| args["source"]["name"].as_str());
+        let break_points = context
+            .global_runtime_state_mut()
+            .debugger_mut()
+            .break_points_mut();
+
+        #[cfg(not(feature = "no_position"))]
+        break_points.retain(|bp| match inner_break_point(bp) {
+            BreakPoint::AtPosition { source, .. } => source.as_deref() != _source,
+            _ => true,
+        });
+
+        let requested = args["breakpoints"]
+            .as_array()
+            .map_or(&[][..], Vec::as_slice);
+
+        let verified = requested
+            .iter()
+            .map(|bp| {
+                let line = bp["line"].as_u64().unwrap_or(0);
+
+                #[cfg(not(feature = "no_position"))]
+                if let Ok(line) = u16::try_from(line) {
+                    let break_point = BreakPoint::AtPosition {
+                        source: _source.map(Into::into),
+                        pos: Position::new(line, 0),
+                        enabled: true,
+                    };
+                    break_points.push(with_condition(break_point, &bp["condition"]));
+
+                    return json!({ "verified": true, "line": line });
+                }
+
+                json!({ "verified": false, "line": line })
+            })
+            .collect::<Vec<_>>();
+
+        json!({ "breakpoints": verified })
+    }
+    /// Replace all function break-points.
+    fn set_function_break_points(context: &mut EvalContext, args: &Value) -> Value {
+        let break_points = context
+            .global_runtime_state_mut()
+            .debugger_mut()
+            .break_points_mut();
+
+        break_points
+            .retain(|bp| !matches!(inner_break_point(bp), BreakPoint::AtFunctionName { .. }));
+
+        let requested = args["breakpoints"]
+            .as_array()
+            .map_or(&[][..], Vec::as_slice);
+
+        let verified = requested
+            .iter()
+            .map(|bp| match bp["name"].as_str() {
+                Some(name) => {
+                    let break_point = BreakPoint::AtFunctionName {
+                        name: name.into(),
+                        enabled: true,
+                    };
+                    break_points.push(with_condition(break_point, &bp["condition"]));
+                    json!({ "verified": true })
+                }
+                None => json!({ "verified": false }),
+            })
+            .collect::<Vec<_>>();
+
+        json!({ "breakpoints": verified })
+    }
+
+    /// Get the stack frames, from the current one outwards.
+    fn stack_frames(context: &EvalContext, src: Option<&str>, pos: Position) -> Vec<Value> {
+        let mut frames = Vec::new();
+        let mut location = (src.map(Into::into), pos);
+
+        #[cfg(not(feature = "no_function"))]
+        for frame in context
+            .global_runtime_state()
+            .debugger()
+            .call_stack()
+            .iter()
+            .rev()
+        {
+            frames.push(stack_frame(frames.len(), &frame.fn_name, &location));
+            location = (frame.source.as_ref().map(|s| s.to_string()), frame.pos);
+        }
+        #[cfg(feature = "no_function")]
+        let _ = context;
+
+        frames.push(stack_frame(frames.len(), "<script>", &location));
+        frames
+    }
+    /// Get the variables under a variables reference.
+    fn variables(&mut self, context: &EvalContext, reference: Option<i64>) -> Vec<Value> {
+        match reference {
+            Some(LOCALS_REF) => {
+                let mut variables = context
+                    .scope()
+                    .clone_visible()
+                    .iter()
+                    .map(|(name, .., value)| self.variable(context, name, &value))
+                    .collect::<Vec<_>>();
+
+                if let Some(this_ptr) = context.this_ptr() {
+                    variables.push(self.variable(context, "this", this_ptr));
+                }
+
+                variables
+            }
+            Some(n) if n >= FIRST_VALUE_REF => {
+                let index = usize::try_from(n - FIRST_VALUE_REF).unwrap_or(usize::MAX);
+                let _value = match self.values.get(index) {
+                    Some(value) => value.clone(),
+                    None => return Vec::new(),
+                };
+                let mut _variables = Vec::new();
+
+                #[cfg(not(feature = "no_index"))]
+                if let Some(array) = _value.read_lock::<crate::Array>() {
+                    for (i, item) in array.iter().enumerate() {
+                        _variables.push(self.variable(context, &format!("[{i}]"), item));
+                    }
+                }
+                #[cfg(not(feature = "no_object"))]
+                if let Some(map) = _value.read_lock::<crate::Map>() {
+                    for (key, item) in map.iter() {
+                        _variables.push(self.variable(context, key, item));
+                    }
+                }
+
+                _variables
+            }
+            _ => Vec::new(),
+        }
+    }
+    /// Describe a variable to the client.
+    fn variable(&mut self, context: &EvalContext, name: &str, value: &Dynamic) -> Value {
+        let value = &context.engine().redact_value(Some(name), value);
+
+        json!({
+            "name": name,
+            "value": format!("{value:?}"),
+            "type": context.engine().map_type_name(value.type_name()),
+            "variablesReference": self.add_value(value),
+        })
+    }
+    /// Keep a value that can be expanded by the client (e.g. an array or object map) and return
+    /// its variables reference, or zero if the value cannot be expanded.
+    fn add_value(&mut self, value: &Dynamic) -> i64 {
+        let value = value.flatten_clone();
+
+        let expandable = match () {
+            #[cfg(not(feature = "no_index"))]
+            _ if value.is_array() => value
+                .read_lock::<crate::Array>()
+                .map_or(false, |a| !a.is_empty()),
+            #[cfg(not(feature = "no_object"))]
+            _ if value.is_map() => value
+                .read_lock::<crate::Map>()
+                .map_or(false, |m| !m.is_empty()),
+            _ => false,
+        };
+
+        if !expandable {
+            return 0;
+        }
+
+        self.values.push(value);
+        FIRST_VALUE_REF + self.values.len() as i64 - 1
+    }
+}
+
+/// Get the break-point underneath any [condition][BreakPoint::Conditional].
+fn inner_break_point(bp: &BreakPoint) -> &BreakPoint {
+    match bp {
+        BreakPoint::Conditional { break_point, .. } => inner_break_point(break_point),
+        _ => bp,
+    }
+}
+
+/// Is a break-point (ignoring any condition) a line break-point?
+#[cfg(not(feature = "no_position"))]
+fn is_line_break_point(context: &EvalContext, index: usize) -> bool {
+    let break_points = context.global_runtime_state().debugger().break_points();
+
+    matches!(
+        break_points.get(index).map(inner_break_point),
+        Some(BreakPoint::AtPosition { .. })
+    )
+}
+
+/// Wrap a break-point with a condition, if any.
+fn with_condition(break_point: BreakPoint, condition: &Value) -> BreakPoint {
+    match condition.as_str().map(str::trim) {
+        Some(condition) if !condition.is_empty() => BreakPoint::Conditional {
+            break_point: break_point.into(),
+            condition: condition.into(),
+        },
+        _ => break_point,
+    }
+}
+
+/// Describe a stack frame to the client.
+fn stack_frame(id: usize, name: &str, (source, pos): &(Option<String>, Position)) -> Value {
+    let mut frame = Map::new();
+
+    frame.insert("id".into(), id.into());
+    frame.insert("name".into(), name.into());
+    frame.insert("line".into(), pos.line().unwrap_or(0).into());
+    frame.insert("column".into(), pos.position().unwrap_or(0).into());
+
+    if let Some(source) = source {
+        frame.insert("source".into(), json!({ "name": source, "path": source }));
+    }
+
+    frame.into()
+}
+
+impl Engine {
+    /// _(debugging)_ Register a [Debug Adapter Protocol](https://microsoft.github.io/debug-adapter-protocol/)
+    /// server as the debugger, so that scripts can be debugged from a client such as VS Code.
+    /// Exported under the `dap` feature only.
+    ///
+    /// At the start of the first evaluation run, the server waits for the client to finish
+    /// configuration (i.e. until the `configurationDone` request). Execution stops at the
+    /// beginning of the script if the `launch` or `attach` request sets `stopOnEntry`.
+    ///
+    /// The following requests are supported: `initialize`, `launch`, `attach`,
+    /// `configurationDone`, `setBreakpoints` (with conditions), `setFunctionBreakpoints`,
+    /// `threads`, `stackTrace`, `scopes`, `variables`, `evaluate`, `continue`, `next`,
+    /// `stepIn`, `stepOut`, `pause`, `disconnect` and `terminate`.
+    ///
+    /// Requests are only served while execution is stopped. Break-points are matched against
+    /// the source of the script, so the [`AST`][crate::AST] should have its source set to the
+    /// path used by the client.
+    ///
+    /// Evaluation terminates with [`ErrorTerminated`][crate::EvalAltResult::ErrorTerminated]
+    /// when the client disconnects.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rhai::Engine;
+    /// use rhai::debugger::DapStream;
+    /// use std::io::{stdin, stdout, BufReader};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_dap_server(DapStream::new(BufReader::new(stdin()), stdout()));
+    ///
+    /// let mut ast = engine.compile("let x = 42; print(x);").unwrap();
+    /// ast.set_source("script.rhai");
+    ///
+    /// engine.run_ast(&ast).unwrap();
+    /// ```
+    #[allow(deprecated)]
+    pub fn register_dap_server(&mut self, transport: impl DapTransport + 'static) -> &mut Self {
+        let session = Shared::new(Locked::new(DapSession::new(Box::new(transport))));
+
+        self.register_debugger(
+            |_, debugger| debugger,
+            move |context, event, node, src, pos| match locked_write(&session) {
+                Some(mut session) => session.on_debugger(context, event, node, src, pos),
+                None => Ok(DebuggerCommand::Continue),
+            },
+        )
+    }
+}
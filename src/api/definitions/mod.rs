@@ -207,6 +207,13 @@ impl Definitions<'_> {
 
         def_file += "\n";
 
+        #[cfg(not(feature = "no_custom_syntax"))]
+        if self.has_custom_syntax() {
+            def_file += "\n";
+            def_file += &self.custom_syntax_impl(config);
+            def_file += "\n";
+        }
+
         def_file
     }
 
@@ -245,6 +252,21 @@ impl Definitions<'_> {
                 self.scope_items_impl(config),
             )
         }))
+        .chain(
+            #[cfg(not(feature = "no_custom_syntax"))]
+            {
+                self.has_custom_syntax().then(|| {
+                    (
+                        "__custom_syntax__.d.rhai".to_string(),
+                        self.custom_syntax_impl(config),
+                    )
+                })
+            },
+            #[cfg(feature = "no_custom_syntax")]
+            {
+                None
+            },
+        )
         .chain(
             #[cfg(not(feature = "no_module"))]
             {
@@ -350,6 +372,73 @@ impl Definitions<'_> {
         s
     }
 
+    /// Does the [`Engine`] have custom operators or custom syntax registered?
+    #[cfg(not(feature = "no_custom_syntax"))]
+    #[must_use]
+    fn has_custom_syntax(&self) -> bool {
+        !self.engine.custom_syntax.is_empty()
+            || self.engine.custom_keywords.values().any(Option::is_some)
+    }
+
+    /// Return definitions for all custom operators (with their precedence) and all custom syntax
+    /// (by their leading keywords) registered with the [`Engine`].
+    ///
+    /// Not available under `no_custom_syntax`.
+    #[cfg(not(feature = "no_custom_syntax"))]
+    #[inline(always)]
+    #[must_use]
+    pub fn custom_syntax(&self) -> String {
+        self.custom_syntax_impl(self.config)
+    }
+
+    /// Return definitions for all custom operators and all custom syntax.
+    #[cfg(not(feature = "no_custom_syntax"))]
+    #[must_use]
+    fn custom_syntax_impl(&self, config: DefinitionsConfig) -> String {
+        use std::fmt::Write;
+
+        let mut s = if config.write_headers {
+            String::from("module static;\n\n")
+        } else {
+            String::new()
+        };
+
+        let mut first = true;
+
+        for (name, precedence) in &self.engine.custom_keywords {
+            if let Some(precedence) = precedence {
+                if !first {
+                    s += "\n\n";
+                }
+                first = false;
+
+                writeln!(s, "/// Custom operator with precedence {precedence}.").unwrap();
+                write!(s, "op {name}(?, ?) -> ?;").unwrap();
+            }
+        }
+
+        for (key, syntax) in &self.engine.custom_syntax {
+            if !first {
+                s += "\n\n";
+            }
+            first = false;
+
+            if syntax.symbols.is_empty() {
+                writeln!(s, "/// Custom syntax.").unwrap();
+            } else {
+                let symbols = syntax
+                    .symbols
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>();
+                writeln!(s, "/// Custom syntax: `{}`", symbols.join(" ")).unwrap();
+            }
+            write!(s, "syntax {key};").unwrap();
+        }
+
+        s
+    }
+
     /// Return a (module name, definitions) pair for each registered static [module][Module].
     ///
     /// Not available under `no_module`.
@@ -3,14 +3,80 @@
 use crate::eval::{Caches, GlobalRuntimeState};
 use crate::parser::ParseState;
 use crate::types::dynamic::Variant;
-use crate::{Dynamic, Engine, Position, RhaiResult, RhaiResultOf, Scope, AST, ERR};
+use crate::{
+    Dynamic, Engine, Identifier, ImmutableString, Position, RhaiResult, RhaiResultOf, Scope, AST,
+    ERR,
+};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 use std::{
     any::{type_name, TypeId},
+    hash::{Hash, Hasher},
     mem,
 };
 
+/// Full output of an evaluation run via [`Engine::eval_full`] and friends.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct EvalOutput {
+    /// Result value of the evaluation, or the error that terminated it.
+    pub result: RhaiResult,
+    /// Text printed via `print` during the evaluation, in order.
+    pub prints: Vec<ImmutableString>,
+    /// Messages emitted via `debug` during the evaluation, in order, together with their positions.
+    pub debug_messages: Vec<(ImmutableString, Position)>,
+    /// Number of operations performed.
+    pub num_operations: u64,
+    /// Number of [modules][crate::Module] loaded.
+    ///
+    /// Not available under `no_module`.
+    #[cfg(not(feature = "no_module"))]
+    pub num_modules_loaded: usize,
+    /// Names of variables in the [`Scope`] that were added or modified, in [`Scope`] order.
+    ///
+    /// Values that cannot be hashed (e.g. custom types and timestamps) are only detected as
+    /// modified when their types change.
+    pub modified_variables: Vec<Identifier>,
+}
+
+impl EvalOutput {
+    /// Create an [`EvalOutput`] for an evaluation that never started.
+    #[inline]
+    #[must_use]
+    fn from_err(err: crate::RhaiError) -> Self {
+        Self {
+            result: Err(err),
+            prints: Vec::new(),
+            debug_messages: Vec::new(),
+            num_operations: 0,
+            #[cfg(not(feature = "no_module"))]
+            num_modules_loaded: 0,
+            modified_variables: Vec::new(),
+        }
+    }
+}
+
+/// Output captured during an evaluation run for [`EvalOutput`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CapturedOutput {
+    /// Text printed via `print`.
+    pub prints: Vec<ImmutableString>,
+    /// Messages emitted via `debug`, together with their positions.
+    pub debug_messages: Vec<(ImmutableString, Position)>,
+}
+
+/// Fingerprint of a variable's value, used to detect modifications.
+#[must_use]
+fn fingerprint(value: &Dynamic) -> (&'static str, Option<u64>) {
+    let hash = value.is_hashable().then(|| {
+        let hasher = &mut crate::func::get_hasher();
+        value.hash(hasher);
+        hasher.finish()
+    });
+
+    (value.type_name(), hash)
+}
+
 impl Engine {
     /// Evaluate a string as a script, returning the result value or an error.
     ///
@@ -212,6 +278,118 @@ impl Engine {
             .into()
         })
     }
+    /// Evaluate a string as a script, returning the result value together with everything the
+    /// evaluation did (see [`EvalOutput`]).
+    ///
+    /// Text printed via `print` and `debug` is captured in the output, and also passed on to the
+    /// callbacks registered via [`on_print`][Engine::on_print] and [`on_debug`][Engine::on_debug].
+    ///
+    /// If the evaluation fails, the error is returned in [`result`][EvalOutput::result] together
+    /// with the output captured and the statistics up to that point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let output = engine.eval_full(r#"print("hello"); debug("careful!"); 40 + 2"#);
+    ///
+    /// assert_eq!(output.result?.as_int().unwrap(), 42);
+    /// assert_eq!(output.prints, ["hello"]);
+    /// assert_eq!(output.debug_messages[0].0, r#""careful!""#);
+    /// assert!(output.num_operations > 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn eval_full(&self, script: &str) -> EvalOutput {
+        self.eval_full_with_scope(&mut Scope::new(), script)
+    }
+    /// Evaluate a string as a script with own scope, returning the result value together with
+    /// everything the evaluation did (see [`EvalOutput`]).
+    ///
+    /// ## Constants Propagation
+    ///
+    /// If not [`OptimizationLevel::None`][crate::OptimizationLevel::None], constants defined within
+    /// the scope are propagated throughout the script _including_ functions. This allows functions
+    /// to be optimized based on dynamic global constants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.push("x", 40_i64);
+    /// scope.push("y", 0_i64);
+    ///
+    /// let output = engine.eval_full_with_scope(&mut scope, "x += 2; let z = x; x");
+    ///
+    /// assert_eq!(output.modified_variables, ["x", "z"]);
+    /// assert_eq!(output.result?.as_int().unwrap(), 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn eval_full_with_scope(&self, scope: &mut Scope, script: &str) -> EvalOutput {
+        match self.compile_scripts_with_scope_raw(
+            Some(scope),
+            [script],
+            #[cfg(not(feature = "no_optimize"))]
+            self.optimization_level,
+        ) {
+            Ok(ast) => self.eval_ast_full_with_scope(scope, &ast),
+            Err(err) => EvalOutput::from_err(err.into()),
+        }
+    }
+    /// Evaluate an [`AST`] with own scope, returning the result value together with everything
+    /// the evaluation did (see [`EvalOutput`]).
+    #[must_use]
+    pub fn eval_ast_full_with_scope(&self, scope: &mut Scope, ast: &AST) -> EvalOutput {
+        let global = &mut self.new_global_runtime_state();
+        let caches = &mut Caches::new();
+
+        let captured = crate::Shared::new(crate::Locked::new(CapturedOutput::default()));
+        global.output = Some(captured.clone());
+
+        let orig_scope_len = scope.len();
+        let orig_values = scope
+            .iter_inner()
+            .map(|(.., v)| fingerprint(v))
+            .collect::<Vec<_>>();
+
+        let result = self.eval_ast_with_scope_raw(global, caches, scope, ast);
+
+        global.output = None;
+
+        // Copies of the state may still be held (e.g. by closures), so take the captured output
+        let captured = crate::func::locked_write(&captured)
+            .map(|mut captured| mem::take(&mut *captured))
+            .unwrap_or_default();
+
+        EvalOutput {
+            result,
+            prints: captured.prints,
+            debug_messages: captured.debug_messages,
+            num_operations: global.num_operations,
+            #[cfg(not(feature = "no_module"))]
+            num_modules_loaded: global.num_modules_loaded,
+            modified_variables: scope
+                .iter_inner()
+                .enumerate()
+                .filter(|&(i, (.., v))| i >= orig_scope_len || fingerprint(v) != orig_values[i])
+                .map(|(.., (name, ..))| name.as_str().into())
+                .collect(),
+        }
+    }
     /// Evaluate an [`AST`] with own scope, returning the result value or an error.
     #[inline]
     pub(crate) fn eval_ast_with_scope_raw(
@@ -223,9 +401,17 @@ impl Engine {
     ) -> RhaiResult {
         let orig_source = mem::replace(&mut global.source, ast.source_raw().cloned());
 
+        if let Some(state) = scope.shared_host_state() {
+            global.host_state = Some(state.clone());
+        }
+
         #[cfg(not(feature = "no_function"))]
         let orig_lib_len = global.lib.len();
 
+        #[cfg(not(feature = "no_function"))]
+        if let Some(ref prelude) = self.prelude {
+            global.lib.push(prelude.clone());
+        }
         #[cfg(not(feature = "no_function"))]
         global.lib.push(ast.shared_lib().clone());
 
@@ -63,7 +63,7 @@ impl Engine {
             + SendSync
             + 'static,
     ) -> &mut Self {
-        self.resolve_var = Some(Box::new(callback));
+        self.resolve_var = Some(crate::Shared::new(callback));
         self
     }
     /// Provide a callback that will be invoked before the definition of each variable .
@@ -123,7 +123,7 @@ impl Engine {
         &mut self,
         callback: impl Fn(bool, VarDefInfo, EvalContext) -> RhaiResultOf<bool> + SendSync + 'static,
     ) -> &mut Self {
-        self.def_var_filter = Some(Box::new(callback));
+        self.def_var_filter = Some(crate::Shared::new(callback));
         self
     }
     /// _(internals)_ Register a callback that will be invoked during parsing to remap certain tokens.
@@ -189,21 +189,152 @@ impl Engine {
             + SendSync
             + 'static,
     ) -> &mut Self {
-        self.token_mapper = Some(Box::new(callback));
+        self.token_mapper = Some(crate::Shared::new(callback));
+        self
+    }
+    /// Register a callback for mapping type names for display, e.g. in `type_of` and in error
+    /// messages.
+    ///
+    /// The callback is invoked with a type name (usually the full Rust type path) and may return
+    /// another type name to use in its place. The returned name is mapped again (e.g. via type
+    /// aliases or names registered via [`register_type_with_name`][Engine::register_type_with_name]),
+    /// so a callback that strips generic wrappers resolves to the display name of the inner type.
+    ///
+    /// # Callback Function Signature
+    ///
+    /// `Fn(name: &str) -> Option<&str>`
+    ///
+    /// ## Return value
+    ///
+    /// * `None`: keep the type name unchanged.
+    /// * `Some(&str)`: replace the type name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use std::sync::{Arc, RwLock};
+    /// use rhai::Engine;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Rule;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine
+    ///     .register_type_with_name::<Rule>("Rule")
+    ///     .register_type::<Arc<RwLock<Rule>>>()
+    ///     .register_fn("new_rule", || Arc::new(RwLock::new(Rule)));
+    ///
+    /// // Display generic wrappers (e.g. `Arc<RwLock<Rule>>`) as the innermost type.
+    /// engine.on_map_type_name(|name| {
+    ///     name.rsplit_once('<').map(|(_, inner)| inner.trim_end_matches('>'))
+    /// });
+    ///
+    /// assert_eq!(engine.eval::<String>("type_of(new_rule())")?, "Rule");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn on_map_type_name(
+        &mut self,
+        callback: impl Fn(&str) -> Option<&str> + SendSync + 'static,
+    ) -> &mut Self {
+        self.type_name_mapper = Some(crate::Shared::new(callback));
+        self
+    }
+    /// Provide a callback that runs parallel tasks spawned by scripts via the `spawn` function.
+    ///
+    /// Only available under `parallel`. Not available under `no_std`.
+    ///
+    /// The callback usually hands the task over to a thread pool.
+    /// If no callback is provided, tasks are run immediately when spawned.
+    ///
+    /// # Callback Function Signature
+    ///
+    /// `Fn(task: PendingTask)`
+    ///
+    /// where `task` is the [`PendingTask`][crate::PendingTask] to run via
+    /// [`PendingTask::run`][crate::PendingTask::run].
+    ///
+    /// Every task must eventually be run; otherwise, scripts waiting for the task via `join`
+    /// block forever.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use std::sync::Arc;
+    /// use rhai::Engine;
+    ///
+    /// // Engine used by worker threads to run tasks
+    /// let worker = Arc::new(Engine::new());
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_task_executor(move |task| {
+    ///     let worker = worker.clone();
+    ///     std::thread::spawn(move || task.run(&worker));
+    /// });
+    ///
+    /// let result = engine.eval::<i64>(
+    ///     "
+    ///         fn sum(from, to) {
+    ///             let total = 0;
+    ///             for n in from..to { total += n; }
+    ///             total
+    ///         }
+    ///
+    ///         let tasks = [];
+    ///
+    ///         for n in 0..4 {
+    ///             tasks.push(spawn(Fn(\"sum\"), [n * 25, n * 25 + 25]));
+    ///         }
+    ///
+    ///         let total = 0;
+    ///         for task in tasks { total += task.join(); }
+    ///         total
+    ///     ",
+    /// )?;
+    ///
+    /// assert_eq!(result, 4950);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    #[cfg(not(feature = "no_std"))]
+    #[inline(always)]
+    pub fn set_task_executor(
+        &mut self,
+        callback: impl Fn(crate::PendingTask) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.task_executor = Some(crate::Shared::new(callback));
         self
     }
     /// Register a callback for script evaluation progress.
     ///
+    /// The callback is called at every operation with a [`ProgressInfo`][crate::ProgressInfo]
+    /// holding the number of operations performed so far, the current source and position, the
+    /// current function call depth and (unless under `no_time`) the time elapsed since the
+    /// evaluation run started.
+    ///
     /// Not available under `unchecked`.
     ///
     /// # Callback Function Signature
     ///
-    /// `Fn(counter: u64) -> Option<Dynamic>`
+    /// `Fn(info: &ProgressInfo) -> ProgressControl`
     ///
     /// ## Return value
     ///
-    /// * `None`: continue running the script.
-    /// * `Some(Dynamic)`: terminate the script with the specified exception value.
+    /// * [`ProgressControl::Continue`][crate::ProgressControl::Continue]: continue running the script.
+    /// * [`ProgressControl::Abort(Dynamic)`][crate::ProgressControl::Abort]: terminate the script
+    ///   with the specified exception value.
+    ///
+    /// An `Option<Dynamic>` converts into a [`ProgressControl`][crate::ProgressControl] via
+    /// `into()`, with `None` meaning to continue and `Some` to abort.
+    ///
+    /// The script is suspended for as long as the callback runs, so a host can pause evaluation
+    /// simply by blocking inside the callback (e.g. waiting on a channel) until it is resumed.
     ///
     /// # Example
     ///
@@ -211,21 +342,21 @@ impl Engine {
     /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
     /// # use std::sync::RwLock;
     /// # use std::sync::Arc;
-    /// use rhai::Engine;
+    /// use rhai::{Engine, ProgressControl};
     ///
     /// let result = Arc::new(RwLock::new(0_u64));
     /// let logger = result.clone();
     ///
     /// let mut engine = Engine::new();
     ///
-    /// engine.on_progress(move |ops| {
-    ///     if ops > 1000 {
-    ///         Some("Over 1,000 operations!".into())
-    ///     } else if ops % 123 == 0 {
-    ///         *logger.write().unwrap() = ops;
-    ///         None
+    /// engine.on_progress(move |info| {
+    ///     if info.operations > 1000 {
+    ///         ProgressControl::Abort("Over 1,000 operations!".into())
+    ///     } else if info.operations % 123 == 0 {
+    ///         *logger.write().unwrap() = info.operations;
+    ///         ProgressControl::Continue
     ///     } else {
-    ///         None
+    ///         ProgressControl::Continue
     ///     }
     /// });
     ///
@@ -241,9 +372,128 @@ impl Engine {
     #[inline(always)]
     pub fn on_progress(
         &mut self,
-        callback: impl Fn(u64) -> Option<Dynamic> + SendSync + 'static,
+        callback: impl Fn(&crate::ProgressInfo) -> crate::ProgressControl + SendSync + 'static,
+    ) -> &mut Self {
+        self.progress = Some(crate::Shared::new(callback));
+        self
+    }
+    /// Register a callback that warns when the number of operations performed reaches a
+    /// percentage of the [maximum number of operations][Engine::set_max_operations].
+    ///
+    /// The callback is called at most once per evaluation, so that script authors can be warned
+    /// that their scripts are close to being terminated with
+    /// [`ErrorTooManyOperations`][crate::EvalAltResult::ErrorTooManyOperations].
+    ///
+    /// It is never called if there is no maximum number of operations.
+    ///
+    /// Not available under `unchecked`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percent` is zero or larger than 100.
+    ///
+    /// # Callback Function Signature
+    ///
+    /// `Fn(num_operations: u64, max_operations: u64, pos: Position)`
+    ///
+    /// where:
+    /// * `num_operations`: number of operations performed so far
+    /// * `max_operations`: maximum number of operations allowed
+    /// * `pos`: location of the operation reaching the threshold
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # use std::sync::RwLock;
+    /// # use std::sync::Arc;
+    /// use rhai::Engine;
+    ///
+    /// let warnings = Arc::new(RwLock::new(Vec::new()));
+    /// let logger = warnings.clone();
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_max_operations(1000);
+    /// engine.on_operations_warning(80, move |ops, max, _| {
+    ///     logger.write().unwrap().push(format!("{ops} of {max} operations used"));
+    /// });
+    ///
+    /// engine.run("let s = 0; for x in 0..5000 { s += x; }").expect_err("should error");
+    ///
+    /// assert_eq!(*warnings.read().unwrap(), ["800 of 1000 operations used"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "unchecked"))]
+    #[inline]
+    pub fn on_operations_warning(
+        &mut self,
+        percent: u8,
+        callback: impl Fn(u64, u64, Position) + SendSync + 'static,
+    ) -> &mut Self {
+        assert!(
+            (1..=100).contains(&percent),
+            "operations warning threshold must be between 1 and 100 percent"
+        );
+        self.operations_warning = Some((percent, crate::Shared::new(callback)));
+        self
+    }
+    /// Register a callback that is called every `interval` operations, typically to yield to a
+    /// cooperative scheduler (e.g. on single-core embedded or RTOS targets under `no_std`) so that
+    /// long-running scripts do not starve other tasks.
+    ///
+    /// Unlike [`on_progress`][Engine::on_progress], the callback cannot terminate the script.
+    ///
+    /// Not available under `unchecked`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    ///
+    /// # Callback Function Signature
+    ///
+    /// `Fn(num_operations: u64)`
+    ///
+    /// where `num_operations` is the number of operations performed so far.
+    ///
+    /// The callback is first called when the operations count reaches `interval` (never at zero),
+    /// and then at every multiple of `interval`. The operations count restarts at zero for each
+    /// evaluation run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # use std::sync::atomic::{AtomicU64, Ordering};
+    /// # use std::sync::Arc;
+    /// use rhai::Engine;
+    ///
+    /// let ticks = Arc::new(AtomicU64::new(0));
+    /// let counter = ticks.clone();
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.on_tick(100, move |_| {
+    ///     // Yield to the scheduler here...
+    ///     counter.fetch_add(1, Ordering::Relaxed);
+    /// });
+    ///
+    /// engine.run("let s = 0; for x in 0..1000 { s += x; }")?;
+    ///
+    /// assert!(ticks.load(Ordering::Relaxed) >= 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "unchecked"))]
+    #[inline]
+    pub fn on_tick(
+        &mut self,
+        interval: u64,
+        callback: impl Fn(u64) + SendSync + 'static,
     ) -> &mut Self {
-        self.progress = Some(Box::new(callback));
+        let interval = std::num::NonZeroU64::new(interval).expect("tick interval must not be zero");
+        self.tick = Some((interval, crate::Shared::new(callback)));
         self
     }
     /// Override default action of `print` (print to stdout using [`println!`])
@@ -272,7 +522,7 @@ impl Engine {
     /// ```
     #[inline(always)]
     pub fn on_print(&mut self, callback: impl Fn(&str) + SendSync + 'static) -> &mut Self {
-        self.print = Some(Box::new(callback));
+        self.print = Some(crate::Shared::new(callback));
         self
     }
     /// Override default action of `debug` (print to stdout using [`println!`])
@@ -322,7 +572,7 @@ impl Engine {
         &mut self,
         callback: impl Fn(&str, Option<&str>, Position) + SendSync + 'static,
     ) -> &mut Self {
-        self.debug = Some(Box::new(callback));
+        self.debug = Some(crate::Shared::new(callback));
         self
     }
     /// _(internals)_ Register a callback for access to [`Map`][crate::Map] properties that do not exist.
@@ -401,7 +651,7 @@ impl Engine {
             + SendSync
             + 'static,
     ) -> &mut Self {
-        self.invalid_array_index = Some(Box::new(callback));
+        self.invalid_array_index = Some(crate::Shared::new(callback));
         self
     }
     /// _(internals)_ Register a callback for access to [`Map`][crate::Map] properties that do not exist.
@@ -475,7 +725,7 @@ impl Engine {
             + SendSync
             + 'static,
     ) -> &mut Self {
-        self.missing_map_property = Some(Box::new(callback));
+        self.missing_map_property = Some(crate::Shared::new(callback));
         self
     }
     /// _(debugging)_ Register a callback for debugging.
@@ -502,7 +752,7 @@ impl Engine {
             + SendSync
             + 'static,
     ) -> &mut Self {
-        self.debugger_interface = Some((Box::new(init), Box::new(callback)));
+        self.debugger_interface = Some((crate::Shared::new(init), crate::Shared::new(callback)));
         self
     }
 }
@@ -13,10 +13,8 @@ use std::{
 };
 
 impl Engine {
-    /// Read the contents of a file into a string.
-    fn read_file(path: impl AsRef<Path>) -> RhaiResultOf<String> {
-        let path = path.as_ref();
-
+    /// Read the contents of a file into a string, without removing the shebang line.
+    fn read_file_raw(path: &Path) -> RhaiResultOf<String> {
         let mut f = File::open(path).map_err(|err| {
             ERR::ErrorSystem(
                 format!("Cannot open script file '{}'", path.to_string_lossy()),
@@ -33,6 +31,10 @@ impl Engine {
             )
         })?;
 
+        Ok(contents)
+    }
+    /// Remove the shebang line (if any) from the contents of a script file.
+    fn strip_shebang(mut contents: String) -> String {
         if contents.starts_with("#!") {
             // Remove shebang
             match contents.find('\n') {
@@ -43,7 +45,32 @@ impl Engine {
             }
         };
 
-        Ok(contents)
+        contents
+    }
+    /// Read the contents of a file into a string.
+    fn read_file(path: impl AsRef<Path>) -> RhaiResultOf<String> {
+        Self::read_file_raw(path.as_ref()).map(Self::strip_shebang)
+    }
+    /// Read the contents of a script file into a string, verifying its signature if a
+    /// [`ScriptSigner`][crate::ScriptSigner] is set.
+    fn read_script_file(&self, path: impl AsRef<Path>) -> RhaiResultOf<String> {
+        #[cfg(feature = "signing")]
+        if self.script_signer.is_some() {
+            let path = path.as_ref();
+            let contents = Self::read_file_raw(path)?;
+
+            let mut sig_path = path.as_os_str().to_owned();
+            sig_path.push(".");
+            sig_path.push(crate::api::signing::SIGNATURE_EXTENSION);
+            let signature = std::fs::read_to_string(sig_path).ok();
+
+            let name = path.to_string_lossy();
+            self.verify_signature(&name, contents.as_bytes(), signature.as_deref(), true)?;
+
+            return Ok(Self::strip_shebang(contents));
+        }
+
+        Self::read_file(path)
     }
     /// Compile a script file into an [`AST`], which can be used later for evaluation.
     ///
@@ -107,7 +134,7 @@ impl Engine {
     /// ```
     #[inline]
     pub fn compile_file_with_scope(&self, scope: &Scope, path: PathBuf) -> RhaiResultOf<AST> {
-        Self::read_file(&path).and_then(|contents| {
+        self.read_script_file(&path).and_then(|contents| {
             let mut ast = self.compile_with_scope(scope, contents)?;
             ast.set_source(path.to_string_lossy().as_ref());
             Ok(ast)
@@ -132,7 +159,8 @@ impl Engine {
     /// ```
     #[inline]
     pub fn eval_file<T: Variant + Clone>(&self, path: PathBuf) -> RhaiResultOf<T> {
-        Self::read_file(path).and_then(|contents| self.eval::<T>(&contents))
+        self.read_script_file(path)
+            .and_then(|contents| self.eval::<T>(&contents))
     }
     /// Evaluate a script file with own scope, returning the result value or an error.
     ///
@@ -168,7 +196,8 @@ impl Engine {
         scope: &mut Scope,
         path: PathBuf,
     ) -> RhaiResultOf<T> {
-        Self::read_file(path).and_then(|contents| self.eval_with_scope(scope, &contents))
+        self.read_script_file(path)
+            .and_then(|contents| self.eval_with_scope(scope, &contents))
     }
     /// Evaluate a file.
     ///
@@ -189,7 +218,8 @@ impl Engine {
     /// ```
     #[inline]
     pub fn run_file(&self, path: PathBuf) -> RhaiResultOf<()> {
-        Self::read_file(path).and_then(|contents| self.run(&contents))
+        self.read_script_file(path)
+            .and_then(|contents| self.run(&contents))
     }
     /// Evaluate a file with own scope.
     ///
@@ -221,7 +251,8 @@ impl Engine {
     /// ```
     #[inline]
     pub fn run_file_with_scope(&self, scope: &mut Scope, path: PathBuf) -> RhaiResultOf<()> {
-        Self::read_file(path).and_then(|contents| self.run_with_scope(scope, &contents))
+        self.read_script_file(path)
+            .and_then(|contents| self.run_with_scope(scope, &contents))
     }
 }
 
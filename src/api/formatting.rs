@@ -9,6 +9,9 @@ use std::any::type_name;
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 
+/// Maximum number of type aliases to follow when mapping a type name.
+const MAX_TYPE_ALIAS_DEPTH: usize = 16;
+
 /// Map the name of a standard type into a friendly form.
 #[inline]
 #[must_use]
@@ -50,14 +53,35 @@ pub fn map_std_type_name(name: &str, shorthands: bool) -> &str {
     if name == type_name::<crate::Blob>() || name == "Blob" {
         return if shorthands { "blob" } else { "Blob" };
     }
+    #[cfg(not(feature = "no_index"))]
+    if name == type_name::<crate::SharedBlob>() || name == "SharedBlob" {
+        return if shorthands {
+            "shared_blob"
+        } else {
+            "SharedBlob"
+        };
+    }
     #[cfg(not(feature = "no_object"))]
     if name == type_name::<crate::Map>() || name == "Map" {
         return if shorthands { "map" } else { "Map" };
     }
+    #[cfg(not(feature = "no_object"))]
+    if name == type_name::<crate::Prototype>() || name == "Prototype" {
+        return "Prototype";
+    }
     #[cfg(not(feature = "no_time"))]
     if name == type_name::<crate::Instant>() || name == "Instant" {
         return if shorthands { "timestamp" } else { "Instant" };
     }
+    #[cfg(feature = "parallel")]
+    #[cfg(not(feature = "no_std"))]
+    if name == type_name::<crate::Task>() || name == "Task" {
+        return "Task";
+    }
+    #[cfg(not(feature = "no_std"))]
+    if name == type_name::<crate::StreamReader>() || name == "StreamReader" {
+        return if shorthands { "stream" } else { "StreamReader" };
+    }
     if name == type_name::<ExclusiveRange>() || name == "ExclusiveRange" {
         return if shorthands {
             "range"
@@ -200,9 +224,15 @@ impl Engine {
     ///
     /// If a type is registered via [`register_type_with_name`][Engine::register_type_with_name],
     /// the type name provided for the registration will be used.
+    ///
+    /// Type aliases (registered via [`register_type_alias`][Engine::register_type_alias]) and
+    /// the type name mapper (registered via [`on_map_type_name`][Engine::on_map_type_name]) are
+    /// resolved first.
     #[inline]
     #[must_use]
     pub fn map_type_name<'a>(&'a self, name: &'a str) -> &'a str {
+        let name = self.resolve_type_alias(name);
+
         self.global_modules
             .iter()
             .find_map(|m| m.get_custom_type_display_by_name(name))
@@ -219,6 +249,29 @@ impl Engine {
             .unwrap_or_else(|| map_std_type_name(name, true))
     }
 
+    /// Resolve a type name via type aliases and the type name mapper, following alias chains.
+    #[must_use]
+    fn resolve_type_alias<'a>(&'a self, mut name: &'a str) -> &'a str {
+        if self.type_aliases.is_empty() && self.type_name_mapper.is_none() {
+            return name;
+        }
+
+        // Guard against cycles
+        for _ in 0..MAX_TYPE_ALIAS_DEPTH {
+            if let Some(alias) = self.type_aliases.get(name) {
+                name = alias;
+                continue;
+            }
+
+            match self.type_name_mapper.as_ref().and_then(|f| f(name)) {
+                Some(mapped) if mapped != name => name = mapped,
+                _ => break,
+            }
+        }
+
+        name
+    }
+
     /// Format a Rust parameter type.
     ///
     /// If a type is registered via [`register_type_with_name`][Engine::register_type_with_name],
@@ -248,6 +301,45 @@ impl Engine {
             .into()
     }
 
+    /// Get the rules for converting floating-point numbers to and from strings.
+    ///
+    /// Not available under `no_float`.
+    #[cfg(not(feature = "no_float"))]
+    #[inline(always)]
+    #[must_use]
+    pub const fn float_format(&self) -> &crate::FloatFormat {
+        &self.float_format
+    }
+    /// Set the rules for converting floating-point numbers to and from strings.
+    ///
+    /// They apply to `print`, `to_string`, string interpolation and `parse_float`,
+    /// but not to the debug format, which always renders numbers in full.
+    ///
+    /// Not available under `no_float`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, FloatFormat};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_float_format(FloatFormat::new().with_significant_digits(4).with_separators(true));
+    ///
+    /// assert_eq!(engine.eval::<String>("`${1.0 / 3.0}`")?, "0.3333");
+    ///
+    /// assert_eq!(engine.eval::<String>(r#"parse_float("1_234.56").to_string()"#)?, "1235.0");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "no_float"))]
+    #[inline(always)]
+    pub fn set_float_format(&mut self, format: crate::FloatFormat) -> &mut Self {
+        self.float_format = format;
+        self
+    }
+
     /// Compact a script to eliminate insignificant whitespaces and comments.
     ///
     /// This is useful to prepare a script for further compressing.
@@ -0,0 +1,235 @@
+//! Module that defines the syntax highlighting API of [`Engine`].
+#![cfg(not(feature = "no_position"))]
+
+use crate::tokenizer::Token;
+use crate::{Engine, Position};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{iter::once, ops::Range};
+
+/// Semantic category of a token, for syntax highlighting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum TokenCategory {
+    /// Standard keyword (e.g. `let`, `if`, `fn`, `true`).
+    Keyword,
+    /// Operator (e.g. `+`, `==`, `?.`, `..=`).
+    Operator,
+    /// Punctuation (e.g. `(`, `}`, `,`, `;`, `::`, `#{`).
+    Punctuation,
+    /// Custom keyword or custom operator registered with the [`Engine`].
+    CustomKeyword,
+    /// Reserved keyword or symbol that is not in use.
+    Reserved,
+    /// Identifier (e.g. a variable or function name).
+    Identifier,
+    /// Numeric literal.
+    Number,
+    /// String or character literal.
+    String,
+    /// Part of an interpolated string: the text before an interpolated expression (up to and
+    /// including `$`), or the braces around an interpolated expression.
+    Interpolation,
+    /// Comment.
+    Comment,
+    /// Invalid input that fails to tokenize.
+    Error,
+}
+
+/// A token of a script together with its [category][TokenCategory], as returned by
+/// [`Engine::tokenize_classified`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ClassifiedToken {
+    /// Semantic category of the token.
+    pub category: TokenCategory,
+    /// Range of bytes spanned by the token in the script.
+    pub span: Range<usize>,
+    /// Position of the start of the token.
+    pub position: Position,
+}
+
+impl TokenCategory {
+    /// Get the category of a token.
+    #[must_use]
+    fn of(token: &Token) -> Self {
+        match token {
+            Token::Comment(..) => Self::Comment,
+            Token::Identifier(..) => Self::Identifier,
+            Token::IntegerConstant(..) => Self::Number,
+            #[cfg(not(feature = "no_float"))]
+            Token::FloatConstant(..) => Self::Number,
+            #[cfg(feature = "decimal")]
+            Token::DecimalConstant(..) => Self::Number,
+            Token::CharConstant(..) | Token::StringConstant(..) => Self::String,
+            Token::InterpolatedString(..) => Self::Interpolation,
+            #[cfg(not(feature = "no_custom_syntax"))]
+            Token::Custom(..) => Self::CustomKeyword,
+            Token::Reserved(..) => Self::Reserved,
+            Token::LexError(..) => Self::Error,
+            Token::LeftBrace
+            | Token::RightBrace
+            | Token::LeftParen
+            | Token::RightParen
+            | Token::LeftBracket
+            | Token::RightBracket
+            | Token::Unit
+            | Token::MapStart
+            | Token::SemiColon
+            | Token::Colon
+            | Token::DoubleColon
+            | Token::DoubleArrow
+            | Token::Comma
+            | Token::Period
+            | Token::Underscore => Self::Punctuation,
+            Token::NotIn => Self::Operator,
+            token if token.is_standard_symbol() => Self::Operator,
+            _ => Self::Keyword,
+        }
+    }
+}
+
+impl Engine {
+    /// Tokenize a script into tokens classified by [category][TokenCategory], for syntax
+    /// highlighting.
+    ///
+    /// The real tokenizer is used, so custom keywords and custom operators registered with this
+    /// [`Engine`], as well as disabled symbols, are classified correctly.
+    ///
+    /// Whitespace is not included. Invalid input is returned as tokens of category
+    /// [`Error`][TokenCategory::Error] and tokenization continues afterwards, so this method
+    /// never fails.
+    ///
+    /// Not available under `no_position`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Engine, TokenCategory};
+    ///
+    /// let mut engine = Engine::new();
+    /// # #[cfg(not(feature = "no_custom_syntax"))]
+    /// engine.register_custom_operator("minus", 100).unwrap();
+    ///
+    /// let script = "let x = 42 minus y; // answer";
+    ///
+    /// let tokens = engine
+    ///     .tokenize_classified(script)
+    ///     .into_iter()
+    ///     .map(|t| (t.category, &script[t.span]))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(tokens[0], (TokenCategory::Keyword, "let"));
+    /// assert_eq!(tokens[3], (TokenCategory::Number, "42"));
+    /// # #[cfg(not(feature = "no_custom_syntax"))]
+    /// assert_eq!(tokens[4], (TokenCategory::CustomKeyword, "minus"));
+    /// assert_eq!(tokens[7], (TokenCategory::Comment, "// answer"));
+    /// ```
+    #[must_use]
+    pub fn tokenize_classified(&self, script: &str) -> Vec<ClassifiedToken> {
+        let scripts = [script];
+        let (mut stream, tc) = self.lex(&scripts);
+        stream.state.include_comments = true;
+
+        let line_starts = once(0)
+            .chain(script.match_indices('\n').map(|(i, ..)| i + 1))
+            .collect::<Vec<_>>();
+
+        // Map a position to the range of bytes of the character at that position, or to an empty
+        // range at the start of the line if the position is at the beginning of a line
+        let char_at = |pos: Position| {
+            let start = line_starts[pos.line().unwrap() - 1];
+
+            match pos.position() {
+                Some(col) => script[start..]
+                    .char_indices()
+                    .nth(col - 1)
+                    .map_or(script.len()..script.len(), |(i, ch)| {
+                        start + i..start + i + ch.len_utf8()
+                    }),
+                None => start..start,
+            }
+        };
+
+        // Map a byte offset to a position
+        let position_of = |offset: usize| {
+            let line = line_starts.partition_point(|&start| start <= offset);
+            let col = script[line_starts[line - 1]..offset].chars().count();
+            Position::new(line as u16, col as u16 + 1)
+        };
+
+        // Comments not returned by the tokenizer (e.g. module doc-comments) are in the gaps
+        // between tokens
+        let gap_comment = |range: Range<usize>| {
+            let gap = &script[range.clone()];
+            let start = range.start + (gap.len() - gap.trim_start().len());
+            let end = range.start + gap.trim_end().len();
+
+            (start < end).then(|| ClassifiedToken {
+                category: TokenCategory::Comment,
+                span: start..end,
+                position: position_of(start),
+            })
+        };
+
+        let mut tokens = Vec::<ClassifiedToken>::new();
+        // Brace depths of interpolated expressions within interpolated strings
+        let mut interpolations = Vec::<usize>::new();
+        let mut last_end = 0;
+
+        loop {
+            let (token, pos) = stream.next().unwrap();
+
+            if matches!(token, Token::EOF) {
+                break;
+            }
+
+            let mut category = TokenCategory::of(&token);
+
+            match token {
+                Token::InterpolatedString(..) => interpolations.push(0),
+                Token::LeftBrace | Token::MapStart if !interpolations.is_empty() => {
+                    let depth = interpolations.last_mut().unwrap();
+                    if *depth == 0 {
+                        // Start of interpolated expression
+                        category = TokenCategory::Interpolation;
+                    }
+                    *depth += 1;
+                }
+                Token::RightBrace if !interpolations.is_empty() => {
+                    let depth = interpolations.last_mut().unwrap();
+                    *depth -= 1;
+                    if *depth == 0 {
+                        // End of interpolated expression - continue with the text
+                        interpolations.pop();
+                        tc.borrow_mut().is_within_text = true;
+                        category = TokenCategory::Interpolation;
+                    }
+                }
+                _ => (),
+            }
+
+            // Text continuing an interpolated string has no position
+            let (start, pos) = if pos.is_none() {
+                (last_end, position_of(last_end))
+            } else {
+                (char_at(pos).start, pos)
+            };
+            let end = char_at(stream.pos).end.max(start);
+            let end = start + script[start..end].trim_end_matches(['\r', '\n']).len();
+
+            tokens.extend(gap_comment(last_end..start));
+            last_end = end;
+
+            tokens.push(ClassifiedToken {
+                category,
+                span: start..end,
+                position: pos,
+            });
+        }
+
+        tokens.extend(gap_comment(last_end..script.len()));
+
+        tokens
+    }
+}
@@ -0,0 +1,618 @@
+//! Module that defines the type inference API of [`Engine`].
+#![cfg(feature = "metadata")]
+#![cfg(not(feature = "no_position"))]
+
+use crate::ast::{ASTNode, Expr, FnCallExpr, Stmt};
+use crate::func::RhaiFunc;
+use crate::module::FuncMetadata;
+use crate::tokenizer::Token;
+use crate::{Dynamic, Engine, FnAccess, ImmutableString, Module, Position, AST, INT};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{any::type_name, iter::once};
+
+/// Statically inferred type of an expression, as returned by [`Engine::infer_type_at`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct InferredType {
+    /// Position of the expression.
+    pub position: Position,
+    /// Names of the candidate types of the expression (e.g. `i64`, `string`), sorted.
+    ///
+    /// There is a single type if it is statically known.
+    /// This is empty if the type cannot be inferred.
+    pub types: Vec<String>,
+    /// Value of the expression, if it is a constant.
+    pub value: Option<Dynamic>,
+}
+
+/// Candidate types (and constant value) of an expression. No candidates means unknown.
+#[derive(Debug, Clone, Default)]
+struct Types {
+    names: Vec<String>,
+    value: Option<Dynamic>,
+}
+
+impl Types {
+    /// A single known type.
+    #[must_use]
+    fn of(name: &str) -> Self {
+        Self {
+            names: vec![name.to_string()],
+            value: None,
+        }
+    }
+    /// Is this a single known type?
+    #[must_use]
+    fn is(&self, name: &str) -> bool {
+        self.names.len() == 1 && self.names[0] == name
+    }
+    /// Merge two sets of candidate types. Unknown types absorb everything.
+    #[must_use]
+    fn union(mut self, other: Self) -> Self {
+        if self.names.is_empty() || other.names.is_empty() {
+            return Self::default();
+        }
+        self.names.extend(other.names);
+        self.names.sort();
+        self.names.dedup();
+        self.value = None;
+        self
+    }
+}
+
+/// A variable in scope during inference.
+struct Binding {
+    name: ImmutableString,
+    types: Types,
+    /// Length of the path to the declaring statement.
+    depth: usize,
+    /// Is the variable visible yet (i.e. after its initializer)?
+    active: bool,
+}
+
+/// Best-effort static type inference over an [`AST`].
+struct Inference<'a> {
+    engine: &'a Engine,
+    #[cfg(not(feature = "no_function"))]
+    lib: &'a Module,
+}
+
+impl Inference<'_> {
+    /// Name of a Rust type as displayed by the [`Engine`].
+    #[must_use]
+    fn name_of(&self, rust_type: &str) -> String {
+        self.engine.map_type_name(rust_type).to_string()
+    }
+    /// Infer the types of an expression.
+    #[must_use]
+    fn expr(&self, expr: &Expr, bindings: &[Binding]) -> Types {
+        if let Some(value) = expr.get_literal_value() {
+            return Types {
+                names: vec![self.name_of(value.type_name())],
+                value: Some(value),
+            };
+        }
+
+        match expr {
+            Expr::InterpolatedString(..) => Types::of("string"),
+            Expr::Array(..) => Types::of("array"),
+            Expr::Map(..) => Types::of("map"),
+            Expr::Variable(x, ..) => {
+                #[cfg(not(feature = "no_module"))]
+                if !x.2.is_empty() {
+                    return Types::default();
+                }
+                bindings
+                    .iter()
+                    .rev()
+                    .find(|b| b.active && b.name == x.1)
+                    .map(|b| b.types.clone())
+                    .unwrap_or_default()
+            }
+            Expr::And(..) | Expr::Or(..) => Types::of("bool"),
+            Expr::Coalesce(x, ..) => {
+                let mut lhs = self.expr(&x.lhs, bindings);
+                lhs.names.retain(|t| t != "()");
+                lhs.union(self.expr(&x.rhs, bindings))
+            }
+            Expr::Stmt(block) => self.block(block.statements(), bindings),
+            Expr::FnCall(x, ..) => self.call(x, None, bindings),
+            Expr::Dot(x, ..) => {
+                let object = self.expr(&x.lhs, bindings);
+                self.chain(object, &x.rhs, bindings)
+            }
+            Expr::Index(x, ..) => {
+                let object = self.expr(&x.lhs, bindings);
+                let index = self.expr(&x.rhs, bindings);
+                self.index(&object, &index)
+            }
+            _ => Types::default(),
+        }
+    }
+    /// Infer the types of the value of a statements block.
+    #[must_use]
+    fn block(&self, statements: &[Stmt], bindings: &[Binding]) -> Types {
+        match statements.last() {
+            Some(Stmt::Expr(expr)) => self.expr(expr, bindings),
+            Some(Stmt::FnCall(x, ..)) => self.call(x, None, bindings),
+            Some(Stmt::If(x, ..)) => self
+                .block(x.body.statements(), bindings)
+                .union(self.block(x.branch.statements(), bindings)),
+            Some(Stmt::Block(block)) => self.block(block.statements(), bindings),
+            None => Types::of("()"),
+            _ => Types::default(),
+        }
+    }
+    /// Infer the types of the right-hand side of a property/method chain on an object.
+    #[must_use]
+    fn chain(&self, object: Types, rhs: &Expr, bindings: &[Binding]) -> Types {
+        match rhs {
+            Expr::Property(x, ..) if !object.is("map") => self.lookup(&x.0 .0, &[object], false),
+            Expr::MethodCall(x, ..) => self.call(x, Some(object), bindings),
+            Expr::Dot(x, ..) => {
+                let object = self.chain(object, &x.lhs, bindings);
+                self.chain(object, &x.rhs, bindings)
+            }
+            Expr::Index(x, ..) => {
+                let object = self.chain(object, &x.lhs, bindings);
+                let index = self.expr(&x.rhs, bindings);
+                self.index(&object, &index)
+            }
+            _ => Types::default(),
+        }
+    }
+    /// Infer the types of a property/method chain on an object up to (and including) the link
+    /// at a particular position.
+    #[must_use]
+    fn chain_upto(&self, object: Types, rhs: &Expr, pos: Position, bindings: &[Binding]) -> Types {
+        match rhs {
+            Expr::Dot(x, ..) => {
+                let object = self.chain(object, &x.lhs, bindings);
+                if x.lhs.position() == pos {
+                    object
+                } else {
+                    self.chain_upto(object, &x.rhs, pos, bindings)
+                }
+            }
+            _ => self.chain(object, rhs, bindings),
+        }
+    }
+    /// Infer the types of indexing into an object.
+    #[must_use]
+    fn index(&self, object: &Types, _index: &Types) -> Types {
+        if object.is("string") {
+            return Types::of("char");
+        }
+        if object.is("blob") {
+            return Types::of(&self.name_of(type_name::<INT>()));
+        }
+
+        #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+        return self.lookup(
+            crate::engine::FN_IDX_GET,
+            &[object.clone(), _index.clone()],
+            false,
+        );
+        #[cfg(all(feature = "no_index", feature = "no_object"))]
+        return Types::default();
+    }
+    /// Infer the types of a function call, with the object of a method call (if any) as the
+    /// first argument.
+    #[must_use]
+    fn call(&self, x: &FnCallExpr, object: Option<Types>, bindings: &[Binding]) -> Types {
+        let args = object
+            .into_iter()
+            .chain(x.args.iter().map(|arg| self.expr(arg, bindings)))
+            .collect::<Vec<_>>();
+
+        if let Some(ref op) = x.op_token {
+            if let Some(types) = self.builtin_op(op, &args) {
+                return types;
+            }
+        }
+
+        match x.name.as_str() {
+            crate::engine::KEYWORD_FN_PTR | crate::engine::KEYWORD_FN_PTR_CURRY => {
+                return Types::of("Fn")
+            }
+            crate::engine::KEYWORD_TYPE_OF => return Types::of("string"),
+            crate::engine::KEYWORD_IS_DEF_VAR => return Types::of("bool"),
+            #[cfg(not(feature = "no_function"))]
+            crate::engine::KEYWORD_IS_DEF_FN => return Types::of("bool"),
+            #[cfg(not(feature = "no_closure"))]
+            crate::engine::KEYWORD_IS_SHARED => return Types::of("bool"),
+            _ => (),
+        }
+
+        #[cfg(not(feature = "no_module"))]
+        if !x.namespace.is_empty() {
+            return self
+                .engine
+                .global_sub_modules
+                .get(x.namespace.root())
+                .and_then(|m| {
+                    x.namespace.path[1..]
+                        .iter()
+                        .try_fold(&**m, |m, ident| m.get_sub_module(&ident.name))
+                })
+                .map_or_else(Types::default, |m| {
+                    self.lookup_in(once(m), None, &x.name, &args)
+                });
+        }
+
+        self.lookup(&x.name, &args, true)
+    }
+    /// Infer the result type of a built-in operator.
+    #[must_use]
+    fn builtin_op(&self, op: &Token, args: &[Types]) -> Option<Types> {
+        let int = self.name_of(type_name::<INT>());
+        #[cfg(not(feature = "no_float"))]
+        let float = self.name_of(type_name::<crate::FLOAT>());
+        #[cfg(feature = "no_float")]
+        let float = String::new();
+        let is_num = |t: &Types| t.is(&int) || t.is(&float) || t.is("decimal");
+
+        match (op, args) {
+            (
+                Token::EqualsTo
+                | Token::NotEqualsTo
+                | Token::LessThan
+                | Token::LessThanEqualsTo
+                | Token::GreaterThan
+                | Token::GreaterThanEqualsTo
+                | Token::Bang,
+                _,
+            ) => Some(Types::of("bool")),
+            (Token::UnaryMinus | Token::UnaryPlus, [x]) if is_num(x) => Some(x.clone()),
+            (
+                Token::Plus
+                | Token::Minus
+                | Token::Multiply
+                | Token::Divide
+                | Token::Modulo
+                | Token::PowerOf,
+                [x, y],
+            ) if is_num(x) && is_num(y) => {
+                if x.names == y.names {
+                    Some(Types::of(&x.names[0]))
+                } else if x.is(&float) || y.is(&float) {
+                    Some(Types::of(&float))
+                } else {
+                    Some(Types::of("decimal"))
+                }
+            }
+            (Token::Plus, [x, y])
+                if (x.is("string") || x.is("char")) && (y.is("string") || y.is("char")) =>
+            {
+                Some(Types::of("string"))
+            }
+            (Token::Ampersand | Token::Pipe | Token::XOr, [x, y])
+                if (x.is("bool") || x.is(&int)) && x.names == y.names =>
+            {
+                Some(Types::of(&x.names[0]))
+            }
+            (Token::LeftShift | Token::RightShift, [x, y]) if x.is(&int) && y.is(&int) => {
+                Some(Types::of(&int))
+            }
+            (Token::ExclusiveRange, [x, y]) if x.is(&int) && y.is(&int) => Some(Types::of("range")),
+            (Token::InclusiveRange, [x, y]) if x.is(&int) && y.is(&int) => {
+                Some(Types::of("range="))
+            }
+            _ => None,
+        }
+    }
+    /// Infer the return types of all functions of a name matching the types of the arguments,
+    /// searching functions registered with the [`Engine`] (and script-defined functions, if
+    /// `_script` is set).
+    #[must_use]
+    fn lookup(&self, name: &str, args: &[Types], _script: bool) -> Types {
+        let modules = self
+            .engine
+            .global_modules
+            .iter()
+            .map(|m| &**m)
+            .chain(self.engine.prelude.as_deref());
+
+        #[cfg(not(feature = "no_function"))]
+        let modules = modules.chain(_script.then_some(self.lib));
+
+        #[cfg(not(feature = "no_module"))]
+        let sub_modules = self.engine.global_sub_modules.values().map(|m| &**m);
+        #[cfg(feature = "no_module")]
+        let sub_modules = std::iter::empty();
+
+        self.lookup_in(modules, sub_modules, name, args)
+    }
+    /// Infer the return types of all functions of a name matching the types of the arguments
+    /// in a list of modules, plus functions exposed to the global namespace in a list of
+    /// sub-modules.
+    #[must_use]
+    fn lookup_in<'m>(
+        &self,
+        modules: impl IntoIterator<Item = &'m Module>,
+        _sub_modules: impl IntoIterator<Item = &'m Module>,
+        name: &str,
+        args: &[Types],
+    ) -> Types {
+        let mut result: Option<Types> = None;
+
+        let mut check = |func: &RhaiFunc, f: &FuncMetadata| {
+            if f.name != name || f.num_params != args.len() {
+                return;
+            }
+            if f.access == FnAccess::Private && !func.is_script() {
+                return;
+            }
+
+            // Skip functions whose parameter types do not match the arguments
+            let matches = f.params_info.iter().zip(args).all(|(param, arg)| {
+                match param.split_once(':').map(|(.., typ)| typ.trim()) {
+                    Some(typ) if !arg.names.is_empty() && !typ.ends_with("Dynamic") => {
+                        let typ = typ.trim_start_matches("&mut ").trim();
+                        arg.names.contains(&self.name_of(typ))
+                    }
+                    _ => true,
+                }
+            });
+            if !matches {
+                return;
+            }
+
+            let ret = if func.is_script() {
+                Types::default()
+            } else {
+                self.return_type(&f.return_type)
+            };
+
+            result = Some(match result.take() {
+                Some(types) => types.union(ret),
+                None => ret,
+            });
+        };
+
+        for m in modules {
+            m.iter_fn().for_each(|(func, f)| check(func, f));
+        }
+        #[cfg(not(feature = "no_module"))]
+        for m in _sub_modules {
+            super::signature_help::add_global_fns(m, &mut check);
+        }
+
+        result.unwrap_or_default()
+    }
+    /// Map the recorded return type of a native function to a type name.
+    #[must_use]
+    fn return_type(&self, typ: &str) -> Types {
+        let mut typ = typ.trim();
+
+        // Unwrap `Result<T, Box<EvalAltResult>>` and `RhaiResultOf<T>`
+        for wrapper in ["Result<", "RhaiResultOf<"] {
+            if let Some(i) = typ.find(wrapper) {
+                if typ.ends_with('>') && (i == 0 || typ[..i].ends_with("::")) {
+                    let inner = &typ[i + wrapper.len()..typ.len() - 1];
+                    typ = inner.split(',').next().unwrap().trim();
+                    break;
+                }
+            }
+        }
+
+        match typ {
+            "" | "()" => Types::of("()"),
+            t if t.ends_with("Dynamic") || t == "RhaiResult" => Types::default(),
+            t => Types::of(&self.name_of(t)),
+        }
+    }
+}
+
+impl Engine {
+    /// Infer the type of the expression under the cursor in a script, for hover information in
+    /// script editors.
+    ///
+    /// `offset` is the byte offset of the cursor within the script.
+    ///
+    /// The inference is best-effort: types of constants are propagated through variables, and
+    /// return types of functions registered with this [`Engine`] are used, narrowed by the types
+    /// of the arguments. Return types of script-defined functions are not inferred.
+    ///
+    /// Returns [`None`] if the script fails to compile or there is no expression under the
+    /// cursor. [`InferredType::types`] is empty if the type cannot be inferred.
+    ///
+    /// Not available under `no_position`. Exported under the `metadata` feature only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn("shout", |s: &str| s.to_uppercase());
+    ///
+    /// let script = r#"const X = "hello"; let y = shout(X + "!");"#;
+    ///
+    /// let x = engine.infer_type_at(script, script.find("X +").unwrap()).unwrap();
+    /// assert_eq!(x.types, ["string"]);
+    /// assert_eq!(x.value.unwrap().into_string().unwrap(), "hello");
+    ///
+    /// let y = engine.infer_type_at(script, script.find("y").unwrap()).unwrap();
+    /// assert_eq!(y.types, ["string"]);
+    /// assert!(y.value.is_none());
+    /// ```
+    #[must_use]
+    pub fn infer_type_at(&self, script: &str, offset: usize) -> Option<InferredType> {
+        let tokens = self.tokenize_classified(script);
+
+        let token = tokens
+            .iter()
+            .find(|t| t.span.contains(&offset))
+            .or_else(|| tokens.iter().find(|t| t.span.end == offset))?;
+
+        let ast = self
+            .compile_scripts_with_scope_raw(
+                None,
+                [script],
+                #[cfg(not(feature = "no_optimize"))]
+                crate::OptimizationLevel::None,
+            )
+            .ok()?;
+
+        self.infer_type_at_position(&ast, token.position)
+    }
+    /// Infer the type of the expression at a [position][Position] in an [`AST`].
+    ///
+    /// The position is the position of the token that an expression is recorded with, i.e. the
+    /// start of a literal, variable or function call, or the operator of a binary expression.
+    /// For `let` and `const` statements, the position of the variable name yields the type of
+    /// the initial value.
+    ///
+    /// See [`infer_type_at`][Engine::infer_type_at] for details.
+    ///
+    /// Not available under `no_position`. Exported under the `metadata` feature only.
+    #[must_use]
+    pub fn infer_type_at_position(&self, ast: &AST, pos: Position) -> Option<InferredType> {
+        let inference = Inference {
+            engine: self,
+            #[cfg(not(feature = "no_function"))]
+            lib: ast.shared_lib(),
+        };
+
+        let bodies = once((ast.statements(), Vec::<Binding>::new()));
+
+        // Parameters of script-defined functions are bound to their types, if any
+        #[cfg(not(feature = "no_function"))]
+        let bodies = bodies.chain(ast.shared_lib().iter_script_fn().map(|(.., f)| {
+            let params = f
+                .params
+                .iter()
+                .enumerate()
+                .map(|(i, name)| Binding {
+                    name: name.clone(),
+                    types: f
+                        .param_types
+                        .get(i)
+                        .map_or_else(Types::default, |t| Types::of(&inference.name_of(t))),
+                    depth: 1,
+                    active: true,
+                })
+                .collect();
+            (f.body.statements(), params)
+        }));
+
+        let mut found = None;
+
+        for (statements, mut bindings) in bodies {
+            let path = &mut Vec::new();
+
+            for stmt in statements {
+                stmt.walk(path, &mut |path| {
+                    let depth = path.len();
+
+                    // Variables go out of scope with their blocks and come into scope after
+                    // their initializers
+                    bindings.retain(|b| b.depth <= depth);
+                    bindings
+                        .iter_mut()
+                        .filter(|b| b.depth == depth)
+                        .for_each(|b| b.active = true);
+
+                    let bindings = &mut bindings;
+                    let mut record = |types: Types| {
+                        found = Some(InferredType {
+                            position: pos,
+                            types: types.names,
+                            value: types.value,
+                        });
+                    };
+
+                    match path.last().unwrap() {
+                        ASTNode::Stmt(Stmt::Var(x, options, ..)) => {
+                            let mut types = inference.expr(&x.1, bindings);
+                            if !options.intersects(crate::ast::ASTFlags::CONSTANT) {
+                                types.value = None;
+                            }
+                            if x.0.pos == pos {
+                                record(types.clone());
+                            }
+                            bindings.push(Binding {
+                                name: x.0.name.clone(),
+                                types,
+                                depth,
+                                active: false,
+                            });
+                        }
+                        ASTNode::Stmt(Stmt::For(x, ..)) => {
+                            let iter = inference.expr(&x.2.expr, bindings);
+                            let int = inference.name_of(type_name::<INT>());
+                            let types = if iter.is("range") || iter.is("range=") {
+                                Types::of(&int)
+                            } else if iter.is("string") {
+                                Types::of("char")
+                            } else {
+                                Types::default()
+                            };
+                            let counter = x.1.iter().map(|c| (c, Types::of(&int)));
+
+                            for (ident, types) in once((&x.0, types)).chain(counter) {
+                                if ident.pos == pos {
+                                    record(types.clone());
+                                }
+                                bindings.push(Binding {
+                                    name: ident.name.clone(),
+                                    types,
+                                    depth: depth + 1,
+                                    active: true,
+                                });
+                            }
+                        }
+                        ASTNode::Stmt(Stmt::Assignment(x)) => {
+                            if let Expr::Variable(v, ..) = &x.1.lhs {
+                                let value = inference.expr(&x.1.rhs, bindings);
+                                if let Some(b) = bindings
+                                    .iter_mut()
+                                    .rev()
+                                    .find(|b| b.active && b.name == v.1)
+                                {
+                                    b.types = if x.0.is_op_assignment() {
+                                        Types {
+                                            value: None,
+                                            ..b.types.clone()
+                                        }
+                                    } else {
+                                        b.types.clone().union(value)
+                                    };
+                                }
+                            }
+                        }
+                        ASTNode::Stmt(Stmt::FnCall(x, call_pos)) if *call_pos == pos => {
+                            record(inference.call(x, None, bindings));
+                        }
+                        ASTNode::Expr(expr @ (Expr::Property(..) | Expr::MethodCall(..)))
+                            if expr.position() == pos =>
+                        {
+                            // Find the root of the property/method chain
+                            let root = path[..depth - 1]
+                                .iter()
+                                .rev()
+                                .take_while(|node| matches!(node, ASTNode::Expr(Expr::Dot(..))))
+                                .last();
+
+                            if let Some(ASTNode::Expr(Expr::Dot(x, ..))) = root {
+                                let object = inference.expr(&x.lhs, bindings);
+                                record(inference.chain_upto(object, &x.rhs, pos, bindings));
+                            }
+                        }
+                        ASTNode::Expr(expr) if expr.position() == pos => {
+                            record(inference.expr(expr, bindings));
+                        }
+                        _ => (),
+                    }
+
+                    true
+                });
+            }
+        }
+
+        found
+    }
+}
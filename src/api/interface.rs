@@ -0,0 +1,177 @@
+//! Module that defines the API for checking the methods and properties exposed by a value.
+
+use crate::module::FnNamespace;
+use crate::{Dynamic, Engine, Module, RhaiResultOf, ERR};
+use std::any::TypeId;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+impl Engine {
+    /// Does a value expose a particular member (i.e. a method or property)?
+    ///
+    /// A member is exposed if:
+    /// * the value is an object map containing the member as a property (which may hold a
+    ///   function pointer for a method), either directly or in its [prototype][crate::Prototype], or
+    /// * a function (or property getter) with the member's name is registered that takes the
+    ///   value's type as its first parameter, or
+    /// * a script-defined function with the member's name exists in `lib`, either untyped or
+    ///   bound to the value's type.
+    pub(crate) fn has_member<'a>(
+        &self,
+        lib: impl IntoIterator<Item = &'a Module>,
+        value: &Dynamic,
+        name: &str,
+    ) -> bool {
+        #[cfg(not(feature = "no_object"))]
+        if value.read_lock::<crate::Map>().map_or(false, |map| {
+            map.contains_key(name) || crate::Prototype::find_inherited(&map, name).is_some()
+        }) {
+            return true;
+        }
+
+        #[cfg(not(feature = "no_object"))]
+        let getter = crate::engine::make_getter(name);
+        let type_id = value.type_id();
+        #[cfg(not(feature = "no_function"))]
+        #[cfg(not(feature = "no_object"))]
+        let type_name = self.map_type_name(value.type_name());
+
+        let is_match = |module: &Module, global_only: bool| {
+            module.iter_fn().any(|(_func, f)| {
+                if global_only && f.namespace != FnNamespace::Global {
+                    return false;
+                }
+
+                #[cfg(not(feature = "no_function"))]
+                if let Some(fn_def) = _func.get_script_fn_def() {
+                    #[cfg(not(feature = "no_object"))]
+                    return fn_def.name == name
+                        && fn_def.this_type.as_ref().map_or(true, |t| t == type_name);
+                    #[cfg(feature = "no_object")]
+                    return fn_def.name == name;
+                }
+
+                #[cfg(not(feature = "no_object"))]
+                let is_name = f.name == name || f.name == getter;
+                #[cfg(feature = "no_object")]
+                let is_name = f.name == name;
+
+                is_name
+                    && f.param_types
+                        .first()
+                        .map_or(false, |&t| t == type_id || t == TypeId::of::<Dynamic>())
+            })
+        };
+
+        if lib.into_iter().any(|m| is_match(m, false))
+            || self.global_modules.iter().any(|m| is_match(m, false))
+        {
+            return true;
+        }
+
+        #[cfg(not(feature = "no_module"))]
+        if self.global_sub_modules.values().any(|m| is_match(m, true)) {
+            return true;
+        }
+
+        false
+    }
+
+    /// Find the members (i.e. methods or properties) in a list that a value does _not_ expose.
+    ///
+    /// See [`implements`][Engine::implements] for details.
+    #[must_use]
+    pub fn missing_members<'a>(
+        &self,
+        value: &Dynamic,
+        members: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<&'a str> {
+        members
+            .into_iter()
+            .filter(|&name| !self.has_member(None, value, name))
+            .collect()
+    }
+
+    /// Does a value expose all the members (i.e. methods or properties) in a list?
+    ///
+    /// This is useful for validating duck-typed objects (e.g. plugin objects supplied by users)
+    /// before use.
+    ///
+    /// A member is exposed if:
+    /// * the value is an object map containing the member as a property (which may hold a
+    ///   function pointer for a method), either directly or in its [prototype][crate::Prototype], or
+    /// * a function (or property getter) with the member's name is registered that takes the
+    ///   value's type as its first parameter.
+    ///
+    /// Script-defined functions are not considered. Use the `implements` function within scripts
+    /// to include script-defined functions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Dynamic, Engine};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Sprite;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine
+    ///     .register_type_with_name::<Sprite>("Sprite")
+    ///     .register_fn("update", |_: &mut Sprite, _dt: i64| ())
+    ///     .register_fn("draw", |_: &mut Sprite| ());
+    ///
+    /// let sprite = Dynamic::from(Sprite);
+    ///
+    /// assert!(engine.implements(&sprite, ["update", "draw"]));
+    /// assert!(!engine.implements(&sprite, ["update", "draw", "resize"]));
+    /// assert_eq!(engine.missing_members(&sprite, ["update", "resize"]), ["resize"]);
+    ///
+    /// # #[cfg(not(feature = "no_object"))]
+    /// # {
+    /// let object = engine.eval::<Dynamic>("#{ update: |dt| dt, draw: 42 }")?;
+    /// assert!(engine.implements(&object, ["update", "draw"]));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn implements<'a>(
+        &self,
+        value: &Dynamic,
+        members: impl IntoIterator<Item = &'a str>,
+    ) -> bool {
+        members
+            .into_iter()
+            .all(|name| self.has_member(None, value, name))
+    }
+
+    /// Check that a value exposes all the members (i.e. methods or properties) in a list.
+    ///
+    /// See [`implements`][Engine::implements] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorMismatchDataType`][crate::EvalAltResult::ErrorMismatchDataType] listing
+    /// all the members that are not exposed.
+    pub fn ensure_implements<'a>(
+        &self,
+        value: &Dynamic,
+        members: impl IntoIterator<Item = &'a str>,
+    ) -> RhaiResultOf<()> {
+        let missing = self.missing_members(value, members);
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        Err(ERR::ErrorMismatchDataType(
+            format!("implementation of `{}`", missing.join("`, `")),
+            self.map_type_name(value.type_name()).into(),
+            crate::Position::NONE,
+        )
+        .into())
+    }
+}
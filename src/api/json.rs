@@ -153,7 +153,7 @@ impl Engine {
 pub fn format_map_as_json(map: &Map) -> String {
     let mut result = String::from('{');
 
-    for (key, value) in map {
+    for (key, value) in crate::types::prototype::iter_properties(map) {
         if result.len() > 1 {
             result += ",";
         }
@@ -169,6 +169,19 @@ pub fn format_map_as_json(map: &Map) -> String {
     result
 }
 
+/// Format a byte array as a JSON array.
+#[cfg(not(feature = "no_index"))]
+fn format_bytes_as_json(result: &mut String, bytes: &[u8]) {
+    *result += "[";
+    for (i, x) in bytes.iter().enumerate() {
+        if i > 0 {
+            *result += ",";
+        }
+        write!(result, "{x}").unwrap();
+    }
+    *result += "]";
+}
+
 /// Format a [`Dynamic`] value as JSON.
 fn format_dynamic_as_json(result: &mut String, value: &Dynamic) {
     match value.0 {
@@ -196,16 +209,9 @@ fn format_dynamic_as_json(result: &mut String, value: &Dynamic) {
             *result += "]";
         }
         #[cfg(not(feature = "no_index"))]
-        Union::Blob(ref b, _, _) => {
-            *result += "[";
-            for (i, x) in b.iter().enumerate() {
-                if i > 0 {
-                    *result += ",";
-                }
-                write!(result, "{x}").unwrap();
-            }
-            *result += "]";
-        }
+        Union::Blob(ref b, _, _) => format_bytes_as_json(result, b),
+        #[cfg(not(feature = "no_index"))]
+        Union::SharedBlob(ref b, _, _) => format_bytes_as_json(result, b),
         #[cfg(not(feature = "no_closure"))]
         Union::Shared(ref v, _, _) => {
             let value = &*crate::func::locked_read(v).unwrap();
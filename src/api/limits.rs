@@ -1,7 +1,10 @@
 //! Settings for [`Engine`]'s limitations.
 #![cfg(not(feature = "unchecked"))]
 
-use crate::Engine;
+use crate::func::SendSync;
+use crate::types::dynamic::Variant;
+use crate::{Dynamic, Engine};
+use std::any::TypeId;
 use std::num::{NonZeroU64, NonZeroUsize};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
@@ -20,6 +23,11 @@ pub mod default_limits {
     /// Not available under `no_function`.
     #[cfg(not(feature = "no_function"))]
     pub const MAX_FUNCTION_EXPR_DEPTH: usize = 16;
+    /// Minimum amount of native stack space (in bytes) that must remain.
+    ///
+    /// Only available under `stack_guard`.
+    #[cfg(feature = "stack_guard")]
+    pub const MIN_STACK_REMAINING: usize = 64 * 1024;
 }
 #[cfg(not(debug_assertions))]
 pub mod default_limits {
@@ -35,6 +43,11 @@ pub mod default_limits {
     /// Not available under `no_function`.
     #[cfg(not(feature = "no_function"))]
     pub const MAX_FUNCTION_EXPR_DEPTH: usize = 32;
+    /// Minimum amount of native stack space (in bytes) that must remain.
+    ///
+    /// Only available under `stack_guard`.
+    #[cfg(feature = "stack_guard")]
+    pub const MIN_STACK_REMAINING: usize = 64 * 1024;
 }
 
 /// A type containing all the limits imposed by the [`Engine`].
@@ -58,10 +71,32 @@ pub struct Limits {
     pub function_expr_depth: Option<NonZeroUsize>,
     /// Maximum number of operations allowed to run.
     pub num_operations: Option<NonZeroU64>,
+    /// Point in time after which a script is no longer allowed to run.
+    ///
+    /// Not available under `no_time`.
+    #[cfg(not(feature = "no_time"))]
+    pub deadline: Option<crate::Instant>,
     /// Maximum number of variables allowed at any instant.
     ///
     /// Set to zero to effectively disable creating variables.
     pub num_variables: usize,
+    /// Maximum number of [handles][crate::Handle] open at any instant during an evaluation run.
+    ///
+    /// Set to zero to effectively disable opening handles.
+    pub num_handles: usize,
+    /// Maximum number of variables captured by a closure.
+    ///
+    /// Set to zero to effectively disable capturing variables in closures.
+    ///
+    /// Not available under `no_closure`.
+    #[cfg(not(feature = "no_closure"))]
+    pub closure_captures: usize,
+    /// Maximum number of values made shared (e.g. by being captured in closures) during an
+    /// evaluation run.
+    ///
+    /// Not available under `no_closure`.
+    #[cfg(not(feature = "no_closure"))]
+    pub num_shared_values: usize,
     /// Maximum number of scripted functions allowed.
     ///
     /// Set to zero to effectively disable defining any function.
@@ -88,6 +123,17 @@ pub struct Limits {
     /// Not available under `no_object`.
     #[cfg(not(feature = "no_object"))]
     pub map_size: Option<NonZeroUsize>,
+    /// Maximum total size of custom types, as reported by the hooks registered via
+    /// [`Engine::register_size_of`].
+    pub custom_data_size: Option<NonZeroUsize>,
+    /// Minimum amount of native stack space (in bytes) that must remain when parsing or
+    /// evaluating a script, to guard against overflowing the native stack.
+    ///
+    /// Set to zero to disable the check.
+    ///
+    /// Only available under `stack_guard`.
+    #[cfg(feature = "stack_guard")]
+    pub stack_remaining: usize,
 }
 
 impl Limits {
@@ -103,7 +149,14 @@ impl Limits {
             #[cfg(not(feature = "no_function"))]
             function_expr_depth: NonZeroUsize::new(default_limits::MAX_FUNCTION_EXPR_DEPTH),
             num_operations: None,
+            #[cfg(not(feature = "no_time"))]
+            deadline: None,
             num_variables: usize::MAX,
+            num_handles: usize::MAX,
+            #[cfg(not(feature = "no_closure"))]
+            closure_captures: usize::MAX,
+            #[cfg(not(feature = "no_closure"))]
+            num_shared_values: usize::MAX,
             #[cfg(not(feature = "no_function"))]
             num_functions: usize::MAX,
             #[cfg(not(feature = "no_module"))]
@@ -113,6 +166,9 @@ impl Limits {
             array_size: None,
             #[cfg(not(feature = "no_object"))]
             map_size: None,
+            custom_data_size: None,
+            #[cfg(feature = "stack_guard")]
+            stack_remaining: default_limits::MIN_STACK_REMAINING,
         }
     }
 }
@@ -146,6 +202,7 @@ impl Engine {
                 #[cfg(feature = "no_object")]
                 false
             }
+            || self.limits.custom_data_size.is_some()
     }
     /// Set the maximum levels of function calls allowed for a script in order to avoid
     /// infinite recursion and stack overflows.
@@ -186,6 +243,54 @@ impl Engine {
             None => 0,
         }
     }
+    /// Set the point in time after which a script is no longer allowed to run.
+    ///
+    /// The deadline is checked at every operation; once it has passed, evaluation is terminated
+    /// with [`ErrorDeadlineExceeded`][crate::EvalAltResult::ErrorDeadlineExceeded].
+    ///
+    /// The deadline applies to all evaluation runs started on this [`Engine`] until it is
+    /// changed or cleared via [`clear_deadline`][Engine::clear_deadline].
+    ///
+    /// Not available under `unchecked` or `no_time`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use rhai::{Engine, EvalAltResult};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_deadline(Instant::now() + Duration::from_millis(50));
+    ///
+    /// let err = engine.run("loop {}").unwrap_err();
+    ///
+    /// assert!(matches!(*err, EvalAltResult::ErrorDeadlineExceeded(..)));
+    /// ```
+    #[cfg(not(feature = "no_time"))]
+    #[inline(always)]
+    pub fn set_deadline(&mut self, deadline: crate::Instant) -> &mut Self {
+        self.limits.deadline = Some(deadline);
+        self
+    }
+    /// Clear the deadline set via [`set_deadline`][Engine::set_deadline].
+    ///
+    /// Not available under `unchecked` or `no_time`.
+    #[cfg(not(feature = "no_time"))]
+    #[inline(always)]
+    pub fn clear_deadline(&mut self) -> &mut Self {
+        self.limits.deadline = None;
+        self
+    }
+    /// The point in time after which a script is no longer allowed to run, if any.
+    ///
+    /// Not available under `unchecked` or `no_time`.
+    #[cfg(not(feature = "no_time"))]
+    #[inline(always)]
+    #[must_use]
+    pub const fn deadline(&self) -> Option<crate::Instant> {
+        self.limits.deadline
+    }
     /// Set the maximum number of variables allowed for a script at any instant.
     ///
     /// Not available under `unchecked`.
@@ -202,6 +307,90 @@ impl Engine {
     pub const fn max_variables(&self) -> usize {
         self.limits.num_variables
     }
+    /// Set the maximum number of [handles][crate::Handle] open at any instant during an evaluation run.
+    ///
+    /// Not available under `unchecked`.
+    #[inline(always)]
+    pub fn set_max_open_handles(&mut self, handles: usize) -> &mut Self {
+        self.limits.num_handles = handles;
+        self
+    }
+    /// The maximum number of [handles][crate::Handle] open at any instant during an evaluation run.
+    ///
+    /// Not available under `unchecked`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_open_handles(&self) -> usize {
+        self.limits.num_handles
+    }
+    /// Set the minimum amount of native stack space (in bytes) that must remain when parsing or
+    /// evaluating a script (0 to disable the check).
+    ///
+    /// When less stack space remains, parsing fails with
+    /// [`ParseErrorType::ExprTooDeep`][crate::ParseErrorType::ExprTooDeep] and evaluation fails
+    /// with [`ErrorStackOverflow`][crate::EvalAltResult::ErrorStackOverflow], instead of the
+    /// process aborting due to a stack overflow.
+    ///
+    /// The remaining stack space is estimated via the [`stacker`](https://crates.io/crates/stacker)
+    /// crate. On platforms where it cannot be determined, no check is performed.
+    ///
+    /// Not available under `unchecked`. Only available under `stack_guard`.
+    #[cfg(feature = "stack_guard")]
+    #[inline(always)]
+    pub fn set_min_stack_remaining(&mut self, bytes: usize) -> &mut Self {
+        self.limits.stack_remaining = bytes;
+        self
+    }
+    /// The minimum amount of native stack space (in bytes) that must remain when parsing or
+    /// evaluating a script (0 if there is no check).
+    ///
+    /// Not available under `unchecked`. Only available under `stack_guard`.
+    #[cfg(feature = "stack_guard")]
+    #[inline(always)]
+    #[must_use]
+    pub const fn min_stack_remaining(&self) -> usize {
+        self.limits.stack_remaining
+    }
+    /// Set the maximum number of variables that a closure can capture.
+    ///
+    /// Closures capturing more variables fail to compile with
+    /// [`ParseErrorType::TooManyCaptures`][crate::ParseErrorType::TooManyCaptures].
+    ///
+    /// Not available under `unchecked` or `no_closure`.
+    #[cfg(not(feature = "no_closure"))]
+    #[inline(always)]
+    pub fn set_max_closure_captures(&mut self, captures: usize) -> &mut Self {
+        self.limits.closure_captures = captures;
+        self
+    }
+    /// The maximum number of variables that a closure can capture.
+    ///
+    /// Not available under `unchecked` or `no_closure`.
+    #[cfg(not(feature = "no_closure"))]
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_closure_captures(&self) -> usize {
+        self.limits.closure_captures
+    }
+    /// Set the maximum number of values that can be made shared (e.g. by being captured in
+    /// closures) during an evaluation run.
+    ///
+    /// Not available under `unchecked` or `no_closure`.
+    #[cfg(not(feature = "no_closure"))]
+    #[inline(always)]
+    pub fn set_max_shared_values(&mut self, values: usize) -> &mut Self {
+        self.limits.num_shared_values = values;
+        self
+    }
+    /// The maximum number of values that can be made shared during an evaluation run.
+    ///
+    /// Not available under `unchecked` or `no_closure`.
+    #[cfg(not(feature = "no_closure"))]
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_shared_values(&self) -> usize {
+        self.limits.num_shared_values
+    }
     /// Set the maximum number of scripted functions allowed for a script at any instant.
     ///
     /// Not available under `unchecked` or `no_function`
@@ -344,4 +533,71 @@ impl Engine {
         #[cfg(feature = "no_object")]
         return 0;
     }
+    /// Set the maximum total size of custom types within a value (0 for unlimited).
+    ///
+    /// The size of each custom type is reported by the hook registered for it via
+    /// [`register_size_of`][Engine::register_size_of].  Custom types without a registered hook
+    /// have zero size.
+    ///
+    /// Not available under `unchecked`.
+    #[inline(always)]
+    pub fn set_max_custom_data_size(&mut self, max_size: usize) -> &mut Self {
+        self.limits.custom_data_size = NonZeroUsize::new(max_size);
+        self
+    }
+    /// The maximum total size of custom types within a value (0 for unlimited).
+    ///
+    /// Not available under `unchecked`.
+    #[inline]
+    #[must_use]
+    pub const fn max_custom_data_size(&self) -> usize {
+        match self.limits.custom_data_size {
+            Some(n) => n.get(),
+            None => 0,
+        }
+    }
+    /// Register a hook that reports the approximate size of a custom type.
+    ///
+    /// The unit of size is up to the host (e.g. bytes) but must be consistent among all the
+    /// hooks, as the sizes of all custom types within a value are added together and checked
+    /// against [`max_custom_data_size`][Engine::max_custom_data_size].
+    ///
+    /// Not available under `unchecked`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// #[derive(Clone)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine
+    ///     .register_type_with_name::<Buffer>("Buffer")
+    ///     .register_fn("buffer", |size: i64| Buffer(vec![0; size as usize]))
+    ///     .register_size_of(|buf: &Buffer| buf.0.len())
+    ///     .set_max_custom_data_size(1000);
+    ///
+    /// engine.run("let x = [buffer(400), buffer(400)];")?;
+    ///
+    /// assert!(engine.run("let x = [buffer(400), buffer(400)]; x.push(buffer(400));").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn register_size_of<T: Variant + Clone>(
+        &mut self,
+        size_of: impl Fn(&T) -> usize + SendSync + 'static,
+    ) -> &mut Self {
+        self.size_of.insert(
+            TypeId::of::<T>(),
+            crate::Shared::new(move |value: &Dynamic| {
+                value.read_lock::<T>().map_or(0, |v| size_of(&v))
+            }),
+        );
+        self
+    }
 }
@@ -0,0 +1,510 @@
+//! Module that defines the script minification API of [`Engine`].
+
+use crate::parser::ParseResult;
+use crate::Engine;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+impl Engine {
+    /// Minify a script, producing a smaller script with the same behavior (e.g. for embedding in
+    /// firmware images or web payloads).
+    ///
+    /// Comments and insignificant whitespace are stripped (as per
+    /// [`compact_script`][Engine::compact_script]), and variables and parameters are renamed to
+    /// short names, both within script-defined functions and at global level.
+    ///
+    /// Renaming is conservative:
+    ///
+    /// * Variables at global level that are exported, or that are used before they are declared
+    ///   (and so may be provided by the host via a [`Scope`][crate::Scope]), are never renamed.
+    /// * Names that are also used as function names, module aliases, qualified names or shorthand
+    ///   object map properties are never renamed.
+    /// * Functions (or the global level) that use custom syntax, `eval` or `is_def_var` are left
+    ///   untouched.
+    /// * Variables captured by closures keep the same new name in the closure bodies.
+    ///
+    /// Variables declared at global level therefore get new names in the [`Scope`][crate::Scope]
+    /// after running a minified script. `export` variables that the host needs to read back.
+    ///
+    /// Under `no_position` or `no_function`, no variables are renamed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let script = engine.minify_script("
+    ///     // Add one to a number
+    ///     fn add_one(number) {
+    ///         let result = number + 1;
+    ///         result
+    ///     }
+    ///
+    ///     let value = add_one(41);
+    ///     value
+    /// ")?;
+    ///
+    /// assert!(!script.contains("Add one"));
+    /// # #[cfg(not(feature = "no_position"))]
+    /// assert!(!script.contains("number") && !script.contains("result") && !script.contains("value"));
+    ///
+    /// assert_eq!(engine.eval::<rhai::INT>(&script)?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn minify_script(&self, script: impl AsRef<str>) -> ParseResult<String> {
+        #[cfg(not(feature = "no_function"))]
+        #[cfg(not(feature = "no_position"))]
+        return self.compact_script(self.rename_local_variables(script.as_ref())?);
+
+        #[cfg(any(feature = "no_function", feature = "no_position"))]
+        return self.compact_script(script);
+    }
+
+    /// Rename variables and parameters to short names.
+    ///
+    /// Formatting and comments are preserved.
+    #[cfg(not(feature = "no_function"))]
+    #[cfg(not(feature = "no_position"))]
+    fn rename_local_variables(&self, script: &str) -> ParseResult<String> {
+        use crate::ast::{ASTFlags, ASTNode, Expr, Stmt};
+        use crate::func::locked_write;
+        use crate::parser::ParseState;
+        use crate::tokenizer::{is_valid_identifier, Token, TokenizeState};
+        use crate::{ImmutableString, Locked, Position, Shared};
+        use std::collections::{BTreeMap, BTreeSet};
+        use std::iter::once;
+
+        // Parse the script, recording all tokens in the process,
+        // so that the tokenizer is driven by the parser (e.g. for interpolated strings)
+        let tokens = Shared::new(Locked::new(Vec::new()));
+
+        let recorder = {
+            let tokens = tokens.clone();
+            let token_mapper = self.token_mapper.clone();
+
+            move |token: Token, pos: Position, state: &TokenizeState| {
+                let token = match token_mapper {
+                    Some(ref func) => func(token, pos, state),
+                    None => token,
+                };
+                locked_write(&tokens).unwrap().push((token.clone(), pos));
+                token
+            }
+        };
+
+        let ast = {
+            let scripts = [script];
+            let (stream, tc) = self.lex_raw(&scripts, Some(&recorder));
+            let input = &mut stream.peekable();
+            let lib = &mut <_>::default();
+            let state = ParseState::new(None, input, tc, lib);
+
+            self.parse(
+                state,
+                #[cfg(not(feature = "no_optimize"))]
+                crate::OptimizationLevel::None,
+            )?
+        };
+
+        let tokens = std::mem::take(&mut *locked_write(&tokens).unwrap());
+
+        // Collect the names of all variables declared within statements
+        let declared_names = |stmts: &[Stmt]| {
+            let mut names = BTreeSet::<ImmutableString>::new();
+            let path = &mut Vec::new();
+
+            for stmt in stmts {
+                stmt.walk(path, &mut |path: &[ASTNode]| {
+                    match path.last() {
+                        Some(ASTNode::Stmt(Stmt::Var(x, ..))) => {
+                            names.insert(x.0.name.clone());
+                        }
+                        Some(ASTNode::Stmt(Stmt::Destructure(x, ..))) => {
+                            names.extend(x.0.variables().into_iter().map(|v| v.name.clone()));
+                        }
+                        Some(ASTNode::Stmt(Stmt::Switch(x, ..))) => {
+                            names.extend(
+                                x.1.patterns
+                                    .iter()
+                                    .flat_map(|(p, ..)| p.variables())
+                                    .map(|v| v.name.clone()),
+                            );
+                        }
+                        Some(ASTNode::Stmt(Stmt::For(x, ..))) => {
+                            names.insert(x.0.name.clone());
+                            names.extend(x.1.iter().map(|v| v.name.clone()));
+                        }
+                        Some(ASTNode::Stmt(Stmt::TryCatch(x, ..))) => {
+                            if let Expr::Variable(ref v, ..) = x.expr {
+                                names.insert(v.1.clone());
+                            }
+                        }
+                        _ => (),
+                    }
+                    true
+                });
+            }
+
+            names.retain(|name| !name.is_empty());
+            names
+        };
+
+        // Collect the local variables of each function, by name and number of parameters
+        let locals = ast
+            .iter_fn_def()
+            .map(|fn_def| {
+                let mut names = declared_names(fn_def.body.statements());
+                names.extend(fn_def.params.iter().cloned());
+                ((fn_def.name.clone(), fn_def.params.len()), names)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        // Collect the variables at global level
+        let mut globals = declared_names(ast.statements());
+        let path = &mut Vec::new();
+
+        for stmt in ast.statements() {
+            stmt.walk(path, &mut |path: &[ASTNode]| {
+                match path.last() {
+                    // Variables that may be provided by the host or another script
+                    #[cfg(not(feature = "no_module"))]
+                    Some(ASTNode::Expr(Expr::Variable(x, ..)))
+                        if x.0.is_none() && x.2.is_empty() =>
+                    {
+                        globals.remove(&x.1);
+                    }
+                    #[cfg(feature = "no_module")]
+                    Some(ASTNode::Expr(Expr::Variable(x, ..))) if x.0.is_none() => {
+                        globals.remove(&x.1);
+                    }
+                    // Exported variables
+                    Some(ASTNode::Stmt(Stmt::Var(x, options, ..)))
+                        if options.contains(ASTFlags::EXPORTED) =>
+                    {
+                        globals.remove(&x.0.name);
+                    }
+                    Some(ASTNode::Stmt(Stmt::Destructure(x, options, ..)))
+                        if options.contains(ASTFlags::EXPORTED) =>
+                    {
+                        for v in x.0.variables() {
+                            globals.remove(&v.name);
+                        }
+                    }
+                    #[cfg(not(feature = "no_module"))]
+                    Some(ASTNode::Stmt(Stmt::Export(x, ..))) => {
+                        globals.remove(&x.0.name);
+                    }
+                    _ => (),
+                }
+                true
+            });
+        }
+
+        // All names in the script, which new names must not clash with
+        let mut used = BTreeSet::new();
+        // Names that must not be renamed
+        let mut pinned = BTreeSet::new();
+        // Stack of open brackets, `true` for object map literals and patterns
+        let mut brackets = Vec::new();
+
+        for (i, (token, ..)) in tokens.iter().enumerate() {
+            let prev = i.checked_sub(1).map(|j| &tokens[j].0);
+            let next = tokens.get(i + 1).map(|(token, ..)| token);
+
+            match token {
+                Token::MapStart => brackets.push(true),
+                Token::LeftBrace | Token::InterpolatedString(..) => brackets.push(false),
+                Token::RightBrace => {
+                    brackets.pop();
+                }
+                Token::Identifier(name) => {
+                    used.insert(name.as_str());
+
+                    let is_shorthand_property = brackets.last() == Some(&true)
+                        && matches!(prev, Some(Token::MapStart | Token::Comma))
+                        && matches!(next, Some(Token::Comma | Token::RightBrace));
+
+                    #[cfg(not(feature = "no_module"))]
+                    let is_alias = matches!(prev, Some(Token::As));
+                    #[cfg(feature = "no_module")]
+                    let is_alias = false;
+
+                    // Function names, module aliases, qualified names and shorthand properties
+                    if is_shorthand_property
+                        || is_alias
+                        || matches!(prev, Some(Token::DoubleColon))
+                        || matches!(
+                            next,
+                            Some(Token::LeftParen | Token::Unit | Token::Bang | Token::DoubleColon)
+                        )
+                    {
+                        pinned.insert(name.as_str());
+                    }
+                }
+                #[cfg(not(feature = "no_custom_syntax"))]
+                Token::Custom(name) => {
+                    used.insert(name.as_str());
+                }
+                Token::Reserved(name) => {
+                    used.insert(name.as_str());
+                }
+                _ => (),
+            }
+        }
+
+        // Generate new names in order: a, b, ... z, A, B, ... Z, aa, ab, ...
+        const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+        let new_name = |mut index: usize| {
+            let mut name = String::new();
+            loop {
+                name.insert(0, CHARS[index % CHARS.len()] as char);
+                index /= CHARS.len();
+                if index == 0 {
+                    break name;
+                }
+                index -= 1;
+            }
+        };
+
+        let is_available = |name: &str| {
+            #[cfg(not(feature = "no_custom_syntax"))]
+            if self.custom_keywords.contains_key(name) {
+                return false;
+            }
+
+            is_valid_identifier(name)
+                && !used.contains(name)
+                && !self.is_keyword_reserved(name)
+                && Token::lookup_symbol_from_syntax(name).is_none()
+        };
+
+        let line_starts = once(0)
+            .chain(script.match_indices('\n').map(|(i, ..)| i + 1))
+            .collect::<Vec<_>>();
+
+        // Map a position to a byte offset in the script
+        let offset_of = |pos: Position| {
+            let start = line_starts.get(pos.line()? - 1)?;
+            let col = pos.position()? - 1;
+
+            script[*start..]
+                .char_indices()
+                .nth(col)
+                .map(|(i, ..)| start + i)
+        };
+
+        let mut edits = Vec::new();
+
+        // Rename all occurrences of variables within a region of tokens
+        let mut rename = |region: &[(Token, Position)], names: &BTreeSet<ImmutableString>| {
+            // Skip regions that may look up variables by name
+            #[cfg(not(feature = "no_custom_syntax"))]
+            if region.iter().any(|(t, ..)| matches!(t, Token::Custom(..))) {
+                return;
+            }
+            if region.iter().any(|(t, ..)| {
+                matches!(t, Token::Identifier(s) | Token::Reserved(s)
+                        if s.as_str() == crate::engine::KEYWORD_EVAL
+                            || s.as_str() == crate::engine::KEYWORD_IS_DEF_VAR)
+            }) {
+                return;
+            }
+
+            // Find all occurrences of variables
+            let mut occurrences = BTreeMap::<&str, Vec<Position>>::new();
+            let mut brackets = Vec::new();
+
+            for (j, (token, pos)) in region.iter().enumerate() {
+                let prev = j.checked_sub(1).map(|k| &region[k].0);
+                let next = region.get(j + 1).map(|(token, ..)| token);
+
+                match token {
+                    Token::MapStart => brackets.push(true),
+                    Token::LeftBrace | Token::InterpolatedString(..) => brackets.push(false),
+                    Token::RightBrace => {
+                        brackets.pop();
+                    }
+                    Token::Identifier(s) if names.contains(s.as_str()) => {
+                        #[cfg(not(feature = "no_object"))]
+                        let is_property = matches!(prev, Some(Token::Period | Token::Elvis));
+                        #[cfg(feature = "no_object")]
+                        let is_property = false;
+
+                        // Skip the type of `this`, parameter types, properties and property names
+                        if is_property
+                            || matches!(prev, Some(Token::Fn))
+                            || (brackets.is_empty() && matches!(prev, Some(Token::Colon)))
+                            || (brackets.last() == Some(&true)
+                                && matches!(next, Some(Token::Colon)))
+                        {
+                            continue;
+                        }
+                        occurrences.entry(s.as_str()).or_default().push(*pos);
+                    }
+                    _ => (),
+                }
+            }
+
+            // Most frequently used variables get the shortest names
+            let mut occurrences = occurrences
+                .into_iter()
+                .filter(|(name, ..)| !pinned.contains(name))
+                .filter_map(|(name, positions)| {
+                    let offsets = positions
+                        .into_iter()
+                        .map(|pos| offset_of(pos).filter(|&o| script[o..].starts_with(name)))
+                        .collect::<Option<Vec<_>>>()?;
+                    Some((name, offsets))
+                })
+                .collect::<Vec<_>>();
+
+            occurrences.sort_by_key(|(_, offsets)| std::cmp::Reverse(offsets.len()));
+
+            let mut index = 0;
+
+            for (name, offsets) in occurrences {
+                let alias = loop {
+                    let alias = new_name(index);
+                    index += 1;
+                    if is_available(&alias) {
+                        break alias;
+                    }
+                };
+
+                if alias.len() < name.len() {
+                    edits.extend(offsets.into_iter().map(|o| (o, name.len(), alias.clone())));
+                }
+            }
+        };
+
+        // Find all function definitions: fn [type.]name(params) { body }
+        let mut functions = Vec::new();
+        let mut i = 0;
+
+        while i < tokens.len() {
+            if !matches!(tokens[i].0, Token::Fn) {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            let mut name = None;
+            let mut num_params = 0;
+            let mut depth = 0_usize;
+            let mut in_params = false;
+            let mut in_body = false;
+            let mut end = None;
+
+            for (j, (token, ..)) in tokens.iter().enumerate().skip(start + 1) {
+                let next = tokens.get(j + 1).map(|(token, ..)| token);
+
+                match token {
+                    Token::Identifier(s) if depth == 0 && !in_body => name = Some(s.as_str()),
+                    Token::Unit if depth == 0 && !in_body => in_body = true,
+                    Token::LeftParen if depth == 0 && !in_body => {
+                        depth += 1;
+                        in_params = true;
+                        if !matches!(next, Some(Token::RightParen)) {
+                            num_params = 1;
+                        }
+                    }
+                    Token::RightParen if depth == 1 && in_params => {
+                        depth = 0;
+                        in_params = false;
+                        in_body = true;
+                    }
+                    Token::Comma
+                        if depth == 1 && in_params && !matches!(next, Some(Token::RightParen)) =>
+                    {
+                        num_params += 1;
+                    }
+                    Token::LeftParen
+                    | Token::LeftBracket
+                    | Token::LeftBrace
+                    | Token::MapStart
+                    | Token::InterpolatedString(..) => depth += 1,
+                    #[cfg(not(feature = "no_index"))]
+                    Token::QuestionBracket => depth += 1,
+                    Token::RightParen | Token::RightBracket => depth = depth.saturating_sub(1),
+                    Token::RightBrace => {
+                        depth = depth.saturating_sub(1);
+                        if depth == 0 && in_body {
+                            end = Some(j);
+                            break;
+                        }
+                    }
+                    Token::EOF => break,
+                    _ => (),
+                }
+            }
+
+            let Some(end) = end else {
+                break;
+            };
+
+            i = end + 1;
+            functions.push((start, end, name, num_params));
+        }
+
+        // Rename local variables within functions
+        for &(start, end, name, num_params) in &functions {
+            if let Some(names) = name.and_then(|name| locals.get(&(name.into(), num_params))) {
+                rename(&tokens[start..=end], names);
+            }
+        }
+
+        // Rename variables at global level, outside all functions
+        let global_tokens = tokens
+            .iter()
+            .enumerate()
+            .filter(|&(j, ..)| {
+                !functions
+                    .iter()
+                    .any(|&(start, end, ..)| (start..=end).contains(&j))
+            })
+            .map(|(.., t)| t.clone())
+            .collect::<Vec<_>>();
+
+        rename(&global_tokens, &globals);
+
+        edits.sort_by_key(|&(offset, ..)| offset);
+
+        let mut output = String::with_capacity(script.len());
+        let mut cursor = 0;
+
+        for (offset, len, alias) in edits {
+            output.push_str(&script[cursor..offset]);
+            output.push_str(&alias);
+            cursor = offset + len;
+        }
+
+        output.push_str(&script[cursor..]);
+
+        Ok(output)
+    }
+}
+
+/// Minify a script with a default [`Engine`], stripping comments and insignificant whitespace
+/// and renaming variables to short names.
+///
+/// See [`Engine::minify_script`] for details.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+/// let script = rhai::minify("let x = 40 + 2;  // the answer")?;
+///
+/// assert!(!script.contains("answer"));
+/// # Ok(())
+/// # }
+/// ```
+#[inline(always)]
+pub fn minify(script: &str) -> ParseResult<String> {
+    Engine::new().minify_script(script)
+}
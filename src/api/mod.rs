@@ -10,6 +10,8 @@ pub mod json;
 
 pub mod files;
 
+pub mod reader;
+
 pub mod register;
 
 pub mod call_fn;
@@ -20,22 +22,69 @@ pub mod optimize;
 
 pub mod limits;
 
+pub mod arithmetic;
+
 pub mod events;
 
 pub mod formatting;
 
+pub mod minify;
+
+pub mod codemod;
+
+pub mod highlight;
+
+pub mod signature_help;
+
+pub mod completion;
+
+pub mod infer;
+
 pub mod custom_syntax;
 
 pub mod build_type;
 
+pub mod interface;
+
+pub mod coverage;
+
+pub mod trace;
+
+pub mod sampling;
+
+pub mod op_profile;
+
+pub mod progress;
+
+pub mod module_tests;
+
+pub mod dap;
+
+pub mod module_graph;
+
+pub mod prelude;
+
+pub mod capabilities;
+
+pub mod codec;
+
+pub mod allow_list;
+
+pub mod taint;
+
+pub mod redact;
+
+pub mod signing;
+
 #[cfg(feature = "metadata")]
 pub mod definitions;
 
 pub mod deprecated;
 
-use crate::func::{locked_read, locked_write};
-use crate::types::StringsInterner;
-use crate::{Dynamic, Engine, Identifier};
+use crate::func::{locked_read, locked_write, SendSync};
+use crate::types::{InternerStats, SharedStrInterner, StrInterner, StringsInterner};
+use crate::{Dynamic, Engine, Identifier, Locked, Shared};
+use std::any::TypeId;
 
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
@@ -54,16 +103,18 @@ pub mod default_limits {
 
 impl Engine {
     /// Set the maximum number of strings to be interned.
+    ///
+    /// Setting it to zero removes the strings interner.
     #[inline(always)]
     pub fn set_max_strings_interned(&mut self, max: usize) -> &mut Self {
         if max == 0 {
             self.interned_strings = None;
         } else if let Some(ref interner) = self.interned_strings {
             if let Some(mut guard) = locked_write(interner) {
-                guard.set_max(max);
+                guard.set_capacity(max);
             }
         } else {
-            self.interned_strings = Some(StringsInterner::new(max).into());
+            self.set_strings_interner(StringsInterner::new(max));
         }
         self
     }
@@ -72,9 +123,60 @@ impl Engine {
     #[must_use]
     pub fn max_strings_interned(&self) -> usize {
         self.interned_strings.as_ref().map_or(0, |interner| {
-            locked_read(interner).map_or(0, |guard| guard.max())
+            locked_read(interner).map_or(0, |guard| guard.capacity())
         })
     }
+    /// Use a custom [strings interner][StrInterner] when compiling and evaluating scripts.
+    #[inline(always)]
+    pub fn set_strings_interner(&mut self, interner: impl StrInterner + 'static) -> &mut Self {
+        let interner: SharedStrInterner = Shared::new(Locked::new(interner));
+        self.interned_strings = Some(interner);
+        self
+    }
+    /// Use a [strings interner][StrInterner] shared with other [`Engine`]'s, so that strings
+    /// interned by one [`Engine`] are reused by all of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Engine;
+    ///
+    /// let engine1 = Engine::new();
+    /// let mut engine2 = Engine::new();
+    ///
+    /// engine2.set_shared_strings_interner(engine1.shared_strings_interner().unwrap());
+    ///
+    /// engine1.compile("let hello = 1; hello + 1").unwrap();
+    /// engine2.compile("let hello = 1; hello + 1").unwrap();
+    ///
+    /// assert!(engine1.string_interner_stats().hits > 0);
+    /// assert_eq!(engine1.string_interner_stats(), engine2.string_interner_stats());
+    /// ```
+    #[inline(always)]
+    pub fn set_shared_strings_interner(&mut self, interner: SharedStrInterner) -> &mut Self {
+        self.interned_strings = Some(interner);
+        self
+    }
+    /// Get the [strings interner][StrInterner] used by this [`Engine`] (if any), to be shared
+    /// with other [`Engine`]'s via [`set_shared_strings_interner`][Engine::set_shared_strings_interner].
+    #[inline(always)]
+    #[must_use]
+    pub fn shared_strings_interner(&self) -> Option<SharedStrInterner> {
+        self.interned_strings.clone()
+    }
+    /// Get the statistics (e.g. the numbers of hits, misses and evictions) of the
+    /// [strings interner][StrInterner] used by this [`Engine`].
+    ///
+    /// All statistics are zero if there is no strings interner.
+    #[inline]
+    #[must_use]
+    pub fn string_interner_stats(&self) -> InternerStats {
+        self.interned_strings
+            .as_ref()
+            .map_or_else(InternerStats::default, |interner| {
+                locked_read(interner).map_or_else(InternerStats::default, |guard| guard.stats())
+            })
+    }
 
     /// The module resolution service used by the [`Engine`].
     ///
@@ -98,7 +200,7 @@ impl Engine {
         &mut self,
         resolver: impl crate::ModuleResolver + 'static,
     ) -> &mut Self {
-        self.module_resolver = Some(Box::new(resolver));
+        self.module_resolver = Some(crate::Shared::new(resolver));
         self
     }
 
@@ -163,6 +265,55 @@ impl Engine {
         self.disabled_symbols.contains(symbol)
     }
 
+    /// Reserve an additional keyword, so that it cannot be used as a variable, constant,
+    /// function or parameter name (e.g. to keep words free for future use in a deployment).
+    ///
+    /// A reserved keyword can still be registered as a custom operator or used as a keyword in
+    /// custom syntax.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.reserve_keyword("defer");
+    ///
+    /// assert!(engine.is_keyword_reserved("defer"));
+    ///
+    /// assert!(engine.compile("let defer = 42;").is_err());
+    /// ```
+    #[inline]
+    pub fn reserve_keyword(&mut self, keyword: impl Into<Identifier>) -> &mut Self {
+        self.reserved_keywords.insert(keyword.into());
+        self
+    }
+
+    /// Is a particular keyword reserved, either by the language or via
+    /// [`reserve_keyword`][Engine::reserve_keyword]?
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// assert!(engine.is_keyword_reserved("var"));     // 'var' is reserved by the language
+    /// assert!(!engine.is_keyword_reserved("defer"));
+    ///
+    /// engine.reserve_keyword("defer");
+    ///
+    /// assert!(engine.is_keyword_reserved("defer"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_keyword_reserved(&self, keyword: &str) -> bool {
+        crate::tokenizer::is_reserved_keyword_or_symbol(keyword).0
+            || self.reserved_keywords.contains(keyword)
+    }
+
     /// Register a custom operator with a precedence into the language.
     ///
     /// Not available under `no_custom_syntax`.
@@ -242,6 +393,55 @@ impl Engine {
         Ok(self)
     }
 
+    /// Register a custom prefix for integer literals in a particular radix (from 2 to 36).
+    ///
+    /// An integer literal with a custom prefix is written as `0` followed by the prefix character,
+    /// then digits in the radix (e.g. `0h1F`), just like the built-in `0x`, `0o` and `0b` prefixes.
+    /// Underscores (`_`) can be used as separators between digits.
+    ///
+    /// The prefix must be an ASCII letter, which is case-sensitive. It cannot be one of the built-in
+    /// prefixes (`x`, `o`, `b`, in either case) or the exponent marker `e`/`E`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// // Register 'q' as the prefix for quaternary (base-4) literals
+    /// engine.register_literal_prefix('q', 4).expect("should succeed");
+    ///
+    /// assert_eq!(engine.eval_expression::<i64>("0q1_230")?, 108);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_literal_prefix(
+        &mut self,
+        prefix: char,
+        radix: u32,
+    ) -> Result<&mut Self, String> {
+        if !(2..=36).contains(&radix) {
+            return Err(format!("radix {radix} is not between 2 and 36"));
+        }
+
+        match prefix {
+            'x' | 'X' | 'o' | 'O' | 'b' | 'B' => {
+                return Err(format!("'{prefix}' is a built-in literal prefix"))
+            }
+            'e' | 'E' => return Err(format!("'{prefix}' is the exponent marker")),
+            ch if !ch.is_ascii_alphabetic() => {
+                return Err(format!("'{prefix}' is not an ASCII letter"))
+            }
+            _ => (),
+        }
+
+        self.literal_prefixes.insert(prefix, radix);
+
+        Ok(self)
+    }
+
     /// Get the default value of the custom state for each evaluation run.
     #[inline(always)]
     pub const fn default_tag(&self) -> &Dynamic {
@@ -258,4 +458,131 @@ impl Engine {
         self.def_tag = value.into();
         self
     }
+
+    /// Register a host service (e.g. a database pool or a logger), keyed by its type.
+    ///
+    /// Registered functions can access the service via
+    /// [`NativeCallContext::service`][crate::NativeCallContext::service].
+    ///
+    /// Any existing service of the same type is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, NativeCallContext};
+    ///
+    /// struct Greeter {
+    ///     greeting: String,
+    /// }
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine
+    ///     .set_service(Greeter { greeting: "Hello".into() })
+    ///     .register_fn("greet", |ctx: NativeCallContext, name: &str| {
+    ///         let greeter = ctx.service::<Greeter>().unwrap();
+    ///         format!("{}, {name}!", greeter.greeting)
+    ///     });
+    ///
+    /// assert_eq!(engine.eval::<String>(r#"greet("world")"#)?, "Hello, world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn set_service<T: SendSync + 'static>(&mut self, value: T) -> &mut Self {
+        self.services
+            .insert(TypeId::of::<T>(), crate::Shared::new(value));
+        self
+    }
+    /// Get the host service of a particular type, if any.
+    #[inline]
+    #[must_use]
+    pub fn service<T: 'static>(&self) -> Option<&T> {
+        self.services
+            .get(&TypeId::of::<T>())
+            .and_then(|s| s.downcast_ref::<T>())
+    }
+    /// Get a mutable reference to the host service of a particular type, if any.
+    ///
+    /// Returns [`None`] if the service is shared with a [forked][Engine::fork] [`Engine`] (or
+    /// the [`Engine`] it is forked from). Either drop the other [`Engine`] first, or use
+    /// [`set_service`][Engine::set_service] to replace the service with one not shared.
+    #[inline]
+    #[must_use]
+    pub fn service_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.services
+            .get_mut(&TypeId::of::<T>())
+            .and_then(crate::Shared::get_mut)
+            .and_then(|s| s.downcast_mut::<T>())
+    }
+    /// Remove the host service of a particular type, returning it if it exists.
+    ///
+    /// The service is removed but [`None`] is returned if it is still shared with a
+    /// [forked][Engine::fork] [`Engine`].
+    #[inline]
+    pub fn remove_service<T: SendSync + 'static>(&mut self) -> Option<T> {
+        self.services
+            .remove(&TypeId::of::<T>())
+            .and_then(|s| s.downcast::<T>().ok())
+            .and_then(|s| crate::Shared::try_unwrap(s).ok())
+    }
+
+    /// Set a configuration flag (e.g. whether an optional integration is available in the
+    /// deployment environment), which scripts can query via the `cfg` function.
+    ///
+    /// Any existing flag with the same name is replaced.
+    ///
+    /// Scripts get `false` from `cfg` for flags that are not set. All flags are also available in
+    /// the object map returned by the `engine_info` function, under the `cfg` property.
+    ///
+    /// Under [`OptimizationLevel::Full`][crate::OptimizationLevel::Full], calls to `cfg` with a
+    /// constant name may be evaluated when a script is compiled, so flags should be set before
+    /// compiling scripts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_cfg_flag("redis", true).set_cfg_flag("region", "eu");
+    ///
+    /// let result = engine.eval::<String>(r#"
+    ///     let store = if cfg("redis") { "redis" } else { "memory" };
+    ///     let backup = if cfg("s3") { "s3" } else { "none" };
+    ///     `${store}/${backup}/${cfg("region")}`
+    /// "#)?;
+    ///
+    /// assert_eq!(result, "redis/none/eu");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn set_cfg_flag(
+        &mut self,
+        name: impl Into<Identifier>,
+        value: impl Into<Dynamic>,
+    ) -> &mut Self {
+        self.cfg_flags.insert(name.into(), value.into());
+        self
+    }
+    /// Get the value of a configuration flag, if it is set.
+    #[inline(always)]
+    #[must_use]
+    pub fn cfg_flag(&self, name: &str) -> Option<&Dynamic> {
+        self.cfg_flags.get(name)
+    }
+    /// Get an iterator over the names and values of all configuration flags.
+    #[inline]
+    pub fn iter_cfg_flags(&self) -> impl Iterator<Item = (&str, &Dynamic)> {
+        self.cfg_flags.iter().map(|(k, v)| (k.as_str(), v))
+    }
+    /// Remove a configuration flag, returning its value if it is set.
+    #[inline(always)]
+    pub fn remove_cfg_flag(&mut self, name: &str) -> Option<Dynamic> {
+        self.cfg_flags.remove(name)
+    }
 }
@@ -0,0 +1,207 @@
+//! Module that defines the module dependency graph API of [`Engine`].
+#![cfg(not(feature = "no_module"))]
+
+use crate::func::{locked_read, locked_write};
+use crate::{Engine, Identifier, Locked, Position};
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Dependency graph of [modules][crate::Module] recorded during module resolution.
+///
+/// Each edge records that a source (empty if none, e.g. the main script) imports a module path
+/// via an `import` statement, together with the position of the statement.
+///
+/// Not available under `no_module`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleGraph {
+    /// Positions of `import` statements keyed by importing source and then by imported path.
+    edges: BTreeMap<Identifier, BTreeMap<Identifier, Vec<Position>>>,
+}
+
+impl ModuleGraph {
+    /// Create a new, empty [`ModuleGraph`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            edges: BTreeMap::new(),
+        }
+    }
+    /// Is this [`ModuleGraph`] empty?
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+    /// Clear all edges.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.edges.clear();
+    }
+    /// Record an `import` of a module path from a source at a position.
+    pub fn add_import(&mut self, source: Option<&str>, path: &str, pos: Position) {
+        let positions = self
+            .edges
+            .entry(source.unwrap_or_default().into())
+            .or_default()
+            .entry(path.into())
+            .or_default();
+
+        if !positions.contains(&pos) {
+            positions.push(pos);
+        }
+    }
+    /// Get an iterator over all edges in the form of `(source, path, position)`.
+    ///
+    /// The source is empty if none.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, Position)> {
+        self.edges.iter().flat_map(|(source, paths)| {
+            paths.iter().flat_map(move |(path, positions)| {
+                positions
+                    .iter()
+                    .map(move |&pos| (source.as_str(), path.as_str(), pos))
+            })
+        })
+    }
+    /// Get an iterator over the module paths imported by a source.
+    pub fn imports_of<'a>(&'a self, source: Option<&str>) -> impl Iterator<Item = &'a str> {
+        self.edges
+            .get(source.unwrap_or_default())
+            .into_iter()
+            .flat_map(|paths| paths.keys().map(Identifier::as_str))
+    }
+    /// Get an iterator over the sources importing a module path.
+    ///
+    /// The source is empty if none.
+    pub fn importers_of<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a str> {
+        self.edges
+            .iter()
+            .filter(move |(.., paths)| paths.contains_key(path))
+            .map(|(source, ..)| source.as_str())
+    }
+    /// Find a cycle of imports, if any.
+    ///
+    /// The cycle is returned as a list of edges in the form of `(source, path, position)`,
+    /// where the path of each edge is the source of the next, and the path of the last edge
+    /// is the source of the first.
+    #[must_use]
+    pub fn find_cycle(&self) -> Option<Vec<(&str, &str, Position)>> {
+        fn visit<'a>(
+            graph: &'a ModuleGraph,
+            node: &'a str,
+            stack: &mut Vec<(&'a str, &'a str, Position)>,
+            done: &mut BTreeSet<&'a str>,
+        ) -> Option<usize> {
+            for (path, positions) in graph.edges.get(node).into_iter().flatten() {
+                let path = path.as_str();
+
+                if done.contains(path) {
+                    continue;
+                }
+
+                stack.push((node, path, positions[0]));
+
+                // Back edge to a module currently being visited?
+                if let Some(start) = stack.iter().position(|&(source, ..)| source == path) {
+                    return Some(start);
+                }
+                if let Some(start) = visit(graph, path, stack, done) {
+                    return Some(start);
+                }
+
+                stack.pop();
+            }
+
+            done.insert(node);
+            None
+        }
+
+        let mut done = BTreeSet::new();
+
+        self.edges.keys().find_map(|source| {
+            let mut stack = Vec::new();
+            visit(self, source, &mut stack, &mut done).map(|start| stack.split_off(start))
+        })
+    }
+}
+
+impl Engine {
+    /// Turn on/off the recording of the [module dependency graph][ModuleGraph].
+    ///
+    /// When on, import edges are recorded whenever an `import` statement is evaluated, and when
+    /// modules are resolved via [`compile_into_self_contained`][Engine::compile_into_self_contained].
+    /// Turning it on again clears the graph recorded so far.
+    ///
+    /// Recording is off by default.
+    ///
+    /// Not available under `no_module`.
+    #[inline]
+    pub fn set_record_module_graph(&mut self, enable: bool) -> &mut Self {
+        self.module_graph = if enable {
+            Some(Locked::new(ModuleGraph::new()))
+        } else {
+            None
+        };
+        self
+    }
+    /// Is the [module dependency graph][ModuleGraph] being recorded?
+    ///
+    /// Not available under `no_module`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn record_module_graph(&self) -> bool {
+        self.module_graph.is_some()
+    }
+    /// Get a snapshot of the [module dependency graph][ModuleGraph] recorded so far.
+    ///
+    /// Returns [`None`] if recording is not turned on.
+    ///
+    /// Not available under `no_module`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Module, Scope};
+    /// use rhai::module_resolvers::StaticModuleResolver;
+    ///
+    /// let mut resolver = StaticModuleResolver::new();
+    /// resolver.insert("hello", Module::new());
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.set_module_resolver(resolver);
+    /// engine.set_record_module_graph(true);
+    ///
+    /// engine.compile_into_self_contained(&Scope::new(), r#"import "hello" as h;"#)?;
+    ///
+    /// let graph = engine.module_graph().unwrap();
+    /// assert_eq!(graph.imports_of(None).collect::<Vec<_>>(), ["hello"]);
+    /// assert!(graph.find_cycle().is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn module_graph(&self) -> Option<ModuleGraph> {
+        self.module_graph
+            .as_ref()
+            .and_then(|graph| locked_read(graph).map(|graph| graph.clone()))
+    }
+    /// Clear the [module dependency graph][ModuleGraph] recorded so far.
+    ///
+    /// Not available under `no_module`.
+    #[inline]
+    pub fn clear_module_graph(&self) -> &Self {
+        if let Some(mut graph) = self.module_graph.as_ref().and_then(locked_write) {
+            graph.clear();
+        }
+        self
+    }
+    /// Record an `import` of a module path from a source if recording is turned on.
+    #[inline(always)]
+    pub(crate) fn record_import(&self, source: Option<&str>, path: &str, pos: Position) {
+        if let Some(mut graph) = self.module_graph.as_ref().and_then(locked_write) {
+            graph.add_import(source, path, pos);
+        }
+    }
+}
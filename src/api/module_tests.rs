@@ -0,0 +1,176 @@
+//! Module that defines the API for running embedded script tests of a [`Module`].
+#![cfg(not(feature = "no_function"))]
+
+use crate::{
+    Engine, EvalAltResult, FuncRegistration, Identifier, ImmutableString, Module,
+    NativeCallContext, Position, RhaiResultOf, Scope, Shared, SharedModule, SmartString, ERR,
+};
+#[cfg(feature = "no_std")]
+use core_error::Error;
+#[cfg(not(feature = "no_std"))]
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// A failed embedded script test of a [`Module`].
+///
+/// Not available under `no_function`.
+#[derive(Debug)]
+pub struct ModuleTestFailure {
+    /// Path of the sub-module containing the test, separated by `::` (empty for the root module).
+    pub path: Identifier,
+    /// Name of the function tested.
+    pub fn_name: Identifier,
+    /// Script of the test.
+    pub script: SmartString,
+    /// Error raised by the test.
+    pub error: Box<EvalAltResult>,
+}
+
+impl ModuleTestFailure {
+    /// Get the position within the test script where the test failed.
+    #[inline(always)]
+    #[must_use]
+    pub fn position(&self) -> Position {
+        self.error.position()
+    }
+}
+
+impl fmt::Display for ModuleTestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "test of '{}' failed: {}", self.fn_name, self.error)
+        } else {
+            write!(
+                f,
+                "test of '{}::{}' failed: {}",
+                self.path, self.fn_name, self.error
+            )
+        }
+    }
+}
+
+impl Error for ModuleTestFailure {}
+
+/// Create a [`Module`] containing the `assert` functions available to embedded script tests.
+fn assert_module() -> SharedModule {
+    let mut module = Module::new();
+
+    FuncRegistration::new("assert").set_into_module(
+        &mut module,
+        |ctx: NativeCallContext, cond: bool| -> RhaiResultOf<()> {
+            if cond {
+                Ok(())
+            } else {
+                Err(ERR::ErrorRuntime("assertion failed".into(), ctx.position()).into())
+            }
+        },
+    );
+    FuncRegistration::new("assert").set_into_module(
+        &mut module,
+        |ctx: NativeCallContext, cond: bool, message: ImmutableString| -> RhaiResultOf<()> {
+            if cond {
+                Ok(())
+            } else {
+                Err(ERR::ErrorRuntime(message.into(), ctx.position()).into())
+            }
+        },
+    );
+
+    module.into()
+}
+
+impl Engine {
+    /// Run all embedded script tests of a [`Module`] and its sub-modules.
+    ///
+    /// Each test script is evaluated in a fresh [`Scope`] with all functions of the module
+    /// containing it (including private functions) callable without qualification. The
+    /// functions `assert(condition)` and `assert(condition, message)` are also available,
+    /// raising a runtime error when `condition` is `false`.
+    ///
+    /// A test passes if it evaluates without error.
+    ///
+    /// Returns the number of tests run, or all failed tests.
+    ///
+    /// Tests are added via [`Module::add_test`], or by marking functions in plugin modules with
+    /// `#[rhai_fn(test = "...")]`.
+    ///
+    /// Not available under `no_function`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Engine, Module};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let mut module = Module::new();
+    /// module.set_native_fn("double", |x: i64| Ok(x * 2));
+    /// module.add_test("double", "assert(double(21) == 42);");
+    /// module.add_test("double", "assert(double(1) == 3, \"bad double\");");
+    ///
+    /// let failures = engine.run_module_tests(&module).unwrap_err();
+    ///
+    /// assert_eq!(failures.len(), 1);
+    /// assert_eq!(failures[0].fn_name, "double");
+    /// assert_eq!(failures[0].position().position(), Some(1));
+    /// ```
+    pub fn run_module_tests(&self, module: &Module) -> Result<usize, Vec<ModuleTestFailure>> {
+        let asserts = assert_module();
+        let mut count = 0;
+        let mut failures = Vec::new();
+
+        self.run_module_tests_raw(
+            &asserts,
+            &Shared::new(module.clone()),
+            "",
+            &mut count,
+            &mut failures,
+        );
+
+        if failures.is_empty() {
+            Ok(count)
+        } else {
+            Err(failures)
+        }
+    }
+    /// Run all embedded script tests of a [`Module`] and its sub-modules, recording failures.
+    fn run_module_tests_raw(
+        &self,
+        asserts: &SharedModule,
+        module: &SharedModule,
+        path: &str,
+        count: &mut usize,
+        failures: &mut Vec<ModuleTestFailure>,
+    ) {
+        for (fn_name, script) in module.iter_tests() {
+            *count += 1;
+
+            let result = self.compile(script).map_err(Into::into).and_then(|ast| {
+                let global = &mut self.new_global_runtime_state();
+                global.lib.push(asserts.clone());
+                global.lib.push(module.clone());
+                self.run_ast_with_scope_raw(global, &mut Scope::new(), &ast)
+            });
+
+            if let Err(error) = result {
+                failures.push(ModuleTestFailure {
+                    path: path.into(),
+                    fn_name: fn_name.into(),
+                    script: script.into(),
+                    error,
+                });
+            }
+        }
+
+        for (name, sub_module) in module.iter_sub_modules() {
+            let path = if path.is_empty() {
+                name.into()
+            } else {
+                format!("{path}::{name}")
+            };
+            self.run_module_tests_raw(asserts, sub_module, &path, count, failures);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Module that defines the operations profiling API of [`Engine`].
+#![cfg(not(feature = "unchecked"))]
+
+use crate::eval::GlobalRuntimeState;
+use crate::func::{locked_read, locked_write};
+use crate::{Engine, Identifier, ImmutableString, Locked, Position, Shared};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{collections::BTreeMap, mem};
+
+/// Operation counts collected during script evaluation, attributed to the positions and the
+/// script-defined functions where the operations were performed.
+///
+/// Counts are keyed by source (empty if none) and by position (line and column), and
+/// separately by the name of the innermost script-defined function running (empty for the
+/// global level of the script).
+///
+/// Not available under `unchecked`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationsProfile {
+    /// Total number of operations recorded.
+    total: u64,
+    /// Operation counts keyed by source and then by (line, column).
+    positions: BTreeMap<Identifier, BTreeMap<(usize, usize), u64>>,
+    /// Operation counts keyed by function name.
+    functions: BTreeMap<Identifier, u64>,
+}
+
+impl OperationsProfile {
+    /// Create a new, empty [`OperationsProfile`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            total: 0,
+            positions: BTreeMap::new(),
+            functions: BTreeMap::new(),
+        }
+    }
+    /// Is this [`OperationsProfile`] empty?
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+    /// Clear all operation counts.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.total = 0;
+        self.positions.clear();
+        self.functions.clear();
+    }
+    /// Record one operation performed at a position within a function (empty for the global
+    /// level of the script).
+    ///
+    /// Positions that are [`NONE`][Position::NONE] are only counted towards the total and the
+    /// function.
+    pub fn record(&mut self, source: Option<&str>, fn_name: &str, pos: Position) {
+        self.total += 1;
+
+        match self.functions.get_mut(fn_name) {
+            Some(count) => *count += 1,
+            None => {
+                self.functions.insert(fn_name.into(), 1);
+            }
+        }
+
+        if let (Some(line), Some(col)) = (pos.line(), pos.position()) {
+            let source = source.unwrap_or_default();
+
+            let positions = match self.positions.get_mut(source) {
+                Some(positions) => positions,
+                None => self.positions.entry(source.into()).or_default(),
+            };
+
+            *positions.entry((line, col)).or_insert(0) += 1;
+        }
+    }
+    /// Get the total number of operations recorded.
+    #[inline(always)]
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.total
+    }
+    /// Get the number of operations performed at a position.
+    ///
+    /// Returns [`None`] if nothing is recorded at the position.
+    #[must_use]
+    pub fn count_at(&self, source: Option<&str>, pos: Position) -> Option<u64> {
+        let positions = self.positions.get(source.unwrap_or_default())?;
+        positions.get(&(pos.line()?, pos.position()?)).copied()
+    }
+    /// Get the number of operations performed within a script-defined function (empty for the
+    /// global level of the script), not including the functions it calls.
+    ///
+    /// Returns [`None`] if nothing is recorded for the function.
+    #[inline]
+    #[must_use]
+    pub fn function_count(&self, fn_name: &str) -> Option<u64> {
+        self.functions.get(fn_name).copied()
+    }
+    /// Get an iterator over all recorded positions in the form of
+    /// `(source, line, column, operations)`.
+    ///
+    /// The source is empty if none.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize, usize, u64)> {
+        self.positions.iter().flat_map(|(source, positions)| {
+            positions
+                .iter()
+                .map(move |(&(line, col), &count)| (source.as_str(), line, col, count))
+        })
+    }
+    /// Get an iterator over all recorded functions in the form of `(function name, operations)`.
+    ///
+    /// The function name is empty for the global level of the script.
+    #[inline]
+    pub fn functions(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.functions
+            .iter()
+            .map(|(name, &count)| (name.as_str(), count))
+    }
+    /// Get the positions with the most operations, in descending order of operations, in the
+    /// form of `(source, line, column, operations)`.
+    ///
+    /// At most `limit` positions are returned.
+    #[must_use]
+    pub fn hot_spots(&self, limit: usize) -> Vec<(&str, usize, usize, u64)> {
+        let mut spots: Vec<_> = self.iter().collect();
+        spots.sort_by_key(|&(.., count)| std::cmp::Reverse(count));
+        spots.truncate(limit);
+        spots
+    }
+}
+
+impl Engine {
+    /// Turn on/off profiling of the operations performed during evaluation.
+    ///
+    /// When on, each operation counted towards the
+    /// [maximum number of operations][Engine::set_max_operations] is attributed to the position
+    /// where it is performed and to the innermost script-defined function running.
+    /// Turning it on again clears all previously collected data.
+    ///
+    /// This shows which parts of a script consume the operations budget, e.g. after evaluation
+    /// fails with [`ErrorTooManyOperations`][crate::EvalAltResult::ErrorTooManyOperations].
+    ///
+    /// Not available under `unchecked`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "no_function"))]
+    /// # #[cfg(not(feature = "no_position"))]
+    /// # {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_max_operations(500);
+    /// engine.set_profile_operations(true);
+    ///
+    /// let result = engine.run("fn spin() { loop {} }\nlet x = 42;\nspin();");
+    /// assert!(result.is_err());
+    ///
+    /// let profile = engine.operations_profile().unwrap();
+    ///
+    /// // Almost all operations are spent in the loop
+    /// assert!(profile.function_count("spin").unwrap() > 490);
+    ///
+    /// let (_, line, ..) = profile.hot_spots(1)[0];
+    /// assert_eq!(line, 1);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_profile_operations(&mut self, enable: bool) -> &mut Self {
+        self.op_profile = if enable {
+            Some(Shared::new(Locked::new(OperationsProfile::new())))
+        } else {
+            None
+        };
+        self
+    }
+    /// Is profiling of the operations performed turned on?
+    ///
+    /// Not available under `unchecked`.
+    #[inline(always)]
+    #[must_use]
+    pub fn profile_operations(&self) -> bool {
+        self.op_profile.is_some()
+    }
+    /// Get a snapshot of the operations profile collected so far.
+    ///
+    /// Returns [`None`] if operations profiling is not turned on.
+    ///
+    /// Not available under `unchecked`.
+    #[must_use]
+    pub fn operations_profile(&self) -> Option<OperationsProfile> {
+        self.op_profile
+            .as_ref()
+            .and_then(|p| locked_read(p).map(|p| p.clone()))
+    }
+    /// Clear the operations profile collected so far.
+    ///
+    /// Not available under `unchecked`.
+    #[inline]
+    pub fn clear_operations_profile(&self) -> &Self {
+        if let Some(mut p) = self.op_profile.as_ref().and_then(|p| locked_write(p)) {
+            p.clear();
+        }
+        self
+    }
+    /// Record one operation at a position if operations profiling is turned on.
+    #[inline(always)]
+    pub(crate) fn track_op_profile(&self, global: &GlobalRuntimeState, pos: Position) {
+        if let Some(mut p) = global.op_profile.as_ref().and_then(|p| locked_write(p)) {
+            let fn_name = global.op_profile_fn.as_ref().map_or("", |f| f.as_str());
+            p.record(global.source(), fn_name, pos);
+        }
+    }
+    /// Set the script-defined function that operations are attributed to, if operations
+    /// profiling is turned on.
+    ///
+    /// Returns the previous function, to be restored afterwards.
+    #[inline(always)]
+    pub(crate) fn swap_op_profile_fn(
+        &self,
+        global: &mut GlobalRuntimeState,
+        fn_name: Option<ImmutableString>,
+    ) -> Option<ImmutableString> {
+        if global.op_profile.is_some() {
+            mem::replace(&mut global.op_profile_fn, fn_name)
+        } else {
+            None
+        }
+    }
+}
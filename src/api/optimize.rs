@@ -1,7 +1,7 @@
 //! Module that defines the script optimization API of [`Engine`].
 #![cfg(not(feature = "no_optimize"))]
 
-use crate::{Engine, OptimizationLevel, Scope, AST};
+use crate::{Engine, OptimizationLevel, OptimizerOptions, OptimizerPass, Scope, AST};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 
@@ -25,6 +25,109 @@ impl Engine {
         self.optimization_level
     }
 
+    /// Set the options controlling the script optimizer.
+    ///
+    /// Not available under `no_optimize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, OptimizationLevel, OptimizerOptions};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_optimization_level(OptimizationLevel::Full)
+    ///       .set_optimizer_options(OptimizerOptions::new().with_max_inline_fn_size(64));
+    ///
+    /// assert_eq!(engine.optimizer_options().max_inline_fn_size, 64);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn set_optimizer_options(&mut self, options: OptimizerOptions) -> &mut Self {
+        self.optimizer_options = options;
+        self
+    }
+
+    /// The current options controlling the script optimizer.
+    ///
+    /// Not available under `no_optimize`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn optimizer_options(&self) -> OptimizerOptions {
+        self.optimizer_options
+    }
+
+    /// Set the passes of the script optimizer, which run in order whenever a script is compiled
+    /// (or an [`AST`] is re-optimized via [`optimize_ast`][Engine::optimize_ast]).
+    ///
+    /// By default, only the [`DefaultOptimizerPass`][crate::DefaultOptimizerPass] runs.
+    /// Leaving it out disables all built-in optimizations.
+    ///
+    /// Not available under `no_optimize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # #[cfg(not(feature = "no_function"))]
+    /// # {
+    /// use rhai::{DefaultOptimizerPass, Engine, OptimizationLevel, OptimizerPass, Scope, AST};
+    ///
+    /// // A custom pass that removes all script-defined functions
+    /// fn strip_functions(_: &Engine, _: Option<&Scope>, ast: &mut AST, _: OptimizationLevel) {
+    ///     ast.clear_functions();
+    /// }
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_optimizer_passes(vec![Box::new(DefaultOptimizerPass), Box::new(strip_functions)]);
+    ///
+    /// let ast = engine.compile("fn foo() { 42 } foo()")?;
+    ///
+    /// assert!(!ast.has_functions());
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn set_optimizer_passes(&mut self, passes: Vec<Box<dyn OptimizerPass>>) -> &mut Self {
+        self.optimizer_passes = Some(passes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add a pass to the end of the script optimizer's passes.
+    ///
+    /// Not available under `no_optimize`.
+    #[inline(always)]
+    pub fn add_optimizer_pass(&mut self, pass: impl OptimizerPass + 'static) -> &mut Self {
+        self.optimizer_passes
+            .get_or_insert_with(|| {
+                let default: crate::Shared<dyn OptimizerPass> =
+                    crate::Shared::new(crate::DefaultOptimizerPass);
+                vec![default]
+            })
+            .push(crate::Shared::new(pass));
+        self
+    }
+
+    /// Iterate the passes of the script optimizer, in order.
+    ///
+    /// Not available under `no_optimize`.
+    #[inline]
+    pub fn optimizer_passes(&self) -> impl Iterator<Item = &dyn OptimizerPass> {
+        const DEFAULT: &[&dyn OptimizerPass] = &[&crate::DefaultOptimizerPass];
+
+        let passes = self.optimizer_passes.as_deref();
+
+        DEFAULT
+            .iter()
+            .copied()
+            .filter(move |_| passes.is_none())
+            .chain(passes.into_iter().flatten().map(|p| &**p))
+    }
+
     /// Optimize the [`AST`] with constants defined in an external Scope.
     /// An optimized copy of the [`AST`] is returned while the original [`AST`] is consumed.
     ///
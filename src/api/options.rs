@@ -32,6 +32,13 @@ bitflags! {
         const FAIL_ON_INVALID_MAP_PROPERTY = 0b_0001_0000_0000;
         /// Fast operators mode?
         const FAST_OPS = 0b_0010_0000_0000;
+        /// Deterministic execution mode?
+        const DETERMINISTIC = 0b_0100_0000_0000;
+        /// Are Unicode identifiers allowed?
+        #[cfg(feature = "unicode-xid-ident")]
+        const UNICODE_IDENTIFIERS = 0b_1000_0000_0000;
+        /// Are assignment expressions allowed?
+        const ASSIGN_EXPR = 0b_0001_0000_0000_0000;
     }
 }
 
@@ -57,6 +64,16 @@ impl LangOptions {
                     {
                         Self::empty().bits()
                     }
+                }
+                | {
+                    #[cfg(feature = "unicode-xid-ident")]
+                    {
+                        Self::UNICODE_IDENTIFIERS.bits()
+                    }
+                    #[cfg(not(feature = "unicode-xid-ident"))]
+                    {
+                        Self::empty().bits()
+                    }
                 },
         )
     }
@@ -115,6 +132,54 @@ impl Engine {
         self.options.set(LangOptions::STMT_EXPR, enable);
         self
     }
+    /// Are assignment expressions allowed?
+    /// Default is `false`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn allow_assignment_expressions(&self) -> bool {
+        self.options.intersects(LangOptions::ASSIGN_EXPR)
+    }
+    /// Set whether assignment expressions are allowed.
+    ///
+    /// When allowed, an assignment (or op-assignment) to a variable can be used as an expression
+    /// within parentheses, as a function call argument, on the right-hand side of another
+    /// assignment or as the initial value of a variable definition. Its value is the new value of
+    /// the variable.
+    ///
+    /// Assignment expressions are right-associative, so `x = y = 0` assigns zero to both variables.
+    ///
+    /// When not allowed (the default), assignments are statements that return `()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// assert!(engine.compile("let x = 0; let y = 0; x = y = 42;").is_err());
+    ///
+    /// engine.set_allow_assignment_expressions(true);
+    ///
+    /// assert_eq!(engine.eval::<i64>("let x = 0; let y = 0; x = y = 21; x + y")?, 42);
+    ///
+    /// let script = "
+    ///     let lines = [1, 2, 3];
+    ///     let sum = 0;
+    ///     let line;
+    ///     while (line = lines.shift()) != () { sum += line; }
+    ///     sum
+    /// ";
+    /// assert_eq!(engine.eval::<i64>(script)?, 6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn set_allow_assignment_expressions(&mut self, enable: bool) -> &mut Self {
+        self.options.set(LangOptions::ASSIGN_EXPR, enable);
+        self
+    }
     /// Is anonymous function allowed?
     /// Default is `true`.
     ///
@@ -207,4 +272,104 @@ impl Engine {
         self.options.set(LangOptions::FAST_OPS, enable);
         self
     }
+    /// Is deterministic execution mode enabled?
+    /// Default is `false`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn deterministic(&self) -> bool {
+        self.options.intersects(LangOptions::DETERMINISTIC)
+    }
+    /// Set whether deterministic execution mode is enabled.
+    ///
+    /// In deterministic mode, the same script run with the same inputs produces bit-identical
+    /// results on every machine, as required for replays and lockstep simulations.
+    ///
+    /// * _Volatile_ functions (i.e. those that may not return the same result for the same inputs,
+    ///   such as `timestamp` and `sleep`, as well as all functions registered via
+    ///   [`register_raw_fn`][Engine::register_raw_fn]) cannot be registered, and calls to
+    ///   volatile functions that are already registered fail with
+    ///   [`ErrorFunctionNotFound`][crate::EvalAltResult::ErrorFunctionNotFound].
+    ///
+    /// * Rhai has no randomness-dependent built-in functions, object maps always iterate in
+    ///   the order of their keys, and hashing seeds never affect script-visible results, so
+    ///   no further action is needed for them.
+    ///
+    /// Packages should be registered _before_ turning on deterministic mode, because standard
+    /// packages contain volatile functions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, EvalAltResult, FuncRegistration};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// FuncRegistration::new("roll_dice")
+    ///     .with_volatility(true)
+    ///     .register_into_engine(&mut engine, || 4_i64);
+    ///
+    /// engine.set_deterministic(true);
+    ///
+    /// assert_eq!(engine.eval::<i64>("40 + 2")?, 42);
+    ///
+    /// assert!(matches!(
+    ///     *engine.eval::<i64>("roll_dice()").unwrap_err(),
+    ///     EvalAltResult::ErrorFunctionNotFound(..)
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn set_deterministic(&mut self, enable: bool) -> &mut Self {
+        self.options.set(LangOptions::DETERMINISTIC, enable);
+        self
+    }
+    /// Are Unicode identifiers allowed?
+    /// Default is `true`.
+    ///
+    /// Only available under `unicode-xid-ident`.
+    #[cfg(feature = "unicode-xid-ident")]
+    #[inline(always)]
+    #[must_use]
+    pub const fn allow_unicode_identifiers(&self) -> bool {
+        self.options.intersects(LangOptions::UNICODE_IDENTIFIERS)
+    }
+    /// Set whether Unicode identifiers are allowed.
+    ///
+    /// When allowed, identifiers follow [Unicode Standard Annex #31](https://unicode.org/reports/tr31/).
+    /// Identifiers with non-ASCII characters are normalized to NFC, so that the same name typed
+    /// with different sequences of code points refers to the same variable or function.
+    /// Identifiers mixing characters from different scripts (e.g. a Cyrillic `а` within a Latin
+    /// name), which are commonly used to spoof other names, are rejected.
+    ///
+    /// When not allowed, identifiers can only contain ASCII letters, digits and underscores.
+    ///
+    /// Only available under `unicode-xid-ident`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// assert_eq!(engine.eval::<i64>("let größe = 42; größe")?, 42);
+    ///
+    /// // 'pаypal' contains a Cyrillic 'а'
+    /// assert!(engine.compile("let pаypal = 1;").is_err());
+    ///
+    /// engine.set_allow_unicode_identifiers(false);
+    ///
+    /// assert!(engine.compile("let größe = 42;").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "unicode-xid-ident")]
+    #[inline(always)]
+    pub fn set_allow_unicode_identifiers(&mut self, enable: bool) -> &mut Self {
+        self.options.set(LangOptions::UNICODE_IDENTIFIERS, enable);
+        self
+    }
 }
@@ -0,0 +1,89 @@
+//! Module that defines the prelude API of [`Engine`].
+
+use crate::{Dynamic, Engine, Module, RhaiResultOf, Scope, AST};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+impl Engine {
+    /// Set a prelude [`AST`] that is linked into every script evaluated by this [`Engine`].
+    ///
+    /// The top-level statements of the prelude are evaluated once, right here, and the resultant
+    /// constants become visible to all scripts (including their functions) as if they were
+    /// defined in the global namespace. Functions defined in the prelude can be called by all
+    /// scripts, which may override them with functions of the same name and number of parameters.
+    ///
+    /// Scripts compiled after the prelude is set are compiled against it: its constants are
+    /// recognized under [_Strict Variables_][Engine::set_strict_variables] mode and propagated
+    /// during optimization. The prelude itself is neither concatenated with nor re-optimized
+    /// for each script.
+    ///
+    /// Non-constant variables defined in the prelude are discarded.
+    ///
+    /// Any existing prelude is removed before the new prelude is evaluated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// let prelude = engine.compile("
+    ///     const ANSWER = 40;
+    ///     fn add_answer(x) { x + ANSWER }
+    /// ")?;
+    ///
+    /// engine.set_prelude(&prelude)?;
+    ///
+    /// assert_eq!(engine.eval::<i64>("add_answer(2)")?, 42);
+    /// assert_eq!(engine.eval::<i64>("fn double() { ANSWER * 2 } double()")?, 80);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_prelude(&mut self, prelude: &AST) -> RhaiResultOf<&mut Self> {
+        self.prelude = None;
+
+        let mut scope = Scope::new();
+        self.run_ast_with_scope(&mut scope, prelude)?;
+
+        #[cfg(not(feature = "no_function"))]
+        let mut module = Module::clone(prelude.shared_lib());
+        #[cfg(feature = "no_function")]
+        let mut module = Module::new();
+
+        if let Some(source) = prelude.source_raw() {
+            module.set_id(source.clone());
+        }
+
+        scope
+            .iter_inner()
+            .filter(|&(.., constant, _)| constant)
+            .for_each(|(name, _, value)| {
+                // Later definitions shadow earlier ones.
+                module.set_var(name.as_str(), Dynamic::clone(value));
+            });
+
+        module.build_index();
+
+        self.prelude = Some(module.into());
+        Ok(self)
+    }
+    /// Remove the prelude, if any, set via [`set_prelude`][Engine::set_prelude].
+    #[inline(always)]
+    pub fn clear_prelude(&mut self) -> &mut Self {
+        self.prelude = None;
+        self
+    }
+    /// Does this [`Engine`] have a prelude set via [`set_prelude`][Engine::set_prelude]?
+    #[inline(always)]
+    #[must_use]
+    pub const fn has_prelude(&self) -> bool {
+        self.prelude.is_some()
+    }
+    /// Get the value of a constant defined in the prelude, if any.
+    #[inline]
+    pub(crate) fn get_prelude_var(&self, name: &str) -> Option<Dynamic> {
+        self.prelude.as_ref().and_then(|m| m.get_var(name))
+    }
+}
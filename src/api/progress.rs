@@ -0,0 +1,51 @@
+//! Module that defines the types used for progress reporting by [`Engine`][crate::Engine].
+#![cfg(not(feature = "unchecked"))]
+
+use crate::{Dynamic, Position};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Information on the progress of an evaluation run, passed to the
+/// [progress callback][crate::Engine::on_progress].
+///
+/// Not available under `unchecked`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ProgressInfo<'a> {
+    /// Number of operations performed so far.
+    pub operations: u64,
+    /// Source of the script currently running, if any.
+    pub source: Option<&'a str>,
+    /// Position of the current operation.
+    pub position: Position,
+    /// Current nesting level of function calls.
+    pub call_depth: usize,
+    /// Time elapsed since the start of the evaluation run.
+    ///
+    /// Not available under `no_time`.
+    #[cfg(not(feature = "no_time"))]
+    pub elapsed: std::time::Duration,
+}
+
+/// Decision returned by the [progress callback][crate::Engine::on_progress] on how to proceed
+/// with an evaluation run.
+///
+/// Not available under `unchecked`.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub enum ProgressControl {
+    /// Continue running the script.
+    #[default]
+    Continue,
+    /// Terminate the script with the specified exception value.
+    ///
+    /// The value is returned as the token of [`ErrorTerminated`][crate::EvalAltResult::ErrorTerminated].
+    Abort(Dynamic),
+}
+
+impl From<Option<Dynamic>> for ProgressControl {
+    #[inline(always)]
+    fn from(value: Option<Dynamic>) -> Self {
+        value.map_or(Self::Continue, Self::Abort)
+    }
+}
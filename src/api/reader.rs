@@ -0,0 +1,191 @@
+//! Module that defines the public streaming compilation API of [`Engine`].
+#![cfg(not(feature = "no_std"))]
+
+use crate::parser::ParseState;
+use crate::{Engine, RhaiResultOf, Scope, AST, ERR};
+use std::{
+    cell::RefCell,
+    io::{self, Bytes, Read},
+    mem,
+};
+
+/// An iterator that incrementally decodes UTF-8 characters from a reader.
+///
+/// Decoding stops at the first I/O error or invalid UTF-8 sequence, which is stored for later.
+struct ReaderChars<'a, R: Read> {
+    /// Bytes of the reader.
+    bytes: Bytes<io::BufReader<R>>,
+    /// Is this the start of the input?
+    at_start: bool,
+    /// Character decoded but not yet returned, if any.
+    pending: Option<char>,
+    /// Error encountered, if any.
+    error: &'a RefCell<Option<io::Error>>,
+}
+
+impl<R: Read> ReaderChars<'_, R> {
+    /// Read the next byte, storing any error.
+    fn next_byte(&mut self) -> Option<u8> {
+        match self.bytes.next()? {
+            Ok(b) => Some(b),
+            Err(err) => {
+                *self.error.borrow_mut() = Some(err);
+                None
+            }
+        }
+    }
+    /// Decode the next character, storing any error.
+    fn next_char(&mut self) -> Option<char> {
+        let first = self.next_byte()?;
+
+        let len = match first {
+            0x00..=0x7f => return Some(first as char),
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => 0,
+        };
+
+        let mut buf = [first, 0, 0, 0];
+
+        for b in buf.iter_mut().take(len).skip(1) {
+            *b = self.next_byte()?;
+        }
+
+        match std::str::from_utf8(&buf[..len]) {
+            Ok(s) if len > 0 => s.chars().next(),
+            _ => {
+                let err = io::Error::new(io::ErrorKind::InvalidData, "stream is not valid UTF-8");
+                *self.error.borrow_mut() = Some(err);
+                None
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for ReaderChars<'_, R> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if let Some(ch) = self.pending.take() {
+            return Some(ch);
+        }
+        if self.error.borrow().is_some() {
+            return None;
+        }
+
+        let ch = self.next_char()?;
+
+        if !mem::take(&mut self.at_start) || ch != '#' {
+            return Some(ch);
+        }
+
+        match self.next_char() {
+            // Skip shebang, keeping the new-line
+            Some('!') => loop {
+                if self.next_char()? == '\n' {
+                    return Some('\n');
+                }
+            },
+            next => {
+                self.pending = next;
+                Some('#')
+            }
+        }
+    }
+}
+
+impl Engine {
+    /// Compile a script read incrementally from a [reader][Read] into an [`AST`],
+    /// which can be used later for evaluation.
+    ///
+    /// The script is fed into the tokenizer as it is read, without first materializing it in
+    /// memory, which is useful for very large generated scripts and network streams.
+    ///
+    /// A leading shebang line (i.e. one starting with `#!`) is skipped.
+    ///
+    /// Not available under `no_std`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorSystem`][crate::EvalAltResult::ErrorSystem] if the reader fails or the
+    /// script is not valid UTF-8, and [`ErrorParsing`][crate::EvalAltResult::ErrorParsing] if
+    /// the script fails to compile.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let script = "let x = 40; x + 2";
+    ///
+    /// // Any type implementing `std::io::Read` can be used.
+    /// let ast = engine.compile_from_reader(script.as_bytes())?;
+    ///
+    /// assert_eq!(engine.eval_ast::<i64>(&ast)?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn compile_from_reader(&self, reader: impl Read) -> RhaiResultOf<AST> {
+        self.compile_from_reader_with_scope(&Scope::new(), reader)
+    }
+    /// Compile a script read incrementally from a [reader][Read] into an [`AST`] using own scope,
+    /// which can be used later for evaluation.
+    ///
+    /// See [`compile_from_reader`][Engine::compile_from_reader] for details.
+    ///
+    /// Not available under `no_std`.
+    ///
+    /// ## Constants Propagation
+    ///
+    /// If not [`OptimizationLevel::None`][crate::OptimizationLevel::None], constants defined within
+    /// the scope are propagated throughout the script _including_ functions.
+    ///
+    /// This allows functions to be optimized based on dynamic global constants.
+    pub fn compile_from_reader_with_scope(
+        &self,
+        scope: &Scope,
+        reader: impl Read,
+    ) -> RhaiResultOf<AST> {
+        let error = RefCell::new(None);
+
+        let chars = ReaderChars {
+            bytes: io::BufReader::new(reader).bytes(),
+            at_start: true,
+            pending: None,
+            error: &error,
+        };
+
+        let no_scripts: [&str; 0] = [];
+        let (mut stream, tc) = self.lex(&no_scripts);
+        stream.stream.reader = Some((Box::new(chars) as Box<dyn Iterator<Item = char>>).peekable());
+
+        let input = &mut stream.peekable();
+        let lib = &mut <_>::default();
+        let state = ParseState::new(Some(scope), input, tc.clone(), lib);
+        let result = self.parse(
+            state,
+            #[cfg(not(feature = "no_optimize"))]
+            self.optimization_level,
+        );
+
+        // I/O errors take precedence because they cut the script short
+        if let Some(err) = error.borrow_mut().take() {
+            return Err(ERR::ErrorSystem("Cannot read script".into(), err.into()).into());
+        }
+
+        let mut _ast = result?;
+
+        #[cfg(feature = "metadata")]
+        {
+            let global_comments = &tc.borrow().global_comments;
+            _ast.doc = global_comments.into();
+        }
+
+        Ok(_ast)
+    }
+}
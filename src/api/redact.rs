@@ -0,0 +1,159 @@
+//! Module that defines the value redaction API of [`Engine`].
+
+use crate::func::native::OnRedactCallback;
+use crate::func::SendSync;
+use crate::types::dynamic::Union;
+use crate::{Dynamic, Engine};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Apply a redaction callback to a value and, recursively, to all values contained within it.
+///
+/// `parents` holds the shared values currently being redacted, for cycle detection.
+fn redact(
+    callback: &OnRedactCallback,
+    name: Option<&str>,
+    value: &Dynamic,
+    #[cfg(not(feature = "no_closure"))] parents: &mut Vec<*const crate::Locked<Dynamic>>,
+) -> Dynamic {
+    if let Some(replacement) = callback(name, value) {
+        return replacement;
+    }
+
+    match value.0 {
+        #[cfg(not(feature = "no_index"))]
+        Union::Array(ref arr, ..) => arr
+            .iter()
+            .map(|v| {
+                redact(
+                    callback,
+                    None,
+                    v,
+                    #[cfg(not(feature = "no_closure"))]
+                    parents,
+                )
+            })
+            .collect::<crate::Array>()
+            .into(),
+        #[cfg(not(feature = "no_object"))]
+        Union::Map(ref map, ..) => map
+            .iter()
+            .map(|(k, v)| {
+                // The prototype is a method table, not data
+                if k.as_str() == crate::types::prototype::PROP_PROTOTYPE {
+                    return (k.clone(), v.clone());
+                }
+
+                let v = redact(
+                    callback,
+                    Some(k.as_str()),
+                    v,
+                    #[cfg(not(feature = "no_closure"))]
+                    parents,
+                );
+                (k.clone(), v)
+            })
+            .collect::<crate::Map>()
+            .into(),
+        #[cfg(not(feature = "no_closure"))]
+        Union::Shared(ref cell, ..) => {
+            let ptr = crate::Shared::as_ptr(cell);
+
+            // A value that contains itself cannot be copied
+            if parents.contains(&ptr) {
+                return Dynamic::UNIT;
+            }
+
+            match crate::func::locked_read(cell) {
+                Some(v) => {
+                    parents.push(ptr);
+                    let v = redact(callback, name, &v, parents);
+                    parents.pop();
+                    v
+                }
+                None => Dynamic::UNIT,
+            }
+        }
+        _ => value.clone(),
+    }
+}
+
+impl Engine {
+    /// Register a callback that redacts values before they are rendered for output, so that
+    /// sensitive data handled by scripts never reaches logs.
+    ///
+    /// The callback is applied to the argument of `print`, `debug` and `pprint`, and to values
+    /// inspected via the debug adapter (under the `dap` feature).
+    ///
+    /// # Callback Function Signature
+    ///
+    /// `Fn(name: Option<&str>, value: &Dynamic) -> Option<Dynamic>`
+    ///
+    /// where:
+    /// * `name`: name of the object map property (or variable) holding the value, if any
+    /// * `value`: the value to render
+    ///
+    /// ## Return value
+    ///
+    /// * `Some(replacement)`: render `replacement` instead of the value
+    /// * `None`: render the value, redacting all values contained within it (i.e. items of
+    ///   arrays and properties of object maps, except for the prototype) in turn
+    ///
+    /// Redaction works on a copy, so the values seen by scripts are not changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # use std::sync::RwLock;
+    /// # use std::sync::Arc;
+    /// use rhai::Engine;
+    ///
+    /// let result = Arc::new(RwLock::new(String::new()));
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// let logger = result.clone();
+    /// engine.on_print(move |s| logger.write().unwrap().push_str(s));
+    ///
+    /// // Mask all properties named 'password'
+    /// engine.on_redact(|name, _| match name {
+    ///     Some("password") => Some("***".into()),
+    ///     _ => None,
+    /// });
+    ///
+    /// engine.run(r#"let user = #{ password: "secret" }; print(user);"#)?;
+    ///
+    /// assert_eq!(*result.read().unwrap(), r#"#{"password": "***"}"#);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn on_redact(
+        &mut self,
+        callback: impl Fn(Option<&str>, &Dynamic) -> Option<Dynamic> + SendSync + 'static,
+    ) -> &mut Self {
+        self.redact = Some(crate::Shared::new(callback));
+        self
+    }
+    /// Return a copy of a value with all redactions applied via the callback registered by
+    /// [`on_redact`][Engine::on_redact].
+    ///
+    /// `name` is the name of the variable (or object map property) holding the value, if any.
+    ///
+    /// This is useful for rendering values in custom debugger interfaces.
+    ///
+    /// A _shared_ value that contains itself is cut off with `()` where it recurs.
+    pub fn redact_value(&self, name: Option<&str>, value: &Dynamic) -> Dynamic {
+        match self.redact {
+            Some(ref callback) => redact(
+                callback.as_ref(),
+                name,
+                value,
+                #[cfg(not(feature = "no_closure"))]
+                &mut Vec::new(),
+            ),
+            None => value.clone(),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Module that defines the public function/module registration API of [`Engine`].
 
-use crate::func::{FnCallArgs, RhaiFunc, RhaiNativeFunc, SendSync};
-use crate::module::FuncRegistration;
+use crate::func::{FnCallArgs, RhaiFunc, RhaiNativeFunc, SendSync, WithHostState};
+use crate::module::{FuncMetadata, FuncRegistration};
 use crate::types::dynamic::Variant;
 use crate::{
     Dynamic, Engine, Identifier, Module, NativeCallContext, RhaiResultOf, Shared, SharedModule,
@@ -15,7 +15,7 @@ use crate::func::register::Mut;
 
 impl Engine {
     /// Get a mutable reference to the global namespace module
-    /// (which is the first module in `global_modules`).
+    /// (which is the first module in `global_modules`), copying it if it is shared.
     #[inline(always)]
     #[must_use]
     pub(crate) fn global_namespace_mut(&mut self) -> &mut Module {
@@ -25,7 +25,8 @@ impl Engine {
             self.global_modules.push(global_namespace.into());
         }
 
-        Shared::get_mut(self.global_modules.first_mut().unwrap()).unwrap()
+        // Copy the global namespace on write if it is shared with a forked engine
+        crate::func::shared_make_mut(self.global_modules.first_mut().unwrap())
     }
     /// Register a custom function with the [`Engine`].
     ///
@@ -77,6 +78,132 @@ impl Engine {
 
         self
     }
+    /// Register a custom function with the [`Engine`], taking mutable host state of type `S` as
+    /// its first parameter.
+    ///
+    /// The host state is set via [`Scope::set_host_state`][crate::Scope::set_host_state] on the
+    /// [`Scope`][crate::Scope] used for evaluation, and is _not_ a parameter of the function in
+    /// scripts. This avoids wrapping host state in `Rc<RefCell<...>>` captured in every closure.
+    ///
+    /// Calling the function returns an error if no host state of type `S` is set.
+    ///
+    /// The same assumptions as [`register_fn`][Engine::register_fn] apply.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Scope};
+    ///
+    /// #[derive(Default)]
+    /// struct Log(Vec<String>);
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn_with_state("log", |log: &mut Log, msg: &str| log.0.push(msg.into()));
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.set_host_state(Log::default());
+    ///
+    /// engine.run_with_scope(&mut scope, r#"log("hello"); log("world");"#)?;
+    ///
+    /// assert_eq!(scope.with_host_state(|log: &mut Log| log.0.join(" ")).unwrap(), "hello world");
+    ///
+    /// // No host state set
+    /// assert!(engine.run(r#"log("oops")"#).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn register_fn_with_state<
+        S: 'static,
+        A: 'static,
+        const N: usize,
+        R: Variant + Clone,
+        const F: bool,
+        FUNC,
+    >(
+        &mut self,
+        name: impl AsRef<str> + Into<Identifier>,
+        func: FUNC,
+    ) -> &mut Self
+    where
+        WithHostState<S, FUNC>: RhaiNativeFunc<A, N, true, R, F> + SendSync + 'static,
+    {
+        FuncRegistration::new(name.into()).register_into_engine(self, WithHostState::new(func));
+
+        self
+    }
+    /// Register an operator implementation scoped to the types of its operands, typically a
+    /// custom type.
+    ///
+    /// Operators registered this way are dispatched _before_ any other function resolution,
+    /// including script-defined functions, functions registered via
+    /// [`register_fn`][Engine::register_fn] and functions with [`Dynamic`] parameters.
+    /// This allows domain-specific types (e.g. matrices) to overload operators without colliding
+    /// with generic implementations of the same operator.
+    ///
+    /// The operator can be a standard operator (e.g. `*`), or a custom operator registered via
+    /// [`register_custom_operator`][Engine::register_custom_operator].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function does not take one or two parameters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Dynamic, Engine};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Matrix([i64; 4]);
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine
+    ///     .register_type_with_name::<Matrix>("Matrix")
+    ///     .register_fn("matrix", |a: i64, b: i64, c: i64, d: i64| Matrix([a, b, c, d]))
+    ///     .register_fn("trace", |m: &mut Matrix| m.0[0] + m.0[3])
+    ///     // A generic `*` for all types...
+    ///     .register_fn("*", |_: Dynamic, _: Dynamic| -> i64 { 0 })
+    ///     // ... is overridden for matrices
+    ///     .register_type_operator("*", |a: Matrix, b: Matrix| {
+    ///         let (a, b) = (a.0, b.0);
+    ///         Matrix([
+    ///             a[0] * b[0] + a[1] * b[2], a[0] * b[1] + a[1] * b[3],
+    ///             a[2] * b[0] + a[3] * b[2], a[2] * b[1] + a[3] * b[3],
+    ///         ])
+    ///     });
+    ///
+    /// assert_eq!(engine.eval::<i64>("trace(matrix(1, 2, 3, 4) * matrix(1, 0, 0, 1))")?, 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn register_type_operator<
+        A: 'static,
+        const N: usize,
+        const X: bool,
+        R: Variant + Clone,
+        const F: bool,
+    >(
+        &mut self,
+        op: impl AsRef<str> + Into<Identifier>,
+        func: impl RhaiNativeFunc<A, N, X, R, F> + SendSync + 'static,
+    ) -> &mut Self {
+        assert!(
+            N == 1 || N == 2,
+            "operator `{}` must take one or two parameters",
+            op.as_ref()
+        );
+
+        FuncRegistration::new(op.into())
+            .in_global_namespace()
+            .set_into_module(&mut self.type_operators, func);
+
+        self
+    }
     /// Register a function of the [`Engine`].
     ///
     /// # WARNING - Low Level API
@@ -95,7 +222,12 @@ impl Engine {
     /// Notice that this will _consume_ the argument, replacing it with `()`.
     ///
     /// To access the first mutable parameter, use `args.get_mut(0).unwrap()`
-    #[inline(always)]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Engine`] is in [deterministic mode][Engine::set_deterministic], because
+    /// functions registered via this method are always _volatile_.
+    #[inline]
     pub fn register_raw_fn<T: Variant + Clone>(
         &mut self,
         name: impl AsRef<str> + Into<Identifier>,
@@ -103,6 +235,13 @@ impl Engine {
         func: impl Fn(NativeCallContext, &mut FnCallArgs) -> RhaiResultOf<T> + SendSync + 'static,
     ) -> &mut Self {
         let name = name.into();
+
+        assert!(
+            !self.deterministic(),
+            "cannot register volatile function '{}' in deterministic mode",
+            name
+        );
+
         let arg_types = arg_types.as_ref();
         let is_pure = true;
 
@@ -238,6 +377,87 @@ impl Engine {
             .set_custom_type_raw(type_path, name);
         self
     }
+    /// Register a type alias, so that the type `T` is displayed (e.g. in `type_of` and in error
+    /// messages) under the same name as the type `U`.
+    ///
+    /// Aliases can be chained, and multiple types can be aliased to the same type. This is useful
+    /// for showing domain terminology for generic wrapper types, e.g. `Arc<RwLock<Rule>>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use std::sync::{Arc, RwLock};
+    /// use rhai::Engine;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Rule;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine
+    ///     .register_type_with_name::<Rule>("Rule")
+    ///     .register_type_alias::<Arc<RwLock<Rule>>, Rule>()
+    ///     .register_fn("new_rule", || Arc::new(RwLock::new(Rule)));
+    ///
+    /// assert_eq!(engine.eval::<String>("type_of(new_rule())")?, "Rule");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn register_type_alias<T: Variant + Clone, U: Variant + Clone>(&mut self) -> &mut Self {
+        self.register_type_alias_raw(type_name::<T>(), type_name::<U>())
+    }
+    /// Register a type alias, so that the type named `type_path` is displayed (e.g. in `type_of`
+    /// and in error messages) under the same name as the type named `alias_of`.
+    ///
+    /// `alias_of` may itself be an alias, or a name registered via
+    /// [`register_type_with_name`][Engine::register_type_with_name].
+    ///
+    /// # WARNING - Low Level API
+    ///
+    /// This function is low level.
+    #[inline(always)]
+    pub fn register_type_alias_raw(
+        &mut self,
+        type_path: impl Into<Identifier>,
+        alias_of: impl Into<Identifier>,
+    ) -> &mut Self {
+        self.type_aliases.insert(type_path.into(), alias_of.into());
+        self
+    }
+    /// Register a [`Handle`][crate::Handle] type for use with the [`Engine`], with a pretty-print
+    /// name for the `type_of` function.
+    ///
+    /// This also registers the functions `close` and `is_open` for the handle type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Handle, NativeCallContext};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine
+    ///     .register_handle::<String>("Connection")
+    ///     .register_fn("connect", |ctx: NativeCallContext, url: &str| {
+    ///         Handle::open(&ctx, url.to_string(), |_url| { /* disconnect here */ })
+    ///     });
+    ///
+    /// assert_eq!(
+    ///     engine.eval::<bool>(r#"let c = connect("db://test"); c.close(); c.is_open()"#)?,
+    ///     false
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn register_handle<T: SendSync + 'static>(&mut self, name: &str) -> &mut Self {
+        self.register_type_with_name::<crate::Handle<T>>(name)
+            .register_fn("close", |handle: &mut crate::Handle<T>| handle.close())
+            .register_fn("is_open", |handle: &mut crate::Handle<T>| handle.is_open())
+    }
     /// Register a type iterator for an iterable type with the [`Engine`].
     /// This is an advanced API.
     #[inline(always)]
@@ -635,11 +855,66 @@ impl Engine {
         self.global_modules.insert(1, module);
         self
     }
+    /// Register a shared [`Module`] into the global namespace of [`Engine`], keeping only the
+    /// functions that pass a filter predicate.
+    ///
+    /// The predicate is passed the [metadata][FuncMetadata] of each function in the [`Module`],
+    /// together with whether the function is volatile (i.e. may not return the same result for the
+    /// same inputs).  Functions for which the predicate returns `false` are removed.
+    ///
+    /// This is useful for stripping unwanted functions (e.g. volatile or unsafe ones) from
+    /// third-party modules without manually cloning and editing them.
+    ///
+    /// The [`Module`] is cloned only if it is shared elsewhere.
+    ///
+    /// Sub-modules and variables are **ignored**.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, FuncRegistration, Module};
+    ///
+    /// let mut module = Module::new();
+    /// module.set_native_fn("calc", |x: i64| Ok(x + 1));
+    /// module.set_native_fn("delete_everything", || Ok(()));
+    /// FuncRegistration::new("random").with_volatility(true).set_into_module(&mut module, || 42_i64);
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_global_module_filtered(module.into(), |f, is_volatile| {
+    ///     !is_volatile && !f.name.starts_with("delete_")
+    /// });
+    ///
+    /// assert_eq!(engine.eval::<i64>("calc(41)")?, 42);
+    /// assert!(engine.run("delete_everything()").is_err());
+    /// assert!(engine.run("random()").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn register_global_module_filtered(
+        &mut self,
+        module: SharedModule,
+        filter: impl Fn(&FuncMetadata, bool) -> bool,
+    ) -> &mut Self {
+        let mut module = crate::func::shared_take_or_clone(module);
+        module.retain_functions(filter);
+        self.register_global_module(module.into())
+    }
     /// Register a shared [`Module`] as a static module namespace with the [`Engine`].
     ///
     /// Functions marked [`FnNamespace::Global`][`crate::FnNamespace::Global`] and type iterators are exposed to scripts without
     /// namespace qualifications.
     ///
+    /// The name can be a path (e.g. `a::b::c`). Intermediate sub-modules (e.g. `a` and `a::b`)
+    /// are created if missing.
+    ///
+    /// If a module is already registered under the same name, the new module is merged into it,
+    /// with functions and variables of the new module overriding existing ones of the same
+    /// signatures. Use [`try_register_static_module`][Engine::try_register_static_module] to
+    /// reject such conflicts instead.
+    ///
     /// Not available under `no_module`.
     ///
     /// # Example
@@ -701,6 +976,12 @@ impl Engine {
                     m.build_index();
                     root.insert(sub_module.into(), m.into());
                 }
+            } else if let Some(existing) = root.remove(name) {
+                // Merge into the existing module
+                let mut m = crate::func::shared_take_or_clone(existing);
+                merge_static_module(&mut m, crate::func::shared_take_or_clone(module));
+                m.build_index();
+                root.insert(name.into(), m.into());
             } else if module.is_indexed() {
                 root.insert(name.into(), module);
             } else {
@@ -711,9 +992,100 @@ impl Engine {
             }
         }
 
+        fn merge_static_module(target: &mut Module, mut source: Module) {
+            let sub_modules = std::mem::take(source.get_sub_modules_mut());
+
+            target.combine(source);
+
+            for (name, sub_module) in sub_modules {
+                register_static_module_raw(target.get_sub_modules_mut(), &name, sub_module);
+            }
+        }
+
         register_static_module_raw(&mut self.global_sub_modules, name.as_ref(), module);
         self
     }
+    /// Register a shared [`Module`] as a static module namespace with the [`Engine`], failing if
+    /// any of its functions is already registered under the same name.
+    ///
+    /// This is the same as [`register_static_module`][Engine::register_static_module], except that
+    /// merging into an existing module (or any of its sub-modules) is rejected when both contain
+    /// a function of the same signature.
+    ///
+    /// Nothing is registered if an error is returned.
+    ///
+    /// Not available under `no_module`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Module};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// let mut module = Module::new();
+    /// module.set_native_fn("calc", |x: i64| Ok(x + 1));
+    /// engine.try_register_static_module("foo::bar", module.into()).unwrap();
+    ///
+    /// let mut module = Module::new();
+    /// module.set_native_fn("calc2", |x: i64| Ok(x * 2));
+    /// engine.try_register_static_module("foo::bar", module.into()).unwrap();
+    ///
+    /// assert_eq!(engine.eval::<i64>("foo::bar::calc(foo::bar::calc2(20))")?, 41);
+    ///
+    /// let mut module = Module::new();
+    /// module.set_native_fn("calc", |x: i64| Ok(x - 1));
+    ///
+    /// assert_eq!(
+    ///     engine.try_register_static_module("foo::bar", module.into()).unwrap_err(),
+    ///     "function 'calc' is already registered in module 'foo::bar'"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "no_module"))]
+    pub fn try_register_static_module(
+        &mut self,
+        name: impl AsRef<str>,
+        module: SharedModule,
+    ) -> Result<&mut Self, String> {
+        /// Find a function in `module`, or any of its sub-modules, that also exists in `existing`.
+        fn find_conflict(existing: &Module, module: &Module) -> Option<String> {
+            module
+                .iter_fn()
+                .find(|(.., m)| existing.contains_fn(m.hash))
+                .map(|(.., m)| m.name.to_string())
+                .or_else(|| {
+                    module.iter_sub_modules().find_map(|(name, m)| {
+                        let sub_module = existing.get_sub_module(name)?;
+                        find_conflict(sub_module, m).map(|f| format!("{name}::{f}"))
+                    })
+                })
+        }
+
+        let name = name.as_ref();
+        let mut path = name
+            .split(crate::engine::NAMESPACE_SEPARATOR)
+            .map(str::trim);
+
+        let mut existing = path
+            .next()
+            .and_then(|n| self.global_sub_modules.get(n))
+            .map(|m| &**m);
+
+        for n in path {
+            existing = existing.and_then(|m| m.get_sub_module(n));
+        }
+
+        if let Some(f) = existing.and_then(|m| find_conflict(m, &module)) {
+            return Err(format!(
+                "function '{f}' is already registered in module '{name}'"
+            ));
+        }
+
+        Ok(self.register_static_module(name, module))
+    }
     /// _(metadata)_ Generate a list of all registered functions.
     /// Exported under the `metadata` feature only.
     ///
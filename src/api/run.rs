@@ -1,8 +1,9 @@
 //! Module that defines the public evaluation API of [`Engine`].
 
-use crate::eval::Caches;
+use crate::eval::{Caches, GlobalRuntimeState};
+use crate::func::SendSync;
 use crate::parser::ParseState;
-use crate::{Engine, RhaiResultOf, Scope, AST};
+use crate::{Engine, RhaiResultOf, Scope, Shared, AST};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 
@@ -120,10 +121,106 @@ impl Engine {
     /// ```
     #[inline]
     pub fn run_ast_with_scope(&self, scope: &mut Scope, ast: &AST) -> RhaiResultOf<()> {
-        let caches = &mut Caches::new();
+        self.run_ast_with_scope_raw(&mut self.new_global_runtime_state(), scope, ast)
+    }
+    /// Evaluate a string as a script with typed per-run user data, returning the user data
+    /// when done.
+    ///
+    /// Registered functions can access the user data via
+    /// [`NativeCallContext::user_data`][crate::NativeCallContext::user_data].
+    ///
+    /// The user data is returned together with the result of the evaluation, even when it fails.
+    /// It is [`None`] only if it is still shared when the evaluation ends, e.g. if a
+    /// `NativeCallContextStore` created during the evaluation is kept alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, NativeCallContext};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// #[derive(Default)]
+    /// struct Request {
+    ///     user: String,
+    ///     num_queries: AtomicUsize,
+    /// }
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn("query", |ctx: NativeCallContext| {
+    ///     let request = ctx.user_data::<Request>().unwrap();
+    ///     request.num_queries.fetch_add(1, Ordering::Relaxed);
+    ///     request.user.clone()
+    /// });
+    ///
+    /// let request = Request { user: "alice".into(), ..Default::default() };
+    ///
+    /// let (result, request) = engine.run_with_context(request, "query(); query();");
+    /// result?;
+    ///
+    /// assert_eq!(request.unwrap().num_queries.load(Ordering::Relaxed), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn run_with_context<C: SendSync + 'static>(
+        &self,
+        context: C,
+        script: &str,
+    ) -> (RhaiResultOf<()>, Option<C>) {
+        match self.compile(script) {
+            Ok(ast) => self.run_ast_with_scope_and_context(&mut Scope::new(), &ast, context),
+            Err(err) => (Err(err.into()), Some(context)),
+        }
+    }
+    /// Evaluate an [`AST`] with own scope and typed per-run user data, returning the user data
+    /// when done.
+    ///
+    /// Registered functions can access the user data via
+    /// [`NativeCallContext::user_data`][crate::NativeCallContext::user_data].
+    ///
+    /// The user data is returned together with the result of the evaluation, even when it fails.
+    /// It is [`None`] only if it is still shared when the evaluation ends, e.g. if a
+    /// `NativeCallContextStore` created during the evaluation is kept alive.
+    #[inline]
+    pub fn run_ast_with_scope_and_context<C: SendSync + 'static>(
+        &self,
+        scope: &mut Scope,
+        ast: &AST,
+        context: C,
+    ) -> (RhaiResultOf<()>, Option<C>) {
         let global = &mut self.new_global_runtime_state();
+        global.user_data = Some(Shared::new(context));
+
+        let result = self.run_ast_with_scope_raw(global, scope, ast);
+
+        let context = global
+            .user_data
+            .take()
+            .and_then(|d| d.downcast::<C>().ok())
+            .and_then(|d| crate::func::native::shared_try_take(d).ok());
+
+        (result, context)
+    }
+    /// Evaluate an [`AST`] with own scope and global runtime state.
+    pub(crate) fn run_ast_with_scope_raw(
+        &self,
+        global: &mut GlobalRuntimeState,
+        scope: &mut Scope,
+        ast: &AST,
+    ) -> RhaiResultOf<()> {
+        let caches = &mut Caches::new();
         global.source = ast.source_raw().cloned();
 
+        if let Some(state) = scope.shared_host_state() {
+            global.host_state = Some(state.clone());
+        }
+
+        #[cfg(not(feature = "no_function"))]
+        if let Some(ref prelude) = self.prelude {
+            global.lib.push(prelude.clone());
+        }
         #[cfg(not(feature = "no_function"))]
         global.lib.push(ast.shared_lib().clone());
 
@@ -0,0 +1,181 @@
+//! Module that defines the call stack sampling API of [`Engine`].
+
+use crate::func::{locked_read, locked_write};
+use crate::{Engine, ImmutableString, Locked, Position, Shared};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// A frame of a script call stack captured by a [`StackSampler`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct SampledFrame {
+    /// Name of the function, or empty for the global level of the script.
+    pub fn_name: ImmutableString,
+    /// Position currently executing within this frame.
+    ///
+    /// For frames other than the innermost one, this is the position of the call to the next
+    /// function.
+    pub pos: Position,
+}
+
+impl fmt::Display for SampledFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.fn_name.is_empty() {
+            f.write_str("<script>")?;
+        } else {
+            f.write_str(&self.fn_name)?;
+        }
+        if !self.pos.is_none() {
+            write!(f, " @ {:?}", self.pos)?;
+        }
+        Ok(())
+    }
+}
+
+/// Call stack state shared between the [`Engine`] and all [`StackSampler`] handles.
+#[derive(Debug, Default)]
+struct SamplerState {
+    /// Script functions currently being called, with the positions of the calls.
+    frames: Locked<Vec<(ImmutableString, Position)>>,
+    /// Position of the statement currently executing, packed into a [`u32`].
+    pos: AtomicU32,
+}
+
+/// Pack a [`Position`] into a [`u32`].
+#[inline]
+fn pack(pos: Position) -> u32 {
+    let line = pos.line().unwrap_or(0) as u32;
+    let col = pos.position().unwrap_or(0) as u32;
+    (line << 16) | col
+}
+
+/// Unpack a [`Position`] packed via [`pack`].
+#[inline]
+fn unpack(value: u32) -> Position {
+    match value >> 16 {
+        0 => Position::NONE,
+        line => Position::new(line as u16, (value & 0xffff) as u16),
+    }
+}
+
+/// A handle to capture samples of the current script call stack during evaluation, e.g. to
+/// generate flamegraphs.
+///
+/// Created via [`Engine::stack_sampler`]. It is cheap to clone, and under the `sync` feature it
+/// can be sent to another thread to poll the call stack while a script is running on an
+/// [`Engine`].
+///
+/// Only script-defined functions are tracked. Samples are only meaningful while a single
+/// evaluation is running on the [`Engine`]; otherwise they may mix the call stacks of
+/// different evaluations.
+#[derive(Debug, Clone, Default)]
+pub struct StackSampler(Shared<SamplerState>);
+
+impl StackSampler {
+    /// Capture the current script call stack, starting from the global level of the script.
+    ///
+    /// When no script function is running, only the global level (with the position of the
+    /// last statement executed) is returned.
+    #[must_use]
+    pub fn sample(&self) -> Vec<SampledFrame> {
+        let frames = match locked_read(&self.0.frames) {
+            Some(frames) => frames.clone(),
+            None => return Vec::new(),
+        };
+        let pos = unpack(self.0.pos.load(Ordering::Relaxed));
+
+        let names =
+            std::iter::once(ImmutableString::new()).chain(frames.iter().map(|f| f.0.clone()));
+        let positions = frames.iter().map(|f| f.1).chain(std::iter::once(pos));
+
+        names
+            .zip(positions)
+            .map(|(fn_name, pos)| SampledFrame { fn_name, pos })
+            .collect()
+    }
+}
+
+impl Engine {
+    /// Turn on/off tracking of the script call stack for sampling via a [`StackSampler`].
+    ///
+    /// Tracking incurs a small overhead for each statement executed and each script-defined
+    /// function called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # #[cfg(not(feature = "no_function"))]
+    /// # {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_stack_sampling(true);
+    ///
+    /// let sampler = engine.stack_sampler().unwrap();
+    ///
+    /// // Normally, samples are captured from another thread at regular intervals
+    /// let s = sampler.clone();
+    /// engine.register_fn("sample", move || {
+    ///     let names: Vec<_> = s.sample().iter().map(|f| f.fn_name.to_string()).collect();
+    ///     names.join(";")
+    /// });
+    ///
+    /// let stack = engine.eval::<String>("fn foo() { bar() } fn bar() { sample() } foo()")?;
+    ///
+    /// assert_eq!(stack, ";foo;bar");
+    ///
+    /// // No script function is running now
+    /// assert_eq!(sampler.sample().len(), 1);
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_stack_sampling(&mut self, enable: bool) -> &mut Self {
+        self.stack_sampler = enable.then(StackSampler::default);
+        self
+    }
+    /// Get a [`StackSampler`] handle to capture samples of the script call stack.
+    ///
+    /// Returns [`None`] if call stack tracking is not turned on via
+    /// [`set_stack_sampling`][Engine::set_stack_sampling].
+    #[inline(always)]
+    #[must_use]
+    pub fn stack_sampler(&self) -> Option<StackSampler> {
+        self.stack_sampler.clone()
+    }
+    /// Record the position of the statement currently executing, if call stack tracking is
+    /// turned on.
+    #[inline(always)]
+    pub(crate) fn track_sample_pos(&self, pos: Position) {
+        if let Some(ref sampler) = self.stack_sampler {
+            sampler.0.pos.store(pack(pos), Ordering::Relaxed);
+        }
+    }
+    /// Push a frame onto the tracked call stack, if call stack tracking is turned on.
+    #[inline]
+    pub(crate) fn push_sample_frame(&self, fn_name: &ImmutableString, pos: Position) {
+        if let Some(ref sampler) = self.stack_sampler {
+            if let Some(mut frames) = locked_write(&sampler.0.frames) {
+                frames.push((fn_name.clone(), pos));
+            }
+        }
+    }
+    /// Pop a frame from the tracked call stack, if call stack tracking is turned on.
+    ///
+    /// The current position is restored to the position of the call.
+    #[inline]
+    pub(crate) fn pop_sample_frame(&self) {
+        if let Some(ref sampler) = self.stack_sampler {
+            if let Some((.., pos)) = locked_write(&sampler.0.frames).and_then(|mut f| f.pop()) {
+                sampler.0.pos.store(pack(pos), Ordering::Relaxed);
+            }
+        }
+    }
+}
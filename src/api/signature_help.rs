@@ -0,0 +1,307 @@
+//! Module that defines the signature help API of [`Engine`].
+#![cfg(feature = "metadata")]
+#![cfg(not(feature = "no_position"))]
+
+use super::highlight::TokenCategory;
+use crate::func::RhaiFunc;
+use crate::module::FuncMetadata;
+use crate::{Engine, FnAccess};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Signature of a function matching a call site, as returned by [`Engine::signature_help`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct SignatureInfo {
+    /// Signature of the function (e.g. `foo(x: i64, y: i64) -> bool`).
+    pub label: String,
+    /// Signatures of the parameters of the function (e.g. `x: i64`).
+    pub params: Vec<String>,
+    /// Doc-comments of the function, if any.
+    pub comments: Vec<String>,
+    /// Index of the parameter corresponding to the argument under the cursor, or [`None`] if the
+    /// function has too few parameters.
+    ///
+    /// For method calls, the object is the first parameter of native Rust functions, but it is
+    /// bound to `this` for script-defined functions.
+    pub active_param: Option<usize>,
+    /// Is the function script-defined?
+    pub is_script: bool,
+}
+
+/// Signature help for the function call under the cursor, as returned by
+/// [`Engine::signature_help`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct SignatureHelp {
+    /// Name of the function being called, including any namespace (e.g. `foo::bar`).
+    pub name: String,
+    /// Is the call a method call?
+    pub is_method_call: bool,
+    /// Index of the argument under the cursor.
+    pub active_arg: usize,
+    /// Signatures of all functions matching the call, ordered by number of parameters.
+    ///
+    /// This is empty if no function of that name is known.
+    pub signatures: Vec<SignatureInfo>,
+}
+
+impl Engine {
+    /// Get signature help for the function call under the cursor, i.e. the signatures of all
+    /// functions matching the call together with the parameter corresponding to the argument
+    /// under the cursor.
+    ///
+    /// `offset` is the byte offset of the cursor within the script.
+    ///
+    /// Functions registered with this [`Engine`] (including packages, static modules and the
+    /// prelude) are searched, as are script-defined functions in the script itself, which need
+    /// not compile as a whole (e.g. because it is being edited).
+    ///
+    /// Returns [`None`] if the cursor is not within the arguments of a function call.
+    ///
+    /// Not available under `no_position`. Exported under the `metadata` feature only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn("clamp", |x: i64, lo: i64, hi: i64| x.max(lo).min(hi));
+    ///
+    /// let script = "let y = clamp(x, 0, ";
+    ///
+    /// let help = engine.signature_help(script, script.len()).unwrap();
+    ///
+    /// assert_eq!(help.name, "clamp");
+    /// assert_eq!(help.active_arg, 2);
+    /// assert_eq!(help.signatures[0].label, "clamp(x: i64, lo: i64, hi: i64) -> i64");
+    /// assert_eq!(help.signatures[0].active_param, Some(2));
+    /// ```
+    #[must_use]
+    pub fn signature_help(&self, script: &str, offset: usize) -> Option<SignatureHelp> {
+        let tokens = self.tokenize_classified(script);
+        let text = |i: usize| &script[tokens[i].span.clone()];
+        let is_ident = |i: usize| tokens[i].category == TokenCategory::Identifier;
+
+        // Open brackets before the cursor: index of the token, number of commas
+        let mut brackets = Vec::<(usize, usize)>::new();
+
+        for (i, token) in tokens.iter().enumerate() {
+            if token.span.start >= offset {
+                break;
+            }
+
+            match (token.category, text(i)) {
+                (
+                    TokenCategory::Punctuation | TokenCategory::Interpolation,
+                    "(" | "[" | "{" | "#{",
+                )
+                | (TokenCategory::Operator, "?[") => brackets.push((i, 0)),
+                // Cursor within `()`
+                (TokenCategory::Punctuation, "()") if offset < token.span.end => {
+                    brackets.push((i, 0));
+                }
+                (TokenCategory::Punctuation | TokenCategory::Interpolation, ")" | "]" | "}") => {
+                    brackets.pop();
+                }
+                (TokenCategory::Punctuation, ",") => {
+                    if let Some((.., commas)) = brackets.last_mut() {
+                        *commas += 1;
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        // Find the innermost function call, skipping function definitions
+        let (open, active_arg) = brackets.iter().rev().copied().find(|&(i, ..)| {
+            matches!(text(i), "(" | "()")
+                && i > 0
+                && is_ident(i - 1)
+                && (i < 2 || text(i - 2) != "fn")
+        })?;
+
+        let name = text(open - 1);
+
+        // Namespace path, if any
+        let mut path = Vec::new();
+        let mut start = open - 1;
+
+        while start >= 2 && text(start - 1) == "::" && is_ident(start - 2) {
+            path.insert(0, text(start - 2));
+            start -= 2;
+        }
+
+        let is_method_call = path.is_empty() && start >= 1 && matches!(text(start - 1), "." | "?.");
+
+        let mut signatures = Vec::new();
+
+        let mut add_fn = |func: &RhaiFunc, f: &FuncMetadata| {
+            let is_script = func.is_script();
+
+            if f.name != name || f.access == FnAccess::Private {
+                return;
+            }
+            if is_method_call && !is_script && f.num_params == 0 {
+                return;
+            }
+
+            let index = if is_method_call && !is_script {
+                active_arg + 1
+            } else {
+                active_arg
+            };
+
+            let type_mapper = |s| self.format_param_type(s);
+
+            // Doc-comments of script-defined functions are kept in their definitions
+            #[cfg(not(feature = "no_function"))]
+            let comments = func
+                .get_script_fn_def()
+                .map_or(&f.comments, |def| &def.comments);
+            #[cfg(feature = "no_function")]
+            let comments = &f.comments;
+
+            signatures.push(SignatureInfo {
+                label: f.gen_signature(type_mapper),
+                params: f
+                    .gen_param_signatures(type_mapper)
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                comments: comments.iter().map(|c| c.to_string()).collect(),
+                active_param: (index < f.num_params).then_some(index),
+                is_script,
+            });
+        };
+
+        if path.is_empty() {
+            for m in &self.global_modules {
+                m.iter_fn().for_each(|(func, f)| add_fn(func, f));
+            }
+
+            #[cfg(not(feature = "no_module"))]
+            for m in self.global_sub_modules.values() {
+                add_global_fns(m, &mut add_fn);
+            }
+
+            if let Some(ref prelude) = self.prelude {
+                prelude.iter_fn().for_each(|(func, f)| add_fn(func, f));
+            }
+
+            #[cfg(not(feature = "no_function"))]
+            for fn_text in script_fn_defs(script, &tokens, name) {
+                if let Ok(ast) = self.compile(fn_text) {
+                    ast.shared_lib()
+                        .iter_fn()
+                        .for_each(|(func, f)| add_fn(func, f));
+                }
+            }
+        } else {
+            #[cfg(not(feature = "no_module"))]
+            if let Some(m) = self.global_sub_modules.get(path[0]).and_then(|m| {
+                path[1..]
+                    .iter()
+                    .try_fold(&**m, |m, &name| m.get_sub_module(name))
+            }) {
+                m.iter_fn().for_each(|(func, f)| add_fn(func, f));
+            }
+        }
+
+        signatures.sort_by(|a, b| {
+            a.params
+                .len()
+                .cmp(&b.params.len())
+                .then_with(|| a.label.cmp(&b.label))
+        });
+        signatures.dedup_by(|a, b| a.label == b.label);
+
+        path.push(name);
+
+        Some(SignatureHelp {
+            name: path.join(crate::tokenizer::Token::DoubleColon.literal_syntax()),
+            is_method_call,
+            active_arg,
+            signatures,
+        })
+    }
+}
+
+/// Call a function on all functions in a [`Module`] (and its sub-modules) that are exposed to
+/// the global namespace.
+#[cfg(not(feature = "no_module"))]
+pub(super) fn add_global_fns(
+    module: &crate::Module,
+    add_fn: &mut impl FnMut(&RhaiFunc, &FuncMetadata),
+) {
+    module
+        .iter_fn()
+        .filter(|(_, f)| f.namespace == crate::FnNamespace::Global)
+        .for_each(|(func, f)| add_fn(func, f));
+
+    for (_, m) in module.iter_sub_modules() {
+        add_global_fns(m, add_fn);
+    }
+}
+
+/// Get the text of all function definitions (including doc-comments) in a script for functions
+/// of a particular name.
+///
+/// Functions that are not terminated (e.g. because they are being edited) are skipped.
+#[cfg(not(feature = "no_function"))]
+fn script_fn_defs<'a>(
+    script: &'a str,
+    tokens: &[super::highlight::ClassifiedToken],
+    name: &str,
+) -> Vec<&'a str> {
+    let text = |i: usize| &script[tokens[i].span.clone()];
+    let mut defs = Vec::new();
+
+    for i in 0..tokens.len() {
+        if tokens[i].category != TokenCategory::Keyword || text(i) != "fn" {
+            continue;
+        }
+
+        // Find the name of the function, i.e. the identifier before the first `(`
+        let params = match (i + 1..tokens.len()).find(|&j| matches!(text(j), "(" | "()")) {
+            Some(j) if j > i + 1 && text(j - 1) == name => j,
+            _ => continue,
+        };
+
+        // Find the end of the function body
+        let mut depth = 0_usize;
+        let end = (params..tokens.len()).find(|&j| match text(j) {
+            "{" | "#{" => {
+                depth += 1;
+                false
+            }
+            "}" => {
+                depth = depth.saturating_sub(1);
+                depth == 0
+            }
+            _ => false,
+        });
+        let end = match end {
+            Some(end) => end,
+            None => continue,
+        };
+
+        // Include `private` and doc-comments
+        let mut start = i;
+        if start > 0 && text(start - 1) == "private" {
+            start -= 1;
+        }
+        while start > 0
+            && tokens[start - 1].category == TokenCategory::Comment
+            && (text(start - 1).starts_with("///") || text(start - 1).starts_with("/**"))
+        {
+            start -= 1;
+        }
+
+        defs.push(&script[tokens[start].span.start..tokens[end].span.end]);
+    }
+
+    defs
+}
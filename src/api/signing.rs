@@ -0,0 +1,195 @@
+//! Module that defines the script signing API of [`Engine`].
+#![cfg(feature = "signing")]
+
+use crate::{Engine, Position, RhaiResultOf, AST, ERR};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// File extension appended to a script file's name to form the name of its signature file.
+pub const SIGNATURE_EXTENSION: &str = "sig";
+
+/// Size of an HMAC-SHA256 tag in bytes.
+const DIGEST_SIZE: usize = 32;
+
+/// HMAC-SHA256 message authentication code.
+type HmacSha256 = Hmac<Sha256>;
+
+/// A signer holding a host key, used to sign script sources (or serialized [`AST`]'s) and to
+/// verify their signatures when they are loaded.
+///
+/// Signatures are HMAC-SHA256 tags in lower-case hexadecimal.
+///
+/// Set via [`Engine::set_script_signer`] to only allow scripts with valid signatures to be
+/// loaded from files (including modules loaded via
+/// [`FileModuleResolver`][crate::module_resolvers::FileModuleResolver]).
+///
+/// Only available under the `signing` feature.
+#[derive(Clone)]
+pub struct ScriptSigner {
+    /// HMAC keyed with the host key, cloned for each signature.
+    mac: HmacSha256,
+}
+
+impl fmt::Debug for ScriptSigner {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never show the key
+        f.debug_struct("ScriptSigner").finish_non_exhaustive()
+    }
+}
+
+impl ScriptSigner {
+    /// Create a new [`ScriptSigner`] with a host key.
+    #[must_use]
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            mac: HmacSha256::new_from_slice(key.as_ref()).expect("HMAC takes keys of any size"),
+        }
+    }
+    /// Calculate the HMAC of some data, ready to be finalized or verified.
+    #[must_use]
+    fn hmac(&self, data: &[u8]) -> HmacSha256 {
+        let mut mac = self.mac.clone();
+        mac.update(data);
+        mac
+    }
+    /// Sign some data (e.g. a script source or a serialized [`AST`]), returning the signature in
+    /// hexadecimal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::ScriptSigner;
+    ///
+    /// let signer = ScriptSigner::new("secret key");
+    ///
+    /// let signature = signer.sign("40 + 2");
+    ///
+    /// assert!(signer.verify("40 + 2", &signature));
+    /// assert!(!signer.verify("40 + 3", &signature));
+    /// ```
+    #[must_use]
+    pub fn sign(&self, data: impl AsRef<[u8]>) -> String {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+
+        self.hmac(data.as_ref())
+            .finalize()
+            .into_bytes()
+            .iter()
+            .flat_map(|&b| [HEX[(b >> 4) as usize], HEX[(b & 0x0f) as usize]])
+            .map(char::from)
+            .collect()
+    }
+    /// Verify the signature (in hexadecimal) of some data.
+    ///
+    /// Leading and trailing whitespace in the signature is ignored.
+    #[must_use]
+    pub fn verify(&self, data: impl AsRef<[u8]>, signature: &str) -> bool {
+        let signature = signature.trim().as_bytes();
+
+        if signature.len() != DIGEST_SIZE * 2 {
+            return false;
+        }
+
+        let nibble = |c: u8| char::from(c).to_digit(16).map(|n| n as u8);
+        let mut expected = [0; DIGEST_SIZE];
+
+        for (b, pair) in expected.iter_mut().zip(signature.chunks_exact(2)) {
+            match (nibble(pair[0]), nibble(pair[1])) {
+                (Some(hi), Some(lo)) => *b = (hi << 4) | lo,
+                _ => return false,
+            }
+        }
+
+        // Compare in constant time
+        self.hmac(data.as_ref()).verify_slice(&expected).is_ok()
+    }
+}
+
+impl Engine {
+    /// Set the [`ScriptSigner`] used to verify the signatures of scripts.
+    ///
+    /// Once set, script files are only loaded (e.g. via [`compile_file`][Engine::compile_file],
+    /// [`run_file`][Engine::run_file] or
+    /// [`FileModuleResolver`][crate::module_resolvers::FileModuleResolver]) if a signature file
+    /// with the same name plus a `.sig` extension (e.g. `script.rhai.sig`) sits next to it and
+    /// contains a valid signature of the file's contents.
+    ///
+    /// Only available under the `signing` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, ScriptSigner};
+    ///
+    /// let signer = ScriptSigner::new("secret key");
+    /// let script = "40 + 2";
+    /// let signature = signer.sign(script);
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.set_script_signer(signer);
+    ///
+    /// let ast = engine.compile_signed(script, &signature)?;
+    /// assert_eq!(engine.eval_ast::<i64>(&ast)?, 42);
+    ///
+    /// assert!(engine.compile_signed("40 + 3", &signature).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn set_script_signer(&mut self, signer: ScriptSigner) -> &mut Self {
+        self.script_signer = Some(signer);
+        self
+    }
+    /// The [`ScriptSigner`] used to verify the signatures of scripts, if any.
+    ///
+    /// Only available under the `signing` feature.
+    #[inline(always)]
+    #[must_use]
+    pub const fn script_signer(&self) -> Option<&ScriptSigner> {
+        self.script_signer.as_ref()
+    }
+    /// Verify a script's signature with the [`ScriptSigner`] of the [`Engine`].
+    ///
+    /// Always succeeds if no [`ScriptSigner`] is set, unless `required` is `true`.
+    pub(crate) fn verify_signature(
+        &self,
+        name: &str,
+        data: &[u8],
+        signature: Option<&str>,
+        required: bool,
+    ) -> RhaiResultOf<()> {
+        let is_valid = match (&self.script_signer, signature) {
+            (None, ..) => !required,
+            (Some(..), None) => false,
+            (Some(signer), Some(signature)) => signer.verify(data, signature),
+        };
+
+        if is_valid {
+            Ok(())
+        } else {
+            Err(ERR::ErrorInvalidSignature(name.into(), Position::NONE).into())
+        }
+    }
+    /// Compile a string into an [`AST`] after verifying its signature (in hexadecimal) with the
+    /// [`ScriptSigner`] set via [`set_script_signer`][Engine::set_script_signer].
+    ///
+    /// Only available under the `signing` feature.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if no [`ScriptSigner`] is set, if the signature is invalid, or if the
+    /// script fails to compile.
+    pub fn compile_signed(&self, script: impl AsRef<str>, signature: &str) -> RhaiResultOf<AST> {
+        let script = script.as_ref();
+
+        self.verify_signature("<string>", script.as_bytes(), Some(signature), true)?;
+
+        Ok(self.compile(script)?)
+    }
+}
@@ -0,0 +1,145 @@
+//! Module that defines the taint tracking API of [`Engine`].
+
+use crate::func::FnCallArgs;
+use crate::{Dynamic, Engine, Identifier, Position, RhaiResultOf, ERR};
+use std::collections::BTreeSet;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Functions marked as taint sources and taint sinks.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TaintRules {
+    /// Functions returning tainted values.
+    sources: BTreeSet<Identifier>,
+    /// Functions rejecting tainted values.
+    sinks: BTreeSet<Identifier>,
+}
+
+impl TaintRules {
+    /// Check the arguments of a call to a native function.
+    ///
+    /// Returns `true` if the result of the call should be tainted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorTaintedValue`][ERR::ErrorTaintedValue] if the function is a taint sink
+    /// and any argument contains a tainted value.
+    pub(crate) fn check_call(
+        &self,
+        name: &str,
+        args: &FnCallArgs,
+        pos: Position,
+    ) -> RhaiResultOf<bool> {
+        if self.sinks.contains(name) && args.iter().any(|v| contains_taint(v)) {
+            return Err(ERR::ErrorTaintedValue(name.to_string(), pos).into());
+        }
+
+        Ok(self.sources.contains(name) || args.iter().any(|v| v.is_tainted()))
+    }
+}
+
+/// Is a [`Dynamic`] value, or any value contained within it, tainted?
+fn contains_taint(value: &Dynamic) -> bool {
+    if value.is_tainted() {
+        return true;
+    }
+
+    match value.0 {
+        #[cfg(not(feature = "no_index"))]
+        crate::types::dynamic::Union::Array(ref arr, ..) => arr.iter().any(contains_taint),
+        #[cfg(not(feature = "no_object"))]
+        crate::types::dynamic::Union::Map(ref map, ..) => map.values().any(contains_taint),
+        #[cfg(not(feature = "no_closure"))]
+        crate::types::dynamic::Union::Shared(ref cell, ..) => {
+            crate::func::locked_read(cell).map_or(false, |v| contains_taint(&v))
+        }
+        _ => false,
+    }
+}
+
+impl Engine {
+    /// Mark a native function as a _taint source_, turning on taint tracking.
+    ///
+    /// Values returned by a taint source are _tainted_. During evaluation, the taint is carried
+    /// over to the results of all native function calls and operators with tainted arguments, to
+    /// strings built via interpolation, to characters and slices of tainted strings, and to the
+    /// items of `for` loops over tainted values. Native functions that modify their first
+    /// argument taint it if any argument is tainted.
+    ///
+    /// Tainted values are rejected by [taint sinks][Engine::register_taint_sink].
+    ///
+    /// Built-in operators are never short-circuited while taint tracking is on, even under
+    /// [fast operators mode][Engine::set_fast_operators].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, EvalAltResult};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine
+    ///     .register_fn("user_input", || "Robert'); DROP TABLE students;--".to_string())
+    ///     .register_fn("sql_exec", |_sql: &str| ())
+    ///     .register_taint_source("user_input")
+    ///     .register_taint_sink("sql_exec");
+    ///
+    /// let result = engine.run(
+    ///     r#"
+    ///         let name = user_input();
+    ///         sql_exec(`SELECT * FROM students WHERE name = '${name}'`);
+    ///     "#,
+    /// );
+    ///
+    /// assert!(matches!(
+    ///     *result.unwrap_err(),
+    ///     EvalAltResult::ErrorTaintedValue(ref f, ..) if f == "sql_exec"
+    /// ));
+    ///
+    /// engine.run(r#"sql_exec("SELECT * FROM students")"#)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn register_taint_source(&mut self, name: impl Into<Identifier>) -> &mut Self {
+        self.taint_rules
+            .get_or_insert_with(Default::default)
+            .sources
+            .insert(name.into());
+        self
+    }
+    /// Mark a native function as a _taint sink_, turning on taint tracking.
+    ///
+    /// Calling a taint sink with any argument that is tainted (or, for arrays and object maps,
+    /// contains a tainted value) raises [`ErrorTaintedValue`][ERR::ErrorTaintedValue], which
+    /// cannot be caught by scripts.
+    ///
+    /// See [`register_taint_source`][Engine::register_taint_source] for details.
+    #[inline]
+    pub fn register_taint_sink(&mut self, name: impl Into<Identifier>) -> &mut Self {
+        self.taint_rules
+            .get_or_insert_with(Default::default)
+            .sinks
+            .insert(name.into());
+        self
+    }
+    /// Is taint tracking turned on?
+    #[inline(always)]
+    #[must_use]
+    pub const fn taint_tracking(&self) -> bool {
+        self.taint_rules.is_some()
+    }
+    /// Turn on/off taint tracking.
+    ///
+    /// Turning it off removes all taint sources and sinks.
+    #[inline]
+    pub fn set_taint_tracking(&mut self, enable: bool) -> &mut Self {
+        if !enable {
+            self.taint_rules = None;
+        } else if self.taint_rules.is_none() {
+            self.taint_rules = Some(Default::default());
+        }
+        self
+    }
+}
@@ -0,0 +1,398 @@
+//! Module that defines the execution trace recording API of [`Engine`].
+
+use crate::func::SendSync;
+use crate::{Dynamic, Engine, ImmutableString, Position};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{collections::BTreeMap, fmt};
+
+/// Maximum number of characters in the digest of a value.
+const MAX_DIGEST_LEN: usize = 64;
+
+/// An event recorded in an execution trace.
+///
+/// Values are recorded as digests, i.e. their debug representations truncated to a maximum
+/// length, so that traces stay compact.
+///
+/// Each event is displayed as one line in a compact text format, which can be parsed back via
+/// [`TraceEvent::parse`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TraceEvent {
+    /// A statement is entered.
+    Statement(Position),
+    /// A function is called.
+    /// Wrapped values are the name of the function and the digests of the arguments.
+    Call(ImmutableString, Vec<String>, Position),
+    /// A value is assigned to a variable.
+    /// Wrapped values are the name of the variable and the digest of the value.
+    Assign(ImmutableString, String, Position),
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (tag, pos) = match self {
+            Self::Statement(pos) => ("S", pos),
+            Self::Call(.., pos) => ("C", pos),
+            Self::Assign(.., pos) => ("A", pos),
+        };
+
+        f.write_str(tag)?;
+
+        match (pos.line(), pos.position()) {
+            (Some(line), Some(col)) => write!(f, "\t{line}:{col}")?,
+            (Some(line), None) => write!(f, "\t{line}:0")?,
+            _ => f.write_str("\t-")?,
+        }
+
+        match self {
+            Self::Statement(..) => (),
+            Self::Call(name, args, ..) => {
+                write!(f, "\t{}", escape(name))?;
+                for arg in args {
+                    write!(f, "\t{}", escape(arg))?;
+                }
+            }
+            Self::Assign(name, value, ..) => {
+                write!(f, "\t{}\t{}", escape(name), escape(value))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TraceEvent {
+    /// Get the [position][Position] of this event.
+    #[inline]
+    #[must_use]
+    pub const fn position(&self) -> Position {
+        match self {
+            Self::Statement(pos) | Self::Call(.., pos) | Self::Assign(.., pos) => *pos,
+        }
+    }
+    /// Get the digest of a value, i.e. its debug representation truncated to a maximum length.
+    #[must_use]
+    pub fn digest(value: &Dynamic) -> String {
+        let mut text = format!("{value:?}");
+
+        if let Some((index, ..)) = text.char_indices().nth(MAX_DIGEST_LEN) {
+            text.truncate(index);
+            text.push('…');
+        }
+
+        text
+    }
+    /// Parse an event from one line in the compact text format.
+    ///
+    /// Returns [`None`] if the line is not a valid event.
+    #[must_use]
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let tag = fields.next()?;
+
+        let pos = match fields.next()? {
+            "-" => Position::NONE,
+            pos => {
+                let (line, col) = pos.split_once(':')?;
+                match line.parse().ok()? {
+                    0 => return None,
+                    line => Position::new(line, col.parse().ok()?),
+                }
+            }
+        };
+
+        let mut fields = fields.map(unescape);
+
+        match tag {
+            "S" => Some(Self::Statement(pos)),
+            "C" => Some(Self::Call(fields.next()?.into(), fields.collect(), pos)),
+            "A" => match (fields.next(), fields.next(), fields.next()) {
+                (Some(name), Some(value), None) => Some(Self::Assign(name.into(), value, pos)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Escape tabs, line breaks and backslashes in a field.
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '\\' => output.push_str("\\\\"),
+            '\t' => output.push_str("\\t"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            ch => output.push(ch),
+        }
+    }
+
+    output
+}
+
+/// Reverse [`escape`].
+fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(ch) = chars.next() {
+        match (ch, chars.clone().next()) {
+            ('\\', Some(next @ ('\\' | 't' | 'n' | 'r'))) => {
+                chars.next();
+                output.push(match next {
+                    't' => '\t',
+                    'n' => '\n',
+                    'r' => '\r',
+                    _ => '\\',
+                });
+            }
+            (ch, ..) => output.push(ch),
+        }
+    }
+
+    output
+}
+
+/// An execution trace, i.e. a stream of [events][TraceEvent] recorded during evaluation.
+///
+/// A [`Trace`] is displayed in a compact text format (one event per line) that can be stored
+/// and later parsed back via [`Trace::parse`], e.g. to [replay][Trace::replay] it offline.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Trace {
+    /// Events of the trace, in order.
+    events: Vec<TraceEvent>,
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for event in &self.events {
+            writeln!(f, "{event}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Extend<TraceEvent> for Trace {
+    #[inline(always)]
+    fn extend<T: IntoIterator<Item = TraceEvent>>(&mut self, iter: T) {
+        self.events.extend(iter);
+    }
+}
+
+impl Trace {
+    /// Create a new, empty [`Trace`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { events: Vec::new() }
+    }
+    /// Append an event to the end of this [`Trace`].
+    #[inline(always)]
+    pub fn push(&mut self, event: TraceEvent) {
+        self.events.push(event);
+    }
+    /// Get all events in this [`Trace`], in order.
+    #[inline(always)]
+    #[must_use]
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+    /// Get the number of events in this [`Trace`].
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+    /// Is this [`Trace`] empty?
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+    /// Clear all events.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+    /// Parse a [`Trace`] in the compact text format.
+    ///
+    /// Empty lines are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the line number (1-based) of the first line that is not a valid event.
+    pub fn parse(text: &str) -> Result<Self, usize> {
+        text.lines()
+            .enumerate()
+            .filter(|(.., line)| !line.trim().is_empty())
+            .map(|(i, line)| TraceEvent::parse(line.trim_end_matches('\r')).ok_or(i + 1))
+            .collect::<Result<_, _>>()
+            .map(|events| Self { events })
+    }
+    /// Create a [`TraceReplay`] to step through this [`Trace`].
+    #[inline(always)]
+    #[must_use]
+    pub fn replay(&self) -> TraceReplay<'_> {
+        TraceReplay {
+            events: &self.events,
+            index: 0,
+        }
+    }
+}
+
+/// Cursor for stepping forwards and backwards through a [`Trace`], created via
+/// [`Trace::replay`].
+///
+/// The cursor starts before the first event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceReplay<'a> {
+    /// Events of the trace.
+    events: &'a [TraceEvent],
+    /// Number of events stepped through.
+    index: usize,
+}
+
+impl<'a> Iterator for TraceReplay<'a> {
+    type Item = &'a TraceEvent;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step()
+    }
+}
+
+impl<'a> TraceReplay<'a> {
+    /// Step forward to the next event, returning it.
+    ///
+    /// Returns [`None`] if the end of the trace is reached.
+    #[inline]
+    pub fn step(&mut self) -> Option<&'a TraceEvent> {
+        let event = self.events.get(self.index)?;
+        self.index += 1;
+        Some(event)
+    }
+    /// Step back to the previous event, returning it.
+    ///
+    /// Returns [`None`] if the start of the trace is reached.
+    #[inline]
+    pub fn step_back(&mut self) -> Option<&'a TraceEvent> {
+        if self.index <= 1 {
+            self.index = 0;
+            return None;
+        }
+        self.index -= 1;
+        self.current()
+    }
+    /// Step forward until an event matching a predicate, returning it.
+    ///
+    /// Returns [`None`] if the end of the trace is reached without a match.
+    #[inline]
+    pub fn step_until(
+        &mut self,
+        mut predicate: impl FnMut(&TraceEvent) -> bool,
+    ) -> Option<&'a TraceEvent> {
+        while let Some(event) = self.step() {
+            if predicate(event) {
+                return Some(event);
+            }
+        }
+        None
+    }
+    /// Move the cursor to just after the event at an index (0-based), or to the start of the
+    /// trace if [`None`].
+    ///
+    /// Indices beyond the end of the trace move the cursor to the end.
+    #[inline]
+    pub fn seek(&mut self, index: Option<usize>) {
+        self.index = index.map_or(0, |i| (i + 1).min(self.events.len()));
+    }
+    /// Get the number of events stepped through so far.
+    #[inline(always)]
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+    /// Get the current event, i.e. the last one stepped through.
+    ///
+    /// Returns [`None`] if the cursor is at the start of the trace.
+    #[inline]
+    #[must_use]
+    pub fn current(&self) -> Option<&'a TraceEvent> {
+        self.index.checked_sub(1).map(|i| &self.events[i])
+    }
+    /// Get the digests of the values last assigned to each variable up to (and including)
+    /// the current event.
+    #[must_use]
+    pub fn variables(&self) -> BTreeMap<&'a str, &'a str> {
+        self.events[..self.index]
+            .iter()
+            .filter_map(|event| match event {
+                TraceEvent::Assign(name, value, ..) => Some((name.as_str(), value.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Engine {
+    /// Register a callback that records an execution trace during evaluation.
+    ///
+    /// The callback is called with a [`TraceEvent`] each time a statement is entered, a function
+    /// with a valid name (i.e. not an operator, property access or indexer) is called, or a value
+    /// is assigned to a variable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # use std::sync::RwLock;
+    /// # use std::sync::Arc;
+    /// use rhai::{Engine, Trace, TraceEvent};
+    ///
+    /// let trace = Arc::new(RwLock::new(Trace::new()));
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// let recorder = trace.clone();
+    /// engine.on_trace(move |event| recorder.write().unwrap().push(event.clone()));
+    ///
+    /// engine.run("let x = 40;\nx = max(x, 42);")?;
+    ///
+    /// // Store the trace as text, then parse and replay it
+    /// let text = trace.read().unwrap().to_string();
+    /// let trace = Trace::parse(&text).unwrap();
+    /// let mut replay = trace.replay();
+    ///
+    /// let call = replay.step_until(|e| matches!(e, TraceEvent::Call(..))).unwrap();
+    /// assert_eq!(call, &TraceEvent::Call("max".into(), vec!["40".into(), "42".into()], call.position()));
+    /// assert_eq!(replay.variables()["x"], "40");
+    ///
+    /// replay.step_until(|_| false);
+    /// assert_eq!(replay.variables()["x"], "42");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn on_trace(&mut self, callback: impl Fn(&TraceEvent) + SendSync + 'static) -> &mut Self {
+        self.trace = Some(crate::Shared::new(callback));
+        self
+    }
+    /// Is an execution trace being recorded?
+    #[inline(always)]
+    #[must_use]
+    pub fn is_tracing(&self) -> bool {
+        self.trace.is_some()
+    }
+    /// Record a trace event if an execution trace is being recorded.
+    ///
+    /// The event is only created when needed.
+    #[inline(always)]
+    pub(crate) fn track_trace(&self, event: impl FnOnce() -> TraceEvent) {
+        if let Some(ref trace) = self.trace {
+            trace(&event());
+        }
+    }
+}
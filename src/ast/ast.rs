@@ -199,6 +199,15 @@ impl AST {
     const fn shared_lib(&self) -> &crate::SharedModule {
         &self.lib
     }
+    /// Get a mutable reference to the internal shared [`Module`][crate::Module] containing all
+    /// script-defined functions.
+    #[cfg(not(feature = "no_function"))]
+    #[inline(always)]
+    #[must_use]
+    #[allow(dead_code)]
+    pub(crate) fn shared_lib_mut(&mut self) -> &mut crate::SharedModule {
+        &mut self.lib
+    }
     /// _(internals)_ Get the embedded [module resolver][crate::ModuleResolver].
     /// Exported under the `internals` feature only.
     ///
@@ -642,6 +651,104 @@ impl AST {
         }
         self
     }
+    /// Filter out the functions not reachable from the statements of the [`AST`] or from a list
+    /// of entry points, retaining only the reachable ones (i.e. symbol-level dead-code elimination).
+    ///
+    /// This is typically used after [merging][AST::merge] or [combining][AST::combine] multiple
+    /// [`AST`]'s (e.g. with a library of functions) to shrink the function table of the result.
+    ///
+    /// Reachability is determined by function names alone, regardless of the number of
+    /// parameters. A function is considered referenced when its name appears in a function or
+    /// method call, a variable access, a function pointer or a string literal (e.g. `Fn("foo")`
+    /// or `call("foo")`). Function names constructed dynamically at runtime cannot be tracked, so
+    /// such functions must be listed as entry points.
+    ///
+    /// Not available under `no_function`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # #[cfg(not(feature = "no_function"))]
+    /// # {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let lib = engine.compile("
+    ///     fn helper(x) { x * 2 }
+    ///     fn main(x) { helper(x) + 1 }
+    ///     fn unused() { 42 }
+    ///     fn hook() { main(1) }
+    /// ")?;
+    ///
+    /// let mut ast = engine.compile("")?.merge(&lib);
+    ///
+    /// ast.retain_reachable_functions(["main"]);
+    ///
+    /// let mut names = ast.iter_functions().map(|f| f.name).collect::<Vec<_>>();
+    /// names.sort();
+    ///
+    /// assert_eq!(names, ["helper", "main"]);
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "no_function"))]
+    pub fn retain_reachable_functions<S: AsRef<str>>(
+        &mut self,
+        entry_points: impl IntoIterator<Item = S>,
+    ) -> &mut Self {
+        if !self.has_functions() {
+            return self;
+        }
+
+        fn collect_names(stmts: &[Stmt], names: &mut Vec<ImmutableString>) {
+            let path = &mut Vec::new();
+
+            for stmt in stmts {
+                stmt.walk(path, &mut |path| {
+                    let name = match path.last() {
+                        Some(ASTNode::Stmt(Stmt::FnCall(x, ..)))
+                        | Some(ASTNode::Expr(Expr::FnCall(x, ..) | Expr::MethodCall(x, ..))) => {
+                            x.name.clone()
+                        }
+                        Some(ASTNode::Expr(Expr::Variable(x, ..))) => x.1.clone(),
+                        Some(ASTNode::Expr(Expr::StringConstant(s, ..))) => s.clone(),
+                        Some(ASTNode::Expr(Expr::DynamicConstant(v, ..))) if v.is_fnptr() => {
+                            v.read_lock::<crate::FnPtr>().unwrap().fn_name().into()
+                        }
+                        _ => return true,
+                    };
+                    names.push(name);
+                    true
+                });
+            }
+        }
+
+        let mut reachable = std::collections::BTreeSet::new();
+        let mut pending = entry_points
+            .into_iter()
+            .map(|name| name.as_ref().into())
+            .collect::<Vec<ImmutableString>>();
+
+        collect_names(self.statements(), &mut pending);
+
+        while let Some(name) = pending.pop() {
+            if reachable.contains(&name) {
+                continue;
+            }
+
+            self.lib
+                .iter_script_fn()
+                .filter(|&(_, _, fn_name, ..)| fn_name == name.as_str())
+                .for_each(|(.., fn_def)| collect_names(fn_def.body.as_ref(), &mut pending));
+
+            reachable.insert(name);
+        }
+
+        self.retain_functions(|_, _, name, _| reachable.contains(name))
+    }
     /// _(internals)_ Iterate through all function definitions.
     /// Exported under the `internals` feature only.
     ///
@@ -763,6 +870,92 @@ impl AST {
     pub fn walk(&self, on_node: &mut (impl FnMut(&[ASTNode]) -> bool + ?Sized)) -> bool {
         self._walk(on_node)
     }
+    /// _(internals)_ Visit the [`AST`] with a [`Visitor`][super::Visitor], including function
+    /// bodies (if any).
+    /// Exported under the `internals` feature only.
+    ///
+    /// Return `false` if the visit is terminated.
+    #[cfg(feature = "internals")]
+    pub fn visit(&self, visitor: &mut (impl super::Visitor + ?Sized)) -> bool {
+        if !self.statements().iter().all(|s| visitor.visit_stmt(s)) {
+            return false;
+        }
+        #[cfg(not(feature = "no_function"))]
+        if !self
+            .iter_fn_def()
+            .flat_map(|f| f.body.iter())
+            .all(|s| visitor.visit_stmt(s))
+        {
+            return false;
+        }
+
+        true
+    }
+    /// _(internals)_ Rewrite the [`AST`] in place with a [`VisitorMut`][super::VisitorMut],
+    /// including function bodies (if any).
+    /// Exported under the `internals` feature only.
+    ///
+    /// After rewriting, nodes with no [position][Position] take the position of their parent
+    /// node, and all pre-calculated hashes (e.g. of function calls) are recalculated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Expr, INT};
+    /// use rhai::visit::{walk_expr_mut, VisitorMut};
+    ///
+    /// // Replace all calls to `foo` with calls to `bar`
+    /// struct Rename;
+    ///
+    /// impl VisitorMut for Rename {
+    ///     fn visit_expr_mut(&mut self, expr: &mut Expr) {
+    ///         if let Expr::FnCall(f, ..) = expr {
+    ///             if f.name == "foo" {
+    ///                 f.name = "bar".into();
+    ///             }
+    ///         }
+    ///         walk_expr_mut(self, expr);
+    ///     }
+    /// }
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let mut ast = engine.compile("fn foo(x) { x + 1 } fn bar(x) { x * 2 } foo(21)")?;
+    ///
+    /// ast.visit_mut(&mut Rename);
+    ///
+    /// assert_eq!(engine.eval_ast::<INT>(&ast)?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "internals")]
+    pub fn visit_mut(&mut self, visitor: &mut (impl super::VisitorMut + ?Sized)) {
+        fn rewrite(stmt: &mut Stmt, visitor: &mut (impl super::VisitorMut + ?Sized)) {
+            use super::VisitorMut;
+
+            visitor.visit_stmt_mut(stmt);
+            super::visit::Revalidator::new(stmt.position()).visit_stmt_mut(stmt);
+        }
+
+        for stmt in self.statements_mut() {
+            rewrite(stmt, visitor);
+        }
+
+        #[cfg(not(feature = "no_function"))]
+        if self.has_functions() {
+            let functions = self.iter_fn_def().cloned().collect::<Vec<_>>();
+
+            self.lib = crate::Module::from(functions.into_iter().map(|fn_def| {
+                let mut fn_def = crate::func::shared_take_or_clone(fn_def);
+                for stmt in fn_def.body.statements_mut() {
+                    rewrite(stmt, visitor);
+                }
+                fn_def.into()
+            }))
+            .into();
+        }
+    }
     /// Recursively walk the [`AST`], including function bodies (if any).
     /// Return `false` from the callback to terminate the walk.
     pub(crate) fn _walk(&self, on_node: &mut (impl FnMut(&[ASTNode]) -> bool + ?Sized)) -> bool {
@@ -0,0 +1,226 @@
+//! Module defining the call graph of an [`AST`].
+#![cfg(not(feature = "no_function"))]
+
+use super::{ASTNode, Expr, FnCallExpr, Stmt, AST};
+use crate::{ImmutableString, Position};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{collections::BTreeSet, fmt};
+
+/// A node in a [`CallGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CallGraphNode {
+    /// The top-level statements of the [`AST`].
+    Main,
+    /// A script-defined function in the [`AST`], with its name and number of parameters.
+    Script(ImmutableString, usize),
+    /// A function not defined in the [`AST`] (e.g. a native Rust function), with its name
+    /// (prefixed by its namespace, if any) and number of arguments.
+    ///
+    /// For method calls, the object counts as the first argument.
+    Native(ImmutableString, usize),
+}
+
+impl fmt::Display for CallGraphNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Main => f.write_str("<main>"),
+            Self::Script(name, num_params) | Self::Native(name, num_params) => {
+                write!(f, "{name}/{num_params}")
+            }
+        }
+    }
+}
+
+/// An edge in a [`CallGraph`], representing a call site.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CallGraphEdge {
+    /// The caller.
+    pub caller: CallGraphNode,
+    /// The function being called.
+    pub callee: CallGraphNode,
+    /// Position of the call site.
+    pub position: Position,
+}
+
+/// Call graph of an [`AST`], containing all direct function calls made by its top-level
+/// statements and script-defined functions.
+///
+/// Operators, property accesses and indirect calls (e.g. via function pointers) are not included.
+///
+/// Not available under `no_function`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallGraph {
+    /// All script-defined functions in the [`AST`].
+    functions: Vec<CallGraphNode>,
+    /// All call sites.
+    edges: Vec<CallGraphEdge>,
+}
+
+impl CallGraph {
+    /// Get all script-defined functions in the [`AST`].
+    #[inline(always)]
+    #[must_use]
+    pub fn functions(&self) -> &[CallGraphNode] {
+        &self.functions
+    }
+    /// Get all edges (i.e. call sites) in the [`CallGraph`].
+    #[inline(always)]
+    #[must_use]
+    pub fn edges(&self) -> &[CallGraphEdge] {
+        &self.edges
+    }
+    /// Get an iterator over all calls made by a caller.
+    #[inline]
+    pub fn calls_from<'a>(
+        &'a self,
+        caller: &'a CallGraphNode,
+    ) -> impl Iterator<Item = &'a CallGraphEdge> + 'a {
+        self.edges.iter().filter(move |edge| edge.caller == *caller)
+    }
+    /// Get an iterator over all calls made to a function.
+    #[inline]
+    pub fn calls_to<'a>(
+        &'a self,
+        callee: &'a CallGraphNode,
+    ) -> impl Iterator<Item = &'a CallGraphEdge> + 'a {
+        self.edges.iter().filter(move |edge| edge.callee == *callee)
+    }
+    /// Get all nodes reachable from a list of roots, including the roots themselves.
+    #[must_use]
+    pub fn reachable_from<'a>(
+        &'a self,
+        roots: impl IntoIterator<Item = &'a CallGraphNode>,
+    ) -> BTreeSet<&'a CallGraphNode> {
+        let mut reachable = BTreeSet::new();
+        let mut pending = roots.into_iter().collect::<Vec<_>>();
+
+        while let Some(node) = pending.pop() {
+            if reachable.insert(node) {
+                pending.extend(self.calls_from(node).map(|edge| &edge.callee));
+            }
+        }
+
+        reachable
+    }
+    /// Get all script-defined functions not reachable from the top-level statements of the
+    /// [`AST`].
+    ///
+    /// Functions that are only called indirectly (e.g. via function pointers) or from outside
+    /// (e.g. via [`Engine::call_fn`][crate::Engine::call_fn]) are also returned.
+    #[must_use]
+    pub fn unreachable_functions(&self) -> Vec<&CallGraphNode> {
+        let reachable = self.reachable_from([&CallGraphNode::Main]);
+
+        self.functions
+            .iter()
+            .filter(|f| !reachable.contains(f))
+            .collect()
+    }
+}
+
+impl AST {
+    /// Extract the [call graph][CallGraph] of this [`AST`].
+    ///
+    /// Not available under `no_function`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{CallGraphNode, Engine};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let ast = engine.compile("
+    ///     fn double(x) { x * 2 }
+    ///     fn unused() { print(42) }
+    ///
+    ///     print(double(21));
+    /// ")?;
+    ///
+    /// let graph = ast.call_graph();
+    ///
+    /// let calls = graph
+    ///     .calls_from(&CallGraphNode::Main)
+    ///     .map(|edge| edge.callee.to_string())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(calls, ["print/1", "double/1"]);
+    ///
+    /// let unused = CallGraphNode::Script("unused".into(), 0);
+    /// assert_eq!(graph.unreachable_functions(), [&unused]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn call_graph(&self) -> CallGraph {
+        let lib = self.shared_lib();
+
+        let mut graph = CallGraph {
+            functions: lib
+                .iter_script_fn()
+                .map(|(.., name, num_params, _)| CallGraphNode::Script(name.into(), num_params))
+                .collect(),
+            edges: Vec::new(),
+        };
+
+        let callee_of = |x: &FnCallExpr, is_method_call: bool| {
+            let num_args = x.args.len();
+
+            #[cfg(not(feature = "no_module"))]
+            if x.is_qualified() {
+                let name = format!(
+                    "{}{}{}",
+                    x.namespace,
+                    crate::engine::NAMESPACE_SEPARATOR,
+                    x.name
+                );
+                return CallGraphNode::Native(name.into(), num_args);
+            }
+
+            if lib.get_script_fn(&x.name, num_args).is_some() {
+                CallGraphNode::Script(x.name.clone(), num_args)
+            } else if is_method_call {
+                CallGraphNode::Native(x.name.clone(), num_args + 1)
+            } else {
+                CallGraphNode::Native(x.name.clone(), num_args)
+            }
+        };
+
+        let mut collect = |caller: CallGraphNode, stmts: &[Stmt]| {
+            let path = &mut Vec::new();
+
+            for stmt in stmts {
+                stmt.walk(path, &mut |path| {
+                    let (x, is_method_call, pos) = match path.last() {
+                        Some(ASTNode::Stmt(Stmt::FnCall(x, pos)))
+                        | Some(ASTNode::Expr(Expr::FnCall(x, pos))) => (x, false, *pos),
+                        Some(ASTNode::Expr(Expr::MethodCall(x, pos))) => (x, true, *pos),
+                        _ => return true,
+                    };
+
+                    if !x.is_operator_call() {
+                        graph.edges.push(CallGraphEdge {
+                            caller: caller.clone(),
+                            callee: callee_of(x, is_method_call),
+                            position: pos,
+                        });
+                    }
+                    true
+                });
+            }
+        };
+
+        collect(CallGraphNode::Main, self.statements());
+
+        for (.., name, num_params, fn_def) in lib.iter_script_fn() {
+            collect(
+                CallGraphNode::Script(name.into(), num_params),
+                fn_def.body.as_ref(),
+            );
+        }
+
+        graph
+    }
+}
@@ -48,6 +48,8 @@ pub struct CustomExpr {
     pub scope_may_be_changed: bool,
     /// Is this custom syntax self-terminated?
     pub self_terminated: bool,
+    /// Names of new variables declared by this custom syntax (via `$var$`), in order.
+    pub declared_vars: FnArgsVec<ImmutableString>,
 }
 
 #[cfg(not(feature = "no_custom_syntax"))]
@@ -2,14 +2,18 @@
 
 #[allow(clippy::module_inception)]
 pub mod ast;
+pub mod call_graph;
 pub mod expr;
 pub mod flags;
 pub mod ident;
 pub mod namespace;
 pub mod script_fn;
 pub mod stmt;
+pub mod visit;
 
 pub use ast::{ASTNode, EncapsulatedEnviron, AST};
+#[cfg(not(feature = "no_function"))]
+pub use call_graph::{CallGraph, CallGraphEdge, CallGraphNode};
 #[cfg(not(feature = "no_custom_syntax"))]
 pub use expr::CustomExpr;
 pub use expr::{BinaryExpr, Expr, FnCallExpr, FnCallHashes};
@@ -20,9 +24,11 @@ pub use namespace::Namespace;
 #[cfg(not(feature = "no_function"))]
 pub use script_fn::{ScriptFnMetadata, ScriptFuncDef};
 pub use stmt::{
-    CaseBlocksList, FlowControl, OpAssignment, RangeCase, Stmt, StmtBlock, StmtBlockContainer,
-    SwitchCasesCollection,
+    CaseBlocksList, FlowControl, OpAssignment, Pattern, RangeCase, Stmt, StmtBlock,
+    StmtBlockContainer, SwitchCasesCollection,
 };
+#[cfg(feature = "internals")]
+pub use visit::{Visitor, VisitorMut};
 
 /// _(internals)_ Empty placeholder for a script-defined function.
 /// Exported under the `internals` feature only.
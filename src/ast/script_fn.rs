@@ -1,7 +1,7 @@
 //! Module defining script-defined functions.
 #![cfg(not(feature = "no_function"))]
 
-use super::{FnAccess, StmtBlock};
+use super::{Expr, FnAccess, StmtBlock};
 use crate::{FnArgsVec, ImmutableString};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
@@ -23,6 +23,18 @@ pub struct ScriptFuncDef {
     pub this_type: Option<ImmutableString>,
     /// Names of function parameters.
     pub params: FnArgsVec<ImmutableString>,
+    /// Names of the types of function parameters, if they are typed (e.g. `fn foo(x: int)`).
+    ///
+    /// Empty if the parameters are not typed.
+    pub param_types: FnArgsVec<ImmutableString>,
+    /// Default values of the trailing optional parameters (e.g. `fn foo(x, y = 42)`), excluding
+    /// the rest parameter (if any).
+    ///
+    /// Default values are evaluated in the scope of the function, after all previous parameters.
+    pub param_defaults: FnArgsVec<Expr>,
+    /// Does the last parameter collect all remaining arguments into an array (e.g.
+    /// `fn foo(x, ...rest)`)?
+    pub is_variadic: bool,
     /// _(metadata)_ Function doc-comments (if any). Exported under the `metadata` feature only.
     ///
     /// Doc-comments are comment lines beginning with `///` or comment blocks beginning with `/**`,
@@ -53,10 +65,61 @@ impl ScriptFuncDef {
             #[cfg(not(feature = "no_object"))]
             this_type: self.this_type.clone(),
             params: self.params.clone(),
+            param_types: self.param_types.clone(),
+            param_defaults: self.param_defaults.clone(),
+            is_variadic: self.is_variadic,
             #[cfg(feature = "metadata")]
             comments: <_>::default(),
         }
     }
+    /// Minimum number of arguments that must be passed to this function.
+    #[inline]
+    #[must_use]
+    pub fn min_num_args(&self) -> usize {
+        self.params.len() - self.param_defaults.len() - usize::from(self.is_variadic)
+    }
+    /// Does this function take a range of number of arguments, i.e. does it have parameters with
+    /// default values or a rest parameter?
+    #[inline(always)]
+    #[must_use]
+    pub fn has_arity_range(&self) -> bool {
+        self.is_variadic || !self.param_defaults.is_empty()
+    }
+    /// Can this function be called with a particular number of arguments?
+    #[inline]
+    #[must_use]
+    pub fn accepts_num_args(&self, num_args: usize) -> bool {
+        num_args >= self.min_num_args() && (self.is_variadic || num_args <= self.params.len())
+    }
+    /// Format a parameter of this function, with its type, default value marker or rest marker.
+    #[must_use]
+    pub(crate) fn param_to_string(&self, index: usize, param: &str) -> String {
+        match self.param_types.get(index) {
+            Some(typ) => format!("{param}: {typ}"),
+            None if self.is_variadic && index == self.params.len() - 1 => format!("...{param}"),
+            None if index >= self.min_num_args() => format!("{param} = .."),
+            None => param.to_string(),
+        }
+    }
+    /// Calculate the hash of this [`ScriptFuncDef`], based on its name, number of parameters,
+    /// the type of `this` pointer (if any) and the types of its parameters (if any).
+    #[must_use]
+    pub(crate) fn calc_hash(&self, base: u64) -> u64 {
+        #[cfg(not(feature = "no_object"))]
+        let base = self
+            .this_type
+            .as_ref()
+            .map_or(base, |typ| crate::calc_typed_method_hash(base, typ));
+
+        if self.param_types.is_empty() {
+            base
+        } else {
+            crate::calc_typed_params_hash(
+                base,
+                self.param_types.iter().map(ImmutableString::as_str),
+            )
+        }
+    }
 }
 
 impl fmt::Display for ScriptFuncDef {
@@ -81,7 +144,8 @@ impl fmt::Display for ScriptFuncDef {
             self.name,
             self.params
                 .iter()
-                .map(ImmutableString::as_str)
+                .enumerate()
+                .map(|(i, p)| self.param_to_string(i, p))
                 .collect::<FnArgsVec<_>>()
                 .join(", ")
         )
@@ -109,6 +173,12 @@ pub struct ScriptFnMetadata<'a> {
         serde(default, skip_serializing_if = "Vec::is_empty")
     )]
     pub params: Vec<&'a str>,
+    /// Names of the types of function parameters (if typed).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub param_types: Vec<&'a str>,
     /// Function access mode.
     pub access: FnAccess,
     /// Type of `this` pointer, if any.
@@ -160,7 +230,11 @@ impl fmt::Display for ScriptFnMetadata<'_> {
             self.name,
             self.params
                 .iter()
-                .copied()
+                .enumerate()
+                .map(|(i, &p)| match self.param_types.get(i) {
+                    Some(typ) => format!("{p}: {typ}"),
+                    None => p.to_string(),
+                })
                 .collect::<FnArgsVec<_>>()
                 .join(", ")
         )
@@ -173,6 +247,11 @@ impl<'a> From<&'a ScriptFuncDef> for ScriptFnMetadata<'a> {
         Self {
             name: &value.name,
             params: value.params.iter().map(ImmutableString::as_str).collect(),
+            param_types: value
+                .param_types
+                .iter()
+                .map(ImmutableString::as_str)
+                .collect(),
             access: value.access,
             #[cfg(not(feature = "no_object"))]
             this_type: value.this_type.as_deref(),
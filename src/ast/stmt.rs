@@ -325,6 +325,148 @@ impl RangeCase {
     }
 }
 
+/// _(internals)_ A destructuring pattern, used in `let`/`const` statements and `switch` cases.
+/// Exported under the `internals` feature only.
+#[derive(Debug, Clone, Hash)]
+pub enum Pattern {
+    /// `_` -- matches anything without binding it.
+    Ignore(Position),
+    /// Variable name -- matches anything and binds it to the variable.
+    Var(Ident),
+    /// Literal constant -- matches a value equal to the constant.
+    Literal(Dynamic, Position),
+    /// `[` pattern `,` ... `,` `..` rest `]`
+    ///
+    /// Matches an [array][crate::Array] with exactly as many items as patterns, or at least as
+    /// many items when there is a rest pattern (which binds the remaining items as an array).
+    ///
+    /// Not available under `no_index`.
+    #[cfg(not(feature = "no_index"))]
+    Array(Box<(StaticVec<Pattern>, Option<Pattern>)>, Position),
+    /// `#{` property `:` pattern `,` ... `,` `..` `}`
+    ///
+    /// Matches an [object map][crate::Map] with exactly the listed properties, or at least the
+    /// listed properties when the pattern ends with `..` (`true`).
+    /// A property without a pattern binds to a variable of the same name.
+    ///
+    /// Not available under `no_object`.
+    #[cfg(not(feature = "no_object"))]
+    Map(Box<(StaticVec<(Ident, Pattern)>, bool)>, Position),
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ignore(..) => f.write_str("_"),
+            Self::Var(x) => f.write_str(x.as_str()),
+            Self::Literal(value, ..) => write!(f, "{value:?}"),
+            #[cfg(not(feature = "no_index"))]
+            Self::Array(x, ..) => {
+                f.write_str("[")?;
+                for (i, p) in x.0.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{p}")?;
+                }
+                if let Some(ref rest) = x.1 {
+                    if !x.0.is_empty() {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "..{rest}")?;
+                }
+                f.write_str("]")
+            }
+            #[cfg(not(feature = "no_object"))]
+            Self::Map(x, ..) => {
+                f.write_str("#{")?;
+                for (i, (key, p)) in x.0.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    match p {
+                        Self::Var(v) if v.name == key.name => write!(f, " {}", key.as_str())?,
+                        _ => write!(f, " {}: {p}", key.as_str())?,
+                    }
+                }
+                if x.1 {
+                    if !x.0.is_empty() {
+                        f.write_str(",")?;
+                    }
+                    f.write_str(" ..")?;
+                }
+                f.write_str(" }")
+            }
+        }
+    }
+}
+
+impl Pattern {
+    /// Get the [position][Position] of this pattern.
+    #[inline]
+    #[must_use]
+    pub const fn position(&self) -> Position {
+        match self {
+            Self::Ignore(pos) | Self::Literal(.., pos) => *pos,
+            Self::Var(x) => x.pos,
+            #[cfg(not(feature = "no_index"))]
+            Self::Array(.., pos) => *pos,
+            #[cfg(not(feature = "no_object"))]
+            Self::Map(.., pos) => *pos,
+        }
+    }
+    /// Get all the variables bound by this pattern, in binding order.
+    #[must_use]
+    pub fn variables(&self) -> StaticVec<&Ident> {
+        fn collect<'a>(pattern: &'a Pattern, list: &mut StaticVec<&'a Ident>) {
+            match pattern {
+                Pattern::Ignore(..) | Pattern::Literal(..) => (),
+                Pattern::Var(x) => list.push(x),
+                #[cfg(not(feature = "no_index"))]
+                Pattern::Array(x, ..) => {
+                    x.0.iter().for_each(|p| collect(p, list));
+                    if let Some(ref rest) = x.1 {
+                        collect(rest, list);
+                    }
+                }
+                #[cfg(not(feature = "no_object"))]
+                Pattern::Map(x, ..) => x.0.iter().for_each(|(.., p)| collect(p, list)),
+            }
+        }
+
+        let mut list = StaticVec::new_const();
+        collect(self, &mut list);
+        list
+    }
+    /// Get the constant value of this pattern, if it consists only of literals
+    /// (i.e. it binds no variables and matches exactly one value).
+    #[must_use]
+    pub fn get_literal_value(&self) -> Option<Dynamic> {
+        match self {
+            Self::Ignore(..) | Self::Var(..) => None,
+            Self::Literal(value, ..) => Some(value.clone()),
+            #[cfg(not(feature = "no_index"))]
+            Self::Array(x, ..) if x.1.is_none() => {
+                x.0.iter()
+                    .map(Self::get_literal_value)
+                    .collect::<Option<crate::Array>>()
+                    .map(Into::into)
+            }
+            #[cfg(not(feature = "no_index"))]
+            Self::Array(..) => None,
+            #[cfg(not(feature = "no_object"))]
+            Self::Map(x, ..) if !x.1 => {
+                x.0.iter()
+                    .map(|(key, p)| p.get_literal_value().map(|v| (key.name.clone().into(), v)))
+                    .collect::<Option<crate::Map>>()
+                    .map(Into::into)
+            }
+            #[cfg(not(feature = "no_object"))]
+            Self::Map(..) => None,
+        }
+    }
+}
+
 pub type CaseBlocksList = smallvec::SmallVec<[usize; 2]>;
 
 /// _(internals)_ A type containing all cases for a `switch` statement.
@@ -339,6 +481,8 @@ pub struct SwitchCasesCollection {
     pub ranges: StaticVec<RangeCase>,
     /// Statements block for the default case (there can be no condition for the default case).
     pub def_case: Option<usize>,
+    /// List of destructuring pattern cases, tried in order after all other cases fail to match.
+    pub patterns: StaticVec<(Pattern, usize)>,
 }
 
 impl Hash for SwitchCasesCollection {
@@ -351,6 +495,7 @@ impl Hash for SwitchCasesCollection {
 
         self.ranges.hash(state);
         self.def_case.hash(state);
+        self.patterns.hash(state);
     }
 }
 
@@ -620,6 +765,13 @@ pub enum Stmt {
     /// * [`EXPORTED`][ASTFlags::EXPORTED] = `export`  
     /// * [`CONSTANT`][ASTFlags::CONSTANT] = `const`
     Var(Box<(Ident, Expr, Option<NonZeroUsize>)>, ASTFlags, Position),
+    /// `let`|`const` pattern `=` expr
+    ///
+    /// ### Flags
+    ///
+    /// * [`NONE`][ASTFlags::NONE] = `let`
+    /// * [`CONSTANT`][ASTFlags::CONSTANT] = `const`
+    Destructure(Box<(Pattern, Expr)>, ASTFlags, Position),
     /// expr op`=` expr
     Assignment(Box<(OpAssignment, BinaryExpr)>),
     /// func `(` expr `,` ... `)`
@@ -647,11 +799,11 @@ pub enum Stmt {
     /// * [`NONE`][ASTFlags::NONE] = `return`
     /// * [`BREAK`][ASTFlags::BREAK] = `throw`
     Return(Option<Box<Expr>>, ASTFlags, Position),
-    /// `import` expr `as` alias
+    /// `import` expr `as` alias `use` `{` symbol `as` alias `,` ... `}`
     ///
     /// Not available under `no_module`.
     #[cfg(not(feature = "no_module"))]
-    Import(Box<(Expr, Ident)>, Position),
+    Import(Box<(Expr, Ident, crate::ThinVec<(Ident, Ident)>)>, Position),
     /// `export` var `as` alias
     ///
     /// Not available under `no_module`.
@@ -691,6 +843,7 @@ impl Stmt {
         match self {
             Self::Do(_, options, _)
             | Self::Var(_, options, _)
+            | Self::Destructure(_, options, _)
             | Self::BreakLoop(_, options, _)
             | Self::Return(_, options, _) => *options,
 
@@ -726,6 +879,7 @@ impl Stmt {
             | Self::For(.., pos)
             | Self::Return(.., pos)
             | Self::Var(.., pos)
+            | Self::Destructure(.., pos)
             | Self::TryCatch(.., pos) => *pos,
 
             Self::Assignment(x) => x.0.pos,
@@ -756,6 +910,7 @@ impl Stmt {
             | Self::For(.., pos)
             | Self::Return(.., pos)
             | Self::Var(.., pos)
+            | Self::Destructure(.., pos)
             | Self::TryCatch(.., pos) => *pos = new_pos,
 
             Self::Assignment(x) => x.0.pos = new_pos,
@@ -793,7 +948,11 @@ impl Stmt {
             | Self::For(..)
             | Self::TryCatch(..) => false,
 
-            Self::Var(..) | Self::Assignment(..) | Self::BreakLoop(..) | Self::Return(..) => false,
+            Self::Var(..)
+            | Self::Destructure(..)
+            | Self::Assignment(..)
+            | Self::BreakLoop(..)
+            | Self::Return(..) => false,
 
             #[cfg(not(feature = "no_module"))]
             Self::Import(..) | Self::Export(..) => false,
@@ -823,6 +982,7 @@ impl Stmt {
             },
 
             Self::Var(..)
+            | Self::Destructure(..)
             | Self::Assignment(..)
             | Self::FnCall(..)
             | Self::Do(..)
@@ -860,6 +1020,10 @@ impl Stmt {
                         let block = &sw.expressions[r.index()];
                         block.lhs.is_pure() && block.rhs.is_pure()
                     })
+                    && sw.patterns.iter().all(|&(.., c)| {
+                        let block = &sw.expressions[c];
+                        block.lhs.is_pure() && block.rhs.is_pure()
+                    })
                     && sw.def_case.is_some()
                     && sw.expressions[sw.def_case.unwrap()].rhs.is_pure()
             }
@@ -880,7 +1044,10 @@ impl Stmt {
             // so infinite loops can never occur.
             Self::For(x, ..) => x.2.expr.is_pure() && x.2.body.iter().all(Self::is_pure),
 
-            Self::Var(..) | Self::Assignment(..) | Self::FnCall(..) => false,
+            // Destructuring may fail if the value does not match the pattern.
+            Self::Var(..) | Self::Destructure(..) | Self::Assignment(..) | Self::FnCall(..) => {
+                false
+            }
             Self::Block(block, ..) => block.iter().all(Self::is_pure),
             Self::BreakLoop(..) | Self::Return(..) => false,
             Self::TryCatch(x, ..) => {
@@ -909,7 +1076,7 @@ impl Stmt {
     #[must_use]
     pub fn is_block_dependent(&self) -> bool {
         match self {
-            Self::Var(..) => true,
+            Self::Var(..) | Self::Destructure(..) => true,
 
             Self::Expr(e) => match &**e {
                 Expr::Stmt(s) => s.iter().all(Self::is_block_dependent),
@@ -991,6 +1158,11 @@ impl Stmt {
                     return false;
                 }
             }
+            Self::Destructure(x, ..) => {
+                if !x.1.walk(path, on_node) {
+                    return false;
+                }
+            }
             Self::If(x, ..) => {
                 if !x.expr.walk(path, on_node) {
                     return false;
@@ -1034,6 +1206,16 @@ impl Stmt {
                         return false;
                     }
                 }
+                for &(.., index) in &sw.patterns {
+                    let block = &sw.expressions[index];
+
+                    if !block.lhs.walk(path, on_node) {
+                        return false;
+                    }
+                    if !block.rhs.walk(path, on_node) {
+                        return false;
+                    }
+                }
                 if let Some(index) = sw.def_case {
                     if !sw.expressions[index].lhs.walk(path, on_node) {
                         return false;
@@ -0,0 +1,285 @@
+//! Module defining traits to visit and rewrite an [`AST`][crate::AST].
+#![cfg(feature = "internals")]
+
+use super::{Expr, FnCallExpr, FnCallHashes, Stmt, StmtBlock};
+use crate::tokenizer::is_valid_function_name;
+use crate::{calc_fn_hash, Position};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// _(internals)_ A visitor over the nodes of an [`AST`][crate::AST].
+/// Exported under the `internals` feature only.
+///
+/// All methods have default implementations that visit all child nodes (via [`walk_stmt`] and
+/// [`walk_expr`]), so only the methods of interest need to be overridden.
+///
+/// Use [`AST::visit`][crate::AST::visit] to visit an entire [`AST`][crate::AST], including
+/// function bodies.
+pub trait Visitor {
+    /// Visit a statement.
+    /// Return `false` to terminate the visit.
+    #[inline(always)]
+    fn visit_stmt(&mut self, stmt: &Stmt) -> bool {
+        walk_stmt(self, stmt)
+    }
+    /// Visit an expression.
+    /// Return `false` to terminate the visit.
+    #[inline(always)]
+    fn visit_expr(&mut self, expr: &Expr) -> bool {
+        walk_expr(self, expr)
+    }
+}
+
+/// _(internals)_ A visitor that can rewrite the nodes of an [`AST`][crate::AST] in place.
+/// Exported under the `internals` feature only.
+///
+/// All methods have default implementations that visit all child nodes (via [`walk_stmt_mut`]
+/// and [`walk_expr_mut`]), so only the methods of interest need to be overridden.
+///
+/// Use [`AST::visit_mut`][crate::AST::visit_mut] to rewrite an entire [`AST`][crate::AST],
+/// including function bodies. Positions and function call hashes are re-validated afterwards,
+/// so new nodes can be created with [`Position::NONE`] and function calls can be renamed freely.
+pub trait VisitorMut {
+    /// Visit (and possibly rewrite) a statement.
+    #[inline(always)]
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        walk_stmt_mut(self, stmt);
+    }
+    /// Visit (and possibly rewrite) an expression.
+    #[inline(always)]
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr);
+    }
+}
+
+/// Visit all the statements in a block.
+fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &StmtBlock) -> bool {
+    block.iter().all(|s| visitor.visit_stmt(s))
+}
+
+/// Visit all the statements in a block, allowing them to be rewritten.
+fn walk_block_mut<V: VisitorMut + ?Sized>(visitor: &mut V, block: &mut StmtBlock) {
+    block
+        .statements_mut()
+        .iter_mut()
+        .for_each(|s| visitor.visit_stmt_mut(s));
+}
+
+/// _(internals)_ Visit all the child nodes of a statement.
+/// Exported under the `internals` feature only.
+///
+/// Return `false` if the visit is terminated.
+pub fn walk_stmt<V: Visitor + ?Sized>(visitor: &mut V, stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Var(x, ..) => visitor.visit_expr(&x.1),
+        Stmt::Destructure(x, ..) => visitor.visit_expr(&x.1),
+        Stmt::If(x, ..) | Stmt::While(x, ..) | Stmt::Do(x, ..) | Stmt::TryCatch(x, ..) => {
+            visitor.visit_expr(&x.expr)
+                && walk_block(visitor, &x.body)
+                && walk_block(visitor, &x.branch)
+        }
+        Stmt::Switch(x, ..) => {
+            let (expr, sw) = &**x;
+
+            visitor.visit_expr(expr)
+                && sw
+                    .expressions
+                    .iter()
+                    .all(|b| visitor.visit_expr(&b.lhs) && visitor.visit_expr(&b.rhs))
+        }
+        Stmt::For(x, ..) => visitor.visit_expr(&x.2.expr) && walk_block(visitor, &x.2.body),
+        Stmt::Assignment(x, ..) => visitor.visit_expr(&x.1.lhs) && visitor.visit_expr(&x.1.rhs),
+        Stmt::FnCall(x, ..) => x.args.iter().all(|e| visitor.visit_expr(e)),
+        Stmt::Block(x, ..) => walk_block(visitor, x),
+        Stmt::Expr(e) => visitor.visit_expr(e),
+        Stmt::BreakLoop(Some(e), ..) | Stmt::Return(Some(e), ..) => visitor.visit_expr(e),
+        #[cfg(not(feature = "no_module"))]
+        Stmt::Import(x, ..) => visitor.visit_expr(&x.0),
+        _ => true,
+    }
+}
+
+/// _(internals)_ Visit all the child nodes of an expression.
+/// Exported under the `internals` feature only.
+///
+/// Return `false` if the visit is terminated.
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, expr: &Expr) -> bool {
+    match expr {
+        Expr::Stmt(x) => walk_block(visitor, x),
+        Expr::InterpolatedString(x, ..) | Expr::Array(x, ..) => {
+            x.iter().all(|e| visitor.visit_expr(e))
+        }
+        Expr::Map(x, ..) => x.0.iter().all(|(.., e)| visitor.visit_expr(e)),
+        Expr::Index(x, ..)
+        | Expr::Dot(x, ..)
+        | Expr::And(x, ..)
+        | Expr::Or(x, ..)
+        | Expr::Coalesce(x, ..) => visitor.visit_expr(&x.lhs) && visitor.visit_expr(&x.rhs),
+        Expr::FnCall(x, ..) | Expr::MethodCall(x, ..) => {
+            x.args.iter().all(|e| visitor.visit_expr(e))
+        }
+        #[cfg(not(feature = "no_custom_syntax"))]
+        Expr::Custom(x, ..) => x.inputs.iter().all(|e| visitor.visit_expr(e)),
+        _ => true,
+    }
+}
+
+/// _(internals)_ Visit all the child nodes of a statement, allowing them to be rewritten.
+/// Exported under the `internals` feature only.
+pub fn walk_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, stmt: &mut Stmt) {
+    match stmt {
+        Stmt::Var(x, ..) => visitor.visit_expr_mut(&mut x.1),
+        Stmt::Destructure(x, ..) => visitor.visit_expr_mut(&mut x.1),
+        Stmt::If(x, ..) | Stmt::While(x, ..) | Stmt::Do(x, ..) | Stmt::TryCatch(x, ..) => {
+            visitor.visit_expr_mut(&mut x.expr);
+            walk_block_mut(visitor, &mut x.body);
+            walk_block_mut(visitor, &mut x.branch);
+        }
+        Stmt::Switch(x, ..) => {
+            let (expr, sw) = &mut **x;
+
+            visitor.visit_expr_mut(expr);
+            for b in &mut sw.expressions {
+                visitor.visit_expr_mut(&mut b.lhs);
+                visitor.visit_expr_mut(&mut b.rhs);
+            }
+        }
+        Stmt::For(x, ..) => {
+            visitor.visit_expr_mut(&mut x.2.expr);
+            walk_block_mut(visitor, &mut x.2.body);
+        }
+        Stmt::Assignment(x, ..) => {
+            visitor.visit_expr_mut(&mut x.1.lhs);
+            visitor.visit_expr_mut(&mut x.1.rhs);
+        }
+        Stmt::FnCall(x, ..) => x.args.iter_mut().for_each(|e| visitor.visit_expr_mut(e)),
+        Stmt::Block(x, ..) => walk_block_mut(visitor, x),
+        Stmt::Expr(e) => visitor.visit_expr_mut(e),
+        Stmt::BreakLoop(Some(e), ..) | Stmt::Return(Some(e), ..) => visitor.visit_expr_mut(e),
+        #[cfg(not(feature = "no_module"))]
+        Stmt::Import(x, ..) => visitor.visit_expr_mut(&mut x.0),
+        _ => (),
+    }
+}
+
+/// _(internals)_ Visit all the child nodes of an expression, allowing them to be rewritten.
+/// Exported under the `internals` feature only.
+pub fn walk_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expr: &mut Expr) {
+    match expr {
+        Expr::Stmt(x) => walk_block_mut(visitor, x),
+        Expr::InterpolatedString(x, ..) | Expr::Array(x, ..) => {
+            x.iter_mut().for_each(|e| visitor.visit_expr_mut(e));
+        }
+        Expr::Map(x, ..) => x.0.iter_mut().for_each(|(.., e)| visitor.visit_expr_mut(e)),
+        Expr::Index(x, ..)
+        | Expr::Dot(x, ..)
+        | Expr::And(x, ..)
+        | Expr::Or(x, ..)
+        | Expr::Coalesce(x, ..) => {
+            visitor.visit_expr_mut(&mut x.lhs);
+            visitor.visit_expr_mut(&mut x.rhs);
+        }
+        Expr::FnCall(x, ..) | Expr::MethodCall(x, ..) => {
+            x.args.iter_mut().for_each(|e| visitor.visit_expr_mut(e));
+        }
+        #[cfg(not(feature = "no_custom_syntax"))]
+        Expr::Custom(x, ..) => x.inputs.iter_mut().for_each(|e| visitor.visit_expr_mut(e)),
+        _ => (),
+    }
+}
+
+/// Recalculate the pre-calculated hashes of a function call.
+///
+/// Calls that are native-only (e.g. operators) remain native-only.
+fn rehash_fn_call(f: &mut FnCallExpr, is_method_call: bool) {
+    let args_len = f.args.len();
+
+    #[cfg(not(feature = "no_module"))]
+    if !f.namespace.is_empty() {
+        let path = f.namespace.path.iter().map(super::Ident::as_str);
+        let hash = calc_fn_hash(path, &f.name, args_len);
+        f.hashes = if f.hashes.is_native_only() {
+            FnCallHashes::from_native_only(hash)
+        } else {
+            FnCallHashes::from_hash(hash)
+        };
+        return;
+    }
+
+    f.hashes = if f.hashes.is_native_only() || !is_valid_function_name(&f.name) {
+        FnCallHashes::from_native_only(calc_fn_hash(None, &f.name, args_len))
+    } else if is_method_call {
+        #[cfg(not(feature = "no_function"))]
+        {
+            FnCallHashes::from_script_and_native(
+                calc_fn_hash(None, &f.name, args_len),
+                calc_fn_hash(None, &f.name, args_len + 1),
+            )
+        }
+        #[cfg(feature = "no_function")]
+        {
+            FnCallHashes::from_native_only(calc_fn_hash(None, &f.name, args_len + 1))
+        }
+    } else {
+        FnCallHashes::from_hash(calc_fn_hash(None, &f.name, args_len))
+    };
+}
+
+/// Re-validates the nodes of a rewritten [`AST`][crate::AST].
+///
+/// * Nodes with no position take the position of their parent node.
+/// * All pre-calculated hashes of function calls, properties and namespace-qualified variables
+///   are recalculated.
+pub(crate) struct Revalidator {
+    /// Position of the current parent node.
+    pos: Position,
+}
+
+impl Revalidator {
+    /// Create a new [`Revalidator`] for a root node.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(pos: Position) -> Self {
+        Self { pos }
+    }
+}
+
+impl VisitorMut for Revalidator {
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        if stmt.position().is_none() {
+            stmt.set_position(self.pos);
+        }
+        if let Stmt::FnCall(x, ..) = stmt {
+            rehash_fn_call(x, false);
+        }
+
+        let pos = std::mem::replace(&mut self.pos, stmt.position());
+        walk_stmt_mut(self, stmt);
+        self.pos = pos;
+    }
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if expr.position().is_none() {
+            expr.set_position(self.pos);
+        }
+
+        match expr {
+            Expr::FnCall(x, ..) => rehash_fn_call(x, false),
+            Expr::MethodCall(x, ..) => rehash_fn_call(x, true),
+            Expr::Property(x, ..) => {
+                let ((getter, hash_get), (setter, hash_set), ..) = &mut **x;
+                *hash_get = calc_fn_hash(None, getter, 1);
+                *hash_set = calc_fn_hash(None, setter, 2);
+            }
+            #[cfg(not(feature = "no_module"))]
+            Expr::Variable(x, ..) if !x.2.is_empty() => {
+                let (.., name, namespace, hash) = &mut **x;
+                *hash = crate::calc_var_hash(namespace.path.iter().map(super::Ident::as_str), name);
+            }
+            _ => (),
+        }
+
+        let pos = std::mem::replace(&mut self.pos, expr.position());
+        walk_expr_mut(self, expr);
+        self.pos = pos;
+    }
+}
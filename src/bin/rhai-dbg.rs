@@ -138,6 +138,10 @@ fn print_debug_help() {
     println!("disable/dis <bp#>      => disable a break-point");
     println!("delete, d              => delete all break-points");
     println!("delete/d <bp#>         => delete a break-point");
+    println!("cond <bp#> <expr...>   => break at a break-point only when an expression is true");
+    println!("watch/w <expr...>      => add a watch expression");
+    println!("info watch, i w        => print all watch expressions");
+    println!("unwatch <w#>           => delete a watch expression");
     #[cfg(not(feature = "no_position"))]
     println!("break, b               => set a new break-point at the current position");
     #[cfg(not(feature = "no_position"))]
@@ -156,6 +160,7 @@ fn print_debug_help() {
     println!("over, o                => go to the next expression, skipping oer functions");
     println!("next, n, <Enter>       => go to the next statement, skipping over functions");
     println!("finish, f              => continue until the end of the current function call");
+    println!("up, u                  => continue until the caller of the current function call");
     println!("continue, c            => continue normal execution");
     println!();
 }
@@ -258,6 +263,7 @@ fn debug_callback(
                 BreakPoint::AtProperty { ref name, .. } => {
                     println!("! Property {name} accessed.")
                 }
+                ref bp @ BreakPoint::Conditional { .. } => println!("! Break-point {bp} hit."),
                 _ => unreachable!(),
             }
         }
@@ -290,6 +296,17 @@ fn debug_callback(
         _ => unreachable!(),
     }
 
+    // Print watch expressions
+    for (i, watch) in context
+        .global_runtime_state()
+        .debugger()
+        .watches()
+        .iter()
+        .enumerate()
+    {
+        println!("\x1b[36m[{}] {watch}\x1b[39m", i + 1);
+    }
+
     // Print current source line
     print_current_source(&mut context, source, pos, lines, (0, 0));
 
@@ -340,6 +357,7 @@ fn debug_callback(
                 }
                 ["continue" | "c"] => break Ok(DebuggerCommand::Continue),
                 ["finish" | "f"] => break Ok(DebuggerCommand::FunctionExit),
+                ["up" | "u"] => break Ok(DebuggerCommand::StepOut),
                 [] | ["step" | "s"] => break Ok(DebuggerCommand::StepInto),
                 ["over" | "o"] => break Ok(DebuggerCommand::StepOver),
                 ["next" | "n"] => break Ok(DebuggerCommand::Next),
@@ -471,6 +489,64 @@ fn debug_callback(
                         eprintln!("\x1b[31mInvalid break-point: '{n}'\x1b[39m");
                     }
                 }
+                ["info" | "i", "watch" | "w"] => Iterator::for_each(
+                    context
+                        .global_runtime_state()
+                        .debugger()
+                        .watches()
+                        .iter()
+                        .enumerate(),
+                    |(i, watch)| println!("[{}] {watch}", i + 1),
+                ),
+                ["watch" | "w", ..] => {
+                    let expr = input.trim().split_once(' ').map(|(_, x)| x).unwrap_or("");
+                    let n = context
+                        .global_runtime_state_mut()
+                        .debugger_mut()
+                        .add_watch(expr.trim());
+                    println!("Watch expression [{}] added: {}", n + 1, expr.trim());
+                }
+                ["unwatch", n] => {
+                    let removed = n.parse::<usize>().ok().and_then(|n| {
+                        context
+                            .global_runtime_state_mut()
+                            .debugger_mut()
+                            .remove_watch(n.wrapping_sub(1))
+                    });
+                    match removed {
+                        Some(watch) => println!("Watch expression deleted: {}", watch.expr),
+                        None => eprintln!("\x1b[31mInvalid watch expression: '{n}'\x1b[39m"),
+                    }
+                }
+                ["cond", n, _, ..] => {
+                    let condition = input
+                        .trim()
+                        .split_once(' ')
+                        .and_then(|(_, x)| x.trim().split_once(' '))
+                        .map(|(_, x)| x.trim())
+                        .unwrap_or("");
+                    let bp = n.parse::<usize>().ok().and_then(|n| {
+                        context
+                            .global_runtime_state_mut()
+                            .debugger_mut()
+                            .break_points_mut()
+                            .get_mut(n.wrapping_sub(1))
+                    });
+
+                    if let Some(bp) = bp {
+                        let break_point = match bp.clone() {
+                            BreakPoint::Conditional { break_point, .. } => break_point,
+                            bp => bp.into(),
+                        };
+                        *bp = BreakPoint::Conditional {
+                            break_point,
+                            condition: condition.into(),
+                        };
+                        println!("Break-point updated: {bp}");
+                    } else {
+                        eprintln!("\x1b[31mInvalid break-point: '{n}'\x1b[39m");
+                    }
+                }
                 ["delete" | "d"] => {
                     context
                         .global_runtime_state_mut()
@@ -8,7 +8,7 @@ use crate::func::native::{
 };
 use crate::packages::{Package, StandardPackage};
 use crate::tokenizer::Token;
-use crate::types::StringsInterner;
+use crate::types::SharedStrInterner;
 use crate::{Dynamic, Identifier, ImmutableString, Locked, SharedModule};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
@@ -18,6 +18,7 @@ pub type Precedence = NonZeroU8;
 
 pub const KEYWORD_PRINT: &str = "print";
 pub const KEYWORD_DEBUG: &str = "debug";
+pub const FN_PRETTY_PRINT: &str = "pprint";
 pub const KEYWORD_TYPE_OF: &str = "type_of";
 pub const KEYWORD_EVAL: &str = "eval";
 pub const KEYWORD_FN_PTR: &str = "Fn";
@@ -43,6 +44,13 @@ pub const FN_IDX_GET: &str = "index$get$";
 pub const FN_IDX_SET: &str = "index$set$";
 #[cfg(not(feature = "no_function"))]
 pub const FN_ANONYMOUS: &str = "anon$";
+#[cfg(not(feature = "no_module"))]
+pub const KEYWORD_USE: &str = "use";
+
+/// Name of the imported [module][crate::Module] holding the symbols selected via
+/// `import ... use { ... }`.
+#[cfg(not(feature = "no_module"))]
+pub const IMPORT_USE_SYMBOLS: &str = "use$";
 
 /// Standard equality comparison operator.
 ///
@@ -88,70 +96,193 @@ pub const NAMESPACE_SEPARATOR: &str = Token::DoubleColon.literal_syntax();
 pub struct Engine {
     /// A collection of all modules loaded into the global namespace of the Engine.
     pub(crate) global_modules: Vec<SharedModule>,
+    /// Operators scoped to their operand types, which take precedence over all other functions.
+    pub(crate) type_operators: crate::Module,
     /// A collection of all sub-modules directly loaded into the Engine.
     #[cfg(not(feature = "no_module"))]
     pub(crate) global_sub_modules: std::collections::BTreeMap<Identifier, SharedModule>,
+    /// Prelude (constants and script-defined functions) linked into every script, if any.
+    pub(crate) prelude: Option<SharedModule>,
 
     /// A module resolution service.
     #[cfg(not(feature = "no_module"))]
-    pub(crate) module_resolver: Option<Box<dyn crate::ModuleResolver>>,
+    pub(crate) module_resolver: Option<crate::Shared<dyn crate::ModuleResolver>>,
 
     /// Strings interner.
-    pub(crate) interned_strings: Option<Locked<StringsInterner>>,
+    pub(crate) interned_strings: Option<SharedStrInterner>,
 
     /// A set of symbols to disable.
     pub(crate) disabled_symbols: BTreeSet<Identifier>,
+    /// A set of additional keywords to reserve.
+    pub(crate) reserved_keywords: BTreeSet<Identifier>,
     /// A map containing custom keywords and precedence to recognize.
     #[cfg(not(feature = "no_custom_syntax"))]
     pub(crate) custom_keywords: std::collections::BTreeMap<Identifier, Option<Precedence>>,
     /// Custom syntax.
     #[cfg(not(feature = "no_custom_syntax"))]
-    pub(crate) custom_syntax:
-        std::collections::BTreeMap<Identifier, Box<crate::api::custom_syntax::CustomSyntax>>,
+    pub(crate) custom_syntax: std::collections::BTreeMap<
+        Identifier,
+        crate::Shared<crate::api::custom_syntax::CustomSyntax>,
+    >,
+    /// Custom prefixes of integer literals, mapped to their radix.
+    pub(crate) literal_prefixes: std::collections::BTreeMap<char, u32>,
 
     /// Callback closure for filtering variable definition.
-    pub(crate) def_var_filter: Option<Box<OnDefVarCallback>>,
+    pub(crate) def_var_filter: Option<crate::Shared<OnDefVarCallback>>,
     /// Callback closure for resolving variable access.
-    pub(crate) resolve_var: Option<Box<OnVarCallback>>,
+    pub(crate) resolve_var: Option<crate::Shared<OnVarCallback>>,
     /// Callback closure to remap tokens during parsing.
-    pub(crate) token_mapper: Option<Box<OnParseTokenCallback>>,
+    pub(crate) token_mapper: Option<crate::Shared<OnParseTokenCallback>>,
+
+    /// Type name aliases, mapping a type name to the name of another type.
+    pub(crate) type_aliases: std::collections::BTreeMap<Identifier, Identifier>,
+    /// Callback closure to map type names for display.
+    pub(crate) type_name_mapper: Option<crate::Shared<crate::func::native::OnMapTypeNameCallback>>,
+
+    /// Callback closure for running parallel tasks.
+    #[cfg(feature = "parallel")]
+    #[cfg(not(feature = "no_std"))]
+    pub(crate) task_executor: Option<crate::Shared<crate::func::native::OnSpawnTaskCallback>>,
+
+    /// Script coverage data, if collection is turned on.
+    #[cfg(not(feature = "no_position"))]
+    pub(crate) coverage: Option<crate::Shared<Locked<crate::api::coverage::Coverage>>>,
+
+    /// Operations profile, if profiling is turned on.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) op_profile: Option<crate::Shared<Locked<crate::api::op_profile::OperationsProfile>>>,
+
+    /// Tracked script call stack, if call stack sampling is turned on.
+    pub(crate) stack_sampler: Option<crate::api::sampling::StackSampler>,
+
+    /// Dependency graph of modules, if recording is turned on.
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) module_graph: Option<Locked<crate::api::module_graph::ModuleGraph>>,
 
     /// Callback closure when a [`Array`][crate::Array] property accessed does not exist.
     #[cfg(not(feature = "no_index"))]
     #[cfg(feature = "internals")]
-    pub(crate) invalid_array_index: Option<Box<crate::func::native::OnInvalidArrayIndexCallback>>,
+    pub(crate) invalid_array_index:
+        Option<crate::Shared<crate::func::native::OnInvalidArrayIndexCallback>>,
     /// Callback closure when a [`Map`][crate::Map] property accessed does not exist.
     #[cfg(not(feature = "no_object"))]
     #[cfg(feature = "internals")]
-    pub(crate) missing_map_property: Option<Box<crate::func::native::OnMissingMapPropertyCallback>>,
+    pub(crate) missing_map_property:
+        Option<crate::Shared<crate::func::native::OnMissingMapPropertyCallback>>,
+    /// Callbacks, keyed by custom type, when a property accessed does not exist.
+    #[cfg(not(feature = "no_object"))]
+    pub(crate) missing_property: std::collections::BTreeMap<
+        std::any::TypeId,
+        crate::Shared<crate::func::native::OnMissingPropertyCallback>,
+    >,
 
     /// Callback closure for implementing the `print` command.
-    pub(crate) print: Option<Box<OnPrintCallback>>,
+    pub(crate) print: Option<crate::Shared<OnPrintCallback>>,
     /// Callback closure for implementing the `debug` command.
-    pub(crate) debug: Option<Box<OnDebugCallback>>,
+    pub(crate) debug: Option<crate::Shared<OnDebugCallback>>,
+    /// Callback closure for redacting values before output.
+    pub(crate) redact: Option<crate::Shared<crate::func::native::OnRedactCallback>>,
+    /// Callback closure for recording execution traces.
+    pub(crate) trace: Option<crate::Shared<crate::func::native::OnTraceCallback>>,
     /// Callback closure for progress reporting.
     #[cfg(not(feature = "unchecked"))]
-    pub(crate) progress: Option<Box<crate::func::native::OnProgressCallback>>,
+    pub(crate) progress: Option<crate::Shared<crate::func::native::OnProgressCallback>>,
+    /// Threshold (percentage of the maximum number of operations) and callback closure for
+    /// warning that the number of operations is close to the limit.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) operations_warning: Option<(
+        u8,
+        crate::Shared<crate::func::native::OnOperationsWarningCallback>,
+    )>,
+    /// Interval (number of operations) and callback closure for yielding to a cooperative
+    /// scheduler.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) tick: Option<(
+        std::num::NonZeroU64,
+        crate::Shared<crate::func::native::OnTickCallback>,
+    )>,
+    /// Callback closures for reporting the approximate sizes of custom types.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) size_of: std::collections::BTreeMap<
+        std::any::TypeId,
+        crate::Shared<crate::func::native::OnSizeOfCallback>,
+    >,
+
+    /// Codecs of custom types, keyed by type.
+    #[cfg(not(feature = "no_object"))]
+    pub(crate) type_codecs:
+        std::collections::BTreeMap<std::any::TypeId, crate::Shared<crate::api::codec::TypeCodec>>,
+
+    /// Taint sources and sinks, if taint tracking is turned on.
+    pub(crate) taint_rules: Option<Box<crate::api::taint::TaintRules>>,
+
+    /// Native functions that evaluation is locked to, if any.
+    pub(crate) fn_allow_list: Option<crate::Shared<crate::FnAllowList>>,
+
+    /// Signer used to verify the signatures of script files.
+    #[cfg(feature = "signing")]
+    pub(crate) script_signer: Option<crate::ScriptSigner>,
 
     /// Language options.
     pub(crate) options: LangOptions,
 
+    /// Rules for converting floating-point numbers to and from strings.
+    #[cfg(not(feature = "no_float"))]
+    pub(crate) float_format: crate::FloatFormat,
+
     /// Default value for the custom state.
     pub(crate) def_tag: Dynamic,
 
+    /// Host services, keyed by type.
+    pub(crate) services:
+        std::collections::BTreeMap<std::any::TypeId, crate::Shared<crate::func::native::Service>>,
+
+    /// Configuration flags queryable from scripts via `cfg`.
+    pub(crate) cfg_flags: std::collections::BTreeMap<Identifier, Dynamic>,
+
     /// Script optimization level.
     #[cfg(not(feature = "no_optimize"))]
     pub(crate) optimization_level: crate::OptimizationLevel,
+    /// Script optimizer options.
+    #[cfg(not(feature = "no_optimize"))]
+    pub(crate) optimizer_options: crate::OptimizerOptions,
+    /// Script optimizer passes, run in order.
+    /// [`None`] runs only the [`DefaultOptimizerPass`][crate::DefaultOptimizerPass].
+    #[cfg(not(feature = "no_optimize"))]
+    pub(crate) optimizer_passes: Option<Vec<crate::Shared<dyn crate::OptimizerPass>>>,
 
     /// Max limits.
     #[cfg(not(feature = "unchecked"))]
     pub(crate) limits: crate::api::limits::Limits,
 
+    /// Behavior of integer arithmetic on overflow.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) arithmetic_mode: crate::api::arithmetic::ArithmeticMode,
+
+    /// Cache of compiled regular expressions, keyed by pattern.
+    #[cfg(feature = "regex")]
+    pub(crate) regex_cache: Locked<std::collections::BTreeMap<ImmutableString, regex::Regex>>,
+
+    /// Cache of locale collators, keyed by locale.
+    #[cfg(feature = "icu")]
+    pub(crate) collator_cache:
+        Locked<std::collections::BTreeMap<ImmutableString, crate::Shared<icu_collator::Collator>>>,
+
+    /// Cache of locale plural rules, keyed by locale and whether they are ordinal.
+    #[cfg(feature = "icu")]
+    #[cfg(not(feature = "no_object"))]
+    pub(crate) plural_rules_cache: Locked<
+        std::collections::BTreeMap<
+            (ImmutableString, bool),
+            crate::Shared<icu_plurals::PluralRules>,
+        >,
+    >,
+
     /// Callback closure for debugging.
     #[cfg(feature = "debugging")]
     pub(crate) debugger_interface: Option<(
-        Box<crate::eval::OnDebuggingInit>,
-        Box<crate::eval::OnDebuggerCallback>,
+        crate::Shared<crate::eval::OnDebuggingInit>,
+        crate::Shared<crate::eval::OnDebuggerCallback>,
     )>,
 }
 
@@ -161,12 +292,15 @@ impl fmt::Debug for Engine {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut f = f.debug_struct("Engine");
 
-        f.field("global_modules", &self.global_modules);
+        f.field("global_modules", &self.global_modules)
+            .field("type_operators", &self.type_operators);
 
         #[cfg(not(feature = "no_module"))]
         f.field("global_sub_modules", &self.global_sub_modules);
 
-        f.field("disabled_symbols", &self.disabled_symbols);
+        f.field("prelude", &self.prelude)
+            .field("disabled_symbols", &self.disabled_symbols)
+            .field("reserved_keywords", &self.reserved_keywords);
 
         #[cfg(not(feature = "no_custom_syntax"))]
         f.field("custom_keywords", &self.custom_keywords).field(
@@ -178,21 +312,89 @@ impl fmt::Debug for Engine {
                 .collect::<String>(),
         );
 
-        f.field("def_var_filter", &self.def_var_filter.is_some())
+        f.field("literal_prefixes", &self.literal_prefixes)
+            .field("def_var_filter", &self.def_var_filter.is_some())
             .field("resolve_var", &self.resolve_var.is_some())
-            .field("token_mapper", &self.token_mapper.is_some());
+            .field("token_mapper", &self.token_mapper.is_some())
+            .field("type_aliases", &self.type_aliases)
+            .field("type_name_mapper", &self.type_name_mapper.is_some());
+
+        #[cfg(feature = "parallel")]
+        #[cfg(not(feature = "no_std"))]
+        f.field("task_executor", &self.task_executor.is_some());
+
+        #[cfg(not(feature = "no_position"))]
+        f.field("coverage", &self.coverage.is_some());
+
+        #[cfg(not(feature = "unchecked"))]
+        f.field("op_profile", &self.op_profile.is_some());
+
+        f.field("stack_sampler", &self.stack_sampler.is_some());
+
+        #[cfg(not(feature = "no_module"))]
+        f.field("module_graph", &self.module_graph.is_some());
 
         #[cfg(not(feature = "unchecked"))]
-        f.field("progress", &self.progress.is_some());
+        f.field("progress", &self.progress.is_some())
+            .field(
+                "operations_warning",
+                &self
+                    .operations_warning
+                    .as_ref()
+                    .map(|(percent, ..)| percent),
+            )
+            .field("tick", &self.tick.as_ref().map(|(interval, ..)| interval))
+            .field("size_of", &self.size_of.len());
+
+        #[cfg(not(feature = "no_object"))]
+        f.field("type_codecs", &self.type_codecs.len());
+
+        f.field("taint_rules", &self.taint_rules)
+            .field("fn_allow_list", &self.fn_allow_list.is_some());
 
-        f.field("options", &self.options)
-            .field("default_tag", &self.def_tag);
+        #[cfg(feature = "signing")]
+        f.field("script_signer", &self.script_signer.is_some());
+
+        f.field("options", &self.options);
+
+        #[cfg(not(feature = "no_float"))]
+        f.field("float_format", &self.float_format);
+
+        f.field("default_tag", &self.def_tag)
+            .field("services", &self.services.len())
+            .field("cfg_flags", &self.cfg_flags);
 
         #[cfg(not(feature = "no_optimize"))]
-        f.field("optimization_level", &self.optimization_level);
+        f.field("optimization_level", &self.optimization_level)
+            .field("optimizer_options", &self.optimizer_options)
+            .field(
+                "optimizer_passes",
+                &self
+                    .optimizer_passes()
+                    .map(|p| p.name())
+                    .collect::<Vec<_>>(),
+            );
 
         #[cfg(not(feature = "unchecked"))]
-        f.field("limits", &self.limits);
+        f.field("limits", &self.limits)
+            .field("arithmetic_mode", &self.arithmetic_mode);
+
+        #[cfg(feature = "regex")]
+        f.field(
+            "regex_cache",
+            &crate::func::locked_read(&self.regex_cache).map_or(0, |c| c.len()),
+        );
+        #[cfg(feature = "icu")]
+        f.field(
+            "collator_cache",
+            &crate::func::locked_read(&self.collator_cache).map_or(0, |c| c.len()),
+        );
+        #[cfg(feature = "icu")]
+        #[cfg(not(feature = "no_object"))]
+        f.field(
+            "plural_rules_cache",
+            &crate::func::locked_read(&self.plural_rules_cache).map_or(0, |c| c.len()),
+        );
 
         #[cfg(feature = "debugging")]
         f.field("debugger_interface", &self.debugger_interface.is_some());
@@ -235,47 +437,113 @@ impl Engine {
     /// An empty raw [`Engine`].
     pub const RAW: Self = Self {
         global_modules: Vec::new(),
+        type_operators: crate::Module::new(),
 
         #[cfg(not(feature = "no_module"))]
         global_sub_modules: std::collections::BTreeMap::new(),
 
+        prelude: None,
+
         #[cfg(not(feature = "no_module"))]
         module_resolver: None,
 
         interned_strings: None,
         disabled_symbols: BTreeSet::new(),
+        reserved_keywords: BTreeSet::new(),
         #[cfg(not(feature = "no_custom_syntax"))]
         custom_keywords: std::collections::BTreeMap::new(),
         #[cfg(not(feature = "no_custom_syntax"))]
         custom_syntax: std::collections::BTreeMap::new(),
+        literal_prefixes: std::collections::BTreeMap::new(),
 
         def_var_filter: None,
         resolve_var: None,
         token_mapper: None,
 
+        type_aliases: std::collections::BTreeMap::new(),
+        type_name_mapper: None,
+
+        #[cfg(feature = "parallel")]
+        #[cfg(not(feature = "no_std"))]
+        task_executor: None,
+
+        #[cfg(not(feature = "no_position"))]
+        coverage: None,
+
+        #[cfg(not(feature = "unchecked"))]
+        op_profile: None,
+
+        stack_sampler: None,
+
+        #[cfg(not(feature = "no_module"))]
+        module_graph: None,
+
         #[cfg(not(feature = "no_index"))]
         #[cfg(feature = "internals")]
         invalid_array_index: None,
         #[cfg(not(feature = "no_object"))]
         #[cfg(feature = "internals")]
         missing_map_property: None,
+        #[cfg(not(feature = "no_object"))]
+        missing_property: std::collections::BTreeMap::new(),
 
         print: None,
         debug: None,
+        redact: None,
+        trace: None,
 
         #[cfg(not(feature = "unchecked"))]
         progress: None,
+        #[cfg(not(feature = "unchecked"))]
+        operations_warning: None,
+        #[cfg(not(feature = "unchecked"))]
+        tick: None,
+        #[cfg(not(feature = "unchecked"))]
+        size_of: std::collections::BTreeMap::new(),
+
+        #[cfg(not(feature = "no_object"))]
+        type_codecs: std::collections::BTreeMap::new(),
+
+        taint_rules: None,
+
+        fn_allow_list: None,
+
+        #[cfg(feature = "signing")]
+        script_signer: None,
 
         options: LangOptions::new(),
 
+        #[cfg(not(feature = "no_float"))]
+        float_format: crate::FloatFormat::new(),
+
         def_tag: Dynamic::UNIT,
 
+        services: std::collections::BTreeMap::new(),
+
+        cfg_flags: std::collections::BTreeMap::new(),
+
         #[cfg(not(feature = "no_optimize"))]
         optimization_level: crate::OptimizationLevel::Simple,
+        #[cfg(not(feature = "no_optimize"))]
+        optimizer_options: crate::OptimizerOptions::new(),
+        #[cfg(not(feature = "no_optimize"))]
+        optimizer_passes: None,
 
         #[cfg(not(feature = "unchecked"))]
         limits: crate::api::limits::Limits::new(),
 
+        #[cfg(not(feature = "unchecked"))]
+        arithmetic_mode: crate::api::arithmetic::ArithmeticMode::Checked,
+
+        #[cfg(feature = "regex")]
+        regex_cache: Locked::new(std::collections::BTreeMap::new()),
+
+        #[cfg(feature = "icu")]
+        collator_cache: Locked::new(std::collections::BTreeMap::new()),
+        #[cfg(feature = "icu")]
+        #[cfg(not(feature = "no_object"))]
+        plural_rules_cache: Locked::new(std::collections::BTreeMap::new()),
+
         #[cfg(feature = "debugging")]
         debugger_interface: None,
     };
@@ -291,8 +559,9 @@ impl Engine {
         #[cfg(not(feature = "no_std"))]
         #[cfg(any(not(target_family = "wasm"), not(target_os = "unknown")))]
         {
-            engine.module_resolver =
-                Some(Box::new(crate::module::resolvers::FileModuleResolver::new()));
+            engine.module_resolver = Some(crate::Shared::new(
+                crate::module::resolvers::FileModuleResolver::new(),
+            ));
         }
 
         // Turn on the strings interner
@@ -302,8 +571,8 @@ impl Engine {
         #[cfg(not(feature = "no_std"))]
         #[cfg(any(not(target_family = "wasm"), not(target_os = "unknown")))]
         {
-            engine.print = Some(Box::new(|s| println!("{s}")));
-            engine.debug = Some(Box::new(|s, source, pos| match (source, pos) {
+            engine.print = Some(crate::Shared::new(|s| println!("{s}")));
+            engine.debug = Some(crate::Shared::new(|s, source, pos| match (source, pos) {
                 (Some(source), crate::Position::NONE) => println!("{source} | {s}"),
                 #[cfg(not(feature = "no_position"))]
                 (Some(source), pos) => println!("{source} @ {pos:?} | {s}"),
@@ -331,6 +600,159 @@ impl Engine {
         Self::RAW
     }
 
+    /// Create a cheap copy of this [`Engine`], e.g. to serve each request in a web server with
+    /// its own [`Engine`] without re-registering all functions.
+    ///
+    /// All registered modules, packages, custom syntax, callbacks, host services and the module
+    /// resolver are shared with the fork behind reference-counted pointers.
+    /// Registering new functions on either [`Engine`] copies its global namespace on write,
+    /// so changes made to a fork never affect the original, and vice versa.
+    ///
+    /// Script coverage data, operations profiles, call stack sampling, the module dependency
+    /// graph and internal caches are not shared. They start out empty in the fork.
+    ///
+    /// Host services stay shared until replaced via [`set_service`][Engine::set_service] on either
+    /// [`Engine`], so [`service_mut`][Engine::service_mut] and
+    /// [`remove_service`][Engine::remove_service] cannot get hold of a shared service. Use
+    /// services with interior mutability (e.g. a `Mutex`) to modify them from a fork.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, INT};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn("add", |x: INT, y: INT| x + y);
+    ///
+    /// let mut fork = engine.fork();
+    ///
+    /// fork.register_fn("double", |x: INT| x * 2);
+    ///
+    /// assert_eq!(fork.eval::<INT>("double(add(20, 1))")?, 42);
+    ///
+    /// // The original engine is not affected
+    /// assert!(engine.eval::<INT>("double(add(20, 1))").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        Self {
+            global_modules: self.global_modules.clone(),
+            type_operators: self.type_operators.clone(),
+
+            #[cfg(not(feature = "no_module"))]
+            global_sub_modules: self.global_sub_modules.clone(),
+
+            prelude: self.prelude.clone(),
+
+            #[cfg(not(feature = "no_module"))]
+            module_resolver: self.module_resolver.clone(),
+
+            interned_strings: self.interned_strings.clone(),
+            disabled_symbols: self.disabled_symbols.clone(),
+            reserved_keywords: self.reserved_keywords.clone(),
+            #[cfg(not(feature = "no_custom_syntax"))]
+            custom_keywords: self.custom_keywords.clone(),
+            #[cfg(not(feature = "no_custom_syntax"))]
+            custom_syntax: self.custom_syntax.clone(),
+            literal_prefixes: self.literal_prefixes.clone(),
+
+            def_var_filter: self.def_var_filter.clone(),
+            resolve_var: self.resolve_var.clone(),
+            token_mapper: self.token_mapper.clone(),
+
+            type_aliases: self.type_aliases.clone(),
+            type_name_mapper: self.type_name_mapper.clone(),
+
+            #[cfg(feature = "parallel")]
+            #[cfg(not(feature = "no_std"))]
+            task_executor: self.task_executor.clone(),
+
+            #[cfg(not(feature = "no_position"))]
+            coverage: self.coverage.as_ref().map(|_| Default::default()),
+
+            #[cfg(not(feature = "unchecked"))]
+            op_profile: self.op_profile.as_ref().map(|_| Default::default()),
+
+            stack_sampler: self.stack_sampler.as_ref().map(|_| Default::default()),
+
+            #[cfg(not(feature = "no_module"))]
+            module_graph: self.module_graph.as_ref().map(|_| Default::default()),
+
+            #[cfg(not(feature = "no_index"))]
+            #[cfg(feature = "internals")]
+            invalid_array_index: self.invalid_array_index.clone(),
+            #[cfg(not(feature = "no_object"))]
+            #[cfg(feature = "internals")]
+            missing_map_property: self.missing_map_property.clone(),
+            #[cfg(not(feature = "no_object"))]
+            missing_property: self.missing_property.clone(),
+
+            print: self.print.clone(),
+            debug: self.debug.clone(),
+            redact: self.redact.clone(),
+            trace: self.trace.clone(),
+
+            #[cfg(not(feature = "unchecked"))]
+            progress: self.progress.clone(),
+            #[cfg(not(feature = "unchecked"))]
+            operations_warning: self.operations_warning.clone(),
+            #[cfg(not(feature = "unchecked"))]
+            tick: self.tick.clone(),
+            #[cfg(not(feature = "unchecked"))]
+            size_of: self.size_of.clone(),
+
+            #[cfg(not(feature = "no_object"))]
+            type_codecs: self.type_codecs.clone(),
+
+            taint_rules: self.taint_rules.clone(),
+
+            fn_allow_list: self.fn_allow_list.clone(),
+
+            #[cfg(feature = "signing")]
+            script_signer: self.script_signer.clone(),
+
+            options: self.options,
+
+            #[cfg(not(feature = "no_float"))]
+            float_format: self.float_format,
+
+            def_tag: self.def_tag.clone(),
+
+            services: self.services.clone(),
+
+            cfg_flags: self.cfg_flags.clone(),
+
+            #[cfg(not(feature = "no_optimize"))]
+            optimization_level: self.optimization_level,
+            #[cfg(not(feature = "no_optimize"))]
+            optimizer_options: self.optimizer_options,
+            #[cfg(not(feature = "no_optimize"))]
+            optimizer_passes: self.optimizer_passes.clone(),
+
+            #[cfg(not(feature = "unchecked"))]
+            limits: self.limits.clone(),
+
+            #[cfg(not(feature = "unchecked"))]
+            arithmetic_mode: self.arithmetic_mode,
+
+            #[cfg(feature = "regex")]
+            regex_cache: Locked::new(std::collections::BTreeMap::new()),
+
+            #[cfg(feature = "icu")]
+            collator_cache: Locked::new(std::collections::BTreeMap::new()),
+            #[cfg(feature = "icu")]
+            #[cfg(not(feature = "no_object"))]
+            plural_rules_cache: Locked::new(std::collections::BTreeMap::new()),
+
+            #[cfg(feature = "debugging")]
+            debugger_interface: self.debugger_interface.clone(),
+        }
+    }
+
     /// Get an interned [string][ImmutableString].
     ///
     /// [`Engine`] keeps a cache of [`ImmutableString`] instances and tries to avoid new allocations
@@ -345,7 +767,7 @@ impl Engine {
     ) -> ImmutableString {
         match self.interned_strings {
             Some(ref interner) => match locked_write(interner) {
-                Some(mut cache) => cache.get(string),
+                Some(mut cache) => cache.intern(0, string.as_ref(), &mut |s| s.into()),
                 None => string.into(),
             },
             None => string.into(),
@@ -362,7 +784,7 @@ impl Engine {
         match self.interned_strings {
             Some(ref interner) => match locked_write(interner) {
                 Some(mut cache) => {
-                    cache.get_with_mapper(b'g', |s| make_getter(s.as_ref()).into(), text)
+                    cache.intern(b'g', text.as_ref(), &mut |s| make_getter(s).into())
                 }
                 None => make_getter(text.as_ref()).into(),
             },
@@ -381,7 +803,7 @@ impl Engine {
         match self.interned_strings {
             Some(ref interner) => match locked_write(interner) {
                 Some(mut cache) => {
-                    cache.get_with_mapper(b's', |s| make_setter(s.as_ref()).into(), text)
+                    cache.intern(b's', text.as_ref(), &mut |s| make_setter(s).into())
                 }
                 None => make_setter(text.as_ref()).into(),
             },
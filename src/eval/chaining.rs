@@ -76,6 +76,35 @@ impl Engine {
         .map(|(r, ..)| r)
     }
 
+    /// Get a property not found via its getter, by calling a get indexer with the property name
+    /// or, failing that, the missing-property callback registered for the type of the object.
+    ///
+    /// `err` is the error returned if neither is available.
+    #[cfg(not(feature = "no_object"))]
+    fn call_missing_property_get(
+        &self,
+        global: &mut GlobalRuntimeState,
+        caches: &mut Caches,
+        target: &mut Dynamic,
+        name: &str,
+        err: crate::RhaiError,
+        pos: Position,
+    ) -> RhaiResultOf<Dynamic> {
+        let mut prop = name.into();
+
+        match self.call_indexer_get(global, caches, target, &mut prop, pos) {
+            Err(e) if matches!(*e, ERR::ErrorIndexingType(..)) => {
+                match self.missing_property.get(&target.type_id()) {
+                    Some(resolve) => {
+                        resolve(target, name).map_err(|e| e.fill_position(err.position()))
+                    }
+                    None => Err(err),
+                }
+            }
+            r => r,
+        }
+    }
+
     /// Call a set indexer.
     #[inline]
     fn call_indexer_set(
@@ -120,6 +149,10 @@ impl Engine {
     ) -> RhaiResultOf<Target<'t>> {
         self.track_operation(global, Position::NONE)?;
 
+        // Characters and slices of a tainted string are tainted
+        #[cfg(not(feature = "no_index"))]
+        let tainted = self.taint_tracking() && target.is_tainted();
+
         match target {
             #[cfg(not(feature = "no_index"))]
             Dynamic(Union::Array(arr, ..)) => {
@@ -169,8 +202,22 @@ impl Engine {
                 })
             }
 
+            #[cfg(not(feature = "no_index"))]
+            Dynamic(Union::SharedBlob(blob, ..)) if !_add_if_not_found => {
+                // val_shared_blob[idx] - read-only
+                let index = idx
+                    .as_int()
+                    .map_err(|typ| self.make_type_mismatch_err::<crate::INT>(typ, idx_pos))?;
+                let len = blob.len();
+                let blob_idx = super::calc_index(len, index, true, || {
+                    ERR::ErrorArrayBounds(len, index, idx_pos).into()
+                })?;
+
+                Ok(Target::from(blob[blob_idx] as crate::INT))
+            }
+
             #[cfg(not(feature = "no_object"))]
-            Dynamic(Union::Map(map, ..)) => {
+            Dynamic(Union::Map(map, _, flags)) => {
                 // val_map[idx]
                 let index = idx.read_lock::<crate::ImmutableString>().ok_or_else(|| {
                     self.make_type_mismatch_err::<crate::ImmutableString>(idx.type_name(), idx_pos)
@@ -188,6 +235,13 @@ impl Engine {
                 }
 
                 if _add_if_not_found && (map.is_empty() || !map.contains_key(index.as_str())) {
+                    // Cannot add properties to a frozen object map
+                    if flags.contains(crate::types::dynamic::DynamicFlags::FROZEN) {
+                        return Err(
+                            ERR::ErrorAssignmentToConstant(index.to_string(), idx_pos).into()
+                        );
+                    }
+
                     map.insert(index.clone().into(), Dynamic::UNIT);
                 }
 
@@ -357,9 +411,15 @@ impl Engine {
                             )
                         };
 
+                        let mut value: Dynamic = ch.into();
+
+                        if tainted {
+                            value.set_tainted(true);
+                        }
+
                         Ok(Target::StringChar {
                             source: target,
-                            value: ch.into(),
+                            value,
                             index: offset,
                         })
                     }
@@ -370,7 +430,12 @@ impl Engine {
                             || typ == std::any::type_name::<InclusiveRange>())
                             && s.is_empty() =>
                     {
-                        let value = s.clone().into();
+                        let mut value: Dynamic = s.clone().into();
+
+                        if tainted {
+                            value.set_tainted(true);
+                        }
+
                         Ok(Target::StringSlice {
                             source: target,
                             value,
@@ -402,13 +467,17 @@ impl Engine {
                             .unwrap_or(0)
                         };
 
-                        let value = if start == 0 && end >= chars_count {
+                        let mut value: Dynamic = if start == 0 && end >= chars_count {
                             s.clone().into()
                         } else {
                             let take = if end > start { end - start } else { 0 };
                             s.chars().skip(start).take(take).collect::<String>().into()
                         };
 
+                        if tainted {
+                            value.set_tainted(true);
+                        }
+
                         Ok(Target::StringSlice {
                             source: target,
                             value,
@@ -438,13 +507,17 @@ impl Engine {
                             .unwrap_or(0)
                         };
 
-                        let value = if start == 0 && end >= chars_count - 1 {
+                        let mut value: Dynamic = if start == 0 && end >= chars_count - 1 {
                             s.clone().into()
                         } else {
                             let take = if end > start { end - start + 1 } else { 0 };
                             s.chars().skip(start).take(take).collect::<String>().into()
                         };
 
+                        if tainted {
+                            value.set_tainted(true);
+                        }
+
                         Ok(Target::StringSlice {
                             source: target,
                             value,
@@ -906,6 +979,7 @@ impl Engine {
                         self.make_method_call(
                             global, caches, name, *hashes, target, call_args, arg1_pos, *pos,
                         )
+                        .map(|(v, u)| (crate::func::call::BuilderUpdated::into_unit(v), u))
                     }
                     // {xxx:map}.id op= ???
                     (Expr::Property(x, pos), Some((new_val, op_info)), true) => {
@@ -982,17 +1056,10 @@ impl Engine {
                                     // Try an indexer if property does not exist
                                     ERR::ErrorDotExpr(..) => {
                                         let target = target.as_mut();
-                                        let mut prop = name.into();
-                                        self.call_indexer_get(
-                                            global, caches, target, &mut prop, op_pos,
+                                        self.call_missing_property_get(
+                                            global, caches, target, name, err, op_pos,
                                         )
                                         .map(|r| (r, false))
-                                        .map_err(|e| {
-                                            match *e {
-                                                ERR::ErrorIndexingType(..) => err,
-                                                _ => e,
-                                            }
-                                        })
                                     }
                                     _ => Err(err),
                                 })?;
@@ -1046,13 +1113,10 @@ impl Engine {
                                 // Try an indexer if property does not exist
                                 ERR::ErrorDotExpr(..) => {
                                     let target = target.as_mut();
-                                    let mut prop = name.into();
-                                    self.call_indexer_get(global, caches, target, &mut prop, op_pos)
-                                        .map(|r| (r, false))
-                                        .map_err(|e| match *e {
-                                            ERR::ErrorIndexingType(..) => err,
-                                            _ => e,
-                                        })
+                                    self.call_missing_property_get(
+                                        global, caches, target, name, err, op_pos,
+                                    )
+                                    .map(|r| (r, false))
                                 }
                                 _ => Err(err),
                             },
@@ -1113,11 +1177,16 @@ impl Engine {
                                 let call_args = &mut idx_values[offset..];
                                 let arg1_pos = args.first().map_or(Position::NONE, Expr::position);
 
-                                self.make_method_call(
+                                let (value, ..) = self.make_method_call(
                                     global, caches, name, *hashes, target, call_args, arg1_pos, pos,
-                                )?
-                                .0
-                                .into()
+                                )?;
+
+                                // A builder method has updated the object, so continue with it
+                                if value.is::<crate::func::call::BuilderUpdated>() {
+                                    Target::RefMut(target.as_mut())
+                                } else {
+                                    value.into()
+                                }
                             }
                             // Others - syntax error
                             ref expr => unreachable!("invalid dot expression: {:?}", expr),
@@ -1153,17 +1222,10 @@ impl Engine {
                                         // Try an indexer if property does not exist
                                         ERR::ErrorDotExpr(..) => {
                                             let target = target.as_mut();
-                                            let mut prop = name.into();
-                                            self.call_indexer_get(
-                                                global, caches, target, &mut prop, op_pos,
+                                            self.call_missing_property_get(
+                                                global, caches, target, name, err, op_pos,
                                             )
                                             .map(|r| (r, false))
-                                            .map_err(
-                                                |e| match *e {
-                                                    ERR::ErrorIndexingType(..) => err,
-                                                    _ => e,
-                                                },
-                                            )
                                         }
                                         _ => Err(err),
                                     })?;
@@ -1242,6 +1304,18 @@ impl Engine {
                                     .0
                                 };
 
+                                // A builder method has updated the object, so continue with it
+                                if val.is::<crate::func::call::BuilderUpdated>() {
+                                    let val = &mut Target::RefMut(target.as_mut());
+
+                                    return self
+                                        .eval_dot_index_chain_raw(
+                                            global, caches, s, _this_ptr, root, rhs, val, &x.rhs,
+                                            idx_values, new_val,
+                                        )
+                                        .map(|(v, ..)| (v, true));
+                                }
+
                                 let val = &mut val.into();
 
                                 self.eval_dot_index_chain_raw(
@@ -31,6 +31,7 @@ pub fn calc_array_sizes(array: &crate::Array) -> (usize, usize, usize) {
                 sx += s;
             }
             Union::Blob(ref a, ..) => ax += 1 + a.len(),
+            Union::SharedBlob(ref a, ..) => ax += 1 + a.len(),
             #[cfg(not(feature = "no_object"))]
             Union::Map(ref m, ..) => {
                 let (a, m, s) = calc_map_sizes(m);
@@ -74,6 +75,8 @@ pub fn calc_map_sizes(map: &crate::Map) -> (usize, usize, usize) {
             }
             #[cfg(not(feature = "no_index"))]
             Union::Blob(ref a, ..) => ax += 1 + a.len(),
+            #[cfg(not(feature = "no_index"))]
+            Union::SharedBlob(ref a, ..) => ax += 1 + a.len(),
             Union::Map(ref m, ..) => {
                 let (a, m, s) = calc_map_sizes(m);
                 ax += a;
@@ -106,6 +109,8 @@ pub fn calc_data_sizes(value: &Dynamic, _top: bool) -> (usize, usize, usize) {
         Union::Array(ref arr, ..) => calc_array_sizes(arr),
         #[cfg(not(feature = "no_index"))]
         Union::Blob(ref blob, ..) => (blob.len(), 0, 0),
+        #[cfg(not(feature = "no_index"))]
+        Union::SharedBlob(ref blob, ..) => (blob.len(), 0, 0),
         #[cfg(not(feature = "no_object"))]
         Union::Map(ref map, ..) => calc_map_sizes(map),
         Union::Str(ref s, ..) => (0, 0, s.len()),
@@ -167,9 +172,49 @@ impl Engine {
         self.throw_on_size(sizes)
             .map_err(|err| err.fill_position(pos))?;
 
+        if let Some(max) = self.limits.custom_data_size {
+            if self.calc_custom_data_size(value.borrow(), true) > max.get() {
+                return Err(ERR::ErrorDataTooLarge("Size of custom data".to_string(), pos).into());
+            }
+        }
+
         Ok(value)
     }
 
+    /// Recursively calculate the total size of all custom types within a value, as reported by
+    /// the hooks registered via [`register_size_of`][Engine::register_size_of].
+    ///
+    /// A shared value is only followed at the top level, and is skipped if it is currently locked.
+    #[cfg(not(feature = "unchecked"))]
+    #[must_use]
+    pub(crate) fn calc_custom_data_size(&self, value: &Dynamic, _top: bool) -> usize {
+        if self.size_of.is_empty() {
+            return 0;
+        }
+
+        match value.0 {
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(ref arr, ..) => arr
+                .iter()
+                .map(|v| self.calc_custom_data_size(v, false))
+                .sum(),
+            #[cfg(not(feature = "no_object"))]
+            Union::Map(ref map, ..) => map
+                .values()
+                .map(|v| self.calc_custom_data_size(v, false))
+                .sum(),
+            #[cfg(not(feature = "no_closure"))]
+            Union::Shared(..) if _top => value
+                .read_lock::<Dynamic>()
+                .map_or(0, |v| self.calc_custom_data_size(&v, false)),
+            Union::Variant(..) => self
+                .size_of
+                .get(&value.type_id())
+                .map_or(0, |size_of| size_of(value)),
+            _ => 0,
+        }
+    }
+
     /// Raise an error if the size of a [`Dynamic`] is out of limits (if any).
     ///
     /// Not available under `unchecked`.
@@ -188,18 +233,78 @@ impl Engine {
     ) -> RhaiResultOf<()> {
         global.num_operations += 1;
 
+        self.track_op_profile(global, pos);
+
+        // Guard against overflowing the native stack
+        #[cfg(feature = "stack_guard")]
+        if self.min_stack_remaining() > 0
+            && stacker::remaining_stack().map_or(false, |n| n < self.min_stack_remaining())
+        {
+            return Err(ERR::ErrorStackOverflow(pos).into());
+        }
+
         // Guard against too many operations
         #[cfg(not(feature = "unchecked"))]
         if self.max_operations() > 0 && global.num_operations > self.max_operations() {
             return Err(ERR::ErrorTooManyOperations(pos).into());
         }
 
-        self.progress
-            .as_ref()
-            .and_then(|progress| {
-                progress(global.num_operations)
-                    .map(|token| Err(ERR::ErrorTerminated(token, pos).into()))
-            })
-            .unwrap_or(Ok(()))
+        // Warn when close to the maximum number of operations
+        #[cfg(not(feature = "unchecked"))]
+        if let Some((percent, ref callback)) = self.operations_warning {
+            let max = self.max_operations();
+            let threshold = (u128::from(max) * u128::from(percent) / 100) as u64;
+
+            if !global.operations_warned && max > 0 && global.num_operations >= threshold {
+                global.operations_warned = true;
+                callback(global.num_operations, max, pos);
+            }
+        }
+
+        // Guard against exceeding the operations limit of the module being run
+        #[cfg(not(feature = "unchecked"))]
+        if global
+            .operations_limit
+            .map_or(false, |limit| global.num_operations > limit)
+        {
+            return Err(ERR::ErrorTooManyOperations(pos).into());
+        }
+
+        // Guard against running past the deadline
+        #[cfg(not(feature = "unchecked"))]
+        #[cfg(not(feature = "no_time"))]
+        if let Some(deadline) = global.deadline {
+            if crate::Instant::now() > deadline {
+                return Err(ERR::ErrorDeadlineExceeded(pos).into());
+            }
+        }
+
+        // Yield to a cooperative scheduler
+        if let Some((interval, ref callback)) = self.tick {
+            if global.num_operations % interval.get() == 0 {
+                callback(global.num_operations);
+            }
+        }
+
+        // Report progress
+        if let Some(ref progress) = self.progress {
+            let info = crate::ProgressInfo {
+                operations: global.num_operations,
+                source: global.source(),
+                position: pos,
+                call_depth: global.level,
+                #[cfg(not(feature = "no_time"))]
+                elapsed: global
+                    .start_time
+                    .map(|start| start.elapsed())
+                    .unwrap_or_default(),
+            };
+
+            if let crate::ProgressControl::Abort(token) = progress(&info) {
+                return Err(ERR::ErrorTerminated(token, pos).into());
+            }
+        }
+
+        Ok(())
     }
 }
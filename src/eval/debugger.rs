@@ -47,6 +47,9 @@ pub enum DebuggerCommand {
     Next,
     /// Run to the end of the current function call.
     FunctionExit,
+    /// Run to the end of the current function call, then stop at the next statement or
+    /// expression in the caller.
+    StepOut,
 }
 
 /// The debugger status.
@@ -59,6 +62,9 @@ pub enum DebuggerStatus {
     Next(bool, bool),
     // Run to the end of the current level of function call.
     FunctionExit(usize),
+    // Run to the end of the current level of function call, then stop at the next statement or
+    // expression.
+    StepOut(usize),
     // Script evaluation ends.
     Terminate,
 }
@@ -130,6 +136,16 @@ pub enum BreakPoint {
         /// Is the break-point enabled?
         enabled: bool,
     },
+    /// Break at another break-point only when a script expression, evaluated in the current
+    /// scope, returns `true`.
+    ///
+    /// The break-point is also triggered when the expression fails to evaluate.
+    Conditional {
+        /// The underlying break-point.
+        break_point: Box<BreakPoint>,
+        /// Script expression of the condition.
+        condition: ImmutableString,
+    },
 }
 
 impl fmt::Display for BreakPoint {
@@ -180,6 +196,10 @@ impl fmt::Display for BreakPoint {
                 }
                 Ok(())
             }
+            Self::Conditional {
+                break_point,
+                condition,
+            } => write!(f, "{break_point} if {condition}"),
         }
     }
 }
@@ -195,6 +215,7 @@ impl BreakPoint {
             Self::AtFunctionName { enabled, .. } | Self::AtFunctionCall { enabled, .. } => *enabled,
             #[cfg(not(feature = "no_object"))]
             Self::AtProperty { enabled, .. } => *enabled,
+            Self::Conditional { break_point, .. } => break_point.is_enabled(),
         }
     }
     /// Enable/disable this [`BreakPoint`].
@@ -208,6 +229,85 @@ impl BreakPoint {
             }
             #[cfg(not(feature = "no_object"))]
             Self::AtProperty { enabled, .. } => *enabled = value,
+            Self::Conditional { break_point, .. } => break_point.enable(value),
+        }
+    }
+    /// Get the condition of this [`BreakPoint`], if any.
+    #[inline]
+    #[must_use]
+    pub fn condition(&self) -> Option<&str> {
+        match self {
+            Self::Conditional { condition, .. } => Some(condition.as_str()),
+            _ => None,
+        }
+    }
+    /// Does this [`BreakPoint`] match a particular [`AST` Node][ASTNode], without considering
+    /// its condition (if any)?
+    #[must_use]
+    pub fn matches(&self, src: Option<&str>, node: ASTNode) -> bool {
+        let _src = src;
+
+        match self {
+            #[cfg(not(feature = "no_position"))]
+            Self::AtPosition { pos, .. } if pos.is_none() => false,
+            #[cfg(not(feature = "no_position"))]
+            Self::AtPosition { source, pos, .. } if pos.is_beginning_of_line() => {
+                node.position().line().unwrap_or(0) == pos.line().unwrap()
+                    && _src == source.as_deref()
+            }
+            #[cfg(not(feature = "no_position"))]
+            Self::AtPosition { source, pos, .. } => {
+                node.position() == *pos && _src == source.as_deref()
+            }
+            Self::AtFunctionName { name, .. } => match node {
+                ASTNode::Expr(Expr::FnCall(x, ..)) | ASTNode::Stmt(Stmt::FnCall(x, ..)) => {
+                    x.name == *name
+                }
+                ASTNode::Stmt(Stmt::Expr(e)) => match &**e {
+                    Expr::FnCall(x, ..) => x.name == *name,
+                    _ => false,
+                },
+                _ => false,
+            },
+            Self::AtFunctionCall { name, args, .. } => match node {
+                ASTNode::Expr(Expr::FnCall(x, ..)) | ASTNode::Stmt(Stmt::FnCall(x, ..)) => {
+                    x.args.len() == *args && x.name == *name
+                }
+                ASTNode::Stmt(Stmt::Expr(e)) => match &**e {
+                    Expr::FnCall(x, ..) => x.args.len() == *args && x.name == *name,
+                    _ => false,
+                },
+                _ => false,
+            },
+            #[cfg(not(feature = "no_object"))]
+            Self::AtProperty { name, .. } => match node {
+                ASTNode::Expr(Expr::Property(x, ..)) => x.2 == *name,
+                _ => false,
+            },
+            Self::Conditional { break_point, .. } => break_point.matches(src, node),
+        }
+    }
+}
+
+/// A watch expression for debugging.
+#[derive(Debug, Clone, Hash)]
+#[non_exhaustive]
+pub struct Watch {
+    /// Script expression to watch.
+    pub expr: ImmutableString,
+    /// Result of the expression when the debugger last paused, or the error message if it failed
+    /// to evaluate.
+    ///
+    /// [`None`] if the expression has not been evaluated yet.
+    pub value: Option<Result<Dynamic, ImmutableString>>,
+}
+
+impl fmt::Display for Watch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            Some(Ok(ref value)) => write!(f, "{} = {value:?}", self.expr),
+            Some(Err(ref err)) => write!(f, "{} = <error: {err}>", self.expr),
+            None => write!(f, "{} = <not evaluated>", self.expr),
         }
     }
 }
@@ -246,6 +346,25 @@ impl fmt::Display for CallStackFrame {
     }
 }
 
+/// Current value of a variable referenced on the line of a paused statement, for displaying
+/// inline values in debugger UIs.
+#[derive(Debug, Clone, Hash)]
+#[non_exhaustive]
+pub struct InlineValue {
+    /// Name of the variable (or `this`).
+    pub name: ImmutableString,
+    /// [Position][`Position`] of the variable reference.
+    pub pos: Position,
+    /// Current value of the variable.
+    pub value: Dynamic,
+}
+
+impl fmt::Display for InlineValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {:?}", self.name, self.value)
+    }
+}
+
 /// A type providing debugging facilities.
 #[derive(Debug, Clone, Hash)]
 pub struct Debugger {
@@ -255,6 +374,8 @@ pub struct Debugger {
     break_points: Vec<BreakPoint>,
     /// The current function call stack.
     call_stack: Vec<CallStackFrame>,
+    /// The current set of watch expressions.
+    watches: Vec<Watch>,
     /// The current state.
     state: Dynamic,
 }
@@ -268,6 +389,7 @@ impl Debugger {
             status,
             break_points: Vec::new(),
             call_stack: Vec::new(),
+            watches: Vec::new(),
             state: Dynamic::UNIT,
         }
     }
@@ -318,53 +440,13 @@ impl Debugger {
         }
     }
     /// Returns the first break-point triggered by a particular [`AST` Node][ASTNode].
+    ///
+    /// Conditions of [conditional break-points][BreakPoint::Conditional] are not evaluated.
     #[must_use]
     pub fn is_break_point(&self, src: Option<&str>, node: ASTNode) -> Option<usize> {
-        let _src = src;
-
         self.break_points()
             .iter()
-            .enumerate()
-            .filter(|(.., bp)| bp.is_enabled())
-            .find(|(.., bp)| match bp {
-                #[cfg(not(feature = "no_position"))]
-                BreakPoint::AtPosition { pos, .. } if pos.is_none() => false,
-                #[cfg(not(feature = "no_position"))]
-                BreakPoint::AtPosition { source, pos, .. } if pos.is_beginning_of_line() => {
-                    node.position().line().unwrap_or(0) == pos.line().unwrap()
-                        && _src == source.as_deref()
-                }
-                #[cfg(not(feature = "no_position"))]
-                BreakPoint::AtPosition { source, pos, .. } => {
-                    node.position() == *pos && _src == source.as_deref()
-                }
-                BreakPoint::AtFunctionName { name, .. } => match node {
-                    ASTNode::Expr(Expr::FnCall(x, ..)) | ASTNode::Stmt(Stmt::FnCall(x, ..)) => {
-                        x.name == *name
-                    }
-                    ASTNode::Stmt(Stmt::Expr(e)) => match &**e {
-                        Expr::FnCall(x, ..) => x.name == *name,
-                        _ => false,
-                    },
-                    _ => false,
-                },
-                BreakPoint::AtFunctionCall { name, args, .. } => match node {
-                    ASTNode::Expr(Expr::FnCall(x, ..)) | ASTNode::Stmt(Stmt::FnCall(x, ..)) => {
-                        x.args.len() == *args && x.name == *name
-                    }
-                    ASTNode::Stmt(Stmt::Expr(e)) => match &**e {
-                        Expr::FnCall(x, ..) => x.args.len() == *args && x.name == *name,
-                        _ => false,
-                    },
-                    _ => false,
-                },
-                #[cfg(not(feature = "no_object"))]
-                BreakPoint::AtProperty { name, .. } => match node {
-                    ASTNode::Expr(Expr::Property(x, ..)) => x.2 == *name,
-                    _ => false,
-                },
-            })
-            .map(|(i, ..)| i)
+            .position(|bp| bp.is_enabled() && bp.matches(src, node))
     }
     /// Get a slice of all [`BreakPoint`]'s.
     #[inline(always)]
@@ -378,6 +460,36 @@ impl Debugger {
     pub fn break_points_mut(&mut self) -> &mut Vec<BreakPoint> {
         &mut self.break_points
     }
+    /// Add a watch expression, which is evaluated in the current scope each time the debugger
+    /// pauses, right before the debugger callback is called.
+    ///
+    /// Returns the index of the new watch expression.
+    #[inline]
+    pub fn add_watch(&mut self, expr: impl Into<ImmutableString>) -> usize {
+        self.watches.push(Watch {
+            expr: expr.into(),
+            value: None,
+        });
+        self.watches.len() - 1
+    }
+    /// Remove a watch expression by index.
+    ///
+    /// Returns [`None`] if the index is out of bounds.
+    #[inline]
+    pub fn remove_watch(&mut self, index: usize) -> Option<Watch> {
+        (index < self.watches.len()).then(|| self.watches.remove(index))
+    }
+    /// Remove all watch expressions.
+    #[inline(always)]
+    pub fn clear_watches(&mut self) {
+        self.watches.clear();
+    }
+    /// Get a slice of all watch expressions, with their results when the debugger last paused.
+    #[inline(always)]
+    #[must_use]
+    pub fn watches(&self) -> &[Watch] {
+        &self.watches
+    }
     /// Get the custom state.
     #[inline(always)]
     pub const fn state(&self) -> &Dynamic {
@@ -395,6 +507,50 @@ impl Debugger {
     }
 }
 
+impl EvalContext<'_, '_, '_, '_, '_, '_> {
+    /// Get the current values of all variables referenced on the same line as an [`ASTNode`]
+    /// within it (typically the node passed to the debugger callback), ordered by position.
+    ///
+    /// Values are looked up in the current [`Scope`] (plus the `this` pointer, if any).
+    /// References to variables not (yet) in scope, as well as qualified references to module
+    /// variables, are skipped.
+    #[must_use]
+    pub fn inline_values(&self, node: ASTNode) -> Vec<InlineValue> {
+        let line = node.position().line();
+        let mut values = Vec::new();
+
+        let mut on_node = |path: &[ASTNode]| {
+            let (name, pos, value) = match path.last() {
+                #[cfg(not(feature = "no_module"))]
+                Some(ASTNode::Expr(Expr::Variable(x, ..))) if !x.2.is_empty() => return true,
+                Some(ASTNode::Expr(Expr::Variable(x, _, pos))) => {
+                    (x.1.clone(), *pos, self.scope().get(&x.1))
+                }
+                Some(ASTNode::Expr(Expr::ThisPtr(pos))) => ("this".into(), *pos, self.this_ptr()),
+                _ => return true,
+            };
+
+            if let Some(value) = value.filter(|_| pos.line() == line) {
+                values.push(InlineValue {
+                    name,
+                    pos,
+                    value: value.flatten_clone(),
+                });
+            }
+
+            true
+        };
+
+        match node {
+            ASTNode::Stmt(stmt) => stmt.walk(&mut Vec::new(), &mut on_node),
+            ASTNode::Expr(expr) => expr.walk(&mut Vec::new(), &mut on_node),
+        };
+
+        values.sort_by_key(|v| v.pos);
+        values
+    }
+}
+
 impl Engine {
     /// Run the debugger callback if there is a debugging interface registered.
     #[inline(always)]
@@ -447,7 +603,7 @@ impl Engine {
         global: &mut GlobalRuntimeState,
         caches: &mut Caches,
         scope: &mut Scope,
-        this_ptr: Option<&mut Dynamic>,
+        mut this_ptr: Option<&mut Dynamic>,
         node: impl Into<ASTNode<'a>>,
     ) -> RhaiResultOf<Option<DebuggerStatus>> {
         let node = node.into();
@@ -471,7 +627,13 @@ impl Engine {
 
                 let event = match event {
                     Some(e) => e,
-                    None => match dbg.is_break_point(global.source(), node) {
+                    None => match self.dbg_find_break_point(
+                        global,
+                        caches,
+                        scope,
+                        this_ptr.as_deref_mut(),
+                        node,
+                    ) {
                         Some(bp) => DebuggerEvent::BreakPoint(bp),
                         None => return Ok(None),
                     },
@@ -494,12 +656,14 @@ impl Engine {
         global: &mut GlobalRuntimeState,
         caches: &mut Caches,
         scope: &mut Scope,
-        this_ptr: Option<&mut Dynamic>,
+        mut this_ptr: Option<&mut Dynamic>,
         node: ASTNode,
         event: DebuggerEvent,
     ) -> Result<Option<DebuggerStatus>, Box<crate::EvalAltResult>> {
         match self.debugger_interface {
             Some(ref x) => {
+                self.dbg_update_watches(global, caches, scope, this_ptr.as_deref_mut());
+
                 let orig_scope_len = scope.len();
 
                 let src = global.source_raw().cloned();
@@ -544,9 +708,93 @@ impl Engine {
                         global.debugger_mut().status = DebuggerStatus::FunctionExit(level);
                         Ok(None)
                     }
+                    DebuggerCommand::StepOut => {
+                        global.debugger_mut().status = DebuggerStatus::StepOut(global.level);
+                        Ok(None)
+                    }
                 }
             }
             None => Ok(None),
         }
     }
+    /// Find the first enabled break-point triggered by a particular [`AST` Node][ASTNode],
+    /// evaluating the conditions of [conditional break-points][BreakPoint::Conditional].
+    fn dbg_find_break_point(
+        &self,
+        global: &mut GlobalRuntimeState,
+        caches: &mut Caches,
+        scope: &mut Scope,
+        mut this_ptr: Option<&mut Dynamic>,
+        node: ASTNode,
+    ) -> Option<usize> {
+        let candidates = global
+            .debugger()
+            .break_points()
+            .iter()
+            .enumerate()
+            .filter(|(.., bp)| bp.is_enabled() && bp.matches(global.source(), node))
+            .map(|(i, bp)| (i, bp.condition().map(ImmutableString::from)))
+            .collect::<Vec<_>>();
+
+        candidates.into_iter().find_map(|(i, condition)| {
+            let triggered = condition.map_or(true, |condition| {
+                self.dbg_eval_expr(global, caches, scope, this_ptr.as_deref_mut(), &condition)
+                    .map_or(true, |v| v.as_bool().unwrap_or(false))
+            });
+            triggered.then_some(i)
+        })
+    }
+    /// Evaluate all watch expressions of the debugger and store their results.
+    fn dbg_update_watches(
+        &self,
+        global: &mut GlobalRuntimeState,
+        caches: &mut Caches,
+        scope: &mut Scope,
+        mut this_ptr: Option<&mut Dynamic>,
+    ) {
+        let exprs = match global.debugger {
+            Some(ref dbg) if !dbg.watches.is_empty() => dbg
+                .watches
+                .iter()
+                .map(|w| w.expr.clone())
+                .collect::<Vec<_>>(),
+            _ => return,
+        };
+
+        let values = exprs
+            .iter()
+            .map(|expr| {
+                self.dbg_eval_expr(global, caches, scope, this_ptr.as_deref_mut(), expr)
+                    .map_err(|err| ImmutableString::from(err.to_string()))
+            })
+            .collect::<Vec<_>>();
+
+        for (watch, value) in global.debugger_mut().watches.iter_mut().zip(values) {
+            watch.value = Some(value);
+        }
+    }
+    /// Evaluate a script expression (e.g. a watch expression or the condition of a break-point)
+    /// in the current scope, with the debugger suspended.
+    ///
+    /// Operations performed are not counted.
+    pub(crate) fn dbg_eval_expr(
+        &self,
+        global: &mut GlobalRuntimeState,
+        caches: &mut Caches,
+        scope: &mut Scope,
+        this_ptr: Option<&mut Dynamic>,
+        expr: &str,
+    ) -> RhaiResultOf<Dynamic> {
+        let ast = self.compile_expression(expr)?;
+
+        let debugger = global.debugger.take();
+        let num_operations = global.num_operations;
+
+        let result = self.eval_stmt_block(global, caches, scope, this_ptr, ast.statements(), true);
+
+        global.num_operations = num_operations;
+        global.debugger = debugger;
+
+        result
+    }
 }
@@ -81,6 +81,20 @@ impl<'a, 's, 'ps, 'g, 'c, 't> EvalContext<'a, 's, 'ps, 'g, 'c, 't> {
     pub fn tag_mut(&mut self) -> &mut Dynamic {
         &mut self.global.tag
     }
+    /// Get the per-run user data of a particular type passed via
+    /// [`Engine::run_with_context`][crate::Engine::run_with_context], if any.
+    #[inline(always)]
+    #[must_use]
+    pub fn user_data<T: 'static>(&self) -> Option<&T> {
+        self.global.user_data::<T>()
+    }
+    /// Get a mutable reference to the per-run user data of a particular type passed via
+    /// [`Engine::run_with_context`][crate::Engine::run_with_context], if any.
+    #[inline(always)]
+    #[must_use]
+    pub fn user_data_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.global.user_data_mut::<T>()
+    }
     /// _(internals)_ The current [`GlobalRuntimeState`].
     /// Exported under the `internals` feature only.
     #[cfg(feature = "internals")]
@@ -135,6 +149,17 @@ impl<'a, 's, 'ps, 'g, 'c, 't> EvalContext<'a, 's, 'ps, 'g, 'c, 't> {
         self.global.level
     }
 
+    /// Evaluate a script expression within this [evaluation context][`EvalContext`], with the
+    /// debugger suspended.
+    ///
+    /// Operations performed are not counted.
+    #[cfg(feature = "debugging")]
+    #[inline(always)]
+    pub(crate) fn dbg_eval_expr(&mut self, expr: &str) -> crate::RhaiResult {
+        let this_ptr = self.this_ptr.as_deref_mut();
+        self.engine
+            .dbg_eval_expr(self.global, self.caches, self.scope, this_ptr, expr)
+    }
     /// Evaluate an [expression tree][crate::Expression] within this [evaluation context][`EvalContext`].
     ///
     /// # WARNING - Low Level API
@@ -122,10 +122,15 @@ impl Engine {
             match scope.search(var_name) {
                 Some(index) => index,
                 None => {
+                    // Symbols imported via `import ... use { ... }`
+                    #[cfg(not(feature = "no_module"))]
+                    if let Some(val) = global.get_imported_var(var_name) {
+                        return Ok(val.into());
+                    }
+
                     return self
-                        .global_modules
-                        .iter()
-                        .find_map(|m| m.get_var(var_name))
+                        .get_prelude_var(var_name)
+                        .or_else(|| self.global_modules.iter().find_map(|m| m.get_var(var_name)))
                         .map_or_else(
                             || {
                                 Err(ERR::ErrorVariableNotFound(
@@ -135,7 +140,7 @@ impl Engine {
                                 .into())
                             },
                             |val| Ok(val.into()),
-                        )
+                        );
                 }
             }
         };
@@ -226,6 +231,9 @@ impl Engine {
     ) -> RhaiResult {
         self.track_operation(global, expr.position())?;
 
+        #[cfg(not(feature = "no_position"))]
+        self.track_coverage(global, expr.position());
+
         #[cfg(feature = "debugging")]
         let reset = self.dbg_reset(global, caches, scope, this_ptr.as_deref_mut(), expr)?;
         #[cfg(feature = "debugging")]
@@ -256,6 +264,7 @@ impl Engine {
 
             Expr::InterpolatedString(x, _) => {
                 let mut concat = SmartString::new_const();
+                let mut tainted = false;
 
                 for expr in &**x {
                     let item = &mut self
@@ -263,6 +272,8 @@ impl Engine {
                         .flatten();
                     let pos = expr.position();
 
+                    tainted |= self.taint_tracking() && item.is_tainted();
+
                     if item.is_string() {
                         write!(concat, "{item}").unwrap();
                     } else {
@@ -277,7 +288,13 @@ impl Engine {
                         .map_err(|err| err.fill_position(pos))?;
                 }
 
-                Ok(self.get_interned_string(concat).into())
+                let mut result: Dynamic = self.get_interned_string(concat).into();
+
+                if tainted {
+                    result.set_tainted(true);
+                }
+
+                Ok(result)
             }
 
             #[cfg(not(feature = "no_index"))]
@@ -386,10 +403,32 @@ impl Engine {
                         *pos,
                     ))
                 })?;
+                let orig_scope_len = scope.len();
                 let mut context = EvalContext::new(self, global, caches, scope, this_ptr);
 
-                (custom_def.func)(&mut context, &expressions, &custom.state)
-                    .and_then(|r| self.check_data_size(r, expr.start_position()))
+                let result = (custom_def.func)(&mut context, &expressions, &custom.state);
+
+                // Declare new variables in the order the parser expects
+                if !custom.declared_vars.is_empty() {
+                    let values: crate::StaticVec<_> = custom
+                        .declared_vars
+                        .iter()
+                        .map(|name| match scope.search(name) {
+                            Some(index) if index >= orig_scope_len => {
+                                std::mem::take(scope.get_mut_by_index(index))
+                            }
+                            _ => Dynamic::UNIT,
+                        })
+                        .collect();
+
+                    scope.rewind(orig_scope_len);
+
+                    for (name, value) in custom.declared_vars.iter().zip(values) {
+                        scope.push_dynamic(name.as_str(), value);
+                    }
+                }
+
+                result.and_then(|r| self.check_data_size(r, expr.start_position()))
             }
 
             Expr::Stmt(x) => {
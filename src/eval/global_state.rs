@@ -39,9 +39,51 @@ pub struct GlobalRuntimeState {
     pub source: Option<ImmutableString>,
     /// Number of operations performed.
     pub num_operations: u64,
+    /// Point in time after which evaluation is terminated.
+    ///
+    /// Initialized from [`Engine::deadline`][crate::Engine::deadline].
+    ///
+    /// Not available under `unchecked` or `no_time`.
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_time"))]
+    pub deadline: Option<crate::Instant>,
+    /// Point in time when evaluation started, if [progress reporting][crate::Engine::on_progress]
+    /// is turned on.
+    ///
+    /// Not available under `unchecked` or `no_time`.
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_time"))]
+    pub start_time: Option<crate::Instant>,
+    /// Number of operations after which evaluation is terminated, set while running a function
+    /// of a [module][crate::Module] with [resource limits][crate::ModuleLimits].
+    ///
+    /// Not available under `unchecked`.
+    #[cfg(not(feature = "unchecked"))]
+    pub operations_limit: Option<u64>,
+    /// Has the [operations warning][crate::Engine::on_operations_warning] been raised during
+    /// this evaluation?
+    ///
+    /// Not available under `unchecked`.
+    #[cfg(not(feature = "unchecked"))]
+    pub operations_warned: bool,
+    /// Number of operations performed on copies of this state (e.g. by script callbacks of native
+    /// functions), yet to be added to [`num_operations`][GlobalRuntimeState::num_operations].
+    ///
+    /// The counter is shared among copies of this state.
+    ///
+    /// Not available under `unchecked`.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) pending_operations: crate::Shared<crate::Locked<u64>>,
     /// Number of modules loaded.
     #[cfg(not(feature = "no_module"))]
     pub num_modules_loaded: usize,
+    /// Number of values made shared (e.g. by being captured in closures).
+    #[cfg(not(feature = "no_closure"))]
+    pub num_shared_values: usize,
+    /// Paths of [modules][crate::Module] currently being loaded, with the positions of their
+    /// `import` statements.
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) import_chain: crate::StaticVec<(ImmutableString, crate::Position)>,
     /// The current nesting level of function calls.
     pub level: usize,
     /// Level of the current scope.
@@ -70,6 +112,44 @@ pub struct GlobalRuntimeState {
     pub constants: Option<SharedGlobalConstants>,
     /// Custom state that can be used by the external host.
     pub tag: Dynamic,
+    /// Typed per-run user data passed via [`Engine::run_with_context`][crate::Engine::run_with_context].
+    ///
+    /// The user data is shared among copies of this state.
+    pub user_data: Option<crate::Shared<crate::func::native::UserData>>,
+    /// Host state injected into native functions, taken from the [`Scope`][crate::Scope] of the
+    /// evaluation run.
+    pub(crate) host_state:
+        Option<crate::Shared<crate::Locked<Box<crate::func::native::HostState>>>>,
+    /// Output captured for [`Engine::eval_full`][crate::Engine::eval_full], if any.
+    ///
+    /// Shared among all copies of this state, so that output from callbacks is also captured.
+    pub(crate) output: Option<crate::Shared<crate::Locked<crate::api::eval::CapturedOutput>>>,
+    /// [Handles][crate::Handle] owned by this evaluation run.
+    ///
+    /// All handles still open are closed when the last copy of this state is dropped.
+    pub(crate) handles: crate::types::HandleRegistry,
+    /// Undo actions enlisted by native functions during this evaluation run.
+    pub undo_log: crate::types::UndoLog,
+    /// Script coverage data collected during evaluation, if any.
+    ///
+    /// Initialized from the [`Engine`] when [coverage collection][Engine::set_collect_coverage]
+    /// is turned on. The data is shared among copies of this state.
+    ///
+    /// Not available under `no_position`.
+    #[cfg(not(feature = "no_position"))]
+    pub coverage: Option<crate::Shared<crate::Locked<crate::Coverage>>>,
+    /// Operations profile collected during evaluation, if any.
+    ///
+    /// Initialized from the [`Engine`] when [operations profiling][Engine::set_profile_operations]
+    /// is turned on. The data is shared among copies of this state.
+    ///
+    /// Not available under `unchecked`.
+    #[cfg(not(feature = "unchecked"))]
+    pub op_profile: Option<crate::Shared<crate::Locked<crate::OperationsProfile>>>,
+    /// Name of the script-defined function that operations are currently attributed to, if
+    /// operations profiling is turned on.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) op_profile_fn: Option<ImmutableString>,
     /// Debugging interface.
     #[cfg(feature = "debugging")]
     pub(crate) debugger: Option<Box<super::Debugger>>,
@@ -91,8 +171,24 @@ impl Engine {
             lib: crate::ThinVec::new(),
             source: None,
             num_operations: 0,
+            #[cfg(not(feature = "unchecked"))]
+            #[cfg(not(feature = "no_time"))]
+            deadline: self.deadline(),
+            #[cfg(not(feature = "unchecked"))]
+            #[cfg(not(feature = "no_time"))]
+            start_time: self.progress.as_ref().map(|_| crate::Instant::now()),
+            #[cfg(not(feature = "unchecked"))]
+            operations_limit: None,
+            #[cfg(not(feature = "unchecked"))]
+            operations_warned: false,
+            #[cfg(not(feature = "unchecked"))]
+            pending_operations: crate::Shared::new(crate::Locked::new(0)),
             #[cfg(not(feature = "no_module"))]
             num_modules_loaded: 0,
+            #[cfg(not(feature = "no_closure"))]
+            num_shared_values: 0,
+            #[cfg(not(feature = "no_module"))]
+            import_chain: crate::StaticVec::new_const(),
             scope_level: 0,
             level: 0,
             always_search_scope: false,
@@ -103,6 +199,17 @@ impl Engine {
             constants: None,
 
             tag: self.default_tag().clone(),
+            user_data: None,
+            host_state: None,
+            output: None,
+            handles: crate::types::HandleRegistry::new(),
+            undo_log: crate::types::UndoLog::new(),
+            #[cfg(not(feature = "no_position"))]
+            coverage: self.coverage.clone(),
+            #[cfg(not(feature = "unchecked"))]
+            op_profile: self.op_profile.clone(),
+            #[cfg(not(feature = "unchecked"))]
+            op_profile_fn: None,
 
             #[cfg(feature = "debugging")]
             debugger: self.debugger_interface.as_ref().map(|x| {
@@ -114,6 +221,89 @@ impl Engine {
 }
 
 impl GlobalRuntimeState {
+    /// Get the per-run user data of a particular type, if any.
+    #[inline]
+    #[must_use]
+    pub fn user_data<T: 'static>(&self) -> Option<&T> {
+        self.user_data.as_ref().and_then(|d| d.downcast_ref::<T>())
+    }
+    /// Get a mutable reference to the per-run user data of a particular type, if any.
+    ///
+    /// Returns [`None`] if the user data is currently shared with copies of this state.
+    #[inline]
+    #[must_use]
+    pub fn user_data_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.user_data
+            .as_mut()
+            .and_then(crate::Shared::get_mut)
+            .and_then(|d| d.downcast_mut::<T>())
+    }
+    /// Apply the [resource limits][crate::ModuleLimits] of a [module][crate::Module], on top of
+    /// any limits currently in effect.
+    ///
+    /// Returns the original limits, to be restored via
+    /// [`restore_limits`][GlobalRuntimeState::restore_limits].
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_module"))]
+    #[must_use]
+    pub(crate) fn apply_module_limits(&mut self, limits: &crate::ModuleLimits) -> LimitsBackup {
+        let backup = LimitsBackup {
+            operations_limit: self.operations_limit,
+            #[cfg(not(feature = "no_time"))]
+            deadline: self.deadline,
+        };
+
+        if limits.max_operations() > 0 {
+            let limit = self.num_operations.saturating_add(limits.max_operations());
+            self.operations_limit = Some(self.operations_limit.map_or(limit, |l| l.min(limit)));
+        }
+
+        #[cfg(not(feature = "no_time"))]
+        if let Some(duration) = limits.max_duration() {
+            let deadline = crate::Instant::now() + duration;
+            self.deadline = Some(self.deadline.map_or(deadline, |d| d.min(deadline)));
+        }
+
+        backup
+    }
+    /// Restore the limits saved by [`apply_module_limits`][GlobalRuntimeState::apply_module_limits].
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_module"))]
+    #[inline(always)]
+    pub(crate) fn restore_limits(&mut self, backup: LimitsBackup) {
+        self.operations_limit = backup.operations_limit;
+        #[cfg(not(feature = "no_time"))]
+        {
+            self.deadline = backup.deadline;
+        }
+    }
+    /// Record operations performed on a copy of this state, to be added to the original state
+    /// via [`absorb_pending_operations`][GlobalRuntimeState::absorb_pending_operations].
+    ///
+    /// Not available under `unchecked`.
+    #[cfg(not(feature = "unchecked"))]
+    #[inline]
+    pub(crate) fn add_pending_operations(&self, num: u64) {
+        if num > 0 {
+            if let Some(mut pending) = crate::func::locked_write(&self.pending_operations) {
+                *pending += num;
+            }
+        }
+    }
+    /// Add all operations performed on copies of this state to
+    /// [`num_operations`][GlobalRuntimeState::num_operations].
+    ///
+    /// Returns `true` if there are any.
+    ///
+    /// Not available under `unchecked`.
+    #[cfg(not(feature = "unchecked"))]
+    #[inline]
+    pub(crate) fn absorb_pending_operations(&mut self) -> bool {
+        let num = crate::func::locked_write(&self.pending_operations)
+            .map_or(0, |mut pending| std::mem::take(&mut *pending));
+        self.num_operations += num;
+        num > 0
+    }
     /// Get the length of the stack of globally-imported [modules][crate::Module].
     ///
     /// Not available under `no_module`.
@@ -141,6 +331,20 @@ impl GlobalRuntimeState {
     pub fn find_import(&self, name: &str) -> Option<usize> {
         self.imports.iter().rposition(|key| key == name)
     }
+    /// Get the value of a variable imported via `import ... use { ... }`, searching in reverse.
+    ///
+    /// Not available under `no_module`.
+    #[cfg(not(feature = "no_module"))]
+    #[inline]
+    #[must_use]
+    pub(crate) fn get_imported_var(&self, name: &str) -> Option<Dynamic> {
+        self.imports
+            .iter()
+            .zip(self.modules.iter())
+            .rev()
+            .filter(|(key, ..)| key.as_str() == crate::engine::IMPORT_USE_SYMBOLS)
+            .find_map(|(.., m)| m.get_var(name))
+    }
     /// Push an imported [module][crate::Module] onto the stack.
     ///
     /// Not available under `no_module`.
@@ -280,6 +484,12 @@ impl GlobalRuntimeState {
     pub(crate) const fn source_raw(&self) -> Option<&ImmutableString> {
         self.source.as_ref()
     }
+    /// Get the registry of [handles][crate::Handle] owned by this evaluation run.
+    #[inline(always)]
+    #[must_use]
+    pub const fn handles(&self) -> &crate::types::HandleRegistry {
+        &self.handles
+    }
 
     /// Return a reference to the debugging interface.
     ///
@@ -314,6 +524,16 @@ impl<K: Into<ImmutableString>, M: Into<crate::SharedModule>> Extend<(K, M)> for
     }
 }
 
+/// Limits in effect before applying the resource limits of a [module][crate::Module].
+#[cfg(not(feature = "unchecked"))]
+#[cfg(not(feature = "no_module"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LimitsBackup {
+    operations_limit: Option<u64>,
+    #[cfg(not(feature = "no_time"))]
+    deadline: Option<crate::Instant>,
+}
+
 impl fmt::Debug for GlobalRuntimeState {
     #[cold]
     #[inline(never)]
@@ -323,22 +543,44 @@ impl fmt::Debug for GlobalRuntimeState {
         #[cfg(not(feature = "no_module"))]
         f.field("imports", &self.scan_imports_raw().collect::<Vec<_>>())
             .field("num_modules_loaded", &self.num_modules_loaded)
+            .field("import_chain", &self.import_chain)
             .field("embedded_module_resolver", &self.embedded_module_resolver);
 
         #[cfg(not(feature = "no_function"))]
         f.field("lib", &self.lib);
 
+        #[cfg(not(feature = "no_closure"))]
+        f.field("num_shared_values", &self.num_shared_values);
+
         f.field("source", &self.source)
             .field("num_operations", &self.num_operations)
             .field("level", &self.level)
             .field("scope_level", &self.scope_level)
             .field("always_search_scope", &self.always_search_scope);
 
+        #[cfg(not(feature = "unchecked"))]
+        #[cfg(not(feature = "no_time"))]
+        f.field("deadline", &self.deadline)
+            .field("start_time", &self.start_time);
+
+        #[cfg(not(feature = "unchecked"))]
+        f.field("operations_limit", &self.operations_limit)
+            .field("operations_warned", &self.operations_warned);
+
         #[cfg(not(feature = "no_module"))]
         #[cfg(not(feature = "no_function"))]
         f.field("constants", &self.constants);
 
-        f.field("tag", &self.tag);
+        f.field("tag", &self.tag)
+            .field("handles", &self.handles)
+            .field("undo_log", &self.undo_log)
+            .field("host_state", &self.host_state.is_some());
+
+        #[cfg(not(feature = "no_position"))]
+        f.field("coverage", &self.coverage.is_some());
+
+        #[cfg(not(feature = "unchecked"))]
+        f.field("op_profile", &self.op_profile.is_some());
 
         #[cfg(feature = "debugging")]
         f.field("debugger", &self.debugger);
@@ -22,8 +22,8 @@ pub use data_check::calc_data_sizes;
 pub use debugger::CallStackFrame;
 #[cfg(feature = "debugging")]
 pub use debugger::{
-    BreakPoint, Debugger, DebuggerCommand, DebuggerEvent, DebuggerStatus, OnDebuggerCallback,
-    OnDebuggingInit,
+    BreakPoint, Debugger, DebuggerCommand, DebuggerEvent, DebuggerStatus, InlineValue,
+    OnDebuggerCallback, OnDebuggingInit, Watch,
 };
 pub use eval_context::EvalContext;
 
@@ -2,26 +2,162 @@
 
 use super::{Caches, EvalContext, GlobalRuntimeState, Target};
 use crate::ast::{
-    ASTFlags, BinaryExpr, Expr, FlowControl, OpAssignment, Stmt, SwitchCasesCollection,
+    ASTFlags, BinaryExpr, Expr, FlowControl, FnCallHashes, OpAssignment, Pattern, Stmt,
+    SwitchCasesCollection,
 };
 use crate::func::{get_builtin_op_assignment_fn, get_hasher};
 use crate::tokenizer::Token;
 use crate::types::dynamic::{AccessMode, Union};
-use crate::{Dynamic, Engine, RhaiResult, RhaiResultOf, Scope, VarDefInfo, ERR, INT};
+use crate::{
+    Dynamic, Engine, Position, RhaiResult, RhaiResultOf, Scope, TraceEvent, VarDefInfo, ERR, INT,
+};
 use std::hash::{Hash, Hasher};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 
 impl Engine {
-    /// If the value is a string, intern it.
+    /// If the value is a string, intern it, keeping its tag (including the taint bit).
     #[inline(always)]
     fn intern_string(&self, value: Dynamic) -> Dynamic {
         match value.0 {
-            Union::Str(s, ..) => self.get_interned_string(s).into(),
+            Union::Str(s, tag, access) => {
+                Dynamic(Union::Str(self.get_interned_string(s), tag, access))
+            }
             _ => value,
         }
     }
 
+    /// Check a variable definition against the shadowing option and the variable definition filter.
+    fn check_var_def(
+        &self,
+        global: &mut GlobalRuntimeState,
+        caches: &mut Caches,
+        scope: &mut Scope,
+        this_ptr: Option<&mut Dynamic>,
+        var_name: &str,
+        access: AccessMode,
+        pos: Position,
+    ) -> RhaiResultOf<()> {
+        if !self.allow_shadowing() && scope.contains(var_name) {
+            return Err(ERR::ErrorVariableExists(var_name.to_string(), pos).into());
+        }
+
+        if let Some(ref filter) = self.def_var_filter {
+            let will_shadow = scope.contains(var_name);
+            let is_const = access == AccessMode::ReadOnly;
+            let info = VarDefInfo::new(var_name, is_const, global.scope_level, will_shadow);
+            let orig_scope_len = scope.len();
+            let context = EvalContext::new(self, global, caches, scope, this_ptr);
+            let filter_result = filter(true, info, context);
+
+            if orig_scope_len != scope.len() {
+                // The scope is changed, always search from now on
+                global.always_search_scope = true;
+            }
+
+            if !filter_result? {
+                return Err(ERR::ErrorForbiddenVariable(var_name.to_string(), pos).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Match a value against a destructuring [`Pattern`], pushing all the variables it binds into
+    /// the [`Scope`] in order.
+    ///
+    /// Returns `false` if the value does not match the pattern, in which case some of the
+    /// variables may already have been pushed.
+    fn bind_pattern(
+        &self,
+        scope: &mut Scope,
+        pattern: &Pattern,
+        value: Dynamic,
+        access: AccessMode,
+    ) -> RhaiResultOf<bool> {
+        let value = value.flatten();
+
+        match pattern {
+            Pattern::Ignore(..) => Ok(true),
+            Pattern::Var(x) => {
+                // Guard against too many variables
+                #[cfg(not(feature = "unchecked"))]
+                if scope.len() >= self.max_variables() {
+                    return Err(ERR::ErrorTooManyVariables(x.pos).into());
+                }
+
+                scope.push_entry(x.name.clone(), access, self.intern_string(value));
+                Ok(true)
+            }
+            Pattern::Literal(literal, ..) => {
+                if !value.is_hashable() {
+                    return Ok(false);
+                }
+
+                let hasher1 = &mut get_hasher();
+                value.hash(hasher1);
+                let hasher2 = &mut get_hasher();
+                literal.hash(hasher2);
+
+                Ok(hasher1.finish() == hasher2.finish())
+            }
+            #[cfg(not(feature = "no_index"))]
+            Pattern::Array(x, ..) => {
+                let (items, rest) = &**x;
+
+                let mut array = match value.try_cast::<crate::Array>() {
+                    Some(array) => array,
+                    None => return Ok(false),
+                };
+
+                if array.len() < items.len() || (rest.is_none() && array.len() > items.len()) {
+                    return Ok(false);
+                }
+
+                let rest_items = array.split_off(items.len());
+
+                for (item, value) in items.iter().zip(array) {
+                    if !self.bind_pattern(scope, item, value, access)? {
+                        return Ok(false);
+                    }
+                }
+
+                match rest {
+                    Some(rest) => self.bind_pattern(scope, rest, rest_items.into(), access),
+                    None => Ok(true),
+                }
+            }
+            #[cfg(not(feature = "no_object"))]
+            Pattern::Map(x, ..) => {
+                let (items, rest) = &**x;
+
+                let mut map = match value.try_cast::<crate::Map>() {
+                    Some(map) => map,
+                    None => return Ok(false),
+                };
+
+                let len = crate::types::prototype::num_properties(&map);
+
+                if len < items.len() || (!rest && len > items.len()) {
+                    return Ok(false);
+                }
+
+                for (key, item) in items {
+                    let matched = match map.remove(key.as_str()) {
+                        Some(value) => self.bind_pattern(scope, item, value, access)?,
+                        None => false,
+                    };
+
+                    if !matched {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+        }
+    }
+
     /// Evaluate a statements block.
     pub(crate) fn eval_stmt_block(
         &self,
@@ -128,7 +264,7 @@ impl Engine {
             let mut done = false;
 
             // Short-circuit built-in op-assignments if under Fast Operators mode
-            if self.fast_operators() {
+            if self.fast_operators() && !self.taint_tracking() {
                 #[allow(clippy::wildcard_imports)]
                 use Token::*;
 
@@ -145,25 +281,33 @@ impl Engine {
                     },
                     (Union::Int(n1, ..), Union::Int(n2, ..)) => {
                         #[cfg(not(feature = "unchecked"))]
-                        #[allow(clippy::wildcard_imports)]
-                        use crate::packages::arithmetic::arith_basic::INT::functions::*;
+                        use crate::packages::arithmetic::arith_basic::INT::{
+                            add_with, divide_with, modulo_with, multiply_with, subtract_with,
+                        };
+                        #[cfg(not(feature = "unchecked"))]
+                        let mode = self.arithmetic_mode();
 
                         #[cfg(not(feature = "unchecked"))]
                         match op_x {
                             PlusAssign => {
-                                *n1 = add(*n1, *n2).map_err(|err| err.fill_position(pos))?
+                                *n1 = add_with(mode, *n1, *n2)
+                                    .map_err(|err| err.fill_position(pos))?
                             }
                             MinusAssign => {
-                                *n1 = subtract(*n1, *n2).map_err(|err| err.fill_position(pos))?
+                                *n1 = subtract_with(mode, *n1, *n2)
+                                    .map_err(|err| err.fill_position(pos))?
                             }
                             MultiplyAssign => {
-                                *n1 = multiply(*n1, *n2).map_err(|err| err.fill_position(pos))?
+                                *n1 = multiply_with(mode, *n1, *n2)
+                                    .map_err(|err| err.fill_position(pos))?
                             }
                             DivideAssign => {
-                                *n1 = divide(*n1, *n2).map_err(|err| err.fill_position(pos))?
+                                *n1 = divide_with(mode, *n1, *n2)
+                                    .map_err(|err| err.fill_position(pos))?
                             }
                             ModuloAssign => {
-                                *n1 = modulo(*n1, *n2).map_err(|err| err.fill_position(pos))?
+                                *n1 = modulo_with(mode, *n1, *n2)
+                                    .map_err(|err| err.fill_position(pos))?
                             }
                             _ => done = false,
                         }
@@ -227,9 +371,11 @@ impl Engine {
                         // Expand to `var = var op rhs`
                         let op = Some(op);
 
+                        let hashes = FnCallHashes::from_native_only(hash);
+
                         *args[0] = self
-                            .exec_native_fn_call(
-                                global, caches, op_str, op, hash, args, true, false, pos,
+                            .exec_fn_call(
+                                global, caches, None, op_str, op, hashes, args, true, false, pos,
                             )?
                             .0;
                     }
@@ -265,6 +411,17 @@ impl Engine {
     ) -> RhaiResult {
         self.track_operation(global, stmt.position())?;
 
+        // Expression statements are recorded when the expression is evaluated
+        #[cfg(not(feature = "no_position"))]
+        if !matches!(stmt, Stmt::Expr(..)) {
+            self.track_coverage(global, stmt.position());
+        }
+
+        if !matches!(stmt, Stmt::Noop(..) | Stmt::Block(..)) {
+            self.track_trace(|| TraceEvent::Statement(stmt.position()));
+            self.track_sample_pos(stmt.position());
+        }
+
         #[cfg(feature = "debugging")]
         let reset = self.dbg_reset(global, caches, scope, this_ptr.as_deref_mut(), stmt)?;
         #[cfg(feature = "debugging")]
@@ -315,6 +472,15 @@ impl Engine {
                         let target = &mut this_ptr.unwrap().try_into()?;
 
                         self.eval_op_assignment(global, caches, op_info, lhs, target, rhs_val)?;
+
+                        self.track_trace(|| {
+                            let value = TraceEvent::digest(&target.as_ref().flatten_clone());
+                            TraceEvent::Assign(
+                                crate::engine::KEYWORD_THIS.into(),
+                                value,
+                                lhs.position(),
+                            )
+                        });
                     }
                     #[cfg(feature = "no_function")]
                     unreachable!();
@@ -344,6 +510,11 @@ impl Engine {
                     }
 
                     self.eval_op_assignment(global, caches, op_info, lhs, &mut target, rhs_val)?;
+
+                    self.track_trace(|| {
+                        let value = TraceEvent::digest(&target.as_ref().flatten_clone());
+                        TraceEvent::Assign(x.1.clone(), value, lhs.position())
+                    });
                 } else {
                     #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
                     {
@@ -383,10 +554,6 @@ impl Engine {
 
             // Variable definition
             Stmt::Var(x, options, pos) => {
-                if !self.allow_shadowing() && scope.contains(x.0.as_str()) {
-                    return Err(ERR::ErrorVariableExists(x.0.as_str().to_string(), *pos).into());
-                }
-
                 // Let/const statement
                 let (var_name, expr, index) = &**x;
 
@@ -397,34 +564,16 @@ impl Engine {
                 };
                 let export = options.intersects(ASTFlags::EXPORTED);
 
-                // Check variable definition filter
-                if let Some(ref filter) = self.def_var_filter {
-                    let will_shadow = scope.contains(var_name.as_str());
-                    let is_const = access == AccessMode::ReadOnly;
-                    let info = VarDefInfo::new(
-                        var_name.as_str(),
-                        is_const,
-                        global.scope_level,
-                        will_shadow,
-                    );
-                    let orig_scope_len = scope.len();
-                    let context =
-                        EvalContext::new(self, global, caches, scope, this_ptr.as_deref_mut());
-                    let filter_result = filter(true, info, context);
-
-                    if orig_scope_len != scope.len() {
-                        // The scope is changed, always search from now on
-                        global.always_search_scope = true;
-                    }
-
-                    if !filter_result? {
-                        return Err(ERR::ErrorForbiddenVariable(
-                            var_name.as_str().to_string(),
-                            *pos,
-                        )
-                        .into());
-                    }
-                }
+                // Check shadowing and variable definition filter
+                self.check_var_def(
+                    global,
+                    caches,
+                    scope,
+                    this_ptr.as_deref_mut(),
+                    var_name.as_str(),
+                    access,
+                    *pos,
+                )?;
 
                 // Guard against too many variables
                 #[cfg(not(feature = "unchecked"))]
@@ -438,6 +587,11 @@ impl Engine {
                     .flatten();
                 let mut value = self.intern_string(value);
 
+                self.track_trace(|| {
+                    let digest = TraceEvent::digest(&value);
+                    TraceEvent::Assign(var_name.name.clone(), digest, var_name.pos)
+                });
+
                 let _alias = if !rewind_scope {
                     // Put global constants into global module
                     #[cfg(not(feature = "no_function"))]
@@ -480,6 +634,51 @@ impl Engine {
                 Ok(Dynamic::UNIT)
             }
 
+            // Destructuring let/const statement
+            Stmt::Destructure(x, options, pos) => {
+                let (pattern, expr) = &**x;
+
+                let access = if options.intersects(ASTFlags::CONSTANT) {
+                    AccessMode::ReadOnly
+                } else {
+                    AccessMode::ReadWrite
+                };
+
+                // Check shadowing and variable definition filter
+                for var in pattern.variables() {
+                    self.check_var_def(
+                        global,
+                        caches,
+                        scope,
+                        this_ptr.as_deref_mut(),
+                        var.as_str(),
+                        access,
+                        var.pos,
+                    )?;
+                }
+
+                // Evaluate initial value
+                let value = self
+                    .eval_expr(global, caches, scope, this_ptr, expr)?
+                    .flatten();
+                let type_name = value.type_name();
+
+                let orig_scope_len = scope.len();
+
+                if self.bind_pattern(scope, pattern, value, access)? {
+                    return Ok(Dynamic::UNIT);
+                }
+
+                scope.rewind(orig_scope_len);
+
+                Err(ERR::ErrorMismatchDataType(
+                    pattern.to_string(),
+                    self.map_type_name(type_name).to_string(),
+                    *pos,
+                )
+                .into())
+            }
+
             // If statement
             Stmt::If(x, ..) => {
                 let FlowControl { expr, body, branch } = &**x;
@@ -507,6 +706,7 @@ impl Engine {
                         cases,
                         def_case,
                         ranges,
+                        patterns,
                     },
                 ) = &**x;
 
@@ -564,6 +764,41 @@ impl Engine {
                     }
                 }
 
+                // Then check destructuring patterns
+                if result.is_none() && !patterns.is_empty() {
+                    let value = value.flatten();
+
+                    for (pattern, index) in patterns {
+                        // Variables bound by the pattern are only visible within the case
+                        defer! { scope => rewind; let orig_scope_len = scope.len(); }
+
+                        if !self.bind_pattern(
+                            scope,
+                            pattern,
+                            value.clone(),
+                            AccessMode::ReadWrite,
+                        )? {
+                            continue;
+                        }
+
+                        let BinaryExpr { lhs, rhs } = &expressions[*index];
+
+                        let cond_result = match lhs {
+                            Expr::BoolConstant(b, ..) => *b,
+                            c => self
+                                .eval_expr(global, caches, scope, this_ptr.as_deref_mut(), c)?
+                                .as_bool()
+                                .map_err(|typ| {
+                                    self.make_type_mismatch_err::<bool>(typ, c.position())
+                                })?,
+                        };
+
+                        if cond_result {
+                            return self.eval_expr(global, caches, scope, this_ptr, rhs);
+                        }
+                    }
+                }
+
                 result
                     .or_else(|| def_case.as_ref().map(|&index| &expressions[index].rhs))
                     .map_or(Ok(Dynamic::UNIT), |expr| {
@@ -678,6 +913,7 @@ impl Engine {
                     .flatten();
 
                 let iter_type = iter_obj.type_id();
+                let tainted = self.taint_tracking() && iter_obj.is_tainted();
 
                 // lib should only contain scripts, so technically they cannot have iterators
 
@@ -743,10 +979,14 @@ impl Engine {
                         }
 
                         // Set loop value
-                        let value = iter_value
+                        let mut value = iter_value
                             .map_err(|err| err.fill_position(expr.position()))?
                             .flatten();
 
+                        if tainted {
+                            value.set_tainted(true);
+                        }
+
                         *scope.get_mut_by_index(index).write_lock().unwrap() = value;
 
                         // Run block
@@ -792,6 +1032,8 @@ impl Engine {
                     branch,
                 } = &**x;
 
+                let undo_point = global.undo_log.len();
+
                 match self.eval_stmt_block(
                     global,
                     caches,
@@ -804,6 +1046,9 @@ impl Engine {
                     Err(err) if err.is_pseudo_error() => Err(err),
                     Err(err) if !err.is_catchable() => Err(err),
                     Err(mut err) => {
+                        // Undo changes made within the try block
+                        global.undo_log.rollback_to(undo_point);
+
                         let err_value = match err.unwrap_inner() {
                             // No error variable
                             _ if catch_var.is_unit() => Dynamic::UNIT,
@@ -894,7 +1139,7 @@ impl Engine {
             Stmt::Import(x, _pos) => {
                 use crate::ModuleResolver;
 
-                let (expr, export) = &**x;
+                let (expr, export, symbols) = &**x;
 
                 // Guard against too many modules
                 #[cfg(not(feature = "unchecked"))]
@@ -913,6 +1158,20 @@ impl Engine {
 
                 let path_pos = expr.start_position();
 
+                self.record_import(global.source(), &path, path_pos);
+
+                // Guard against circular imports
+                if let Some(start) = global.import_chain.iter().position(|(p, ..)| *p == path) {
+                    let cycle = global.import_chain[start..]
+                        .iter()
+                        .map(|(p, pos)| (p.to_string(), *pos))
+                        .chain(std::iter::once((path.to_string(), path_pos)))
+                        .collect();
+                    return Err(ERR::ErrorCircularImport(cycle, path_pos).into());
+                }
+
+                global.import_chain.push((path.clone(), path_pos));
+
                 let resolver = global.embedded_module_resolver.clone();
 
                 let module = resolver
@@ -931,7 +1190,11 @@ impl Engine {
                     })
                     .unwrap_or_else(|| {
                         Err(ERR::ErrorModuleNotFound(path.to_string(), path_pos).into())
-                    })?;
+                    });
+
+                global.import_chain.pop();
+
+                let module = module?;
 
                 let (export, must_be_indexed) = if export.is_empty() {
                     (self.const_empty_string(), false)
@@ -939,6 +1202,33 @@ impl Engine {
                     (export.name.clone(), true)
                 };
 
+                // Collect the selected symbols, if any
+                let selected = if symbols.is_empty() {
+                    None
+                } else {
+                    let mut m = crate::Module::new();
+
+                    for (name, alias) in symbols {
+                        let mut found = false;
+
+                        if let Some(value) = module.get_var(&name.name) {
+                            m.set_var(alias.name.clone(), value);
+                            found = true;
+                        }
+                        if m.copy_fn_as(&module, &name.name, &alias.name) {
+                            found = true;
+                        }
+                        if !found {
+                            let sep = crate::engine::NAMESPACE_SEPARATOR;
+                            let name_path = format!("{path}{sep}{}", name.name);
+                            return Err(ERR::ErrorVariableNotFound(name_path, name.pos).into());
+                        }
+                    }
+
+                    m.build_index();
+                    Some(m)
+                };
+
                 if !must_be_indexed || module.is_indexed() {
                     global.push_import(export, module);
                 } else {
@@ -948,6 +1238,10 @@ impl Engine {
                     global.push_import(export, m);
                 }
 
+                if let Some(m) = selected {
+                    global.push_import(crate::engine::IMPORT_USE_SYMBOLS, m);
+                }
+
                 global.num_modules_loaded += 1;
 
                 Ok(Dynamic::UNIT)
@@ -1009,8 +1303,18 @@ impl Engine {
                     let val = scope.get_mut_by_index(index);
 
                     if !val.is_shared() {
+                        #[cfg(not(feature = "unchecked"))]
+                        if global.num_shared_values >= self.max_shared_values() {
+                            return Err(ERR::ErrorDataTooLarge(
+                                "Number of shared values".to_string(),
+                                var.pos,
+                            )
+                            .into());
+                        }
+
                         // Replace the variable with a shared value.
                         *val = val.take().into_shared();
+                        global.num_shared_values += 1;
                     }
                 }
 
@@ -1033,6 +1337,8 @@ impl Engine {
         statements: &[Stmt],
         map_exit_to_return_value: bool,
     ) -> RhaiResult {
+        let undo_point = global.undo_log.len();
+
         self.eval_stmt_block(global, caches, scope, None, statements, false)
             .or_else(|err| match *err {
                 ERR::Return(out, ..) => Ok(out),
@@ -1040,7 +1346,13 @@ impl Engine {
                 ERR::LoopBreak(..) => {
                     unreachable!("no outer loop scope to break out of")
                 }
-                _ => Err(err),
+                _ => {
+                    // Undo changes made during this run
+                    if !matches!(*err, ERR::Exit(..)) {
+                        global.undo_log.rollback_to(undo_point);
+                    }
+                    Err(err)
+                }
             })
     }
 }
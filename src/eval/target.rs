@@ -428,9 +428,16 @@ impl<'a> TryFrom<&'a mut Dynamic> for Target<'a> {
             // Cloning is cheap for a shared value
             let shared_value = value.clone();
             let Some(guard) = value.write_lock::<Dynamic>() else {
-                return Err(
-                    crate::EvalAltResult::ErrorDataRace(String::new(), Position::NONE).into(),
-                );
+                // A native function holding the value may have called back into the engine
+                let err = match shared_value.0 {
+                    crate::types::dynamic::Union::Shared(ref cell, ..)
+                        if crate::func::native::is_locked_by_current_thread(cell, true) =>
+                    {
+                        crate::EvalAltResult::ErrorReentrantLock(String::new(), Position::NONE)
+                    }
+                    _ => crate::EvalAltResult::ErrorDataRace(String::new(), Position::NONE),
+                };
+                return Err(err.into());
             };
             return Ok(Self::SharedValue {
                 guard,
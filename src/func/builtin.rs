@@ -128,6 +128,12 @@ pub fn get_builtin_binary_op_fn(op: &Token, x: &Dynamic, y: &Dynamic) -> Option<
             let y = args[1].$yy().unwrap() as $base;
             $func(x, y).map(Into::into)
         }, false)) };
+        (mode $base:ty => $func:ident ( $xx:ident, $yy:ident )) => { Some((|ctx, args| {
+            let mode = ctx.unwrap().engine().arithmetic_mode();
+            let x = args[0].$xx().unwrap() as $base;
+            let y = args[1].$yy().unwrap() as $base;
+            $func(mode, x, y).map(Into::into)
+        }, true)) };
         (from $base:ty => $xx:ident $op:tt $yy:ident) => { Some((|_, args| {
             let x = <$base>::from(args[0].$xx().unwrap());
             let y = <$base>::from(args[1].$yy().unwrap());
@@ -154,17 +160,20 @@ pub fn get_builtin_binary_op_fn(op: &Token, x: &Dynamic, y: &Dynamic) -> Option<
     if type1 == type2 {
         if type1 == TypeId::of::<INT>() {
             #[cfg(not(feature = "unchecked"))]
-            #[allow(clippy::wildcard_imports)]
-            use crate::packages::arithmetic::arith_basic::INT::functions::*;
+            use crate::packages::arithmetic::arith_basic::INT::{
+                add_with, divide_with,
+                functions::{shift_left, shift_right},
+                modulo_with, multiply_with, power_with, subtract_with,
+            };
 
             #[cfg(not(feature = "unchecked"))]
             match op {
-                Plus => return impl_op!(INT => add(as_int, as_int)),
-                Minus => return impl_op!(INT => subtract(as_int, as_int)),
-                Multiply => return impl_op!(INT => multiply(as_int, as_int)),
-                Divide => return impl_op!(INT => divide(as_int, as_int)),
-                Modulo => return impl_op!(INT => modulo(as_int, as_int)),
-                PowerOf => return impl_op!(INT => power(as_int, as_int)),
+                Plus => return impl_op!(mode INT => add_with(as_int, as_int)),
+                Minus => return impl_op!(mode INT => subtract_with(as_int, as_int)),
+                Multiply => return impl_op!(mode INT => multiply_with(as_int, as_int)),
+                Divide => return impl_op!(mode INT => divide_with(as_int, as_int)),
+                Modulo => return impl_op!(mode INT => modulo_with(as_int, as_int)),
+                PowerOf => return impl_op!(mode INT => power_with(as_int, as_int)),
                 RightShift => return impl_op!(INT => Ok(shift_right(as_int, as_int))),
                 LeftShift => return impl_op!(INT => Ok(shift_left(as_int, as_int))),
                 _ => (),
@@ -673,6 +682,12 @@ pub fn get_builtin_op_assignment_fn(op: &Token, x: &Dynamic, y: &Dynamic) -> Opt
             let y = args[1].$yy().unwrap() as $x;
             Ok((*args[0].write_lock().unwrap() = $func(x, y)?).into())
         }, false)) };
+        (mode $x:ty => $func:ident ( $xx:ident, $yy:ident )) => { Some((|ctx, args| {
+            let mode = ctx.unwrap().engine().arithmetic_mode();
+            let x = args[0].$xx().unwrap();
+            let y = args[1].$yy().unwrap() as $x;
+            Ok((*args[0].write_lock().unwrap() = $func(mode, x, y)?).into())
+        }, true)) };
         (from $x:ident $op:tt $yy:ident) => { Some((|_, args| {
             let y = <$x>::from(args[1].$yy().unwrap());
             Ok((*args[0].write_lock::<$x>().unwrap() $op y).into())
@@ -698,17 +713,20 @@ pub fn get_builtin_op_assignment_fn(op: &Token, x: &Dynamic, y: &Dynamic) -> Opt
     if type1 == type2 {
         if type1 == TypeId::of::<INT>() {
             #[cfg(not(feature = "unchecked"))]
-            #[allow(clippy::wildcard_imports)]
-            use crate::packages::arithmetic::arith_basic::INT::functions::*;
+            use crate::packages::arithmetic::arith_basic::INT::{
+                add_with, divide_with,
+                functions::{shift_left, shift_right},
+                modulo_with, multiply_with, power_with, subtract_with,
+            };
 
             #[cfg(not(feature = "unchecked"))]
             match op {
-                PlusAssign => return impl_op!(INT => add(as_int, as_int)),
-                MinusAssign => return impl_op!(INT => subtract(as_int, as_int)),
-                MultiplyAssign => return impl_op!(INT => multiply(as_int, as_int)),
-                DivideAssign => return impl_op!(INT => divide(as_int, as_int)),
-                ModuloAssign => return impl_op!(INT => modulo(as_int, as_int)),
-                PowerOfAssign => return impl_op!(INT => power(as_int, as_int)),
+                PlusAssign => return impl_op!(mode INT => add_with(as_int, as_int)),
+                MinusAssign => return impl_op!(mode INT => subtract_with(as_int, as_int)),
+                MultiplyAssign => return impl_op!(mode INT => multiply_with(as_int, as_int)),
+                DivideAssign => return impl_op!(mode INT => divide_with(as_int, as_int)),
+                ModuloAssign => return impl_op!(mode INT => modulo_with(as_int, as_int)),
+                PowerOfAssign => return impl_op!(mode INT => power_with(as_int, as_int)),
                 RightShiftAssign => return impl_op!(INT => Ok(shift_right(as_int, as_int))),
                 LeftShiftAssign => return impl_op!(INT => Ok(shift_left(as_int, as_int))),
                 _ => (),
@@ -4,11 +4,11 @@ use super::{get_builtin_binary_op_fn, get_builtin_op_assignment_fn, RhaiFunc};
 use crate::api::default_limits::MAX_DYNAMIC_PARAMETERS;
 use crate::ast::{Expr, FnCallExpr, FnCallHashes};
 use crate::engine::{
-    KEYWORD_DEBUG, KEYWORD_EVAL, KEYWORD_FN_PTR, KEYWORD_FN_PTR_CALL, KEYWORD_FN_PTR_CURRY,
-    KEYWORD_IS_DEF_VAR, KEYWORD_PRINT, KEYWORD_TYPE_OF,
+    FN_PRETTY_PRINT, KEYWORD_DEBUG, KEYWORD_EVAL, KEYWORD_FN_PTR, KEYWORD_FN_PTR_CALL,
+    KEYWORD_FN_PTR_CURRY, KEYWORD_IS_DEF_VAR, KEYWORD_PRINT, KEYWORD_TYPE_OF,
 };
 use crate::eval::{Caches, FnResolutionCacheEntry, GlobalRuntimeState};
-use crate::tokenizer::{is_valid_function_name, Token};
+use crate::tokenizer::{is_valid_function_name, is_valid_identifier, Token};
 use crate::types::dynamic::Union;
 use crate::{
     calc_fn_hash, calc_fn_hash_full, Dynamic, Engine, FnArgsVec, FnPtr, ImmutableString, Position,
@@ -112,6 +112,26 @@ impl Drop for ArgBackup<'_> {
     }
 }
 
+/// Marker value returned by a builder method (see
+/// [`TypeBuilder::with_builder_fn`][crate::TypeBuilder::with_builder_fn]) that has updated the
+/// object in its first argument in place.
+///
+/// A method call in a dot chain continues with the same object; elsewhere it is mapped to `()`.
+#[derive(Debug, Clone, Copy)]
+pub struct BuilderUpdated;
+
+impl BuilderUpdated {
+    /// Map the marker value to `()`, leaving any other value unchanged.
+    #[inline(always)]
+    pub fn into_unit(value: Dynamic) -> Dynamic {
+        if value.is::<Self>() {
+            Dynamic::UNIT
+        } else {
+            value
+        }
+    }
+}
+
 // Ensure no data races in function call arguments.
 #[cfg(not(feature = "no_closure"))]
 #[inline]
@@ -128,6 +148,37 @@ pub fn ensure_no_data_race(fn_name: &str, args: &FnCallArgs, is_ref_mut: bool) -
         })
 }
 
+/// Call a native Rust function, converting any panic into an error at the call site.
+///
+/// Without the `catch_panics` feature, the function is simply called and panics propagate.
+#[inline(always)]
+fn call_native_fn(
+    _name: &str,
+    _source: Option<&str>,
+    _pos: Position,
+    f: impl FnOnce() -> RhaiResult,
+) -> RhaiResult {
+    #[cfg(not(feature = "catch_panics"))]
+    return f();
+
+    #[cfg(feature = "catch_panics")]
+    return std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+
+        Err(ERR::ErrorInFunctionCall(
+            _name.to_string(),
+            _source.unwrap_or_default().to_string(),
+            ERR::ErrorSystem("Function panicked".to_string(), msg.into()).into(),
+            _pos,
+        )
+        .into())
+    });
+}
+
 /// Is a function name an anonymous function?
 #[cfg(not(feature = "no_function"))]
 #[inline]
@@ -188,15 +239,22 @@ impl Engine {
                 let mut bitmask = 1usize; // Bitmask of which parameter to replace with `Dynamic`
 
                 loop {
-                    // First check scripted functions in the AST or embedded environments
+                    // First check operators scoped to the argument types, if any
+                    let func = if self.type_operators.is_empty() {
+                        None
+                    } else {
+                        self.type_operators.get_fn(hash).map(|f| (f, None))
+                    };
+
+                    // Then check scripted functions in the AST or embedded environments
                     #[cfg(not(feature = "no_function"))]
-                    let func = _global
-                        .lib
-                        .iter()
-                        .rev()
-                        .find_map(|m| m.get_fn(hash).map(|f| (f, m.id_raw())));
-                    #[cfg(feature = "no_function")]
-                    let func = None;
+                    let func = func.or_else(|| {
+                        _global
+                            .lib
+                            .iter()
+                            .rev()
+                            .find_map(|m| m.get_fn(hash).map(|f| (f, m.id_raw())))
+                    });
 
                     // Then check the global namespace
                     let func = func.or_else(|| {
@@ -361,11 +419,32 @@ impl Engine {
         if let Some(FnResolutionCacheEntry { func, source }) = func {
             debug_assert!(func.is_native());
 
-            if non_volatile_only && func.is_volatile() {
+            if (non_volatile_only || self.deterministic()) && func.is_volatile() {
                 let gen_fn_call_signature = self.gen_fn_call_signature(name, args);
                 return Err(ERR::ErrorFunctionNotFound(gen_fn_call_signature, pos).into());
             }
 
+            // Only functions in the allow-list can be called
+            if let Some(ref list) = self.fn_allow_list {
+                if !list.is_allowed(name, op_token, func, args) {
+                    let gen_fn_call_signature = self.gen_fn_call_signature(name, args);
+                    return Err(ERR::ErrorFunctionNotFound(gen_fn_call_signature, pos).into());
+                }
+            }
+
+            // Check taint sinks, and whether the result is tainted
+            let tainted = match self.taint_rules {
+                Some(ref rules) => rules.check_call(name, args, pos)?,
+                None => false,
+            };
+
+            if is_valid_identifier(name) {
+                self.track_trace(|| {
+                    let args = args.iter().map(|v| crate::TraceEvent::digest(v)).collect();
+                    crate::TraceEvent::Call(self.get_interned_string(name), args, pos)
+                });
+            }
+
             let is_method = func.is_method();
 
             // Push a new call stack frame
@@ -377,13 +456,22 @@ impl Engine {
 
             let backup = &mut ArgBackup::new();
 
+            // Redact the value to be rendered by print/debug
+            let redact = self.redact.is_some()
+                && !args.is_empty()
+                && matches!(name, KEYWORD_PRINT | KEYWORD_DEBUG | FN_PRETTY_PRINT);
+
             // Calling non-method function but the first argument is a reference?
-            let swap = is_ref_mut && !is_method && !args.is_empty();
+            // Redaction must not change the original value either.
+            let swap = is_ref_mut && (!is_method || redact) && !args.is_empty();
 
             if swap {
                 // Clone the first argument
                 backup.change_first_arg_to_copy(args);
             }
+            if redact {
+                *args[0] = self.redact_value(None, &*args[0]);
+            }
 
             #[cfg(feature = "debugging")]
             if self.is_debugger_registered() {
@@ -407,8 +495,12 @@ impl Engine {
                 f if !f.is_pure() && !args.is_empty() && args[0].is_read_only() => {
                     Err(ERR::ErrorNonPureMethodCallOnConstant(name.to_string(), pos).into())
                 }
-                RhaiFunc::Plugin { func } => func.call(context, args),
-                RhaiFunc::Pure { func, .. } | RhaiFunc::Method { func, .. } => func(context, args),
+                RhaiFunc::Plugin { func } => {
+                    call_native_fn(name, source.as_deref(), pos, || func.call(context, args))
+                }
+                RhaiFunc::Pure { func, .. } | RhaiFunc::Method { func, .. } => {
+                    call_native_fn(name, source.as_deref(), pos, || func(context, args))
+                }
                 _ => unreachable!("non-native function"),
             }
             .and_then(|r| self.check_data_size(r, pos))
@@ -418,6 +510,24 @@ impl Engine {
                 backup.restore_first_arg(args);
             }
 
+            // Add the operations performed by script callbacks of the function
+            #[cfg(not(feature = "unchecked"))]
+            if global.absorb_pending_operations() {
+                _result = _result.and_then(|r| self.track_operation(global, pos).map(|()| r));
+            }
+
+            if tainted {
+                if let Ok(ref mut r) = _result {
+                    r.set_tainted(true);
+                }
+                // The object may be modified in place (op-assignments always modify it)
+                let is_op_assignment = op_token.map_or(false, Token::is_op_assignment);
+
+                if is_method && (!func.is_pure() || is_op_assignment) && !args.is_empty() {
+                    args[0].set_tainted(true);
+                }
+            }
+
             #[cfg(feature = "debugging")]
             if self.is_debugger_registered() {
                 use crate::eval::{DebuggerEvent, DebuggerStatus};
@@ -425,6 +535,11 @@ impl Engine {
                 let trigger = match global.debugger().status {
                     DebuggerStatus::FunctionExit(n) => n >= global.level,
                     DebuggerStatus::Next(.., true) => true,
+                    DebuggerStatus::StepOut(n) if n >= global.level => {
+                        // Stop at the next step in the caller
+                        global.debugger_mut().status = DebuggerStatus::STEP;
+                        false
+                    }
                     _ => false,
                 };
                 if trigger {
@@ -454,25 +569,50 @@ impl Engine {
                 self.check_data_size(&*args[0], pos)?;
             }
 
+            // A builder method has updated the object in place.
+            // The marker is kept for references so that a dot chain can continue with the object,
+            // otherwise the updated temporary object is the result.
+            if result.is::<BuilderUpdated>() {
+                return Ok(if is_ref_mut {
+                    (result, true)
+                } else {
+                    (self.check_data_size(args[0].take(), pos)?, false)
+                });
+            }
+
             // See if the function match print/debug (which requires special processing)
             return Ok(match name {
-                KEYWORD_PRINT => {
-                    if let Some(ref print) = self.print {
+                KEYWORD_PRINT | FN_PRETTY_PRINT => {
+                    if self.print.is_some() || global.output.is_some() {
                         let text = result.into_immutable_string().map_err(|typ| {
                             let t = self.map_type_name(type_name::<ImmutableString>()).into();
                             ERR::ErrorMismatchOutputType(t, typ.into(), pos)
                         })?;
-                        print(&text);
+                        if let Some(ref print) = self.print {
+                            print(&text);
+                        }
+                        if let Some(mut output) =
+                            global.output.as_deref().and_then(crate::func::locked_write)
+                        {
+                            output.prints.push(text);
+                        }
                     }
                     (Dynamic::UNIT, false)
                 }
                 KEYWORD_DEBUG => {
-                    if let Some(ref debug) = self.debug {
+                    if self.debug.is_some() || global.output.is_some() {
                         let text = result.into_immutable_string().map_err(|typ| {
                             let t = self.map_type_name(type_name::<ImmutableString>()).into();
                             ERR::ErrorMismatchOutputType(t, typ.into(), pos)
                         })?;
-                        debug(&text, global.source(), pos);
+                        if let Some(ref debug) = self.debug {
+                            debug(&text, global.source(), pos);
+                        }
+                        if let Some(mut output) =
+                            global.output.as_deref().and_then(crate::func::locked_write)
+                        {
+                            output.debug_messages.push((text, pos));
+                        }
                     }
                     (Dynamic::UNIT, false)
                 }
@@ -606,6 +746,32 @@ impl Engine {
 
         defer! { let orig_level = global.level; global.level += 1 }
 
+        // Script-defined operator functions are called only when no native function or built-in
+        // operator matches the operands
+        #[cfg(not(feature = "no_function"))]
+        let hashes = match op_token {
+            Some(..)
+                if hashes.is_native_only()
+                    && global.lib.iter().any(|m| {
+                        m.contains_fn(hashes.native()) || m.may_contain_typed_fn(hashes.native())
+                    })
+                    && self
+                        .resolve_fn(
+                            global,
+                            caches,
+                            &mut None,
+                            op_token,
+                            hashes.native(),
+                            Some(&mut *args),
+                            true,
+                        )
+                        .is_none() =>
+            {
+                FnCallHashes::from_hash(hashes.native())
+            }
+            _ => hashes,
+        };
+
         // Script-defined function call?
         #[cfg(not(feature = "no_function"))]
         if !hashes.is_native_only() {
@@ -613,18 +779,67 @@ impl Engine {
             let local_entry = &mut None;
             let mut resolved = None;
 
+            // Are there script-defined functions with typed parameters?
+            let has_typed_params = global.lib.iter().any(|m| m.may_contain_typed_fn(hash));
+            let calc_typed_params_hash = |base: u64, args: &FnCallArgs| {
+                let params = if _is_method_call { &args[1..] } else { args };
+                let types = params.iter().map(|a| self.map_type_name(a.type_name()));
+                crate::calc_typed_params_hash(base, types)
+            };
+
             #[cfg(not(feature = "no_object"))]
             if _is_method_call && !args.is_empty() {
                 let typed_hash =
                     crate::calc_typed_method_hash(hash, self.map_type_name(args[0].type_name()));
-                resolved =
-                    self.resolve_fn(global, caches, local_entry, None, typed_hash, None, false);
+
+                if has_typed_params {
+                    let hash = calc_typed_params_hash(typed_hash, args);
+                    resolved =
+                        self.resolve_fn(global, caches, local_entry, None, hash, None, false);
+                }
+                if resolved.is_none() {
+                    resolved =
+                        self.resolve_fn(global, caches, local_entry, None, typed_hash, None, false);
+                }
+            }
+
+            if resolved.is_none() && has_typed_params {
+                let hash = calc_typed_params_hash(hash, args);
+                resolved = self.resolve_fn(global, caches, local_entry, None, hash, None, false);
             }
 
             if resolved.is_none() {
                 resolved = self.resolve_fn(global, caches, local_entry, None, hash, None, false);
             }
 
+            // Then search functions taking a range of number of arguments, and cache the result
+            if resolved.is_none() {
+                let num_args = if _is_method_call {
+                    args.len().saturating_sub(1)
+                } else {
+                    args.len()
+                };
+                #[cfg(not(feature = "no_object"))]
+                let this_type = (_is_method_call && !args.is_empty())
+                    .then(|| self.map_type_name(args[0].type_name()));
+                #[cfg(feature = "no_object")]
+                let this_type = None;
+
+                let entry = global.lib.iter().rev().find_map(|m| {
+                    m.get_script_fn_by_arity(fn_name, num_args, this_type)
+                        .map(|f| FnResolutionCacheEntry {
+                            func: f.clone(),
+                            source: m.id_raw().cloned(),
+                        })
+                });
+
+                if let Some(entry) = entry {
+                    let dict = &mut caches.fn_resolution_cache_mut().dict;
+                    dict.insert(hash, Some(entry));
+                    resolved = dict.get(&hash).and_then(Option::as_ref);
+                }
+            }
+
             if let Some(FnResolutionCacheEntry { func, source }) = resolved.cloned() {
                 let RhaiFunc::Script { fn_def, environ } = func else {
                     unreachable!("Script function expected");
@@ -909,7 +1124,17 @@ impl Engine {
 
                 #[cfg(not(feature = "no_object"))]
                 if let Some(map) = target.as_ref().read_lock::<crate::Map>() {
-                    if let Some(val) = map.get(fn_name) {
+                    // Fall back to the prototype chain of the object map
+                    let _inherited;
+                    let val = match map.get(fn_name) {
+                        Some(val) => Some(val),
+                        None => {
+                            _inherited = crate::Prototype::find_inherited(&map, fn_name);
+                            _inherited.as_ref()
+                        }
+                    };
+
+                    if let Some(val) = val {
                         if let Some(fn_ptr) = val.read_lock::<FnPtr>() {
                             // Remap the function name
                             _redirected = fn_ptr.fn_name_raw().clone();
@@ -1187,8 +1412,19 @@ impl Engine {
                     Dynamic::FALSE
                 } else if num_params >= 0 {
                     #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-                    let hash_script = calc_fn_hash(None, &fn_name, num_params as usize);
-                    self.has_script_fn(global, caches, hash_script).into()
+                    let num_params = num_params as usize;
+                    let hash_script = calc_fn_hash(None, &fn_name, num_params);
+
+                    // Also check for script-defined functions with typed parameters,
+                    // or taking a range of number of arguments
+                    (self.has_script_fn(global, caches, hash_script)
+                        || global.lib.iter().any(|m| {
+                            (m.may_contain_typed_fn(hash_script)
+                                && m.get_script_fn(&fn_name, num_params).is_some())
+                                || m.get_script_fn_by_arity(&fn_name, num_params, None)
+                                    .is_some()
+                        }))
+                    .into()
                 } else {
                     Dynamic::FALSE
                 });
@@ -1229,11 +1465,17 @@ impl Engine {
                     Dynamic::FALSE
                 } else if num_params >= 0 {
                     #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+                    let num_params = num_params as usize;
                     let hash_script = crate::calc_typed_method_hash(
-                        calc_fn_hash(None, &fn_name, num_params as usize),
+                        calc_fn_hash(None, &fn_name, num_params),
                         &this_type,
                     );
-                    self.has_script_fn(global, caches, hash_script).into()
+                    (self.has_script_fn(global, caches, hash_script)
+                        || global.lib.iter().any(|m| {
+                            m.get_script_fn_by_arity(&fn_name, num_params, Some(&this_type))
+                                .is_some()
+                        }))
+                    .into()
                 } else {
                     Dynamic::FALSE
                 });
@@ -1320,7 +1562,7 @@ impl Engine {
                     global, caches, scope, fn_name, op_token, hashes, &mut args, is_ref_mut, false,
                     pos,
                 )
-                .map(|(v, ..)| v);
+                .map(|(v, ..)| BuilderUpdated::into_unit(v));
         }
 
         // Call with blank scope
@@ -1394,7 +1636,7 @@ impl Engine {
         self.exec_fn_call(
             global, caches, None, fn_name, op_token, hashes, &mut args, is_ref_mut, false, pos,
         )
-        .map(|(v, ..)| v)
+        .map(|(v, ..)| BuilderUpdated::into_unit(v))
     }
 
     /// Call a namespace-qualified function in normal function-call style.
@@ -1492,13 +1734,36 @@ impl Engine {
             .search_imports(global, namespace)
             .ok_or_else(|| ERR::ErrorModuleNotFound(namespace.to_string(), namespace.position()))?;
 
-        // First search script-defined functions in namespace (can override built-in)
-        let mut func = module.get_qualified_fn(hash).or_else(|| {
+        // First search script-defined functions in namespace with typed parameters
+        #[cfg(not(feature = "no_function"))]
+        let func = if args.is_empty() {
+            None
+        } else {
+            let types = args.iter().map(|a| self.map_type_name(a.type_name()));
+            module.get_qualified_fn(crate::calc_typed_params_hash(hash, types))
+        };
+        #[cfg(feature = "no_function")]
+        let func = None;
+
+        // Then search script-defined functions in namespace (can override built-in)
+        let mut func = func.or_else(|| module.get_qualified_fn(hash)).or_else(|| {
             // Then search native Rust functions
             let hash_qualified_fn = calc_fn_hash_full(hash, args.iter().map(|a| a.type_id()));
             module.get_qualified_fn(hash_qualified_fn)
         });
 
+        // Then search script-defined functions in namespace with a rest parameter
+        #[cfg(not(feature = "no_function"))]
+        if func.is_none() {
+            let path = namespace.path.iter().map(crate::ast::Ident::as_str);
+            let hash = crate::calc_arity_range_hash(fn_name) ^ calc_fn_hash(path, fn_name, 0);
+
+            func = module.get_qualified_fn(hash).filter(|f| {
+                f.get_script_fn_def()
+                    .map_or(false, |f| f.accepts_num_args(args.len()))
+            });
+        }
+
         // Check for `Dynamic` parameters.
         //
         // Note - This is done during every function call mismatch without cache,
@@ -1535,6 +1800,16 @@ impl Engine {
             }
         }
 
+        // Volatile functions are not available in deterministic mode
+        if self.deterministic() {
+            func = func.filter(|f| !f.is_volatile());
+        }
+
+        // Only native functions in the allow-list can be called
+        if let Some(ref list) = self.fn_allow_list {
+            func = func.filter(|f| f.is_script() || list.is_allowed(fn_name, None, f, args));
+        }
+
         // Clone first argument if the function is not a method after-all
         if !func.map_or(true, RhaiFunc::is_method) {
             if let Some(first) = first_arg_value {
@@ -1545,6 +1820,14 @@ impl Engine {
 
         defer! { let orig_level = global.level; global.level += 1 }
 
+        // Run under the resource limits of the module, if any
+        #[cfg(not(feature = "unchecked"))]
+        let backup = module
+            .resource_limits()
+            .map(|limits| global.apply_module_limits(limits));
+        #[cfg(not(feature = "unchecked"))]
+        defer! { global if Some(backup) => move |g| g.restore_limits(backup) }
+
         match func {
             #[cfg(not(feature = "no_function"))]
             Some(RhaiFunc::Script { fn_def, environ }) => {
@@ -1568,7 +1851,7 @@ impl Engine {
                 let context = func
                     .has_context()
                     .then(|| (self, fn_name, module.id(), &*global, pos).into());
-                func.call(context, args)
+                call_native_fn(fn_name, module.id(), pos, || func.call(context, args))
                     .and_then(|r| self.check_data_size(r, pos))
             }
 
@@ -1582,7 +1865,8 @@ impl Engine {
             ) => {
                 let context =
                     has_context.then(|| (self, fn_name, module.id(), &*global, pos).into());
-                func(context, args).and_then(|r| self.check_data_size(r, pos))
+                call_native_fn(fn_name, module.id(), pos, || func(context, args))
+                    .and_then(|r| self.check_data_size(r, pos))
             }
 
             Some(RhaiFunc::Iterator { .. }) => {
@@ -1675,7 +1959,11 @@ impl Engine {
         let op_token = op_token.as_ref();
 
         // Short-circuit native unary operator call if under Fast Operators mode
-        if self.fast_operators() && args.len() == 1 && op_token == Some(&Token::Bang) {
+        if self.fast_operators()
+            && !self.taint_tracking()
+            && args.len() == 1
+            && op_token == Some(&Token::Bang)
+        {
             let mut value = self
                 .get_arg_value(global, caches, scope, this_ptr.as_deref_mut(), &args[0])?
                 .0
@@ -1693,7 +1981,8 @@ impl Engine {
         }
 
         // Short-circuit native binary operator call if under Fast Operators mode
-        if self.fast_operators() && args.len() == 2 && op_token.is_some() {
+        if self.fast_operators() && !self.taint_tracking() && args.len() == 2 && op_token.is_some()
+        {
             #[allow(clippy::wildcard_imports)]
             use Token::*;
 
@@ -1731,8 +2020,11 @@ impl Engine {
                 },
                 (Union::Int(n1, ..), Union::Int(n2, ..)) => {
                     #[cfg(not(feature = "unchecked"))]
-                    #[allow(clippy::wildcard_imports)]
-                    use crate::packages::arithmetic::arith_basic::INT::functions::*;
+                    use crate::packages::arithmetic::arith_basic::INT::{
+                        add_with, divide_with, modulo_with, multiply_with, subtract_with,
+                    };
+                    #[cfg(not(feature = "unchecked"))]
+                    let mode = self.arithmetic_mode();
 
                     #[cfg(not(feature = "unchecked"))]
                     match op_token {
@@ -1742,11 +2034,11 @@ impl Engine {
                         GreaterThanEqualsTo => return Ok((n1 >= n2).into()),
                         LessThan => return Ok((n1 < n2).into()),
                         LessThanEqualsTo => return Ok((n1 <= n2).into()),
-                        Plus => return add(*n1, *n2).map(Into::into),
-                        Minus => return subtract(*n1, *n2).map(Into::into),
-                        Multiply => return multiply(*n1, *n2).map(Into::into),
-                        Divide => return divide(*n1, *n2).map(Into::into),
-                        Modulo => return modulo(*n1, *n2).map(Into::into),
+                        Plus => return add_with(mode, *n1, *n2).map(Into::into),
+                        Minus => return subtract_with(mode, *n1, *n2).map(Into::into),
+                        Multiply => return multiply_with(mode, *n1, *n2).map(Into::into),
+                        Divide => return divide_with(mode, *n1, *n2).map(Into::into),
+                        Modulo => return modulo_with(mode, *n1, *n2).map(Into::into),
                         _ => (),
                     }
                     #[cfg(feature = "unchecked")]
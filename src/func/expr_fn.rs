@@ -0,0 +1,67 @@
+//! Module which defines the mechanism to create a Rust closure from an expression.
+
+#![allow(non_snake_case)]
+
+use crate::func::SendSync;
+use crate::types::dynamic::Variant;
+use crate::{Dynamic, RhaiResultOf, StaticVec};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Trait, implemented for tuples of parameter types, to create a Rust closure from an
+/// expression via [`Engine::compile_expression_to_fn`][crate::Engine::compile_expression_to_fn].
+pub trait ExprFnArgs<'e, R>: Sized {
+    /// The closure's type.
+    type Output;
+
+    /// Number of parameters of the closure.
+    const NUM_PARAMS: usize;
+
+    /// Wrap a function, which evaluates the expression on a list of argument values,
+    /// into a closure taking the parameters individually.
+    #[doc(hidden)]
+    fn wrap_fn(
+        eval: impl Fn(StaticVec<Dynamic>) -> RhaiResultOf<R> + SendSync + 'e,
+    ) -> Self::Output;
+}
+
+/// Macro to count the number of identifiers.
+macro_rules! count_params {
+    () => { 0 };
+    ($p0:ident $(, $p:ident)*) => { 1 + count_params!($($p),*) };
+}
+
+macro_rules! def_expr_fn_args {
+    () => {
+        def_expr_fn_args!(imp);
+    };
+    (imp $($par:ident),*) => {
+        impl<'e, $($par: Variant + Clone,)* RET: Variant + Clone> ExprFnArgs<'e, RET> for ($($par,)*)
+        {
+            #[cfg(feature = "sync")]
+            type Output = Box<dyn Fn($($par,)*) -> RhaiResultOf<RET> + Send + Sync + 'e>;
+            #[cfg(not(feature = "sync"))]
+            type Output = Box<dyn Fn($($par,)*) -> RhaiResultOf<RET> + 'e>;
+
+            const NUM_PARAMS: usize = count_params!($($par),*);
+
+            #[inline]
+            fn wrap_fn(
+                eval: impl Fn(StaticVec<Dynamic>) -> RhaiResultOf<RET> + SendSync + 'e,
+            ) -> Self::Output {
+                Box::new(move |$($par,)*| {
+                    #[allow(unused_mut)]
+                    let mut args = StaticVec::new_const();
+                    $(args.push(Dynamic::from($par));)*
+                    eval(args)
+                })
+            }
+        }
+    };
+    ($p0:ident $(, $p:ident)*) => {
+        def_expr_fn_args!(imp $p0 $(, $p)*);
+        def_expr_fn_args!($($p),*);
+    };
+}
+
+def_expr_fn_args!(A, B, C, D, E, F, G, H, J, K, L, M, N, P, Q, R, S, T, U, V);
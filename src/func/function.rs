@@ -148,6 +148,23 @@ impl RhaiFunc {
             | Self::Plugin { .. } => false,
         }
     }
+    /// Get an identifier of a native Rust function, which is unique while the function is alive.
+    ///
+    /// Returns [`None`] for script-defined functions.
+    #[inline]
+    #[must_use]
+    pub(crate) fn native_fn_id(&self) -> Option<usize> {
+        match self {
+            Self::Pure { func, .. } | Self::Method { func, .. } => {
+                Some(Shared::as_ptr(func).cast::<()>() as usize)
+            }
+            Self::Iterator { func } => Some(Shared::as_ptr(func).cast::<()>() as usize),
+            Self::Plugin { func } => Some(Shared::as_ptr(func).cast::<()>() as usize),
+
+            #[cfg(not(feature = "no_function"))]
+            Self::Script { .. } => None,
+        }
+    }
     /// Is this a plugin function?
     #[inline]
     #[must_use]
@@ -166,3 +166,44 @@ pub fn calc_typed_method_hash(base: u64, this_type: &str) -> u64 {
 
     s.finish() ^ base
 }
+
+/// Calculate a [`u64`] hash key from the name of a script-defined function that takes a range of
+/// number of arguments (i.e. with parameters that have default values, or a rest parameter).
+///
+/// The hash key does not depend on the number of arguments.
+#[cfg(not(feature = "no_function"))]
+#[inline]
+#[must_use]
+pub fn calc_arity_range_hash(fn_name: &str) -> u64 {
+    let s = &mut get_hasher();
+
+    s.write_u8(b'R'); // hash a discriminant
+    fn_name.hash(s);
+
+    s.finish()
+}
+
+/// Calculate a [`u64`] hash key from a base [`u64`] hash key and the names of the parameter
+/// types of a script-defined function.
+///
+/// Parameter type names are passed in via `&str` references from an iterator.
+#[cfg(not(feature = "no_function"))]
+#[inline]
+#[must_use]
+pub fn calc_typed_params_hash<'a>(
+    base: u64,
+    param_types: impl IntoIterator<Item = &'a str>,
+) -> u64 {
+    let s = &mut get_hasher();
+
+    s.write_u8(b'P'); // hash a discriminant
+
+    let mut count = 0;
+    param_types.into_iter().for_each(|t| {
+        t.hash(s);
+        count += 1;
+    });
+    s.write_usize(count);
+
+    s.finish() ^ base
+}
@@ -2,6 +2,7 @@
 
 pub mod builtin;
 pub mod call;
+pub mod expr_fn;
 pub mod func_args;
 #[allow(clippy::module_inception)]
 pub mod func_trait;
@@ -18,6 +19,7 @@ pub use call::ensure_no_data_race;
 #[cfg(not(feature = "no_function"))]
 pub use call::is_anonymous_fn;
 pub use call::FnCallArgs;
+pub use expr_fn::ExprFnArgs;
 pub use func_args::FuncArgs;
 #[cfg(not(feature = "no_function"))]
 pub use func_trait::Func;
@@ -25,6 +27,8 @@ pub use function::RhaiFunc;
 #[cfg(not(feature = "no_object"))]
 #[cfg(not(feature = "no_function"))]
 pub use hashing::calc_typed_method_hash;
+#[cfg(not(feature = "no_function"))]
+pub use hashing::{calc_arity_range_hash, calc_typed_params_hash};
 pub use hashing::{calc_fn_hash, calc_fn_hash_full, calc_var_hash, get_hasher, StraightHashMap};
 #[cfg(feature = "internals")]
 #[allow(deprecated)]
@@ -34,4 +38,4 @@ pub use native::{
     locked_read, locked_write, shared_get_mut, shared_make_mut, shared_take, shared_take_or_clone,
     FnIterator, Locked, NativeCallContext, SendSync, Shared,
 };
-pub use register::RhaiNativeFunc;
+pub use register::{RhaiNativeFunc, WithHostState};
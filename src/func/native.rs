@@ -1,6 +1,6 @@
 //! Module defining interfaces to native-Rust functions.
 
-use super::call::FnCallArgs;
+use super::call::{BuilderUpdated, FnCallArgs};
 use crate::ast::FnCallHashes;
 use crate::eval::{Caches, GlobalRuntimeState};
 use crate::plugin::PluginFunc;
@@ -10,7 +10,7 @@ use crate::{
     calc_fn_hash, expose_under_internals, Dynamic, Engine, EvalContext, FnArgsVec, FuncArgs,
     Position, RhaiResult, RhaiResultOf, StaticVec, VarDefInfo, ERR,
 };
-use std::any::type_name;
+use std::any::{type_name, Any};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 
@@ -250,6 +250,56 @@ impl<'a> NativeCallContext<'a> {
     pub const fn tag(&self) -> Option<&Dynamic> {
         Some(&self.global.tag)
     }
+    /// Get the per-run user data of a particular type passed via
+    /// [`Engine::run_with_context`], if any.
+    #[inline(always)]
+    #[must_use]
+    pub fn user_data<T: 'static>(&self) -> Option<&'a T> {
+        self.global.user_data::<T>()
+    }
+    /// Get the host service of a particular type registered via
+    /// [`Engine::set_service`], if any.
+    #[inline(always)]
+    #[must_use]
+    pub fn service<T: 'static>(&self) -> Option<&'a T> {
+        self.engine.service::<T>()
+    }
+    /// Run a closure with mutable access to the host state of a particular type set via
+    /// [`Scope::set_host_state`][crate::Scope::set_host_state].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no host state of type `S` is set, or if the host state is already
+    /// being accessed.
+    pub fn with_host_state<S: 'static, R>(&self, f: impl FnOnce(&mut S) -> R) -> RhaiResultOf<R> {
+        let state = self.global.host_state.as_ref().ok_or_else(|| {
+            ERR::ErrorRuntime(
+                format!("Host state of type {} not set", type_name::<S>()).into(),
+                self.position(),
+            )
+        })?;
+        let mut state = locked_write(state)
+            .ok_or_else(|| ERR::ErrorDataRace(String::new(), self.position()))?;
+        let state = state.downcast_mut::<S>().ok_or_else(|| {
+            ERR::ErrorRuntime(
+                format!("Host state is not of type {}", type_name::<S>()).into(),
+                self.position(),
+            )
+        })?;
+        Ok(f(state))
+    }
+    /// Enlist an undo action in the current evaluation run.
+    ///
+    /// If an error is later thrown, the undo action is run (in reverse order of enlistment)
+    /// when the error is caught by a `try` block started before this call, or when the error
+    /// escapes the evaluation run. Otherwise, the undo action is discarded without being run
+    /// when the evaluation run completes.
+    ///
+    /// This allows changes to host state made by native functions to be applied atomically.
+    #[inline(always)]
+    pub fn enlist_undo(&self, action: impl FnOnce() + SendSync + 'static) -> RhaiResultOf<()> {
+        self.global.undo_log.enlist(action)
+    }
     /// Get an iterator over the current set of modules imported via `import` statements
     /// in reverse order.
     ///
@@ -290,6 +340,18 @@ impl<'a> NativeCallContext<'a> {
         &self.global.lib
     }
     /// Call a function inside the call context with the provided arguments.
+    ///
+    /// # Re-Entrancy
+    ///
+    /// A native function called as a method on a _shared_ value (e.g. a variable captured by a
+    /// closure) holds a lock on that value for the duration of the call.
+    ///
+    /// If the function calls back into the [`Engine`] and the callback accesses the same value,
+    /// it cannot be locked again. Instead of deadlocking (under the `sync` feature, in debug builds
+    /// only), an [`ErrorReentrantLock`][ERR::ErrorReentrantLock] error is returned.
+    ///
+    /// To call back into the [`Engine`] with a value, do not take it by reference, or work on a
+    /// copy and write back the result after the call returns.
     #[inline]
     pub fn call_fn<T: Variant + Clone>(
         &self,
@@ -429,42 +491,41 @@ impl<'a> NativeCallContext<'a> {
 
         let caches = &mut Caches::new();
 
+        // The `this` pointer is passed as a reference, so it is never locked here
+        let skip = usize::from(is_ref_mut);
+        ensure_no_reentrant_lock(&args[skip..], std::iter::empty())?;
+
         let fn_name = fn_name.as_ref();
         let op_token = Token::lookup_symbol_from_syntax(fn_name);
         let args_len = args.len();
 
-        if native_only {
-            return self
-                .engine()
-                .exec_native_fn_call(
-                    global,
-                    caches,
-                    fn_name,
-                    op_token.as_ref(),
-                    calc_fn_hash(None, fn_name, args_len),
-                    args,
-                    is_ref_mut,
-                    false,
-                    Position::NONE,
-                )
-                .map(|(r, ..)| r);
-        }
-
-        // Native or script
-
-        let hash = match is_method_call {
-            #[cfg(not(feature = "no_function"))]
-            true => FnCallHashes::from_script_and_native(
-                calc_fn_hash(None, fn_name, args_len - 1),
+        let result = if native_only {
+            self.engine().exec_native_fn_call(
+                global,
+                caches,
+                fn_name,
+                op_token.as_ref(),
                 calc_fn_hash(None, fn_name, args_len),
-            ),
-            #[cfg(feature = "no_function")]
-            true => FnCallHashes::from_native_only(calc_fn_hash(None, fn_name, args_len)),
-            _ => FnCallHashes::from_hash(calc_fn_hash(None, fn_name, args_len)),
-        };
+                args,
+                is_ref_mut,
+                false,
+                Position::NONE,
+            )
+        } else {
+            // Native or script
+
+            let hash = match is_method_call {
+                #[cfg(not(feature = "no_function"))]
+                true => FnCallHashes::from_script_and_native(
+                    calc_fn_hash(None, fn_name, args_len - 1),
+                    calc_fn_hash(None, fn_name, args_len),
+                ),
+                #[cfg(feature = "no_function")]
+                true => FnCallHashes::from_native_only(calc_fn_hash(None, fn_name, args_len)),
+                _ => FnCallHashes::from_hash(calc_fn_hash(None, fn_name, args_len)),
+            };
 
-        self.engine()
-            .exec_fn_call(
+            self.engine().exec_fn_call(
                 global,
                 caches,
                 None,
@@ -476,7 +537,17 @@ impl<'a> NativeCallContext<'a> {
                 is_method_call,
                 Position::NONE,
             )
-            .map(|(r, ..)| r)
+        };
+
+        // Charge the operations performed to the calling evaluation run
+        #[cfg(not(feature = "unchecked"))]
+        self.global.add_pending_operations(
+            global
+                .num_operations
+                .saturating_sub(self.global.num_operations),
+        );
+
+        result.map(|(r, ..)| BuilderUpdated::into_unit(r))
     }
 }
 
@@ -526,20 +597,154 @@ pub fn shared_take<T>(value: Shared<T>) -> T {
         .unwrap_or_else(|| panic!("`value` is shared (i.e. has outstanding references)"))
 }
 
+#[cfg(feature = "sync")]
+#[cfg(not(feature = "no_std"))]
+#[cfg(debug_assertions)]
+std::thread_local! {
+    /// Addresses of [`Locked`] resources held by [`LockTracker`]s on the current thread,
+    /// each with whether it is held for write.
+    static HELD_LOCKS: std::cell::RefCell<Vec<(usize, bool)>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Record of a [`Locked`] resource being held by a long-lived lock guard on the current thread,
+/// removed when dropped.
+///
+/// Under the `sync` feature, this lets [`locked_read`] and [`locked_write`] fail immediately,
+/// instead of deadlocking, when the current thread re-enters the [`Engine`] (e.g. via
+/// [`NativeCallContext::call_fn`]) and accesses a resource that it already holds.
+///
+/// Held locks are only recorded in debug builds (i.e. with `debug_assertions`), so that release
+/// builds do not pay for checking them on every lock.
+#[derive(Debug)]
+#[must_use]
+pub struct LockTracker(
+    #[cfg(all(feature = "sync", not(feature = "no_std"), debug_assertions))] (usize, bool),
+);
+
+impl LockTracker {
+    /// Record that a [`Locked`] resource is held by the current thread.
+    #[inline]
+    #[allow(unused_variables)]
+    pub fn new<T: ?Sized>(value: &Locked<T>, is_write: bool) -> Self {
+        #[cfg(all(feature = "sync", not(feature = "no_std"), debug_assertions))]
+        let tracker = {
+            let entry = ((value as *const Locked<T>).cast::<()>() as usize, is_write);
+            let _ = HELD_LOCKS.try_with(|held| held.borrow_mut().push(entry));
+            Self(entry)
+        };
+
+        #[cfg(not(all(feature = "sync", not(feature = "no_std"), debug_assertions)))]
+        let tracker = Self();
+
+        tracker
+    }
+}
+
+impl Drop for LockTracker {
+    #[inline]
+    fn drop(&mut self) {
+        #[cfg(all(feature = "sync", not(feature = "no_std"), debug_assertions))]
+        let _ = HELD_LOCKS.try_with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(n) = held.iter().rposition(|&entry| entry == self.0) {
+                held.remove(n);
+            }
+        });
+    }
+}
+
+/// Is a [`Locked`] resource held by the current thread such that locking it (for write if
+/// `is_write` is `true`, otherwise for read) would fail or deadlock?
+///
+/// Under the `sync` feature, only resources held via a [`LockTracker`] are detected, and only in
+/// debug builds; otherwise this always returns `false`.
+#[inline]
+#[must_use]
+#[allow(unused_variables)]
+pub fn is_locked_by_current_thread<T: ?Sized>(value: &Locked<T>, is_write: bool) -> bool {
+    #[cfg(not(feature = "sync"))]
+    let locked = if is_write {
+        value.try_borrow_mut().is_err()
+    } else {
+        value.try_borrow().is_err()
+    };
+
+    #[cfg(all(feature = "sync", not(feature = "no_std"), debug_assertions))]
+    let locked = {
+        let addr = (value as *const Locked<T>).cast::<()>() as usize;
+
+        HELD_LOCKS
+            .try_with(|held| {
+                held.borrow()
+                    .iter()
+                    .any(|&(a, w)| a == addr && (w || is_write))
+            })
+            .unwrap_or(false)
+    };
+
+    #[cfg(feature = "sync")]
+    #[cfg(not(all(not(feature = "no_std"), debug_assertions)))]
+    let locked = false;
+
+    locked
+}
+
+/// Raise [`ErrorReentrantLock`][ERR::ErrorReentrantLock] if any argument is a _shared_ value
+/// already locked by the current thread, instead of failing (or deadlocking) later on.
+///
+/// `param_names` are the names of the corresponding parameters, if known.
+#[inline]
+pub(crate) fn ensure_no_reentrant_lock<'a>(
+    args: &[&mut Dynamic],
+    param_names: impl IntoIterator<Item = &'a str>,
+) -> RhaiResultOf<()> {
+    #[cfg(not(feature = "no_closure"))]
+    {
+        let mut param_names = param_names.into_iter();
+
+        for arg in args {
+            let name = param_names.next().unwrap_or_default();
+
+            if arg.is_locked() {
+                return Err(ERR::ErrorReentrantLock(name.into(), Position::NONE).into());
+            }
+        }
+    }
+
+    #[cfg(feature = "no_closure")]
+    let _ = (args, param_names);
+
+    Ok(())
+}
+
 /// _(internals)_ Lock a [`Locked`] resource for immutable access.
 /// Exported under the `internals` feature only.
 #[inline(always)]
 #[must_use]
 #[allow(dead_code)]
-pub fn locked_read<T>(value: &Locked<T>) -> Option<LockGuard<T>> {
+pub fn locked_read<T: ?Sized>(value: &Locked<T>) -> Option<LockGuard<'_, T>> {
     #[cfg(not(feature = "sync"))]
     return value.try_borrow().ok();
 
     #[cfg(feature = "sync")]
     #[cfg(not(feature = "no_std"))]
     {
+        // Fail instead of deadlocking when the current thread already holds the lock
+        if is_locked_by_current_thread(value, false) {
+            return None;
+        }
+
         #[cfg(feature = "unchecked")]
+        #[cfg(not(feature = "catch_panics"))]
         return value.read().ok();
+        // Recover from a lock poisoned by a panic in a native function
+        #[cfg(feature = "unchecked")]
+        #[cfg(feature = "catch_panics")]
+        return Some(
+            value
+                .read()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
 
         #[cfg(not(feature = "unchecked"))]
         {
@@ -550,6 +755,9 @@ pub fn locked_read<T>(value: &Locked<T>) -> Option<LockGuard<T>> {
                     Err(std::sync::TryLockError::WouldBlock) => {
                         std::thread::sleep(std::time::Duration::from_millis(10))
                     }
+                    #[cfg(feature = "catch_panics")]
+                    Err(std::sync::TryLockError::Poisoned(err)) => return Some(err.into_inner()),
+                    #[cfg(not(feature = "catch_panics"))]
                     Err(_) => return None,
                 }
             }
@@ -573,15 +781,29 @@ pub fn locked_read<T>(value: &Locked<T>) -> Option<LockGuard<T>> {
 #[inline(always)]
 #[must_use]
 #[allow(dead_code)]
-pub fn locked_write<T>(value: &Locked<T>) -> Option<LockGuardMut<T>> {
+pub fn locked_write<T: ?Sized>(value: &Locked<T>) -> Option<LockGuardMut<'_, T>> {
     #[cfg(not(feature = "sync"))]
     return value.try_borrow_mut().ok();
 
     #[cfg(feature = "sync")]
     #[cfg(not(feature = "no_std"))]
     {
+        // Fail instead of deadlocking when the current thread already holds the lock
+        if is_locked_by_current_thread(value, true) {
+            return None;
+        }
+
         #[cfg(feature = "unchecked")]
+        #[cfg(not(feature = "catch_panics"))]
         return value.write().ok();
+        // Recover from a lock poisoned by a panic in a native function
+        #[cfg(feature = "unchecked")]
+        #[cfg(feature = "catch_panics")]
+        return Some(
+            value
+                .write()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
 
         #[cfg(not(feature = "unchecked"))]
         {
@@ -592,6 +814,9 @@ pub fn locked_write<T>(value: &Locked<T>) -> Option<LockGuardMut<T>> {
                     Err(std::sync::TryLockError::WouldBlock) => {
                         std::thread::sleep(std::time::Duration::from_millis(10))
                     }
+                    #[cfg(feature = "catch_panics")]
+                    Err(std::sync::TryLockError::Poisoned(err)) => return Some(err.into_inner()),
+                    #[cfg(not(feature = "catch_panics"))]
                     Err(_) => return None,
                 }
             }
@@ -641,11 +866,79 @@ pub type FnPlugin = dyn PluginFunc + Send + Sync;
 /// Callback function for progress reporting.
 #[cfg(not(feature = "unchecked"))]
 #[cfg(not(feature = "sync"))]
-pub type OnProgressCallback = dyn Fn(u64) -> Option<Dynamic>;
+pub type OnProgressCallback = dyn Fn(&crate::ProgressInfo) -> crate::ProgressControl;
 /// Callback function for progress reporting.
 #[cfg(not(feature = "unchecked"))]
 #[cfg(feature = "sync")]
-pub type OnProgressCallback = dyn Fn(u64) -> Option<Dynamic> + Send + Sync;
+pub type OnProgressCallback = dyn Fn(&crate::ProgressInfo) -> crate::ProgressControl + Send + Sync;
+
+/// Callback function for warning that the number of operations is close to the limit.
+#[cfg(not(feature = "unchecked"))]
+#[cfg(not(feature = "sync"))]
+pub type OnOperationsWarningCallback = dyn Fn(u64, u64, Position);
+/// Callback function for warning that the number of operations is close to the limit.
+#[cfg(not(feature = "unchecked"))]
+#[cfg(feature = "sync")]
+pub type OnOperationsWarningCallback = dyn Fn(u64, u64, Position) + Send + Sync;
+
+/// Callback function for yielding to a cooperative scheduler.
+#[cfg(not(feature = "unchecked"))]
+#[cfg(not(feature = "sync"))]
+pub type OnTickCallback = dyn Fn(u64);
+/// Callback function for yielding to a cooperative scheduler.
+#[cfg(not(feature = "unchecked"))]
+#[cfg(feature = "sync")]
+pub type OnTickCallback = dyn Fn(u64) + Send + Sync;
+
+/// Callback function for reporting the approximate size of a custom type.
+#[cfg(not(feature = "unchecked"))]
+#[cfg(not(feature = "sync"))]
+pub type OnSizeOfCallback = dyn Fn(&Dynamic) -> usize;
+/// Callback function for reporting the approximate size of a custom type.
+#[cfg(not(feature = "unchecked"))]
+#[cfg(feature = "sync")]
+pub type OnSizeOfCallback = dyn Fn(&Dynamic) -> usize + Send + Sync;
+
+/// Callback function for encoding a custom type into a value made up of standard types.
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "sync"))]
+pub type OnEncodeTypeCallback = dyn Fn(&Dynamic) -> RhaiResult;
+/// Callback function for encoding a custom type into a value made up of standard types.
+#[cfg(not(feature = "no_object"))]
+#[cfg(feature = "sync")]
+pub type OnEncodeTypeCallback = dyn Fn(&Dynamic) -> RhaiResult + Send + Sync;
+
+/// Callback function for decoding a custom type from a value made up of standard types.
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "sync"))]
+pub type OnDecodeTypeCallback = dyn Fn(Dynamic) -> RhaiResult;
+/// Callback function for decoding a custom type from a value made up of standard types.
+#[cfg(not(feature = "no_object"))]
+#[cfg(feature = "sync")]
+pub type OnDecodeTypeCallback = dyn Fn(Dynamic) -> RhaiResult + Send + Sync;
+
+/// A type-erased host service registered via [`Engine::set_service`].
+#[cfg(not(feature = "sync"))]
+pub type Service = dyn Any;
+/// A type-erased host service registered via [`Engine::set_service`].
+#[cfg(feature = "sync")]
+pub type Service = dyn Any + Send + Sync;
+
+/// Type-erased per-run user data passed via [`Engine::run_with_context`].
+#[cfg(not(feature = "sync"))]
+pub type UserData = dyn Any;
+/// Type-erased per-run user data passed via [`Engine::run_with_context`].
+#[cfg(feature = "sync")]
+pub type UserData = dyn Any + Send + Sync;
+
+/// Type-erased host state injected into native functions registered via
+/// [`Engine::register_fn_with_state`].
+#[cfg(not(feature = "sync"))]
+pub type HostState = dyn Any;
+/// Type-erased host state injected into native functions registered via
+/// [`Engine::register_fn_with_state`].
+#[cfg(feature = "sync")]
+pub type HostState = dyn Any + Send + Sync;
 
 /// Callback function for printing.
 #[cfg(not(feature = "sync"))]
@@ -654,6 +947,20 @@ pub type OnPrintCallback = dyn Fn(&str);
 #[cfg(feature = "sync")]
 pub type OnPrintCallback = dyn Fn(&str) + Send + Sync;
 
+/// Callback function for redacting values before output.
+#[cfg(not(feature = "sync"))]
+pub type OnRedactCallback = dyn Fn(Option<&str>, &Dynamic) -> Option<Dynamic>;
+/// Callback function for redacting values before output.
+#[cfg(feature = "sync")]
+pub type OnRedactCallback = dyn Fn(Option<&str>, &Dynamic) -> Option<Dynamic> + Send + Sync;
+
+/// Callback function for recording execution traces.
+#[cfg(not(feature = "sync"))]
+pub type OnTraceCallback = dyn Fn(&crate::TraceEvent);
+/// Callback function for recording execution traces.
+#[cfg(feature = "sync")]
+pub type OnTraceCallback = dyn Fn(&crate::TraceEvent) + Send + Sync;
+
 /// Callback function for debugging.
 #[cfg(not(feature = "sync"))]
 pub type OnDebugCallback = dyn Fn(&str, Option<&str>, Position);
@@ -696,6 +1003,15 @@ pub type OnMissingMapPropertyCallback = dyn for<'a> Fn(&'a mut crate::Map, &str,
     + Send
     + Sync;
 
+/// Callback function when a property accessed is not found in a custom type.
+#[cfg(not(feature = "sync"))]
+#[cfg(not(feature = "no_object"))]
+pub type OnMissingPropertyCallback = dyn Fn(&mut Dynamic, &str) -> RhaiResult;
+/// Callback function when a property accessed is not found in a custom type.
+#[cfg(feature = "sync")]
+#[cfg(not(feature = "no_object"))]
+pub type OnMissingPropertyCallback = dyn Fn(&mut Dynamic, &str) -> RhaiResult + Send + Sync;
+
 /// Callback function for mapping tokens during parsing.
 #[cfg(not(feature = "sync"))]
 pub type OnParseTokenCallback = dyn Fn(Token, Position, &TokenizeState) -> Token;
@@ -703,6 +1019,18 @@ pub type OnParseTokenCallback = dyn Fn(Token, Position, &TokenizeState) -> Token
 #[cfg(feature = "sync")]
 pub type OnParseTokenCallback = dyn Fn(Token, Position, &TokenizeState) -> Token + Send + Sync;
 
+/// Callback function for mapping type names for display.
+#[cfg(not(feature = "sync"))]
+pub type OnMapTypeNameCallback = dyn Fn(&str) -> Option<&str>;
+/// Callback function for mapping type names for display.
+#[cfg(feature = "sync")]
+pub type OnMapTypeNameCallback = dyn Fn(&str) -> Option<&str> + Send + Sync;
+
+/// Callback function for running a [`PendingTask`][crate::PendingTask].
+#[cfg(feature = "parallel")]
+#[cfg(not(feature = "no_std"))]
+pub type OnSpawnTaskCallback = dyn Fn(crate::PendingTask) + Send + Sync;
+
 /// Callback function for variable access.
 #[cfg(not(feature = "sync"))]
 pub type OnVarCallback = dyn Fn(&str, usize, EvalContext) -> RhaiResultOf<Option<Dynamic>>;
@@ -53,3 +53,92 @@ pub trait PluginFunc {
         true
     }
 }
+
+/// Lazily-initialized storage for a _plugin module_ generated by `exported_static_module!`.
+///
+/// This type should not be used directly.
+#[doc(hidden)]
+pub struct StaticModule {
+    #[cfg(all(feature = "sync", not(feature = "no_std")))]
+    cell: once_cell::sync::OnceCell<crate::Shared<Module>>,
+    #[cfg(all(feature = "sync", feature = "no_std"))]
+    cell: once_cell::race::OnceBox<crate::Shared<Module>>,
+    #[cfg(not(feature = "sync"))]
+    cell: once_cell::unsync::OnceCell<crate::Shared<Module>>,
+}
+
+impl StaticModule {
+    /// Create a new, uninitialized [`StaticModule`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            #[cfg(all(feature = "sync", not(feature = "no_std")))]
+            cell: once_cell::sync::OnceCell::new(),
+            #[cfg(all(feature = "sync", feature = "no_std"))]
+            cell: once_cell::race::OnceBox::new(),
+            #[cfg(not(feature = "sync"))]
+            cell: once_cell::unsync::OnceCell::new(),
+        }
+    }
+    /// Get the shared module, generating it via `generate` on first access.
+    #[inline]
+    #[must_use]
+    pub fn get_or_init(&self, generate: impl FnOnce() -> Module) -> crate::Shared<Module> {
+        #[cfg(all(feature = "sync", feature = "no_std"))]
+        return self
+            .cell
+            .get_or_init(|| Box::new(generate().into()))
+            .clone();
+
+        #[cfg(not(all(feature = "sync", feature = "no_std")))]
+        return self.cell.get_or_init(|| generate().into()).clone();
+    }
+}
+
+impl Default for StaticModule {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cache a generated _plugin module_ in a `static` (under `sync`).
+///
+/// This macro should not be used directly. Use `exported_static_module!` instead.
+#[cfg(feature = "sync")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __exported_static_module {
+    ($generate:expr) => {{
+        static MODULE: $crate::plugin::StaticModule = $crate::plugin::StaticModule::new();
+        MODULE.get_or_init(|| $generate)
+    }};
+}
+
+/// Cache a generated _plugin module_ in a thread-local (without `sync`).
+///
+/// This macro should not be used directly. Use `exported_static_module!` instead.
+#[cfg(all(not(feature = "sync"), not(feature = "no_std")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __exported_static_module {
+    ($generate:expr) => {{
+        ::std::thread_local! {
+            static MODULE: $crate::plugin::StaticModule = $crate::plugin::StaticModule::new();
+        }
+        MODULE.with(|m| m.get_or_init(|| $generate))
+    }};
+}
+
+/// No caching is possible under `no_std` without `sync`, so simply generate the module.
+///
+/// This macro should not be used directly. Use `exported_static_module!` instead.
+#[cfg(all(not(feature = "sync"), feature = "no_std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __exported_static_module {
+    ($generate:expr) => {{
+        $crate::Shared::<$crate::Module>::new($generate)
+    }};
+}
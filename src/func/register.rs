@@ -14,6 +14,7 @@ use crate::{Dynamic, Identifier, NativeCallContext, RhaiResultOf};
 use std::prelude::v1::*;
 use std::{
     any::{type_name, TypeId},
+    marker::PhantomData,
     mem,
 };
 
@@ -69,6 +70,23 @@ pub fn by_value<T: Variant + Clone>(data: &mut Dynamic) -> T {
     data.take().cast::<T>()
 }
 
+/// Wrapper of a native Rust function taking host state of type `S` as its first parameter.
+///
+/// The host state is set via [`Scope::set_host_state`][crate::Scope::set_host_state] and is
+/// _not_ a parameter of the function in scripts.
+///
+/// Register such functions via [`Engine::register_fn_with_state`][crate::Engine::register_fn_with_state].
+pub struct WithHostState<S, FN>(FN, PhantomData<fn(&mut S)>);
+
+impl<S, FN> WithHostState<S, FN> {
+    /// Wrap a native Rust function taking host state of type `S` as its first parameter.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(func: FN) -> Self {
+        Self(func, PhantomData)
+    }
+}
+
 /// Trait to register custom Rust functions.
 ///
 /// # Type Parameters
@@ -225,6 +243,51 @@ macro_rules! def_register {
             }
         }
 
+        impl<
+            STATE: 'static,
+            FN: Fn(&mut STATE, $($param),*) -> RET + SendSync + 'static,
+            $($par: Variant + Clone,)*
+            RET: Variant + Clone,
+        > RhaiNativeFunc<($($mark,)*), $n, true, RET, false> for WithHostState<STATE, FN> {
+            #[inline(always)] fn param_types() -> [TypeId;$n] { [$(TypeId::of::<$par>()),*] }
+            #[cfg(feature = "metadata")] #[inline(always)] fn param_names() -> [&'static str;$n] { [$(type_name::<$param>()),*] }
+            #[inline(always)] fn into_rhai_function(self, is_pure: bool, is_volatile: bool) -> RhaiFunc {
+                RhaiFunc::$abi { func: Shared::new(move |ctx: Option<NativeCallContext>, args: &mut FnCallArgs| {
+                    let ctx = ctx.unwrap();
+
+                    // The arguments are assumed to be of the correct number and types!
+                    let mut drain = args.iter_mut();
+                    $(let mut $par = $clone(drain.next().unwrap()); )*
+
+                    // Call the function with the host state and each argument value
+                    ctx.with_host_state(|state: &mut STATE| (self.0)(state, $($arg),*)).map(Dynamic::from)
+                }), has_context: true, is_pure, is_volatile }
+            }
+        }
+
+        impl<
+            STATE: 'static,
+            FN: Fn(&mut STATE, $($param),*) -> RhaiResultOf<RET> + SendSync + 'static,
+            $($par: Variant + Clone,)*
+            RET: Variant + Clone
+        > RhaiNativeFunc<($($mark,)*), $n, true, RET, true> for WithHostState<STATE, FN> {
+            #[inline(always)] fn param_types() -> [TypeId;$n] { [$(TypeId::of::<$par>()),*] }
+            #[cfg(feature = "metadata")] #[inline(always)] fn param_names() -> [&'static str;$n] { [$(type_name::<$param>()),*] }
+            #[cfg(feature = "metadata")] #[inline(always)] fn return_type_name() -> &'static str { type_name::<RhaiResultOf<RET>>() }
+            #[inline(always)] fn into_rhai_function(self, is_pure: bool, is_volatile: bool) -> RhaiFunc {
+                RhaiFunc::$abi { func: Shared::new(move |ctx: Option<NativeCallContext>, args: &mut FnCallArgs| {
+                    let ctx = ctx.unwrap();
+
+                    // The arguments are assumed to be of the correct number and types!
+                    let mut drain = args.iter_mut();
+                    $(let mut $par = $clone(drain.next().unwrap()); )*
+
+                    // Call the function with the host state and each argument value
+                    ctx.with_host_state(|state: &mut STATE| (self.0)(state, $($arg),*))?.map(Dynamic::from)
+                }), has_context: true, is_pure, is_volatile }
+            }
+        }
+
         //def_register!(imp_pop $($par => $mark => $param),*);
     };
     ($p0:ident:$n0:expr $(, $p:ident: $n:expr)*) => {
@@ -33,7 +33,7 @@ impl Engine {
         rewind_scope: bool,
         pos: Position,
     ) -> RhaiResult {
-        debug_assert_eq!(fn_def.params.len(), args.len());
+        debug_assert!(fn_def.accepts_num_args(args.len()));
 
         self.track_operation(global, pos)?;
 
@@ -43,6 +43,11 @@ impl Engine {
             return Err(ERR::ErrorStackOverflow(pos).into());
         }
 
+        self.track_trace(|| {
+            let args = args.iter().map(|v| crate::TraceEvent::digest(v)).collect();
+            crate::TraceEvent::Call(fn_def.name.clone(), args, pos)
+        });
+
         #[cfg(feature = "debugging")]
         if self.debugger_interface.is_none() && fn_def.body.is_empty() {
             return Ok(Dynamic::UNIT);
@@ -69,6 +74,19 @@ impl Engine {
             return Err(ERR::ErrorTooManyVariables(pos).into());
         }
 
+        // Split off the arguments collected by the rest parameter, if any
+        let num_fixed_params = fn_def.params.len() - usize::from(fn_def.is_variadic);
+        let (args, _rest_args) = args.split_at_mut(args.len().min(num_fixed_params));
+        let num_args = args.len();
+
+        #[cfg(not(feature = "no_index"))]
+        let rest_array = fn_def.is_variadic.then(|| {
+            _rest_args
+                .iter_mut()
+                .map(|v| v.take())
+                .collect::<crate::Array>()
+        });
+
         // Put arguments into scope as variables
         scope.extend(fn_def.params.iter().cloned().zip(args.iter_mut().map(|v| {
             // Actually consume the arguments instead of cloning them
@@ -117,16 +135,40 @@ impl Engine {
             self.dbg(global, caches, scope, this_ptr.as_deref_mut(), &node)?;
         }
 
-        // Evaluate the function
-        let mut _result: RhaiResult = self
-            .eval_stmt_block(
-                global,
-                caches,
-                scope,
-                this_ptr.as_deref_mut(),
-                fn_def.body.statements(),
-                rewind_scope,
-            )
+        self.push_sample_frame(&fn_def.name, pos);
+
+        #[cfg(not(feature = "unchecked"))]
+        let orig_op_profile_fn = self.swap_op_profile_fn(global, Some(fn_def.name.clone()));
+
+        // Evaluate the default values of parameters not passed, in order
+        let defaults = &fn_def.param_defaults[num_args - fn_def.min_num_args()..];
+
+        let mut _result: RhaiResult = defaults
+            .iter()
+            .zip(&fn_def.params[num_args..])
+            .try_for_each(|(expr, param)| {
+                let value = self.eval_expr(global, caches, scope, this_ptr.as_deref_mut(), expr)?;
+                scope.push_dynamic(param.clone(), value.flatten());
+                Ok(())
+            })
+            .and_then(|()| {
+                // Collect all remaining arguments into the rest parameter
+                #[cfg(not(feature = "no_index"))]
+                if let Some(rest_array) = rest_array {
+                    let param = fn_def.params.last().unwrap().clone();
+                    scope.push_dynamic(param, rest_array.into());
+                }
+
+                // Evaluate the function
+                self.eval_stmt_block(
+                    global,
+                    caches,
+                    scope,
+                    this_ptr.as_deref_mut(),
+                    fn_def.body.statements(),
+                    rewind_scope,
+                )
+            })
             .or_else(|err| match *err {
                 // Convert return statement to return value
                 ERR::Return(x, ..) => Ok(x),
@@ -156,11 +198,22 @@ impl Engine {
                 .into()),
             });
 
+        self.pop_sample_frame();
+
+        #[cfg(not(feature = "unchecked"))]
+        self.swap_op_profile_fn(global, orig_op_profile_fn);
+
         #[cfg(feature = "debugging")]
         if self.is_debugger_registered() {
+            let level = global.level;
             let trigger = match global.debugger_mut().status {
-                crate::eval::DebuggerStatus::FunctionExit(n) => n >= global.level,
+                crate::eval::DebuggerStatus::FunctionExit(n) => n >= level,
                 crate::eval::DebuggerStatus::Next(.., true) => true,
+                crate::eval::DebuggerStatus::StepOut(n) if n >= level => {
+                    // Stop at the next step in the caller
+                    global.debugger_mut().status = crate::eval::DebuggerStatus::STEP;
+                    false
+                }
                 _ => false,
             };
 
@@ -188,9 +241,10 @@ impl Engine {
         // Remove all local variables and imported modules
         if rewind_scope {
             scope.rewind(orig_scope_len);
-        } else if !args.is_empty() {
+        } else if !fn_def.params.is_empty() {
             // Remove arguments only, leaving new variables in the scope
-            scope.remove_range(orig_scope_len, args.len());
+            let num_params = fn_def.params.len().min(scope.len() - orig_scope_len);
+            scope.remove_range(orig_scope_len, num_params);
         }
         global.lib.truncate(orig_lib_len);
         #[cfg(not(feature = "no_module"))]
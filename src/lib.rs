@@ -93,7 +93,6 @@
 #![allow(clippy::no_effect_underscore_binding)] // Underscored variables may be used by code within feature guards
 #![allow(clippy::semicolon_if_nothing_returned)] // One-liner `match` cases are sometimes formatted as multi-line blocks
 
-#[cfg(feature = "no_std")]
 extern crate alloc;
 
 #[cfg(feature = "no_std")]
@@ -226,13 +225,51 @@ use once_cell::sync::OnceCell;
 #[cfg(not(feature = "std"))]
 use once_cell::race::OnceBox as OnceCell;
 
+pub use api::allow_list::FnAllowList;
 pub use api::build_type::{CustomType, TypeBuilder};
+pub use api::capabilities::{EngineCapabilities, GlobalModuleInfo};
+#[cfg(not(feature = "no_position"))]
+#[cfg(not(feature = "no_std"))]
+#[cfg(any(not(target_family = "wasm"), not(target_os = "unknown")))]
+pub use api::codemod::CodemodFile;
+#[cfg(not(feature = "no_position"))]
+pub use api::codemod::{Codemod, CodemodChange};
+pub use api::compile::BatchCompilation;
+#[cfg(feature = "metadata")]
+#[cfg(not(feature = "no_position"))]
+pub use api::completion::Completions;
+#[cfg(not(feature = "no_position"))]
+pub use api::coverage::Coverage;
 #[cfg(not(feature = "no_custom_syntax"))]
 pub use api::custom_syntax::Expression;
 #[cfg(not(feature = "no_std"))]
 #[cfg(any(not(target_family = "wasm"), not(target_os = "unknown")))]
 pub use api::files::{eval_file, run_file};
-pub use api::{eval::eval, run::run};
+#[cfg(not(feature = "no_position"))]
+pub use api::highlight::{ClassifiedToken, TokenCategory};
+#[cfg(feature = "metadata")]
+#[cfg(not(feature = "no_position"))]
+pub use api::infer::InferredType;
+#[cfg(not(feature = "no_module"))]
+pub use api::module_graph::ModuleGraph;
+#[cfg(not(feature = "no_function"))]
+pub use api::module_tests::ModuleTestFailure;
+#[cfg(not(feature = "unchecked"))]
+pub use api::op_profile::OperationsProfile;
+#[cfg(not(feature = "unchecked"))]
+pub use api::progress::{ProgressControl, ProgressInfo};
+pub use api::sampling::{SampledFrame, StackSampler};
+#[cfg(feature = "metadata")]
+#[cfg(not(feature = "no_position"))]
+pub use api::signature_help::{SignatureHelp, SignatureInfo};
+#[cfg(feature = "signing")]
+pub use api::signing::ScriptSigner;
+pub use api::trace::{Trace, TraceEvent, TraceReplay};
+pub use api::{
+    eval::{eval, EvalOutput},
+    minify::minify,
+    run::run,
+};
 pub use ast::{FnAccess, AST};
 use defer::Deferred;
 pub use engine::{Engine, OP_CONTAINS, OP_EQUALS};
@@ -240,25 +277,44 @@ pub use eval::EvalContext;
 #[cfg(not(feature = "no_function"))]
 #[cfg(not(feature = "no_object"))]
 use func::calc_typed_method_hash;
+#[cfg(not(feature = "no_function"))]
+use func::{calc_arity_range_hash, calc_typed_params_hash};
 use func::{calc_fn_hash, calc_fn_hash_full, calc_var_hash};
-pub use func::{plugin, FuncArgs, NativeCallContext, RhaiNativeFunc};
-pub use module::{FnNamespace, FuncRegistration, Module};
+pub use func::{plugin, ExprFnArgs, FuncArgs, NativeCallContext, RhaiNativeFunc, WithHostState};
+#[cfg(not(feature = "unchecked"))]
+pub use module::ModuleLimits;
+pub use module::{FnNamespace, FuncMetadata, FuncRegistration, Module};
 pub use packages::string_basic::{FUNC_TO_DEBUG, FUNC_TO_STRING};
 pub use rhai_codegen::*;
+#[cfg(not(feature = "no_float"))]
+pub use types::FloatFormat;
 #[cfg(not(feature = "no_time"))]
 pub use types::Instant;
+#[cfg(not(feature = "no_object"))]
+pub use types::Prototype;
+#[cfg(not(feature = "no_std"))]
+pub use types::StreamReader;
 pub use types::{
-    Dynamic, EvalAltResult, FnPtr, ImmutableString, LexError, ParseError, ParseErrorType, Position,
-    Scope, VarDefInfo,
+    Dynamic, DynamicRef, DynamicRefMut, EvalAltResult, FnPtr, Handle, ImmutableString,
+    InternerStats, LexError, ParseError, ParseErrorType, Position, PrettyPrintOptions, Scope,
+    SharedStrInterner, StrInterner, VarDefInfo,
 };
+#[cfg(feature = "parallel")]
+#[cfg(not(feature = "no_std"))]
+pub use types::{PendingTask, Task};
 
 /// _(debugging)_ Module containing types for debugging.
 /// Exported under the `debugging` feature only.
 #[cfg(feature = "debugging")]
 pub mod debugger {
+    #[cfg(feature = "dap")]
+    #[cfg(not(feature = "no_std"))]
+    pub use super::api::dap::{DapStream, DapTransport};
     #[cfg(not(feature = "no_function"))]
     pub use super::eval::CallStackFrame;
-    pub use super::eval::{BreakPoint, Debugger, DebuggerCommand, DebuggerEvent};
+    pub use super::eval::{
+        BreakPoint, Debugger, DebuggerCommand, DebuggerEvent, InlineValue, Watch,
+    };
 }
 
 /// _(internals)_ An identifier in Rhai.
@@ -286,6 +342,9 @@ pub use func::Func;
 #[cfg(not(feature = "no_function"))]
 pub use ast::ScriptFnMetadata;
 
+#[cfg(not(feature = "no_function"))]
+pub use ast::{CallGraph, CallGraphEdge, CallGraphNode};
+
 #[cfg(not(feature = "no_function"))]
 pub use api::call_fn::CallFnOptions;
 
@@ -301,6 +360,15 @@ pub type Array = Vec<Dynamic>;
 #[cfg(not(feature = "no_index"))]
 pub type Blob = Vec<u8>;
 
+/// Shared, immutable byte array.
+///
+/// Cloning a [`SharedBlob`] does not copy its bytes, so it can be used to pass large binary
+/// buffers into scripts cheaply.
+///
+/// Not available under `no_index`.
+#[cfg(not(feature = "no_index"))]
+pub type SharedBlob = Shared<Blob>;
+
 /// A dictionary of [`Dynamic`] values with string keys.
 ///
 /// Not available under `no_object`.
@@ -321,7 +389,10 @@ pub use module::ModuleResolver;
 pub use module::resolvers as module_resolvers;
 
 #[cfg(not(feature = "no_optimize"))]
-pub use optimizer::OptimizationLevel;
+pub use optimizer::{DefaultOptimizerPass, OptimizationLevel, OptimizerOptions, OptimizerPass};
+
+#[cfg(not(feature = "unchecked"))]
+pub use api::arithmetic::ArithmeticMode;
 
 // Expose internal data structures.
 
@@ -329,14 +400,14 @@ pub use optimizer::OptimizationLevel;
 pub use types::dynamic::{AccessMode, DynamicReadLock, DynamicWriteLock, Variant};
 
 #[cfg(feature = "internals")]
-pub use module::{FuncInfo, FuncMetadata};
+pub use module::FuncInfo;
 
 #[cfg(feature = "internals")]
 #[cfg(not(feature = "no_float"))]
 pub use types::FloatWrapper;
 
 #[cfg(feature = "internals")]
-pub use types::{BloomFilterU64, CustomTypeInfo, Span, StringsInterner};
+pub use types::{BloomFilterU64, CustomTypeInfo, HandleRegistry, Span, StringsInterner, UndoLog};
 
 #[cfg(feature = "internals")]
 pub use tokenizer::{
@@ -354,7 +425,7 @@ pub use api::default_limits;
 #[cfg(feature = "internals")]
 pub use ast::{
     ASTFlags, ASTNode, BinaryExpr, EncapsulatedEnviron, Expr, FlowControl, FnCallExpr,
-    FnCallHashes, Ident, OpAssignment, RangeCase, ScriptFuncDef, Stmt, StmtBlock,
+    FnCallHashes, Ident, OpAssignment, Pattern, RangeCase, ScriptFuncDef, Stmt, StmtBlock,
     SwitchCasesCollection,
 };
 
@@ -362,6 +433,14 @@ pub use ast::{
 #[cfg(not(feature = "no_custom_syntax"))]
 pub use ast::CustomExpr;
 
+/// _(internals)_ Module containing traits to visit and rewrite an [`AST`].
+/// Exported under the `internals` feature only.
+#[cfg(feature = "internals")]
+pub mod visit {
+    pub use super::ast::visit::{walk_expr, walk_expr_mut, walk_stmt, walk_stmt_mut};
+    pub use super::ast::{Visitor, VisitorMut};
+}
+
 #[cfg(feature = "internals")]
 #[cfg(not(feature = "no_module"))]
 pub use ast::Namespace;
@@ -377,6 +456,9 @@ pub use func::{locked_read, locked_write, NativeCallContextStore, RhaiFunc};
 #[cfg(feature = "metadata")]
 pub use api::definitions::Definitions;
 
+#[cfg(feature = "metadata")]
+pub use serde::metadata::ApiChange;
+
 /// Number of items to keep inline for [`StaticVec`].
 const STATIC_VEC_INLINE_SIZE: usize = 3;
 
@@ -477,6 +559,14 @@ compile_error!("`wasm-bindgen` cannot be used with `no-std`");
 #[cfg(feature = "stdweb")]
 compile_error!("`stdweb` cannot be used with `no-std`");
 
+#[cfg(feature = "no_std")]
+#[cfg(feature = "catch_panics")]
+compile_error!("`catch_panics` cannot be used with `no-std`");
+
+#[cfg(feature = "no_std")]
+#[cfg(feature = "stack_guard")]
+compile_error!("`stack_guard` cannot be used with `no-std`");
+
 #[cfg(target_family = "wasm")]
 #[cfg(feature = "no_std")]
 compile_error!("`no_std` cannot be used for WASM target");
@@ -69,8 +69,7 @@ impl FnNamespace {
     }
 }
 
-/// _(internals)_ A type containing the metadata of a single registered function.
-/// Exported under the `internals` features only.
+/// A type containing the metadata of a single registered function.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[non_exhaustive]
 pub struct FuncMetadata {
@@ -113,38 +112,7 @@ impl FuncMetadata {
 
         let return_type = format_param_type_for_display(&self.return_type, true);
 
-        if self.params_info.is_empty() {
-            for x in 0..self.num_params {
-                signature += "_";
-                if x < self.num_params - 1 {
-                    signature += ", ";
-                }
-            }
-        } else {
-            let params = self
-                .params_info
-                .iter()
-                .map(|param| {
-                    let mut segment = param.splitn(2, ':');
-                    let name = match segment.next().unwrap().trim() {
-                        "" => "_",
-                        s => s,
-                    };
-                    let result: std::borrow::Cow<_> = segment.next().map_or_else(
-                        || name.into(),
-                        |typ| {
-                            format!(
-                                "{name}: {}",
-                                format_param_type_for_display(&type_mapper(typ), false)
-                            )
-                            .into()
-                        },
-                    );
-                    result
-                })
-                .collect::<crate::FnArgsVec<_>>();
-            signature += &params.join(", ");
-        }
+        signature += &self.gen_param_signatures(type_mapper).join(", ");
         signature += ")";
 
         if !return_type.is_empty() {
@@ -154,6 +122,39 @@ impl FuncMetadata {
 
         signature
     }
+    /// Generate signatures of the parameters of the function (e.g. `x: i64`), with `_` for each
+    /// parameter without information.
+    #[cfg(feature = "metadata")]
+    #[must_use]
+    pub(crate) fn gen_param_signatures<'a>(
+        &'a self,
+        type_mapper: impl Fn(&'a str) -> std::borrow::Cow<'a, str>,
+    ) -> crate::FnArgsVec<std::borrow::Cow<'a, str>> {
+        if self.params_info.is_empty() {
+            return (0..self.num_params).map(|_| "_".into()).collect();
+        }
+
+        self.params_info
+            .iter()
+            .map(|param| {
+                let mut segment = param.splitn(2, ':');
+                let name = match segment.next().unwrap().trim() {
+                    "" => "_",
+                    s => s,
+                };
+                segment.next().map_or_else(
+                    || name.into(),
+                    |typ| {
+                        format!(
+                            "{name}: {}",
+                            format_param_type_for_display(&type_mapper(typ), false)
+                        )
+                        .into()
+                    },
+                )
+            })
+            .collect()
+    }
 }
 
 /// Information about a function, native or scripted.
@@ -205,6 +206,8 @@ pub struct FuncRegistration {
     purity: Option<bool>,
     /// Is the function volatile?
     volatility: Option<bool>,
+    /// Additional names of the function.
+    aliases: crate::StaticVec<Identifier>,
 }
 
 impl FuncRegistration {
@@ -253,6 +256,7 @@ impl FuncRegistration {
             },
             purity: None,
             volatility: None,
+            aliases: crate::StaticVec::new_const(),
         }
     }
     /// Create a new [`FuncRegistration`] for a property getter.
@@ -367,6 +371,37 @@ impl FuncRegistration {
         self.volatility = Some(volatile);
         self
     }
+    /// Add additional names to register the function under.
+    ///
+    /// The function is created only once and shared by all its names, each of which gets its own
+    /// copy of the function's metadata.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Module, FuncRegistration, INT};
+    ///
+    /// let mut module = Module::new();
+    ///
+    /// FuncRegistration::new("add")
+    ///     .with_aliases(["plus", "sum"])
+    ///     .in_global_namespace()
+    ///     .set_into_module(&mut module, |x: INT, y: INT| x + y);
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.register_global_module(module.into());
+    ///
+    /// assert_eq!(engine.eval::<INT>("add(1, 2) + plus(3, 4) + sum(5, 6)")?, 21);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_aliases<S: Into<Identifier>>(
+        mut self,
+        aliases: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.aliases.extend(aliases.into_iter().map(Into::into));
+        self
+    }
     /// _(metadata)_ Set the function's parameter names and/or types.
     /// Exported under the `metadata` feature only.
     ///
@@ -411,6 +446,11 @@ impl FuncRegistration {
         self
     }
     /// Register the function into the specified [`Engine`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function is _volatile_ and the [`Engine`] is in
+    /// [deterministic mode][Engine::set_deterministic].
     #[inline]
     pub fn register_into_engine<A: 'static, const N: usize, const X: bool, R, const F: bool, FUNC>(
         self,
@@ -421,6 +461,12 @@ impl FuncRegistration {
         R: Variant + Clone,
         FUNC: RhaiNativeFunc<A, N, X, R, F> + SendSync + 'static,
     {
+        assert!(
+            !engine.deterministic() || self.volatility != Some(true),
+            "cannot register volatile function '{}' in deterministic mode",
+            self.metadata.name
+        );
+
         #[cfg(feature = "metadata")]
         {
             // Do not update parameter information if `with_params_info` was called previously.
@@ -517,14 +563,16 @@ impl FuncRegistration {
         debug_assert!(self.volatility.is_none());
 
         let mut f = self.metadata;
+        let aliases = self.aliases;
 
         f.num_params = arg_types.as_ref().len();
         f.param_types.extend(arg_types.as_ref().iter().copied());
 
         #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
-        if (f.name == crate::engine::FN_IDX_GET && f.num_params == 2)
-            || (f.name == crate::engine::FN_IDX_SET && f.num_params == 3)
-        {
+        if std::iter::once(&f.name).chain(aliases.iter()).any(|name| {
+            (name == crate::engine::FN_IDX_GET && f.num_params == 2)
+                || (name == crate::engine::FN_IDX_SET && f.num_params == 3)
+        }) {
             if let Some(&type_id) = f.param_types.first() {
                 #[cfg(not(feature = "no_index"))]
                 assert!(
@@ -569,40 +617,84 @@ impl FuncRegistration {
             f.return_type = f.params_info.pop().unwrap();
         }
 
-        let hash_base = calc_fn_hash(None, &f.name, f.param_types.len());
-        let hash_fn = calc_fn_hash_full(hash_base, f.param_types.iter().copied());
-        f.hash = hash_fn;
-
-        // Catch hash collisions in testing environment only.
-        #[cfg(feature = "testing-environ")]
-        if let Some(fx) = module.functions.as_ref().and_then(|f| f.get(&hash_base)) {
-            unreachable!(
-                "Hash {} already exists when registering function {}:\n{:#?}",
-                hash_base, f.name, fx
-            );
-        }
-
-        if is_dynamic {
-            module.dynamic_functions_filter.mark(hash_base);
-        }
-
         module
             .flags
             .remove(ModuleFlags::INDEXED | ModuleFlags::INDEXED_GLOBAL_FUNCTIONS);
 
-        let entry = match module
-            .functions
-            .get_or_insert_with(|| new_hash_map(FN_MAP_SIZE))
-            .entry(hash_fn)
-        {
-            Entry::Occupied(mut entry) => {
-                entry.insert((func, f.into()));
-                entry.into_mut()
-            }
-            Entry::Vacant(entry) => entry.insert((func, f.into())),
-        };
+        // All aliases share the same function
+        for alias in aliases {
+            let mut fa = f.clone();
+            fa.name = alias;
+            module.insert_fn_with_metadata(fa, func.clone(), is_dynamic);
+        }
 
-        &entry.1
+        module.insert_fn_with_metadata(f, func, is_dynamic)
+    }
+}
+
+/// Resource limits applied when running functions of a [`Module`] via qualified calls
+/// (e.g. `plugin::foo()`).
+///
+/// Not available under `unchecked`.
+///
+/// These limits are in addition to the limits of the [`Engine`][crate::Engine], and are useful
+/// for running untrusted modules under tighter budgets than the host script.
+#[cfg(not(feature = "unchecked"))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ModuleLimits {
+    /// Maximum number of operations allowed for each function call.
+    max_operations: Option<std::num::NonZeroU64>,
+    /// Maximum duration allowed for each function call.
+    #[cfg(not(feature = "no_time"))]
+    max_duration: Option<std::time::Duration>,
+}
+
+#[cfg(not(feature = "unchecked"))]
+impl ModuleLimits {
+    /// Create a new [`ModuleLimits`] with no limits.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_operations: None,
+            #[cfg(not(feature = "no_time"))]
+            max_duration: None,
+        }
+    }
+    /// Set the maximum number of operations allowed for each function call (0 for unlimited).
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_max_operations(mut self, operations: u64) -> Self {
+        self.max_operations = std::num::NonZeroU64::new(operations);
+        self
+    }
+    /// The maximum number of operations allowed for each function call (0 for unlimited).
+    #[inline]
+    #[must_use]
+    pub const fn max_operations(&self) -> u64 {
+        match self.max_operations {
+            Some(n) => n.get(),
+            None => 0,
+        }
+    }
+    /// Set the maximum duration allowed for each function call.
+    ///
+    /// Not available under `no_time`.
+    #[cfg(not(feature = "no_time"))]
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_max_duration(mut self, duration: std::time::Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+    /// The maximum duration allowed for each function call, if any.
+    ///
+    /// Not available under `no_time`.
+    #[cfg(not(feature = "no_time"))]
+    #[inline(always)]
+    #[must_use]
+    pub const fn max_duration(&self) -> Option<std::time::Duration> {
+        self.max_duration
     }
 }
 
@@ -645,12 +737,21 @@ pub struct Module {
     all_functions: Option<StraightHashMap<RhaiFunc>>,
     /// Bloom filter on native Rust functions (in scripted hash format) that contain [`Dynamic`] parameters.
     dynamic_functions_filter: BloomFilterU64,
+    /// Bloom filter on script-defined functions (in un-typed scripted hash format) that have typed
+    /// parameters.
+    #[cfg(not(feature = "no_function"))]
+    typed_functions_filter: BloomFilterU64,
     /// Iterator functions, keyed by the type producing the iterator.
     type_iterators: BTreeMap<TypeId, Shared<FnIterator>>,
     /// Flattened collection of iterator functions, including those in sub-modules.
     all_type_iterators: BTreeMap<TypeId, Shared<FnIterator>>,
     /// Flags.
     flags: ModuleFlags,
+    /// Resource limits for functions called via this [`Module`].
+    #[cfg(not(feature = "unchecked"))]
+    limits: Option<ModuleLimits>,
+    /// Embedded script tests, keyed by the name of the function tested.
+    tests: Vec<(Identifier, SmartString)>,
 }
 
 impl Default for Module {
@@ -693,7 +794,11 @@ impl fmt::Debug for Module {
                     })
                     .collect::<Vec<_>>(),
             )
-            .field("flags", &self.flags);
+            .field("flags", &self.flags)
+            .field("tests", &self.tests.len());
+
+        #[cfg(not(feature = "unchecked"))]
+        d.field("limits", &self.limits);
 
         #[cfg(feature = "metadata")]
         d.field("doc", &self.doc);
@@ -780,9 +885,14 @@ impl Module {
             functions: None,
             all_functions: None,
             dynamic_functions_filter: BloomFilterU64::new(),
+            #[cfg(not(feature = "no_function"))]
+            typed_functions_filter: BloomFilterU64::new(),
             type_iterators: BTreeMap::new(),
             all_type_iterators: BTreeMap::new(),
             flags: ModuleFlags::INDEXED,
+            #[cfg(not(feature = "unchecked"))]
+            limits: None,
+            tests: Vec::new(),
         }
     }
 
@@ -915,10 +1025,13 @@ impl Module {
         self.functions = None;
         self.all_functions = None;
         self.dynamic_functions_filter.clear();
+        #[cfg(not(feature = "no_function"))]
+        self.typed_functions_filter.clear();
         self.type_iterators.clear();
         self.all_type_iterators.clear();
         self.flags
             .remove(ModuleFlags::INDEXED | ModuleFlags::INDEXED_GLOBAL_FUNCTIONS);
+        self.tests.clear();
     }
 
     /// Map a custom type to a friendly display name.
@@ -1154,6 +1267,96 @@ impl Module {
         self.flags.set(ModuleFlags::STANDARD_LIB, value)
     }
 
+    /// Get the resource limits applied when running functions of the [`Module`] via qualified
+    /// calls, if any.
+    ///
+    /// Not available under `unchecked`.
+    #[cfg(not(feature = "unchecked"))]
+    #[inline(always)]
+    #[must_use]
+    pub const fn resource_limits(&self) -> Option<&ModuleLimits> {
+        self.limits.as_ref()
+    }
+    /// Set the resource limits applied when running functions of the [`Module`] via qualified
+    /// calls (e.g. `plugin::foo()`).
+    ///
+    /// Not available under `unchecked`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # #[cfg(not(feature = "no_function"))]
+    /// # #[cfg(not(feature = "no_module"))]
+    /// # {
+    /// use rhai::{Engine, EvalAltResult, Module, ModuleLimits};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let ast = engine.compile("fn spin() { loop {} }")?;
+    /// let mut module = Module::eval_ast_as_new(Default::default(), &ast, &engine)?;
+    /// module.set_resource_limits(ModuleLimits::new().with_max_operations(1000));
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.register_static_module("untrusted", module.into());
+    ///
+    /// let err = engine.run("untrusted::spin()").unwrap_err();
+    /// assert!(matches!(*err, EvalAltResult::ErrorTooManyOperations(..)));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "unchecked"))]
+    #[inline(always)]
+    pub fn set_resource_limits(&mut self, limits: ModuleLimits) -> &mut Self {
+        self.limits = Some(limits);
+        self
+    }
+    /// Clear the resource limits of the [`Module`].
+    ///
+    /// Not available under `unchecked`.
+    #[cfg(not(feature = "unchecked"))]
+    #[inline(always)]
+    pub fn clear_resource_limits(&mut self) -> &mut Self {
+        self.limits = None;
+        self
+    }
+
+    /// Add an embedded script test for a function in the [`Module`].
+    ///
+    /// The test script is run against the [`Module`] by [`Engine::run_module_tests`], and
+    /// passes if it evaluates without error.
+    ///
+    /// Plugin modules generated by `#[export_module]` add the tests of functions marked with
+    /// `#[rhai_fn(test = "...")]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rhai::Module;
+    /// let mut module = Module::new();
+    /// module.set_native_fn("double", |x: i64| Ok(x * 2));
+    /// module.add_test("double", "assert(double(21) == 42);");
+    /// assert_eq!(module.iter_tests().count(), 1);
+    /// ```
+    #[inline]
+    pub fn add_test(
+        &mut self,
+        fn_name: impl Into<Identifier>,
+        script: impl Into<SmartString>,
+    ) -> &mut Self {
+        self.tests.push((fn_name.into(), script.into()));
+        self
+    }
+    /// Get an iterator over all embedded script tests in the [`Module`] (not including
+    /// sub-modules) in the form of `(function name, script)`.
+    #[inline]
+    pub fn iter_tests(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.tests
+            .iter()
+            .map(|(name, script)| (name.as_str(), script.as_str()))
+    }
+
     /// _(metadata)_ Generate signatures for all the non-private functions in the [`Module`].
     /// Exported under the `metadata` feature only.
     #[cfg(feature = "metadata")]
@@ -1273,27 +1476,36 @@ impl Module {
 
     /// Set a script-defined function into the [`Module`].
     ///
-    /// If there is an existing function of the same name and number of arguments, it is replaced.
+    /// If there is an existing function of the same name, number of arguments and parameter types
+    /// (if typed), it is replaced.
     #[cfg(not(feature = "no_function"))]
     #[inline]
     pub fn set_script_fn(&mut self, fn_def: impl Into<Shared<crate::ast::ScriptFuncDef>>) -> u64 {
         let fn_def = fn_def.into();
 
         // None + function name + number of arguments.
-        let namespace = FnNamespace::Internal;
         let num_params = fn_def.params.len();
-        let hash_script = crate::calc_fn_hash(None, &fn_def.name, num_params);
+        let hash_base = crate::calc_fn_hash(None, &fn_def.name, num_params);
+        let hash_script = fn_def.calc_hash(hash_base);
+
         #[cfg(not(feature = "no_object"))]
-        let (hash_script, namespace) =
-            fn_def
-                .this_type
-                .as_ref()
-                .map_or((hash_script, namespace), |this_type| {
-                    (
-                        crate::calc_typed_method_hash(hash_script, this_type),
-                        FnNamespace::Global,
-                    )
-                });
+        let namespace = if fn_def.this_type.is_some() {
+            FnNamespace::Global
+        } else {
+            FnNamespace::Internal
+        };
+        #[cfg(feature = "no_object")]
+        let namespace = FnNamespace::Internal;
+
+        // Script-defined functions with typed parameters are resolved based on argument types.
+        if !fn_def.param_types.is_empty() {
+            self.typed_functions_filter.mark(hash_base);
+        }
+        // Script-defined functions taking a range of number of arguments are resolved by name.
+        if fn_def.has_arity_range() {
+            self.dynamic_functions_filter
+                .mark(crate::calc_arity_range_hash(&fn_def.name));
+        }
 
         // Catch hash collisions in testing environment only.
         #[cfg(feature = "testing-environ")]
@@ -1312,7 +1524,12 @@ impl Module {
             num_params,
             param_types: FnArgsVec::new_const(),
             #[cfg(feature = "metadata")]
-            params_info: fn_def.params.iter().map(Into::into).collect(),
+            params_info: fn_def
+                .params
+                .iter()
+                .enumerate()
+                .map(|(i, p)| fn_def.param_to_string(i, p).into())
+                .collect(),
             #[cfg(feature = "metadata")]
             return_type: <_>::default(),
             #[cfg(feature = "metadata")]
@@ -1348,6 +1565,71 @@ impl Module {
         })
     }
 
+    /// Get a shared reference to the script-defined function in the [`Module`] based on name
+    /// and the types of arguments.
+    ///
+    /// Functions with typed parameters matching the argument types take precedence over
+    /// functions with untyped parameters.
+    #[cfg(not(feature = "no_function"))]
+    #[must_use]
+    pub(crate) fn get_script_fn_by_types<'a>(
+        &self,
+        name: &str,
+        arg_types: impl ExactSizeIterator<Item = &'a str>,
+    ) -> Option<&Shared<crate::ast::ScriptFuncDef>> {
+        let lib = self.functions.as_ref()?;
+        let num_params = arg_types.len();
+        let hash = crate::calc_typed_params_hash(calc_fn_hash(None, name, num_params), arg_types);
+
+        lib.get(&hash)
+            .and_then(|(f, _)| f.get_script_fn_def())
+            .or_else(|| {
+                lib.values()
+                    .filter(|(_, m)| m.num_params == num_params && m.name == name)
+                    .filter_map(|(f, _)| f.get_script_fn_def())
+                    .find(|f| f.param_types.is_empty())
+            })
+            .or_else(|| {
+                self.get_script_fn_by_arity(name, num_params, None)
+                    .and_then(RhaiFunc::get_script_fn_def)
+            })
+    }
+    /// Get a script-defined function taking a range of number of arguments (i.e. with parameters
+    /// that have default values, or a rest parameter) which accepts a particular number of
+    /// arguments, and (if any) the type of the `this` pointer.
+    ///
+    /// Functions taking an exact number of arguments are not considered.
+    #[cfg(not(feature = "no_function"))]
+    #[must_use]
+    pub(crate) fn get_script_fn_by_arity(
+        &self,
+        name: &str,
+        num_args: usize,
+        _this_type: Option<&str>,
+    ) -> Option<&RhaiFunc> {
+        if !self.may_contain_dynamic_fn(crate::calc_arity_range_hash(name)) {
+            return None;
+        }
+
+        self.functions
+            .as_ref()?
+            .values()
+            .filter(|(_, m)| m.name == name)
+            .map(|(f, _)| f)
+            .find(|f| {
+                f.get_script_fn_def().map_or(false, |f| {
+                    #[cfg(not(feature = "no_object"))]
+                    if f.this_type
+                        .as_deref()
+                        .map_or(false, |t| _this_type != Some(t))
+                    {
+                        return false;
+                    }
+                    f.has_arity_range() && f.accepts_num_args(num_args)
+                })
+            })
+    }
+
     /// Get a mutable reference to the underlying [`BTreeMap`] of sub-modules,
     /// creating one if empty.
     ///
@@ -1506,6 +1788,68 @@ impl Module {
         self
     }
 
+    /// Copy all non-private functions of a particular name from another [`Module`] into this
+    /// [`Module`] under a new name, exposing them to the global namespace.
+    ///
+    /// Returns `false` if there is no such function.
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) fn copy_fn_as(&mut self, other: &Self, name: &str, alias: &str) -> bool {
+        let mut found = false;
+
+        for (func, metadata) in other.iter_fn() {
+            if metadata.name != name || metadata.access == FnAccess::Private {
+                continue;
+            }
+
+            found = true;
+
+            let hash = match func {
+                #[cfg(not(feature = "no_function"))]
+                RhaiFunc::Script { fn_def, environ } => {
+                    let mut fn_def = (**fn_def).clone();
+                    fn_def.name = alias.into();
+                    let hash = self.set_script_fn(fn_def);
+
+                    if let Some((RhaiFunc::Script { environ: e, .. }, _)) =
+                        self.functions.as_mut().and_then(|m| m.get_mut(&hash))
+                    {
+                        e.clone_from(environ);
+                    }
+
+                    hash
+                }
+                _ => {
+                    let mut f = metadata.clone();
+                    f.name = alias.into();
+
+                    let hash_base = calc_fn_hash(None, alias, f.param_types.len());
+                    f.hash = calc_fn_hash_full(hash_base, f.param_types.iter().copied());
+
+                    if f.param_types.contains(&TypeId::of::<Dynamic>()) {
+                        self.dynamic_functions_filter.mark(hash_base);
+                    }
+
+                    let hash = f.hash;
+                    self.functions
+                        .get_or_insert_with(|| new_hash_map(FN_MAP_SIZE))
+                        .insert(hash, (func.clone(), f.into()));
+                    hash
+                }
+            };
+
+            if let Some(f) = self.get_fn_metadata_mut(hash) {
+                f.namespace = FnNamespace::Global;
+            }
+        }
+
+        if found {
+            self.flags
+                .remove(ModuleFlags::INDEXED | ModuleFlags::INDEXED_GLOBAL_FUNCTIONS);
+        }
+
+        found
+    }
+
     /// Get a registered function's metadata.
     #[inline]
     #[allow(dead_code)]
@@ -1516,6 +1860,47 @@ impl Module {
             .map(|(_, f)| f.as_mut())
     }
 
+    /// Insert a native function together with its metadata, calculating its hash.
+    ///
+    /// Any existing function with the same hash is replaced.
+    fn insert_fn_with_metadata(
+        &mut self,
+        mut f: FuncMetadata,
+        func: RhaiFunc,
+        is_dynamic: bool,
+    ) -> &FuncMetadata {
+        let hash_base = calc_fn_hash(None, &f.name, f.param_types.len());
+        let hash_fn = calc_fn_hash_full(hash_base, f.param_types.iter().copied());
+        f.hash = hash_fn;
+
+        // Catch hash collisions in testing environment only.
+        #[cfg(feature = "testing-environ")]
+        if let Some(fx) = self.functions.as_ref().and_then(|f| f.get(&hash_base)) {
+            unreachable!(
+                "Hash {} already exists when registering function {}:\n{:#?}",
+                hash_base, f.name, fx
+            );
+        }
+
+        if is_dynamic {
+            self.dynamic_functions_filter.mark(hash_base);
+        }
+
+        let entry = match self
+            .functions
+            .get_or_insert_with(|| new_hash_map(FN_MAP_SIZE))
+            .entry(hash_fn)
+        {
+            Entry::Occupied(mut entry) => {
+                entry.insert((func, f.into()));
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => entry.insert((func, f.into())),
+        };
+
+        &entry.1
+    }
+
     /// Remap type ID.
     #[inline]
     #[must_use]
@@ -1927,6 +2312,17 @@ impl Module {
     pub(crate) const fn may_contain_dynamic_fn(&self, hash_script: u64) -> bool {
         !self.dynamic_functions_filter.is_absent(hash_script)
     }
+    /// Can the particular script-defined function with typed parameters exist in the [`Module`]?
+    ///
+    /// The hash is calculated without parameter types.
+    ///
+    /// A `true` return value does not automatically imply that the function _must_ exist.
+    #[cfg(not(feature = "no_function"))]
+    #[inline(always)]
+    #[must_use]
+    pub(crate) const fn may_contain_typed_fn(&self, hash_script: u64) -> bool {
+        !self.typed_functions_filter.is_absent(hash_script)
+    }
 
     /// Does the particular namespace-qualified function exist in the [`Module`]?
     ///
@@ -1963,7 +2359,12 @@ impl Module {
             None => self.functions = other.functions,
         }
         self.dynamic_functions_filter += other.dynamic_functions_filter;
+        #[cfg(not(feature = "no_function"))]
+        {
+            self.typed_functions_filter += other.typed_functions_filter;
+        }
         self.type_iterators.extend(other.type_iterators);
+        self.tests.extend(other.tests);
         self.all_functions = None;
         self.all_variables = None;
         self.all_type_iterators.clear();
@@ -1996,7 +2397,12 @@ impl Module {
             None => self.functions = other.functions,
         }
         self.dynamic_functions_filter += other.dynamic_functions_filter;
+        #[cfg(not(feature = "no_function"))]
+        {
+            self.typed_functions_filter += other.typed_functions_filter;
+        }
         self.type_iterators.extend(other.type_iterators);
+        self.tests.extend(other.tests);
         self.all_functions = None;
         self.all_variables = None;
         self.all_type_iterators.clear();
@@ -2040,9 +2446,14 @@ impl Module {
             }
         }
         self.dynamic_functions_filter += &other.dynamic_functions_filter;
+        #[cfg(not(feature = "no_function"))]
+        {
+            self.typed_functions_filter += &other.typed_functions_filter;
+        }
         for (&k, v) in &other.type_iterators {
             self.type_iterators.entry(k).or_insert_with(|| v.clone());
         }
+        self.tests.extend(other.tests.iter().cloned());
 
         self.all_functions = None;
         self.all_variables = None;
@@ -2097,8 +2508,13 @@ impl Module {
             }
         }
         self.dynamic_functions_filter += &other.dynamic_functions_filter;
+        #[cfg(not(feature = "no_function"))]
+        {
+            self.typed_functions_filter += &other.typed_functions_filter;
+        }
 
         self.type_iterators.extend(other.type_iterators.clone());
+        self.tests.extend(other.tests.iter().cloned());
         self.all_functions = None;
         self.all_variables = None;
         self.all_type_iterators.clear();
@@ -2136,6 +2552,48 @@ impl Module {
         });
 
         self.dynamic_functions_filter.clear();
+        self.typed_functions_filter.clear();
+
+        // Re-mark script-defined functions with typed parameters or taking a range of number of
+        // arguments
+        #[cfg(not(feature = "no_function"))]
+        if let Some(ref functions) = self.functions {
+            for (f, m) in functions.values() {
+                if f.get_script_fn_def()
+                    .map_or(false, |f| !f.param_types.is_empty())
+                {
+                    self.typed_functions_filter
+                        .mark(calc_fn_hash(None, &m.name, m.num_params));
+                }
+                if f.get_script_fn_def().map_or(false, |f| f.has_arity_range()) {
+                    self.dynamic_functions_filter
+                        .mark(crate::calc_arity_range_hash(&m.name));
+                }
+            }
+        }
+
+        self.all_functions = None;
+        self.all_variables = None;
+        self.all_type_iterators.clear();
+        self.flags
+            .remove(ModuleFlags::INDEXED | ModuleFlags::INDEXED_GLOBAL_FUNCTIONS);
+        self
+    }
+
+    /// Filter out the functions, retaining only those that pass a filter predicate.
+    ///
+    /// The predicate is passed the metadata of each function, together with whether it is volatile.
+    #[inline]
+    pub(crate) fn retain_functions(
+        &mut self,
+        filter: impl Fn(&FuncMetadata, bool) -> bool,
+    ) -> &mut Self {
+        self.functions = std::mem::take(&mut self.functions).map(|m| {
+            m.into_iter()
+                .filter(|(.., (f, m))| filter(m, f.is_volatile()))
+                .collect()
+        });
+
         self.all_functions = None;
         self.all_variables = None;
         self.all_type_iterators.clear();
@@ -2495,6 +2953,9 @@ impl Module {
             }
 
             // Index all functions
+            #[cfg(not(feature = "no_function"))]
+            let mut arity_range_fns = Vec::new();
+
             for (&hash, (f, m)) in module.functions.iter().flatten() {
                 match m.namespace {
                     FnNamespace::Global => {
@@ -2522,16 +2983,13 @@ impl Module {
                     #[cfg(not(feature = "no_function"))]
                     {
                         let hash_script =
-                            crate::calc_fn_hash(path.iter().copied(), &m.name, m.num_params);
-                        #[cfg(not(feature = "no_object"))]
-                        let hash_script = f
-                            .get_script_fn_def()
-                            .unwrap()
-                            .this_type
-                            .as_ref()
-                            .map_or(hash_script, |this_type| {
-                                crate::calc_typed_method_hash(hash_script, this_type)
-                            });
+                            f.get_script_fn_def()
+                                .unwrap()
+                                .calc_hash(crate::calc_fn_hash(
+                                    path.iter().copied(),
+                                    &m.name,
+                                    m.num_params,
+                                ));
 
                         // Catch hash collisions in testing environment only.
                         #[cfg(feature = "testing-environ")]
@@ -2543,6 +3001,22 @@ impl Module {
                         }
 
                         functions.insert(hash_script, f.clone());
+
+                        // Functions taking a range of number of arguments are also indexed
+                        // under each number of arguments, plus their name for rest parameters
+                        let fn_def = f.get_script_fn_def().unwrap();
+
+                        if fn_def.has_arity_range() {
+                            let hashes = (fn_def.min_num_args()..m.num_params)
+                                .map(|n| calc_fn_hash(path.iter().copied(), &m.name, n))
+                                .chain(fn_def.is_variadic.then(|| {
+                                    crate::calc_arity_range_hash(&m.name)
+                                        ^ calc_fn_hash(path.iter().copied(), &m.name, 0)
+                                }));
+
+                            arity_range_fns
+                                .extend(hashes.map(|hash| (fn_def.calc_hash(hash), f.clone())));
+                        }
                     }
                 } else {
                     let hash_fn =
@@ -2561,6 +3035,12 @@ impl Module {
                 }
             }
 
+            // Functions taking an exact number of arguments take precedence
+            #[cfg(not(feature = "no_function"))]
+            for (hash, f) in arity_range_fns {
+                functions.entry(hash).or_insert(f);
+            }
+
             contains_indexed_global_functions
         }
 
@@ -0,0 +1,241 @@
+use crate::eval::GlobalRuntimeState;
+use crate::{
+    Engine, EvalAltResult, Identifier, ModuleResolver, Position, RhaiResultOf, Scope, SharedModule,
+    AST, ERR,
+};
+#[cfg(feature = "no_std")]
+use core_error::Error;
+#[cfg(not(feature = "no_std"))]
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Errors of all [module resolvers][ModuleResolver] in a [`ChainedModuleResolver`] that failed
+/// to load a [module][crate::Module].
+///
+/// When more than one module resolver fails, a [`ChainedModuleResolver`] returns
+/// [`ErrorInModule`][EvalAltResult::ErrorInModule] wrapping an
+/// [`ErrorSystem`][EvalAltResult::ErrorSystem] that holds this type.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ModuleResolutionErrors {
+    /// Errors of the module resolvers, in the order they were tried.
+    pub errors: Vec<Box<EvalAltResult>>,
+}
+
+impl fmt::Display for ModuleResolutionErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} module resolvers failed", self.errors.len())?;
+
+        for err in &self.errors {
+            write!(f, "\n- {err}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ModuleResolutionErrors {}
+
+/// [Module][crate::Module] resolution service that chains module resolvers, tried in sequential
+/// order, each optionally restricted to paths starting with a particular prefix.
+///
+/// A module resolver added with [`push_prefixed`][ChainedModuleResolver::push_prefixed] is only
+/// tried for paths starting with its prefix, and the prefix is removed from the path passed to it.
+/// This routes paths such as `std::math` or `plugins::foo` to different module resolvers.
+///
+/// By default, when a module resolver fails to load a module it has found (e.g. a script file
+/// with a syntax error), the next module resolver is tried. If no module resolver succeeds, the
+/// errors of all failing module resolvers are reported together (see [`ModuleResolutionErrors`]).
+///
+/// # Example
+///
+/// ```
+/// use rhai::{Engine, Module};
+/// use rhai::module_resolvers::{ChainedModuleResolver, StaticModuleResolver};
+///
+/// let mut std_modules = StaticModuleResolver::new();
+/// let mut module = Module::new();
+/// module.set_var("answer", 42_i64);
+/// std_modules.insert("math", module);
+///
+/// let mut resolver = ChainedModuleResolver::new();
+/// resolver.push_prefixed("std::", std_modules);
+///
+/// let mut engine = Engine::new();
+/// engine.set_module_resolver(resolver);
+///
+/// assert_eq!(engine.eval::<i64>(r#"import "std::math" as m; m::answer"#).unwrap(), 42);
+/// assert!(engine.eval::<i64>(r#"import "math" as m; m::answer"#).is_err());
+/// ```
+pub struct ChainedModuleResolver {
+    /// Module resolvers with their path prefixes (empty for all paths).
+    resolvers: Vec<(Identifier, Box<dyn ModuleResolver>)>,
+    /// Try the next module resolver when a module resolver fails with an error?
+    fallback_on_error: bool,
+}
+
+impl Default for ChainedModuleResolver {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChainedModuleResolver {
+    /// Create a new [`ChainedModuleResolver`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            resolvers: Vec::new(),
+            fallback_on_error: true,
+        }
+    }
+    /// Append a [module resolver][ModuleResolver] for all paths to the end of the chain.
+    #[inline(always)]
+    pub fn push(&mut self, resolver: impl ModuleResolver + 'static) -> &mut Self {
+        self.resolvers
+            .push((Identifier::new_const(), Box::new(resolver)));
+        self
+    }
+    /// Append a [module resolver][ModuleResolver] for paths starting with a prefix to the end of
+    /// the chain.
+    ///
+    /// The prefix is removed from the path passed to the module resolver.
+    #[inline(always)]
+    pub fn push_prefixed(
+        &mut self,
+        prefix: impl Into<Identifier>,
+        resolver: impl ModuleResolver + 'static,
+    ) -> &mut Self {
+        self.resolvers.push((prefix.into(), Box::new(resolver)));
+        self
+    }
+    /// Should the next [module resolver][ModuleResolver] be tried when a module resolver fails
+    /// to load a [module][crate::Module] it has found?
+    /// Default is `true`.
+    ///
+    /// If `false`, the error is returned immediately.
+    #[inline(always)]
+    pub fn set_fallback_on_error(&mut self, enable: bool) -> &mut Self {
+        self.fallback_on_error = enable;
+        self
+    }
+    /// Is the next [module resolver][ModuleResolver] tried when a module resolver fails to load a
+    /// [module][crate::Module] it has found?
+    #[inline(always)]
+    #[must_use]
+    pub const fn fallback_on_error(&self) -> bool {
+        self.fallback_on_error
+    }
+    /// Get an iterator of all the [module resolvers][ModuleResolver] together with their path
+    /// prefixes (empty for all paths), in order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &dyn ModuleResolver)> {
+        self.resolvers.iter().map(|(p, r)| (p.as_str(), r.as_ref()))
+    }
+    /// Remove all [module resolvers][ModuleResolver].
+    #[inline(always)]
+    pub fn clear(&mut self) -> &mut Self {
+        self.resolvers.clear();
+        self
+    }
+    /// Returns `true` if this [`ChainedModuleResolver`] contains no module resolvers.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.resolvers.is_empty()
+    }
+    /// Get the number of [module resolvers][ModuleResolver] in this [`ChainedModuleResolver`].
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.resolvers.len()
+    }
+
+    /// Try all [module resolvers][ModuleResolver] matching a path in order.
+    ///
+    /// The resolution function returns [`None`] if a module resolver does not support the
+    /// resolution. [`None`] is returned if no matching module resolver supports it.
+    fn chain<T>(
+        &self,
+        path: &str,
+        pos: Position,
+        mut resolve: impl FnMut(&dyn ModuleResolver, &str) -> Option<RhaiResultOf<T>>,
+    ) -> Option<RhaiResultOf<T>> {
+        let mut supported = false;
+        let mut errors = Vec::new();
+
+        for (prefix, resolver) in &self.resolvers {
+            let sub_path = match path.strip_prefix(prefix.as_str()) {
+                Some(sub_path) => sub_path,
+                None => continue,
+            };
+
+            match resolve(resolver.as_ref(), sub_path) {
+                None => continue,
+                Some(Ok(r)) => return Some(Ok(r)),
+                Some(Err(err)) => match *err {
+                    ERR::ErrorModuleNotFound(..) => supported = true,
+                    _ if self.fallback_on_error => errors.push(err),
+                    _ => return Some(Err(err)),
+                },
+            }
+        }
+
+        Some(Err(match errors.len() {
+            0 if !supported => return None,
+            0 => ERR::ErrorModuleNotFound(path.into(), pos).into(),
+            1 => errors.pop().unwrap(),
+            _ => ERR::ErrorInModule(
+                path.into(),
+                ERR::ErrorSystem(String::new(), Box::new(ModuleResolutionErrors { errors })).into(),
+                pos,
+            )
+            .into(),
+        }))
+    }
+}
+
+impl ModuleResolver for ChainedModuleResolver {
+    fn resolve_raw(
+        &self,
+        engine: &Engine,
+        global: &mut GlobalRuntimeState,
+        scope: &mut Scope,
+        path: &str,
+        pos: Position,
+    ) -> RhaiResultOf<SharedModule> {
+        self.chain(path, pos, |resolver, path| {
+            Some(resolver.resolve_raw(engine, global, scope, path, pos))
+        })
+        .unwrap_or_else(|| Err(ERR::ErrorModuleNotFound(path.into(), pos).into()))
+    }
+
+    fn resolve(
+        &self,
+        engine: &Engine,
+        source: Option<&str>,
+        path: &str,
+        pos: Position,
+    ) -> RhaiResultOf<SharedModule> {
+        self.chain(path, pos, |resolver, path| {
+            Some(resolver.resolve(engine, source, path, pos))
+        })
+        .unwrap_or_else(|| Err(ERR::ErrorModuleNotFound(path.into(), pos).into()))
+    }
+
+    fn resolve_ast(
+        &self,
+        engine: &Engine,
+        source: Option<&str>,
+        path: &str,
+        pos: Position,
+    ) -> Option<RhaiResultOf<AST>> {
+        self.chain(path, pos, |resolver, path| {
+            resolver.resolve_ast(engine, source, path, pos)
+        })
+    }
+}
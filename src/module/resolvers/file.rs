@@ -12,6 +12,7 @@ use std::{
     collections::BTreeMap,
     io::Error as IoError,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 pub const RHAI_SCRIPT_EXTENSION: &str = "rhai";
@@ -24,7 +25,20 @@ pub const RHAI_SCRIPT_EXTENSION: &str = "rhai";
 /// for subsequent requests.
 ///
 /// Use [`clear_cache`][FileModuleResolver::clear_cache] or
-/// [`clear_cache_for_path`][FileModuleResolver::clear_cache_for_path] to clear the internal cache.
+/// [`clear_cache_for_path`][FileModuleResolver::clear_cache_for_path] to clear the internal cache,
+/// or [`invalidate_path`][FileModuleResolver::invalidate_path] to drop a single entry via a shared
+/// reference (e.g. from a file watcher).
+///
+/// With [`enable_auto_reload`][FileModuleResolver::enable_auto_reload], the modification time of
+/// each script file is checked whenever it is resolved, and the module is recompiled if the file
+/// has changed since it was cached. Only the file itself is checked, not the modules it imports.
+///
+/// ## Signing
+///
+/// Under the `signing` feature, if a [`ScriptSigner`][crate::ScriptSigner] is set via
+/// [`Engine::set_script_signer`], only script files with a valid signature in a signature file
+/// next to them (e.g. `module.rhai.sig`) are loaded. Modules cached before the
+/// [`ScriptSigner`][crate::ScriptSigner] is set are verified again when next resolved.
 ///
 /// ## Namespace
 ///
@@ -54,13 +68,34 @@ pub struct FileModuleResolver {
     extension: Identifier,
     /// Is the cache enabled?
     cache_enabled: bool,
+    /// Reload cached modules whose script files have changed?
+    auto_reload: bool,
     /// [`Scope`] holding variables for compiling scripts.
     scope: Scope<'static>,
     /// Internal cache of resolved modules.
     ///
     /// The cache is wrapped in interior mutability because [`resolve`][FileModuleResolver::resolve]
     /// is immutable.
-    cache: Locked<BTreeMap<PathBuf, SharedModule>>,
+    cache: Locked<BTreeMap<PathBuf, CachedModule>>,
+}
+
+/// A resolved [module][Module] in the cache of a [`FileModuleResolver`].
+#[derive(Debug, Clone)]
+struct CachedModule {
+    /// The resolved module.
+    module: SharedModule,
+    /// Modification time of the script file when the module was resolved (if available).
+    modified: Option<SystemTime>,
+    /// Was the signature of the script file verified when the module was resolved?
+    #[cfg(feature = "signing")]
+    verified: bool,
+}
+
+/// Get the modification time of a file, if available.
+#[inline]
+#[must_use]
+fn file_modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
 }
 
 impl Default for FileModuleResolver {
@@ -139,6 +174,7 @@ impl FileModuleResolver {
             base_path: None,
             extension: extension.into(),
             cache_enabled: true,
+            auto_reload: false,
             cache: BTreeMap::new().into(),
             scope: Scope::new(),
         }
@@ -169,6 +205,7 @@ impl FileModuleResolver {
             base_path: Some(path.into()),
             extension: extension.into(),
             cache_enabled: true,
+            auto_reload: false,
             cache: BTreeMap::new().into(),
             scope: Scope::new(),
         }
@@ -240,6 +277,30 @@ impl FileModuleResolver {
         self.cache_enabled
     }
 
+    /// Enable/disable automatic reloading of changed script files.
+    ///
+    /// When enabled, the modification time of a cached script file is checked each time it is
+    /// resolved, and the module is reloaded and recompiled if the file has changed (or cannot be
+    /// accessed any more).
+    ///
+    /// Only the modification time of the script file itself is checked. A module is _not_ reloaded
+    /// when only a module that it imports (directly or indirectly) has changed; use
+    /// [`invalidate_path`][FileModuleResolver::invalidate_path] on the importing script to force
+    /// that.
+    ///
+    /// This has no effect if the cache is disabled.
+    #[inline(always)]
+    pub fn enable_auto_reload(&mut self, enable: bool) -> &mut Self {
+        self.auto_reload = enable;
+        self
+    }
+    /// Is automatic reloading of changed script files enabled?
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_auto_reload_enabled(&self) -> bool {
+        self.auto_reload
+    }
+
     /// Is a particular path cached?
     #[inline]
     #[must_use]
@@ -266,7 +327,20 @@ impl FileModuleResolver {
         locked_write(&self.cache)
             .unwrap()
             .remove_entry(path.as_ref())
-            .map(|(.., v)| v)
+            .map(|(.., v)| v.module)
+    }
+    /// Remove the specified path from internal cache via a shared reference, so that the
+    /// script file is loaded once again the next time it is resolved.
+    ///
+    /// This is useful when the resolver is shared (e.g. with a file watcher).
+    ///
+    /// Returns `true` if the path was cached.
+    #[inline]
+    pub fn invalidate_path(&self, path: impl AsRef<Path>) -> bool {
+        locked_write(&self.cache)
+            .unwrap()
+            .remove(path.as_ref())
+            .is_some()
     }
     /// Construct a full file path.
     #[must_use]
@@ -309,11 +383,27 @@ impl FileModuleResolver {
         let file_path = self.get_file_path(path, source_path);
 
         if self.is_cache_enabled() {
-            if let Some(module) = locked_read(&self.cache).unwrap().get(&file_path) {
-                return Ok(module.clone());
+            if let Some(entry) = locked_read(&self.cache).unwrap().get(&file_path) {
+                // Modules cached before a script signer is set must be verified again
+                #[cfg(feature = "signing")]
+                let is_valid = entry.verified || engine.script_signer().is_none();
+                #[cfg(not(feature = "signing"))]
+                let is_valid = true;
+
+                if is_valid
+                    && (!self.auto_reload || entry.modified == file_modified_time(&file_path))
+                {
+                    return Ok(entry.module.clone());
+                }
             }
         }
 
+        let modified = if self.is_cache_enabled() && self.auto_reload {
+            file_modified_time(&file_path)
+        } else {
+            None
+        };
+
         let mut ast = engine
             .compile_file_with_scope(&self.scope, file_path.clone())
             .map_err(|err| match *err {
@@ -330,9 +420,13 @@ impl FileModuleResolver {
             .into();
 
         if self.is_cache_enabled() {
-            locked_write(&self.cache)
-                .unwrap()
-                .insert(file_path, m.clone());
+            let entry = CachedModule {
+                module: m.clone(),
+                modified,
+                #[cfg(feature = "signing")]
+                verified: engine.script_signer().is_some(),
+            };
+            locked_write(&self.cache).unwrap().insert(file_path, entry);
         }
 
         Ok(m)
@@ -4,17 +4,28 @@ use crate::{Engine, Position, RhaiResultOf, Scope, SharedModule, AST};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 
+mod chained;
 mod collection;
 mod dummy;
 mod file;
 mod stat;
+mod tenant;
+mod wasm;
 
+pub use chained::{ChainedModuleResolver, ModuleResolutionErrors};
 pub use collection::ModuleResolversCollection;
 pub use dummy::DummyModuleResolver;
 #[cfg(not(feature = "no_std"))]
 #[cfg(any(not(target_family = "wasm"), not(target_os = "unknown")))]
 pub use file::FileModuleResolver;
 pub use stat::StaticModuleResolver;
+pub use tenant::{TenantModuleCache, TenantModuleResolver};
+#[cfg(feature = "wasm-components")]
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+#[cfg(any(not(target_family = "wasm"), not(target_os = "unknown")))]
+pub use wasm::WasmComponentModuleResolver;
 
 /// Trait that encapsulates a module resolution service.
 pub trait ModuleResolver: SendSync {
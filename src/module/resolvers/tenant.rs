@@ -0,0 +1,357 @@
+use crate::eval::GlobalRuntimeState;
+use crate::func::{locked_read, locked_write};
+use crate::types::dynamic::Union;
+use crate::{
+    Dynamic, Engine, Identifier, Locked, Module, ModuleResolver, Position, RhaiResultOf, Scope,
+    Shared, SharedModule, StaticVec, ERR,
+};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{collections::BTreeMap, fmt};
+
+/// Cache key of a resolved [module][Module]: the source of the importing script and the path.
+type CacheKey = (Option<Identifier>, Identifier);
+
+/// Modules resolution state of a single tenant.
+struct TenantModules {
+    /// Module resolver of the tenant.
+    resolver: Box<dyn ModuleResolver>,
+    /// Resolved modules, or [`None`] if the tenant has been evicted.
+    cache: Locked<Option<BTreeMap<CacheKey, SharedModule>>>,
+}
+
+/// A cache of resolved [modules][Module] partitioned by tenant, for hosts that run scripts on
+/// behalf of multiple mutually-untrusting tenants.
+///
+/// Each tenant has its own [module resolver][ModuleResolver] and its own cache of resolved
+/// modules. A tenant can only ever see modules resolved through its own resolver.
+///
+/// Packages registered via [`register_package`][TenantModuleCache::register_package] (e.g. the
+/// standard library) are shared, read-only, by all tenants.
+///
+/// # Isolation Guarantees
+///
+/// * Modules resolved for one tenant are never served to another.
+///
+/// * A resolved module, or any package, holding a _shared_ value (e.g. a variable captured by a
+///   closure), including within arrays, object maps and sub-modules, is rejected, because that
+///   value could be mutated by one tenant and observed by another.
+///
+/// * [Evicting][TenantModuleCache::evict_tenant] a tenant drops all its cached modules.
+///   Engines still holding the tenant's [resolver][TenantModuleResolver] can no longer import
+///   any module.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+/// use rhai::{Engine, Module, INT};
+/// use rhai::module_resolvers::{StaticModuleResolver, TenantModuleCache};
+///
+/// let cache = TenantModuleCache::new();
+///
+/// for (tenant, answer) in [("alice", 1 as INT), ("bob", 2)] {
+///     let mut module = Module::new();
+///     module.set_var("answer", answer);
+///
+///     let mut resolver = StaticModuleResolver::new();
+///     resolver.insert("config", module);
+///
+///     cache.add_tenant(tenant, resolver);
+/// }
+///
+/// let base = Engine::new();
+///
+/// let alice = cache.engine_for(&base, "alice").unwrap();
+/// let bob = cache.engine_for(&base, "bob").unwrap();
+///
+/// assert_eq!(alice.eval::<INT>(r#"import "config" as c; c::answer"#)?, 1);
+/// assert_eq!(bob.eval::<INT>(r#"import "config" as c; c::answer"#)?, 2);
+///
+/// cache.evict_tenant("alice");
+///
+/// assert!(alice.eval::<INT>(r#"import "config" as c; c::answer"#).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct TenantModuleCache {
+    /// Read-only packages shared by all tenants.
+    packages: StaticVec<SharedModule>,
+    /// Modules resolution state of each tenant.
+    tenants: Locked<BTreeMap<Identifier, Shared<TenantModules>>>,
+}
+
+impl fmt::Debug for TenantModuleCache {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tenants = locked_read(&self.tenants)
+            .map(|t| t.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        f.debug_struct("TenantModuleCache")
+            .field("packages", &self.packages.len())
+            .field("tenants", &tenants)
+            .finish()
+    }
+}
+
+impl TenantModuleCache {
+    /// Create a new [`TenantModuleCache`].
+    #[inline(always)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Register a read-only package that is shared by all tenants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the package holds any shared value.
+    #[inline]
+    pub fn register_package(&mut self, package: SharedModule) -> &mut Self {
+        assert!(
+            !has_shared_state(&package),
+            "Package shared among tenants must not hold shared values."
+        );
+        self.packages.push(package);
+        self
+    }
+    /// Add a tenant with its own [module resolver][ModuleResolver].
+    ///
+    /// An existing tenant with the same name is [evicted][TenantModuleCache::evict_tenant] first.
+    #[inline]
+    pub fn add_tenant(
+        &self,
+        tenant: impl Into<Identifier>,
+        resolver: impl ModuleResolver + 'static,
+    ) -> &Self {
+        let modules = TenantModules {
+            resolver: Box::new(resolver),
+            cache: Some(BTreeMap::new()).into(),
+        };
+
+        if let Some(old) = locked_write(&self.tenants)
+            .unwrap()
+            .insert(tenant.into(), modules.into())
+        {
+            *locked_write(&old.cache).unwrap() = None;
+        }
+        self
+    }
+    /// Does a tenant exist?
+    #[inline]
+    #[must_use]
+    pub fn contains_tenant(&self, tenant: &str) -> bool {
+        locked_read(&self.tenants).unwrap().contains_key(tenant)
+    }
+    /// Get the number of modules cached for a tenant.
+    ///
+    /// Returns [`None`] if the tenant does not exist.
+    #[inline]
+    #[must_use]
+    pub fn num_cached(&self, tenant: &str) -> Option<usize> {
+        locked_read(&self.tenants).unwrap().get(tenant).map(|t| {
+            locked_read(&t.cache)
+                .unwrap()
+                .as_ref()
+                .map_or(0, BTreeMap::len)
+        })
+    }
+    /// Evict a tenant, dropping all of its cached modules.
+    ///
+    /// [Resolvers][TenantModuleResolver] of the tenant still held by any [`Engine`] fail to
+    /// resolve modules afterwards.
+    ///
+    /// Returns `false` if the tenant does not exist.
+    #[inline]
+    pub fn evict_tenant(&self, tenant: &str) -> bool {
+        match locked_write(&self.tenants).unwrap().remove(tenant) {
+            Some(t) => {
+                *locked_write(&t.cache).unwrap() = None;
+                true
+            }
+            None => false,
+        }
+    }
+    /// Empty the cached modules of a tenant, without evicting it.
+    ///
+    /// Returns `false` if the tenant does not exist.
+    #[inline]
+    pub fn clear_cache_for_tenant(&self, tenant: &str) -> bool {
+        match locked_read(&self.tenants).unwrap().get(tenant) {
+            Some(t) => {
+                if let Some(cache) = locked_write(&t.cache).unwrap().as_mut() {
+                    cache.clear();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+    /// Get a [module resolver][ModuleResolver] that resolves modules for a tenant.
+    ///
+    /// Returns [`None`] if the tenant does not exist.
+    #[inline]
+    #[must_use]
+    pub fn resolver_for(&self, tenant: &str) -> Option<TenantModuleResolver> {
+        locked_read(&self.tenants)
+            .unwrap()
+            .get(tenant)
+            .map(|t| TenantModuleResolver {
+                tenant: tenant.into(),
+                modules: t.clone(),
+            })
+    }
+    /// Create an [`Engine`] for a tenant, by [forking][Engine::fork] a base [`Engine`],
+    /// registering all shared packages and setting the tenant's [resolver][TenantModuleResolver].
+    ///
+    /// Returns [`None`] if the tenant does not exist.
+    #[inline]
+    #[must_use]
+    pub fn engine_for(&self, base: &Engine, tenant: &str) -> Option<Engine> {
+        let resolver = self.resolver_for(tenant)?;
+        let mut engine = base.fork();
+
+        for package in &self.packages {
+            engine.register_global_module(package.clone());
+        }
+        engine.set_module_resolver(resolver);
+
+        Some(engine)
+    }
+}
+
+/// [Module][Module] resolution service that resolves modules for a single tenant of a
+/// [`TenantModuleCache`].
+///
+/// Not [`Clone`] so that the resolver of one tenant is never accidentally copied into the
+/// [`Engine`] of another.
+pub struct TenantModuleResolver {
+    /// Name of the tenant.
+    tenant: Identifier,
+    /// Modules resolution state of the tenant.
+    modules: Shared<TenantModules>,
+}
+
+impl fmt::Debug for TenantModuleResolver {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantModuleResolver")
+            .field("tenant", &self.tenant)
+            .finish()
+    }
+}
+
+impl TenantModuleResolver {
+    /// Get the name of the tenant.
+    #[inline(always)]
+    #[must_use]
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+    /// Resolve a module via the cache, falling back to the tenant's own resolver.
+    fn resolve_cached(
+        &self,
+        source: Option<&str>,
+        path: &str,
+        pos: Position,
+        resolve: impl FnOnce(&dyn ModuleResolver) -> RhaiResultOf<SharedModule>,
+    ) -> RhaiResultOf<SharedModule> {
+        let key = (source.map(Into::into), path.into());
+
+        match *locked_read(&self.modules.cache).unwrap() {
+            Some(ref cache) => {
+                if let Some(module) = cache.get(&key) {
+                    return Ok(module.clone());
+                }
+            }
+            None => return Err(ERR::ErrorModuleNotFound(path.to_string(), pos).into()),
+        }
+
+        let module = resolve(&*self.modules.resolver)?;
+
+        if has_shared_state(&module) {
+            let err = ERR::ErrorSystem(
+                format!("Module of tenant '{}' holds shared values", self.tenant),
+                "modules cannot share mutable state among tenants".into(),
+            );
+            return Err(ERR::ErrorInModule(path.to_string(), err.into(), pos).into());
+        }
+
+        // The tenant may have been evicted while the module was being resolved
+        match *locked_write(&self.modules.cache).unwrap() {
+            Some(ref mut cache) => cache.insert(key, module.clone()),
+            None => return Err(ERR::ErrorModuleNotFound(path.to_string(), pos).into()),
+        };
+
+        Ok(module)
+    }
+}
+
+impl ModuleResolver for TenantModuleResolver {
+    #[inline]
+    fn resolve(
+        &self,
+        engine: &Engine,
+        source: Option<&str>,
+        path: &str,
+        pos: Position,
+    ) -> RhaiResultOf<SharedModule> {
+        self.resolve_cached(source, path, pos, |r| r.resolve(engine, source, path, pos))
+    }
+
+    #[inline]
+    fn resolve_raw(
+        &self,
+        engine: &Engine,
+        global: &mut GlobalRuntimeState,
+        scope: &mut Scope,
+        path: &str,
+        pos: Position,
+    ) -> RhaiResultOf<SharedModule> {
+        let source = global.source().map(Identifier::from);
+
+        self.resolve_cached(source.as_deref(), path, pos, |r| {
+            r.resolve_raw(engine, global, scope, path, pos)
+        })
+    }
+
+    #[inline]
+    fn resolve_ast(
+        &self,
+        engine: &Engine,
+        source: Option<&str>,
+        path: &str,
+        pos: Position,
+    ) -> Option<RhaiResultOf<crate::AST>> {
+        if locked_read(&self.modules.cache).unwrap().is_none() {
+            return Some(Err(ERR::ErrorModuleNotFound(path.to_string(), pos).into()));
+        }
+
+        self.modules.resolver.resolve_ast(engine, source, path, pos)
+    }
+}
+
+/// Does a [module][Module] hold any shared value in its variables or sub-modules?
+fn has_shared_state(module: &Module) -> bool {
+    module.iter_var_raw().any(|(.., v)| is_shared_value(v))
+        || module
+            .iter_sub_modules_raw()
+            .any(|(.., m)| has_shared_state(m))
+}
+
+/// Is a [`Dynamic`] value, or any value contained within it, shared?
+fn is_shared_value(value: &Dynamic) -> bool {
+    match value.0 {
+        #[cfg(not(feature = "no_closure"))]
+        Union::Shared(..) => true,
+        #[cfg(not(feature = "no_index"))]
+        Union::Array(ref arr, ..) => arr.iter().any(is_shared_value),
+        #[cfg(not(feature = "no_object"))]
+        Union::Map(ref map, ..) => map.values().any(is_shared_value),
+        _ => false,
+    }
+}
@@ -0,0 +1,708 @@
+#![cfg(feature = "wasm-components")]
+#![cfg(not(feature = "no_std"))]
+#![cfg(not(feature = "no_index"))]
+#![cfg(not(feature = "no_object"))]
+#![cfg(any(not(target_family = "wasm"), not(target_os = "unknown")))]
+
+use crate::func::{locked_read, locked_write, FnCallArgs, RhaiFunc};
+use crate::{
+    Array, Dynamic, Engine, FuncRegistration, Identifier, Locked, Map, Module, ModuleResolver,
+    NativeCallContext, Position, RhaiResultOf, Shared, SharedModule, ERR, INT,
+};
+use std::{
+    any::TypeId,
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+use wasmtime::component::{types::ComponentItem, Component, Func, Linker, Type, Val};
+use wasmtime::Store;
+
+pub const WASM_COMPONENT_EXTENSION: &str = "wasm";
+
+/// A [module][Module] resolution service that loads
+/// [WebAssembly components](https://component-model.bytecodealliance.org/) from the file system
+/// via [`wasmtime`](https://crates.io/crates/wasmtime), exposing their exported functions as
+/// native functions of a [module][Module].
+///
+/// Exported interfaces (e.g. `my:plugin/math@0.1.0`) become sub-modules named by the interface
+/// (e.g. `math`). Kebab-case names (e.g. `get-answer`) are exposed in snake-case (e.g.
+/// `get_answer`), including field names of records.
+///
+/// Each resolved module runs in its own instance of the component, so state held by the
+/// component is kept between calls but not shared among modules.
+///
+/// Not available under `no_std`, `no_index` or `no_object`.
+///
+/// ## Type Conversions
+///
+/// | WIT type                           | Rhai type                                       |
+/// |------------------------------------|-------------------------------------------------|
+/// | `bool`                             | `bool`                                          |
+/// | `s8`, `u8`, ..., `s64`, `u64`      | [`INT`] (errors when out of range)              |
+/// | `f32`, `f64`                       | [`FLOAT`][crate::FLOAT]                         |
+/// | `char`                             | `char`                                          |
+/// | `string`                           | string                                          |
+/// | `list<T>`, `tuple<...>`            | [`Array`]                                       |
+/// | `record`                           | object map keyed by (snake-case) field names    |
+/// | `enum`                             | string holding the case name                    |
+/// | `variant`                          | case name, or `#{ case: value }` with a payload |
+/// | `option<T>`                        | `()` for `none`, otherwise the value            |
+/// | `result<T, E>`                     | `#{ ok: value }` or `#{ err: value }`           |
+/// | `flags`                            | [`Array`] of the names of set flags             |
+///
+/// When a function returns a single `result<T, E>`, the `ok` value is returned directly and an
+/// `err` value is raised as a runtime error.
+///
+/// Resources are not supported.
+///
+/// ## Caching
+///
+/// Resolved [Modules][Module] are cached internally so components are not reloaded and
+/// re-instantiated for subsequent requests.
+///
+/// # Example
+///
+/// ```no_run
+/// use rhai::Engine;
+/// use rhai::module_resolvers::WasmComponentModuleResolver;
+///
+/// // Create a new 'WasmComponentModuleResolver' loading components from the 'plugins'
+/// // subdirectory with file extension '.wasm' (the default).
+/// let resolver = WasmComponentModuleResolver::new_with_path("./plugins");
+///
+/// let mut engine = Engine::new();
+/// engine.set_module_resolver(resolver);
+///
+/// engine.run(r#"import "calc" as calc; print(calc::add(40, 2));"#).unwrap();
+/// ```
+pub struct WasmComponentModuleResolver {
+    /// The [`wasmtime`] engine.
+    engine: wasmtime::Engine,
+    /// Linker providing the imports of components.
+    linker: Linker<()>,
+    /// Base path of the directory holding components.
+    base_path: Option<PathBuf>,
+    /// File extension of components, default `.wasm`.
+    extension: Identifier,
+    /// Is the cache enabled?
+    cache_enabled: bool,
+    /// Internal cache of resolved modules.
+    ///
+    /// The cache is wrapped in interior mutability because
+    /// [`resolve`][WasmComponentModuleResolver::resolve] is immutable.
+    cache: Locked<BTreeMap<PathBuf, SharedModule>>,
+}
+
+impl fmt::Debug for WasmComponentModuleResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmComponentModuleResolver")
+            .field("base_path", &self.base_path)
+            .field("extension", &self.extension)
+            .field("cache_enabled", &self.cache_enabled)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for WasmComponentModuleResolver {
+    #[inline(always)]
+    #[must_use]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WasmComponentModuleResolver {
+    /// Create a new [`WasmComponentModuleResolver`] with the current directory as base path and
+    /// a default [`wasmtime`] engine.
+    ///
+    /// The default extension is `.wasm`.
+    #[inline(always)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::new_with_engine(wasmtime::Engine::default())
+    }
+    /// Create a new [`WasmComponentModuleResolver`] with a specific base path.
+    ///
+    /// The default extension is `.wasm`.
+    #[inline]
+    #[must_use]
+    pub fn new_with_path(path: impl Into<PathBuf>) -> Self {
+        let mut resolver = Self::new();
+        resolver.base_path = Some(path.into());
+        resolver
+    }
+    /// Create a new [`WasmComponentModuleResolver`] using a specific [`wasmtime`] engine, e.g.
+    /// one configured with fuel or epoch interruption for sandboxing.
+    ///
+    /// The engine must have the component model enabled.
+    #[inline]
+    #[must_use]
+    pub fn new_with_engine(engine: wasmtime::Engine) -> Self {
+        Self {
+            linker: Linker::new(&engine),
+            engine,
+            base_path: None,
+            extension: WASM_COMPONENT_EXTENSION.into(),
+            cache_enabled: true,
+            cache: BTreeMap::new().into(),
+        }
+    }
+
+    /// Get the [`wasmtime`] engine.
+    #[inline(always)]
+    #[must_use]
+    pub const fn wasm_engine(&self) -> &wasmtime::Engine {
+        &self.engine
+    }
+    /// Get a mutable reference to the [`Linker`] providing the imports of components.
+    ///
+    /// By default, the linker is empty and only components without imports can be loaded.
+    #[inline(always)]
+    #[must_use]
+    pub fn linker_mut(&mut self) -> &mut Linker<()> {
+        &mut self.linker
+    }
+
+    /// Get the base path for components.
+    #[inline(always)]
+    #[must_use]
+    pub fn base_path(&self) -> Option<&Path> {
+        self.base_path.as_deref()
+    }
+    /// Set the base path for components.
+    #[inline(always)]
+    pub fn set_base_path(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.base_path = Some(path.into());
+        self
+    }
+
+    /// Get the component file extension.
+    #[inline(always)]
+    #[must_use]
+    pub fn extension(&self) -> &str {
+        &self.extension
+    }
+    /// Set the component file extension.
+    #[inline(always)]
+    pub fn set_extension(&mut self, extension: impl Into<Identifier>) -> &mut Self {
+        self.extension = extension.into();
+        self
+    }
+
+    /// Enable/disable the cache.
+    #[inline(always)]
+    pub fn enable_cache(&mut self, enable: bool) -> &mut Self {
+        self.cache_enabled = enable;
+        self
+    }
+    /// Is the cache enabled?
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_cache_enabled(&self) -> bool {
+        self.cache_enabled
+    }
+    /// Empty the internal cache.
+    #[inline]
+    pub fn clear_cache(&mut self) -> &mut Self {
+        locked_write(&self.cache).unwrap().clear();
+        self
+    }
+
+    /// Construct a full file path.
+    #[must_use]
+    pub fn get_file_path(&self, path: &str, source_path: Option<&Path>) -> PathBuf {
+        let path = Path::new(path);
+
+        let mut file_path;
+
+        if path.is_relative() {
+            file_path = self
+                .base_path
+                .clone()
+                .or_else(|| source_path.map(Into::into))
+                .unwrap_or_default();
+            file_path.push(path);
+        } else {
+            file_path = path.into();
+        }
+
+        file_path.set_extension(self.extension.as_str()); // Force extension
+        file_path
+    }
+
+    /// Instantiate a WebAssembly component (in binary or text format) and create a
+    /// [module][Module] from its exports.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Engine;
+    /// use rhai::module_resolvers::WasmComponentModuleResolver;
+    ///
+    /// let resolver = WasmComponentModuleResolver::new();
+    ///
+    /// let module = resolver.create_module(r#"
+    ///     (component
+    ///         (core module $m
+    ///             (func (export "add") (param i32 i32) (result i32)
+    ///                 local.get 0
+    ///                 local.get 1
+    ///                 i32.add))
+    ///         (core instance $i (instantiate $m))
+    ///         (func (export "add") (param "a" s32) (param "b" s32) (result s32)
+    ///             (canon lift (core func $i "add"))))
+    /// "#).unwrap();
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.register_static_module("calc", module.into());
+    ///
+    /// assert_eq!(engine.eval::<i64>("calc::add(40, 2)").unwrap(), 42);
+    /// ```
+    pub fn create_module(&self, bytes: impl AsRef<[u8]>) -> RhaiResultOf<Module> {
+        let component = Component::new(&self.engine, bytes)
+            .map_err(|err| ERR::ErrorSystem("Cannot load WASM component".into(), err.into()))?;
+        self.create_module_from_component(&component)
+    }
+    /// Instantiate a compiled WebAssembly [`Component`] and create a [module][Module] from its
+    /// exports.
+    pub fn create_module_from_component(&self, component: &Component) -> RhaiResultOf<Module> {
+        let mut store = Store::new(&self.engine, ());
+
+        let instance = self
+            .linker
+            .instantiate(&mut store, component)
+            .map_err(|err| {
+                ERR::ErrorSystem("Cannot instantiate WASM component".into(), err.into())
+            })?;
+
+        let mut functions = Vec::new();
+        let mut interfaces = Vec::new();
+
+        for (name, item) in component.component_type().exports(&self.engine) {
+            match item {
+                ComponentItem::ComponentFunc(ty) => {
+                    if let Some(func) = instance.get_func(&mut store, name) {
+                        functions.push((name.to_string(), func, ty));
+                    }
+                }
+                ComponentItem::ComponentInstance(ty) => {
+                    let Some(index) = instance.get_export(&mut store, None, name) else {
+                        continue;
+                    };
+                    let mut funcs = Vec::new();
+
+                    for (fn_name, item) in ty.exports(&self.engine) {
+                        if let ComponentItem::ComponentFunc(ty) = item {
+                            let func = instance
+                                .get_export(&mut store, Some(&index), fn_name)
+                                .and_then(|index| instance.get_func(&mut store, &index));
+
+                            if let Some(func) = func {
+                                funcs.push((fn_name.to_string(), func, ty));
+                            }
+                        }
+                    }
+                    interfaces.push((interface_name(name), funcs));
+                }
+                _ => (),
+            }
+        }
+
+        let store = Shared::new(Locked::new(store));
+        let mut module = Module::new();
+
+        for (name, func, ty) in functions {
+            set_wasm_fn(&mut module, &name, func, &ty, store.clone());
+        }
+        for (name, funcs) in interfaces {
+            let mut sub_module = Module::new();
+            for (fn_name, func, ty) in funcs {
+                set_wasm_fn(&mut sub_module, &fn_name, func, &ty, store.clone());
+            }
+            sub_module.build_index();
+            module.set_sub_module(name, sub_module);
+        }
+
+        module.build_index();
+        Ok(module)
+    }
+}
+
+impl ModuleResolver for WasmComponentModuleResolver {
+    fn resolve(
+        &self,
+        _: &Engine,
+        source: Option<&str>,
+        path: &str,
+        pos: Position,
+    ) -> RhaiResultOf<SharedModule> {
+        // Load relative paths from source if there is no base path specified
+        let source_path = source.and_then(|p| Path::new(p).parent());
+
+        let file_path = self.get_file_path(path, source_path);
+
+        if self.is_cache_enabled() {
+            if let Some(module) = locked_read(&self.cache).unwrap().get(&file_path) {
+                return Ok(module.clone());
+            }
+        }
+
+        let bytes = std::fs::read(&file_path)
+            .map_err(|_| ERR::ErrorModuleNotFound(path.to_string(), pos))?;
+
+        let mut module = self
+            .create_module(bytes)
+            .map_err(|err| ERR::ErrorInModule(path.to_string(), err, pos))?;
+        module.set_id(path);
+
+        let module: SharedModule = module.into();
+
+        if self.is_cache_enabled() {
+            locked_write(&self.cache)
+                .unwrap()
+                .insert(file_path, module.clone());
+        }
+
+        Ok(module)
+    }
+}
+
+/// Convert a kebab-case WIT name into snake-case.
+#[inline]
+#[must_use]
+fn snake_case(name: &str) -> Identifier {
+    name.replace('-', "_").into()
+}
+
+/// Get the name of the sub-module for an exported interface, e.g. `math` for
+/// `my:plugin/math@0.1.0`.
+#[must_use]
+fn interface_name(name: &str) -> Identifier {
+    let name = name.rsplit('/').next().unwrap_or(name);
+    snake_case(name.split('@').next().unwrap_or(name))
+}
+
+/// Register an exported function of a WebAssembly component into a [module][Module].
+fn set_wasm_fn(
+    module: &mut Module,
+    name: &str,
+    func: Func,
+    ty: &wasmtime::component::types::ComponentFunc,
+    store: Shared<Locked<Store<()>>>,
+) {
+    let fn_name = snake_case(name);
+    let param_types: Vec<_> = ty.params().map(|(.., ty)| ty).collect();
+    let result_types: Vec<_> = ty.results().collect();
+    let arg_types = vec![TypeId::of::<Dynamic>(); param_types.len()];
+
+    let reg = FuncRegistration::new(fn_name.clone());
+    #[cfg(feature = "metadata")]
+    let reg = reg.with_params_info(ty.params().map(|(name, ..)| snake_case(name)));
+
+    let call = move |_: Option<NativeCallContext>, args: &mut FnCallArgs| {
+        let params = args
+            .iter()
+            .zip(&param_types)
+            .map(|(arg, ty)| to_wasm_val(arg, ty))
+            .collect::<RhaiResultOf<Vec<_>>>()?;
+        let mut results = vec![Val::Bool(false); result_types.len()];
+
+        {
+            let store = &mut *locked_write(&store).unwrap();
+
+            func.call(&mut *store, &params, &mut results)
+                .and_then(|()| func.post_return(&mut *store))
+                .map_err(|err| {
+                    ERR::ErrorSystem(format!("Error in WASM function '{fn_name}'"), err.into())
+                })?;
+        }
+
+        match results.len() {
+            0 => Ok(Dynamic::UNIT),
+            1 => match results.pop().unwrap() {
+                Val::Result(Ok(value)) => value.map_or(Ok(Dynamic::UNIT), |v| from_wasm_val(*v)),
+                Val::Result(Err(value)) => {
+                    let value = value.map_or(Ok(Dynamic::UNIT), |v| from_wasm_val(*v))?;
+                    Err(ERR::ErrorRuntime(value, Position::NONE).into())
+                }
+                value => from_wasm_val(value),
+            },
+            _ => results
+                .into_iter()
+                .map(from_wasm_val)
+                .collect::<RhaiResultOf<Array>>()
+                .map(Into::into),
+        }
+    };
+
+    reg.set_into_module_raw(
+        module,
+        arg_types,
+        RhaiFunc::Method {
+            func: Shared::new(call),
+            has_context: false,
+            is_pure: true,
+            is_volatile: true,
+        },
+    );
+}
+
+/// Get the name of a WIT type for error messages.
+#[must_use]
+fn wit_type_name(ty: &Type) -> &'static str {
+    match ty {
+        Type::Bool => "bool",
+        Type::S8 => "s8",
+        Type::U8 => "u8",
+        Type::S16 => "s16",
+        Type::U16 => "u16",
+        Type::S32 => "s32",
+        Type::U32 => "u32",
+        Type::S64 => "s64",
+        Type::U64 => "u64",
+        Type::Float32 => "f32",
+        Type::Float64 => "f64",
+        Type::Char => "char",
+        Type::String => "string",
+        Type::List(..) => "list",
+        Type::Record(..) => "record",
+        Type::Tuple(..) => "tuple",
+        Type::Variant(..) => "variant",
+        Type::Enum(..) => "enum",
+        Type::Option(..) => "option",
+        Type::Result(..) => "result",
+        Type::Flags(..) => "flags",
+        Type::Own(..) | Type::Borrow(..) => "resource",
+    }
+}
+
+/// Convert a WIT integer into an [`INT`].
+#[inline]
+fn from_wasm_int<T: TryInto<INT> + fmt::Display + Copy>(value: T) -> RhaiResultOf<Dynamic> {
+    value.try_into().map(Dynamic::from_int).map_err(|_| {
+        ERR::ErrorArithmetic(format!("Integer overflow: {value}"), Position::NONE).into()
+    })
+}
+
+/// Convert a value returned from a WebAssembly component into a [`Dynamic`].
+fn from_wasm_val(value: Val) -> RhaiResultOf<Dynamic> {
+    Ok(match value {
+        Val::Bool(b) => b.into(),
+        Val::S8(x) => from_wasm_int(x)?,
+        Val::U8(x) => from_wasm_int(x)?,
+        Val::S16(x) => from_wasm_int(x)?,
+        Val::U16(x) => from_wasm_int(x)?,
+        Val::S32(x) => from_wasm_int(x)?,
+        Val::U32(x) => from_wasm_int(x)?,
+        Val::S64(x) => from_wasm_int(x)?,
+        Val::U64(x) => from_wasm_int(x)?,
+        #[cfg(not(feature = "no_float"))]
+        Val::Float32(f) => (f as crate::FLOAT).into(),
+        #[cfg(not(feature = "no_float"))]
+        Val::Float64(f) => (f as crate::FLOAT).into(),
+        Val::Char(c) => c.into(),
+        Val::String(s) => s.into(),
+        Val::List(list) | Val::Tuple(list) => list
+            .into_iter()
+            .map(from_wasm_val)
+            .collect::<RhaiResultOf<Array>>()?
+            .into(),
+        Val::Record(fields) => fields
+            .into_iter()
+            .map(|(name, v)| from_wasm_val(v).map(|v| (snake_case(&name), v)))
+            .collect::<RhaiResultOf<Map>>()?
+            .into(),
+        Val::Variant(case, None) | Val::Enum(case) => case.into(),
+        Val::Variant(case, Some(v)) => {
+            let mut map = Map::new();
+            map.insert(case.into(), from_wasm_val(*v)?);
+            map.into()
+        }
+        Val::Option(v) => v.map_or(Ok(Dynamic::UNIT), |v| from_wasm_val(*v))?,
+        Val::Result(result) => {
+            let (key, v) = match result {
+                Ok(v) => ("ok", v),
+                Err(v) => ("err", v),
+            };
+            let mut map = Map::new();
+            map.insert(
+                key.into(),
+                v.map_or(Ok(Dynamic::UNIT), |v| from_wasm_val(*v))?,
+            );
+            map.into()
+        }
+        Val::Flags(names) => names
+            .into_iter()
+            .map(Dynamic::from)
+            .collect::<Array>()
+            .into(),
+        value => {
+            return Err(ERR::ErrorMismatchDataType(
+                "a supported type".into(),
+                format!("{value:?}"),
+                Position::NONE,
+            )
+            .into())
+        }
+    })
+}
+
+/// Convert a [`Dynamic`] into an integer of a WIT type.
+#[inline]
+fn to_wasm_int<T: TryFrom<INT>>(value: &Dynamic, ty: &Type) -> RhaiResultOf<T> {
+    let x = value.as_int().map_err(|typ| {
+        ERR::ErrorMismatchDataType(wit_type_name(ty).into(), typ.into(), Position::NONE)
+    })?;
+    T::try_from(x).map_err(|_| {
+        ERR::ErrorArithmetic(
+            format!("Integer overflow for {}: {x}", wit_type_name(ty)),
+            Position::NONE,
+        )
+        .into()
+    })
+}
+
+/// Convert a [`Dynamic`] into a value of a WIT type to pass to a WebAssembly component.
+fn to_wasm_val(value: &Dynamic, ty: &Type) -> RhaiResultOf<Val> {
+    let mismatch = || -> Box<crate::EvalAltResult> {
+        ERR::ErrorMismatchDataType(
+            wit_type_name(ty).into(),
+            value.type_name().into(),
+            Position::NONE,
+        )
+        .into()
+    };
+
+    Ok(match ty {
+        Type::Bool => Val::Bool(value.as_bool().map_err(|_| mismatch())?),
+        Type::S8 => Val::S8(to_wasm_int(value, ty)?),
+        Type::U8 => Val::U8(to_wasm_int(value, ty)?),
+        Type::S16 => Val::S16(to_wasm_int(value, ty)?),
+        Type::U16 => Val::U16(to_wasm_int(value, ty)?),
+        Type::S32 => Val::S32(to_wasm_int(value, ty)?),
+        Type::U32 => Val::U32(to_wasm_int(value, ty)?),
+        Type::S64 => Val::S64(to_wasm_int(value, ty)?),
+        Type::U64 => Val::U64(to_wasm_int(value, ty)?),
+        #[cfg(not(feature = "no_float"))]
+        Type::Float32 => Val::Float32(value.as_float().map_err(|_| mismatch())? as f32),
+        #[cfg(not(feature = "no_float"))]
+        Type::Float64 => Val::Float64(value.as_float().map_err(|_| mismatch())? as f64),
+        Type::Char => Val::Char(value.as_char().map_err(|_| mismatch())?),
+        Type::String => Val::String(
+            value
+                .as_immutable_string_ref()
+                .map_err(|_| mismatch())?
+                .to_string(),
+        ),
+        Type::List(list) => {
+            let ty = list.ty();
+            let array = value.as_array_ref().map_err(|_| mismatch())?;
+            Val::List(
+                array
+                    .iter()
+                    .map(|v| to_wasm_val(v, &ty))
+                    .collect::<RhaiResultOf<_>>()?,
+            )
+        }
+        Type::Tuple(tuple) => {
+            let array = value.as_array_ref().map_err(|_| mismatch())?;
+            if array.len() != tuple.types().len() {
+                return Err(mismatch());
+            }
+            Val::Tuple(
+                array
+                    .iter()
+                    .zip(tuple.types())
+                    .map(|(v, ty)| to_wasm_val(v, &ty))
+                    .collect::<RhaiResultOf<_>>()?,
+            )
+        }
+        Type::Record(record) => {
+            let map = value.as_map_ref().map_err(|_| mismatch())?;
+            Val::Record(
+                record
+                    .fields()
+                    .map(|field| {
+                        let v = map
+                            .get(snake_case(field.name).as_str())
+                            .cloned()
+                            .unwrap_or(Dynamic::UNIT);
+                        to_wasm_val(&v, &field.ty).map(|v| (field.name.to_string(), v))
+                    })
+                    .collect::<RhaiResultOf<_>>()?,
+            )
+        }
+        Type::Variant(variant) => {
+            if let Ok(case) = value.as_immutable_string_ref() {
+                variant
+                    .cases()
+                    .find(|c| c.name == case.as_str() && c.ty.is_none())
+                    .ok_or_else(mismatch)?;
+                Val::Variant(case.to_string(), None)
+            } else {
+                let map = value.as_map_ref().map_err(|_| mismatch())?;
+                let (name, v) = map
+                    .iter()
+                    .next()
+                    .filter(|_| map.len() == 1)
+                    .ok_or_else(mismatch)?;
+                let ty = variant
+                    .cases()
+                    .find(|c| c.name == name.as_str())
+                    .ok_or_else(mismatch)?
+                    .ty;
+                let payload = match ty {
+                    Some(ty) => Some(Box::new(to_wasm_val(v, &ty)?)),
+                    None => None,
+                };
+                Val::Variant(name.to_string(), payload)
+            }
+        }
+        Type::Enum(e) => {
+            let case = value.as_immutable_string_ref().map_err(|_| mismatch())?;
+            if !e.names().any(|name| name == case.as_str()) {
+                return Err(mismatch());
+            }
+            Val::Enum(case.to_string())
+        }
+        Type::Option(option) => {
+            if value.is_unit() {
+                Val::Option(None)
+            } else {
+                Val::Option(Some(Box::new(to_wasm_val(value, &option.ty())?)))
+            }
+        }
+        Type::Result(result) => {
+            let map = value.as_map_ref().map_err(|_| mismatch())?;
+            let payload = |v: Option<&Dynamic>, ty: Option<Type>| match ty {
+                Some(ty) => {
+                    to_wasm_val(v.unwrap_or(&Dynamic::UNIT), &ty).map(|v| Some(Box::new(v)))
+                }
+                None => Ok(None),
+            };
+            if map.contains_key("ok") {
+                Val::Result(Ok(payload(map.get("ok"), result.ok())?))
+            } else if map.contains_key("err") {
+                Val::Result(Err(payload(map.get("err"), result.err())?))
+            } else {
+                return Err(mismatch());
+            }
+        }
+        Type::Flags(flags) => {
+            let array = value.as_array_ref().map_err(|_| mismatch())?;
+            let names = array
+                .iter()
+                .map(|v| {
+                    v.as_immutable_string_ref()
+                        .ok()
+                        .filter(|name| flags.names().any(|n| n == name.as_str()))
+                        .map(|name| name.to_string())
+                        .ok_or_else(mismatch)
+                })
+                .collect::<RhaiResultOf<_>>()?;
+            Val::Flags(names)
+        }
+        _ => return Err(mismatch()),
+    })
+}
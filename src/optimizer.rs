@@ -6,12 +6,13 @@ use crate::ast::{
     SwitchCasesCollection,
 };
 use crate::engine::{
-    KEYWORD_DEBUG, KEYWORD_EVAL, KEYWORD_FN_PTR, KEYWORD_FN_PTR_CURRY, KEYWORD_PRINT,
-    KEYWORD_TYPE_OF, OP_NOT,
+    FN_PRETTY_PRINT, KEYWORD_DEBUG, KEYWORD_EVAL, KEYWORD_FN_PTR, KEYWORD_FN_PTR_CURRY,
+    KEYWORD_PRINT, KEYWORD_TYPE_OF, OP_NOT,
 };
 use crate::eval::{Caches, GlobalRuntimeState};
 use crate::func::builtin::get_builtin_binary_op_fn;
 use crate::func::hashing::get_hasher;
+use crate::func::SendSync;
 use crate::tokenizer::Token;
 use crate::{
     calc_fn_hash, calc_fn_hash_full, Dynamic, Engine, FnArgsVec, FnPtr, ImmutableString, Position,
@@ -20,7 +21,7 @@ use crate::{
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 use std::{
-    any::TypeId,
+    any::{type_name, TypeId},
     borrow::Cow,
     convert::TryFrom,
     hash::{Hash, Hasher},
@@ -44,6 +45,150 @@ pub enum OptimizationLevel {
     Full,
 }
 
+/// Options controlling the [`AST`] optimizer.
+///
+/// Not available under `no_optimize`.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub struct OptimizerOptions {
+    /// Maximum size, in number of [`AST`] nodes, of a script-defined function for calls to it
+    /// with constant arguments to be evaluated under [`OptimizationLevel::Full`].
+    ///
+    /// Only functions without loops, closures, `this`, imports, custom syntax, module-qualified
+    /// names, or calls to volatile or other script-defined functions are ever evaluated.
+    ///
+    /// Zero disables evaluating script-defined functions.
+    pub max_inline_fn_size: usize,
+}
+
+impl OptimizerOptions {
+    /// Create a new [`OptimizerOptions`] with default values.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_inline_fn_size: 32,
+        }
+    }
+    /// Set the maximum size, in number of [`AST`] nodes, of a script-defined function for calls
+    /// to it with constant arguments to be evaluated under [`OptimizationLevel::Full`].
+    ///
+    /// Zero disables evaluating script-defined functions.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_max_inline_fn_size(mut self, size: usize) -> Self {
+        self.max_inline_fn_size = size;
+        self
+    }
+}
+
+impl Default for OptimizerOptions {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A pass of the [`AST`] optimizer.
+///
+/// Passes registered via [`Engine::set_optimizer_passes`] run in order whenever a script is
+/// compiled, or an [`AST`] is re-optimized via [`Engine::optimize_ast`].
+///
+/// The built-in optimizations are performed by [`DefaultOptimizerPass`].
+///
+/// This trait is implemented for all functions with the same signature as
+/// [`optimize`][OptimizerPass::optimize].
+///
+/// Not available under `no_optimize`.
+pub trait OptimizerPass: SendSync {
+    /// Name of the pass.
+    #[inline(always)]
+    #[must_use]
+    fn name(&self) -> &str {
+        type_name::<Self>()
+    }
+    /// Optimize an [`AST`] in place.
+    ///
+    /// `scope` holds constants available for propagation, if any.
+    fn optimize(
+        &self,
+        engine: &Engine,
+        scope: Option<&Scope>,
+        ast: &mut AST,
+        optimization_level: OptimizationLevel,
+    );
+}
+
+impl<F: Fn(&Engine, Option<&Scope>, &mut AST, OptimizationLevel) + SendSync> OptimizerPass for F {
+    #[inline(always)]
+    fn optimize(
+        &self,
+        engine: &Engine,
+        scope: Option<&Scope>,
+        ast: &mut AST,
+        optimization_level: OptimizationLevel,
+    ) {
+        self(engine, scope, ast, optimization_level);
+    }
+}
+
+/// The built-in [`AST`] optimizer pass, performing constants propagation, dead code elimination
+/// and (under [`OptimizationLevel::Full`]) eager function evaluation.
+///
+/// It does nothing under [`OptimizationLevel::None`].
+///
+/// Not available under `no_optimize`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct DefaultOptimizerPass;
+
+impl OptimizerPass for DefaultOptimizerPass {
+    #[inline(always)]
+    fn name(&self) -> &str {
+        "default"
+    }
+    fn optimize(
+        &self,
+        engine: &Engine,
+        scope: Option<&Scope>,
+        ast: &mut AST,
+        optimization_level: OptimizationLevel,
+    ) {
+        if optimization_level == OptimizationLevel::None {
+            return;
+        }
+
+        let statements = mem::take(ast.statements_mut()).into_iter().collect();
+
+        #[cfg(not(feature = "no_function"))]
+        let functions = mem::take(ast.shared_lib_mut())
+            .iter_fn()
+            .filter_map(|(f, ..)| f.get_script_fn_def().cloned())
+            .collect::<Vec<_>>();
+
+        let mut optimized = engine.optimize_script(
+            scope,
+            statements,
+            #[cfg(not(feature = "no_function"))]
+            functions,
+            optimization_level,
+        );
+
+        *ast.statements_mut() = mem::take(optimized.statements_mut());
+        #[cfg(not(feature = "no_function"))]
+        {
+            *ast.shared_lib_mut() = mem::take(optimized.shared_lib_mut());
+        }
+    }
+}
+
+/// Keywords that are handled specially, and never evaluated during optimization.
+const DONT_EVAL_KEYWORDS: &[&str] = &[
+    KEYWORD_PRINT,   // side effects
+    KEYWORD_DEBUG,   // side effects
+    FN_PRETTY_PRINT, // side effects
+    KEYWORD_EVAL,    // arbitrary scripts
+];
+
 /// Mutable state throughout an optimization pass.
 #[derive(Debug, Clone)]
 struct OptimizerState<'a> {
@@ -150,8 +295,115 @@ impl<'a> OptimizerState<'a> {
                 Position::NONE,
             )
             .ok()
+            // Tainted values must not be turned into constants
+            .filter(|(v, ..)| !v.is_tainted())
             .map(|(v, ..)| v)
     }
+    /// Call a script-defined function with constant arguments.
+    #[cfg(not(feature = "no_function"))]
+    pub fn call_script_fn_with_const_args(
+        &mut self,
+        fn_def: &crate::ast::ScriptFuncDef,
+        arg_values: &mut [Dynamic],
+    ) -> Option<Dynamic> {
+        self.engine
+            .call_script_fn(
+                &mut self.global,
+                &mut self.caches,
+                &mut Scope::new(),
+                None,
+                None,
+                fn_def,
+                &mut arg_values.iter_mut().collect::<FnArgsVec<_>>(),
+                false,
+                Position::NONE,
+            )
+            .ok()
+            .filter(|v| !v.is_tainted())
+    }
+}
+
+/// Can calls to a script-defined function with constant arguments be evaluated during optimization?
+///
+/// The function must be no larger than `max_size` [`AST`] nodes and must not contain anything
+/// that depends on, or changes, state outside of the function call (including calls to volatile
+/// functions).
+#[cfg(not(feature = "no_function"))]
+fn is_const_evaluable(
+    engine: &Engine,
+    fn_def: &crate::ast::ScriptFuncDef,
+    lib: &[crate::SharedModule],
+    max_size: usize,
+) -> bool {
+    use crate::ast::{ASTNode, FnCallExpr};
+
+    #[cfg(not(feature = "no_object"))]
+    if fn_def.this_type.is_some() {
+        return false;
+    }
+
+    let is_script_fn = |name: &str, num_params: usize| {
+        lib.iter().any(|m| {
+            m.get_script_fn(name, num_params).is_some()
+                || m.get_script_fn_by_arity(name, num_params, None).is_some()
+        })
+    };
+    let is_volatile_fn = |name: &str| {
+        engine
+            .global_modules
+            .iter()
+            .flat_map(|m| m.iter_fn())
+            .any(|(f, m)| f.is_volatile() && m.name == name)
+    };
+    let is_pure_call = |x: &FnCallExpr, num_params: usize| {
+        #[cfg(not(feature = "no_module"))]
+        if x.is_qualified() {
+            return false;
+        }
+
+        !DONT_EVAL_KEYWORDS.contains(&x.name.as_str())
+            && !matches!(
+                x.name.as_str(),
+                crate::engine::KEYWORD_FN_PTR_CALL
+                    | KEYWORD_FN_PTR_CURRY
+                    | crate::engine::KEYWORD_IS_DEF_VAR
+            )
+            && (x.hashes.is_native_only() || !is_script_fn(&x.name, num_params))
+            && !is_volatile_fn(&x.name)
+    };
+
+    let mut size = 0;
+
+    let on_node = &mut |path: &[ASTNode]| {
+        size += 1;
+
+        if size > max_size {
+            return false;
+        }
+
+        match path.last().unwrap() {
+            ASTNode::Stmt(Stmt::While(..) | Stmt::Do(..) | Stmt::For(..)) => false,
+            #[cfg(not(feature = "no_module"))]
+            ASTNode::Stmt(Stmt::Import(..) | Stmt::Export(..)) => false,
+            #[cfg(not(feature = "no_closure"))]
+            ASTNode::Stmt(Stmt::Share(..)) => false,
+            ASTNode::Stmt(Stmt::FnCall(x, ..)) | ASTNode::Expr(Expr::FnCall(x, ..)) => {
+                is_pure_call(x, x.args.len())
+            }
+            ASTNode::Expr(Expr::MethodCall(x, ..)) => is_pure_call(x, x.args.len() + 1),
+            #[cfg(not(feature = "no_module"))]
+            ASTNode::Expr(Expr::Variable(x, ..)) => x.2.is_empty(),
+            ASTNode::Expr(Expr::ThisPtr(..)) => false,
+            #[cfg(not(feature = "no_custom_syntax"))]
+            ASTNode::Expr(Expr::Custom(..)) => false,
+            _ => true,
+        }
+    };
+
+    let path = &mut Vec::new();
+
+    fn_def.param_defaults.iter().all(|e| e.walk(path, on_node))
+        && fn_def.body.iter().all(|stmt| stmt.walk(path, on_node))
 }
 
 /// Optimize a block of [statements][Stmt].
@@ -231,6 +483,13 @@ fn optimize_stmt_block(
                     };
                     state.push_var(x.0.name.clone(), value);
                 }
+                Stmt::Destructure(x, ..) => {
+                    optimize_expr(&mut x.1, state, false);
+
+                    for var in x.0.variables() {
+                        state.push_var(var.name.clone(), None);
+                    }
+                }
                 // Optimize the statement
                 _ => optimize_stmt(stmt, state, preserve_result),
             }
@@ -514,7 +773,7 @@ fn optimize_stmt(stmt: &mut Stmt, state: &mut OptimizerState, preserve_result: b
         }
 
         // switch const { ... }
-        Stmt::Switch(x, pos) if is_hashable_constant(&x.0) => {
+        Stmt::Switch(x, pos) if is_hashable_constant(&x.0) && x.1.patterns.is_empty() => {
             let (
                 match_expr,
                 SwitchCasesCollection {
@@ -522,6 +781,7 @@ fn optimize_stmt(stmt: &mut Stmt, state: &mut OptimizerState, preserve_result: b
                     cases,
                     ranges,
                     def_case,
+                    ..
                 },
             ) = &mut **x;
 
@@ -666,17 +926,28 @@ fn optimize_stmt(stmt: &mut Stmt, state: &mut OptimizerState, preserve_result: b
                     cases,
                     ranges,
                     def_case,
-                    ..
+                    patterns,
                 },
             ) = &mut **x;
 
             optimize_expr(match_expr, state, false);
 
             // Optimize blocks
-            for b in &mut *expressions {
+            for (index, b) in expressions.iter_mut().enumerate() {
+                let orig_len = state.variables.len();
+
+                // Variables bound by a pattern shadow any constants with the same names
+                if let Some((pattern, ..)) = patterns.iter().find(|&&(.., n)| n == index) {
+                    for var in pattern.variables() {
+                        state.push_var(var.name.clone(), None);
+                    }
+                }
+
                 optimize_expr(&mut b.lhs, state, false);
                 optimize_expr(&mut b.rhs, state, false);
 
+                state.rewind_var(orig_len);
+
                 if matches!(b.lhs, Expr::BoolConstant(false, ..)) && !b.rhs.is_unit() {
                     b.rhs = Expr::Unit(b.rhs.position());
                     state.set_dirty();
@@ -731,6 +1002,7 @@ fn optimize_stmt(stmt: &mut Stmt, state: &mut OptimizerState, preserve_result: b
                 if *def_case != Some(index)
                     && cases.values().flat_map(|c| c.iter()).all(|&n| n != index)
                     && ranges.iter().all(|r| r.index() != index)
+                    && patterns.iter().all(|&(.., n)| n != index)
                     && !b.rhs.is_unit()
                 {
                     b.rhs = Expr::Unit(b.rhs.position());
@@ -773,6 +1045,8 @@ fn optimize_stmt(stmt: &mut Stmt, state: &mut OptimizerState, preserve_result: b
         Stmt::Var(x, options, ..) if !options.intersects(ASTFlags::CONSTANT) => {
             optimize_expr(&mut x.1, state, false);
         }
+        // let pattern = expr;
+        Stmt::Destructure(x, ..) => optimize_expr(&mut x.1, state, false),
         // import expr as var;
         #[cfg(not(feature = "no_module"))]
         Stmt::Import(x, ..) => optimize_expr(&mut x.0, state, false),
@@ -908,13 +1182,6 @@ fn move_constant_arg(arg_expr: &mut Expr) -> bool {
 
 /// Optimize an [expression][Expr].
 fn optimize_expr(expr: &mut Expr, state: &mut OptimizerState, _chaining: bool) {
-    // These keywords are handled specially
-    const DONT_EVAL_KEYWORDS: &[&str] = &[
-        KEYWORD_PRINT, // side effects
-        KEYWORD_DEBUG, // side effects
-        KEYWORD_EVAL,  // arbitrary scripts
-    ];
-
     match expr {
         // {}
         Expr::Stmt(x) if x.is_empty() => { state.set_dirty(); *expr = Expr::Unit(x.position()) }
@@ -1058,7 +1325,21 @@ fn optimize_expr(expr: &mut Expr, state: &mut OptimizerState, _chaining: bool) {
         // `... ${const} ...`
         Expr::InterpolatedString(..) if expr.is_constant() => {
             state.set_dirty();
-            *expr = Expr::StringConstant(expr.get_literal_value().unwrap().cast::<ImmutableString>(), expr.position());
+
+            let mut s = String::new();
+
+            if let Expr::InterpolatedString(x, ..) = expr {
+                for segment in x.iter() {
+                    match segment {
+                        // Floating-point numbers are formatted as configured in the engine
+                        #[cfg(not(feature = "no_float"))]
+                        Expr::FloatConstant(f, ..) => s.push_str(&state.engine.float_format().format(**f)),
+                        _ => s.push_str(&segment.get_literal_value().unwrap().to_string()),
+                    }
+                }
+            }
+
+            *expr = Expr::StringConstant(s.into(), expr.position());
         }
         // `... ${ ... } ...`
         Expr::InterpolatedString(x, ..) => {
@@ -1236,14 +1517,36 @@ fn optimize_expr(expr: &mut Expr, state: &mut OptimizerState, _chaining: bool) {
         Expr::FnCall(x, pos) if state.optimization_level == OptimizationLevel::Full // full optimizations
                                 && x.constant_args() // all arguments are constants
         => {
-            // First search for script-defined functions (can override built-in)
+            let arg_values = &mut x.args.iter().map(Expr::get_literal_value).collect::<Option<FnArgsVec<_>>>().unwrap();
+
+            // First search for script-defined functions (can override built-in),
+            // resolving overloads with typed parameters by the types of the arguments
+            #[cfg(not(feature = "no_function"))]
+            let fn_def = if x.hashes.is_native_only() {
+                None
+            } else {
+                let arg_types = arg_values.iter().map(|a| state.engine.map_type_name(a.type_name()));
+                state.global.lib.iter().find_map(|m| m.get_script_fn_by_types(&x.name, arg_types.clone())).cloned()
+            };
             let _has_script_fn = false;
             #[cfg(not(feature = "no_function"))]
-            let _has_script_fn = !x.hashes.is_native_only() && state.global.lib.iter().find_map(|m| m.get_script_fn(&x.name, x.args.len())).is_some();
+            let _has_script_fn = fn_def.is_some();
 
-            if !_has_script_fn {
-                let arg_values = &mut x.args.iter().map(Expr::get_literal_value).collect::<Option<FnArgsVec<_>>>().unwrap();
+            // Evaluate small pure script-defined functions
+            #[cfg(not(feature = "no_function"))]
+            if let Some(fn_def) = fn_def {
+                let max_size = state.engine.optimizer_options.max_inline_fn_size;
+
+                if max_size > 0 && is_const_evaluable(state.engine, &fn_def, &state.global.lib, max_size) {
+                    if let Some(r) = state.call_script_fn_with_const_args(&fn_def, arg_values) {
+                        state.set_dirty();
+                        *expr = Expr::from_dynamic(r, *pos);
+                        return;
+                    }
+                }
+            }
 
+            if !_has_script_fn {
                 let result = match x.name.as_str() {
                     KEYWORD_TYPE_OF if arg_values.len() == 1 => Some(state.engine.map_type_name(arg_values[0].type_name()).into()),
                     #[cfg(not(feature = "no_closure"))]
@@ -1284,6 +1587,10 @@ fn optimize_expr(expr: &mut Expr, state: &mut OptimizerState, _chaining: bool) {
             if x.scope_may_be_changed {
                 state.propagate_constants = false;
             }
+            // Declared variables shadow any constants of the same names
+            for name in &x.declared_vars {
+                state.push_var(name.clone(), None);
+            }
             // Do not optimize custom syntax expressions as you won't know how they would be called
         }
 
@@ -1349,6 +1656,12 @@ impl Engine {
             .flat_map(|m| m.iter_var())
             .for_each(|(name, value)| state.push_var(name.into(), Some(Cow::Borrowed(value))));
 
+        // Add constants from the prelude
+        self.prelude
+            .iter()
+            .flat_map(|m| m.iter_var())
+            .for_each(|(name, value)| state.push_var(name.into(), Some(Cow::Borrowed(value))));
+
         // Add constants and variables from the scope
         state
             .scope
@@ -1368,8 +1681,33 @@ impl Engine {
         optimize_stmt_block(statements, &mut state, true, false, true)
     }
 
-    /// Optimize a collection of statements and functions into an [`AST`].
+    /// Optimize a collection of statements and functions into an [`AST`] by running all the
+    /// optimizer passes in order.
     pub(crate) fn optimize_into_ast(
+        &self,
+        scope: Option<&Scope>,
+        statements: StmtBlockContainer,
+        #[cfg(not(feature = "no_function"))] functions: impl IntoIterator<
+            Item = crate::Shared<crate::ast::ScriptFuncDef>,
+        >,
+        optimization_level: OptimizationLevel,
+    ) -> AST {
+        let mut ast = AST::new(
+            statements,
+            #[cfg(not(feature = "no_function"))]
+            crate::Module::from(functions),
+        );
+
+        for pass in self.optimizer_passes() {
+            pass.optimize(self, scope, &mut ast, optimization_level);
+        }
+
+        ast
+    }
+
+    /// Optimize a collection of statements and functions into an [`AST`] with the built-in
+    /// optimizations.
+    fn optimize_script(
         &self,
         scope: Option<&Scope>,
         statements: StmtBlockContainer,
@@ -1383,13 +1721,20 @@ impl Engine {
         let lib: crate::Shared<_> = if optimization_level == OptimizationLevel::None {
             crate::Module::from(functions).into()
         } else {
-            // We only need the script library's signatures for optimization purposes
-            let lib2 = crate::Module::from(
-                functions
-                    .as_ref()
-                    .iter()
-                    .map(|fn_def| fn_def.clone_function_signatures().into()),
-            );
+            // We only need the script library's signatures for optimization purposes,
+            // unless calls to script-defined functions may be evaluated
+            let lib2 = if optimization_level == OptimizationLevel::Full
+                && self.optimizer_options.max_inline_fn_size > 0
+            {
+                crate::Module::from(functions.as_ref().iter().cloned())
+            } else {
+                crate::Module::from(
+                    functions
+                        .as_ref()
+                        .iter()
+                        .map(|fn_def| fn_def.clone_function_signatures().into()),
+                )
+            };
 
             let lib2 = &[lib2.into()];
 
@@ -1,4 +1,6 @@
 use crate::plugin::*;
+#[cfg(not(feature = "unchecked"))]
+use crate::ArithmeticMode;
 use crate::{def_package, Position, RhaiError, RhaiResultOf, ERR, INT};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
@@ -15,70 +17,140 @@ pub fn make_err(msg: impl Into<String>) -> RhaiError {
 
 macro_rules! gen_arithmetic_functions {
     ($root:ident => $($arg_type:ident),+) => {
-        #[allow(non_snake_case)]
+        // Functions for `INT` are not registered; only the helpers are used by built-in operators
+        #[allow(non_snake_case, dead_code)]
         pub mod $root { $(pub mod $arg_type {
             use super::super::*;
 
+            /// Add two numbers under an [`ArithmeticMode`].
+            #[cfg(not(feature = "unchecked"))]
+            pub fn add_with(mode: ArithmeticMode, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                x.checked_add(y).map_or_else(|| match mode {
+                    ArithmeticMode::Wrapping => Ok(x.wrapping_add(y)),
+                    ArithmeticMode::Saturating => Ok(x.saturating_add(y)),
+                    _ => Err(make_err(format!("Addition overflow: {x} + {y}"))),
+                }, Ok)
+            }
+            /// Subtract two numbers under an [`ArithmeticMode`].
+            #[cfg(not(feature = "unchecked"))]
+            pub fn subtract_with(mode: ArithmeticMode, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                x.checked_sub(y).map_or_else(|| match mode {
+                    ArithmeticMode::Wrapping => Ok(x.wrapping_sub(y)),
+                    ArithmeticMode::Saturating => Ok(x.saturating_sub(y)),
+                    _ => Err(make_err(format!("Subtraction overflow: {x} - {y}"))),
+                }, Ok)
+            }
+            /// Multiply two numbers under an [`ArithmeticMode`].
+            #[cfg(not(feature = "unchecked"))]
+            pub fn multiply_with(mode: ArithmeticMode, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                x.checked_mul(y).map_or_else(|| match mode {
+                    ArithmeticMode::Wrapping => Ok(x.wrapping_mul(y)),
+                    ArithmeticMode::Saturating => Ok(x.saturating_mul(y)),
+                    _ => Err(make_err(format!("Multiplication overflow: {x} * {y}"))),
+                }, Ok)
+            }
+            /// Divide two numbers under an [`ArithmeticMode`].
+            #[cfg(not(feature = "unchecked"))]
+            pub fn divide_with(mode: ArithmeticMode, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                // Detect division by zero
+                if y == 0 {
+                    return Err(make_err(format!("Division by zero: {x} / {y}")));
+                }
+                x.checked_div(y).map_or_else(|| match mode {
+                    ArithmeticMode::Wrapping => Ok(x.wrapping_div(y)),
+                    ArithmeticMode::Saturating => Ok(x.saturating_div(y)),
+                    _ => Err(make_err(format!("Division overflow: {x} / {y}"))),
+                }, Ok)
+            }
+            /// Find the remainder of dividing two numbers under an [`ArithmeticMode`].
+            #[cfg(not(feature = "unchecked"))]
+            pub fn modulo_with(mode: ArithmeticMode, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                x.checked_rem(y).map_or_else(|| match mode {
+                    // The remainder of an overflowing division is always zero
+                    ArithmeticMode::Wrapping | ArithmeticMode::Saturating if y != 0 => Ok(0),
+                    _ => Err(make_err(format!("Modulo division by zero or overflow: {x} % {y}"))),
+                }, Ok)
+            }
+            /// Raise a number to a power under an [`ArithmeticMode`].
+            #[cfg(not(feature = "unchecked"))]
+            pub fn power_with(mode: ArithmeticMode, x: $arg_type, y: INT) -> RhaiResultOf<$arg_type> {
+                if y < 0 {
+                    return Err(make_err(format!("Integer raised to a negative power: {x} ** {y}")));
+                }
+                let exp = if cfg!(not(feature = "only_i32")) && y > (u32::MAX as INT) {
+                    None
+                } else {
+                    Some(y as u32)
+                };
+
+                match (mode, exp.and_then(|exp| x.checked_pow(exp))) {
+                    (_, Some(r)) => Ok(r),
+                    (ArithmeticMode::Wrapping, _) => Ok(match exp {
+                        Some(exp) => x.wrapping_pow(exp),
+                        None => {
+                            // Exponentiation by squaring for exponents beyond u32
+                            let (mut base, mut exp, mut r): ($arg_type, INT, $arg_type) = (x, y, 1);
+                            while exp > 0 {
+                                if exp & 1 == 1 {
+                                    r = r.wrapping_mul(base);
+                                }
+                                base = base.wrapping_mul(base);
+                                exp >>= 1;
+                            }
+                            r
+                        }
+                    }),
+                    (ArithmeticMode::Saturating, _) => {
+                        // Keep the parity of the exponent for the sign of the result
+                        Ok(x.saturating_pow(exp.unwrap_or(u32::MAX - (1 - (y & 1)) as u32)))
+                    }
+                    _ => Err(make_err(format!("Exponential overflow: {x} ** {y}"))),
+                }
+            }
+
             #[export_module]
             pub mod functions {
                 #[rhai_fn(name = "+", return_raw)]
-                pub fn add(x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
-                    if cfg!(not(feature = "unchecked")) {
-                        x.checked_add(y).ok_or_else(|| make_err(format!("Addition overflow: {x} + {y}")))
-                    } else {
-                        Ok(x + y)
-                    }
+                pub fn add(_ctx: NativeCallContext, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                    #[cfg(not(feature = "unchecked"))]
+                    return super::add_with(_ctx.engine().arithmetic_mode(), x, y);
+                    #[cfg(feature = "unchecked")]
+                    return Ok(x + y);
                 }
                 #[rhai_fn(name = "-", return_raw)]
-                pub fn subtract(x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
-                    if cfg!(not(feature = "unchecked")) {
-                        x.checked_sub(y).ok_or_else(|| make_err(format!("Subtraction overflow: {x} - {y}")))
-                    } else {
-                        Ok(x - y)
-                    }
+                pub fn subtract(_ctx: NativeCallContext, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                    #[cfg(not(feature = "unchecked"))]
+                    return super::subtract_with(_ctx.engine().arithmetic_mode(), x, y);
+                    #[cfg(feature = "unchecked")]
+                    return Ok(x - y);
                 }
                 #[rhai_fn(name = "*", return_raw)]
-                pub fn multiply(x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
-                    if cfg!(not(feature = "unchecked")) {
-                        x.checked_mul(y).ok_or_else(|| make_err(format!("Multiplication overflow: {x} * {y}")))
-                    } else {
-                        Ok(x * y)
-                    }
+                pub fn multiply(_ctx: NativeCallContext, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                    #[cfg(not(feature = "unchecked"))]
+                    return super::multiply_with(_ctx.engine().arithmetic_mode(), x, y);
+                    #[cfg(feature = "unchecked")]
+                    return Ok(x * y);
                 }
                 #[rhai_fn(name = "/", return_raw)]
-                pub fn divide(x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
-                    if cfg!(not(feature = "unchecked")) {
-                        // Detect division by zero
-                        if y == 0 {
-                            Err(make_err(format!("Division by zero: {x} / {y}")))
-                        } else {
-                            x.checked_div(y).ok_or_else(|| make_err(format!("Division overflow: {x} / {y}")))
-                        }
-                    } else {
-                        Ok(x / y)
-                    }
+                pub fn divide(_ctx: NativeCallContext, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                    #[cfg(not(feature = "unchecked"))]
+                    return super::divide_with(_ctx.engine().arithmetic_mode(), x, y);
+                    #[cfg(feature = "unchecked")]
+                    return Ok(x / y);
                 }
                 #[rhai_fn(name = "%", return_raw)]
-                pub fn modulo(x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
-                    if cfg!(not(feature = "unchecked")) {
-                        x.checked_rem(y).ok_or_else(|| make_err(format!("Modulo division by zero or overflow: {x} % {y}")))
-                    } else {
-                        Ok(x % y)
-                    }
+                pub fn modulo(_ctx: NativeCallContext, x: $arg_type, y: $arg_type) -> RhaiResultOf<$arg_type> {
+                    #[cfg(not(feature = "unchecked"))]
+                    return super::modulo_with(_ctx.engine().arithmetic_mode(), x, y);
+                    #[cfg(feature = "unchecked")]
+                    return Ok(x % y);
                 }
                 #[rhai_fn(name = "**", return_raw)]
-                pub fn power(x: $arg_type, y: INT) -> RhaiResultOf<$arg_type> {
-                    if cfg!(not(feature = "unchecked")) {
-                        if cfg!(not(feature = "only_i32")) && y > (u32::MAX as INT) {
-                            Err(make_err(format!("Exponential overflow: {x} ** {y}")))
-                        } else if y < 0 {
-                            Err(make_err(format!("Integer raised to a negative power: {x} ** {y}")))
-                        } else {
-                            x.checked_pow(y as u32).ok_or_else(|| make_err(format!("Exponential overflow: {x} ** {y}")))
-                        }
-                    } else {
-                        Ok(x.pow(y as u32))
-                    }
+                pub fn power(_ctx: NativeCallContext, x: $arg_type, y: INT) -> RhaiResultOf<$arg_type> {
+                    #[cfg(not(feature = "unchecked"))]
+                    return super::power_with(_ctx.engine().arithmetic_mode(), x, y);
+                    #[cfg(feature = "unchecked")]
+                    return Ok(x.pow(y as u32));
                 }
 
                 #[rhai_fn(name = "<<")]
@@ -151,15 +223,33 @@ macro_rules! gen_signed_functions {
         pub mod $root { $(pub mod $arg_type {
             use super::super::*;
 
+            /// Negate a number under an [`ArithmeticMode`].
+            #[cfg(not(feature = "unchecked"))]
+            pub fn neg_with(mode: ArithmeticMode, x: $arg_type) -> RhaiResultOf<$arg_type> {
+                x.checked_neg().map_or_else(|| match mode {
+                    ArithmeticMode::Wrapping => Ok(x.wrapping_neg()),
+                    ArithmeticMode::Saturating => Ok(x.saturating_neg()),
+                    _ => Err(make_err(format!("Negation overflow: -{x}"))),
+                }, Ok)
+            }
+            /// Get the absolute value of a number under an [`ArithmeticMode`].
+            #[cfg(not(feature = "unchecked"))]
+            pub fn abs_with(mode: ArithmeticMode, x: $arg_type) -> RhaiResultOf<$arg_type> {
+                x.checked_abs().map_or_else(|| match mode {
+                    ArithmeticMode::Wrapping => Ok(x.wrapping_abs()),
+                    ArithmeticMode::Saturating => Ok(x.saturating_abs()),
+                    _ => Err(make_err(format!("Negation overflow: -{x}"))),
+                }, Ok)
+            }
+
             #[export_module]
             pub mod functions {
                 #[rhai_fn(name = "-", return_raw)]
-                pub fn neg(x: $arg_type) -> RhaiResultOf<$arg_type> {
-                    if cfg!(not(feature = "unchecked")) {
-                        x.checked_neg().ok_or_else(|| make_err(format!("Negation overflow: -{x}")))
-                    } else {
-                        Ok(-x)
-                    }
+                pub fn neg(_ctx: NativeCallContext, x: $arg_type) -> RhaiResultOf<$arg_type> {
+                    #[cfg(not(feature = "unchecked"))]
+                    return super::neg_with(_ctx.engine().arithmetic_mode(), x);
+                    #[cfg(feature = "unchecked")]
+                    return Ok(-x);
                 }
                 #[rhai_fn(name = "+")]
                 pub const fn plus(x: $arg_type) -> $arg_type {
@@ -167,12 +257,11 @@ macro_rules! gen_signed_functions {
                 }
                 /// Return the absolute value of the number.
                 #[rhai_fn(return_raw)]
-                pub fn abs(x: $arg_type) -> RhaiResultOf<$arg_type> {
-                    if cfg!(not(feature = "unchecked")) {
-                        x.checked_abs().ok_or_else(|| make_err(format!("Negation overflow: -{x}")))
-                    } else {
-                        Ok(x.abs())
-                    }
+                pub fn abs(_ctx: NativeCallContext, x: $arg_type) -> RhaiResultOf<$arg_type> {
+                    #[cfg(not(feature = "unchecked"))]
+                    return super::abs_with(_ctx.engine().arithmetic_mode(), x);
+                    #[cfg(feature = "unchecked")]
+                    return Ok(x.abs());
                 }
                 /// Return the sign (as an integer) of the number according to the following:
                 ///
@@ -4,7 +4,7 @@ use crate::eval::{calc_index, calc_offset_len};
 use crate::plugin::*;
 use crate::{
     def_package, Array, Blob, Dynamic, ExclusiveRange, InclusiveRange, NativeCallContext,
-    RhaiResultOf, INT, INT_BYTES, MAX_USIZE_INT,
+    RhaiResultOf, Shared, SharedBlob, INT, INT_BYTES, MAX_USIZE_INT,
 };
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
@@ -19,6 +19,7 @@ def_package! {
         lib.set_standard_lib(true);
 
         combine_with_exported_module!(lib, "blob", blob_functions);
+        combine_with_exported_module!(lib, "shared_blob", shared_blob_functions);
         combine_with_exported_module!(lib, "parse_int", parse_int_functions);
         combine_with_exported_module!(lib, "write_int", write_int_functions);
         combine_with_exported_module!(lib, "write_string", write_string_functions);
@@ -31,6 +32,12 @@ def_package! {
 
         // Register blob iterator
         lib.set_iterable::<Blob>();
+
+        // Register shared blob iterator
+        lib.set_iter(TypeId::of::<SharedBlob>(), |value| {
+            let blob = value.cast::<SharedBlob>();
+            Box::new((0..blob.len()).map(move |i| Dynamic::from_int(blob[i] as INT)))
+        });
     }
 }
 
@@ -975,6 +982,108 @@ pub mod blob_functions {
     }
 }
 
+#[export_module]
+mod shared_blob_functions {
+    /// Convert the BLOB into a shared BLOB, which can be copied without copying its bytes.
+    ///
+    /// A shared BLOB cannot be modified.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let b = blob(5, 0x42);
+    ///
+    /// let x = b.to_shared();
+    ///
+    /// print(x[0]);        // prints 66
+    /// ```
+    pub fn to_shared(blob: Blob) -> SharedBlob {
+        blob.into()
+    }
+    /// Convert the shared BLOB into a BLOB.
+    ///
+    /// The bytes are copied unless this is the only copy of the shared BLOB.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let x = blob(5, 0x42).to_shared();
+    ///
+    /// let b = x.to_blob();
+    ///
+    /// b[0] = 0;
+    ///
+    /// print(b);           // prints "[0042424242]"
+    /// ```
+    pub fn to_blob(blob: SharedBlob) -> Blob {
+        Shared::try_unwrap(blob).unwrap_or_else(|blob| blob.as_ref().clone())
+    }
+    /// Convert the shared BLOB into an array of integers.
+    #[rhai_fn(pure)]
+    pub fn to_array(blob: &mut SharedBlob) -> Array {
+        blob.iter().map(|&ch| (ch as INT).into()).collect()
+    }
+    /// Convert the shared BLOB into a string.
+    ///
+    /// Invalid UTF-8 sequences are replaced by the Unicode replacement character.
+    #[rhai_fn(pure)]
+    pub fn as_string(blob: &mut SharedBlob) -> String {
+        String::from_utf8_lossy(blob).into_owned()
+    }
+    /// Return the length of the shared BLOB.
+    #[rhai_fn(name = "len", get = "len", pure)]
+    pub fn len(blob: &mut SharedBlob) -> INT {
+        blob.len() as INT
+    }
+    /// Return true if the shared BLOB is empty.
+    #[rhai_fn(name = "is_empty", get = "is_empty", pure)]
+    pub fn is_empty(blob: &mut SharedBlob) -> bool {
+        blob.is_empty()
+    }
+    /// Return `true` if the shared BLOB contains a specified byte value.
+    #[rhai_fn(pure)]
+    pub fn contains(blob: &mut SharedBlob, value: INT) -> bool {
+        #[allow(clippy::cast_sign_loss)]
+        blob.contains(&((value & 0x0000_00ff) as u8))
+    }
+    /// Get the byte value at the `index` position in the shared BLOB.
+    ///
+    /// * If `index` < 0, position counts from the end of the BLOB (`-1` is the last element).
+    /// * If `index` < -length of BLOB, zero is returned.
+    /// * If `index` ≥ length of BLOB, zero is returned.
+    #[rhai_fn(pure)]
+    pub fn get(blob: &mut SharedBlob, index: INT) -> INT {
+        let (index, ..) = calc_offset_len(blob.len(), index, 0);
+        blob.get(index).map_or(0, |&b| b as INT)
+    }
+    /// Copy an exclusive `range` of the shared BLOB into a new BLOB.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let x = blob(5, 0x42).to_shared();
+    ///
+    /// print(x.extract(1..3));     // prints "[4242]"
+    /// ```
+    #[rhai_fn(name = "extract", pure)]
+    pub fn extract_range(blob: &mut SharedBlob, range: ExclusiveRange) -> Blob {
+        let start = INT::max(range.start, 0);
+        let end = INT::max(range.end, start);
+        let (start, len) = calc_offset_len(blob.len(), start, end - start);
+        blob[start..start + len].to_vec()
+    }
+    /// Return `true` if two shared BLOB's contain the same bytes.
+    #[rhai_fn(name = "==", pure)]
+    pub fn equals(blob1: &mut SharedBlob, blob2: SharedBlob) -> bool {
+        **blob1 == *blob2
+    }
+    /// Return `true` if two shared BLOB's do not contain the same bytes.
+    #[rhai_fn(name = "!=", pure)]
+    pub fn not_equals(blob1: &mut SharedBlob, blob2: SharedBlob) -> bool {
+        **blob1 != *blob2
+    }
+}
+
 #[export_module]
 mod parse_int_functions {
     #[inline]
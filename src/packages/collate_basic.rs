@@ -0,0 +1,141 @@
+#![cfg(feature = "icu")]
+
+use crate::func::{locked_read, locked_write};
+use crate::plugin::*;
+use crate::{def_package, ImmutableString, Position, RhaiResultOf, Shared, ERR, INT};
+use icu_collator::{Collator, CollatorOptions};
+use std::cmp::Ordering;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+#[cfg(not(feature = "no_index"))]
+use crate::Array;
+
+/// Maximum number of locale collators cached by an [`Engine`].
+///
+/// The cache is cleared when it is full.
+const MAX_COLLATOR_CACHE_SIZE: usize = 64;
+
+def_package! {
+    /// Package of locale-aware string collation utilities.
+    ///
+    /// Strings are compared according to the rules of a locale via the
+    /// [`icu_collator`](https://crates.io/crates/icu_collator) crate, instead of byte-wise.
+    pub CollationPackage(lib) {
+        lib.set_standard_lib(true);
+
+        combine_with_exported_module!(lib, "collate", collate_functions);
+    }
+}
+
+/// Get the collator for a locale, using the cache of collators in the [`Engine`].
+fn get_collator(
+    ctx: &NativeCallContext,
+    locale: ImmutableString,
+) -> RhaiResultOf<Shared<Collator>> {
+    let cache = &ctx.engine().collator_cache;
+
+    if let Some(collator) = locked_read(cache).and_then(|c| c.get(&locale).cloned()) {
+        return Ok(collator);
+    }
+
+    let invalid_locale = |err: String| {
+        ERR::ErrorRuntime(
+            format!("Invalid locale '{locale}': {err}").into(),
+            Position::NONE,
+        )
+    };
+
+    let id = locale
+        .parse::<icu_locid::Locale>()
+        .map_err(|err| invalid_locale(err.to_string()))?;
+    let collator = Collator::try_new(&(&id).into(), CollatorOptions::new())
+        .map(Shared::new)
+        .map_err(|err| invalid_locale(err.to_string()))?;
+
+    if let Some(mut c) = locked_write(cache) {
+        if c.len() >= MAX_COLLATOR_CACHE_SIZE {
+            c.clear();
+        }
+        c.insert(locale, collator.clone());
+    }
+
+    Ok(collator)
+}
+
+#[export_module]
+mod collate_functions {
+    /// Compare two strings according to the collation rules of a `locale`
+    /// (e.g. `"en"`, `"de-AT"`, `"sv"`).
+    ///
+    /// Return -1 if `a` sorts before `b`, 1 if `a` sorts after `b`, and 0 if they are equivalent.
+    ///
+    /// An error is raised if the `locale` is not a valid locale identifier.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// print(compare("a", "B", "en"));     // prints -1 (byte-wise ordering puts "B" first)
+    ///
+    /// print(compare("ä", "z", "de"));     // prints -1
+    ///
+    /// print(compare("ä", "z", "sv"));     // prints 1
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn compare(
+        ctx: NativeCallContext,
+        a: &str,
+        b: &str,
+        locale: ImmutableString,
+    ) -> RhaiResultOf<INT> {
+        Ok(match get_collator(&ctx, locale)?.compare(a, b) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        })
+    }
+
+    /// Sort an array of strings according to the collation rules of a `locale`
+    /// (e.g. `"en"`, `"de-AT"`, `"sv"`).
+    ///
+    /// All elements in the array must be strings.
+    ///
+    /// An error is raised if the `locale` is not a valid locale identifier.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let x = ["zebra", "Äpfel", "apple", "Zoo"];
+    ///
+    /// x.sort("de");
+    ///
+    /// print(x);       // prints ["Äpfel", "apple", "zebra", "Zoo"]
+    /// ```
+    #[cfg(not(feature = "no_index"))]
+    #[rhai_fn(name = "sort", return_raw)]
+    pub fn sort_with_locale(
+        ctx: NativeCallContext,
+        array: &mut Array,
+        locale: ImmutableString,
+    ) -> RhaiResultOf<()> {
+        if array.iter().any(|v| !v.is_string()) {
+            return Err(ERR::ErrorFunctionNotFound(
+                "sort() with a locale can only be called on an array of strings".into(),
+                Position::NONE,
+            )
+            .into());
+        }
+
+        let collator = get_collator(&ctx, locale)?;
+
+        if array.len() > 1 {
+            array.sort_by(|a, b| {
+                let a = &*a.as_immutable_string_ref().unwrap();
+                let b = &*b.as_immutable_string_ref().unwrap();
+                collator.compare(a, b)
+            });
+        }
+
+        Ok(())
+    }
+}
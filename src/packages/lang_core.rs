@@ -16,10 +16,18 @@ def_package! {
 
         combine_with_exported_module!(lib, "core", core_functions);
 
+        #[cfg(not(feature = "no_std"))]
+        #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+        combine_with_exported_module!(lib, "stream", stream_functions);
+
         #[cfg(not(feature = "no_function"))]
         #[cfg(not(feature = "no_index"))]
         #[cfg(not(feature = "no_object"))]
         combine_with_exported_module!(lib, "reflection", reflection_functions);
+
+        #[cfg(feature = "parallel")]
+        #[cfg(not(feature = "no_std"))]
+        combine_with_exported_module!(lib, "parallel", parallel_functions);
     }
 }
 
@@ -69,6 +77,43 @@ mod core_functions {
 
         Ok(std::mem::take(value))
     }
+    /// Deeply freeze a value, making it (and all the items and properties it contains)
+    /// permanently read-only, and return it.
+    ///
+    /// Copies of a frozen value are also frozen. Any attempt to modify a frozen value raises an
+    /// error, which is useful for protecting data passed to untrusted code.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let config = freeze(#{ name: "test", limits: [1, 2, 3] });
+    ///
+    /// config.name = "hello";      // <- error: assignment to frozen value
+    ///
+    /// config.limits.push(4);      // <- error: cannot modify frozen value
+    /// ```
+    #[rhai_fn(pure, volatile)]
+    pub fn freeze(value: &mut Dynamic) -> Dynamic {
+        value.freeze();
+        value.clone()
+    }
+    /// Return `true` if a value is frozen.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let x = [1, 2, 3];
+    ///
+    /// print(is_frozen(x));        // prints false
+    ///
+    /// freeze(x);
+    ///
+    /// print(is_frozen(x));        // prints true
+    /// ```
+    #[rhai_fn(pure)]
+    pub fn is_frozen(value: &mut Dynamic) -> bool {
+        value.is_frozen()
+    }
     /// Return the _tag_ of a `Dynamic` value.
     ///
     /// # Example
@@ -163,6 +208,64 @@ mod core_functions {
         std::thread::sleep(std::time::Duration::from_secs(seconds as u64));
     }
 
+    /// Return the value of a configuration flag set by the host, or `false` if it is not set.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// if cfg("redis") {
+    ///     print("caching in Redis");
+    /// }
+    /// ```
+    #[rhai_fn(name = "cfg")]
+    pub fn cfg_flag(ctx: NativeCallContext, name: &str) -> Dynamic {
+        ctx.engine()
+            .cfg_flag(name)
+            .cloned()
+            .unwrap_or(Dynamic::FALSE)
+    }
+    /// Return an object map with information on the engine running the script:
+    ///
+    /// * `version`: version of Rhai
+    /// * `features`: array of names of the Cargo features of Rhai that are turned on
+    /// * `cfg`: object map of all the configuration flags set by the host
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let info = engine_info();
+    ///
+    /// print(info.version);        // prints "1.21.0"
+    ///
+    /// if "no_float" in info.features {
+    ///     print("floating-point numbers are not supported");
+    /// }
+    /// ```
+    #[cfg(not(feature = "no_object"))]
+    pub fn engine_info(ctx: NativeCallContext) -> crate::Map {
+        let engine = ctx.engine();
+        let mut info = crate::Map::new();
+
+        info.insert("version".into(), env!("CARGO_PKG_VERSION").into());
+
+        #[cfg(not(feature = "no_index"))]
+        info.insert(
+            "features".into(),
+            crate::api::capabilities::enabled_features()
+                .map(Dynamic::from)
+                .collect::<crate::Array>()
+                .into(),
+        );
+
+        let cfg = engine
+            .iter_cfg_flags()
+            .map(|(name, value)| (name.into(), value.clone()))
+            .collect::<crate::Map>();
+        info.insert("cfg".into(), cfg.into());
+
+        info
+    }
+
     /// Parse a JSON string into a value.
     ///
     /// # Example
@@ -186,6 +289,291 @@ mod core_functions {
 
         out
     }
+
+    /// Return `true` if the value exposes a method or property with the specified name.
+    ///
+    /// A method or property is exposed if the value is an object map containing it as a property,
+    /// or a function (or property getter) with that name is defined for the value's type.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let obj = #{ update: |dt| this.t += dt, t: 0 };
+    ///
+    /// print(implements(obj, "update"));   // prints true
+    ///
+    /// print(obj.implements("draw"));      // prints false
+    /// ```
+    #[rhai_fn(name = "implements", pure)]
+    pub fn implements(ctx: NativeCallContext, value: &mut Dynamic, name: &str) -> bool {
+        #[cfg(not(feature = "no_function"))]
+        let lib = ctx.iter_namespaces();
+        #[cfg(feature = "no_function")]
+        let lib = None;
+
+        ctx.engine().has_member(lib, value, name)
+    }
+    /// Return `true` if the value exposes all the methods and properties with the specified names.
+    ///
+    /// A method or property is exposed if the value is an object map containing it as a property,
+    /// or a function (or property getter) with that name is defined for the value's type.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let obj = #{ update: |dt| this.t += dt, draw: || print(this.t), t: 0 };
+    ///
+    /// print(implements(obj, ["update", "draw"]));         // prints true
+    ///
+    /// print(obj.implements(["update", "draw", "size"]));  // prints false
+    /// ```
+    #[cfg(not(feature = "no_index"))]
+    #[rhai_fn(name = "implements", pure)]
+    pub fn implements_all(
+        ctx: NativeCallContext,
+        value: &mut Dynamic,
+        names: crate::Array,
+    ) -> bool {
+        let engine = ctx.engine();
+
+        names.iter().all(|name| {
+            name.read_lock::<ImmutableString>().map_or(false, |name| {
+                #[cfg(not(feature = "no_function"))]
+                let lib = ctx.iter_namespaces();
+                #[cfg(feature = "no_function")]
+                let lib = None;
+
+                engine.has_member(lib, value, &name)
+            })
+        })
+    }
+}
+
+/// Run a stream function on a copy of the current [`GlobalRuntimeState`][crate::eval::GlobalRuntimeState].
+///
+/// Operations performed on the copy (including those by callbacks) are charged back to the
+/// caller when the stream function returns, even if it fails.
+#[cfg(not(feature = "no_std"))]
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+fn run_stream<T>(
+    ctx: &crate::NativeCallContext,
+    f: impl FnOnce(&mut crate::eval::GlobalRuntimeState) -> RhaiResultOf<T>,
+) -> RhaiResultOf<T> {
+    let global = &mut ctx.global_runtime_state().clone();
+
+    let result = f(global);
+
+    #[cfg(not(feature = "unchecked"))]
+    ctx.global_runtime_state().add_pending_operations(
+        global
+            .num_operations
+            .saturating_sub(ctx.global_runtime_state().num_operations),
+    );
+
+    result
+}
+
+/// Deliver a record to a callback, returning `false` if the callback asks to stop.
+#[cfg(not(feature = "no_std"))]
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+fn deliver_stream_record(
+    ctx: &crate::NativeCallContext,
+    global: &mut crate::eval::GlobalRuntimeState,
+    callback: &crate::FnPtr,
+    record: Dynamic,
+    index: INT,
+) -> RhaiResultOf<bool> {
+    // Operations performed by previous callbacks count towards the operations limit.
+    #[cfg(not(feature = "unchecked"))]
+    global.absorb_pending_operations();
+
+    // Each record counts as an operation so that the operations limit (and the progress
+    // callback) can throttle or terminate a long-running stream.
+    ctx.engine().track_operation(global, ctx.position())?;
+
+    let context = crate::NativeCallContext::from((
+        ctx.engine(),
+        ctx.fn_name(),
+        ctx.source(),
+        &*global,
+        ctx.position(),
+    ));
+
+    let result = callback.call_raw_with_extra_args(
+        ctx.fn_name(),
+        &context,
+        None,
+        [record],
+        [index.into()],
+        None,
+    )?;
+
+    Ok(result.as_bool().unwrap_or(true))
+}
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+#[export_module]
+mod stream_functions {
+    use crate::{FnPtr, StreamReader};
+
+    /// Parse a stream of newline-delimited JSON values, calling a function for each record.
+    ///
+    /// Empty lines are skipped.
+    ///
+    /// The function is called with the parsed record and, optionally, the record index.
+    /// Returning `false` from the function stops the stream.
+    ///
+    /// The number of records delivered is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// // 'input' is a stream reader provided by the host
+    /// let total = 0;
+    ///
+    /// input.parse_json_stream(|record, index| total += record.amount);
+    ///
+    /// print(total);
+    /// ```
+    #[cfg(not(feature = "no_object"))]
+    #[rhai_fn(return_raw)]
+    pub fn parse_json_stream(
+        ctx: NativeCallContext,
+        reader: &mut StreamReader,
+        callback: FnPtr,
+    ) -> RhaiResultOf<INT> {
+        super::run_stream(&ctx, |global| {
+            let mut line = String::new();
+            let mut count: INT = 0;
+
+            while reader.read_line(&mut line)? {
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let record = super::core_functions::parse_json(
+                    NativeCallContext::from((
+                        ctx.engine(),
+                        "parse_json",
+                        ctx.source(),
+                        &*global,
+                        ctx.position(),
+                    )),
+                    &line,
+                )?;
+
+                let more = super::deliver_stream_record(&ctx, global, &callback, record, count)?;
+                count += 1;
+
+                if !more {
+                    break;
+                }
+            }
+
+            Ok(count)
+        })
+    }
+    /// Parse a stream of CSV records, calling a function for each record as an array of strings.
+    ///
+    /// Fields enclosed in double-quotes may contain commas, line-breaks and escaped (doubled)
+    /// double-quotes.
+    ///
+    /// The function is called with the record and, optionally, the record index.
+    /// Returning `false` from the function stops the stream.
+    ///
+    /// The number of records delivered is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// // 'input' is a stream reader provided by the host
+    /// input.parse_csv_stream(|fields| print(fields[0]));
+    /// ```
+    #[cfg(not(feature = "no_index"))]
+    #[rhai_fn(name = "parse_csv_stream", return_raw)]
+    pub fn parse_csv_stream(
+        ctx: NativeCallContext,
+        reader: &mut StreamReader,
+        callback: FnPtr,
+    ) -> RhaiResultOf<INT> {
+        super::run_stream(&ctx, |global| {
+            let mut line = String::new();
+            let mut count: INT = 0;
+
+            while let Some(fields) = reader.read_csv_record(&mut line)? {
+                let record: crate::Array = fields.into_iter().map(Into::into).collect();
+
+                let more =
+                    super::deliver_stream_record(&ctx, global, &callback, record.into(), count)?;
+                count += 1;
+
+                if !more {
+                    break;
+                }
+            }
+
+            Ok(count)
+        })
+    }
+    /// Parse a stream of CSV records, calling a function for each record.
+    ///
+    /// If `has_header` is `true`, the first record is treated as a header containing field names
+    /// and each subsequent record is delivered as an object map keyed by those names.
+    /// Otherwise, each record is delivered as an array of strings.
+    ///
+    /// The function is called with the record and, optionally, the record index.
+    /// Returning `false` from the function stops the stream.
+    ///
+    /// The number of records delivered (not counting the header) is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// // 'input' is a stream reader provided by the host
+    /// input.parse_csv_stream(true, |row| print(row.name));
+    /// ```
+    #[cfg(not(feature = "no_index"))]
+    #[cfg(not(feature = "no_object"))]
+    #[rhai_fn(name = "parse_csv_stream", return_raw)]
+    pub fn parse_csv_stream_with_header(
+        ctx: NativeCallContext,
+        reader: &mut StreamReader,
+        has_header: bool,
+        callback: FnPtr,
+    ) -> RhaiResultOf<INT> {
+        if !has_header {
+            return parse_csv_stream(ctx, reader, callback);
+        }
+
+        super::run_stream(&ctx, |global| {
+            let mut line = String::new();
+            let mut count: INT = 0;
+
+            let header: Vec<crate::Identifier> = match reader.read_csv_record(&mut line)? {
+                Some(fields) => fields.into_iter().map(Into::into).collect(),
+                None => return Ok(0),
+            };
+
+            while let Some(fields) = reader.read_csv_record(&mut line)? {
+                let record: crate::Map = header
+                    .iter()
+                    .cloned()
+                    .zip(fields.into_iter().map(Dynamic::from))
+                    .collect();
+
+                let more =
+                    super::deliver_stream_record(&ctx, global, &callback, record.into(), count)?;
+                count += 1;
+
+                if !more {
+                    break;
+                }
+            }
+
+            Ok(count)
+        })
+    }
 }
 
 #[cfg(not(feature = "no_function"))]
@@ -301,3 +689,85 @@ mod reflection_functions {
         collect(ctx, |_, _, n, p, _| p == (params as usize) && n == name)
     }
 }
+
+/// Spawn a function call as a parallel task.
+#[cfg(feature = "parallel")]
+#[cfg(not(feature = "no_std"))]
+fn spawn_task(
+    ctx: &crate::NativeCallContext,
+    fn_ptr: crate::FnPtr,
+    args: impl IntoIterator<Item = Dynamic>,
+) -> crate::Task {
+    let (pending, task) = crate::PendingTask::new(ctx, fn_ptr, args);
+
+    match ctx.engine().task_executor {
+        Some(ref executor) => executor(pending),
+        None => pending.run(ctx.engine()),
+    }
+
+    task
+}
+
+#[cfg(feature = "parallel")]
+#[cfg(not(feature = "no_std"))]
+#[export_module]
+mod parallel_functions {
+    use crate::{FnPtr, Task};
+
+    /// Run a function as a parallel task, returning the task.
+    ///
+    /// Use `join` to wait for the result of the task.
+    ///
+    /// The function cannot access variables in the calling scope except through captured
+    /// variables (which are copied).
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let task = spawn(|| 40 + 2);
+    ///
+    /// print(task.join());     // prints 42
+    /// ```
+    #[rhai_fn(name = "spawn")]
+    pub fn spawn(ctx: NativeCallContext, fn_ptr: FnPtr) -> Task {
+        super::spawn_task(&ctx, fn_ptr, None)
+    }
+    /// Run a function as a parallel task with arguments, returning the task.
+    ///
+    /// The arguments are copied.
+    ///
+    /// Use `join` to wait for the result of the task.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let task = spawn(|x, y| x + y, [40, 2]);
+    ///
+    /// print(task.join());     // prints 42
+    /// ```
+    #[cfg(not(feature = "no_index"))]
+    #[rhai_fn(name = "spawn")]
+    pub fn spawn_with_args(ctx: NativeCallContext, fn_ptr: FnPtr, args: crate::Array) -> Task {
+        super::spawn_task(&ctx, fn_ptr, args)
+    }
+    /// Wait for a parallel task to complete and return its result.
+    ///
+    /// Any error raised by the task is raised again.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let task = spawn(|| 40 + 2);
+    ///
+    /// print(task.join());     // prints 42
+    /// ```
+    #[rhai_fn(return_raw, pure)]
+    pub fn join(task: &mut Task) -> RhaiResult {
+        task.join()
+    }
+    /// Return `true` if a parallel task has completed.
+    #[rhai_fn(name = "is_done", get = "is_done", pure)]
+    pub fn is_done(task: &mut Task) -> bool {
+        task.is_done()
+    }
+}
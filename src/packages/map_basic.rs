@@ -2,8 +2,10 @@
 
 use crate::engine::OP_EQUALS;
 use crate::plugin::*;
+use crate::types::prototype::{iter_properties, num_properties, PROP_PROTOTYPE};
 use crate::{
-    def_package, Dynamic, FnPtr, ImmutableString, Map, NativeCallContext, RhaiResultOf, INT,
+    def_package, Dynamic, FnPtr, ImmutableString, Map, NativeCallContext, Prototype, RhaiResultOf,
+    INT,
 };
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
@@ -25,12 +27,12 @@ mod map_functions {
     /// Return the number of properties in the object map.
     #[rhai_fn(pure)]
     pub fn len(map: &mut Map) -> INT {
-        map.len() as INT
+        num_properties(map) as INT
     }
     /// Return true if the map is empty.
     #[rhai_fn(pure)]
     pub fn is_empty(map: &mut Map) -> bool {
-        map.len() == 0
+        num_properties(map) == 0
     }
     /// Returns `true` if the object map contains a specified property.
     ///
@@ -194,6 +196,99 @@ mod map_functions {
             map.entry(key).or_insert(value);
         }
     }
+    /// Create a prototype (i.e. a method table) from an object map.
+    ///
+    /// A prototype can be attached to many object maps without copying its methods.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// const Counter = prototype(#{
+    ///     inc: || this.count += 1
+    /// });
+    ///
+    /// let c = #{ count: 0 };
+    /// c.set_prototype(Counter);
+    ///
+    /// c.inc();
+    ///
+    /// print(c.count);     // prints 1
+    /// ```
+    pub fn prototype(methods: Map) -> Prototype {
+        Prototype::new(methods)
+    }
+    /// Attach a prototype to the object map, replacing any existing prototype.
+    ///
+    /// Calling a method that does not exist as a property in the object map looks it up in the
+    /// prototype instead.
+    ///
+    /// The prototype is stored under the property `$prototype`.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// const Greeter = prototype(#{
+    ///     greet: |greeting| `${greeting}, ${this.name}!`
+    /// });
+    ///
+    /// let m = #{ name: "world" };
+    /// m.set_prototype(Greeter);
+    ///
+    /// print(m.greet("hello"));    // prints "hello, world!"
+    /// ```
+    pub fn set_prototype(map: &mut Map, prototype: Prototype) {
+        prototype.attach_to(map);
+    }
+    /// Attach a copy of an object map to the object map as its prototype,
+    /// replacing any existing prototype.
+    ///
+    /// Use `prototype` to create a prototype that can be shared among object maps without copying.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let m = #{ name: "world" };
+    /// m.set_prototype(#{ greet: || `hello, ${this.name}!` });
+    ///
+    /// print(m.greet());       // prints "hello, world!"
+    /// ```
+    #[rhai_fn(name = "set_prototype")]
+    pub fn set_prototype_map(map: &mut Map, methods: Map) {
+        Prototype::new(methods).attach_to(map);
+    }
+    /// Return the prototype attached to the object map, or `()` if there is none.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let m = #{ a: 1 };
+    ///
+    /// print(m.get_prototype());   // prints empty (for '()')
+    /// ```
+    #[rhai_fn(pure)]
+    pub fn get_prototype(map: &mut Map) -> Dynamic {
+        Prototype::of(map).map_or(Dynamic::UNIT, Dynamic::from)
+    }
+    /// Remove the prototype (if any) from the object map.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let m = #{ name: "world" };
+    /// m.set_prototype(#{ greet: || `hello, ${this.name}!` });
+    ///
+    /// m.clear_prototype();
+    ///
+    /// m.greet();              // error: function not found
+    /// ```
+    pub fn clear_prototype(map: &mut Map) {
+        Prototype::detach_from(map);
+    }
+    /// Return a copy of the methods in the prototype as an object map.
+    #[rhai_fn(pure)]
+    pub fn methods(prototype: &mut Prototype) -> Map {
+        prototype.methods().clone()
+    }
     /// Return `true` if two object maps are equal (i.e. all property values are equal).
     ///
     /// The operator `==` is used to compare property values and must be defined,
@@ -212,14 +307,17 @@ mod map_functions {
     /// ```
     #[rhai_fn(name = "==", return_raw, pure)]
     pub fn equals(ctx: NativeCallContext, map1: &mut Map, map2: Map) -> RhaiResultOf<bool> {
-        if map1.len() != map2.len() {
+        if num_properties(map1) != num_properties(&map2) {
             return Ok(false);
         }
 
         if !map1.is_empty() {
             let mut map2 = map2;
 
-            for (m1, v1) in map1 {
+            for (m1, v1) in map1
+                .iter_mut()
+                .filter(|(k, ..)| k.as_str() != PROP_PROTOTYPE)
+            {
                 match map2.get_mut(m1) {
                     Some(v2) => {
                         let equals = ctx
@@ -275,7 +373,9 @@ mod map_functions {
             return Array::new();
         }
 
-        map.keys().cloned().map(Into::into).collect()
+        iter_properties(map)
+            .map(|(k, ..)| k.clone().into())
+            .collect()
     }
     /// Return an array with all the property values in the object map.
     ///
@@ -293,7 +393,7 @@ mod map_functions {
             return Array::new();
         }
 
-        map.values().cloned().collect()
+        iter_properties(map).map(|(.., v)| v.clone()).collect()
     }
     /// Iterate through all the elements in the object map, applying a `filter` function to each
     /// and return a new collection of all elements that return `true` as a new object map.
@@ -324,7 +424,10 @@ mod map_functions {
 
         let mut result = Map::new();
 
-        for (key, item) in map.iter_mut() {
+        for (key, item) in map
+            .iter_mut()
+            .filter(|(k, ..)| k.as_str() != PROP_PROTOTYPE)
+        {
             if filter
                 .call_raw_with_extra_args("filter", &ctx, Some(item), [key.into()], [], Some(1))?
                 .as_bool()
@@ -371,6 +474,11 @@ mod map_functions {
         let mut retained = Map::new();
 
         for (key, mut value) in mem::take(map).into_iter() {
+            if key.as_str() == PROP_PROTOTYPE {
+                retained.insert(key, value);
+                continue;
+            }
+
             if filter
                 .call_raw_with_extra_args(
                     "drain",
@@ -428,6 +536,11 @@ mod map_functions {
         let mut retained = Map::new();
 
         for (key, mut value) in mem::take(map).into_iter() {
+            if key.as_str() == PROP_PROTOTYPE {
+                retained.insert(key, value);
+                continue;
+            }
+
             if filter
                 .call_raw_with_extra_args(
                     "retain",
@@ -472,7 +585,10 @@ mod map_functions {
     /// ```
     pub fn to_json(map: &mut Map) -> String {
         #[cfg(feature = "metadata")]
-        return serde_json::to_string(map).unwrap_or_else(|_| "ERROR".into());
+        return serde_json::to_string(
+            &iter_properties(map).collect::<std::collections::BTreeMap<_, _>>(),
+        )
+        .unwrap_or_else(|_| "ERROR".into());
         #[cfg(not(feature = "metadata"))]
         return crate::format_map_as_json(map);
     }
@@ -320,14 +320,17 @@ mod float_functions {
     /// print(x);       // prints 123.456
     /// ```
     #[rhai_fn(return_raw)]
-    pub fn parse_float(string: &str) -> RhaiResultOf<FLOAT> {
-        string.trim().parse::<FLOAT>().map_err(|err| {
-            ERR::ErrorArithmetic(
-                format!("Error parsing floating-point number '{string}': {err}"),
-                Position::NONE,
-            )
-            .into()
-        })
+    pub fn parse_float(ctx: NativeCallContext, string: &str) -> RhaiResultOf<FLOAT> {
+        ctx.engine()
+            .float_format()
+            .parse::<FLOAT>(string)
+            .map_err(|err| {
+                ERR::ErrorArithmetic(
+                    format!("Error parsing floating-point number '{string}': {err}"),
+                    Position::NONE,
+                )
+                .into()
+            })
     }
     /// Convert the 32-bit floating-point number to 64-bit.
     #[cfg(not(feature = "f32_float"))]
@@ -530,6 +533,114 @@ mod decimal_functions {
         #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
         Ok(x.round_dp_with_strategy(digits as u32, RoundingStrategy::MidpointTowardZero))
     }
+    /// Round the decimal number to the specified number of `digits` after the decimal point and return it.
+    /// Always round mid-points towards the closest even number (i.e. banker's rounding).
+    #[rhai_fn(return_raw)]
+    pub fn round_half_even(x: Decimal, digits: INT) -> RhaiResultOf<Decimal> {
+        round_dp(x, digits)
+    }
+    /// Round the decimal number to the specified number of `digits` after the decimal point and return it,
+    /// using the rounding `mode`, which is one of:
+    ///
+    /// * `"half_even"` or `"bankers"`: round mid-points towards the closest even number
+    /// * `"half_up"`: round mid-points away from zero
+    /// * `"half_down"`: round mid-points towards zero
+    /// * `"up"`: always round away from zero
+    /// * `"down"`: always round towards zero
+    /// * `"ceiling"`: always round towards positive infinity
+    /// * `"floor"`: always round towards negative infinity
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let x = parse_decimal("2.345");
+    ///
+    /// print(x.round_dp_with(2, "bankers"));   // prints 2.34
+    ///
+    /// print(x.round_dp_with(2, "half_up"));   // prints 2.35
+    ///
+    /// print(x.round_dp_with(1, "floor"));     // prints 2.3
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn round_dp_with(x: Decimal, digits: INT, mode: &str) -> RhaiResultOf<Decimal> {
+        let strategy = match mode {
+            "half_even" | "bankers" => RoundingStrategy::MidpointNearestEven,
+            "half_up" => RoundingStrategy::MidpointAwayFromZero,
+            "half_down" => RoundingStrategy::MidpointTowardZero,
+            "up" => RoundingStrategy::AwayFromZero,
+            "down" => RoundingStrategy::ToZero,
+            "ceiling" => RoundingStrategy::ToPositiveInfinity,
+            "floor" => RoundingStrategy::ToNegativeInfinity,
+            _ => return Err(make_err(format!("Invalid rounding mode: '{mode}'"))),
+        };
+
+        if cfg!(not(feature = "unchecked")) {
+            if digits < 0 {
+                return Err(make_err(format!(
+                    "Invalid number of digits for rounding: {digits}"
+                )));
+            }
+            if cfg!(not(feature = "only_i32")) && digits > (u32::MAX as INT) {
+                return Ok(x);
+            }
+        }
+
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        Ok(x.round_dp_with_strategy(digits as u32, strategy))
+    }
+    /// Convert the decimal number into a string with `,` separating groups of thousands.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let x = parse_decimal("-1234567.891");
+    ///
+    /// print(x.to_formatted_string());     // prints "-1,234,567.891"
+    /// ```
+    pub fn to_formatted_string(x: Decimal) -> String {
+        to_formatted_string_with(x, ",", ".")
+    }
+    /// Convert the decimal number into a string with `thousands_separator` separating groups of
+    /// thousands and `decimal_point` separating the fractional part.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let x = parse_decimal("1234567.891");
+    ///
+    /// print(x.to_formatted_string(".", ","));     // prints "1.234.567,891"
+    ///
+    /// print(x.to_formatted_string(" ", "."));     // prints "1 234 567.891"
+    /// ```
+    #[rhai_fn(name = "to_formatted_string")]
+    pub fn to_formatted_string_with(
+        x: Decimal,
+        thousands_separator: &str,
+        decimal_point: &str,
+    ) -> String {
+        let s = x.abs().to_string();
+        let (int, fraction) = s.split_once('.').unwrap_or((s.as_str(), ""));
+
+        let mut result = String::with_capacity(s.len() + s.len() / 3 * thousands_separator.len());
+
+        if x.is_sign_negative() && !x.is_zero() {
+            result.push('-');
+        }
+
+        for (i, ch) in int.chars().enumerate() {
+            if i > 0 && (int.len() - i) % 3 == 0 {
+                result.push_str(thousands_separator);
+            }
+            result.push(ch);
+        }
+
+        if !fraction.is_empty() {
+            result.push_str(decimal_point);
+            result.push_str(fraction);
+        }
+
+        result
+    }
     /// Convert the decimal number into an integer.
     #[rhai_fn(return_raw)]
     pub fn to_int(x: Decimal) -> RhaiResultOf<INT> {
@@ -588,6 +699,46 @@ mod decimal_functions {
                 .into()
             })
     }
+    /// Parse a string into a decimal number, with `thousands_separator` separating groups of
+    /// thousands and `decimal_point` separating the fractional part.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let x = parse_decimal("1.234.567,891", ".", ",");
+    ///
+    /// print(x);       // prints 1234567.891
+    /// ```
+    #[rhai_fn(name = "parse_decimal", return_raw)]
+    pub fn parse_decimal_with(
+        string: &str,
+        thousands_separator: &str,
+        decimal_point: &str,
+    ) -> RhaiResultOf<Decimal> {
+        let mut s = string.trim().to_string();
+
+        if !thousands_separator.is_empty() {
+            s = s.replace(thousands_separator, "");
+        }
+        if !decimal_point.is_empty() && decimal_point != "." {
+            if s.contains('.') {
+                return Err(ERR::ErrorArithmetic(
+                    format!("Error parsing decimal number '{string}': unexpected '.'"),
+                    Position::NONE,
+                )
+                .into());
+            }
+            s = s.replace(decimal_point, ".");
+        }
+
+        Decimal::from_str(&s).map_err(|err| {
+            ERR::ErrorArithmetic(
+                format!("Error parsing decimal number '{string}': {err}"),
+                Position::NONE,
+            )
+            .into()
+        })
+    }
 
     /// Convert the floating-point number to decimal.
     #[cfg(not(feature = "no_float"))]
@@ -0,0 +1,422 @@
+#![cfg(feature = "icu")]
+#![cfg(not(feature = "no_object"))]
+
+use crate::func::{locked_read, locked_write};
+use crate::plugin::*;
+use crate::{def_package, Dynamic, ImmutableString, Map, Position, RhaiResultOf, Shared, ERR};
+use icu_plurals::{PluralCategory, PluralOperands, PluralRuleType, PluralRules};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Maximum number of locale plural rules cached by an [`Engine`].
+///
+/// The cache is cleared when it is full.
+const MAX_PLURAL_RULES_CACHE_SIZE: usize = 64;
+
+/// Locale used when none is specified.
+const DEFAULT_LOCALE: &str = "en";
+
+def_package! {
+    /// Package of locale-aware message formatting utilities.
+    ///
+    /// Plural categories are determined according to the rules of a locale via the
+    /// [`icu_plurals`](https://crates.io/crates/icu_plurals) crate.
+    pub MessageFormatPackage(lib) {
+        lib.set_standard_lib(true);
+
+        combine_with_exported_module!(lib, "message", message_functions);
+    }
+}
+
+/// Get the plural rules for a locale, using the cache of plural rules in the [`Engine`].
+fn get_plural_rules(
+    ctx: &NativeCallContext,
+    locale: &ImmutableString,
+    ordinal: bool,
+) -> RhaiResultOf<Shared<PluralRules>> {
+    let cache = &ctx.engine().plural_rules_cache;
+    let key = (locale.clone(), ordinal);
+
+    if let Some(rules) = locked_read(cache).and_then(|c| c.get(&key).cloned()) {
+        return Ok(rules);
+    }
+
+    let invalid_locale = |err: String| {
+        ERR::ErrorRuntime(
+            format!("Invalid locale '{locale}': {err}").into(),
+            Position::NONE,
+        )
+    };
+
+    let rule_type = if ordinal {
+        PluralRuleType::Ordinal
+    } else {
+        PluralRuleType::Cardinal
+    };
+    let id = locale
+        .parse::<icu_locid::Locale>()
+        .map_err(|err| invalid_locale(err.to_string()))?;
+    let rules = PluralRules::try_new(&(&id).into(), rule_type)
+        .map(Shared::new)
+        .map_err(|err| invalid_locale(err.to_string()))?;
+
+    if let Some(mut c) = locked_write(cache) {
+        if c.len() >= MAX_PLURAL_RULES_CACHE_SIZE {
+            c.clear();
+        }
+        c.insert(key, rules.clone());
+    }
+
+    Ok(rules)
+}
+
+/// Create an error for an invalid message pattern.
+fn invalid_pattern(pattern: &str, reason: &str) -> Box<crate::EvalAltResult> {
+    ERR::ErrorRuntime(
+        format!("Invalid message pattern '{pattern}': {reason}").into(),
+        Position::NONE,
+    )
+    .into()
+}
+
+/// Select the form for a number from forms keyed by exact values (e.g. `=0`) or by the names of
+/// plural categories (`zero`, `one`, `two`, `few`, `many` and `other`).
+///
+/// Returns [`None`] if there is no matching form and no `other` form.
+fn select_plural<'a, T>(
+    ctx: &NativeCallContext,
+    n: &Dynamic,
+    forms: impl Iterator<Item = (&'a str, T)> + Clone,
+    locale: &ImmutableString,
+    ordinal: bool,
+) -> RhaiResultOf<Option<T>> {
+    let matches_exact = |key: &str| match key.strip_prefix('=').map(str::trim) {
+        Some(value) => match (n.as_int(), value.parse::<crate::INT>()) {
+            (Ok(x), Ok(y)) => x == y,
+            #[cfg(not(feature = "no_float"))]
+            _ => match (n.as_float(), value.parse::<crate::FLOAT>()) {
+                (Ok(x), Ok(y)) => x == y,
+                _ => false,
+            },
+            #[cfg(feature = "no_float")]
+            _ => false,
+        },
+        None => false,
+    };
+
+    if let Some((.., form)) = forms.clone().find(|&(key, ..)| matches_exact(key)) {
+        return Ok(Some(form));
+    }
+
+    let rules = get_plural_rules(ctx, locale, ordinal)?;
+
+    let category = match n.as_int() {
+        Ok(x) => rules.category_for(x),
+        Err(..) => match n.to_string().parse::<PluralOperands>() {
+            Ok(operands) => rules.category_for(operands),
+            Err(..) => PluralCategory::Other,
+        },
+    };
+    let category = match category {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    };
+
+    let mut other = None;
+
+    for (key, form) in forms {
+        if key == category {
+            return Ok(Some(form));
+        }
+        if key == "other" {
+            other = Some(form);
+        }
+    }
+
+    Ok(other)
+}
+
+/// Special characters which start quoted literal text when following a single quote.
+const QUOTABLE: &[char] = &['\'', '{', '}', '#'];
+
+/// Find the position of the closing brace matching the opening brace at the start of the text,
+/// skipping over quoted literal text.
+fn find_closing_brace(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            // '' is a single quote either inside or outside quoted text
+            '\'' if chars.peek().map_or(false, |&(.., c)| c == '\'') => {
+                chars.next();
+            }
+            '\'' if quoted => quoted = false,
+            '\'' => quoted = chars.peek().map_or(false, |&(.., c)| QUOTABLE.contains(&c)),
+            _ if quoted => (),
+            '{' => depth += 1,
+            '}' if depth == 1 => return Some(i),
+            '}' => depth -= 1,
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Format the `text` of a message pattern (or a form within it) with arguments, appending the
+/// result to `output`.
+///
+/// `number` is the value substituted for `#` within a `plural` or `selectordinal` form.
+fn format_pattern(
+    ctx: &NativeCallContext,
+    pattern: &str,
+    text: &str,
+    args: &Map,
+    locale: &ImmutableString,
+    number: Option<&Dynamic>,
+    output: &mut String,
+) -> RhaiResultOf<()> {
+    let mut rest = text;
+
+    while let Some(ch) = rest.chars().next() {
+        match (ch, number) {
+            // '' is a single quote, otherwise quotes enclose literal text
+            ('\'', ..) if rest[1..].starts_with('\'') => {
+                output.push('\'');
+                rest = &rest[2..];
+            }
+            ('\'', ..) if rest[1..].starts_with(QUOTABLE) => {
+                let quoted = &rest[1..];
+                let end = quoted.find('\'').unwrap_or(quoted.len());
+                output.push_str(&quoted[..end]);
+                rest = &quoted[(end + 1).min(quoted.len())..];
+            }
+            ('#', Some(number)) => {
+                output.push_str(&number.to_string());
+                rest = &rest[1..];
+            }
+            ('}', ..) => return Err(invalid_pattern(pattern, "unmatched '}'")),
+            ('{', ..) => {
+                let end = find_closing_brace(rest)
+                    .ok_or_else(|| invalid_pattern(pattern, "unmatched '{'"))?;
+                format_argument(ctx, pattern, &rest[1..end], args, locale, output)?;
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                let end = rest
+                    .find(QUOTABLE)
+                    .map_or(rest.len(), |i| i.max(ch.len_utf8()));
+                output.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Format an argument placeholder (the text between the braces), appending the result to `output`.
+fn format_argument(
+    ctx: &NativeCallContext,
+    pattern: &str,
+    placeholder: &str,
+    args: &Map,
+    locale: &ImmutableString,
+    output: &mut String,
+) -> RhaiResultOf<()> {
+    let mut parts = placeholder.splitn(3, ',');
+    let name = parts.next().unwrap_or_default().trim();
+    let kind = parts.next().map(str::trim);
+    let branches = parts.next().unwrap_or_default();
+
+    let value = args.get(name).ok_or_else(|| {
+        ERR::ErrorRuntime(
+            format!("Missing message argument '{name}'").into(),
+            Position::NONE,
+        )
+    })?;
+
+    let ordinal = match kind {
+        None => {
+            output.push_str(&value.to_string());
+            return Ok(());
+        }
+        Some("plural") => Some(false),
+        Some("selectordinal") => Some(true),
+        Some("select") => None,
+        Some(kind) => {
+            return Err(invalid_pattern(
+                pattern,
+                &format!("unknown argument type '{kind}'"),
+            ))
+        }
+    };
+
+    // Parse the forms: key {message} key {message} ...
+    let mut forms = Vec::new();
+    let mut rest = branches.trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '{' || c.is_whitespace())
+            .ok_or_else(|| invalid_pattern(pattern, "missing '{' after a selector"))?;
+        let key = &rest[..key_end];
+        rest = rest[key_end..].trim_start();
+
+        if !rest.starts_with('{') || key.is_empty() {
+            return Err(invalid_pattern(pattern, "missing '{' after a selector"));
+        }
+
+        let end =
+            find_closing_brace(rest).ok_or_else(|| invalid_pattern(pattern, "unmatched '{'"))?;
+        forms.push((key, &rest[1..end]));
+        rest = rest[end + 1..].trim_start();
+    }
+
+    let (form, number) = match ordinal {
+        Some(ordinal) => (
+            select_plural(ctx, value, forms.iter().copied(), locale, ordinal)?,
+            Some(value),
+        ),
+        None => {
+            let value = value.to_string();
+            let form = forms
+                .iter()
+                .find(|&&(key, ..)| key == value)
+                .or_else(|| forms.iter().find(|&&(key, ..)| key == "other"))
+                .map(|&(.., form)| form);
+            (form, None)
+        }
+    };
+
+    let form = form.ok_or_else(|| invalid_pattern(pattern, "missing 'other' form"))?;
+
+    format_pattern(ctx, pattern, form, args, locale, number, output)
+}
+
+#[export_module]
+mod message_functions {
+    /// Select the form for a number `n` from an object map of `forms`, according to the plural
+    /// rules of English, replacing `#` in the form with the number.
+    ///
+    /// Forms are keyed by exact values (e.g. `"=0"`) or by the names of plural categories
+    /// (`zero`, `one`, `two`, `few`, `many` and `other`).
+    ///
+    /// An error is raised if no form matches and there is no `other` form.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let forms = #{ "=0": "no messages", one: "# message", other: "# messages" };
+    ///
+    /// print(plural(0, forms));    // prints "no messages"
+    ///
+    /// print(plural(1, forms));    // prints "1 message"
+    ///
+    /// print(plural(42, forms));   // prints "42 messages"
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn plural(ctx: NativeCallContext, n: Dynamic, forms: Map) -> RhaiResultOf<String> {
+        plural_with_locale(ctx, n, forms, DEFAULT_LOCALE.into())
+    }
+    /// Select the form for a number `n` from an object map of `forms`, according to the plural
+    /// rules of a `locale` (e.g. `"en"`, `"pl"`, `"ar"`), replacing `#` in the form with the number.
+    ///
+    /// Forms are keyed by exact values (e.g. `"=0"`) or by the names of plural categories
+    /// (`zero`, `one`, `two`, `few`, `many` and `other`).
+    ///
+    /// An error is raised if no form matches and there is no `other` form, or if the `locale` is
+    /// not a valid locale identifier.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let forms = #{ one: "# plik", few: "# pliki", many: "# plików", other: "# pliku" };
+    ///
+    /// print(plural(1, forms, "pl"));      // prints "1 plik"
+    ///
+    /// print(plural(3, forms, "pl"));      // prints "3 pliki"
+    ///
+    /// print(plural(5, forms, "pl"));      // prints "5 plików"
+    /// ```
+    #[rhai_fn(name = "plural", return_raw)]
+    pub fn plural_with_locale(
+        ctx: NativeCallContext,
+        n: Dynamic,
+        forms: Map,
+        locale: ImmutableString,
+    ) -> RhaiResultOf<String> {
+        let keys = forms.iter().map(|(key, form)| (key.as_str(), form));
+
+        let form = select_plural(&ctx, &n, keys, &locale, false)?
+            .ok_or_else(|| ERR::ErrorRuntime("No matching plural form".into(), Position::NONE))?;
+
+        Ok(form.to_string().replace('#', &n.to_string()))
+    }
+
+    /// Format a message `pattern` in ICU MessageFormat style with an object map of `args`,
+    /// according to the plural rules of English.
+    ///
+    /// See `format_message(pattern, args, locale)` for the supported syntax.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let pattern = "{name} has {count, plural, =0 {no files} one {# file} other {# files}}.";
+    ///
+    /// print(format_message(pattern, #{ name: "Alice", count: 3 }));
+    /// // prints "Alice has 3 files."
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn format_message(
+        ctx: NativeCallContext,
+        pattern: &str,
+        args: Map,
+    ) -> RhaiResultOf<String> {
+        format_message_with_locale(ctx, pattern, args, DEFAULT_LOCALE.into())
+    }
+    /// Format a message `pattern` in ICU MessageFormat style with an object map of `args`,
+    /// according to the plural rules of a `locale` (e.g. `"en"`, `"pl"`, `"ar"`).
+    ///
+    /// # Syntax
+    ///
+    /// * `{name}`: value of the argument `name`
+    /// * `{name, plural, =0 {...} one {...} other {...}}`: form selected by the plural category
+    ///   (or the exact value) of the number `name`; `#` in the form is replaced by the number
+    /// * `{name, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}`: form selected by the
+    ///   ordinal plural category of the number `name`
+    /// * `{name, select, a {...} b {...} other {...}}`: form selected by the value of `name`
+    /// * `''`: a single quote; text enclosed in single quotes (starting with `{`, `}` or `#`)
+    ///   is literal
+    ///
+    /// Forms may contain nested placeholders.
+    ///
+    /// An error is raised if the `pattern` is invalid, an argument is missing, or the `locale` is
+    /// not a valid locale identifier.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let pattern = "{name} finished {place, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}.";
+    ///
+    /// print(format_message(pattern, #{ name: "Bob", place: 2 }, "en"));
+    /// // prints "Bob finished 2nd."
+    /// ```
+    #[rhai_fn(name = "format_message", return_raw)]
+    pub fn format_message_with_locale(
+        ctx: NativeCallContext,
+        pattern: &str,
+        args: Map,
+        locale: ImmutableString,
+    ) -> RhaiResultOf<String> {
+        let mut output = String::with_capacity(pattern.len());
+        format_pattern(&ctx, pattern, pattern, &args, &locale, None, &mut output)?;
+        Ok(output)
+    }
+}
@@ -0,0 +1,313 @@
+#![cfg(feature = "mmap")]
+#![cfg(not(feature = "no_index"))]
+#![cfg(not(feature = "no_std"))]
+
+use crate::eval::{calc_index, calc_offset_len};
+use crate::plugin::*;
+use crate::{
+    def_package, Array, Blob, Dynamic, ExclusiveRange, InclusiveRange, Position, RhaiError,
+    RhaiResultOf, Shared, ERR, INT, INT_BYTES,
+};
+use std::{any::TypeId, fmt, fs::File, ops::Deref, path::Path};
+
+def_package! {
+    /// Package of read-only memory-mapped files.
+    ///
+    /// Files are mapped via the [`memmap2`](https://crates.io/crates/memmap2) crate into
+    /// [`MmapBlob`] values, which support indexing, slicing and iteration like BLOB's but do not
+    /// load the whole file into memory.
+    ///
+    /// This package gives scripts read access to the file system, therefore it is not part of the
+    /// [`StandardPackage`][super::StandardPackage] and must be registered explicitly.
+    pub MmapPackage(lib) {
+        lib.set_standard_lib(true);
+
+        combine_with_exported_module!(lib, "mmap", mmap_functions);
+
+        // Register memory-mapped blob iterator
+        lib.set_iter(TypeId::of::<MmapBlob>(), |value| {
+            let blob = value.cast::<MmapBlob>();
+            Box::new((0..blob.len()).map(move |i| Dynamic::from_int(blob[i] as INT)))
+        });
+    }
+}
+
+/// A read-only view into a memory-mapped file, usable in scripts like a BLOB.
+///
+/// Cloning or slicing an [`MmapBlob`] does not copy any bytes; the file stays mapped until all
+/// views into it are dropped.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated (by this or any other process) while it is
+/// mapped, otherwise the bytes seen through an [`MmapBlob`] may change, or reading them may
+/// crash the process.
+///
+/// Not available under `no_index` or `no_std`.
+#[derive(Clone)]
+pub struct MmapBlob {
+    /// The memory-mapped file.
+    map: Shared<memmap2::Mmap>,
+    /// Offset of the view within the file.
+    start: usize,
+    /// Number of bytes in the view.
+    len: usize,
+}
+
+impl fmt::Debug for MmapBlob {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MmapBlob")
+            .field("start", &self.start)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl Deref for MmapBlob {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.map[self.start..self.start + self.len]
+    }
+}
+
+impl AsRef<[u8]> for MmapBlob {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl MmapBlob {
+    /// Memory-map a whole file read-only.
+    ///
+    /// See the [safety notes][MmapBlob#safety] on modifying the file while it is mapped.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+
+        // SAFETY: The map is read-only. Concurrent modification of the file by others cannot be
+        //         prevented, which is documented on `MmapBlob`.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        let len = map.len();
+
+        Ok(Self {
+            map: map.into(),
+            start: 0,
+            len,
+        })
+    }
+    /// Get a view into a range of bytes, without copying.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn slice(&self, start: usize, len: usize) -> Self {
+        assert!(start + len <= self.len, "range out of bounds");
+
+        Self {
+            map: self.map.clone(),
+            start: self.start + start,
+            len,
+        }
+    }
+}
+
+#[export_module]
+mod mmap_functions {
+    /// A read-only view into a memory-mapped file.
+    pub type MmapBlob = super::MmapBlob;
+
+    /// Memory-map the file at `path` read-only.
+    ///
+    /// The file is not loaded into memory; bytes are read from the file as they are accessed.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let log = open_blob_mmap("events.bin");
+    ///
+    /// print(log.len);                     // prints the size of the file
+    ///
+    /// let kind = log.parse_le_int(0, 2);  // parse the first two bytes
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn open_blob_mmap(path: &str) -> RhaiResultOf<MmapBlob> {
+        super::MmapBlob::open(path)
+            .map_err(|err| ERR::ErrorSystem(format!("Cannot map file '{path}'"), err.into()).into())
+    }
+
+    /// Return the length of the memory-mapped BLOB.
+    #[rhai_fn(name = "len", get = "len", pure)]
+    pub fn len(blob: &mut MmapBlob) -> INT {
+        blob.len() as INT
+    }
+    /// Return true if the memory-mapped BLOB is empty.
+    #[rhai_fn(name = "is_empty", get = "is_empty", pure)]
+    pub fn is_empty(blob: &mut MmapBlob) -> bool {
+        blob.is_empty()
+    }
+    /// Return `true` if the memory-mapped BLOB contains a specified byte value.
+    #[rhai_fn(pure)]
+    pub fn contains(blob: &mut MmapBlob, value: INT) -> bool {
+        #[allow(clippy::cast_sign_loss)]
+        blob.contains(&((value & 0x0000_00ff) as u8))
+    }
+    /// Get the byte value at the `index` position in the memory-mapped BLOB.
+    ///
+    /// * If `index` < 0, position counts from the end of the BLOB (`-1` is the last element).
+    /// * If `index` < -length of BLOB, zero is returned.
+    /// * If `index` ≥ length of BLOB, zero is returned.
+    #[rhai_fn(pure)]
+    pub fn get(blob: &mut MmapBlob, index: INT) -> INT {
+        let (index, ..) = calc_offset_len(blob.len(), index, 0);
+        blob.get(index).map_or(0, |&b| b as INT)
+    }
+    /// Get the byte value at the `index` position in the memory-mapped BLOB.
+    ///
+    /// * If `index` < 0, position counts from the end of the BLOB (`-1` is the last element).
+    /// * If `index` is out of bounds, an error is raised.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let log = open_blob_mmap("events.bin");
+    ///
+    /// print(log[0]);      // prints the first byte
+    ///
+    /// print(log[-1]);     // prints the last byte
+    /// ```
+    #[rhai_fn(index_get, pure, return_raw)]
+    pub fn index(blob: &mut MmapBlob, index: INT) -> RhaiResultOf<INT> {
+        let index = calc_index(blob.len(), index, true, || {
+            Err::<_, RhaiError>(ERR::ErrorArrayBounds(blob.len(), index, Position::NONE).into())
+        })?;
+        Ok(blob[index] as INT)
+    }
+    /// Get a view into an exclusive `range` of the memory-mapped BLOB, without copying.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let log = open_blob_mmap("events.bin");
+    ///
+    /// let header = log[0..16];
+    ///
+    /// print(header.len);  // prints 16
+    /// ```
+    #[rhai_fn(index_get, pure)]
+    pub fn slice_range(blob: &mut MmapBlob, range: ExclusiveRange) -> MmapBlob {
+        let start = INT::max(range.start, 0);
+        let end = INT::max(range.end, start);
+        let (start, len) = calc_offset_len(blob.len(), start, end - start);
+        blob.slice(start, len)
+    }
+    /// Get a view into an inclusive `range` of the memory-mapped BLOB, without copying.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let log = open_blob_mmap("events.bin");
+    ///
+    /// let header = log[0..=15];
+    ///
+    /// print(header.len);  // prints 16
+    /// ```
+    #[rhai_fn(index_get, pure)]
+    pub fn slice_inclusive_range(blob: &mut MmapBlob, range: InclusiveRange) -> MmapBlob {
+        let start = INT::max(*range.start(), 0);
+        let end = INT::max(*range.end(), start);
+        let (start, len) = calc_offset_len(blob.len(), start, end - start + 1);
+        blob.slice(start, len)
+    }
+    /// Copy an exclusive `range` of the memory-mapped BLOB into a new BLOB.
+    #[rhai_fn(name = "extract", pure)]
+    pub fn extract_range(blob: &mut MmapBlob, range: ExclusiveRange) -> Blob {
+        slice_range(blob, range).to_vec()
+    }
+    /// Copy an inclusive `range` of the memory-mapped BLOB into a new BLOB.
+    #[rhai_fn(name = "extract", pure)]
+    pub fn extract_range_inclusive(blob: &mut MmapBlob, range: InclusiveRange) -> Blob {
+        slice_inclusive_range(blob, range).to_vec()
+    }
+    /// Copy a portion of the memory-mapped BLOB into a new BLOB.
+    ///
+    /// * If `start` < 0, position counts from the end of the BLOB (`-1` is the last byte).
+    /// * If `start` < -length of BLOB, position counts from the beginning of the BLOB.
+    /// * If `start` ≥ length of BLOB, an empty BLOB is returned.
+    /// * If `len` ≤ 0, an empty BLOB is returned.
+    /// * If `start` position + `len` ≥ length of BLOB, entire portion of the BLOB after the `start` position is copied.
+    #[rhai_fn(pure)]
+    pub fn extract(blob: &mut MmapBlob, start: INT, len: INT) -> Blob {
+        if blob.is_empty() || len <= 0 {
+            return Blob::new();
+        }
+        let (start, len) = calc_offset_len(blob.len(), start, len);
+        blob[start..start + len].to_vec()
+    }
+    /// Copy the memory-mapped BLOB into a new BLOB.
+    #[rhai_fn(pure)]
+    pub fn to_blob(blob: &mut MmapBlob) -> Blob {
+        blob.to_vec()
+    }
+    /// Convert the memory-mapped BLOB into an array of integers.
+    #[rhai_fn(pure)]
+    pub fn to_array(blob: &mut MmapBlob) -> Array {
+        blob.iter().map(|&ch| (ch as INT).into()).collect()
+    }
+    /// Convert the memory-mapped BLOB into a string.
+    ///
+    /// Invalid UTF-8 sequences are replaced by the Unicode replacement character.
+    #[rhai_fn(pure)]
+    pub fn as_string(blob: &mut MmapBlob) -> String {
+        String::from_utf8_lossy(blob).into_owned()
+    }
+
+    /// Parse the bytes within a portion of the memory-mapped BLOB as an `INT`
+    /// in little-endian byte order.
+    ///
+    /// * If number of bytes in range < number of bytes for `INT`, zeros are padded.
+    /// * If number of bytes in range > number of bytes for `INT`, extra bytes are ignored.
+    #[rhai_fn(pure)]
+    pub fn parse_le_int(blob: &mut MmapBlob, start: INT, len: INT) -> INT {
+        parse_int(blob, start, len, true)
+    }
+    /// Parse the bytes within a portion of the memory-mapped BLOB as an `INT`
+    /// in big-endian byte order.
+    ///
+    /// * If number of bytes in range < number of bytes for `INT`, zeros are padded.
+    /// * If number of bytes in range > number of bytes for `INT`, extra bytes are ignored.
+    #[rhai_fn(pure)]
+    pub fn parse_be_int(blob: &mut MmapBlob, start: INT, len: INT) -> INT {
+        parse_int(blob, start, len, false)
+    }
+}
+
+/// Parse the bytes within a portion of a memory-mapped BLOB as an `INT`.
+#[inline]
+fn parse_int(blob: &[u8], start: INT, len: INT, is_le: bool) -> INT {
+    if blob.is_empty() || len <= 0 {
+        return 0;
+    }
+    let (start, len) = calc_offset_len(blob.len(), start, len);
+
+    if len == 0 {
+        return 0;
+    }
+
+    let len = usize::min(len, INT_BYTES);
+
+    let mut buf = [0_u8; INT_BYTES];
+
+    buf[..len].copy_from_slice(&blob[start..][..len]);
+
+    if is_le {
+        INT::from_le_bytes(buf)
+    } else {
+        INT::from_be_bytes(buf)
+    }
+}
@@ -6,6 +6,7 @@ pub(crate) mod arithmetic;
 pub(crate) mod array_basic;
 pub(crate) mod bit_field;
 pub(crate) mod blob_basic;
+pub(crate) mod collate_basic;
 pub(crate) mod debugging;
 pub(crate) mod fn_basic;
 pub(crate) mod iter_basic;
@@ -13,10 +14,14 @@ pub(crate) mod lang_core;
 pub(crate) mod logic;
 pub(crate) mod map_basic;
 pub(crate) mod math_basic;
+pub(crate) mod message_basic;
+pub(crate) mod mmap_basic;
 pub(crate) mod pkg_core;
 pub(crate) mod pkg_std;
+pub(crate) mod regex_basic;
 pub(crate) mod string_basic;
 pub(crate) mod string_more;
+pub(crate) mod tensor_basic;
 pub(crate) mod time_basic;
 
 pub use arithmetic::ArithmeticPackage;
@@ -25,6 +30,8 @@ pub use array_basic::BasicArrayPackage;
 pub use bit_field::BitFieldPackage;
 #[cfg(not(feature = "no_index"))]
 pub use blob_basic::BasicBlobPackage;
+#[cfg(feature = "icu")]
+pub use collate_basic::CollationPackage;
 #[cfg(feature = "debugging")]
 pub use debugging::DebuggingPackage;
 pub use fn_basic::BasicFnPackage;
@@ -34,10 +41,23 @@ pub use logic::LogicPackage;
 #[cfg(not(feature = "no_object"))]
 pub use map_basic::BasicMapPackage;
 pub use math_basic::BasicMathPackage;
+#[cfg(feature = "icu")]
+#[cfg(not(feature = "no_object"))]
+pub use message_basic::MessageFormatPackage;
+#[cfg(feature = "mmap")]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_std"))]
+pub use mmap_basic::{MmapBlob, MmapPackage};
 pub use pkg_core::CorePackage;
 pub use pkg_std::StandardPackage;
+#[cfg(feature = "regex")]
+pub use regex_basic::RegexPackage;
 pub use string_basic::BasicStringPackage;
 pub use string_more::MoreStringPackage;
+#[cfg(feature = "tensor")]
+#[cfg(not(feature = "no_float"))]
+#[cfg(not(feature = "no_index"))]
+pub use tensor_basic::{Tensor, TensorPackage};
 #[cfg(not(feature = "no_time"))]
 pub use time_basic::BasicTimePackage;
 
@@ -18,6 +18,10 @@ def_package! {
     /// * [`BasicMapPackage`][super::BasicMapPackage]
     /// * [`BasicTimePackage`][super::BasicTimePackage]
     /// * [`MoreStringPackage`][super::MoreStringPackage]
+    /// * [`RegexPackage`][super::RegexPackage]
+    /// * [`CollationPackage`][super::CollationPackage]
+    /// * [`MessageFormatPackage`][super::MessageFormatPackage]
+    /// * [`TensorPackage`][super::TensorPackage]
     pub StandardPackage(lib) :
             CorePackage,
             BitFieldPackage,
@@ -27,7 +31,16 @@ def_package! {
             #[cfg(not(feature = "no_index"))] BasicBlobPackage,
             #[cfg(not(feature = "no_object"))] BasicMapPackage,
             #[cfg(not(feature = "no_time"))] BasicTimePackage,
-            MoreStringPackage
+            MoreStringPackage,
+            #[cfg(feature = "regex")] RegexPackage,
+            #[cfg(feature = "icu")] CollationPackage,
+            #[cfg(feature = "icu")]
+            #[cfg(not(feature = "no_object"))]
+            MessageFormatPackage,
+            #[cfg(feature = "tensor")]
+            #[cfg(not(feature = "no_float"))]
+            #[cfg(not(feature = "no_index"))]
+            TensorPackage
     {
         lib.set_standard_lib(true);
     }
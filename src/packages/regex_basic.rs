@@ -0,0 +1,257 @@
+#![cfg(feature = "regex")]
+
+use crate::func::{locked_read, locked_write};
+use crate::plugin::*;
+use crate::{def_package, Dynamic, ImmutableString, Position, RhaiResultOf, ERR};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+#[cfg(not(feature = "no_index"))]
+use crate::Array;
+
+#[cfg(not(feature = "no_object"))]
+use crate::Map;
+
+/// Maximum number of compiled regular expressions cached by an [`Engine`].
+///
+/// The cache is cleared when it is full.
+const MAX_REGEX_CACHE_SIZE: usize = 256;
+
+def_package! {
+    /// Package of regular expression utilities.
+    ///
+    /// Regular expressions are compiled via the [`regex`](https://crates.io/crates/regex) crate,
+    /// which guarantees matching in time linear to the length of the text.
+    pub RegexPackage(lib) {
+        lib.set_standard_lib(true);
+
+        combine_with_exported_module!(lib, "regex", regex_functions);
+    }
+}
+
+/// Compile a regular expression, using the cache of compiled regular expressions in the [`Engine`].
+fn compile_regex(ctx: &NativeCallContext, pattern: ImmutableString) -> RhaiResultOf<regex::Regex> {
+    let cache = &ctx.engine().regex_cache;
+
+    if let Some(re) = locked_read(cache).and_then(|c| c.get(&pattern).cloned()) {
+        return Ok(re);
+    }
+
+    let re = regex::Regex::new(&pattern).map_err(|err| {
+        ERR::ErrorRuntime(
+            format!("Invalid regular expression: {err}").into(),
+            Position::NONE,
+        )
+    })?;
+
+    if let Some(mut c) = locked_write(cache) {
+        if c.len() >= MAX_REGEX_CACHE_SIZE {
+            c.clear();
+        }
+        c.insert(pattern, re.clone());
+    }
+
+    Ok(re)
+}
+
+#[export_module]
+mod regex_functions {
+    /// A compiled regular expression.
+    pub type Regex = regex::Regex;
+
+    /// Compile a regular expression from a `pattern`.
+    ///
+    /// Compiled regular expressions are cached, so compiling the same `pattern` again is cheap.
+    ///
+    /// An error is raised if the `pattern` is not a valid regular expression.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let re = regex("\\d+");
+    ///
+    /// print(re.is_match("abc123"));   // prints true
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn regex(ctx: NativeCallContext, pattern: ImmutableString) -> RhaiResultOf<Regex> {
+        compile_regex(&ctx, pattern)
+    }
+
+    /// Return the pattern of the regular expression.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let re = regex("\\d+");
+    ///
+    /// print(re.pattern);      // prints "\d+"
+    /// ```
+    #[rhai_fn(get = "pattern", pure)]
+    pub fn pattern(re: &mut Regex) -> ImmutableString {
+        re.as_str().into()
+    }
+    /// Return the pattern of the regular expression.
+    #[rhai_fn(name = "print", name = "to_string", pure)]
+    pub fn to_string(re: &mut Regex) -> ImmutableString {
+        re.as_str().into()
+    }
+    /// Convert the regular expression into a string in debug format.
+    #[rhai_fn(name = "debug", name = "to_debug", pure)]
+    pub fn to_debug(re: &mut Regex) -> ImmutableString {
+        format!("regex({:?})", re.as_str()).into()
+    }
+
+    /// Return `true` if the regular expression matches anywhere in the `text`.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let re = regex("^\\d+$");
+    ///
+    /// print(re.is_match("123"));      // prints true
+    ///
+    /// print(re.is_match("abc123"));   // prints false
+    /// ```
+    #[rhai_fn(pure)]
+    pub fn is_match(re: &mut Regex, text: &str) -> bool {
+        re.is_match(text)
+    }
+    /// Return the first match of the regular expression in the `text`,
+    /// or `()` if there is no match.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let re = regex("\\d+");
+    ///
+    /// print(re.find("abc123def456"));     // prints "123"
+    ///
+    /// print(re.find("abc"));              // prints ""
+    /// ```
+    #[rhai_fn(pure)]
+    pub fn find(re: &mut Regex, text: &str) -> Dynamic {
+        re.find(text)
+            .map_or(Dynamic::UNIT, |m| m.as_str().to_string().into())
+    }
+    /// Replace the first match of the regular expression in the `text` with a `replacement`.
+    ///
+    /// Capture groups can be referred to in the `replacement` as `$1` or `${name}`.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let re = regex("(?<word>\\w+)@");
+    ///
+    /// print(re.replace("a@b@", "[${word}]"));     // prints "[a]b@"
+    /// ```
+    #[rhai_fn(pure)]
+    pub fn replace(re: &mut Regex, text: &str, replacement: &str) -> ImmutableString {
+        re.replace(text, replacement).into_owned().into()
+    }
+    /// Replace all matches of the regular expression in the `text` with a `replacement`.
+    ///
+    /// Capture groups can be referred to in the `replacement` as `$1` or `${name}`.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let re = regex("(?<word>\\w+)@");
+    ///
+    /// print(re.replace_all("a@b@", "[${word}]"));     // prints "[a][b]"
+    /// ```
+    #[rhai_fn(pure)]
+    pub fn replace_all(re: &mut Regex, text: &str, replacement: &str) -> ImmutableString {
+        re.replace_all(text, replacement).into_owned().into()
+    }
+
+    /// Return the named capture groups of the first match of the regular expression in the
+    /// `text` as an object map, or `()` if there is no match.
+    ///
+    /// Named capture groups that do not participate in the match are `()`.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let re = regex("(?<year>\\d{4})-(?<month>\\d{2})");
+    ///
+    /// let m = re.captures("Date: 2024-05");
+    ///
+    /// print(m.year);      // prints "2024"
+    /// print(m.month);     // prints "05"
+    /// ```
+    #[cfg(not(feature = "no_object"))]
+    #[rhai_fn(pure)]
+    pub fn captures(re: &mut Regex, text: &str) -> Dynamic {
+        re.captures(text)
+            .map_or(Dynamic::UNIT, |caps| captures_to_map(re, &caps).into())
+    }
+
+    #[cfg(not(feature = "no_index"))]
+    pub mod arrays {
+        /// Return all non-overlapping matches of the regular expression in the `text`
+        /// as an array of strings.
+        ///
+        /// # Example
+        ///
+        /// ```rhai
+        /// let re = regex("\\d+");
+        ///
+        /// print(re.find_all("a1b22c333"));    // prints ["1", "22", "333"]
+        /// ```
+        #[rhai_fn(pure)]
+        pub fn find_all(re: &mut Regex, text: &str) -> Array {
+            re.find_iter(text)
+                .map(|m| m.as_str().to_string().into())
+                .collect()
+        }
+        /// Split the `text` into segments separated by matches of the regular expression,
+        /// returning an array of the segments.
+        ///
+        /// # Example
+        ///
+        /// ```rhai
+        /// let re = regex("\\s*,\\s*");
+        ///
+        /// print(re.split("a , b,c"));     // prints ["a", "b", "c"]
+        /// ```
+        #[rhai_fn(pure)]
+        pub fn split(re: &mut Regex, text: &str) -> Array {
+            re.split(text).map(|s| s.to_string().into()).collect()
+        }
+        /// Return the named capture groups of all non-overlapping matches of the regular
+        /// expression in the `text` as an array of object maps.
+        ///
+        /// Named capture groups that do not participate in a match are `()`.
+        ///
+        /// # Example
+        ///
+        /// ```rhai
+        /// let re = regex("(?<key>\\w+)=(?<value>\\w+)");
+        ///
+        /// for m in re.captures_all("a=1, b=2") {
+        ///     print(`${m.key} is ${m.value}`);
+        /// }
+        /// ```
+        #[cfg(not(feature = "no_object"))]
+        #[rhai_fn(pure)]
+        pub fn captures_all(re: &mut Regex, text: &str) -> Array {
+            re.captures_iter(text)
+                .map(|caps| captures_to_map(re, &caps).into())
+                .collect()
+        }
+    }
+}
+
+/// Convert the named capture groups of a match into an object map.
+#[cfg(not(feature = "no_object"))]
+fn captures_to_map(re: &regex::Regex, caps: &regex::Captures) -> Map {
+    re.capture_names()
+        .flatten()
+        .map(|name| {
+            let value = caps
+                .name(name)
+                .map_or(Dynamic::UNIT, |m| m.as_str().to_string().into());
+            (name.into(), value)
+        })
+        .collect()
+}
@@ -87,6 +87,29 @@ mod print_debug_functions {
         write!(&mut buf, "{item:?}").unwrap();
         ctx.engine().map_type_name(&buf).into()
     }
+    /// Convert the value of the `item` into a string with nested arrays and object maps indented
+    /// one item per line, truncating large collections and long strings.
+    ///
+    /// When called as `pprint`, the string is printed just like `print`.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let x = #{ a: [1, 2], b: "hello" };
+    ///
+    /// pprint(x);      // prints the object map over multiple lines
+    ///
+    /// let s = to_pretty_string(x);
+    /// ```
+    #[rhai_fn(name = "pprint", name = "to_pretty_string", pure)]
+    pub fn pretty_print_generic(ctx: NativeCallContext, item: &mut Dynamic) -> ImmutableString {
+        item.to_pretty_string_with(crate::PrettyPrintOptions::new(), |value| {
+            let text = print_with_func(FUNC_TO_DEBUG, &ctx, &mut value.clone());
+            let type_name = ctx.engine().map_type_name(value.type_name());
+            (text.to_string(), type_name.to_string())
+        })
+        .into()
+    }
 
     /// Return the empty string.
     #[rhai_fn(name = "print", name = "debug")]
@@ -161,18 +184,14 @@ mod print_debug_functions {
     /// Convert the value of `number` into a string.
     #[cfg(not(feature = "no_float"))]
     #[rhai_fn(name = "print", name = "to_string")]
-    pub fn print_f64(number: f64) -> ImmutableString {
-        let mut buf = SmartString::new_const();
-        write!(&mut buf, "{}", crate::types::FloatWrapper::new(number)).unwrap();
-        buf.into()
+    pub fn print_f64(ctx: NativeCallContext, number: f64) -> ImmutableString {
+        ctx.engine().float_format().format(number).into()
     }
     /// Convert the value of `number` into a string.
     #[cfg(not(feature = "no_float"))]
     #[rhai_fn(name = "print", name = "to_string")]
-    pub fn print_f32(number: f32) -> ImmutableString {
-        let mut buf = SmartString::new_const();
-        write!(&mut buf, "{}", crate::types::FloatWrapper::new(number)).unwrap();
-        buf.into()
+    pub fn print_f32(ctx: NativeCallContext, number: f32) -> ImmutableString {
+        ctx.engine().float_format().format(number).into()
     }
     /// Convert the value of `number` into a string.
     #[cfg(not(feature = "no_float"))]
@@ -226,20 +245,23 @@ mod print_debug_functions {
         pure
     )]
     pub fn format_map(ctx: NativeCallContext, map: &mut Map) -> ImmutableString {
-        let len = map.len();
         let mut result = SmartString::new_const();
         result.push_str("#{");
 
-        map.iter_mut().enumerate().for_each(|(i, (k, v))| {
-            write!(
-                result,
-                "{:?}: {}{}",
-                k,
-                &print_with_func(FUNC_TO_DEBUG, &ctx, v),
-                if i < len - 1 { ", " } else { "" }
-            )
-            .unwrap();
-        });
+        // Skip the prototype (if any)
+        map.iter_mut()
+            .filter(|(k, ..)| k.as_str() != crate::types::prototype::PROP_PROTOTYPE)
+            .enumerate()
+            .for_each(|(i, (k, v))| {
+                write!(
+                    result,
+                    "{}{:?}: {}",
+                    if i > 0 { ", " } else { "" },
+                    k,
+                    &print_with_func(FUNC_TO_DEBUG, &ctx, v)
+                )
+                .unwrap();
+            });
 
         result.push_str("}");
         result.into()
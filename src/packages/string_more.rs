@@ -18,6 +18,98 @@ def_package! {
     }
 }
 
+/// Calculate the Levenshtein distance between two strings, i.e. the minimum number of single
+/// character insertions, deletions and substitutions to change one into the other.
+fn levenshtein(string1: &str, string2: &str) -> usize {
+    let chars2 = string2.chars().collect::<Vec<_>>();
+
+    // Only keep one row of the distance matrix
+    let mut row = (0..=chars2.len()).collect::<Vec<_>>();
+
+    for (i, ch1) in string1.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, &ch2) in chars2.iter().enumerate() {
+            let cost = usize::from(ch1 != ch2);
+            let value = (diagonal + cost).min(row[j] + 1).min(row[j + 1] + 1);
+            diagonal = row[j + 1];
+            row[j + 1] = value;
+        }
+    }
+
+    row[chars2.len()]
+}
+
+/// Calculate the Jaro-Winkler similarity between two strings, from 0.0 (nothing in common) to
+/// 1.0 (identical).
+#[cfg(not(feature = "no_float"))]
+#[allow(clippy::cast_precision_loss)]
+fn jaro_winkler(string1: &str, string2: &str) -> crate::FLOAT {
+    let chars1 = string1.chars().collect::<Vec<_>>();
+    let chars2 = string2.chars().collect::<Vec<_>>();
+
+    match (chars1.len(), chars2.len()) {
+        (0, 0) => return 1.0,
+        (0, _) | (_, 0) => return 0.0,
+        _ => (),
+    }
+
+    // Characters only match within this distance of each other
+    let window = (chars1.len().max(chars2.len()) / 2).saturating_sub(1);
+
+    let mut matched1 = vec![false; chars1.len()];
+    let mut matched2 = vec![false; chars2.len()];
+    let mut matches = 0_usize;
+
+    for (i, &ch) in chars1.iter().enumerate() {
+        let end = (i + window + 1).min(chars2.len());
+
+        for j in i.saturating_sub(window)..end {
+            if !matched2[j] && chars2[j] == ch {
+                matched1[i] = true;
+                matched2[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    // Count matched characters that are out of order
+    let mut transpositions = 0_usize;
+    let mut k = 0;
+
+    for (&ch, ..) in chars1.iter().zip(matched1.iter()).filter(|&(.., &m)| m) {
+        while !matched2[k] {
+            k += 1;
+        }
+        if ch != chars2[k] {
+            transpositions += 1;
+        }
+        k += 1;
+    }
+
+    let m = matches as crate::FLOAT;
+    let jaro = (m / chars1.len() as crate::FLOAT
+        + m / chars2.len() as crate::FLOAT
+        + (m - (transpositions / 2) as crate::FLOAT) / m)
+        / 3.0;
+
+    // Boost strings sharing a common prefix (up to 4 characters)
+    let prefix = chars1
+        .iter()
+        .zip(chars2.iter())
+        .take(4)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    jaro + prefix as crate::FLOAT * 0.1 * (1.0 - jaro)
+}
+
 #[export_module]
 mod string_functions {
     #[rhai_fn(name = "+", pure)]
@@ -1447,10 +1539,71 @@ mod string_functions {
         }
     }
 
+    /// Return the edit (Levenshtein) distance between two strings, i.e. the minimum number of
+    /// single character insertions, deletions and substitutions to change one into the other.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// print(edit_distance("kitten", "sitting"));      // prints 3
+    ///
+    /// print(edit_distance("hello", "hello"));         // prints 0
+    /// ```
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn edit_distance(string1: &str, string2: &str) -> INT {
+        super::levenshtein(string1, string2) as INT
+    }
+    /// Return the (Jaro-Winkler) similarity between two strings, from 0.0 (nothing in common) to
+    /// 1.0 (identical).
+    ///
+    /// Strings sharing a common prefix are considered more similar.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// print(similarity("martha", "marhta"));      // prints 0.9611111111111111
+    ///
+    /// print(similarity("hello", "world") < 0.5);  // prints true
+    /// ```
+    #[cfg(not(feature = "no_float"))]
+    pub fn similarity(string1: &str, string2: &str) -> crate::FLOAT {
+        super::jaro_winkler(string1, string2)
+    }
+
     #[cfg(not(feature = "no_index"))]
     pub mod arrays {
         use crate::Array;
 
+        /// Return all strings in the `haystack` array whose similarity (see `similarity`) to the
+        /// `needle` string is at least `threshold`, ordered from the most similar.
+        ///
+        /// Elements of the `haystack` that are not strings are skipped.
+        ///
+        /// # Example
+        ///
+        /// ```rhai
+        /// let names = ["apple", "apply", "maple", "banana"];
+        ///
+        /// print(fuzzy_match("appel", names, 0.8));    // prints ["apple", "apply"]
+        /// ```
+        #[cfg(not(feature = "no_float"))]
+        pub fn fuzzy_match(needle: &str, haystack: Array, threshold: crate::FLOAT) -> Array {
+            let mut matches = haystack
+                .into_iter()
+                .filter_map(|item| {
+                    let score =
+                        super::super::jaro_winkler(needle, &item.read_lock::<ImmutableString>()?);
+                    (score >= threshold).then_some((score, item))
+                })
+                .collect::<Vec<_>>();
+
+            // Stable sort keeps the original order of equally similar strings
+            matches
+                .sort_by(|(a, ..), (b, ..)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+            matches.into_iter().map(|(.., item)| item).collect()
+        }
+
         /// Split the string into two at the specified `index` position and return it both strings
         /// as an array.
         ///
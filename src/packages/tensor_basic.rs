@@ -0,0 +1,849 @@
+#![cfg(feature = "tensor")]
+#![cfg(not(feature = "no_float"))]
+#![cfg(not(feature = "no_index"))]
+
+use crate::eval::calc_index;
+use crate::plugin::*;
+use crate::{def_package, Array, Dynamic, Position, RhaiError, RhaiResultOf, ERR, FLOAT, INT};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{fmt, iter};
+
+def_package! {
+    /// Package of N-dimensional arrays of floating-point numbers.
+    ///
+    /// The [`Tensor`] type supports element-wise arithmetic operators (with broadcasting),
+    /// matrix multiplication, indexing along the first axis and iteration.
+    pub TensorPackage(lib) {
+        lib.set_standard_lib(true);
+
+        combine_with_exported_module!(lib, "tensor", tensor_functions);
+
+        // Register the iterator over the first axis
+        lib.set_iterable::<Tensor>();
+    }
+}
+
+/// A dense N-dimensional array of floating-point numbers, stored in row-major order.
+///
+/// Not available under `no_float` or `no_index`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Tensor {
+    /// Size of each dimension.
+    shape: Vec<usize>,
+    /// Elements in row-major order.
+    data: Vec<FLOAT>,
+}
+
+impl Tensor {
+    /// Create a new [`Tensor`] from its shape and elements in row-major order.
+    ///
+    /// Returns [`None`] if the number of elements does not match the shape.
+    #[inline]
+    #[must_use]
+    pub fn new(shape: impl Into<Vec<usize>>, data: impl Into<Vec<FLOAT>>) -> Option<Self> {
+        let shape = shape.into();
+        let data = data.into();
+
+        if shape.iter().product::<usize>() == data.len() {
+            Some(Self { shape, data })
+        } else {
+            None
+        }
+    }
+    /// Create a new [`Tensor`] of a particular shape filled with a value.
+    #[inline]
+    #[must_use]
+    pub fn filled(shape: impl Into<Vec<usize>>, value: FLOAT) -> Self {
+        let shape = shape.into();
+        let data = vec![value; shape.iter().product()];
+        Self { shape, data }
+    }
+    /// Get the size of each dimension.
+    #[inline(always)]
+    #[must_use]
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+    /// Get the elements in row-major order.
+    #[inline(always)]
+    #[must_use]
+    pub fn data(&self) -> &[FLOAT] {
+        &self.data
+    }
+    /// Get mutable access to the elements in row-major order.
+    #[inline(always)]
+    #[must_use]
+    pub fn data_mut(&mut self) -> &mut [FLOAT] {
+        &mut self.data
+    }
+    /// Get the number of dimensions.
+    #[inline(always)]
+    #[must_use]
+    pub fn ndim(&self) -> usize {
+        self.shape.len()
+    }
+    /// Get the size of the first dimension, or zero for a scalar.
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shape.first().copied().unwrap_or(0)
+    }
+    /// Is the [`Tensor`] without elements?
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    /// Get the number of elements within each entry along the first dimension.
+    #[inline(always)]
+    #[must_use]
+    fn entry_size(&self) -> usize {
+        self.shape.iter().skip(1).product()
+    }
+    /// Get the entry at an index along the first dimension.
+    ///
+    /// The entry is a number for a one-dimensional [`Tensor`], otherwise it is a [`Tensor`] with
+    /// one fewer dimension.
+    fn entry(&self, index: usize) -> Dynamic {
+        if self.ndim() == 1 {
+            return self.data[index].into();
+        }
+
+        let size = self.entry_size();
+
+        Dynamic::from(Self {
+            shape: self.shape[1..].to_vec(),
+            data: self.data[index * size..(index + 1) * size].to_vec(),
+        })
+    }
+    /// Get the entries in a range along the first dimension.
+    #[must_use]
+    fn slice(&self, start: usize, len: usize) -> Self {
+        let size = self.entry_size();
+        let mut shape = self.shape.clone();
+        shape[0] = len;
+
+        Self {
+            shape,
+            data: self.data[start * size..(start + len) * size].to_vec(),
+        }
+    }
+    /// Apply a function on every element, returning a new [`Tensor`].
+    #[inline]
+    #[must_use]
+    fn map(&self, f: impl Fn(FLOAT) -> FLOAT) -> Self {
+        Self {
+            shape: self.shape.clone(),
+            data: self.data.iter().map(|&x| f(x)).collect(),
+        }
+    }
+    /// Apply a function element-wise on two tensors, broadcasting their shapes when they differ.
+    ///
+    /// Shapes are compatible when, aligned from the last dimension, each pair of dimensions are
+    /// either equal or one of them is 1 (missing leading dimensions count as 1).
+    fn zip_with(&self, other: &Self, f: impl Fn(FLOAT, FLOAT) -> FLOAT) -> RhaiResultOf<Self> {
+        if self.shape == other.shape {
+            return Ok(Self {
+                shape: self.shape.clone(),
+                data: self
+                    .data
+                    .iter()
+                    .zip(other.data.iter())
+                    .map(|(&x, &y)| f(x, y))
+                    .collect(),
+            });
+        }
+
+        let ndim = self.ndim().max(other.ndim());
+        let pad = |shape: &[usize]| {
+            iter::repeat(1)
+                .take(ndim - shape.len())
+                .chain(shape.iter().copied())
+                .collect::<Vec<_>>()
+        };
+        let (shape1, shape2) = (pad(&self.shape), pad(&other.shape));
+
+        let shape = shape1
+            .iter()
+            .zip(shape2.iter())
+            .map(|(&x, &y)| match (x, y) {
+                _ if x == y => Some(x),
+                (1, _) => Some(y),
+                (_, 1) => Some(x),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                ERR::ErrorRuntime(
+                    format!(
+                        "Cannot broadcast tensors of shapes {:?} and {:?}",
+                        self.shape, other.shape
+                    )
+                    .into(),
+                    Position::NONE,
+                )
+            })?;
+
+        // Broadcast dimensions do not advance in the source tensor
+        let broadcast_strides = |src: &[usize]| {
+            strides(src)
+                .into_iter()
+                .zip(src.iter().zip(shape.iter()))
+                .map(|(stride, (&d, &n))| if d == n { stride } else { 0 })
+                .collect::<Vec<_>>()
+        };
+        let (strides1, strides2) = (broadcast_strides(&shape1), broadcast_strides(&shape2));
+
+        let len = shape.iter().product();
+        let mut data = Vec::with_capacity(len);
+
+        for mut n in 0..len {
+            let (mut offset1, mut offset2) = (0, 0);
+
+            for axis in (0..ndim).rev() {
+                let index = n % shape[axis];
+                n /= shape[axis];
+                offset1 += index * strides1[axis];
+                offset2 += index * strides2[axis];
+            }
+
+            data.push(f(self.data[offset1], other.data[offset2]));
+        }
+
+        Ok(Self { shape, data })
+    }
+    /// Convert into nested arrays of numbers.
+    #[must_use]
+    fn to_array(&self) -> Array {
+        (0..self.len())
+            .map(|i| match self.entry(i) {
+                entry if entry.is::<Self>() => entry.cast::<Self>().to_array().into(),
+                entry => entry,
+            })
+            .collect()
+    }
+}
+
+/// Calculate the row-major strides of a shape.
+#[must_use]
+fn strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+
+    strides
+}
+
+/// Convert an array of dimension sizes into a shape.
+fn to_shape(shape: &Array) -> RhaiResultOf<Vec<usize>> {
+    shape
+        .iter()
+        .map(|d| match d.as_int() {
+            Ok(n) if n >= 0 => Ok(n as usize),
+            _ => Err(ERR::ErrorRuntime(
+                format!("Invalid tensor dimension: {d}").into(),
+                Position::NONE,
+            )
+            .into()),
+        })
+        .collect()
+}
+
+/// Flatten nested arrays of a particular shape into a list of numbers.
+fn flatten(value: &Dynamic, shape: &[usize], data: &mut Vec<FLOAT>) -> RhaiResultOf<()> {
+    if shape.is_empty() {
+        let x = match (value.as_float(), value.as_int()) {
+            (Ok(x), _) => x,
+            (_, Ok(n)) => n as FLOAT,
+            _ => {
+                return Err(ERR::ErrorMismatchDataType(
+                    "number".into(),
+                    value.type_name().into(),
+                    Position::NONE,
+                )
+                .into())
+            }
+        };
+        data.push(x);
+        return Ok(());
+    }
+
+    match value.read_lock::<Array>() {
+        Some(array) if array.len() == shape[0] => array
+            .iter()
+            .try_for_each(|item| flatten(item, &shape[1..], data)),
+        _ => Err(ERR::ErrorRuntime(
+            "Nested arrays of unequal lengths cannot be converted into a tensor".into(),
+            Position::NONE,
+        )
+        .into()),
+    }
+}
+
+impl fmt::Display for Tensor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn write(f: &mut fmt::Formatter, shape: &[usize], data: &[FLOAT]) -> fmt::Result {
+            if shape.is_empty() {
+                return write!(f, "{:?}", data[0]);
+            }
+
+            let size = shape[1..].iter().product::<usize>();
+
+            f.write_str("[")?;
+            for i in 0..shape[0] {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write(f, &shape[1..], &data[i * size..(i + 1) * size])?;
+            }
+            f.write_str("]")
+        }
+
+        write(f, &self.shape, &self.data)
+    }
+}
+
+impl IntoIterator for Tensor {
+    type Item = Dynamic;
+    type IntoIter = std::vec::IntoIter<Dynamic>;
+
+    /// Iterate through the entries along the first dimension.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        (0..self.len())
+            .map(|i| self.entry(i))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+#[export_module]
+mod tensor_functions {
+    use crate::{ExclusiveRange, InclusiveRange};
+
+    /// A dense N-dimensional array of floating-point numbers.
+    pub type Tensor = super::Tensor;
+
+    /// Create a tensor from nested arrays of numbers.
+    ///
+    /// All nested arrays at the same depth must be of the same length.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = tensor([[1, 2, 3], [4, 5, 6]]);
+    ///
+    /// print(t.shape);     // prints "[2, 3]"
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn tensor(array: Array) -> RhaiResultOf<Tensor> {
+        let value = Dynamic::from_array(array);
+
+        // Take the shape from the first element at each depth
+        let mut shape = Vec::new();
+        let mut first = Some(value.clone());
+
+        while let Some(v) = first {
+            first = match v.read_lock::<Array>() {
+                Some(a) => {
+                    shape.push(a.len());
+                    a.first().cloned()
+                }
+                None => break,
+            };
+        }
+
+        let mut data = Vec::with_capacity(shape.iter().product());
+        super::flatten(&value, &shape, &mut data)?;
+
+        Ok(Tensor { shape, data })
+    }
+    /// Create a tensor of a particular `shape` filled with zeros.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = zeros([2, 2]);
+    ///
+    /// print(t);       // prints "[[0.0, 0.0], [0.0, 0.0]]"
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn zeros(shape: Array) -> RhaiResultOf<Tensor> {
+        Ok(Tensor::filled(super::to_shape(&shape)?, 0.0))
+    }
+    /// Create a tensor of a particular `shape` filled with ones.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = ones([3]);
+    ///
+    /// print(t);       // prints "[1.0, 1.0, 1.0]"
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn ones(shape: Array) -> RhaiResultOf<Tensor> {
+        Ok(Tensor::filled(super::to_shape(&shape)?, 1.0))
+    }
+    /// Create an `n` by `n` identity matrix.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = eye(2);
+    ///
+    /// print(t);       // prints "[[1.0, 0.0], [0.0, 1.0]]"
+    /// ```
+    pub fn eye(n: INT) -> Tensor {
+        let n = n.max(0) as usize;
+        let mut t = Tensor::filled([n, n], 0.0);
+        (0..n).for_each(|i| t.data[i * n + i] = 1.0);
+        t
+    }
+
+    /// Return the size of each dimension of the tensor as an array.
+    #[rhai_fn(get = "shape", pure)]
+    pub fn shape(t: &mut Tensor) -> Array {
+        t.shape.iter().map(|&d| (d as INT).into()).collect()
+    }
+    /// Return the number of dimensions of the tensor.
+    #[rhai_fn(name = "ndim", get = "ndim", pure)]
+    pub fn ndim(t: &mut Tensor) -> INT {
+        t.ndim() as INT
+    }
+    /// Return the size of the first dimension of the tensor.
+    #[rhai_fn(name = "len", get = "len", pure)]
+    pub fn len(t: &mut Tensor) -> INT {
+        t.len() as INT
+    }
+    /// Return the total number of elements in the tensor.
+    #[rhai_fn(name = "size", get = "size", pure)]
+    pub fn size(t: &mut Tensor) -> INT {
+        t.data.len() as INT
+    }
+    /// Convert the tensor into nested arrays of numbers.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = eye(2);
+    ///
+    /// let a = t.to_array();
+    ///
+    /// print(a[1][1]);     // prints 1.0
+    /// ```
+    #[rhai_fn(pure)]
+    pub fn to_array(t: &mut Tensor) -> Array {
+        t.to_array()
+    }
+
+    /// Return a copy of the tensor with the same elements but a different `shape`.
+    ///
+    /// An error is raised if the number of elements does not match the new `shape`.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = tensor([1, 2, 3, 4, 5, 6]);
+    ///
+    /// print(t.reshape([2, 3]));   // prints "[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]"
+    /// ```
+    #[rhai_fn(pure, return_raw)]
+    pub fn reshape(t: &mut Tensor, shape: Array) -> RhaiResultOf<Tensor> {
+        let shape = super::to_shape(&shape)?;
+
+        Tensor::new(shape, t.data.clone()).ok_or_else(|| {
+            ERR::ErrorRuntime(
+                format!("Cannot reshape a tensor of {} elements", t.data.len()).into(),
+                Position::NONE,
+            )
+            .into()
+        })
+    }
+    /// Return the transpose of the tensor (i.e. with the order of dimensions reversed).
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = tensor([[1, 2, 3], [4, 5, 6]]);
+    ///
+    /// print(t.transpose());   // prints "[[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]"
+    /// ```
+    #[rhai_fn(pure)]
+    pub fn transpose(t: &mut Tensor) -> Tensor {
+        let strides = super::strides(&t.shape);
+        let shape = t.shape.iter().rev().copied().collect::<Vec<_>>();
+
+        let data = (0..t.data.len())
+            .map(|mut n| {
+                // Index in the transpose counts dimensions of the original in reverse
+                let mut offset = 0;
+                for (axis, &d) in shape.iter().enumerate().rev() {
+                    offset += (n % d) * strides[shape.len() - 1 - axis];
+                    n /= d;
+                }
+                t.data[offset]
+            })
+            .collect();
+
+        Tensor { shape, data }
+    }
+    /// Return the matrix product of two tensors.
+    ///
+    /// Both tensors must have one or two dimensions. A one-dimensional `a` is treated as a row
+    /// vector and a one-dimensional `b` as a column vector, and that dimension is removed from
+    /// the result.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let a = tensor([[1, 2], [3, 4]]);
+    /// let b = tensor([[5], [6]]);
+    ///
+    /// print(matmul(a, b));    // prints "[[17.0], [39.0]]"
+    /// ```
+    #[rhai_fn(return_raw)]
+    pub fn matmul(a: Tensor, b: Tensor) -> RhaiResultOf<Tensor> {
+        let dims = match (&a.shape[..], &b.shape[..]) {
+            (&[k1], &[k2]) => Some((1, k1, k2, 1)),
+            (&[k1], &[k2, n]) => Some((1, k1, k2, n)),
+            (&[m, k1], &[k2]) => Some((m, k1, k2, 1)),
+            (&[m, k1], &[k2, n]) => Some((m, k1, k2, n)),
+            _ => None,
+        };
+
+        let (m, k1, n) = match dims {
+            Some((m, k1, k2, n)) if k1 == k2 => (m, k1, n),
+            _ => {
+                return Err(ERR::ErrorRuntime(
+                    format!(
+                        "Cannot multiply matrices of shapes {:?} and {:?}",
+                        a.shape, b.shape
+                    )
+                    .into(),
+                    Position::NONE,
+                )
+                .into())
+            }
+        };
+
+        let mut data = vec![0.0; m * n];
+
+        for i in 0..m {
+            for p in 0..k1 {
+                let x = a.data[i * k1 + p];
+                for j in 0..n {
+                    data[i * n + j] += x * b.data[p * n + j];
+                }
+            }
+        }
+
+        let shape = match (a.ndim(), b.ndim()) {
+            (1, 1) => vec![],
+            (1, _) => vec![n],
+            (_, 1) => vec![m],
+            _ => vec![m, n],
+        };
+
+        Ok(Tensor { shape, data })
+    }
+
+    /// Return the sum of all elements in the tensor.
+    #[rhai_fn(pure)]
+    pub fn sum(t: &mut Tensor) -> FLOAT {
+        t.data.iter().sum()
+    }
+    /// Return the average of all elements in the tensor, or `()` if it is empty.
+    #[rhai_fn(pure)]
+    pub fn mean(t: &mut Tensor) -> Dynamic {
+        if t.data.is_empty() {
+            return Dynamic::UNIT;
+        }
+        (t.data.iter().sum::<FLOAT>() / t.data.len() as FLOAT).into()
+    }
+    /// Return the smallest element in the tensor, or `()` if it is empty.
+    #[rhai_fn(pure)]
+    pub fn min(t: &mut Tensor) -> Dynamic {
+        t.data
+            .iter()
+            .copied()
+            .reduce(FLOAT::min)
+            .map_or(Dynamic::UNIT, Into::into)
+    }
+    /// Return the largest element in the tensor, or `()` if it is empty.
+    #[rhai_fn(pure)]
+    pub fn max(t: &mut Tensor) -> Dynamic {
+        t.data
+            .iter()
+            .copied()
+            .reduce(FLOAT::max)
+            .map_or(Dynamic::UNIT, Into::into)
+    }
+
+    /// Get the entry at the `index` position along the first dimension of the tensor.
+    ///
+    /// The entry is a number for a one-dimensional tensor, otherwise it is a tensor with one
+    /// fewer dimension.
+    ///
+    /// * If `index` < 0, position counts from the end of the dimension (`-1` is the last entry).
+    /// * If `index` is out of bounds, an error is raised.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = tensor([[1, 2], [3, 4]]);
+    ///
+    /// print(t[1]);        // prints "[3.0, 4.0]"
+    ///
+    /// print(t[-1][0]);    // prints 3.0
+    /// ```
+    #[rhai_fn(index_get, pure, return_raw)]
+    pub fn get(t: &mut Tensor, index: INT) -> RhaiResultOf<Dynamic> {
+        let index = calc_index(t.len(), index, true, || {
+            Err::<_, RhaiError>(ERR::ErrorArrayBounds(t.len(), index, Position::NONE).into())
+        })?;
+        Ok(t.entry(index))
+    }
+    /// Get the entries in an exclusive `range` along the first dimension of the tensor.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = tensor([1, 2, 3, 4]);
+    ///
+    /// print(t[1..3]);     // prints "[2.0, 3.0]"
+    /// ```
+    #[rhai_fn(index_get, pure)]
+    pub fn slice_range(t: &mut Tensor, range: ExclusiveRange) -> Tensor {
+        let start = INT::max(range.start, 0);
+        let end = INT::max(range.end, start);
+        let (start, len) = crate::eval::calc_offset_len(t.len(), start, end - start);
+        t.slice(start, len)
+    }
+    /// Get the entries in an inclusive `range` along the first dimension of the tensor.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = tensor([1, 2, 3, 4]);
+    ///
+    /// print(t[1..=2]);    // prints "[2.0, 3.0]"
+    /// ```
+    #[rhai_fn(index_get, pure)]
+    pub fn slice_inclusive_range(t: &mut Tensor, range: InclusiveRange) -> Tensor {
+        let start = INT::max(*range.start(), 0);
+        let end = INT::max(*range.end(), start);
+        let (start, len) = crate::eval::calc_offset_len(t.len(), start, end - start + 1);
+        t.slice(start, len)
+    }
+    /// Set the element at the `index` position of a one-dimensional tensor.
+    ///
+    /// * If `index` < 0, position counts from the end of the tensor (`-1` is the last element).
+    /// * If `index` is out of bounds, an error is raised.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = zeros([3]);
+    ///
+    /// t[1] = 42.0;
+    ///
+    /// print(t);       // prints "[0.0, 42.0, 0.0]"
+    /// ```
+    #[rhai_fn(index_set, return_raw)]
+    pub fn set(t: &mut Tensor, index: INT, value: FLOAT) -> RhaiResultOf<()> {
+        set_entry(t, index, Tensor::filled(Vec::new(), value))
+    }
+    /// Set the element at the `index` position of a one-dimensional tensor.
+    #[rhai_fn(index_set, return_raw)]
+    pub fn set_int(t: &mut Tensor, index: INT, value: INT) -> RhaiResultOf<()> {
+        set_entry(t, index, Tensor::filled(Vec::new(), value as FLOAT))
+    }
+    /// Set the entry at the `index` position along the first dimension of the tensor.
+    ///
+    /// The `value` must have the same shape as the existing entry.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = zeros([2, 2]);
+    ///
+    /// t[0] = tensor([1, 2]);
+    ///
+    /// print(t);       // prints "[[1.0, 2.0], [0.0, 0.0]]"
+    /// ```
+    #[rhai_fn(index_set, return_raw)]
+    pub fn set_entry(t: &mut Tensor, index: INT, value: Tensor) -> RhaiResultOf<()> {
+        let index = calc_index(t.len(), index, true, || {
+            Err::<_, RhaiError>(ERR::ErrorArrayBounds(t.len(), index, Position::NONE).into())
+        })?;
+
+        if value.shape != t.shape[1..] {
+            return Err(ERR::ErrorRuntime(
+                format!(
+                    "Cannot set an entry of shape {:?} into a tensor of shape {:?}",
+                    value.shape, t.shape
+                )
+                .into(),
+                Position::NONE,
+            )
+            .into());
+        }
+
+        let size = t.entry_size();
+        t.data[index * size..(index + 1) * size].copy_from_slice(&value.data);
+
+        Ok(())
+    }
+
+    /// Return `true` if two tensors have the same shape and elements.
+    #[rhai_fn(name = "==")]
+    pub fn equals(a: Tensor, b: Tensor) -> bool {
+        a == b
+    }
+    /// Return `true` if two tensors differ in shape or elements.
+    #[rhai_fn(name = "!=")]
+    pub fn not_equals(a: Tensor, b: Tensor) -> bool {
+        a != b
+    }
+
+    /// Negate every element of the tensor.
+    #[rhai_fn(name = "-")]
+    pub fn neg(t: Tensor) -> Tensor {
+        t.map(|x| -x)
+    }
+
+    /// Add two tensors element-wise, broadcasting their shapes.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = tensor([[1, 2], [3, 4]]) + tensor([10, 20]);
+    ///
+    /// print(t);       // prints "[[11.0, 22.0], [13.0, 24.0]]"
+    /// ```
+    #[rhai_fn(name = "+", return_raw)]
+    pub fn add(a: Tensor, b: Tensor) -> RhaiResultOf<Tensor> {
+        a.zip_with(&b, |x, y| x + y)
+    }
+    /// Subtract two tensors element-wise, broadcasting their shapes.
+    #[rhai_fn(name = "-", return_raw)]
+    pub fn subtract(a: Tensor, b: Tensor) -> RhaiResultOf<Tensor> {
+        a.zip_with(&b, |x, y| x - y)
+    }
+    /// Multiply two tensors element-wise, broadcasting their shapes.
+    ///
+    /// Use `matmul` for the matrix product.
+    #[rhai_fn(name = "*", return_raw)]
+    pub fn multiply(a: Tensor, b: Tensor) -> RhaiResultOf<Tensor> {
+        a.zip_with(&b, |x, y| x * y)
+    }
+    /// Divide two tensors element-wise, broadcasting their shapes.
+    #[rhai_fn(name = "/", return_raw)]
+    pub fn divide(a: Tensor, b: Tensor) -> RhaiResultOf<Tensor> {
+        a.zip_with(&b, |x, y| x / y)
+    }
+
+    /// Add a number to every element of the tensor.
+    ///
+    /// # Example
+    ///
+    /// ```rhai
+    /// let t = tensor([1, 2]) * 2 + 0.5;
+    ///
+    /// print(t);       // prints "[2.5, 4.5]"
+    /// ```
+    #[rhai_fn(name = "+")]
+    pub fn add_scalar(t: Tensor, x: FLOAT) -> Tensor {
+        t.map(|y| y + x)
+    }
+    /// Subtract a number from every element of the tensor.
+    #[rhai_fn(name = "-")]
+    pub fn subtract_scalar(t: Tensor, x: FLOAT) -> Tensor {
+        t.map(|y| y - x)
+    }
+    /// Multiply every element of the tensor by a number.
+    #[rhai_fn(name = "*")]
+    pub fn multiply_scalar(t: Tensor, x: FLOAT) -> Tensor {
+        t.map(|y| y * x)
+    }
+    /// Divide every element of the tensor by a number.
+    #[rhai_fn(name = "/")]
+    pub fn divide_scalar(t: Tensor, x: FLOAT) -> Tensor {
+        t.map(|y| y / x)
+    }
+    /// Add every element of the tensor to a number.
+    #[rhai_fn(name = "+")]
+    pub fn scalar_add(x: FLOAT, t: Tensor) -> Tensor {
+        t.map(|y| x + y)
+    }
+    /// Subtract every element of the tensor from a number.
+    #[rhai_fn(name = "-")]
+    pub fn scalar_subtract(x: FLOAT, t: Tensor) -> Tensor {
+        t.map(|y| x - y)
+    }
+    /// Multiply a number by every element of the tensor.
+    #[rhai_fn(name = "*")]
+    pub fn scalar_multiply(x: FLOAT, t: Tensor) -> Tensor {
+        t.map(|y| x * y)
+    }
+    /// Divide a number by every element of the tensor.
+    #[rhai_fn(name = "/")]
+    pub fn scalar_divide(x: FLOAT, t: Tensor) -> Tensor {
+        t.map(|y| x / y)
+    }
+
+    /// Add an integer to every element of the tensor.
+    #[rhai_fn(name = "+")]
+    pub fn add_int(t: Tensor, x: INT) -> Tensor {
+        add_scalar(t, x as FLOAT)
+    }
+    /// Subtract an integer from every element of the tensor.
+    #[rhai_fn(name = "-")]
+    pub fn subtract_int(t: Tensor, x: INT) -> Tensor {
+        subtract_scalar(t, x as FLOAT)
+    }
+    /// Multiply every element of the tensor by an integer.
+    #[rhai_fn(name = "*")]
+    pub fn multiply_int(t: Tensor, x: INT) -> Tensor {
+        multiply_scalar(t, x as FLOAT)
+    }
+    /// Divide every element of the tensor by an integer.
+    #[rhai_fn(name = "/")]
+    pub fn divide_int(t: Tensor, x: INT) -> Tensor {
+        divide_scalar(t, x as FLOAT)
+    }
+    /// Add every element of the tensor to an integer.
+    #[rhai_fn(name = "+")]
+    pub fn int_add(x: INT, t: Tensor) -> Tensor {
+        scalar_add(x as FLOAT, t)
+    }
+    /// Subtract every element of the tensor from an integer.
+    #[rhai_fn(name = "-")]
+    pub fn int_subtract(x: INT, t: Tensor) -> Tensor {
+        scalar_subtract(x as FLOAT, t)
+    }
+    /// Multiply an integer by every element of the tensor.
+    #[rhai_fn(name = "*")]
+    pub fn int_multiply(x: INT, t: Tensor) -> Tensor {
+        scalar_multiply(x as FLOAT, t)
+    }
+    /// Divide an integer by every element of the tensor.
+    #[rhai_fn(name = "/")]
+    pub fn int_divide(x: INT, t: Tensor) -> Tensor {
+        scalar_divide(x as FLOAT, t)
+    }
+
+    /// Convert the tensor into a string.
+    #[rhai_fn(name = "print", name = "to_string", pure)]
+    pub fn to_string(t: &mut Tensor) -> String {
+        t.to_string()
+    }
+    /// Convert the tensor into a string in debug format.
+    #[rhai_fn(name = "debug", name = "to_debug", pure)]
+    pub fn to_debug(t: &mut Tensor) -> String {
+        format!("tensor({t})")
+    }
+}
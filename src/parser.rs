@@ -3,7 +3,7 @@
 use crate::api::options::LangOptions;
 use crate::ast::{
     ASTFlags, BinaryExpr, CaseBlocksList, Expr, FlowControl, FnCallExpr, FnCallHashes, Ident,
-    OpAssignment, RangeCase, ScriptFuncDef, Stmt, StmtBlock, StmtBlockContainer,
+    OpAssignment, Pattern, RangeCase, ScriptFuncDef, Stmt, StmtBlock, StmtBlockContainer,
     SwitchCasesCollection,
 };
 use crate::engine::{Precedence, OP_CONTAINS, OP_NOT};
@@ -83,6 +83,9 @@ pub struct ParseState<'a, 't, 'f> {
     /// List of globally-imported [module][crate::Module] names.
     #[cfg(not(feature = "no_module"))]
     pub global_imports: ThinVec<ImmutableString>,
+    /// Encapsulates a local stack with the names of symbols imported via `import ... use { ... }`.
+    #[cfg(not(feature = "no_module"))]
+    pub imported_symbols: ThinVec<ImmutableString>,
     /// Unused dummy field.
     #[cfg(feature = "no_function")]
     pub _dummy: &'f (),
@@ -106,7 +109,8 @@ impl fmt::Debug for ParseState<'_, '_, '_> {
 
         #[cfg(not(feature = "no_module"))]
         f.field("imports", &self.imports)
-            .field("global_imports", &self.global_imports);
+            .field("global_imports", &self.global_imports)
+            .field("imported_symbols", &self.imported_symbols);
 
         f.finish()
     }
@@ -142,6 +146,8 @@ impl<'a, 't, 'f> ParseState<'a, 't, 'f> {
             imports: ThinVec::new(),
             #[cfg(not(feature = "no_module"))]
             global_imports: ThinVec::new(),
+            #[cfg(not(feature = "no_module"))]
+            imported_symbols: ThinVec::new(),
         }
     }
 
@@ -174,6 +180,18 @@ impl<'a, 't, 'f> ParseState<'a, 't, 'f> {
         (index, hit_barrier)
     }
 
+    /// Is a name imported as a symbol via `import ... use { ... }`?
+    ///
+    /// Always `false` under `no_module`.
+    #[inline]
+    #[must_use]
+    pub fn is_imported_symbol(&self, _name: &str) -> bool {
+        #[cfg(not(feature = "no_module"))]
+        return self.imported_symbols.iter().any(|s| s == _name);
+        #[cfg(feature = "no_module")]
+        return false;
+    }
+
     /// Find a module by name in the [`ParseState`], searching in reverse.
     ///
     /// Returns the offset to be deducted from `Stack::len`,
@@ -240,6 +258,10 @@ pub struct ParseSettings {
     /// Maximum levels of expression nesting (0 for unlimited).
     #[cfg(not(feature = "unchecked"))]
     pub max_expr_depth: usize,
+    /// Minimum amount of native stack space (in bytes) that must remain (0 for no check).
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(feature = "stack_guard")]
+    pub min_stack_remaining: usize,
 }
 
 impl ParseSettings {
@@ -263,6 +285,15 @@ impl ParseSettings {
             return Err(PERR::ExprTooDeep.into_err(self.pos));
         }
 
+        // Guard against overflowing the native stack
+        #[cfg(not(feature = "unchecked"))]
+        #[cfg(feature = "stack_guard")]
+        if self.min_stack_remaining > 0
+            && stacker::remaining_stack().map_or(false, |n| n < self.min_stack_remaining)
+        {
+            return Err(PERR::ExprTooDeep.into_err(self.pos));
+        }
+
         Ok(Self {
             level: self.level + 1,
             ..*self
@@ -442,6 +473,20 @@ fn parse_var_name(input: &mut TokenStream) -> ParseResult<(SmartString, Position
     }
 }
 
+/// Does a token start a destructuring pattern?
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+#[inline]
+#[must_use]
+const fn is_pattern_start(token: &Token) -> bool {
+    match token {
+        #[cfg(not(feature = "no_index"))]
+        Token::LeftBracket => true,
+        #[cfg(not(feature = "no_object"))]
+        Token::MapStart => true,
+        _ => false,
+    }
+}
+
 /// Optimize the structure of a chained expression where the root expression is another chained expression.
 ///
 /// # Panics
@@ -682,7 +727,7 @@ impl Engine {
             match state.input.peek().unwrap() {
                 // id(...args, ) - handle trailing comma
                 (Token::RightParen, ..) => (),
-                _ => args.push(self.parse_expr(state, settings)?),
+                _ => args.push(self.parse_expr_or_assignment(state, settings)?),
             }
 
             match state.input.peek().unwrap() {
@@ -881,13 +926,13 @@ impl Engine {
                         settings.pos = pos;
                         let settings = settings.level_up()?;
                         // Recursively parse the indexing chain, right-binding each
-                        let options = match token {
+                        let next_options = match token {
                             Token::LeftBracket => ASTFlags::empty(),
                             Token::QuestionBracket => ASTFlags::NEGATED,
                             _ => unreachable!("`[` or `?[`"),
                         };
                         let idx_expr =
-                            self.parse_index_chain(state, settings, idx_expr, options, false)?;
+                            self.parse_index_chain(state, settings, idx_expr, next_options, false)?;
                         // Indexing binds to right
                         Ok(Expr::Index(
                             BinaryExpr { lhs, rhs: idx_expr }.into(),
@@ -1121,13 +1166,16 @@ impl Engine {
         let mut expressions = FnArgsVec::<BinaryExpr>::new();
         let mut cases = StraightHashMap::<CaseBlocksList>::default();
         let mut ranges = StaticVec::<RangeCase>::new();
+        let mut patterns = StaticVec::<(Pattern, usize)>::new();
         let mut def_case = None;
         let mut def_case_pos = Position::NONE;
 
         loop {
             const MISSING_RBRACE: &str = "to end this switch block";
 
-            let (case_expr_list, condition) = match state.input.peek().unwrap() {
+            let stack_len = state.stack.len();
+
+            let (case_expr_list, pattern, condition) = match state.input.peek().unwrap() {
                 (Token::RightBrace, ..) => {
                     eat_token(state.input, &Token::RightBrace);
                     break;
@@ -1150,6 +1198,7 @@ impl Engine {
 
                     (
                         StaticVec::new_const(),
+                        None,
                         Expr::BoolConstant(true, Position::NONE),
                     )
                 }
@@ -1159,8 +1208,51 @@ impl Engine {
 
                 _ => {
                     let mut case_expr_list = StaticVec::new_const();
+                    #[allow(unused_mut)]
+                    let mut pattern = None;
 
                     loop {
+                        // Destructuring pattern
+                        #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+                        if is_pattern_start(&state.input.peek().unwrap().0) {
+                            let filter = state.expr_filter;
+                            state.expr_filter = |t| t != &Token::Pipe;
+                            let p = self.parse_pattern(state, settings);
+                            state.expr_filter = filter;
+                            let p = p?;
+
+                            match p.get_literal_value() {
+                                // Pattern with only literals - treat it as a literal
+                                Some(value) => {
+                                    case_expr_list.push(Expr::from_dynamic(value, p.position()))
+                                }
+                                // Pattern that binds variables
+                                None if case_expr_list.is_empty()
+                                    && !matches!(state.input.peek().unwrap().0, Token::Pipe) =>
+                                {
+                                    self.declare_pattern_vars(
+                                        state,
+                                        settings,
+                                        &p,
+                                        AccessMode::ReadWrite,
+                                    )?;
+                                    pattern = Some(p);
+                                    break;
+                                }
+                                None => return Err(PERR::BadInput(LexError::ImproperSymbol(
+                                    Token::Pipe.literal_syntax().into(),
+                                    "Destructuring patterns cannot be combined with other cases"
+                                        .into(),
+                                ))
+                                .into_err(p.position())),
+                            }
+
+                            if !match_token(state.input, &Token::Pipe).0 {
+                                break;
+                            }
+                            continue;
+                        }
+
                         let filter = state.expr_filter;
                         state.expr_filter = |t| t != &Token::Pipe;
                         let expr = self.parse_expr(state, settings);
@@ -1186,7 +1278,7 @@ impl Engine {
                     } else {
                         Expr::BoolConstant(true, Position::NONE)
                     };
-                    (case_expr_list, condition)
+                    (case_expr_list, pattern, condition)
                 }
             };
 
@@ -1213,6 +1305,9 @@ impl Engine {
                     (Expr::Stmt(stmt_block.into()), need_comma)
                 };
 
+            // Variables bound by a pattern are only visible within the case
+            state.stack.rewind(stack_len);
+
             expressions.push(BinaryExpr {
                 lhs: condition,
                 rhs: action_expr,
@@ -1220,7 +1315,9 @@ impl Engine {
 
             let index = expressions.len() - 1;
 
-            if case_expr_list.is_empty() {
+            if let Some(pattern) = pattern {
+                patterns.push((pattern, index));
+            } else if case_expr_list.is_empty() {
                 def_case = Some(index);
             } else {
                 for expr in case_expr_list {
@@ -1296,12 +1393,14 @@ impl Engine {
         expressions.shrink_to_fit();
         cases.shrink_to_fit();
         ranges.shrink_to_fit();
+        patterns.shrink_to_fit();
 
         let cases = SwitchCasesCollection {
             expressions,
             cases,
             ranges,
             def_case,
+            patterns,
         };
 
         Ok(Stmt::Switch((item, cases).into(), settings.pos))
@@ -1370,7 +1469,7 @@ impl Engine {
             Token::LeftParen => {
                 settings.pos = eat_token(state.input, &Token::LeftParen);
 
-                let expr = self.parse_expr(state, settings.level_up()?)?;
+                let expr = self.parse_expr_or_assignment(state, settings.level_up()?)?;
 
                 match state.input.next().unwrap() {
                     // ( ... )
@@ -1563,6 +1662,8 @@ impl Engine {
                             && !state
                                 .external_constants
                                 .map_or(false, |scope| scope.contains(&s))
+                            && !self.prelude.as_ref().map_or(false, |m| m.contains_var(&s))
+                            && !state.is_imported_symbol(&s)
                         {
                             return Err(
                                 PERR::VariableUndefined(s.to_string()).into_err(settings.pos)
@@ -1769,6 +1870,27 @@ impl Engine {
                     let settings = settings.level_up()?;
                     self.parse_index_chain(state, settings, expr, opt, true)?
                 }
+                // Optional call: expr?.(...) is the same as expr?.call(...)
+                #[cfg(not(feature = "no_object"))]
+                (expr, Token::Elvis)
+                    if matches!(
+                        state.input.peek().unwrap().0,
+                        Token::LeftParen | Token::Unit
+                    ) =>
+                {
+                    let no_args = state.input.next().unwrap().0 == Token::Unit;
+                    let rhs = self.parse_fn_call(
+                        state,
+                        settings.level_up()?,
+                        self.get_interned_string(crate::engine::KEYWORD_FN_PTR_CALL),
+                        no_args,
+                        false,
+                        #[cfg(not(feature = "no_module"))]
+                        crate::ast::Namespace::NONE,
+                    )?;
+
+                    self.make_dot_expr(expr, rhs, _parent_options, ASTFlags::NEGATED, tail_pos)?
+                }
                 // Property access
                 #[cfg(not(feature = "no_object"))]
                 (expr, op @ (Token::Period | Token::Elvis)) => {
@@ -2082,9 +2204,11 @@ impl Engine {
             (Expr::Index(mut x, options, pos), rhs)
                 if !parent_options.intersects(ASTFlags::BREAK) =>
             {
+                // Keep the null-conditional flag of the indexing since the chain continues
+                let index_options = options & ASTFlags::NEGATED;
                 let options = options | parent_options;
                 x.rhs = self.make_dot_expr(x.rhs, rhs, options, op_flags, op_pos)?;
-                Ok(Expr::Index(x, ASTFlags::empty(), pos))
+                Ok(Expr::Index(x, index_options, pos))
             }
             // lhs.module::id - syntax error
             #[cfg(not(feature = "no_module"))]
@@ -2405,6 +2529,7 @@ impl Engine {
     ) -> ParseResult<Expr> {
         #[allow(clippy::wildcard_imports)]
         use crate::api::custom_syntax::markers::*;
+        use crate::api::custom_syntax::{split_typed_marker, static_type_of};
 
         const KEYWORD_SEMICOLON: &str = Token::SemiColon.literal_syntax();
         const KEYWORD_CLOSE_BRACE: &str = Token::RightBrace.literal_syntax();
@@ -2414,6 +2539,7 @@ impl Engine {
         let mut inputs = FnArgsVec::new();
         let mut segments = FnArgsVec::new();
         let mut tokens = FnArgsVec::new();
+        let mut declared_vars = FnArgsVec::new();
 
         // Adjust the variables stack
         if syntax.scope_may_be_changed {
@@ -2465,6 +2591,23 @@ impl Engine {
                         pos,
                     ));
                 }
+                CUSTOM_SYNTAX_MARKER_VAR => {
+                    let (name, pos) = parse_var_name(state.input)?;
+                    let name = self.get_interned_string(name);
+
+                    segments.push(name.clone());
+                    tokens.push(self.get_interned_string(CUSTOM_SYNTAX_MARKER_VAR));
+                    declared_vars.push(name.clone());
+
+                    inputs.push(Expr::Variable(
+                        #[cfg(not(feature = "no_module"))]
+                        (None, name, crate::ast::Namespace::NONE, 0).into(),
+                        #[cfg(feature = "no_module")]
+                        (None, name).into(),
+                        None,
+                        pos,
+                    ));
+                }
                 CUSTOM_SYNTAX_MARKER_SYMBOL => {
                     let (symbol, pos) = match state.input.next().unwrap() {
                         // Standard symbol
@@ -2564,6 +2707,32 @@ impl Engine {
                         return Err(PERR::MissingSymbol("Expecting a string".into()).into_err(pos))
                     }
                 },
+                s if split_typed_marker(s).is_some() => {
+                    let (marker, ty) = split_typed_marker(s).unwrap();
+
+                    let expr = if marker == CUSTOM_SYNTAX_MARKER_EXPR {
+                        self.parse_expr(state, settings)?
+                    } else {
+                        match self.parse_block(state, settings)? {
+                            block @ Stmt::Block(..) => Expr::Stmt(Box::new(block.into())),
+                            stmt => unreachable!("Stmt::Block expected but gets {:?}", stmt),
+                        }
+                    };
+
+                    // Check the type of the expression, if known
+                    match static_type_of(&expr) {
+                        Some(t) if t != ty => {
+                            return Err(PERR::MismatchedType(ty.into(), t.into())
+                                .into_err(expr.start_position()))
+                        }
+                        _ => (),
+                    }
+
+                    inputs.push(expr);
+                    let keyword = self.get_interned_string(s);
+                    segments.push(keyword.clone());
+                    tokens.push(keyword);
+                }
                 s => match state.input.next().unwrap() {
                     (Token::LexError(err), pos) => return Err(err.into_err(pos)),
                     (Token::Identifier(t) | Token::Reserved(t) | Token::Custom(t), ..)
@@ -2590,13 +2759,19 @@ impl Engine {
         inputs.shrink_to_fit();
         tokens.shrink_to_fit();
 
+        // Declared variables are visible to statements following the custom syntax
+        for name in &declared_vars {
+            state.stack.push(name.clone(), ());
+        }
+
         let self_terminated = matches!(
             &*required_token,
             // It is self-terminating if the last symbol is a block
             CUSTOM_SYNTAX_MARKER_BLOCK |
             // If the last symbol is `;` or `}`, it is self-terminating
             KEYWORD_SEMICOLON | KEYWORD_CLOSE_BRACE
-        );
+        ) || split_typed_marker(&required_token)
+            .map_or(false, |(marker, ..)| marker == CUSTOM_SYNTAX_MARKER_BLOCK);
         // It is self-terminating if the last symbol is a block
         #[cfg(not(feature = "no_function"))]
         let self_terminated = required_token == CUSTOM_SYNTAX_MARKER_FUNC || self_terminated;
@@ -2608,6 +2783,7 @@ impl Engine {
                 state: user_state,
                 scope_may_be_changed: syntax.scope_may_be_changed,
                 self_terminated,
+                declared_vars,
             }
             .into(),
             pos,
@@ -2625,6 +2801,50 @@ impl Engine {
         self.parse_binary_op(state, settings, precedence, lhs)
     }
 
+    /// Parse an expression, which may be an assignment expression if allowed.
+    fn parse_expr_or_assignment(
+        &self,
+        state: &mut ParseState,
+        mut settings: ParseSettings,
+    ) -> ParseResult<Expr> {
+        let expr = self.parse_expr(state, settings)?;
+
+        if !settings.has_option(LangOptions::ASSIGN_EXPR) {
+            return Ok(expr);
+        }
+
+        let (op, pos) = match state.input.peek().unwrap() {
+            // var = ...
+            (Token::Equals, ..) => (None, eat_token(state.input, &Token::Equals)),
+            // var op= ...
+            (token, ..) if token.is_op_assignment() => {
+                state.input.next().map(|(op, pos)| (Some(op), pos)).unwrap()
+            }
+            // Not an assignment
+            _ => return Ok(expr),
+        };
+
+        // The value is read back from the variable, so other l-values (which may have side effects) are not allowed
+        if !matches!(expr, Expr::Variable(..) | Expr::ThisPtr(..)) {
+            return Err(PERR::AssignmentToInvalidLHS(
+                "Only variables can be assigned to in an assignment expression".into(),
+            )
+            .into_err(expr.start_position()));
+        }
+
+        settings.pos = pos;
+
+        // Assignments are right-associative: x = y = z
+        let rhs = self.parse_expr_or_assignment(state, settings.level_up()?)?;
+        let start_pos = expr.start_position();
+        let stmt = Self::make_assignment_stmt(op, state, expr.clone(), rhs, pos)?;
+
+        // { var = rhs; var }
+        Ok(Expr::Stmt(
+            StmtBlock::new([stmt, Stmt::Expr(expr.into())], start_pos, Position::NONE).into(),
+        ))
+    }
+
     /// Parse an if statement.
     fn parse_if(&self, state: &mut ParseState, settings: ParseSettings) -> ParseResult<Stmt> {
         // if ...
@@ -2826,44 +3046,58 @@ impl Engine {
         // let/const... (specified in `var_type`)
         settings.pos = state.input.next().unwrap().1;
 
-        // let name ...
-        let (name, pos) = parse_var_name(state.input)?;
+        // let [ ... ] = ... or let #{ ... } = ...
+        #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+        if is_pattern_start(&state.input.peek().unwrap().0) {
+            if is_export {
+                return Err(PERR::BadInput(LexError::ImproperSymbol(
+                    "export".into(),
+                    "Destructuring patterns cannot be exported".into(),
+                ))
+                .into_err(settings.pos));
+            }
 
-        if !self.allow_shadowing() && state.stack.get(&name).is_some() {
-            return Err(PERR::VariableExists(name.into()).into_err(pos));
-        }
+            let pattern = self.parse_pattern(state, settings.level_up()?)?;
 
-        if let Some(ref filter) = self.def_var_filter {
-            let will_shadow = state.stack.get(&name).is_some();
+            match state.input.next().unwrap() {
+                (Token::Equals, ..) => (),
+                (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                (.., pos) => {
+                    return Err(PERR::MissingToken(
+                        Token::Equals.into(),
+                        "to assign a value to this destructuring pattern".into(),
+                    )
+                    .into_err(pos))
+                }
+            }
 
-            let global = state
-                .global
-                .get_or_insert_with(|| self.new_global_runtime_state().into());
+            let expr = self.parse_expr(state, settings.level_up()?)?;
 
-            global.level = settings.level;
-            let is_const = access == AccessMode::ReadOnly;
-            let info = VarDefInfo::new(&name, is_const, settings.level, will_shadow);
-            let caches = &mut Caches::new();
-            let context = EvalContext::new(self, global, caches, &mut state.stack, None);
+            self.declare_pattern_vars(state, settings, &pattern, access)?;
 
-            match filter(false, info, context) {
-                Ok(true) => (),
-                Ok(false) => return Err(PERR::ForbiddenVariable(name.into()).into_err(pos)),
-                Err(err) => {
-                    return Err(match *err {
-                        EvalAltResult::ErrorParsing(e, pos) => e.into_err(pos),
-                        _ => PERR::ForbiddenVariable(name.into()).into_err(pos),
-                    })
-                }
-            }
+            let flags = match access {
+                AccessMode::ReadWrite => ASTFlags::empty(),
+                AccessMode::ReadOnly => ASTFlags::CONSTANT,
+            };
+
+            return Ok(Stmt::Destructure(
+                (pattern, expr).into(),
+                flags,
+                settings.pos,
+            ));
         }
 
+        // let name ...
+        let (name, pos) = parse_var_name(state.input)?;
+
+        self.check_parse_var_def(state, settings, &name, access, pos)?;
+
         let name = self.get_interned_string(name);
 
         // let name = ...
         let expr = if match_token(state.input, &Token::Equals).0 {
             // let name = expr
-            self.parse_expr(state, settings.level_up()?)?
+            self.parse_expr_or_assignment(state, settings.level_up()?)?
         } else {
             Expr::Unit(Position::NONE)
         };
@@ -2914,6 +3148,300 @@ impl Engine {
         })
     }
 
+    /// Check whether a variable can be defined, according to the shadowing option and the
+    /// variable definition filter.
+    fn check_parse_var_def(
+        &self,
+        state: &mut ParseState,
+        settings: ParseSettings,
+        name: &str,
+        access: AccessMode,
+        pos: Position,
+    ) -> ParseResult<()> {
+        if !self.allow_shadowing() && state.stack.get(name).is_some() {
+            return Err(PERR::VariableExists(name.into()).into_err(pos));
+        }
+
+        if let Some(ref filter) = self.def_var_filter {
+            let will_shadow = state.stack.get(name).is_some();
+
+            let global = state
+                .global
+                .get_or_insert_with(|| self.new_global_runtime_state().into());
+
+            global.level = settings.level;
+            let is_const = access == AccessMode::ReadOnly;
+            let info = VarDefInfo::new(name, is_const, settings.level, will_shadow);
+            let caches = &mut Caches::new();
+            let context = EvalContext::new(self, global, caches, &mut state.stack, None);
+
+            match filter(false, info, context) {
+                Ok(true) => (),
+                Ok(false) => return Err(PERR::ForbiddenVariable(name.into()).into_err(pos)),
+                Err(err) => {
+                    return Err(match *err {
+                        EvalAltResult::ErrorParsing(e, pos) => e.into_err(pos),
+                        _ => PERR::ForbiddenVariable(name.into()).into_err(pos),
+                    })
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse a destructuring pattern.
+    #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+    fn parse_pattern(
+        &self,
+        state: &mut ParseState,
+        settings: ParseSettings,
+    ) -> ParseResult<Pattern> {
+        match state.input.peek().unwrap() {
+            // _
+            (Token::Underscore, pos) => {
+                let pos = *pos;
+                eat_token(state.input, &Token::Underscore);
+                Ok(Pattern::Ignore(pos))
+            }
+            // name
+            (Token::Identifier(..) | Token::Reserved(..), ..) => {
+                let (name, pos) = parse_var_name(state.input)?;
+                let name = self.get_interned_string(name);
+                Ok(Pattern::Var(Ident { name, pos }))
+            }
+            // [ ... ]
+            #[cfg(not(feature = "no_index"))]
+            (Token::LeftBracket, ..) => self.parse_array_pattern(state, settings.level_up()?),
+            // #{ ... }
+            #[cfg(not(feature = "no_object"))]
+            (Token::MapStart, ..) => self.parse_map_pattern(state, settings.level_up()?),
+            // literal
+            _ => {
+                let expr = self.parse_unary(state, settings.level_up()?)?;
+
+                match expr.get_literal_value() {
+                    Some(value) if value.is_hashable() => {
+                        Ok(Pattern::Literal(value, expr.start_position()))
+                    }
+                    _ => {
+                        Err(PERR::ExprExpected("a literal".into()).into_err(expr.start_position()))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Parse an array destructuring pattern.
+    #[cfg(not(feature = "no_index"))]
+    fn parse_array_pattern(
+        &self,
+        state: &mut ParseState,
+        mut settings: ParseSettings,
+    ) -> ParseResult<Pattern> {
+        // [ ...
+        settings.pos = eat_token(state.input, &Token::LeftBracket);
+
+        let mut items = StaticVec::<Pattern>::new_const();
+        let mut rest = None;
+
+        loop {
+            const MISSING_RBRACKET: &str = "to end this array pattern";
+
+            match state.input.peek().unwrap() {
+                (Token::RightBracket, ..) => {
+                    eat_token(state.input, &Token::RightBracket);
+                    break;
+                }
+                (Token::EOF, pos) => {
+                    return Err(PERR::MissingToken(
+                        Token::RightBracket.into(),
+                        MISSING_RBRACKET.into(),
+                    )
+                    .into_err(*pos))
+                }
+                (.., pos) if rest.is_some() => {
+                    return Err(PERR::MissingToken(
+                        Token::RightBracket.into(),
+                        "after the rest pattern".into(),
+                    )
+                    .into_err(*pos))
+                }
+                // .. rest
+                (Token::ExclusiveRange, pos) => {
+                    let pos = *pos;
+                    eat_token(state.input, &Token::ExclusiveRange);
+
+                    rest = Some(match state.input.peek().unwrap() {
+                        (Token::Comma | Token::RightBracket, ..) => Pattern::Ignore(pos),
+                        (Token::Underscore | Token::Identifier(..) | Token::Reserved(..), ..) => {
+                            self.parse_pattern(state, settings)?
+                        }
+                        (.., pos) => return Err(PERR::VariableExpected.into_err(*pos)),
+                    });
+                }
+                _ => items.push(self.parse_pattern(state, settings)?),
+            }
+
+            match state.input.peek().unwrap() {
+                (Token::Comma, ..) => {
+                    eat_token(state.input, &Token::Comma);
+                }
+                (Token::RightBracket, ..) => (),
+                (Token::EOF, pos) => {
+                    return Err(PERR::MissingToken(
+                        Token::RightBracket.into(),
+                        MISSING_RBRACKET.into(),
+                    )
+                    .into_err(*pos))
+                }
+                (Token::LexError(err), pos) => return Err(err.clone().into_err(*pos)),
+                (.., pos) => {
+                    return Err(PERR::MissingToken(
+                        Token::Comma.into(),
+                        "to separate the items of this array pattern".into(),
+                    )
+                    .into_err(*pos))
+                }
+            };
+        }
+
+        Ok(Pattern::Array((items, rest).into(), settings.pos))
+    }
+
+    /// Parse an object map destructuring pattern.
+    #[cfg(not(feature = "no_object"))]
+    fn parse_map_pattern(
+        &self,
+        state: &mut ParseState,
+        mut settings: ParseSettings,
+    ) -> ParseResult<Pattern> {
+        // #{ ...
+        settings.pos = eat_token(state.input, &Token::MapStart);
+
+        let mut items = StaticVec::<(Ident, Pattern)>::new_const();
+        let mut rest = false;
+
+        loop {
+            const MISSING_RBRACE: &str = "to end this object map pattern";
+
+            match state.input.peek().unwrap() {
+                (Token::RightBrace, ..) => {
+                    eat_token(state.input, &Token::RightBrace);
+                    break;
+                }
+                (Token::EOF, pos) => {
+                    return Err(
+                        PERR::MissingToken(Token::RightBrace.into(), MISSING_RBRACE.into())
+                            .into_err(*pos),
+                    )
+                }
+                (.., pos) if rest => {
+                    return Err(PERR::MissingToken(
+                        Token::RightBrace.into(),
+                        "after '..' in this object map pattern".into(),
+                    )
+                    .into_err(*pos))
+                }
+                // ..
+                (Token::ExclusiveRange, ..) => {
+                    eat_token(state.input, &Token::ExclusiveRange);
+                    rest = true;
+                    continue;
+                }
+                _ => (),
+            }
+
+            let (name, pos, is_identifier) = match state.input.next().unwrap() {
+                (Token::Identifier(s), pos) => (*s, pos, true),
+                (Token::StringConstant(s), pos) => (*s, pos, false),
+                (Token::Reserved(s), pos) if is_valid_identifier(&s) => {
+                    return Err(PERR::Reserved(s.to_string()).into_err(pos));
+                }
+                (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                (.., pos) => return Err(PERR::PropertyExpected.into_err(pos)),
+            };
+
+            if items.iter().any(|(p, ..)| p.as_str() == name.as_str()) {
+                return Err(PERR::DuplicatedProperty(name.to_string()).into_err(pos));
+            }
+
+            let name = self.get_interned_string(name);
+
+            let pattern = match state.input.peek().unwrap() {
+                // name: pattern
+                (Token::Colon, ..) => {
+                    eat_token(state.input, &Token::Colon);
+                    self.parse_pattern(state, settings)?
+                }
+                // name
+                _ if is_identifier => Pattern::Var(Ident {
+                    name: name.clone(),
+                    pos,
+                }),
+                (.., pos) => {
+                    return Err(PERR::MissingToken(
+                        Token::Colon.into(),
+                        format!("to follow the property '{name}' in this object map pattern"),
+                    )
+                    .into_err(*pos))
+                }
+            };
+
+            items.push((Ident { name, pos }, pattern));
+
+            match state.input.peek().unwrap() {
+                (Token::Comma, ..) => {
+                    eat_token(state.input, &Token::Comma);
+                }
+                (Token::RightBrace, ..) => (),
+                (Token::EOF, pos) => {
+                    return Err(
+                        PERR::MissingToken(Token::RightBrace.into(), MISSING_RBRACE.into())
+                            .into_err(*pos),
+                    )
+                }
+                (Token::LexError(err), pos) => return Err(err.clone().into_err(*pos)),
+                (.., pos) => {
+                    return Err(PERR::MissingToken(
+                        Token::Comma.into(),
+                        "to separate the items of this object map pattern".into(),
+                    )
+                    .into_err(*pos))
+                }
+            }
+        }
+
+        Ok(Pattern::Map((items, rest).into(), settings.pos))
+    }
+
+    /// Declare all the variables bound by a destructuring pattern.
+    #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+    fn declare_pattern_vars(
+        &self,
+        state: &mut ParseState,
+        settings: ParseSettings,
+        pattern: &Pattern,
+        access: AccessMode,
+    ) -> ParseResult<()> {
+        let vars = pattern.variables();
+
+        for (i, var) in vars.iter().enumerate() {
+            if vars[..i].iter().any(|v| v.name == var.name) {
+                return Err(PERR::DuplicatedVariable(var.name.to_string()).into_err(var.pos));
+            }
+            self.check_parse_var_def(state, settings, &var.name, access, var.pos)?;
+        }
+
+        for var in vars {
+            state
+                .stack
+                .push_entry(var.name.clone(), access, Dynamic::UNIT);
+        }
+
+        Ok(())
+    }
+
     /// Parse an import statement.
     #[cfg(not(feature = "no_module"))]
     fn parse_import(&self, state: &mut ParseState, settings: ParseSettings) -> ParseResult<Stmt> {
@@ -2940,7 +3468,81 @@ impl Engine {
 
         state.imports.push(export.name.clone());
 
-        Ok(Stmt::Import((expr, export).into(), settings.pos))
+        // import expr as name use { ... }
+        let mut symbols = ThinVec::new();
+
+        if matches!(state.input.peek().unwrap(), (Token::Reserved(s), ..) if s.as_str() == crate::engine::KEYWORD_USE)
+        {
+            state.input.next().unwrap();
+
+            match state.input.next().unwrap() {
+                (Token::LeftBrace, ..) => (),
+                (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                (.., pos) => {
+                    return Err(PERR::MissingToken(
+                        Token::LeftBrace.into(),
+                        "to start the list of symbols to import".into(),
+                    )
+                    .into_err(pos))
+                }
+            }
+
+            loop {
+                if let (Token::RightBrace, ..) = state.input.peek().unwrap() {
+                    eat_token(state.input, &Token::RightBrace);
+                    break;
+                }
+
+                // symbol ...
+                let (name, pos) = parse_var_name(state.input)?;
+                let name = Ident {
+                    name: self.get_interned_string(name),
+                    pos,
+                };
+
+                // symbol as alias ...
+                let alias = if match_token(state.input, &Token::As).0 {
+                    let (alias, pos) = parse_var_name(state.input)?;
+                    Ident {
+                        name: self.get_interned_string(alias),
+                        pos,
+                    }
+                } else {
+                    name.clone()
+                };
+
+                if symbols
+                    .iter()
+                    .any(|(_, a): &(Ident, Ident)| a.name == alias.name)
+                {
+                    return Err(
+                        PERR::DuplicatedVariable(alias.name.to_string()).into_err(alias.pos)
+                    );
+                }
+
+                state.imported_symbols.push(alias.name.clone());
+                symbols.push((name, alias));
+
+                match state.input.next().unwrap() {
+                    (Token::Comma, ..) => (),
+                    (Token::RightBrace, ..) => break,
+                    (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                    (.., pos) => {
+                        return Err(PERR::MissingToken(
+                            Token::Comma.into(),
+                            "to separate the symbols to import".into(),
+                        )
+                        .into_err(pos))
+                    }
+                }
+            }
+
+            state
+                .imports
+                .push(self.get_interned_string(crate::engine::IMPORT_USE_SYMBOLS));
+        }
+
+        Ok(Stmt::Import((expr, export, symbols).into(), settings.pos))
     }
 
     /// Parse an export statement.
@@ -3041,6 +3643,8 @@ impl Engine {
 
         #[cfg(not(feature = "no_module"))]
         let orig_imports_len = state.imports.len();
+        #[cfg(not(feature = "no_module"))]
+        let orig_imported_symbols_len = state.imported_symbols.len();
 
         let end_pos = loop {
             // Terminated?
@@ -3101,7 +3705,10 @@ impl Engine {
         state.frame_pointer = prev_frame_pointer;
 
         #[cfg(not(feature = "no_module"))]
-        state.imports.truncate(orig_imports_len);
+        {
+            state.imports.truncate(orig_imports_len);
+            state.imported_symbols.truncate(orig_imported_symbols_len);
+        }
 
         Ok(Stmt::Block(
             StmtBlock::new(block, settings.pos, end_pos).into(),
@@ -3131,7 +3738,7 @@ impl Engine {
 
         settings.pos = pos;
 
-        let rhs = self.parse_expr(state, settings)?;
+        let rhs = self.parse_expr_or_assignment(state, settings)?;
 
         Self::make_assignment_stmt(op, state, expr, rhs, pos)
     }
@@ -3253,6 +3860,9 @@ impl Engine {
                             // mode will not complain.
                             new_state.global_imports.clone_from(&state.global_imports);
                             new_state.global_imports.extend(state.imports.clone());
+                            new_state
+                                .imported_symbols
+                                .clone_from(&state.imported_symbols);
                         }
 
                         // Brand new options
@@ -3270,6 +3880,9 @@ impl Engine {
                             pos,
                             #[cfg(not(feature = "unchecked"))]
                             max_expr_depth: self.max_function_expr_depth(),
+                            #[cfg(not(feature = "unchecked"))]
+                            #[cfg(feature = "stack_guard")]
+                            min_stack_remaining: self.min_stack_remaining(),
                         };
 
                         let f = self.parse_fn(
@@ -3280,13 +3893,7 @@ impl Engine {
                             comments,
                         )?;
 
-                        let hash = calc_fn_hash(None, &f.name, f.params.len());
-
-                        #[cfg(not(feature = "no_object"))]
-                        let hash = f
-                            .this_type
-                            .as_ref()
-                            .map_or(hash, |typ| crate::calc_typed_method_hash(hash, typ));
+                        let hash = f.calc_hash(calc_fn_hash(None, &f.name, f.params.len()));
 
                         if state.lib.contains_key(&hash) {
                             return Err(PERR::FnDuplicatedDefinition(
@@ -3487,12 +4094,7 @@ impl Engine {
             match token {
                 Token::StringConstant(s) if next_token == &Token::Period => {
                     eat_token(state.input, &Token::Period);
-                    let s = match s.as_str() {
-                        "int" => self.get_interned_string(std::any::type_name::<crate::INT>()),
-                        #[cfg(not(feature = "no_float"))]
-                        "float" => self.get_interned_string(std::any::type_name::<crate::FLOAT>()),
-                        _ => self.get_interned_string(*s),
-                    };
+                    let s = self.get_interned_type_name(*s);
                     (state.input.next().unwrap(), Some(s))
                 }
                 Token::StringConstant(..) => {
@@ -3504,12 +4106,7 @@ impl Engine {
                 }
                 Token::Identifier(s) if next_token == &Token::Period => {
                     eat_token(state.input, &Token::Period);
-                    let s = match s.as_str() {
-                        "int" => self.get_interned_string(std::any::type_name::<crate::INT>()),
-                        #[cfg(not(feature = "no_float"))]
-                        "float" => self.get_interned_string(std::any::type_name::<crate::FLOAT>()),
-                        _ => self.get_interned_string(*s),
-                    };
+                    let s = self.get_interned_type_name(*s);
                     (state.input.next().unwrap(), Some(s))
                 }
                 _ => ((token, pos), None),
@@ -3520,6 +4117,28 @@ impl Engine {
             #[cfg(not(feature = "no_custom_syntax"))]
             Token::Custom(s) if is_valid_function_name(&s) => *s,
             Token::Identifier(s) if is_valid_function_name(&s) => *s,
+            // Operator function (e.g. `fn +(a: MyType, b: MyType)`), called when no native
+            // function or built-in operator matches the operands
+            Token::Plus
+            | Token::UnaryPlus
+            | Token::Minus
+            | Token::UnaryMinus
+            | Token::Multiply
+            | Token::Divide
+            | Token::Modulo
+            | Token::PowerOf
+            | Token::LeftShift
+            | Token::RightShift
+            | Token::EqualsTo
+            | Token::NotEqualsTo
+            | Token::LessThan
+            | Token::LessThanEqualsTo
+            | Token::GreaterThan
+            | Token::GreaterThanEqualsTo
+            | Token::Ampersand
+            | Token::Pipe
+            | Token::XOr
+            | Token::Bang => token.literal_syntax().into(),
             Token::Reserved(s) => return Err(PERR::Reserved(s.to_string()).into_err(pos)),
             _ => return Err(PERR::FnMissingName.into_err(pos)),
         };
@@ -3537,6 +4156,10 @@ impl Engine {
         };
 
         let mut params = StaticVec::<(ImmutableString, _)>::new_const();
+        let mut param_types = FnArgsVec::<Option<ImmutableString>>::new_const();
+        let mut param_defaults = FnArgsVec::<Expr>::new_const();
+        #[allow(unused_mut)]
+        let mut is_variadic = false;
 
         if !no_params {
             let sep_err = format!("to separate the parameters of function '{name}'");
@@ -3544,6 +4167,29 @@ impl Engine {
             loop {
                 match state.input.next().unwrap() {
                     (Token::RightParen, ..) => break,
+                    // ...rest
+                    #[cfg(not(feature = "no_index"))]
+                    (Token::Reserved(s), ..) if &**s == "..." => {
+                        match state.input.next().unwrap() {
+                            (Token::Identifier(s), pos) => {
+                                if params.iter().any(|(p, _)| p == &*s) {
+                                    return Err(PERR::FnDuplicatedParam(
+                                        name.into(),
+                                        s.to_string(),
+                                    )
+                                    .into_err(pos));
+                                }
+
+                                let s = self.get_interned_string(*s);
+                                state.stack.push(s.clone(), ());
+                                params.push((s, pos));
+                                param_types.push(None);
+                                is_variadic = true;
+                            }
+                            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                            (.., pos) => return Err(PERR::VariableExpected.into_err(pos)),
+                        }
+                    }
                     (Token::Identifier(s), pos) => {
                         if params.iter().any(|(p, _)| p == &*s) {
                             return Err(
@@ -3552,6 +4198,35 @@ impl Engine {
                         }
 
                         let s = self.get_interned_string(*s);
+
+                        // Parse parameter type, if any
+                        if !match_token(state.input, &Token::Colon).0 {
+                            param_types.push(None);
+                        } else {
+                            match state.input.next().unwrap() {
+                                (Token::Identifier(t) | Token::StringConstant(t), ..) => {
+                                    param_types.push(Some(self.get_interned_type_name(*t)));
+                                }
+                                (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                                (.., pos) => {
+                                    return Err(PERR::MissingSymbol(format!(
+                                        "Expecting the type of parameter '{s}' of function '{name}'"
+                                    ))
+                                    .into_err(pos))
+                                }
+                            }
+                        }
+
+                        // Parse default value, if any, before the parameter itself is in scope
+                        if match_token(state.input, &Token::Equals).0 {
+                            param_defaults.push(self.parse_expr(state, settings.level_up()?)?);
+                        } else if !param_defaults.is_empty() {
+                            return Err(PERR::MissingSymbol(format!(
+                                "Expecting a default value for parameter '{s}' of function '{name}' because previous parameters have default values"
+                            ))
+                            .into_err(pos));
+                        }
+
                         state.stack.push(s.clone(), ());
                         params.push((s, pos));
                     }
@@ -3567,6 +4242,13 @@ impl Engine {
 
                 match state.input.next().unwrap() {
                     (Token::RightParen, ..) => break,
+                    (Token::Comma, pos) if is_variadic => {
+                        return Err(PERR::MissingToken(
+                            Token::RightParen.into(),
+                            format!("after the rest parameter of function '{name}'"),
+                        )
+                        .into_err(pos))
+                    }
                     (Token::Comma, ..) => (),
                     (Token::LexError(err), pos) => return Err(err.into_err(pos)),
                     (.., pos) => {
@@ -3583,21 +4265,59 @@ impl Engine {
         }
         .into();
 
+        // Parameters with default values and rest parameters cannot be typed
+        if param_types.iter().any(Option::is_some) && (is_variadic || !param_defaults.is_empty()) {
+            return Err(PERR::MissingSymbol(format!(
+                "Expecting no parameter types for function '{name}' because it has parameters with default values or a rest parameter"
+            ))
+            .into_err(settings.pos));
+        }
+
+        // Either all parameters are typed, or none
+        if param_types.iter().any(Option::is_some) {
+            if let Some(i) = param_types.iter().position(Option::is_none) {
+                let (param, pos) = &params[i];
+                return Err(PERR::MissingSymbol(format!(
+                    "Expecting the type of parameter '{param}' of function '{name}' because other parameters are typed"
+                ))
+                .into_err(*pos));
+            }
+        }
+
         let mut params: FnArgsVec<_> = params.into_iter().map(|(p, ..)| p).collect();
         params.shrink_to_fit();
 
+        let mut param_types: FnArgsVec<_> = param_types.into_iter().flatten().collect();
+        param_types.shrink_to_fit();
+        param_defaults.shrink_to_fit();
+
         Ok(ScriptFuncDef {
             name: self.get_interned_string(name),
             access,
             #[cfg(not(feature = "no_object"))]
             this_type,
             params,
+            param_types,
+            param_defaults,
+            is_variadic,
             body,
             #[cfg(feature = "metadata")]
             comments: comments.into_iter().collect(),
         })
     }
 
+    /// Get the interned name of a type in a function definition (e.g. the type of `this` or of
+    /// a parameter), mapping `int` and `float` to the actual types.
+    #[cfg(not(feature = "no_function"))]
+    fn get_interned_type_name(&self, name: SmartString) -> ImmutableString {
+        match name.as_str() {
+            "int" => self.get_interned_string(std::any::type_name::<crate::INT>()),
+            #[cfg(not(feature = "no_float"))]
+            "float" => self.get_interned_string(std::any::type_name::<crate::FLOAT>()),
+            _ => self.get_interned_string(name),
+        }
+    }
+
     /// Creates a curried expression from a list of external variables
     #[cfg(not(feature = "no_function"))]
     #[cfg(not(feature = "no_closure"))]
@@ -3693,6 +4413,9 @@ impl Engine {
             // mode will not complain.
             new_state.global_imports.clone_from(&state.global_imports);
             new_state.global_imports.extend(state.imports.clone());
+            new_state
+                .imported_symbols
+                .clone_from(&state.imported_symbols);
         }
 
         let mut params_list = StaticVec::<ImmutableString>::new_const();
@@ -3800,6 +4523,9 @@ impl Engine {
             #[cfg(not(feature = "no_object"))]
             this_type: None,
             params,
+            param_types: FnArgsVec::new_const(),
+            param_defaults: FnArgsVec::new_const(),
+            is_variadic: false,
             body: body.into(),
             #[cfg(not(feature = "no_function"))]
             #[cfg(feature = "metadata")]
@@ -3819,6 +4545,12 @@ impl Engine {
 
         // Finished with `new_state` here. Revert back to using `state`.
 
+        #[cfg(not(feature = "no_closure"))]
+        #[cfg(not(feature = "unchecked"))]
+        if _externals.len() > self.max_closure_captures() {
+            return Err(PERR::TooManyCaptures.into_err(settings.pos));
+        }
+
         #[cfg(not(feature = "no_closure"))]
         for Ident { name, pos } in &_externals {
             let (index, is_func) = self.access_var(state, name, *pos);
@@ -3864,6 +4596,9 @@ impl Engine {
             pos: Position::START,
             #[cfg(not(feature = "unchecked"))]
             max_expr_depth: self.max_expr_depth(),
+            #[cfg(not(feature = "unchecked"))]
+            #[cfg(feature = "stack_guard")]
+            min_stack_remaining: self.min_stack_remaining(),
         };
         process_settings(&mut settings);
 
@@ -3910,6 +4645,9 @@ impl Engine {
             pos: Position::START,
             #[cfg(not(feature = "unchecked"))]
             max_expr_depth: self.max_expr_depth(),
+            #[cfg(not(feature = "unchecked"))]
+            #[cfg(feature = "stack_guard")]
+            min_stack_remaining: self.min_stack_remaining(),
         };
         process_settings(&mut settings);
 
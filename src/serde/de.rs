@@ -3,6 +3,7 @@
 use crate::api::formatting::map_std_type_name;
 use crate::types::dynamic::Union;
 use crate::{Dynamic, ImmutableString, LexError, Position, RhaiError, RhaiResultOf, ERR};
+use serde::de::value::BorrowedStrDeserializer;
 use serde::de::{Error, IntoDeserializer, Visitor};
 use serde::{Deserialize, Deserializer};
 #[cfg(feature = "no_std")]
@@ -109,6 +110,55 @@ pub fn from_dynamic<'de, T: Deserialize<'de>>(value: &'de Dynamic) -> RhaiResult
     T::deserialize(DynamicDeserializer::new(value))
 }
 
+/// Deserialize a [`Dynamic`][crate::Dynamic] value into a Rust type that borrows from it, without
+/// copying strings.
+///
+/// Strings (including object map property names and enum variant names) are handed out as
+/// `&str` slices of the source [`Dynamic`][crate::Dynamic] to `&str` and `Cow<str>` fields
+/// (the latter with `#[serde(borrow)]`). This avoids allocating when passing large
+/// configurations from scripts to Rust.
+///
+/// Strings held in shared values (e.g. variables captured by closures) cannot be borrowed,
+/// and an error is returned when deserializing them into `&str`.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+/// # #[cfg(not(feature = "no_object"))]
+/// # {
+/// use rhai::{Dynamic, Engine};
+/// use rhai::serde::from_dynamic_borrowed;
+/// use serde::Deserialize;
+/// use std::collections::BTreeMap;
+///
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct Config<'a> {
+///     name: &'a str,
+///     #[serde(borrow)]
+///     labels: BTreeMap<&'a str, &'a str>,
+/// }
+///
+/// let engine = Engine::new();
+///
+/// let value = engine.eval::<Dynamic>(r#"
+///     #{ name: "server", labels: #{ env: "prod", region: "eu" } }
+/// "#)?;
+///
+/// // 'config' borrows all its strings from 'value'
+/// let config: Config = from_dynamic_borrowed(&value)?;
+///
+/// assert_eq!(config.name, "server");
+/// assert_eq!(config.labels["env"], "prod");
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+#[inline(always)]
+pub fn from_dynamic_borrowed<'de, T: Deserialize<'de>>(value: &'de Dynamic) -> RhaiResultOf<T> {
+    from_dynamic(value)
+}
+
 impl Error for RhaiError {
     #[cold]
     #[inline(never)]
@@ -155,7 +205,7 @@ impl<'de> Deserializer<'de> for DynamicDeserializer<'de> {
             #[cfg(not(feature = "no_index"))]
             Union::Array(..) => self.deserialize_seq(visitor),
             #[cfg(not(feature = "no_index"))]
-            Union::Blob(..) => self.deserialize_bytes(visitor),
+            Union::Blob(..) | Union::SharedBlob(..) => self.deserialize_bytes(visitor),
             #[cfg(not(feature = "no_object"))]
             Union::Map(..) => self.deserialize_map(visitor),
             Union::FnPtr(..) => self.type_error(),
@@ -353,10 +403,11 @@ impl<'de> Deserializer<'de> for DynamicDeserializer<'de> {
 
     fn deserialize_bytes<V: Visitor<'de>>(self, _visitor: V) -> RhaiResultOf<V::Value> {
         #[cfg(not(feature = "no_index"))]
-        return self
-            .0
-            .downcast_ref::<crate::Blob>()
-            .map_or_else(|| self.type_error(), |x| _visitor.visit_bytes(x));
+        return match self.0 .0 {
+            Union::Blob(ref x, ..) => _visitor.visit_bytes(x),
+            Union::SharedBlob(ref x, ..) => _visitor.visit_bytes(x),
+            _ => self.type_error(),
+        };
 
         #[cfg(feature = "no_index")]
         return self.type_error();
@@ -470,9 +521,9 @@ impl<'de> Deserializer<'de> for DynamicDeserializer<'de> {
         _variants: &'static [&'static str],
         visitor: V,
     ) -> RhaiResultOf<V::Value> {
-        match self.0.as_immutable_string_ref() {
-            Ok(s) => visitor.visit_enum(s.into_deserializer()),
-            Err(_) => {
+        match self.0.downcast_ref::<ImmutableString>() {
+            Some(s) => visitor.visit_enum(BorrowedStrDeserializer::new(s)),
+            None => {
                 #[cfg(not(feature = "no_object"))]
                 return self.0.downcast_ref::<crate::Map>().map_or_else(
                     || self.type_error(),
@@ -567,10 +618,11 @@ impl<'de, K: Iterator<Item = &'de str>, V: Iterator<Item = &'de Dynamic>> serde:
         &mut self,
         seed: S,
     ) -> RhaiResultOf<Option<S::Value>> {
-        // Deserialize each `Identifier` key coming out of the keys iterator.
+        // Deserialize each `Identifier` key coming out of the keys iterator,
+        // borrowing it from the map.
         self.keys
             .next()
-            .map(<_>::into_deserializer)
+            .map(BorrowedStrDeserializer::new)
             .map_or(Ok(None), |d| seed.deserialize(d).map(Some))
     }
 
@@ -598,7 +650,7 @@ impl<'de> serde::de::EnumAccess<'de> for EnumDeserializer<'de> {
         self,
         seed: V,
     ) -> RhaiResultOf<(V::Value, Self::Variant)> {
-        seed.deserialize(self.tag.into_deserializer())
+        seed.deserialize(BorrowedStrDeserializer::new(self.tag))
             .map(|v| (v, self))
     }
 }
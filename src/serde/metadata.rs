@@ -5,11 +5,16 @@ use crate::api::formatting::format_param_type_for_display;
 use crate::func::RhaiFunc;
 use crate::module::{calc_native_fn_hash, FuncMetadata};
 use crate::types::custom_types::CustomTypeInfo;
-use crate::{calc_fn_hash, Engine, FnAccess, SmartString, ThinVec, AST};
+use crate::{calc_fn_hash, Dynamic, Engine, FnAccess, SmartString, ThinVec, AST};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
-use std::{borrow::Cow, cmp::Ordering, collections::BTreeMap};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -174,11 +179,30 @@ impl<'a> From<(&'a RhaiFunc, &'a FuncMetadata)> for FnMetadata<'a> {
     }
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConstantMetadata<'a> {
+    #[serde(rename = "type")]
+    pub typ: &'a str,
+    pub value: String,
+}
+
+impl<'a> From<&'a Dynamic> for ConstantMetadata<'a> {
+    fn from(value: &'a Dynamic) -> Self {
+        Self {
+            typ: value.type_name(),
+            value: format!("{value:?}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ModuleMetadata<'a> {
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub modules: BTreeMap<&'a str, Self>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub constants: BTreeMap<&'a str, ConstantMetadata<'a>>,
     #[serde(default, skip_serializing_if = "ThinVec::is_empty")]
     pub custom_types: ThinVec<CustomTypeMetadata<'a>>,
     #[serde(default, skip_serializing_if = "ThinVec::is_empty")]
@@ -193,6 +217,7 @@ impl ModuleMetadata<'_> {
         Self {
             doc: "",
             modules: BTreeMap::new(),
+            constants: BTreeMap::new(),
             custom_types: ThinVec::new(),
             functions: ThinVec::new(),
         }
@@ -206,6 +231,11 @@ impl<'a> From<&'a crate::Module> for ModuleMetadata<'a> {
             .map(|(name, m)| (name, m.as_ref().into()))
             .collect();
 
+        let constants = module
+            .iter_var()
+            .map(|(name, value)| (name, value.into()))
+            .collect();
+
         let mut custom_types = module
             .iter_custom_types()
             .map(Into::into)
@@ -218,6 +248,7 @@ impl<'a> From<&'a crate::Module> for ModuleMetadata<'a> {
         Self {
             doc: module.doc(),
             modules,
+            constants,
             custom_types,
             functions,
         }
@@ -350,3 +381,293 @@ impl Engine {
         self.gen_metadata_to_json_raw(None, include_standard_packages)
     }
 }
+
+/// _(metadata)_ A change to the API surface of an [`Engine`] between two functions metadata
+/// snapshots, as reported by [`Engine::compare_fn_metadata`].
+/// Exported under the `metadata` feature only.
+///
+/// The first wrapped value of each variant is the path of the module containing the item
+/// (e.g. `foo::bar`), which is empty for the global namespace.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ApiChange {
+    /// A function is added.
+    /// Wrapped values are the module path and the function signature.
+    FunctionAdded(String, String),
+    /// A function is removed. This is a breaking change.
+    /// Wrapped values are the module path and the function signature.
+    FunctionRemoved(String, String),
+    /// The parameter types, return type or namespace of a function are changed.
+    /// This is a breaking change.
+    /// Wrapped values are the module path, the old signature and the new signature.
+    FunctionChanged(String, String, String),
+    /// A constant is added.
+    /// Wrapped values are the module path and the constant name.
+    ConstantAdded(String, String),
+    /// A constant is removed. This is a breaking change.
+    /// Wrapped values are the module path and the constant name.
+    ConstantRemoved(String, String),
+    /// The type or value of a constant is changed. This is a breaking change.
+    /// Wrapped values are the module path, the constant name, the old value and the new value.
+    ConstantChanged(String, String, String, String),
+}
+
+impl ApiChange {
+    /// Is this a breaking change, i.e. one that may break existing scripts?
+    #[must_use]
+    pub const fn is_breaking(&self) -> bool {
+        match self {
+            Self::FunctionAdded(..) | Self::ConstantAdded(..) => false,
+            Self::FunctionRemoved(..)
+            | Self::FunctionChanged(..)
+            | Self::ConstantRemoved(..)
+            | Self::ConstantChanged(..) => true,
+        }
+    }
+    /// Get the path of the module containing the changed item, which is empty for the global
+    /// namespace.
+    #[must_use]
+    pub fn module(&self) -> &str {
+        match self {
+            Self::FunctionAdded(path, ..)
+            | Self::FunctionRemoved(path, ..)
+            | Self::FunctionChanged(path, ..)
+            | Self::ConstantAdded(path, ..)
+            | Self::ConstantRemoved(path, ..)
+            | Self::ConstantChanged(path, ..) => path,
+        }
+    }
+}
+
+impl fmt::Display for ApiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.module();
+        let sep = if path.is_empty() {
+            ""
+        } else {
+            crate::tokenizer::Token::DoubleColon.literal_syntax()
+        };
+
+        match self {
+            Self::FunctionAdded(_, sig) => write!(f, "function added: {path}{sep}{sig}"),
+            Self::FunctionRemoved(_, sig) => write!(f, "function removed: {path}{sep}{sig}"),
+            Self::FunctionChanged(_, old, new) => {
+                write!(f, "function changed: {path}{sep}{old} => {new}")
+            }
+            Self::ConstantAdded(_, name) => write!(f, "constant added: {path}{sep}{name}"),
+            Self::ConstantRemoved(_, name) => write!(f, "constant removed: {path}{sep}{name}"),
+            Self::ConstantChanged(_, name, old, new) => {
+                write!(f, "constant changed: {path}{sep}{name} = {old} => {new}")
+            }
+        }
+    }
+}
+
+/// A function in a functions metadata snapshot, with only the fields relevant for comparison.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotFn {
+    pub name: String,
+    #[serde(default)]
+    pub namespace: Option<String>,
+    pub num_params: usize,
+    #[serde(default)]
+    pub params: Vec<SnapshotParam>,
+    #[serde(default)]
+    pub return_type: String,
+    pub signature: String,
+}
+
+impl SnapshotFn {
+    /// Get the shape of this function, i.e. everything that callers depend on.
+    #[must_use]
+    fn shape(&self) -> (Option<&str>, Vec<Option<&str>>, &str) {
+        (
+            self.namespace.as_deref(),
+            self.params.iter().map(|p| p.typ.as_deref()).collect(),
+            &self.return_type,
+        )
+    }
+}
+
+/// A function parameter in a functions metadata snapshot.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct SnapshotParam {
+    #[serde(rename = "type", default)]
+    pub typ: Option<String>,
+}
+
+/// A module in a functions metadata snapshot, with only the fields relevant for comparison.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotModule {
+    #[serde(default)]
+    pub modules: BTreeMap<String, Self>,
+    #[serde(default)]
+    pub constants: BTreeMap<String, SnapshotConstant>,
+    #[serde(default)]
+    pub functions: Vec<SnapshotFn>,
+}
+
+/// A constant in a functions metadata snapshot.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize)]
+struct SnapshotConstant {
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub value: String,
+}
+
+impl SnapshotModule {
+    /// Group all functions by name and number of parameters.
+    #[must_use]
+    fn fn_groups(&self) -> BTreeMap<(&str, usize), Vec<&SnapshotFn>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for f in &self.functions {
+            groups
+                .entry((f.name.as_str(), f.num_params))
+                .or_default()
+                .push(f);
+        }
+        groups
+    }
+    /// Compare this module with a newer version of it.
+    fn diff(&self, new: &Self, path: &str, changes: &mut Vec<ApiChange>) {
+        let old_fns = self.fn_groups();
+        let new_fns = new.fn_groups();
+
+        let keys = old_fns
+            .keys()
+            .chain(new_fns.keys())
+            .collect::<BTreeSet<_>>();
+
+        for key in keys {
+            let old_group = old_fns.get(key).map_or(&[][..], Vec::as_slice);
+            let new_group = new_fns.get(key).map_or(&[][..], Vec::as_slice);
+
+            let removed = old_group
+                .iter()
+                .filter(|f| new_group.iter().all(|g| g.shape() != f.shape()))
+                .collect::<Vec<_>>();
+            let added = new_group
+                .iter()
+                .filter(|f| old_group.iter().all(|g| g.shape() != f.shape()))
+                .collect::<Vec<_>>();
+
+            // A single overload replaced by another is a change in signature
+            if removed.len() == 1 && added.len() == 1 {
+                changes.push(ApiChange::FunctionChanged(
+                    path.into(),
+                    removed[0].signature.clone(),
+                    added[0].signature.clone(),
+                ));
+                continue;
+            }
+
+            changes.extend(
+                removed
+                    .into_iter()
+                    .map(|f| ApiChange::FunctionRemoved(path.into(), f.signature.clone())),
+            );
+            changes.extend(
+                added
+                    .into_iter()
+                    .map(|f| ApiChange::FunctionAdded(path.into(), f.signature.clone())),
+            );
+        }
+
+        for (name, old) in &self.constants {
+            match new.constants.get(name) {
+                None => changes.push(ApiChange::ConstantRemoved(path.into(), name.clone())),
+                Some(new) if new != old => changes.push(ApiChange::ConstantChanged(
+                    path.into(),
+                    name.clone(),
+                    old.value.clone(),
+                    new.value.clone(),
+                )),
+                Some(..) => (),
+            }
+        }
+        changes.extend(
+            new.constants
+                .keys()
+                .filter(|name| !self.constants.contains_key(*name))
+                .map(|name| ApiChange::ConstantAdded(path.into(), name.clone())),
+        );
+
+        let empty = Self::default();
+        let names = self
+            .modules
+            .keys()
+            .chain(new.modules.keys())
+            .collect::<BTreeSet<_>>();
+
+        for name in names {
+            let sub_path = if path.is_empty() {
+                name.clone()
+            } else {
+                format!(
+                    "{path}{}{name}",
+                    crate::tokenizer::Token::DoubleColon.literal_syntax()
+                )
+            };
+            let old = self.modules.get(name).unwrap_or(&empty);
+            let new = new.modules.get(name).unwrap_or(&empty);
+            old.diff(new, &sub_path, changes);
+        }
+    }
+}
+
+impl Engine {
+    /// _(metadata)_ Compare two functions metadata snapshots in JSON format, as generated by
+    /// [`gen_fn_metadata_to_json`][Engine::gen_fn_metadata_to_json] (or
+    /// [`gen_fn_metadata_with_ast_to_json`][Engine::gen_fn_metadata_with_ast_to_json]),
+    /// and return all changes to the API surface from the old snapshot to the new one.
+    /// Exported under the `metadata` feature only.
+    ///
+    /// This is useful for gating releases that would break stored scripts, which can be detected
+    /// via [`ApiChange::is_breaking`].
+    ///
+    /// Functions are matched by module path, name and number of parameters. Changes to
+    /// parameter names and doc-comments are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{ApiChange, Engine};
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.register_fn("area", |w: i64, h: i64| w * h);
+    /// engine.register_fn("perimeter", |w: i64, h: i64| 2 * (w + h));
+    ///
+    /// let old = engine.gen_fn_metadata_to_json(false).unwrap();
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.register_fn("area", |w: i64, h: i64| (w * h) as f64);
+    ///
+    /// let new = engine.gen_fn_metadata_to_json(false).unwrap();
+    ///
+    /// let changes = Engine::compare_fn_metadata(&old, &new).unwrap();
+    ///
+    /// assert_eq!(changes.len(), 2);
+    /// assert!(changes.iter().all(ApiChange::is_breaking));
+    /// assert_eq!(
+    ///     changes[0].to_string(),
+    ///     "function changed: area(_: i64, _: i64) -> i64 => area(_: i64, _: i64) -> f64"
+    /// );
+    /// assert_eq!(
+    ///     changes[1].to_string(),
+    ///     "function removed: perimeter(_: i64, _: i64) -> i64"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn compare_fn_metadata(old: &str, new: &str) -> serde_json::Result<Vec<ApiChange>> {
+        let old: SnapshotModule = serde_json::from_str(old)?;
+        let new: SnapshotModule = serde_json::from_str(new)?;
+
+        let mut changes = Vec::new();
+        old.diff(&new, "", &mut changes);
+        Ok(changes)
+    }
+}
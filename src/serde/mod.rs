@@ -3,9 +3,9 @@
 
 mod de;
 mod deserialize;
-mod metadata;
+pub(crate) mod metadata;
 mod ser;
 mod serialize;
 
-pub use de::{from_dynamic, DynamicDeserializer};
+pub use de::{from_dynamic, from_dynamic_borrowed, DynamicDeserializer};
 pub use ser::{to_dynamic, DynamicSerializer};
@@ -58,10 +58,14 @@ impl Serialize for Dynamic {
             Union::Array(ref a, ..) => (**a).serialize(ser),
             #[cfg(not(feature = "no_index"))]
             Union::Blob(ref a, ..) => ser.serialize_bytes(a),
+            #[cfg(not(feature = "no_index"))]
+            Union::SharedBlob(ref a, ..) => ser.serialize_bytes(a),
             #[cfg(not(feature = "no_object"))]
             Union::Map(ref m, ..) => {
-                let mut map = ser.serialize_map(Some(m.len()))?;
-                m.iter().try_for_each(|(k, v)| map.serialize_entry(k, v))?;
+                let mut map =
+                    ser.serialize_map(Some(crate::types::prototype::num_properties(m)))?;
+                crate::types::prototype::iter_properties(m)
+                    .try_for_each(|(k, v)| map.serialize_entry(k, v))?;
                 map.end()
             }
             Union::FnPtr(ref f, ..) if f.is_curried() => {
@@ -9,7 +9,9 @@ use crate::{Engine, Identifier, LexError, Position, SmartString, StaticVec, INT,
 use std::prelude::v1::*;
 use std::{
     cell::RefCell,
-    char, fmt,
+    char,
+    collections::BTreeMap,
+    fmt,
     iter::{repeat, FusedIterator, Peekable},
     rc::Rc,
     str::{Chars, FromStr},
@@ -547,7 +549,7 @@ static RESERVED_LIST: [(&str, bool, bool, bool); 150] = [
     ("private", cfg!(feature = "no_function"), false, false),
     ("var", true, false, false),
     ("protected", true, false, false),
-    ("spawn", true, false, false),
+    ("spawn", true, cfg!(feature = "parallel"), false),
     ("shared", true, false, false),
     ("is", true, false, false),
     ("===", true, false, false),
@@ -904,6 +906,15 @@ impl Token {
         })
     }
 
+    /// Get an iterator over the syntax of all standard keywords.
+    #[allow(dead_code)]
+    pub(crate) fn iter_standard_keywords() -> impl Iterator<Item = &'static str> {
+        KEYWORDS_LIST
+            .iter()
+            .filter(|(.., token)| token.is_standard_keyword())
+            .map(|&(syntax, ..)| syntax)
+    }
+
     /// Reverse lookup a symbol token from a piece of syntax.
     #[inline]
     #[must_use]
@@ -1154,6 +1165,12 @@ pub struct TokenizeState {
     ///
     /// Set to `Some` to begin tracking this information.
     pub last_token: Option<SmartString>,
+    /// Custom prefixes of integer literals (e.g. `h` for `0h1F`), mapped to their radix.
+    pub literal_prefixes: BTreeMap<char, u32>,
+    /// Are Unicode identifiers allowed?
+    ///
+    /// Always `false` unless under `unicode-xid-ident`.
+    pub unicode_identifiers: bool,
 }
 
 /// _(internals)_ Trait that encapsulates a peekable character input stream.
@@ -1616,30 +1633,6 @@ fn scan_block_comment(
     level
 }
 
-/// Test if the given character is a hex character.
-#[inline(always)]
-const fn is_hex_digit(c: char) -> bool {
-    c.is_ascii_hexdigit()
-}
-
-/// Test if the given character is a numeric digit (i.e. 0-9).
-#[inline(always)]
-const fn is_numeric_digit(c: char) -> bool {
-    c.is_ascii_digit()
-}
-
-/// Test if the given character is an octal digit (i.e. 0-7).
-#[inline(always)]
-const fn is_octal_digit(c: char) -> bool {
-    matches!(c, '0'..='7')
-}
-
-/// Test if the given character is a binary digit (i.e. 0 or 1).
-#[inline(always)]
-const fn is_binary_digit(c: char) -> bool {
-    c == '0' || c == '1'
-}
-
 /// Test if the comment block is a doc-comment.
 #[cfg(not(feature = "no_function"))]
 #[cfg(feature = "metadata")]
@@ -1739,7 +1732,6 @@ fn get_next_token_inner(
             ('0'..='9', ..) => {
                 let mut result = SmartString::new_const();
                 let mut radix_base: Option<u32> = None;
-                let mut valid: fn(char) -> bool = is_numeric_digit;
                 let mut _has_period = false;
                 let mut _has_e = false;
 
@@ -1750,7 +1742,7 @@ fn get_next_token_inner(
                         NUMBER_SEPARATOR => {
                             stream.eat_next_and_advance(pos);
                         }
-                        ch if valid(ch) => {
+                        ch if ch.is_digit(radix_base.unwrap_or(10)) => {
                             result.push(ch);
                             stream.eat_next_and_advance(pos);
                         }
@@ -1819,26 +1811,20 @@ fn get_next_token_inner(
                                 }
                             }
                         }
-                        // 0x????, 0o????, 0b???? at beginning
-                        ch @ ('x' | 'o' | 'b' | 'X' | 'O' | 'B')
-                            if c == '0' && result.len() <= 1 =>
-                        {
-                            result.push(ch);
-                            stream.eat_next_and_advance(pos);
-
-                            valid = match ch {
-                                'x' | 'X' => is_hex_digit,
-                                'o' | 'O' => is_octal_digit,
-                                'b' | 'B' => is_binary_digit,
-                                c => unreachable!("x/X or o/O or b/B expected but gets '{}'", c),
-                            };
-
+                        // 0x????, 0o????, 0b???? or custom prefix at beginning
+                        ch if c == '0' && result.len() <= 1 => {
                             radix_base = Some(match ch {
                                 'x' | 'X' => 16,
                                 'o' | 'O' => 8,
                                 'b' | 'B' => 2,
-                                c => unreachable!("x/X or o/O or b/B expected but gets '{}'", c),
+                                ch => match state.literal_prefixes.get(&ch) {
+                                    Some(&radix) => radix,
+                                    None => break,
+                                },
                             });
+
+                            result.push(ch);
+                            stream.eat_next_and_advance(pos);
                         }
 
                         _ => break,
@@ -1856,10 +1842,14 @@ fn get_next_token_inner(
 
                 // Parse number
                 let token = if let Some(radix) = radix_base {
-                    let result = &result[2..];
+                    // Skip the sign (if any) and the prefix
+                    let digits = &result[if negated.is_some() { 3 } else { 2 }..];
 
-                    UNSIGNED_INT::from_str_radix(result, radix)
-                        .map(|v| v as INT)
+                    UNSIGNED_INT::from_str_radix(digits, radix)
+                        .map(|v| match negated {
+                            Some(..) => (v as INT).wrapping_neg(),
+                            None => v as INT,
+                        })
                         .map_or_else(
                             |_| Token::LexError(LERR::MalformedNumber(result.to_string()).into()),
                             Token::IntegerConstant,
@@ -2377,7 +2367,7 @@ fn get_next_token_inner(
             ('?', ..) => return (Token::Reserved(Box::new("?".into())), start_pos),
 
             // letter or underscore ...
-            _ if is_id_first_alphabetic(c) || c == '_' => {
+            _ if is_id_start_char(c, state.unicode_identifiers) || c == '_' => {
                 return parse_identifier_token(stream, state, pos, start_pos, c);
             }
 
@@ -2418,7 +2408,7 @@ fn parse_identifier_token(
 
     while let Some(next_char) = stream.peek_next() {
         match next_char {
-            x if is_id_continue(x) => {
+            x if is_id_continue_char(x, state.unicode_identifiers) => {
                 stream.eat_next_and_advance(pos);
                 identifier.push(x);
                 if let Some(ref mut last) = state.last_token {
@@ -2429,6 +2419,24 @@ fn parse_identifier_token(
         }
     }
 
+    #[cfg(feature = "unicode-xid-ident")]
+    if !identifier.is_ascii() {
+        use unicode_normalization::UnicodeNormalization;
+        use unicode_security::MixedScript;
+
+        if !unicode_normalization::is_nfc(&identifier) {
+            identifier = identifier.nfc().collect();
+        }
+
+        if !identifier.as_str().is_single_script() {
+            let msg = format!("identifier '{identifier}' mixes characters from different scripts");
+            return (
+                Token::LexError(LERR::ImproperSymbol(identifier.to_string(), msg).into()),
+                start_pos,
+            );
+        }
+    }
+
     if let Some(token) = Token::lookup_symbol_from_syntax(&identifier) {
         return (token, start_pos);
     }
@@ -2479,23 +2487,43 @@ pub fn is_valid_function_name(name: &str) -> bool {
 /// Is a character valid to start an identifier?
 #[inline(always)]
 #[must_use]
-#[allow(clippy::missing_const_for_fn)]
 pub fn is_id_first_alphabetic(x: char) -> bool {
-    #[cfg(feature = "unicode-xid-ident")]
-    return unicode_xid::UnicodeXID::is_xid_start(x);
-    #[cfg(not(feature = "unicode-xid-ident"))]
-    return x.is_ascii_alphabetic();
+    is_id_start_char(x, true)
 }
 
 /// Is a character valid for an identifier?
 #[inline(always)]
 #[must_use]
-#[allow(clippy::missing_const_for_fn)]
 pub fn is_id_continue(x: char) -> bool {
+    is_id_continue_char(x, true)
+}
+
+/// Is a character valid to start an identifier, with or without Unicode identifiers allowed?
+///
+/// Unicode identifiers are never allowed unless under `unicode-xid-ident`.
+#[inline(always)]
+#[must_use]
+#[allow(clippy::missing_const_for_fn)]
+fn is_id_start_char(x: char, _unicode: bool) -> bool {
+    #[cfg(feature = "unicode-xid-ident")]
+    if _unicode {
+        return unicode_xid::UnicodeXID::is_xid_start(x);
+    }
+    x.is_ascii_alphabetic()
+}
+
+/// Is a character valid for an identifier, with or without Unicode identifiers allowed?
+///
+/// Unicode identifiers are never allowed unless under `unicode-xid-ident`.
+#[inline(always)]
+#[must_use]
+#[allow(clippy::missing_const_for_fn)]
+fn is_id_continue_char(x: char, _unicode: bool) -> bool {
     #[cfg(feature = "unicode-xid-ident")]
-    return unicode_xid::UnicodeXID::is_xid_continue(x);
-    #[cfg(not(feature = "unicode-xid-ident"))]
-    return x.is_ascii_alphanumeric() || x == '_';
+    if _unicode {
+        return unicode_xid::UnicodeXID::is_xid_continue(x);
+    }
+    x.is_ascii_alphanumeric() || x == '_'
 }
 
 /// Is a piece of syntax a reserved keyword or reserved symbol?
@@ -2557,6 +2585,9 @@ pub struct MultiInputsStream<'a> {
     pub index: usize,
     /// Input character streams.
     pub streams: StaticVec<Peekable<Chars<'a>>>,
+    /// Trailing character stream (e.g. decoded from a reader), if any, read after all input
+    /// streams are exhausted.
+    pub reader: Option<Peekable<Box<dyn Iterator<Item = char> + 'a>>>,
 }
 
 impl InputStream for MultiInputsStream<'_> {
@@ -2584,7 +2615,7 @@ impl InputStream for MultiInputsStream<'_> {
         loop {
             if self.index >= self.streams.len() {
                 // No more streams
-                return None;
+                return self.reader.as_mut().and_then(Iterator::next);
             }
             if let Some(ch) = self.streams[self.index].next() {
                 // Next character in main stream
@@ -2604,7 +2635,7 @@ impl InputStream for MultiInputsStream<'_> {
         loop {
             if self.index >= self.streams.len() {
                 // No more streams
-                return None;
+                return self.reader.as_mut().and_then(|r| r.peek().copied());
             }
             if let Some(&ch) = self.streams[self.index].peek() {
                 // Next character in main stream
@@ -2716,6 +2747,10 @@ impl<'a> Iterator for TokenIterator<'a> {
             (Token::Identifier(s), pos) if self.engine.custom_keywords.contains_key(&*s) => {
                 (Token::Custom(s), pos)
             }
+            // Keyword reserved by the host
+            (Token::Identifier(s), pos) if self.engine.reserved_keywords.contains(&*s) => {
+                (Token::Reserved(s), pos)
+            }
             // Custom keyword/symbol - must be disabled
             #[cfg(not(feature = "no_custom_syntax"))]
             (token, pos) if token.is_literal() && self.engine.custom_keywords.contains_key(token.literal_syntax()) => {
@@ -2832,6 +2867,11 @@ impl Engine {
                     include_comments: false,
                     is_within_text_terminated_by: None,
                     last_token: None,
+                    literal_prefixes: self.literal_prefixes.clone(),
+                    #[cfg(feature = "unicode-xid-ident")]
+                    unicode_identifiers: self.allow_unicode_identifiers(),
+                    #[cfg(not(feature = "unicode-xid-ident"))]
+                    unicode_identifiers: false,
                 },
                 pos: Position::new(1, 0),
                 stream: MultiInputsStream {
@@ -2841,6 +2881,7 @@ impl Engine {
                         .map(|s| s.as_ref().chars().peekable())
                         .collect(),
                     index: 0,
+                    reader: None,
                 },
                 token_mapper,
             },
@@ -1,6 +1,7 @@
 //! Helper module which defines the [`Dynamic`] data type.
 
 use crate::{ExclusiveRange, FnPtr, ImmutableString, InclusiveRange, INT};
+use bitflags::bitflags;
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 use std::{
@@ -23,7 +24,7 @@ pub use std::time::Instant;
 pub use instant::Instant;
 
 #[cfg(not(feature = "no_index"))]
-use crate::{Array, Blob};
+use crate::{Array, Blob, SharedBlob};
 
 #[cfg(not(feature = "no_object"))]
 use crate::Map;
@@ -39,6 +40,43 @@ pub enum AccessMode {
     ReadOnly,
 }
 
+bitflags! {
+    /// Access mode and other state of a [`Dynamic`] value.
+    #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+    pub struct DynamicFlags: u8 {
+        /// Immutable.
+        const READ_ONLY = 0b_0000_0001;
+        /// Deeply immutable, even when cloned.
+        const FROZEN = 0b_0000_0010;
+        /// Originates from a taint source.
+        const TAINTED = 0b_0000_0100;
+    }
+}
+
+impl DynamicFlags {
+    /// Mutable, with no other flags.
+    pub const READ_WRITE: Self = Self::empty();
+
+    /// Get the [`AccessMode`] of a value with these flags.
+    #[inline(always)]
+    #[must_use]
+    pub const fn access_mode(self) -> AccessMode {
+        if self.intersects(Self::READ_ONLY.union(Self::FROZEN)) {
+            AccessMode::ReadOnly
+        } else {
+            AccessMode::ReadWrite
+        }
+    }
+    /// Get the flags of a clone of a value with these flags.
+    ///
+    /// Only frozen values stay immutable when cloned. Clones of tainted values are also tainted.
+    #[inline(always)]
+    #[must_use]
+    const fn on_clone(self) -> Self {
+        self.intersection(Self::FROZEN.union(Self::TAINTED))
+    }
+}
+
 /// Arbitrary data attached to a [`Dynamic`] value.
 #[cfg(target_pointer_width = "64")]
 pub type Tag = i32;
@@ -60,46 +98,49 @@ pub struct Dynamic(pub(crate) Union);
 #[must_use]
 pub enum Union {
     /// The Unit value - ().
-    Unit((), Tag, AccessMode),
+    Unit((), Tag, DynamicFlags),
     /// A boolean value.
-    Bool(bool, Tag, AccessMode),
+    Bool(bool, Tag, DynamicFlags),
     /// An [`ImmutableString`] value.
-    Str(ImmutableString, Tag, AccessMode),
+    Str(ImmutableString, Tag, DynamicFlags),
     /// A character value.
-    Char(char, Tag, AccessMode),
+    Char(char, Tag, DynamicFlags),
     /// An integer value.
-    Int(INT, Tag, AccessMode),
+    Int(INT, Tag, DynamicFlags),
     /// A floating-point value.
     #[cfg(not(feature = "no_float"))]
-    Float(super::FloatWrapper<crate::FLOAT>, Tag, AccessMode),
+    Float(super::FloatWrapper<crate::FLOAT>, Tag, DynamicFlags),
     /// _(decimal)_ A fixed-precision decimal value.
     /// Exported under the `decimal` feature only.
     #[cfg(feature = "decimal")]
-    Decimal(Box<rust_decimal::Decimal>, Tag, AccessMode),
+    Decimal(Box<rust_decimal::Decimal>, Tag, DynamicFlags),
     /// An array value.
     #[cfg(not(feature = "no_index"))]
-    Array(Box<Array>, Tag, AccessMode),
+    Array(Box<Array>, Tag, DynamicFlags),
     /// An blob (byte array).
     #[cfg(not(feature = "no_index"))]
-    Blob(Box<Blob>, Tag, AccessMode),
+    Blob(Box<Blob>, Tag, DynamicFlags),
+    /// A shared, immutable blob (byte array).
+    #[cfg(not(feature = "no_index"))]
+    SharedBlob(SharedBlob, Tag, DynamicFlags),
     /// An object map value.
     #[cfg(not(feature = "no_object"))]
-    Map(Box<Map>, Tag, AccessMode),
+    Map(Box<Map>, Tag, DynamicFlags),
     /// A function pointer.
-    FnPtr(Box<FnPtr>, Tag, AccessMode),
+    FnPtr(Box<FnPtr>, Tag, DynamicFlags),
     /// A timestamp value.
     #[cfg(not(feature = "no_time"))]
-    TimeStamp(Box<Instant>, Tag, AccessMode),
+    TimeStamp(Box<Instant>, Tag, DynamicFlags),
 
     /// Any type as a trait object.
     ///
     /// An extra level of redirection is used in order to avoid bloating the size of [`Dynamic`]
     /// because `Box<dyn Variant>` is a fat pointer.
-    Variant(Box<Box<dyn Variant>>, Tag, AccessMode),
+    Variant(Box<Box<dyn Variant>>, Tag, DynamicFlags),
 
     /// A _shared_ value of any type.
     #[cfg(not(feature = "no_closure"))]
-    Shared(crate::Shared<crate::Locked<Dynamic>>, Tag, AccessMode),
+    Shared(crate::Shared<crate::Locked<Dynamic>>, Tag, DynamicFlags),
 }
 
 /// _(internals)_ Lock guard for reading a [`Dynamic`].
@@ -118,9 +159,13 @@ enum DynamicReadLockInner<'d, T: Clone> {
     /// A simple reference to a non-shared value.
     Reference(&'d T),
 
-    /// A read guard to a _shared_ value.
+    /// A read guard to a _shared_ value, recorded as held by the current thread.
     #[cfg(not(feature = "no_closure"))]
-    Guard(crate::func::native::LockGuard<'d, Dynamic>),
+    Guard(
+        crate::func::native::LockGuard<'d, Dynamic>,
+        // Only held to be dropped together with the guard
+        #[allow(dead_code)] crate::func::native::LockTracker,
+    ),
 }
 
 impl<'d, T: Any + Clone> Deref for DynamicReadLock<'d, T> {
@@ -131,7 +176,7 @@ impl<'d, T: Any + Clone> Deref for DynamicReadLock<'d, T> {
         match self.0 {
             DynamicReadLockInner::Reference(reference) => reference,
             #[cfg(not(feature = "no_closure"))]
-            DynamicReadLockInner::Guard(ref guard) => guard.downcast_ref().unwrap(),
+            DynamicReadLockInner::Guard(ref guard, ..) => guard.downcast_ref().unwrap(),
         }
     }
 }
@@ -152,9 +197,13 @@ enum DynamicWriteLockInner<'d, T: Clone> {
     /// A simple mutable reference to a non-shared value.
     Reference(&'d mut T),
 
-    /// A write guard to a _shared_ value.
+    /// A write guard to a _shared_ value, recorded as held by the current thread.
     #[cfg(not(feature = "no_closure"))]
-    Guard(crate::func::native::LockGuardMut<'d, Dynamic>),
+    Guard(
+        crate::func::native::LockGuardMut<'d, Dynamic>,
+        // Only held to be dropped together with the guard
+        #[allow(dead_code)] crate::func::native::LockTracker,
+    ),
 }
 
 impl<'d, T: Any + Clone> Deref for DynamicWriteLock<'d, T> {
@@ -165,7 +214,7 @@ impl<'d, T: Any + Clone> Deref for DynamicWriteLock<'d, T> {
         match self.0 {
             DynamicWriteLockInner::Reference(ref reference) => reference,
             #[cfg(not(feature = "no_closure"))]
-            DynamicWriteLockInner::Guard(ref guard) => guard.downcast_ref().unwrap(),
+            DynamicWriteLockInner::Guard(ref guard, ..) => guard.downcast_ref().unwrap(),
         }
     }
 }
@@ -176,11 +225,54 @@ impl<'d, T: Any + Clone> DerefMut for DynamicWriteLock<'d, T> {
         match self.0 {
             DynamicWriteLockInner::Reference(ref mut reference) => reference,
             #[cfg(not(feature = "no_closure"))]
-            DynamicWriteLockInner::Guard(ref mut guard) => guard.downcast_mut().unwrap(),
+            DynamicWriteLockInner::Guard(ref mut guard, ..) => guard.downcast_mut().unwrap(),
         }
     }
 }
 
+/// Borrowed reference to a value of a specific type held by a [`Dynamic`], as returned by
+/// [`Dynamic::try_cast_ref`].
+///
+/// This works transparently with both normal and _shared_ values. A _shared_ value stays locked
+/// for reading until the guard is dropped.
+#[derive(Debug)]
+#[must_use]
+pub struct DynamicRef<'d, T: Clone>(DynamicReadLock<'d, T>);
+
+impl<T: Any + Clone> Deref for DynamicRef<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Borrowed mutable reference to a value of a specific type held by a [`Dynamic`], as returned
+/// by [`Dynamic::try_cast_mut`].
+///
+/// This works transparently with both normal and _shared_ values. A _shared_ value stays locked
+/// for writing until the guard is dropped.
+#[derive(Debug)]
+#[must_use]
+pub struct DynamicRefMut<'d, T: Clone>(DynamicWriteLock<'d, T>);
+
+impl<T: Any + Clone> Deref for DynamicRefMut<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Any + Clone> DerefMut for DynamicRefMut<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 impl Dynamic {
     /// Get the arbitrary data attached to this [`Dynamic`].
     #[must_use]
@@ -199,7 +291,7 @@ impl Dynamic {
             #[cfg(feature = "decimal")]
             Union::Decimal(_, tag, _) => tag,
             #[cfg(not(feature = "no_index"))]
-            Union::Array(_, tag, _) | Union::Blob(_, tag, _) => tag,
+            Union::Array(_, tag, _) | Union::Blob(_, tag, _) | Union::SharedBlob(_, tag, _) => tag,
             #[cfg(not(feature = "no_object"))]
             Union::Map(_, tag, _) => tag,
             #[cfg(not(feature = "no_time"))]
@@ -224,7 +316,9 @@ impl Dynamic {
             #[cfg(feature = "decimal")]
             Union::Decimal(_, ref mut tag, _) => *tag = value,
             #[cfg(not(feature = "no_index"))]
-            Union::Array(_, ref mut tag, _) | Union::Blob(_, ref mut tag, _) => *tag = value,
+            Union::Array(_, ref mut tag, _)
+            | Union::Blob(_, ref mut tag, _)
+            | Union::SharedBlob(_, ref mut tag, _) => *tag = value,
             #[cfg(not(feature = "no_object"))]
             Union::Map(_, ref mut tag, _) => *tag = value,
             #[cfg(not(feature = "no_time"))]
@@ -234,6 +328,35 @@ impl Dynamic {
         }
         self
     }
+    /// Is this [`Dynamic`] tainted, i.e. does it originate from a taint source?
+    ///
+    /// See [`Engine::register_taint_source`][crate::Engine::register_taint_source] for details.
+    #[inline]
+    #[must_use]
+    pub fn is_tainted(&self) -> bool {
+        #[cfg(not(feature = "no_closure"))]
+        if let Union::Shared(ref cell, ..) = self.0 {
+            return crate::func::locked_read(cell).map_or(false, |v| v.is_tainted());
+        }
+
+        self.flags().contains(DynamicFlags::TAINTED)
+    }
+    /// Mark this [`Dynamic`] as tainted or not tainted.
+    ///
+    /// Only this [`Dynamic`] is marked, not the items and properties it contains.
+    #[inline]
+    pub fn set_tainted(&mut self, tainted: bool) -> &mut Self {
+        #[cfg(not(feature = "no_closure"))]
+        if let Union::Shared(ref cell, ..) = self.0 {
+            if let Some(mut value) = crate::func::locked_write(cell) {
+                value.set_tainted(tainted);
+            }
+            return self;
+        }
+
+        self.flags_mut().set(DynamicFlags::TAINTED, tainted);
+        self
+    }
     /// Does this [`Dynamic`] hold a variant data type instead of one of the supported system
     /// primitive types?
     #[inline(always)]
@@ -293,6 +416,10 @@ impl Dynamic {
         if TypeId::of::<T>() == TypeId::of::<Blob>() {
             return matches!(self.0, Union::Blob(..));
         }
+        #[cfg(not(feature = "no_index"))]
+        if TypeId::of::<T>() == TypeId::of::<SharedBlob>() {
+            return matches!(self.0, Union::SharedBlob(..));
+        }
         #[cfg(not(feature = "no_object"))]
         if TypeId::of::<T>() == TypeId::of::<Map>() {
             return matches!(self.0, Union::Map(..));
@@ -333,6 +460,8 @@ impl Dynamic {
             Union::Array(..) => TypeId::of::<Array>(),
             #[cfg(not(feature = "no_index"))]
             Union::Blob(..) => TypeId::of::<Blob>(),
+            #[cfg(not(feature = "no_index"))]
+            Union::SharedBlob(..) => TypeId::of::<SharedBlob>(),
             #[cfg(not(feature = "no_object"))]
             Union::Map(..) => TypeId::of::<Map>(),
             Union::FnPtr(..) => TypeId::of::<FnPtr>(),
@@ -367,6 +496,8 @@ impl Dynamic {
             Union::Array(..) => "array",
             #[cfg(not(feature = "no_index"))]
             Union::Blob(..) => "blob",
+            #[cfg(not(feature = "no_index"))]
+            Union::SharedBlob(..) => "shared_blob",
             #[cfg(not(feature = "no_object"))]
             Union::Map(..) => "map",
             Union::FnPtr(..) => "Fn",
@@ -404,6 +535,8 @@ impl Hash for Dynamic {
             Union::Array(ref a, ..) => a.hash(state),
             #[cfg(not(feature = "no_index"))]
             Union::Blob(ref a, ..) => a.hash(state),
+            #[cfg(not(feature = "no_index"))]
+            Union::SharedBlob(ref a, ..) => a.hash(state),
             #[cfg(not(feature = "no_object"))]
             Union::Map(ref m, ..) => m.hash(state),
             Union::FnPtr(ref f, ..) if f.environ.is_some() => {
@@ -490,7 +623,7 @@ impl fmt::Display for Dynamic {
             #[cfg(not(feature = "no_index"))]
             Union::Array(..) => fmt::Debug::fmt(self, f),
             #[cfg(not(feature = "no_index"))]
-            Union::Blob(..) => fmt::Debug::fmt(self, f),
+            Union::Blob(..) | Union::SharedBlob(..) => fmt::Debug::fmt(self, f),
             #[cfg(not(feature = "no_object"))]
             Union::Map(..) => fmt::Debug::fmt(self, f),
             Union::FnPtr(ref v, ..) => fmt::Display::fmt(v, f),
@@ -609,7 +742,9 @@ impl fmt::Display for Dynamic {
                             dict.insert(value);
 
                             f.write_str("#{")?;
-                            for (i, (k, v)) in map.iter().enumerate() {
+                            for (i, (k, v)) in
+                                crate::types::prototype::iter_properties(map).enumerate()
+                            {
                                 if i > 0 {
                                     f.write_str(", ")?;
                                 }
@@ -629,6 +764,19 @@ impl fmt::Display for Dynamic {
     }
 }
 
+/// Format a blob (byte array) in hex.
+#[cfg(not(feature = "no_index"))]
+fn fmt_blob(blob: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("[")?;
+    blob.iter().enumerate().try_for_each(|(i, v)| {
+        if i > 0 && i % 8 == 0 {
+            f.write_str(" ")?;
+        }
+        write!(f, "{v:02x}")
+    })?;
+    f.write_str("]")
+}
+
 impl fmt::Debug for Dynamic {
     #[cold]
     #[inline(never)]
@@ -646,20 +794,15 @@ impl fmt::Debug for Dynamic {
             #[cfg(not(feature = "no_index"))]
             Union::Array(ref v, ..) => fmt::Debug::fmt(v, f),
             #[cfg(not(feature = "no_index"))]
-            Union::Blob(ref v, ..) => {
-                f.write_str("[")?;
-                v.iter().enumerate().try_for_each(|(i, v)| {
-                    if i > 0 && i % 8 == 0 {
-                        f.write_str(" ")?;
-                    }
-                    write!(f, "{v:02x}")
-                })?;
-                f.write_str("]")
-            }
+            Union::Blob(ref v, ..) => fmt_blob(v, f),
+            #[cfg(not(feature = "no_index"))]
+            Union::SharedBlob(ref v, ..) => fmt_blob(v, f),
             #[cfg(not(feature = "no_object"))]
             Union::Map(ref v, ..) => {
                 f.write_str("#")?;
-                fmt::Debug::fmt(v, f)
+                f.debug_map()
+                    .entries(crate::types::prototype::iter_properties(v))
+                    .finish()
             }
             Union::FnPtr(ref v, ..) => fmt::Debug::fmt(v, f),
             #[cfg(not(feature = "no_time"))]
@@ -776,7 +919,9 @@ impl fmt::Debug for Dynamic {
                             dict.insert(value);
 
                             f.write_str("#{")?;
-                            for (i, (k, v)) in map.iter().enumerate() {
+                            for (i, (k, v)) in
+                                crate::types::prototype::iter_properties(map).enumerate()
+                            {
                                 if i > 0 {
                                     f.write_str(", ")?;
                                 }
@@ -820,36 +965,47 @@ impl Clone for Dynamic {
     ///
     /// # WARNING
     ///
-    /// The cloned copy is marked read-write even if the original is read-only.
+    /// The cloned copy is marked read-write even if the original is read-only, unless the original
+    /// is [frozen][Dynamic::into_frozen].
     fn clone(&self) -> Self {
         match self.0 {
-            Union::Unit(v, tag, ..) => Self(Union::Unit(v, tag, ReadWrite)),
-            Union::Bool(v, tag, ..) => Self(Union::Bool(v, tag, ReadWrite)),
-            Union::Str(ref v, tag, ..) => Self(Union::Str(v.clone(), tag, ReadWrite)),
-            Union::Char(v, tag, ..) => Self(Union::Char(v, tag, ReadWrite)),
-            Union::Int(v, tag, ..) => Self(Union::Int(v, tag, ReadWrite)),
+            Union::Unit(v, tag, flags) => Self(Union::Unit(v, tag, flags.on_clone())),
+            Union::Bool(v, tag, flags) => Self(Union::Bool(v, tag, flags.on_clone())),
+            Union::Str(ref v, tag, flags) => Self(Union::Str(v.clone(), tag, flags.on_clone())),
+            Union::Char(v, tag, flags) => Self(Union::Char(v, tag, flags.on_clone())),
+            Union::Int(v, tag, flags) => Self(Union::Int(v, tag, flags.on_clone())),
             #[cfg(not(feature = "no_float"))]
-            Union::Float(v, tag, ..) => Self(Union::Float(v, tag, ReadWrite)),
+            Union::Float(v, tag, flags) => Self(Union::Float(v, tag, flags.on_clone())),
             #[cfg(feature = "decimal")]
-            Union::Decimal(ref v, tag, ..) => Self(Union::Decimal(v.clone(), tag, ReadWrite)),
+            Union::Decimal(ref v, tag, flags) => {
+                Self(Union::Decimal(v.clone(), tag, flags.on_clone()))
+            }
             #[cfg(not(feature = "no_index"))]
-            Union::Array(ref v, tag, ..) => Self(Union::Array(v.clone(), tag, ReadWrite)),
+            Union::Array(ref v, tag, flags) => Self(Union::Array(v.clone(), tag, flags.on_clone())),
             #[cfg(not(feature = "no_index"))]
-            Union::Blob(ref v, tag, ..) => Self(Union::Blob(v.clone(), tag, ReadWrite)),
+            Union::Blob(ref v, tag, flags) => Self(Union::Blob(v.clone(), tag, flags.on_clone())),
+            #[cfg(not(feature = "no_index"))]
+            Union::SharedBlob(ref v, tag, flags) => {
+                Self(Union::SharedBlob(v.clone(), tag, flags.on_clone()))
+            }
             #[cfg(not(feature = "no_object"))]
-            Union::Map(ref v, tag, ..) => Self(Union::Map(v.clone(), tag, ReadWrite)),
-            Union::FnPtr(ref v, tag, ..) => Self(Union::FnPtr(v.clone(), tag, ReadWrite)),
+            Union::Map(ref v, tag, flags) => Self(Union::Map(v.clone(), tag, flags.on_clone())),
+            Union::FnPtr(ref v, tag, flags) => Self(Union::FnPtr(v.clone(), tag, flags.on_clone())),
             #[cfg(not(feature = "no_time"))]
-            Union::TimeStamp(ref v, tag, ..) => Self(Union::TimeStamp(v.clone(), tag, ReadWrite)),
+            Union::TimeStamp(ref v, tag, flags) => {
+                Self(Union::TimeStamp(v.clone(), tag, flags.on_clone()))
+            }
 
-            Union::Variant(ref v, tag, ..) => Self(Union::Variant(
+            Union::Variant(ref v, tag, flags) => Self(Union::Variant(
                 v.as_ref().as_ref().clone_object().into(),
                 tag,
-                ReadWrite,
+                flags.on_clone(),
             )),
 
             #[cfg(not(feature = "no_closure"))]
-            Union::Shared(ref cell, tag, ..) => Self(Union::Shared(cell.clone(), tag, ReadWrite)),
+            Union::Shared(ref cell, tag, flags) => {
+                Self(Union::Shared(cell.clone(), tag, flags.on_clone()))
+            }
         }
     }
 }
@@ -870,7 +1026,7 @@ use std::f64::consts as FloatConstants;
 
 impl Dynamic {
     /// A [`Dynamic`] containing a `()`.
-    pub const UNIT: Self = Self(Union::Unit((), DEFAULT_TAG_VALUE, ReadWrite));
+    pub const UNIT: Self = Self(Union::Unit((), DEFAULT_TAG_VALUE, DynamicFlags::READ_WRITE));
     /// A [`Dynamic`] containing a `true`.
     pub const TRUE: Self = Self::from_bool(true);
     /// A [`Dynamic`] containing a [`false`].
@@ -1019,17 +1175,29 @@ impl Dynamic {
     /// Create a new [`Dynamic`] from a [`bool`].
     #[inline(always)]
     pub const fn from_bool(value: bool) -> Self {
-        Self(Union::Bool(value, DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Bool(
+            value,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
     /// Create a new [`Dynamic`] from an [`INT`].
     #[inline(always)]
     pub const fn from_int(value: INT) -> Self {
-        Self(Union::Int(value, DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Int(
+            value,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
     /// Create a new [`Dynamic`] from a [`char`].
     #[inline(always)]
     pub const fn from_char(value: char) -> Self {
-        Self(Union::Char(value, DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Char(
+            value,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
     /// Create a new [`Dynamic`] from a [`FLOAT`][crate::FLOAT].
     ///
@@ -1040,7 +1208,7 @@ impl Dynamic {
         Self(Union::Float(
             super::FloatWrapper::new(value),
             DEFAULT_TAG_VALUE,
-            ReadWrite,
+            DynamicFlags::READ_WRITE,
         ))
     }
     /// Create a new [`Dynamic`] from a [`Decimal`](https://docs.rs/rust_decimal).
@@ -1049,25 +1217,62 @@ impl Dynamic {
     #[cfg(feature = "decimal")]
     #[inline(always)]
     pub fn from_decimal(value: rust_decimal::Decimal) -> Self {
-        Self(Union::Decimal(value.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Decimal(
+            value.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
     /// Create a [`Dynamic`] from an [`Array`].
     #[cfg(not(feature = "no_index"))]
     #[inline(always)]
     pub fn from_array(array: Array) -> Self {
-        Self(Union::Array(array.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Array(
+            array.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
     /// Create a [`Dynamic`] from a [`Blob`].
     #[cfg(not(feature = "no_index"))]
     #[inline(always)]
     pub fn from_blob(blob: Blob) -> Self {
-        Self(Union::Blob(blob.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Blob(
+            blob.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
+    }
+    /// Create a [`Dynamic`] from a [`SharedBlob`].
+    ///
+    /// The bytes are not copied.
+    #[cfg(not(feature = "no_index"))]
+    #[inline(always)]
+    pub fn from_shared_blob(blob: SharedBlob) -> Self {
+        Self(Union::SharedBlob(
+            blob,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
+    }
+    /// Create a [`Dynamic`] holding a [`SharedBlob`] from a byte slice.
+    ///
+    /// The bytes are copied once. Afterwards, copies of the [`Dynamic`] (e.g. when passed
+    /// around in scripts) share the same bytes.
+    #[cfg(not(feature = "no_index"))]
+    #[inline]
+    pub fn from_blob_slice(bytes: &[u8]) -> Self {
+        Self::from_shared_blob(bytes.to_vec().into())
     }
     /// Create a [`Dynamic`] from a [`Map`].
     #[cfg(not(feature = "no_object"))]
     #[inline(always)]
     pub fn from_map(map: Map) -> Self {
-        Self(Union::Map(map.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Map(
+            map.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
     /// Create a new [`Dynamic`] from an [`Instant`].
     ///
@@ -1075,71 +1280,103 @@ impl Dynamic {
     #[cfg(not(feature = "no_time"))]
     #[inline(always)]
     pub fn from_timestamp(value: Instant) -> Self {
-        Self(Union::TimeStamp(value.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::TimeStamp(
+            value.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 
-    /// Get the [`AccessMode`] for this [`Dynamic`].
+    /// Get the [`DynamicFlags`] of this [`Dynamic`].
     #[must_use]
-    pub(crate) const fn access_mode(&self) -> AccessMode {
+    pub(crate) const fn flags(&self) -> DynamicFlags {
         match self.0 {
-            Union::Unit(.., access)
-            | Union::Bool(.., access)
-            | Union::Str(.., access)
-            | Union::Char(.., access)
-            | Union::Int(.., access)
-            | Union::FnPtr(.., access)
-            | Union::Variant(.., access) => access,
+            Union::Unit(.., flags)
+            | Union::Bool(.., flags)
+            | Union::Str(.., flags)
+            | Union::Char(.., flags)
+            | Union::Int(.., flags)
+            | Union::FnPtr(.., flags)
+            | Union::Variant(.., flags) => flags,
 
             #[cfg(not(feature = "no_float"))]
-            Union::Float(.., access) => access,
+            Union::Float(.., flags) => flags,
             #[cfg(feature = "decimal")]
-            Union::Decimal(.., access) => access,
+            Union::Decimal(.., flags) => flags,
             #[cfg(not(feature = "no_index"))]
-            Union::Array(.., access) | Union::Blob(.., access) => access,
+            Union::Array(.., flags) | Union::Blob(.., flags) | Union::SharedBlob(.., flags) => {
+                flags
+            }
             #[cfg(not(feature = "no_object"))]
-            Union::Map(.., access) => access,
+            Union::Map(.., flags) => flags,
             #[cfg(not(feature = "no_time"))]
-            Union::TimeStamp(.., access) => access,
+            Union::TimeStamp(.., flags) => flags,
             #[cfg(not(feature = "no_closure"))]
-            Union::Shared(.., access) => access,
+            Union::Shared(.., flags) => flags,
         }
     }
-    /// Set the [`AccessMode`] for this [`Dynamic`].
-    pub(crate) fn set_access_mode(&mut self, typ: AccessMode) -> &mut Self {
+    /// Get a mutable reference to the [`DynamicFlags`] of this [`Dynamic`].
+    fn flags_mut(&mut self) -> &mut DynamicFlags {
         match self.0 {
-            Union::Unit(.., ref mut access)
-            | Union::Bool(.., ref mut access)
-            | Union::Str(.., ref mut access)
-            | Union::Char(.., ref mut access)
-            | Union::Int(.., ref mut access)
-            | Union::FnPtr(.., ref mut access)
-            | Union::Variant(.., ref mut access) => *access = typ,
+            Union::Unit(.., ref mut flags)
+            | Union::Bool(.., ref mut flags)
+            | Union::Str(.., ref mut flags)
+            | Union::Char(.., ref mut flags)
+            | Union::Int(.., ref mut flags)
+            | Union::FnPtr(.., ref mut flags)
+            | Union::Variant(.., ref mut flags) => flags,
 
             #[cfg(not(feature = "no_float"))]
-            Union::Float(.., ref mut access) => *access = typ,
+            Union::Float(.., ref mut flags) => flags,
             #[cfg(feature = "decimal")]
-            Union::Decimal(.., ref mut access) => *access = typ,
+            Union::Decimal(.., ref mut flags) => flags,
             #[cfg(not(feature = "no_index"))]
-            Union::Array(ref mut a, _, ref mut access) => {
-                *access = typ;
+            Union::Array(.., ref mut flags)
+            | Union::Blob(.., ref mut flags)
+            | Union::SharedBlob(.., ref mut flags) => flags,
+            #[cfg(not(feature = "no_object"))]
+            Union::Map(.., ref mut flags) => flags,
+            #[cfg(not(feature = "no_time"))]
+            Union::TimeStamp(.., ref mut flags) => flags,
+            #[cfg(not(feature = "no_closure"))]
+            Union::Shared(.., ref mut flags) => flags,
+        }
+    }
+    /// Update the [`DynamicFlags`] of this [`Dynamic`] and, deeply, of all the items and
+    /// properties it contains.
+    fn update_flags(&mut self, update: &impl Fn(&mut DynamicFlags)) {
+        update(self.flags_mut());
+
+        match self.0 {
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(ref mut a, ..) => {
                 for v in a.as_mut() {
-                    v.set_access_mode(typ);
+                    v.update_flags(update);
                 }
             }
-            #[cfg(not(feature = "no_index"))]
-            Union::Blob(.., ref mut access) => *access = typ,
             #[cfg(not(feature = "no_object"))]
-            Union::Map(ref mut m, _, ref mut access) => {
-                *access = typ;
+            Union::Map(ref mut m, ..) => {
                 for v in m.values_mut() {
-                    v.set_access_mode(typ);
+                    v.update_flags(update);
                 }
             }
-            #[cfg(not(feature = "no_time"))]
-            Union::TimeStamp(.., ref mut access) => *access = typ,
-            #[cfg(not(feature = "no_closure"))]
-            Union::Shared(.., ref mut access) => *access = typ,
+            _ => (),
         }
+    }
+    /// Get the [`AccessMode`] for this [`Dynamic`].
+    ///
+    /// A [frozen][Dynamic::into_frozen] [`Dynamic`] is always [read-only][AccessMode::ReadOnly].
+    #[inline(always)]
+    #[must_use]
+    pub(crate) const fn access_mode(&self) -> AccessMode {
+        self.flags().access_mode()
+    }
+    /// Set the [`AccessMode`] for this [`Dynamic`].
+    ///
+    /// A frozen [`Dynamic`] stays frozen.
+    pub(crate) fn set_access_mode(&mut self, typ: AccessMode) -> &mut Self {
+        let read_only = typ == AccessMode::ReadOnly;
+        self.update_flags(&|flags| flags.set(DynamicFlags::READ_ONLY, read_only));
         self
     }
     /// Make this [`Dynamic`] read-only (i.e. a constant).
@@ -1149,9 +1386,66 @@ impl Dynamic {
         value.set_access_mode(AccessMode::ReadOnly);
         value
     }
+    /// Deeply freeze this [`Dynamic`], making it (and all the items and properties it contains)
+    /// permanently read-only.
+    ///
+    /// Unlike [`into_read_only`][Dynamic::into_read_only], clones of a frozen [`Dynamic`] are also
+    /// frozen, so it stays protected even when passed around or assigned to other variables.
+    /// Mutating it (e.g. assigning to an item or property, or calling a method that modifies it)
+    /// in a script raises an error.
+    ///
+    /// If the [`Dynamic`] holds a _shared_ value, the shared value itself is frozen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Dynamic;
+    ///
+    /// let value = Dynamic::from(42_i64).into_frozen();
+    ///
+    /// assert!(value.is_frozen());
+    /// assert!(value.clone().is_frozen());
+    /// ```
+    #[inline]
+    pub fn into_frozen(self) -> Self {
+        let mut value = self;
+        value.freeze();
+        value
+    }
+    /// Deeply freeze this [`Dynamic`] in place.
+    ///
+    /// See [`into_frozen`][Dynamic::into_frozen] for details.
+    pub(crate) fn freeze(&mut self) {
+        #[cfg(not(feature = "no_closure"))]
+        if let Union::Shared(ref cell, ..) = self.0 {
+            if let Some(mut value) = crate::func::locked_write(cell) {
+                value.update_flags(&|flags| flags.insert(DynamicFlags::FROZEN));
+            }
+        }
+
+        self.update_flags(&|flags| flags.insert(DynamicFlags::FROZEN));
+    }
+    /// Is this [`Dynamic`] frozen?
+    ///
+    /// See [`into_frozen`][Dynamic::into_frozen] for details.
+    ///
+    /// # Shared Value
+    ///
+    /// If a [`Dynamic`] holds a _shared_ value, then it is frozen only if the shared value
+    /// itself is frozen.
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        #[cfg(not(feature = "no_closure"))]
+        if let Union::Shared(ref cell, ..) = self.0 {
+            return crate::func::locked_read(cell)
+                .map_or(false, |v| v.flags().contains(DynamicFlags::FROZEN));
+        }
+
+        self.flags().contains(DynamicFlags::FROZEN)
+    }
     /// Is this [`Dynamic`] read-only?
     ///
-    /// Constant [`Dynamic`] values are read-only.
+    /// Constant and [frozen][Dynamic::into_frozen] [`Dynamic`] values are read-only.
     ///
     /// # Usage
     ///
@@ -1217,7 +1511,7 @@ impl Dynamic {
             #[cfg(not(feature = "no_index"))]
             Union::Array(ref a, ..) => a.iter().all(Self::is_hashable),
             #[cfg(not(feature = "no_index"))]
-            Union::Blob(..) => true,
+            Union::Blob(..) | Union::SharedBlob(..) => true,
             #[cfg(not(feature = "no_object"))]
             Union::Map(ref m, ..) => m.values().all(Self::is_hashable),
             Union::FnPtr(ref f, ..) => {
@@ -1397,6 +1691,8 @@ impl Dynamic {
         #[cfg(not(feature = "no_index"))]
         // don't use blob.into() because it'll be converted into an Array
         reify! { value => |v: Blob| return Self::from_blob(v) }
+        #[cfg(not(feature = "no_index"))]
+        reify! { value => |v: SharedBlob| return Self::from_shared_blob(v) }
         #[cfg(not(feature = "no_object"))]
         reify! { value => |v: Map| return v.into() }
         reify! { value => |v: FnPtr| return v.into() }
@@ -1409,7 +1705,7 @@ impl Dynamic {
         Self(Union::Variant(
             Box::new(Box::new(value)),
             DEFAULT_TAG_VALUE,
-            ReadWrite,
+            DynamicFlags::READ_WRITE,
         ))
     }
     /// Turn the [`Dynamic`] value into a shared [`Dynamic`] value backed by an
@@ -1428,14 +1724,14 @@ impl Dynamic {
     #[cfg(not(feature = "no_closure"))]
     #[inline]
     pub fn into_shared(self) -> Self {
-        let _access = self.access_mode();
+        let _flags = self.flags();
 
         match self.0 {
             Union::Shared(..) => self,
             _ => Self(Union::Shared(
                 crate::Locked::new(self).into(),
                 DEFAULT_TAG_VALUE,
-                _access,
+                _flags,
             )),
         }
     }
@@ -1574,6 +1870,13 @@ impl Dynamic {
                 _ => Err(self),
             };
         }
+        #[cfg(not(feature = "no_index"))]
+        if TypeId::of::<T>() == TypeId::of::<SharedBlob>() {
+            return match self.0 {
+                Union::SharedBlob(b, ..) => Ok(reify! { b => !!! T }),
+                _ => Err(self),
+            };
+        }
         #[cfg(not(feature = "no_object"))]
         if TypeId::of::<T>() == TypeId::of::<Map>() {
             return match self.0 {
@@ -1732,7 +2035,7 @@ impl Dynamic {
     pub fn flatten(self) -> Self {
         match self.0 {
             #[cfg(not(feature = "no_closure"))]
-            Union::Shared(cell, tag, access) => match crate::func::native::shared_try_take(cell) {
+            Union::Shared(cell, tag, flags) => match crate::func::native::shared_try_take(cell) {
                 // If there are no outstanding references, consume the shared value and return it
                 #[cfg(not(feature = "sync"))]
                 Ok(value) => value.into_inner().flatten(),
@@ -1747,7 +2050,7 @@ impl Dynamic {
                     if let Some(guard) = crate::func::locked_read(&cell) {
                         return guard.flatten_clone();
                     }
-                    Self(Union::Shared(cell, tag, access))
+                    Self(Union::Shared(cell, tag, flags))
                 }
             },
             _ => self,
@@ -1759,19 +2062,17 @@ impl Dynamic {
     ///
     /// ## Note
     ///
-    /// Under the `sync` feature, shared values use [`RwLock`][std::sync::RwLock] and they are never locked.
-    /// Access just waits until the [`RwLock`][std::sync::RwLock] is released.
-    /// So this method always returns [`false`] under [`Sync`].
+    /// Under the `sync` feature, shared values use [`RwLock`][std::sync::RwLock] and access by
+    /// other threads just waits until the [`RwLock`][std::sync::RwLock] is released.
+    /// So this method only returns [`true`] under [`Sync`] if the value is locked for write by a
+    /// lock guard (e.g. from [`write_lock`][Dynamic::write_lock]) held by the current thread.
     #[cfg(not(feature = "no_closure"))]
     #[inline]
     #[must_use]
     pub fn is_locked(&self) -> bool {
         #[cfg(not(feature = "no_closure"))]
-        if let Union::Shared(ref _cell, ..) = self.0 {
-            #[cfg(not(feature = "sync"))]
-            return _cell.try_borrow().is_err();
-            #[cfg(feature = "sync")]
-            return false;
+        if let Union::Shared(ref cell, ..) = self.0 {
+            return crate::func::native::is_locked_by_current_thread(cell, false);
         }
 
         false
@@ -1784,8 +2085,8 @@ impl Dynamic {
     ///
     /// # Shared Value
     ///
-    /// Under the `sync` feature, a _shared_ value may deadlock.
-    /// Otherwise, this call also fails if the data is currently borrowed for write.
+    /// This call also fails if the data is currently borrowed for write (under the `sync`
+    /// feature, by a lock guard held by the current thread; other threads are waited for).
     ///
     /// Under these circumstances, [`None`] is also returned.
     ///
@@ -1800,7 +2101,8 @@ impl Dynamic {
                         if TypeId::of::<Self>() == TypeId::of::<T>()
                             || (*guard).type_id() == TypeId::of::<T>() =>
                     {
-                        Some(DynamicReadLock(DynamicReadLockInner::Guard(guard)))
+                        let tracker = crate::func::native::LockTracker::new(cell, false);
+                        Some(DynamicReadLock(DynamicReadLockInner::Guard(guard, tracker)))
                     }
                     _ => None,
                 };
@@ -1820,8 +2122,8 @@ impl Dynamic {
     ///
     /// # Shared Value
     ///
-    /// Under the `sync` feature, a _shared_ value may deadlock.
-    /// Otherwise, this call also fails if the data is currently borrowed for write.
+    /// This call also fails if the data is currently borrowed (under the `sync` feature, by a
+    /// lock guard held by the current thread; other threads are waited for).
     ///
     /// Under these circumstances, [`None`] is also returned.
     ///
@@ -1836,7 +2138,10 @@ impl Dynamic {
                         if TypeId::of::<Self>() == TypeId::of::<T>()
                             || (*guard).type_id() == TypeId::of::<T>() =>
                     {
-                        Some(DynamicWriteLock(DynamicWriteLockInner::Guard(guard)))
+                        let tracker = crate::func::native::LockTracker::new(cell, true);
+                        Some(DynamicWriteLock(DynamicWriteLockInner::Guard(
+                            guard, tracker,
+                        )))
                     }
                     _ => None,
                 };
@@ -1848,6 +2153,75 @@ impl Dynamic {
             .map(DynamicWriteLockInner::Reference)
             .map(DynamicWriteLock)
     }
+    /// Borrow the value of a specific type held by the [`Dynamic`] without cloning it.
+    ///
+    /// This is useful for inspecting large values (e.g. arrays or object maps) returned from
+    /// scripts. It works uniformly for normal and _shared_ values (e.g. variables captured by
+    /// closures).
+    ///
+    /// Casting to [`Dynamic`] just returns a reference to it.
+    ///
+    /// Returns [`None`] if types mismatch.
+    ///
+    /// # Shared Value
+    ///
+    /// A _shared_ value is locked for reading until the returned guard is dropped.
+    ///
+    /// Under the `sync` feature, a _shared_ value may deadlock.
+    /// Otherwise, [`None`] is also returned if the data is currently borrowed for write.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Dynamic, ImmutableString};
+    ///
+    /// let x = Dynamic::from("hello");
+    /// assert_eq!(x.try_cast_ref::<ImmutableString>().unwrap().len(), 5);
+    /// assert!(x.try_cast_ref::<i64>().is_none());
+    ///
+    /// # #[cfg(not(feature = "no_closure"))]
+    /// # {
+    /// let shared = x.into_shared();
+    /// assert_eq!(shared.try_cast_ref::<ImmutableString>().unwrap().as_str(), "hello");
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn try_cast_ref<T: Any + Clone>(&self) -> Option<DynamicRef<'_, T>> {
+        self.read_lock().map(DynamicRef)
+    }
+    /// Mutably borrow the value of a specific type held by the [`Dynamic`] without cloning it.
+    ///
+    /// This works uniformly for normal and _shared_ values (e.g. variables captured by closures).
+    /// Modifying a _shared_ value through the returned guard changes it for all its holders.
+    ///
+    /// Casting to [`Dynamic`] just returns a mutable reference to it.
+    ///
+    /// Returns [`None`] if types mismatch.
+    ///
+    /// # Shared Value
+    ///
+    /// A _shared_ value is locked for writing until the returned guard is dropped.
+    ///
+    /// Under the `sync` feature, a _shared_ value may deadlock.
+    /// Otherwise, [`None`] is also returned if the data is currently borrowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Dynamic;
+    ///
+    /// let mut x = Dynamic::from(vec![Dynamic::from(1_i64)]);
+    ///
+    /// # #[cfg(not(feature = "no_index"))]
+    /// x.try_cast_mut::<rhai::Array>().unwrap().push(Dynamic::from(2_i64));
+    ///
+    /// # #[cfg(not(feature = "no_index"))]
+    /// assert_eq!(x.try_cast_ref::<rhai::Array>().unwrap().len(), 2);
+    /// ```
+    #[inline(always)]
+    pub fn try_cast_mut<T: Any + Clone>(&mut self) -> Option<DynamicRefMut<'_, T>> {
+        self.write_lock().map(DynamicRefMut)
+    }
     /// Get a reference of a specific type to the [`Dynamic`].
     ///
     /// Casting to [`Dynamic`] just returns a reference to it.
@@ -1914,6 +2288,13 @@ impl Dynamic {
                 _ => None,
             };
         }
+        #[cfg(not(feature = "no_index"))]
+        if TypeId::of::<T>() == TypeId::of::<SharedBlob>() {
+            return match self.0 {
+                Union::SharedBlob(ref v, ..) => v.as_any().downcast_ref::<T>(),
+                _ => None,
+            };
+        }
         #[cfg(not(feature = "no_object"))]
         if TypeId::of::<T>() == TypeId::of::<Map>() {
             return match self.0 {
@@ -2017,6 +2398,13 @@ impl Dynamic {
                 _ => None,
             };
         }
+        #[cfg(not(feature = "no_index"))]
+        if TypeId::of::<T>() == TypeId::of::<SharedBlob>() {
+            return match self.0 {
+                Union::SharedBlob(ref mut v, ..) => v.as_any_mut().downcast_mut::<T>(),
+                _ => None,
+            };
+        }
         #[cfg(not(feature = "no_object"))]
         if TypeId::of::<T>() == TypeId::of::<Map>() {
             return match self.0 {
@@ -2799,6 +3187,10 @@ impl Dynamic {
             Union::Blob(b, ..) if TypeId::of::<T>() == TypeId::of::<u8>() => {
                 Ok(reify! { *b => !!! Vec<T> })
             }
+            Union::SharedBlob(b, ..) if TypeId::of::<T>() == TypeId::of::<u8>() => {
+                let b = crate::Shared::try_unwrap(b).unwrap_or_else(|b| b.as_ref().clone());
+                Ok(reify! { b => !!! Vec<T> })
+            }
             #[cfg(not(feature = "no_closure"))]
             Union::Shared(ref cell, ..) => crate::func::locked_read(cell)
                 .and_then(|guard| match guard.0 {
@@ -2817,6 +3209,8 @@ impl Dynamic {
     }
     /// Convert the [`Dynamic`] into a [`Blob`].
     ///
+    /// A [`SharedBlob`] is also converted, copying its bytes only if they are shared.
+    ///
     /// Not available under `no_index`.
     ///
     /// # Errors
@@ -2836,10 +3230,14 @@ impl Dynamic {
     pub fn into_blob(self) -> Result<Blob, &'static str> {
         match self.0 {
             Union::Blob(b, ..) => Ok(*b),
+            Union::SharedBlob(b, ..) => {
+                Ok(crate::Shared::try_unwrap(b).unwrap_or_else(|b| b.as_ref().clone()))
+            }
             #[cfg(not(feature = "no_closure"))]
             Union::Shared(ref cell, ..) => crate::func::locked_read(cell)
                 .and_then(|guard| match guard.0 {
                     Union::Blob(ref b, ..) => Some(b.as_ref().clone()),
+                    Union::SharedBlob(ref b, ..) => Some(b.as_ref().clone()),
                     _ => None,
                 })
                 .ok_or_else(|| cell.type_name()),
@@ -2876,59 +3274,95 @@ impl Dynamic {
 impl From<()> for Dynamic {
     #[inline(always)]
     fn from(value: ()) -> Self {
-        Self(Union::Unit(value, DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Unit(
+            value,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 impl From<bool> for Dynamic {
     #[inline(always)]
     fn from(value: bool) -> Self {
-        Self(Union::Bool(value, DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Bool(
+            value,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 impl From<INT> for Dynamic {
     #[inline(always)]
     fn from(value: INT) -> Self {
-        Self(Union::Int(value, DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Int(
+            value,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 #[cfg(not(feature = "no_float"))]
 impl From<crate::FLOAT> for Dynamic {
     #[inline(always)]
     fn from(value: crate::FLOAT) -> Self {
-        Self(Union::Float(value.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Float(
+            value.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 #[cfg(not(feature = "no_float"))]
 impl From<super::FloatWrapper<crate::FLOAT>> for Dynamic {
     #[inline(always)]
     fn from(value: super::FloatWrapper<crate::FLOAT>) -> Self {
-        Self(Union::Float(value, DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Float(
+            value,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 #[cfg(feature = "decimal")]
 impl From<rust_decimal::Decimal> for Dynamic {
     #[inline(always)]
     fn from(value: rust_decimal::Decimal) -> Self {
-        Self(Union::Decimal(value.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Decimal(
+            value.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 impl From<char> for Dynamic {
     #[inline(always)]
     fn from(value: char) -> Self {
-        Self(Union::Char(value, DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Char(
+            value,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 impl<S: Into<ImmutableString>> From<S> for Dynamic {
     #[inline(always)]
     fn from(value: S) -> Self {
-        Self(Union::Str(value.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Str(
+            value.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 impl FromStr for Dynamic {
     type Err = ();
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        Ok(Self(Union::Str(value.into(), DEFAULT_TAG_VALUE, ReadWrite)))
+        Ok(Self(Union::Str(
+            value.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        )))
     }
 }
 #[cfg(not(feature = "no_index"))]
@@ -2938,7 +3372,7 @@ impl<T: Variant + Clone> From<Vec<T>> for Dynamic {
         Self(Union::Array(
             Box::new(value.into_iter().map(Self::from).collect()),
             DEFAULT_TAG_VALUE,
-            ReadWrite,
+            DynamicFlags::READ_WRITE,
         ))
     }
 }
@@ -2949,7 +3383,7 @@ impl<T: Variant + Clone> From<&[T]> for Dynamic {
         Self(Union::Array(
             Box::new(value.iter().cloned().map(Self::from).collect()),
             DEFAULT_TAG_VALUE,
-            ReadWrite,
+            DynamicFlags::READ_WRITE,
         ))
     }
 }
@@ -2960,7 +3394,7 @@ impl<T: Variant + Clone> std::iter::FromIterator<T> for Dynamic {
         Self(Union::Array(
             Box::new(iter.into_iter().map(Self::from).collect()),
             DEFAULT_TAG_VALUE,
-            ReadWrite,
+            DynamicFlags::READ_WRITE,
         ))
     }
 }
@@ -2979,7 +3413,7 @@ impl<K: Into<crate::Identifier>, T: Variant + Clone> From<std::collections::Hash
                     .collect(),
             ),
             DEFAULT_TAG_VALUE,
-            ReadWrite,
+            DynamicFlags::READ_WRITE,
         ))
     }
 }
@@ -2991,7 +3425,7 @@ impl<K: Into<crate::Identifier>> From<std::collections::HashSet<K>> for Dynamic
         Self(Union::Map(
             Box::new(value.into_iter().map(|k| (k.into(), Self::UNIT)).collect()),
             DEFAULT_TAG_VALUE,
-            ReadWrite,
+            DynamicFlags::READ_WRITE,
         ))
     }
 }
@@ -3009,7 +3443,7 @@ impl<K: Into<crate::Identifier>, T: Variant + Clone> From<std::collections::BTre
                     .collect(),
             ),
             DEFAULT_TAG_VALUE,
-            ReadWrite,
+            DynamicFlags::READ_WRITE,
         ))
     }
 }
@@ -3020,28 +3454,40 @@ impl<K: Into<crate::Identifier>> From<std::collections::BTreeSet<K>> for Dynamic
         Self(Union::Map(
             Box::new(value.into_iter().map(|k| (k.into(), Self::UNIT)).collect()),
             DEFAULT_TAG_VALUE,
-            ReadWrite,
+            DynamicFlags::READ_WRITE,
         ))
     }
 }
 impl From<FnPtr> for Dynamic {
     #[inline(always)]
     fn from(value: FnPtr) -> Self {
-        Self(Union::FnPtr(value.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::FnPtr(
+            value.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 #[cfg(not(feature = "no_time"))]
 impl From<Instant> for Dynamic {
     #[inline(always)]
     fn from(value: Instant) -> Self {
-        Self(Union::TimeStamp(value.into(), DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::TimeStamp(
+            value.into(),
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 #[cfg(not(feature = "no_closure"))]
 impl From<crate::Shared<crate::Locked<Self>>> for Dynamic {
     #[inline(always)]
     fn from(value: crate::Shared<crate::Locked<Self>>) -> Self {
-        Self(Union::Shared(value, DEFAULT_TAG_VALUE, ReadWrite))
+        Self(Union::Shared(
+            value,
+            DEFAULT_TAG_VALUE,
+            DynamicFlags::READ_WRITE,
+        ))
     }
 }
 
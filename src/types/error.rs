@@ -47,8 +47,18 @@ pub enum EvalAltResult {
     ErrorIndexNotFound(Dynamic, Position),
     /// Call to an unknown function. Wrapped value is the function signature.
     ErrorFunctionNotFound(String, Position),
+    /// A tainted value is passed to a function marked as a taint sink.
+    /// Wrapped value is the function name.
+    ErrorTaintedValue(String, Position),
     /// Usage of an unknown [module][crate::Module]. Wrapped value is the [module][crate::Module] name.
     ErrorModuleNotFound(String, Position),
+    /// Circular `import` of [modules][crate::Module].
+    /// Wrapped value is the cycle of [module][crate::Module] paths, each with the position of the
+    /// `import` statement that imports it.
+    ErrorCircularImport(Vec<(String, Position)>, Position),
+    /// A script failed signature verification (under the `signing` feature).
+    /// Wrapped value is the script name.
+    ErrorInvalidSignature(String, Position),
 
     /// An error has occurred inside a called function.
     /// Wrapped values are the function name, function source, and the interior error.
@@ -84,6 +94,10 @@ pub enum EvalAltResult {
 
     /// Data race detected when accessing a variable. Wrapped value is the variable name.
     ErrorDataRace(String, Position),
+    /// A _shared_ value already locked by the current thread (e.g. by a native function that
+    /// calls back into the [`Engine`][crate::Engine]) is locked again.
+    /// Wrapped value is the variable name.
+    ErrorReentrantLock(String, Position),
     /// Calling a non-pure method on a constant.  Wrapped value is the function name.
     ErrorNonPureMethodCallOnConstant(String, Position),
     /// Assignment to a constant variable. Wrapped value is the variable name.
@@ -95,6 +109,8 @@ pub enum EvalAltResult {
 
     /// Number of operations over maximum limit.
     ErrorTooManyOperations(Position),
+    /// Evaluation has run past the deadline.
+    ErrorDeadlineExceeded(Position),
     /// Number of variables over maximum limit.
     ErrorTooManyVariables(Position),
     /// [Modules][crate::Module] over maximum limit.
@@ -161,9 +177,30 @@ impl fmt::Display for EvalAltResult {
             Self::ErrorPropertyNotFound(s, ..) => write!(f, "Property not found: {s}")?,
             Self::ErrorIndexNotFound(s, ..) => write!(f, "Invalid index: {s}")?,
             Self::ErrorFunctionNotFound(s, ..) => write!(f, "Function not found: {s}")?,
+            Self::ErrorTaintedValue(s, ..) => write!(f, "Tainted value passed to function: {s}")?,
             Self::ErrorModuleNotFound(s, ..) => write!(f, "Module not found: {s}")?,
+            Self::ErrorCircularImport(cycle, ..) => {
+                f.write_str("Circular import: ")?;
+                for (i, (path, pos)) in cycle.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" -> ")?;
+                    }
+                    write!(f, "'{path}'")?;
+                    if !pos.is_none() {
+                        write!(f, " ({pos})")?;
+                    }
+                }
+            }
+            Self::ErrorInvalidSignature(s, ..) => write!(f, "Invalid signature for script: {s}")?,
             Self::ErrorDataRace(s, ..) if s.is_empty() => write!(f, "Data race detected")?,
             Self::ErrorDataRace(s, ..) => write!(f, "Data race detected on variable '{s}'")?,
+            Self::ErrorReentrantLock(s, ..) if s.is_empty() => {
+                write!(f, "Shared value is already locked by the current thread")?
+            }
+            Self::ErrorReentrantLock(s, ..) => write!(
+                f,
+                "Shared variable '{s}' is already locked by the current thread"
+            )?,
 
             Self::ErrorDotExpr(s, ..) if s.is_empty() => f.write_str("Malformed dot expression")?,
             Self::ErrorDotExpr(s, ..) => f.write_str(s)?,
@@ -172,6 +209,7 @@ impl fmt::Display for EvalAltResult {
             Self::ErrorUnboundThis(..) => f.write_str("'this' not bound")?,
             Self::ErrorFor(..) => f.write_str("For loop expects iterable type")?,
             Self::ErrorTooManyOperations(..) => f.write_str("Too many operations")?,
+            Self::ErrorDeadlineExceeded(..) => f.write_str("Deadline exceeded")?,
             Self::ErrorTooManyVariables(..) => f.write_str("Too many variables defined")?,
             Self::ErrorTooManyModules(..) => f.write_str("Too many modules imported")?,
             Self::ErrorStackOverflow(..) => f.write_str("Stack overflow")?,
@@ -326,7 +364,9 @@ impl EvalAltResult {
             | Self::ErrorPropertyNotFound(..)
             | Self::ErrorIndexNotFound(..)
             | Self::ErrorModuleNotFound(..)
+            | Self::ErrorCircularImport(..)
             | Self::ErrorDataRace(..)
+            | Self::ErrorReentrantLock(..)
             | Self::ErrorNonPureMethodCallOnConstant(..)
             | Self::ErrorAssignmentToConstant(..)
             | Self::ErrorMismatchOutputType(..)
@@ -340,7 +380,13 @@ impl EvalAltResult {
             // Therefore, this error should not be catchable.
             Self::ErrorCustomSyntax(..) => false,
 
+            Self::ErrorInvalidSignature(..) => false,
+
+            // Scripts must not be able to silence taint violations.
+            Self::ErrorTaintedValue(..) => false,
+
             Self::ErrorTooManyOperations(..)
+            | Self::ErrorDeadlineExceeded(..)
             | Self::ErrorTooManyVariables(..)
             | Self::ErrorTooManyModules(..)
             | Self::ErrorStackOverflow(..)
@@ -360,7 +406,9 @@ impl EvalAltResult {
             Self::ErrorSystem(..)
                 | Self::ErrorParsing(..)
                 | Self::ErrorCustomSyntax(..)
+                | Self::ErrorInvalidSignature(..)
                 | Self::ErrorTooManyOperations(..)
+                | Self::ErrorDeadlineExceeded(..)
                 | Self::ErrorTooManyVariables(..)
                 | Self::ErrorTooManyModules(..)
                 | Self::ErrorStackOverflow(..)
@@ -391,12 +439,15 @@ impl EvalAltResult {
             | Self::ErrorFor(..)
             | Self::ErrorArithmetic(..)
             | Self::ErrorTooManyOperations(..)
+            | Self::ErrorDeadlineExceeded(..)
             | Self::ErrorTooManyVariables(..)
             | Self::ErrorTooManyModules(..)
             | Self::ErrorStackOverflow(..)
             | Self::ErrorRuntime(..) => (),
 
-            Self::ErrorFunctionNotFound(f, ..) | Self::ErrorNonPureMethodCallOnConstant(f, ..) => {
+            Self::ErrorFunctionNotFound(f, ..)
+            | Self::ErrorTaintedValue(f, ..)
+            | Self::ErrorNonPureMethodCallOnConstant(f, ..) => {
                 map.insert("function".into(), f.into());
             }
             Self::ErrorInFunctionCall(f, s, ..) => {
@@ -418,6 +469,7 @@ impl EvalAltResult {
             | Self::ErrorVariableNotFound(v, ..)
             | Self::ErrorPropertyNotFound(v, ..)
             | Self::ErrorDataRace(v, ..)
+            | Self::ErrorReentrantLock(v, ..)
             | Self::ErrorAssignmentToConstant(v, ..) => {
                 map.insert("variable".into(), v.into());
             }
@@ -427,6 +479,19 @@ impl EvalAltResult {
             Self::ErrorInModule(m, ..) | Self::ErrorModuleNotFound(m, ..) => {
                 map.insert("module".into(), m.into());
             }
+            Self::ErrorInvalidSignature(s, ..) => {
+                map.insert("script".into(), s.into());
+            }
+            Self::ErrorCircularImport(cycle, ..) => {
+                let paths = cycle.iter().map(|(path, ..)| path.as_str());
+                map.insert(
+                    "cycle".into(),
+                    #[cfg(not(feature = "no_index"))]
+                    Dynamic::from_array(paths.map(Into::into).collect()),
+                    #[cfg(feature = "no_index")]
+                    paths.collect::<Vec<_>>().join(" -> ").into(),
+                );
+            }
             Self::ErrorDotExpr(p, ..) => {
                 map.insert("property".into(), p.into());
             }
@@ -474,6 +539,7 @@ impl EvalAltResult {
 
             Self::ErrorParsing(.., pos)
             | Self::ErrorFunctionNotFound(.., pos)
+            | Self::ErrorTaintedValue(.., pos)
             | Self::ErrorInFunctionCall(.., pos)
             | Self::ErrorInModule(.., pos)
             | Self::ErrorUnboundThis(pos)
@@ -489,13 +555,17 @@ impl EvalAltResult {
             | Self::ErrorPropertyNotFound(.., pos)
             | Self::ErrorIndexNotFound(.., pos)
             | Self::ErrorModuleNotFound(.., pos)
+            | Self::ErrorCircularImport(.., pos)
+            | Self::ErrorInvalidSignature(.., pos)
             | Self::ErrorDataRace(.., pos)
+            | Self::ErrorReentrantLock(.., pos)
             | Self::ErrorNonPureMethodCallOnConstant(.., pos)
             | Self::ErrorAssignmentToConstant(.., pos)
             | Self::ErrorMismatchOutputType(.., pos)
             | Self::ErrorDotExpr(.., pos)
             | Self::ErrorArithmetic(.., pos)
             | Self::ErrorTooManyOperations(pos)
+            | Self::ErrorDeadlineExceeded(pos)
             | Self::ErrorTooManyVariables(pos)
             | Self::ErrorTooManyModules(pos)
             | Self::ErrorStackOverflow(pos)
@@ -536,6 +606,7 @@ impl EvalAltResult {
 
             Self::ErrorParsing(.., pos)
             | Self::ErrorFunctionNotFound(.., pos)
+            | Self::ErrorTaintedValue(.., pos)
             | Self::ErrorInFunctionCall(.., pos)
             | Self::ErrorInModule(.., pos)
             | Self::ErrorUnboundThis(pos)
@@ -551,13 +622,17 @@ impl EvalAltResult {
             | Self::ErrorPropertyNotFound(.., pos)
             | Self::ErrorIndexNotFound(.., pos)
             | Self::ErrorModuleNotFound(.., pos)
+            | Self::ErrorCircularImport(.., pos)
+            | Self::ErrorInvalidSignature(.., pos)
             | Self::ErrorDataRace(.., pos)
+            | Self::ErrorReentrantLock(.., pos)
             | Self::ErrorNonPureMethodCallOnConstant(.., pos)
             | Self::ErrorAssignmentToConstant(.., pos)
             | Self::ErrorMismatchOutputType(.., pos)
             | Self::ErrorDotExpr(.., pos)
             | Self::ErrorArithmetic(.., pos)
             | Self::ErrorTooManyOperations(pos)
+            | Self::ErrorDeadlineExceeded(pos)
             | Self::ErrorTooManyVariables(pos)
             | Self::ErrorTooManyModules(pos)
             | Self::ErrorStackOverflow(pos)
@@ -114,3 +114,156 @@ impl<F: Float> FloatWrapper<F> {
         Self(value)
     }
 }
+
+/// Rules for converting floating-point numbers to and from strings, configured via
+/// [`Engine::set_float_format`][crate::Engine::set_float_format].
+///
+/// Not available under `no_float`.
+///
+/// # Example
+///
+/// ```
+/// use rhai::FloatFormat;
+///
+/// let format = FloatFormat::new().with_significant_digits(3);
+///
+/// assert_eq!(format.format(3.14159_f64), "3.14");
+/// assert_eq!(format.format(1234567.0_f64), "1230000.0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct FloatFormat {
+    /// Maximum number of significant digits when converting to strings.
+    ///
+    /// Default `None`, which uses the shortest representation that parses back to the same number.
+    pub significant_digits: Option<usize>,
+    /// Absolute value above which numbers are converted to strings in scientific notation.
+    /// Default 1e13.
+    pub scientific_above: f64,
+    /// Absolute value (other than zero) below which numbers are converted to strings in
+    /// scientific notation. Default 1e-13.
+    pub scientific_below: f64,
+    /// Accept `_` separators between digits when parsing? Default `false`.
+    pub allow_separators: bool,
+    /// Accept `inf`, `-inf` and `NaN` (and numbers overflowing to infinity) when parsing?
+    /// Default `true`.
+    pub allow_non_finite: bool,
+}
+
+impl Default for FloatFormat {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FloatFormat {
+    /// Create a new [`FloatFormat`] with default values.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            significant_digits: None,
+            scientific_above: FloatWrapper::<f64>::MAX_NATURAL_FLOAT_FOR_DISPLAY as f64,
+            scientific_below: FloatWrapper::<f64>::MIN_NATURAL_FLOAT_FOR_DISPLAY as f64,
+            allow_separators: false,
+            allow_non_finite: true,
+        }
+    }
+    /// Set the maximum number of significant digits when converting to strings.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_significant_digits(mut self, digits: usize) -> Self {
+        self.significant_digits = Some(digits);
+        self
+    }
+    /// Set the range of absolute values that are converted to strings in natural notation.
+    /// Numbers outside the range are converted in scientific notation.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_scientific_thresholds(mut self, below: f64, above: f64) -> Self {
+        self.scientific_below = below;
+        self.scientific_above = above;
+        self
+    }
+    /// Accept `_` separators between digits when parsing?
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_separators(mut self, allow: bool) -> Self {
+        self.allow_separators = allow;
+        self
+    }
+    /// Accept `inf`, `-inf` and `NaN` when parsing?
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_non_finite(mut self, allow: bool) -> Self {
+        self.allow_non_finite = allow;
+        self
+    }
+
+    /// Convert a floating-point number into a string.
+    #[must_use]
+    pub fn format<F: Float + fmt::Display + fmt::LowerExp + FromStr>(&self, value: F) -> String {
+        let value = match self.significant_digits {
+            Some(digits) if value.is_finite() && !value.is_zero() => {
+                // Round via scientific notation, then render the rounded number as usual
+                let s = format!("{:.*e}", digits.max(1) - 1, value);
+                s.parse().unwrap_or(value)
+            }
+            _ => value,
+        };
+
+        let abs = value.abs();
+        let above =
+            <F as num_traits::NumCast>::from(self.scientific_above).unwrap_or(F::infinity());
+        let below = <F as num_traits::NumCast>::from(self.scientific_below).unwrap_or(F::zero());
+
+        if abs.is_zero() {
+            "0.0".into()
+        } else if abs > above || abs < below {
+            format!("{value:e}")
+        } else if abs.fract().is_zero() {
+            format!("{value}.0")
+        } else {
+            format!("{value}")
+        }
+    }
+    /// Parse a string into a floating-point number.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the error if the string is not a valid number under these rules.
+    pub fn parse<F: Float + FromStr>(&self, string: &str) -> Result<F, String>
+    where
+        F::Err: fmt::Display,
+    {
+        let string = string.trim();
+
+        let value = if self.allow_separators && string.contains('_') {
+            let bytes = string.as_bytes();
+
+            // Only remove separators between two digits
+            let s: String = string
+                .char_indices()
+                .filter(|&(i, ch)| {
+                    ch != '_'
+                        || i == 0
+                        || !bytes[i - 1].is_ascii_digit()
+                        || !bytes.get(i + 1).map_or(false, u8::is_ascii_digit)
+                })
+                .map(|(.., ch)| ch)
+                .collect();
+
+            s.parse::<F>()
+        } else {
+            string.parse::<F>()
+        }
+        .map_err(|err| err.to_string())?;
+
+        if !self.allow_non_finite && !value.is_finite() {
+            return Err("number is not finite".into());
+        }
+
+        Ok(value)
+    }
+}
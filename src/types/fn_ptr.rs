@@ -249,7 +249,10 @@ impl FnPtr {
 
                 let caches = &mut crate::eval::Caches::new();
 
-                return context.engine().call_script_fn(
+                let params = fn_def.params.iter().map(|p| p.as_str());
+                crate::func::native::ensure_no_reentrant_lock(args, params)?;
+
+                let result = context.engine().call_script_fn(
                     global,
                     caches,
                     &mut crate::Scope::new(),
@@ -260,6 +263,16 @@ impl FnPtr {
                     true,
                     context.position(),
                 );
+
+                // Charge the operations performed to the calling evaluation run
+                #[cfg(not(feature = "unchecked"))]
+                context.global_runtime_state().add_pending_operations(
+                    global
+                        .num_operations
+                        .saturating_sub(context.global_runtime_state().num_operations),
+                );
+
+                return result;
             }
             _ => (),
         }
@@ -0,0 +1,278 @@
+//! Module defining handles to host resources with lifetime tracking.
+
+use crate::func::{locked_read, locked_write, SendSync};
+use crate::{Locked, NativeCallContext, Position, RhaiResultOf, Shared, ERR};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{fmt, mem};
+
+#[cfg(not(feature = "sync"))]
+use alloc::rc::Weak;
+#[cfg(feature = "sync")]
+use alloc::sync::Weak;
+
+/// Callback function invoked when a [`Handle`] is closed.
+#[cfg(not(feature = "sync"))]
+pub type OnCloseCallback<T> = dyn FnOnce(T);
+/// Callback function invoked when a [`Handle`] is closed.
+#[cfg(feature = "sync")]
+pub type OnCloseCallback<T> = dyn FnOnce(T) + Send + Sync;
+
+/// Type-erased interface to the locked storage slot of a [`Handle`].
+trait HandleSlot: SendSync {
+    /// Close the slot, running the destructor (if any).
+    fn close(&self);
+    /// Is the slot still open?
+    ///
+    /// A slot that is currently locked is considered open.
+    fn is_open(&self) -> bool;
+}
+
+/// Storage slot of a [`Handle`].
+struct Slot<T> {
+    /// The resource, or [`None`] if closed.
+    value: Option<T>,
+    /// Destructor to run when the resource is closed.
+    on_close: Option<Box<OnCloseCallback<T>>>,
+}
+
+impl<T> Slot<T> {
+    /// Close the slot, running the destructor (if any).
+    fn close_value(&mut self) -> bool {
+        match self.value.take() {
+            Some(value) => {
+                if let Some(on_close) = self.on_close.take() {
+                    on_close(value);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T: SendSync> HandleSlot for Locked<Slot<T>> {
+    #[inline]
+    fn close(&self) {
+        if let Some(mut slot) = locked_write(self) {
+            slot.close_value();
+        }
+    }
+    #[inline]
+    fn is_open(&self) -> bool {
+        locked_read(self).map_or(true, |slot| slot.value.is_some())
+    }
+}
+
+impl<T> Drop for Slot<T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.close_value();
+    }
+}
+
+/// A handle to a host resource (e.g. a file, socket or database connection).
+///
+/// The resource is closed -- and its destructor (if any) run -- when:
+///
+/// * [`close`][Handle::close] is called on any clone of the handle, or
+/// * the last clone of the handle is dropped, or
+/// * the handle is opened via [`Handle::open`] and the evaluation run that owns it ends.
+///
+/// Accessing a closed handle returns an error.
+///
+/// Cloning a [`Handle`] yields another reference to the _same_ resource.
+///
+/// Register the handle type with an [`Engine`][crate::Engine] via
+/// [`register_handle`][crate::Engine::register_handle].
+pub struct Handle<T>(Shared<Locked<Slot<T>>>);
+
+impl<T> Clone for Handle<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match locked_read(&self.0) {
+            Some(slot) if slot.value.is_some() => f.write_str("Handle<open>"),
+            Some(_) => f.write_str("Handle<closed>"),
+            None => f.write_str("Handle<locked>"),
+        }
+    }
+}
+
+impl<T: SendSync + 'static> Handle<T> {
+    /// Create a new [`Handle`] that is not tracked by any evaluation run.
+    #[inline(always)]
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self::new_slot(value, None)
+    }
+    /// Create a new [`Handle`], with a destructor, that is not tracked by any evaluation run.
+    ///
+    /// The destructor is run exactly once, when the handle is closed.
+    #[inline(always)]
+    #[must_use]
+    pub fn with_destructor(value: T, on_close: impl FnOnce(T) + SendSync + 'static) -> Self {
+        Self::new_slot(value, Some(Box::new(on_close)))
+    }
+    /// Create a new [`Handle`] with a destructor, owned by the current evaluation run.
+    ///
+    /// The handle is automatically closed when the evaluation run ends, even if clones of it
+    /// are kept alive elsewhere (e.g. in a [`Scope`][crate::Scope]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorDataTooLarge`][crate::EvalAltResult::ErrorDataTooLarge] if the number of
+    /// open handles owned by the evaluation run would exceed the limit set via
+    /// [`Engine::set_max_open_handles`][crate::Engine::set_max_open_handles].
+    pub fn open(
+        context: &NativeCallContext,
+        value: T,
+        on_close: impl FnOnce(T) + SendSync + 'static,
+    ) -> RhaiResultOf<Self> {
+        let registry = context.global_runtime_state().handles();
+
+        #[cfg(not(feature = "unchecked"))]
+        if registry.num_open() >= context.engine().max_open_handles() {
+            return Err(ERR::ErrorDataTooLarge(
+                "Number of open handles".to_string(),
+                context.position(),
+            )
+            .into());
+        }
+
+        let handle = Self::with_destructor(value, on_close);
+        registry.track(&handle)?;
+        Ok(handle)
+    }
+    /// Create a new [`Handle`] from a value and an optional destructor.
+    #[inline(always)]
+    fn new_slot(value: T, on_close: Option<Box<OnCloseCallback<T>>>) -> Self {
+        Self(Shared::new(Locked::new(Slot {
+            value: Some(value),
+            on_close,
+        })))
+    }
+    /// Is the handle still open?
+    #[inline]
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        locked_read(&self.0).map_or(false, |slot| slot.value.is_some())
+    }
+    /// Close the handle, running its destructor (if any).
+    ///
+    /// Returns `true` if the handle was open.
+    #[inline]
+    pub fn close(&self) -> RhaiResultOf<bool> {
+        locked_write(&self.0)
+            .map(|mut slot| slot.close_value())
+            .ok_or_else(|| ERR::ErrorDataRace(String::new(), Position::NONE).into())
+    }
+    /// Access the resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handle is closed.
+    #[inline]
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> RhaiResultOf<R> {
+        let slot = locked_read(&self.0)
+            .ok_or_else(|| ERR::ErrorDataRace(String::new(), Position::NONE))?;
+        slot.value.as_ref().map(f).ok_or_else(Self::closed_error)
+    }
+    /// Access the resource mutably.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handle is closed.
+    #[inline]
+    pub fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> RhaiResultOf<R> {
+        let mut slot = locked_write(&self.0)
+            .ok_or_else(|| ERR::ErrorDataRace(String::new(), Position::NONE))?;
+        slot.value.as_mut().map(f).ok_or_else(Self::closed_error)
+    }
+    /// Error for accessing a closed handle.
+    #[cold]
+    #[inline(never)]
+    fn closed_error() -> crate::RhaiError {
+        "Handle is closed".into()
+    }
+}
+
+/// List of handles owned by an evaluation run, closed when dropped.
+#[derive(Default)]
+struct HandleList(Vec<Weak<dyn HandleSlot>>);
+
+impl Drop for HandleList {
+    fn drop(&mut self) {
+        for slot in mem::take(&mut self.0) {
+            if let Some(slot) = slot.upgrade() {
+                slot.close();
+            }
+        }
+    }
+}
+
+/// _(internals)_ Registry of [handles][Handle] owned by an evaluation run.
+/// Exported under the `internals` feature only.
+///
+/// All handles still open are closed when the last reference to the registry is dropped.
+#[derive(Clone, Default)]
+pub struct HandleRegistry(Shared<Locked<HandleList>>);
+
+impl fmt::Debug for HandleRegistry {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HandleRegistry")
+            .field("num_open", &self.num_open())
+            .finish()
+    }
+}
+
+impl HandleRegistry {
+    /// Create a new [`HandleRegistry`].
+    #[inline(always)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Number of handles in this registry that are still open.
+    #[inline]
+    #[must_use]
+    pub fn num_open(&self) -> usize {
+        locked_read(&self.0).map_or(0, |list| {
+            list.0
+                .iter()
+                .filter_map(Weak::upgrade)
+                .filter(|slot| slot.is_open())
+                .count()
+        })
+    }
+    /// Start tracking a [`Handle`], so that it is closed when this registry is dropped.
+    pub fn track<T: SendSync + 'static>(&self, handle: &Handle<T>) -> RhaiResultOf<()> {
+        let mut list = locked_write(&self.0)
+            .ok_or_else(|| ERR::ErrorDataRace(String::new(), Position::NONE))?;
+
+        // Prune handles that are already gone
+        list.0
+            .retain(|slot| slot.upgrade().map_or(false, |slot| slot.is_open()));
+
+        let slot: Shared<dyn HandleSlot> = handle.0.clone();
+        list.0.push(Shared::downgrade(&slot));
+
+        Ok(())
+    }
+    /// Close all handles in this registry.
+    #[inline]
+    pub fn close_all(&self) {
+        if let Some(mut list) = locked_write(&self.0) {
+            drop(mem::take(&mut *list));
+        }
+    }
+}
@@ -1,8 +1,8 @@
 //! A strings interner type.
 
 use super::BloomFilterU64;
-use crate::func::{hashing::get_hasher, StraightHashMap};
-use crate::ImmutableString;
+use crate::func::{hashing::get_hasher, Locked, SendSync, StraightHashMap};
+use crate::{ImmutableString, Shared};
 #[cfg(feature = "no_std")]
 use hashbrown::hash_map::Entry;
 #[cfg(not(feature = "no_std"))]
@@ -18,6 +18,55 @@ use std::{
 /// Maximum length of strings interned.
 pub const MAX_STRING_LEN: usize = 32;
 
+/// Statistics of a strings interner.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[non_exhaustive]
+pub struct InternerStats {
+    /// Number of strings currently interned.
+    pub len: usize,
+    /// Number of look-ups that found an interned string.
+    pub hits: u64,
+    /// Number of look-ups that did not find an interned string.
+    pub misses: u64,
+    /// Number of interned strings evicted to keep within the maximum number of strings.
+    pub evictions: u64,
+}
+
+/// Trait for a strings interner used by an [`Engine`][crate::Engine] when compiling and
+/// evaluating scripts.
+///
+/// The default implementation is [`StringsInterner`].
+pub trait StrInterner: SendSync {
+    /// Get an interned string, creating it via `mapper` (from `text`) if it is not yet interned.
+    ///
+    /// Strings under different categories are interned separately even if `text` is the same
+    /// (e.g. category `0` is for plain strings, `b'g'` for property getters and `b's'` for
+    /// property setters).
+    #[must_use]
+    fn intern(
+        &mut self,
+        category: u8,
+        text: &str,
+        mapper: &mut dyn FnMut(&str) -> ImmutableString,
+    ) -> ImmutableString;
+    /// The maximum number of strings to be interned.
+    #[must_use]
+    fn capacity(&self) -> usize;
+    /// Set the maximum number of strings to be interned.
+    fn set_capacity(&mut self, max: usize);
+    /// Clear all interned strings.
+    fn clear(&mut self);
+    /// Get the statistics of this strings interner.
+    #[inline(always)]
+    #[must_use]
+    fn stats(&self) -> InternerStats {
+        InternerStats::default()
+    }
+}
+
+/// A [strings interner][StrInterner] that can be shared among multiple [`Engine`][crate::Engine]'s.
+pub type SharedStrInterner = Shared<Locked<dyn StrInterner>>;
+
 /// _(internals)_ A cache for interned strings.
 /// Exported under the `internals` feature only.
 #[derive(Clone)]
@@ -28,6 +77,8 @@ pub struct StringsInterner {
     cache: StraightHashMap<ImmutableString>,
     /// Bloom filter to avoid caching "one-hit wonders".
     bloom_filter: BloomFilterU64,
+    /// Statistics.
+    stats: InternerStats,
 }
 
 impl fmt::Debug for StringsInterner {
@@ -47,12 +98,14 @@ impl StringsInterner {
             max_strings_interned,
             cache: <_>::default(),
             bloom_filter: BloomFilterU64::new(),
+            stats: InternerStats::default(),
         }
     }
 
     /// Get an identifier from a text string, adding it to the interner if necessary.
     #[inline(always)]
     #[must_use]
+    #[allow(dead_code)]
     pub fn get(&mut self, text: impl AsRef<str> + Into<ImmutableString>) -> ImmutableString {
         self.get_with_mapper(0, Into::into, text)
     }
@@ -92,6 +145,7 @@ impl StringsInterner {
 
         // Do not cache long strings and avoid caching "one-hit wonders".
         if key.len() > MAX_STRING_LEN || self.bloom_filter.is_absent_and_set(hash) {
+            self.stats.misses += 1;
             return mapper(text);
         }
 
@@ -101,8 +155,14 @@ impl StringsInterner {
         }
 
         let result = match self.cache.entry(hash) {
-            Entry::Occupied(e) => return e.get().clone(),
-            Entry::Vacant(e) => e.insert(mapper(text)).clone(),
+            Entry::Occupied(e) => {
+                self.stats.hits += 1;
+                return e.get().clone();
+            }
+            Entry::Vacant(e) => {
+                self.stats.misses += 1;
+                e.insert(mapper(text)).clone()
+            }
         };
 
         // Throttle the cache upon exit
@@ -142,6 +202,7 @@ impl StringsInterner {
             }
 
             self.cache.remove(&index);
+            self.stats.evictions += 1;
         }
     }
 
@@ -168,6 +229,44 @@ impl StringsInterner {
         self.cache.clear();
         self.bloom_filter.clear();
     }
+
+    /// Get the statistics of this strings interner.
+    #[inline(always)]
+    #[must_use]
+    pub fn stats(&self) -> InternerStats {
+        InternerStats {
+            len: self.cache.len(),
+            ..self.stats
+        }
+    }
+}
+
+impl StrInterner for StringsInterner {
+    #[inline(always)]
+    fn intern(
+        &mut self,
+        category: u8,
+        text: &str,
+        mapper: &mut dyn FnMut(&str) -> ImmutableString,
+    ) -> ImmutableString {
+        self.get_with_mapper(category, mapper, text)
+    }
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.max()
+    }
+    #[inline(always)]
+    fn set_capacity(&mut self, max: usize) {
+        self.set_max(max);
+    }
+    #[inline(always)]
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+    #[inline(always)]
+    fn stats(&self) -> InternerStats {
+        Self::stats(self)
+    }
 }
 
 impl AddAssign<Self> for StringsInterner {
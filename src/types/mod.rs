@@ -6,27 +6,37 @@ pub mod dynamic;
 pub mod error;
 pub mod float;
 pub mod fn_ptr;
+pub mod handle;
 pub mod immutable_string;
 pub mod interner;
 pub mod parse_error;
 pub mod position;
 pub mod position_none;
+pub mod pretty;
+pub mod prototype;
 pub mod scope;
+pub mod stream_reader;
+pub mod task;
+pub mod undo;
 pub mod var_def;
 pub mod variant;
 
 pub use bloom_filter::BloomFilterU64;
 pub use custom_types::{CustomTypeInfo, CustomTypesCollection};
-pub use dynamic::Dynamic;
 #[cfg(not(feature = "no_time"))]
 pub use dynamic::Instant;
+pub use dynamic::{Dynamic, DynamicRef, DynamicRefMut};
 pub use error::EvalAltResult;
 #[cfg(not(feature = "no_float"))]
-pub use float::FloatWrapper;
+pub use float::{FloatFormat, FloatWrapper};
 pub use fn_ptr::FnPtr;
+pub use handle::{Handle, HandleRegistry};
 pub use immutable_string::ImmutableString;
-pub use interner::StringsInterner;
+pub use interner::{InternerStats, SharedStrInterner, StrInterner, StringsInterner};
 pub use parse_error::{LexError, ParseError, ParseErrorType};
+pub use pretty::PrettyPrintOptions;
+#[cfg(not(feature = "no_object"))]
+pub use prototype::Prototype;
 pub use var_def::VarDefInfo;
 
 #[cfg(not(feature = "no_position"))]
@@ -35,4 +45,10 @@ pub use position::{Position, Span};
 pub use position_none::{Position, Span};
 
 pub use scope::Scope;
+#[cfg(not(feature = "no_std"))]
+pub use stream_reader::StreamReader;
+#[cfg(feature = "parallel")]
+#[cfg(not(feature = "no_std"))]
+pub use task::{PendingTask, Task};
+pub use undo::UndoLog;
 pub use variant::Variant;
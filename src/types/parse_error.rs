@@ -181,6 +181,8 @@ pub enum ParseErrorType {
     ExprTooDeep,
     /// Number of scripted functions over maximum limit.
     TooManyFunctions,
+    /// Number of variables captured by a closure over maximum limit.
+    TooManyCaptures,
     /// Literal exceeding the maximum size. Wrapped values are the data type name and the maximum size.
     LiteralTooLarge(String, usize),
     /// Break statement not inside a loop.
@@ -251,6 +253,7 @@ impl fmt::Display for ParseErrorType {
             Self::WrongExport => f.write_str("Export statement can only appear at global level"),
             Self::ExprTooDeep => f.write_str("Expression exceeds maximum complexity"),
             Self::TooManyFunctions => f.write_str("Number of functions defined exceeds maximum limit"),
+            Self::TooManyCaptures => f.write_str("Number of variables captured by closure exceeds maximum limit"),
             Self::LoopBreak => f.write_str("Break statement should only be used inside a loop"),
 
             #[allow(deprecated)]
@@ -0,0 +1,310 @@
+//! Module defining the structured pretty-printer of [`Dynamic`] values.
+
+use super::dynamic::Union;
+use crate::tokenizer::is_valid_identifier;
+use crate::Dynamic;
+use std::fmt::Write;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Options for rendering a [`Dynamic`] value via [`Dynamic::to_pretty_string`].
+///
+/// # Example
+///
+/// ```
+/// use rhai::PrettyPrintOptions;
+///
+/// let options = PrettyPrintOptions::new().with_indent(4).with_max_items(10);
+///
+/// assert_eq!(options.indent, 4);
+/// assert_eq!(options.max_items, 10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PrettyPrintOptions {
+    /// Number of spaces per level of indentation. Default 2.
+    pub indent: usize,
+    /// Maximum nesting depth of arrays and object maps to render in full. Default 8.
+    ///
+    /// Deeper collections are rendered with their number of items only.
+    pub max_depth: usize,
+    /// Maximum number of items of an array, object map or BLOB to render. Default 32.
+    pub max_items: usize,
+    /// Maximum number of characters of a string to render. Default 256.
+    pub max_string_len: usize,
+    /// Annotate values of custom types with their type names? Default `true`.
+    pub type_annotations: bool,
+}
+
+impl Default for PrettyPrintOptions {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrettyPrintOptions {
+    /// Create a new [`PrettyPrintOptions`] with default values.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            indent: 2,
+            max_depth: 8,
+            max_items: 32,
+            max_string_len: 256,
+            type_annotations: true,
+        }
+    }
+    /// Set the number of spaces per level of indentation.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+    /// Set the maximum nesting depth of arrays and object maps to render in full.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+    /// Set the maximum number of items of an array, object map or BLOB to render.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = max_items;
+        self
+    }
+    /// Set the maximum number of characters of a string to render.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_max_string_len(mut self, max_string_len: usize) -> Self {
+        self.max_string_len = max_string_len;
+        self
+    }
+    /// Annotate values of custom types with their type names?
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_type_annotations(mut self, type_annotations: bool) -> Self {
+        self.type_annotations = type_annotations;
+        self
+    }
+}
+
+/// State of the rendering of a [`Dynamic`] value.
+struct PrettyPrinter<F: FnMut(&Dynamic) -> (String, String)> {
+    /// Rendering options.
+    options: PrettyPrintOptions,
+    /// Function returning the text and type name of a value of a custom type.
+    fmt_custom: F,
+    /// Shared values currently being rendered, for cycle detection.
+    #[cfg(not(feature = "no_closure"))]
+    parents: Vec<*const crate::Locked<Dynamic>>,
+    /// Output buffer.
+    buf: String,
+}
+
+impl<F: FnMut(&Dynamic) -> (String, String)> PrettyPrinter<F> {
+    /// Start a new line at an indentation level.
+    fn new_line(&mut self, level: usize) {
+        self.buf.push('\n');
+        self.buf
+            .extend(std::iter::repeat(' ').take(level * self.options.indent));
+    }
+    /// Render a value.
+    fn write_value(&mut self, value: &Dynamic, level: usize) {
+        match value.0 {
+            Union::Str(ref s, ..) => {
+                let len = s.chars().count();
+
+                if len > self.options.max_string_len {
+                    let s: String = s.chars().take(self.options.max_string_len).collect();
+                    write!(self.buf, "{s:?}... {len} chars").unwrap();
+                } else {
+                    write!(self.buf, "{s:?}").unwrap();
+                }
+            }
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(ref arr, ..) => {
+                self.write_collection(("[", "]"), arr.len(), arr.iter().map(|v| (None, v)), level);
+            }
+            #[cfg(not(feature = "no_index"))]
+            Union::Blob(ref blob, ..) => self.write_blob(blob),
+            #[cfg(not(feature = "no_index"))]
+            Union::SharedBlob(ref blob, ..) => self.write_blob(blob),
+            #[cfg(not(feature = "no_object"))]
+            Union::Map(ref map, ..) => {
+                // The prototype (if any) is not printed, just like with `Debug`
+                let items = crate::types::prototype::iter_properties(map)
+                    .map(|(k, v)| (Some(k.as_str()), v));
+                let len = crate::types::prototype::num_properties(map);
+                self.write_collection(("#{", "}"), len, items, level);
+            }
+            Union::Variant(..) => {
+                let (text, type_name) = (self.fmt_custom)(value);
+
+                if !self.options.type_annotations {
+                    self.buf.push_str(&text);
+                } else if text == type_name {
+                    write!(self.buf, "<{type_name}>").unwrap();
+                } else {
+                    write!(self.buf, "<{type_name}> {text}").unwrap();
+                }
+            }
+            #[cfg(not(feature = "no_closure"))]
+            Union::Shared(ref cell, ..) => {
+                let ptr = crate::Shared::as_ptr(cell);
+
+                // Check before locking, as the value may already be locked by a parent
+                if self.parents.contains(&ptr) {
+                    self.buf.push_str("<cycle>");
+                    return;
+                }
+
+                match crate::func::locked_read(cell) {
+                    Some(v) => {
+                        self.parents.push(ptr);
+                        self.write_value(&v, level);
+                        self.parents.pop();
+                    }
+                    None => self.buf.push_str("<shared>"),
+                }
+            }
+            _ => write!(self.buf, "{value:?}").unwrap(),
+        }
+    }
+    /// Render an array or object map, one item per line.
+    fn write_collection<'a>(
+        &mut self,
+        (open, close): (&str, &str),
+        len: usize,
+        items: impl Iterator<Item = (Option<&'a str>, &'a Dynamic)>,
+        level: usize,
+    ) {
+        self.buf.push_str(open);
+
+        if len == 0 {
+            self.buf.push_str(close);
+            return;
+        }
+        if level >= self.options.max_depth {
+            let s = if len == 1 { "" } else { "s" };
+            write!(self.buf, "... {len} item{s}{close}").unwrap();
+            return;
+        }
+
+        for (key, value) in items.take(self.options.max_items) {
+            self.new_line(level + 1);
+
+            match key {
+                Some(key) if is_valid_identifier(key) => self.buf.push_str(key),
+                Some(key) => write!(self.buf, "{key:?}").unwrap(),
+                None => (),
+            }
+            if key.is_some() {
+                self.buf.push_str(": ");
+            }
+
+            self.write_value(value, level + 1);
+            self.buf.push(',');
+        }
+
+        if len > self.options.max_items {
+            self.new_line(level + 1);
+            write!(self.buf, "... {} more", len - self.options.max_items).unwrap();
+        }
+
+        self.new_line(level);
+        self.buf.push_str(close);
+    }
+    /// Render a BLOB in hex.
+    #[cfg(not(feature = "no_index"))]
+    fn write_blob(&mut self, blob: &[u8]) {
+        self.buf.push('[');
+
+        for (i, byte) in blob.iter().take(self.options.max_items).enumerate() {
+            if i > 0 && i % 8 == 0 {
+                self.buf.push(' ');
+            }
+            write!(self.buf, "{byte:02x}").unwrap();
+        }
+
+        if blob.len() > self.options.max_items {
+            write!(
+                self.buf,
+                " ... {} more",
+                blob.len() - self.options.max_items
+            )
+            .unwrap();
+        }
+
+        self.buf.push(']');
+    }
+}
+
+impl Dynamic {
+    /// Render this value as a human-readable string, with nested arrays and object maps
+    /// indented one item per line.
+    ///
+    /// Unlike the debug format, the output is bounded by `options`:
+    ///
+    /// * collections nested deeper than [`max_depth`][PrettyPrintOptions::max_depth] are
+    ///   rendered with their number of items only,
+    ///
+    /// * only the first [`max_items`][PrettyPrintOptions::max_items] items of each array,
+    ///   object map or BLOB are rendered,
+    ///
+    /// * strings are truncated to [`max_string_len`][PrettyPrintOptions::max_string_len]
+    ///   characters.
+    ///
+    /// A shared value that (directly or indirectly) contains itself is rendered as `<cycle>` where
+    /// it recurs.
+    ///
+    /// Values of custom types are rendered in debug format and, if
+    /// [`type_annotations`][PrettyPrintOptions::type_annotations] is set, prefixed by their type
+    /// names within angle brackets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Engine, PrettyPrintOptions};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let value = engine.eval::<rhai::Dynamic>("#{ a: [1, 2, 3], b: \"hello\" }").unwrap();
+    ///
+    /// let options = PrettyPrintOptions::new().with_max_items(2);
+    ///
+    /// assert_eq!(
+    ///     value.to_pretty_string(options),
+    ///     "#{\n  a: [\n    1,\n    2,\n    ... 1 more\n  ],\n  b: \"hello\",\n}"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_pretty_string(&self, options: PrettyPrintOptions) -> String {
+        self.to_pretty_string_with(options, |value| {
+            let type_name = value.type_name();
+            (format!("{value:?}"), type_name.into())
+        })
+    }
+    /// Render this value as a human-readable string, with a custom function returning the text
+    /// and type name of a value of a custom type.
+    #[must_use]
+    pub(crate) fn to_pretty_string_with(
+        &self,
+        options: PrettyPrintOptions,
+        fmt_custom: impl FnMut(&Dynamic) -> (String, String),
+    ) -> String {
+        let mut printer = PrettyPrinter {
+            options,
+            fmt_custom,
+            #[cfg(not(feature = "no_closure"))]
+            parents: Vec::new(),
+            buf: String::new(),
+        };
+        printer.write_value(self, 0);
+        printer.buf
+    }
+}
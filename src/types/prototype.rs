@@ -0,0 +1,142 @@
+//! Module defining prototypes (method tables) for object maps.
+#![cfg(not(feature = "no_object"))]
+
+use crate::{Dynamic, Identifier, Map, Shared};
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+use std::{fmt, ops::Deref};
+
+/// Name of the property holding the [`Prototype`] of an object map.
+pub const PROP_PROTOTYPE: &str = "$prototype";
+
+/// Iterate through the properties of an object map, skipping the property holding its
+/// [`Prototype`] (if any).
+#[inline]
+pub fn iter_properties(map: &Map) -> impl Iterator<Item = (&Identifier, &Dynamic)> {
+    map.iter().filter(|(k, ..)| k.as_str() != PROP_PROTOTYPE)
+}
+
+/// Number of properties in an object map, not counting the property holding its [`Prototype`]
+/// (if any).
+#[inline]
+#[must_use]
+pub fn num_properties(map: &Map) -> usize {
+    map.len() - usize::from(map.contains_key(PROP_PROTOTYPE))
+}
+
+/// Maximum length of a chain of prototypes.
+const MAX_PROTOTYPE_DEPTH: usize = 32;
+
+/// A prototype, i.e. a method table, shared by object maps.
+///
+/// An object map with a prototype stores it under the property `$prototype`, which is hidden
+/// from the map's length, keys, values, comparisons and formatting (including JSON).
+///
+/// When a method is called on an object map that does not contain a property with the method's
+/// name, the method is looked up in the prototype instead (and then in the prototype's own
+/// prototype, if any). Methods are usually function pointers or closures which can access the
+/// object map via `this`.
+///
+/// A [`Prototype`] is immutable and cheap to clone, so the method table is not copied into each
+/// object map it is attached to.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+/// use rhai::Engine;
+///
+/// let engine = Engine::new();
+///
+/// let result = engine.eval::<String>(
+///     r#"
+///         const Animal = prototype(#{
+///             speak: || `${this.name} says ${this.sound}`
+///         });
+///
+///         let dog = #{ name: "Rex", sound: "woof" };
+///         dog.set_prototype(Animal);
+///
+///         dog.speak()
+///     "#,
+/// )?;
+///
+/// assert_eq!(result, "Rex says woof");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct Prototype(Shared<Map>);
+
+impl fmt::Debug for Prototype {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Prototype").field(&*self.0).finish()
+    }
+}
+
+impl Deref for Prototype {
+    type Target = Map;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Map> for Prototype {
+    #[inline(always)]
+    fn from(methods: Map) -> Self {
+        Self::new(methods)
+    }
+}
+
+impl Prototype {
+    /// Create a new [`Prototype`] from a method table.
+    #[inline(always)]
+    #[must_use]
+    pub fn new(methods: Map) -> Self {
+        Self(methods.into())
+    }
+    /// Get the method table of this [`Prototype`].
+    #[inline(always)]
+    #[must_use]
+    pub fn methods(&self) -> &Map {
+        &self.0
+    }
+    /// Get the [`Prototype`] of an object map, if any.
+    #[inline]
+    #[must_use]
+    pub fn of(map: &Map) -> Option<Self> {
+        map.get(PROP_PROTOTYPE)?
+            .read_lock::<Self>()
+            .map(|proto| proto.clone())
+    }
+    /// Attach this [`Prototype`] to an object map, replacing any existing prototype.
+    #[inline(always)]
+    pub fn attach_to(self, map: &mut Map) {
+        map.insert(PROP_PROTOTYPE.into(), Dynamic::from(self));
+    }
+    /// Remove the [`Prototype`] (if any) from an object map.
+    #[inline]
+    pub fn detach_from(map: &mut Map) -> Option<Self> {
+        map.remove(PROP_PROTOTYPE)?.try_cast::<Self>()
+    }
+    /// Look up a property in the chain of prototypes of an object map.
+    ///
+    /// Properties in the object map itself are not considered.
+    #[must_use]
+    pub fn find_inherited(map: &Map, name: &str) -> Option<Dynamic> {
+        let mut proto = Self::of(map)?;
+
+        for _ in 0..MAX_PROTOTYPE_DEPTH {
+            if let Some(value) = proto.get(name) {
+                return Some(value.clone());
+            }
+            proto = Self::of(&proto)?;
+        }
+
+        None
+    }
+}
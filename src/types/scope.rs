@@ -1,11 +1,14 @@
 //! Module that defines the [`Scope`] type representing a function call-stack scope.
 
 use super::dynamic::{AccessMode, Variant};
-use crate::{Dynamic, Identifier, ImmutableString, StaticVec, ThinVec};
+use crate::func::native::{locked_write, HostState, SendSync};
+use crate::{Dynamic, Identifier, ImmutableString, Locked, Shared, StaticVec, ThinVec};
 #[cfg(feature = "no_std")]
 use std::prelude::v1::*;
 use std::{
-    fmt, iter,
+    fmt,
+    hash::{Hash, Hasher},
+    iter,
     iter::{Extend, FromIterator},
     marker::PhantomData,
 };
@@ -13,6 +16,23 @@ use std::{
 /// Minimum number of entries in the [`Scope`] to avoid reallocations.
 pub const MIN_SCOPE_ENTRIES: usize = 8;
 
+/// Host state stored in a [`Scope`], shared among its copies.
+#[derive(Clone, Default)]
+struct HostStateSlot(Option<Shared<Locked<Box<HostState>>>>);
+
+impl fmt::Debug for HostStateSlot {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.is_some() { "Some(..)" } else { "None" })
+    }
+}
+
+impl Hash for HostStateSlot {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
 /// Type containing information about the current scope. Useful for keeping state between
 /// [`Engine`][crate::Engine] evaluation runs.
 ///
@@ -69,6 +89,9 @@ pub struct Scope<'a> {
     /// This `Vec` is not filled until needed because aliases are used rarely
     /// (only for `export` statements).
     aliases: ThinVec<StaticVec<ImmutableString>>,
+    /// Host state injected into native functions registered via
+    /// [`Engine::register_fn_with_state`][crate::Engine::register_fn_with_state].
+    host_state: HostStateSlot,
     /// Phantom to keep the lifetime parameter in order not to break existing code.
     dummy: PhantomData<&'a ()>,
 }
@@ -106,6 +129,7 @@ impl Clone for Scope<'_> {
                 .collect(),
             names: self.names.clone(),
             aliases: self.aliases.clone(),
+            host_state: self.host_state.clone(),
             dummy: self.dummy,
         }
     }
@@ -175,6 +199,7 @@ impl Scope<'_> {
             values: ThinVec::new(),
             names: ThinVec::new(),
             aliases: ThinVec::new(),
+            host_state: HostStateSlot(None),
             dummy: PhantomData,
         }
     }
@@ -197,6 +222,7 @@ impl Scope<'_> {
             values: ThinVec::with_capacity(capacity),
             names: ThinVec::with_capacity(capacity),
             aliases: ThinVec::new(),
+            host_state: HostStateSlot(None),
             dummy: PhantomData,
         }
     }
@@ -453,6 +479,56 @@ impl Scope<'_> {
         self.aliases.truncate(size);
         self
     }
+    /// Run a closure against a tentative copy of the [`Scope`], keeping all changes made by it only
+    /// if it succeeds.
+    ///
+    /// If the closure returns an error, the [`Scope`] is left untouched, so a script can be
+    /// evaluated speculatively (e.g. for what-if evaluation) and all its changes discarded on
+    /// failure.
+    ///
+    /// # Cost
+    ///
+    /// The tentative copy is a clone of the [`Scope`]. Strings are reference-counted and cheap to
+    /// clone, but arrays, object maps and custom types are copied.
+    ///
+    /// # Shared Values
+    ///
+    /// Changes made to _shared_ values (e.g. variables captured by closures) via the tentative copy
+    /// are visible in the original [`Scope`] and are not discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let engine = Engine::new();
+    /// let mut my_scope = Scope::new();
+    ///
+    /// my_scope.push("x", 42_i64);
+    ///
+    /// let result = my_scope.speculate(|scope| engine.run_with_scope(scope, "x = 0; let y = 1; throw 42;"));
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(my_scope.get_value::<i64>("x").expect("x should exist"), 42);
+    /// assert!(!my_scope.contains("y"));
+    ///
+    /// let result = my_scope.speculate(|scope| engine.run_with_scope(scope, "x = 0; let y = 1;"));
+    ///
+    /// assert!(result.is_ok());
+    /// assert_eq!(my_scope.get_value::<i64>("x").expect("x should exist"), 0);
+    /// assert!(my_scope.contains("y"));
+    /// ```
+    #[inline]
+    pub fn speculate<T, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, E>) -> Result<T, E> {
+        let mut tentative = self.clone();
+        let result = f(&mut tentative);
+
+        if result.is_ok() {
+            *self = tentative;
+        }
+
+        result
+    }
     /// Does the [`Scope`] contain the entry?
     ///
     /// # Example
@@ -856,6 +932,7 @@ impl Scope<'_> {
     pub fn clone_visible(&self) -> Self {
         let len = self.len();
         let mut scope = Self::new();
+        scope.host_state = self.host_state.clone();
 
         self.names.iter().rev().enumerate().for_each(|(i, name)| {
             if scope.names.contains(name) {
@@ -875,6 +952,69 @@ impl Scope<'_> {
 
         scope
     }
+    /// Set the host state injected into native functions registered via
+    /// [`Engine::register_fn_with_state`][crate::Engine::register_fn_with_state]
+    /// when this [`Scope`] is used for evaluation, replacing any existing host state.
+    ///
+    /// The host state is shared among copies of this [`Scope`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Scope};
+    ///
+    /// struct Counter(i64);
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn_with_state("bump", |c: &mut Counter, n: i64| {
+    ///     c.0 += n;
+    ///     c.0
+    /// });
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.set_host_state(Counter(40));
+    ///
+    /// assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "bump(1); bump(1)")?, 42);
+    /// assert_eq!(scope.with_host_state(|c: &mut Counter| c.0), Some(42));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_host_state<S: SendSync + 'static>(&mut self, state: S) -> &mut Self {
+        self.host_state = HostStateSlot(Some(Shared::new(Locked::new(Box::new(state)))));
+        self
+    }
+    /// Is there a host state in this [`Scope`]?
+    #[inline(always)]
+    #[must_use]
+    pub fn has_host_state(&self) -> bool {
+        self.host_state.0.is_some()
+    }
+    /// Run a closure with mutable access to the host state, if it is of type `S`.
+    ///
+    /// Returns [`None`] if there is no host state of type `S`, or if the host state is currently
+    /// being accessed.
+    #[inline]
+    pub fn with_host_state<S: 'static, R>(&self, f: impl FnOnce(&mut S) -> R) -> Option<R> {
+        let mut state = locked_write(self.host_state.0.as_ref()?)?;
+        state.downcast_mut::<S>().map(f)
+    }
+    /// Remove the host state from this [`Scope`].
+    ///
+    /// Copies of this [`Scope`] still share the host state.
+    #[inline(always)]
+    pub fn clear_host_state(&mut self) -> &mut Self {
+        self.host_state = HostStateSlot(None);
+        self
+    }
+    /// Get the shared host state, if any.
+    #[inline(always)]
+    #[must_use]
+    pub(crate) fn shared_host_state(&self) -> Option<&Shared<Locked<Box<HostState>>>> {
+        self.host_state.0.as_ref()
+    }
     /// Get an iterator to entries in the [`Scope`].
     /// Shared values are flatten-cloned.
     ///
@@ -0,0 +1,151 @@
+//! Host-provided readers for streaming record parsers.
+#![cfg(not(feature = "no_std"))]
+
+use crate::func::{locked_write, SendSync};
+use crate::{Locked, Position, RhaiResultOf, Shared, ERR};
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+
+/// Trait that maps to [`BufRead`] `+` [`SendSync`].
+pub trait StreamSource: BufRead + SendSync {}
+
+impl<T: BufRead + SendSync> StreamSource for T {}
+
+/// A shareable handle to a host-provided input stream.
+///
+/// Not available under `no_std`.
+///
+/// Scripts consume a [`StreamReader`] record-by-record via the `parse_json_stream` and
+/// `parse_csv_stream` functions, so that large inputs never need to be materialized in memory
+/// as a whole.
+///
+/// Cloning a [`StreamReader`] yields another handle to the _same_ underlying stream.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+/// # #[cfg(not(feature = "no_object"))]
+/// # {
+/// use rhai::{Engine, Scope, StreamReader};
+///
+/// let engine = Engine::new();
+/// let mut scope = Scope::new();
+///
+/// let input = "{\"a\": 1}\n{\"a\": 2}\n{\"a\": 39}\n";
+///
+/// scope.push("input", StreamReader::new(std::io::Cursor::new(input)));
+///
+/// let result = engine.eval_with_scope::<i64>(&mut scope, "
+///     let total = 0;
+///     input.parse_json_stream(|rec| total += rec.a);
+///     total
+/// ")?;
+///
+/// assert_eq!(result, 42);
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+#[cfg_attr(all(feature = "no_index", feature = "no_object"), allow(dead_code))]
+pub struct StreamReader(Shared<Locked<Box<dyn StreamSource>>>);
+
+impl fmt::Debug for StreamReader {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StreamReader")
+    }
+}
+
+#[cfg_attr(all(feature = "no_index", feature = "no_object"), allow(dead_code))]
+impl StreamReader {
+    /// Create a new [`StreamReader`] from any [`Read`] source.
+    ///
+    /// The source is wrapped in a [`BufReader`].
+    #[inline(always)]
+    #[must_use]
+    pub fn new(reader: impl Read + SendSync + 'static) -> Self {
+        Self::from_buf_read(BufReader::new(reader))
+    }
+    /// Create a new [`StreamReader`] from a [`BufRead`] source.
+    #[inline(always)]
+    #[must_use]
+    pub fn from_buf_read(reader: impl BufRead + SendSync + 'static) -> Self {
+        Self(Shared::new(Locked::new(Box::new(reader))))
+    }
+    /// Read the next line (without the line terminator) into a buffer.
+    ///
+    /// Returns `false` when the end of the stream is reached.
+    pub(crate) fn read_line(&self, buf: &mut String) -> RhaiResultOf<bool> {
+        let mut reader = locked_write(&self.0)
+            .ok_or_else(|| ERR::ErrorDataRace(String::new(), Position::NONE))?;
+
+        buf.clear();
+
+        let len = reader
+            .read_line(buf)
+            .map_err(|err| ERR::ErrorSystem("Cannot read from stream".to_string(), err.into()))?;
+
+        if buf.ends_with('\n') {
+            buf.pop();
+        }
+        if buf.ends_with('\r') {
+            buf.pop();
+        }
+
+        Ok(len > 0)
+    }
+    /// Read the next CSV record.
+    ///
+    /// Fields may be enclosed in double-quotes, in which case they may contain commas,
+    /// line-breaks and escaped (doubled) double-quotes.
+    ///
+    /// Returns [`None`] when the end of the stream is reached.
+    #[cfg(not(feature = "no_index"))]
+    pub(crate) fn read_csv_record(&self, buf: &mut String) -> RhaiResultOf<Option<Vec<String>>> {
+        if !self.read_line(buf)? {
+            return Ok(None);
+        }
+
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+
+        loop {
+            let mut chars = buf.chars().peekable();
+
+            while let Some(ch) = chars.next() {
+                match ch {
+                    '"' if in_quotes && chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => in_quotes = !in_quotes,
+                    ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+                    ch => field.push(ch),
+                }
+            }
+
+            if !in_quotes {
+                break;
+            }
+
+            // Quoted field continues onto the next line
+            if !self.read_line(buf)? {
+                return Err(ERR::ErrorSystem(
+                    "Cannot read from stream".to_string(),
+                    "unterminated quoted field in CSV record".into(),
+                )
+                .into());
+            }
+
+            field.push('\n');
+        }
+
+        fields.push(field);
+
+        Ok(Some(fields))
+    }
+}
@@ -0,0 +1,186 @@
+//! Module defining tasks for running script functions in parallel.
+#![cfg(feature = "parallel")]
+#![cfg(not(feature = "no_std"))]
+
+use crate::eval::GlobalRuntimeState;
+use crate::{
+    Dynamic, Engine, FnPtr, ImmutableString, NativeCallContext, Position, RhaiResult, Shared,
+    StaticVec, ERR,
+};
+use std::fmt;
+use std::sync::{Condvar, Mutex};
+
+/// Status of a [`Task`].
+enum TaskStatus {
+    /// The task has not yet completed.
+    Pending,
+    /// The task has completed with a result.
+    Done(RhaiResult),
+    /// The error result of the task has already been taken.
+    Joined,
+}
+
+/// Shared state of a [`Task`].
+struct TaskState {
+    /// Status of the task.
+    status: Mutex<TaskStatus>,
+    /// Signaled when the task completes.
+    done: Condvar,
+}
+
+impl TaskState {
+    /// Complete the task with a result.
+    fn complete(&self, result: RhaiResult) {
+        *self.status.lock().unwrap() = TaskStatus::Done(result);
+        self.done.notify_all();
+    }
+}
+
+/// A handle to a script function running as a parallel task, created by the `spawn` function.
+///
+/// Only available under `parallel`. Not available under `no_std`.
+///
+/// Call the `join` function in scripts (or [`Task::join`]) to wait for the result of the task.
+///
+/// Cloning a [`Task`] yields another handle to the _same_ task.
+#[derive(Clone)]
+pub struct Task(Shared<TaskState>);
+
+impl fmt::Debug for Task {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.is_done() {
+            "Task(done)"
+        } else {
+            "Task(pending)"
+        })
+    }
+}
+
+impl Task {
+    /// Create a new pending [`Task`].
+    #[must_use]
+    fn new() -> Self {
+        Self(Shared::new(TaskState {
+            status: Mutex::new(TaskStatus::Pending),
+            done: Condvar::new(),
+        }))
+    }
+    /// Has the task completed?
+    #[inline]
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        !matches!(*self.0.status.lock().unwrap(), TaskStatus::Pending)
+    }
+    /// Wait for the task to complete and return its result.
+    ///
+    /// The result value is cloned so the task can be joined again. An error result is returned
+    /// only once; joining the task again afterwards returns an error.
+    ///
+    /// # Deadlocks
+    ///
+    /// This call blocks forever if the task is never run by the
+    /// [task executor][Engine::set_task_executor].
+    pub fn join(&self) -> RhaiResult {
+        let mut status = self.0.status.lock().unwrap();
+
+        while matches!(*status, TaskStatus::Pending) {
+            status = self.0.done.wait(status).unwrap();
+        }
+
+        match std::mem::replace(&mut *status, TaskStatus::Joined) {
+            TaskStatus::Done(Ok(value)) => {
+                *status = TaskStatus::Done(Ok(value.clone()));
+                Ok(value)
+            }
+            TaskStatus::Done(Err(err)) => Err(err),
+            TaskStatus::Joined => {
+                Err(ERR::ErrorRuntime("task already joined".into(), Position::NONE).into())
+            }
+            TaskStatus::Pending => unreachable!("task is completed"),
+        }
+    }
+}
+
+/// A script function call waiting to be run by a [task executor][Engine::set_task_executor].
+///
+/// Only available under `parallel`. Not available under `no_std`.
+pub struct PendingTask {
+    /// The function to call.
+    fn_ptr: FnPtr,
+    /// Arguments to the function.
+    args: StaticVec<Dynamic>,
+    /// Script-defined functions available to the function.
+    #[cfg(not(feature = "no_function"))]
+    lib: crate::StaticVec<crate::SharedModule>,
+    /// Source of the spawning context, if any.
+    source: Option<ImmutableString>,
+    /// The task to complete.
+    task: Task,
+}
+
+impl fmt::Debug for PendingTask {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingTask")
+            .field("fn_ptr", &self.fn_ptr)
+            .field("args", &self.args)
+            .finish()
+    }
+}
+
+impl PendingTask {
+    /// Create a new [`PendingTask`] from the context of the spawning function call.
+    ///
+    /// The arguments are deep-cloned so that no values are shared with the spawning script.
+    pub(crate) fn new(
+        ctx: &NativeCallContext,
+        fn_ptr: FnPtr,
+        args: impl IntoIterator<Item = Dynamic>,
+    ) -> (Self, Task) {
+        let task = Task::new();
+        let global = ctx.global_runtime_state();
+
+        let pending = Self {
+            fn_ptr,
+            args: args.into_iter().map(Dynamic::flatten).collect(),
+            #[cfg(not(feature = "no_function"))]
+            lib: global.lib.iter().cloned().collect(),
+            source: global.source.clone(),
+            task: task.clone(),
+        };
+
+        (pending, task)
+    }
+    /// Get the name of the function to call.
+    #[inline(always)]
+    #[must_use]
+    pub fn fn_name(&self) -> &str {
+        self.fn_ptr.fn_name()
+    }
+    /// Run the task on an [`Engine`], completing the [`Task`] with the result.
+    ///
+    /// The [`Engine`] should be configured in the same way as the [`Engine`] running the
+    /// spawning script, otherwise functions called by the task may not be found.
+    pub fn run(self, engine: &Engine) {
+        let mut global: GlobalRuntimeState = engine.new_global_runtime_state();
+        #[cfg(not(feature = "no_function"))]
+        global.lib.extend(self.lib);
+        global.source = self.source;
+
+        let ctx = (
+            engine,
+            self.fn_ptr.fn_name(),
+            global.source.as_ref().map(|s| s.as_str()),
+            &global,
+            Position::NONE,
+        )
+            .into();
+
+        let result = self.fn_ptr.call_raw(&ctx, None, self.args);
+
+        self.task.0.complete(result);
+    }
+}
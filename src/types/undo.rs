@@ -0,0 +1,86 @@
+//! Module defining the undo log for transaction-style evaluation runs.
+
+use crate::func::{locked_read, locked_write, SendSync};
+use crate::{Locked, Position, RhaiResultOf, Shared, ERR};
+use std::fmt;
+#[cfg(feature = "no_std")]
+use std::prelude::v1::*;
+
+/// Undo action enlisted by a native function.
+#[cfg(not(feature = "sync"))]
+pub type UndoAction = dyn FnOnce();
+/// Undo action enlisted by a native function.
+#[cfg(feature = "sync")]
+pub type UndoAction = dyn FnOnce() + Send + Sync;
+
+/// _(internals)_ Log of undo actions enlisted during an evaluation run.
+/// Exported under the `internals` feature only.
+///
+/// Native functions that apply changes to host state can enlist an undo action via
+/// [`NativeCallContext::enlist_undo`][crate::NativeCallContext::enlist_undo].
+///
+/// When an error is thrown, the undo actions enlisted since the start of the enclosing `try`
+/// block (or, if the error escapes, since the start of the evaluation run) are run in reverse
+/// order. Undo actions still in the log when the evaluation run completes successfully are
+/// discarded without being run, i.e. the changes are committed.
+#[derive(Clone, Default)]
+pub struct UndoLog(Shared<Locked<Vec<Box<UndoAction>>>>);
+
+impl fmt::Debug for UndoLog {
+    #[cold]
+    #[inline(never)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UndoLog").field("len", &self.len()).finish()
+    }
+}
+
+impl UndoLog {
+    /// Create a new [`UndoLog`].
+    #[inline(always)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Number of undo actions in this log.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        locked_read(&self.0).map_or(0, |list| list.len())
+    }
+    /// Is this log empty?
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Add an undo action to this log.
+    pub fn enlist(&self, action: impl FnOnce() + SendSync + 'static) -> RhaiResultOf<()> {
+        locked_write(&self.0)
+            .ok_or_else(|| ERR::ErrorDataRace(String::new(), Position::NONE))?
+            .push(Box::new(action));
+        Ok(())
+    }
+    /// Discard all undo actions in this log without running them.
+    #[inline]
+    pub fn commit(&self) {
+        if let Some(mut list) = locked_write(&self.0) {
+            list.clear();
+        }
+    }
+    /// Run, in reverse order, all undo actions added after the log had `len` entries,
+    /// then remove them.
+    pub fn rollback_to(&self, len: usize) {
+        // Take the actions out first so that undo actions may access the log.
+        let actions = match locked_write(&self.0) {
+            Some(mut list) if list.len() > len => list.split_off(len),
+            _ => return,
+        };
+
+        actions.into_iter().rev().for_each(|action| action());
+    }
+    /// Run, in reverse order, all undo actions in this log, then remove them.
+    #[inline(always)]
+    pub fn rollback(&self) {
+        self.rollback_to(0);
+    }
+}
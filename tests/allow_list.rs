@@ -0,0 +1,78 @@
+use rhai::{Engine, EvalAltResult, INT};
+
+#[test]
+fn test_allow_list_build() {
+    let engine = Engine::new();
+
+    let ast = engine
+        .compile(
+            r#"
+                let x = [1, 2, 3];
+                x += 4;
+                let s = `${x.len()}`;
+                abs(-42) + x[0]
+            "#,
+        )
+        .unwrap();
+
+    let list = engine.build_fn_allow_list(&ast);
+
+    assert!(list.contains("len", 1));
+    assert!(list.contains("abs", 1));
+    assert!(list.contains("+", 2));
+    assert!(list.contains("+=", 2));
+    assert!(list.contains("to_string", 1));
+    assert!(!list.contains("print", 1));
+    assert!(!list.has_dynamic_calls());
+
+    #[cfg(not(feature = "no_function"))]
+    {
+        let ast = engine.compile("fn foo(x) { sign(x) } let f = Fn(\"foo\"); f.call(42) + foo(1)").unwrap();
+
+        let list = engine.build_fn_allow_list(&ast);
+
+        assert!(list.contains("sign", 1));
+        assert!(!list.contains("foo", 1));
+        assert_eq!(list.iter_dynamic_calls().map(|(name, ..)| name).collect::<Vec<_>>(), ["call"]);
+    }
+
+    let ast = engine.compile("unknown(42)").unwrap();
+    assert_eq!(engine.build_fn_allow_list(&ast).iter_dynamic_calls().map(|(name, ..)| name).collect::<Vec<_>>(), ["unknown"]);
+}
+
+#[test]
+fn test_allow_list_enforce() {
+    let mut engine = Engine::new();
+
+    let ast = engine.compile("let x = abs(-40); x += 2; x").unwrap();
+
+    engine.set_fn_allow_list(engine.build_fn_allow_list(&ast));
+
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 42);
+    assert!(matches!(*engine.eval::<INT>("sign(-42)").unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+
+    // Functions registered after the list is built are not allowed
+    engine.register_fn("abs", |x: INT| x * 2);
+    assert!(matches!(*engine.eval_ast::<INT>(&ast).unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+
+    engine.clear_fn_allow_list();
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), -78);
+}
+
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_allow_list_dynamic() {
+    let mut engine = Engine::new();
+
+    let ast = engine.compile(r#"let f = Fn("sign"); f.call(-42)"#).unwrap();
+    let mut list = engine.build_fn_allow_list(&ast);
+
+    assert!(list.has_dynamic_calls());
+
+    engine.set_fn_allow_list(list.clone());
+    assert!(engine.eval_ast::<INT>(&ast).is_err());
+
+    assert!(list.allow(&engine, "sign", 1));
+    engine.set_fn_allow_list(list);
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), -1);
+}
@@ -499,6 +499,10 @@ fn test_arrays_elvis() {
     engine.eval::<()>("let x = (); x?[2]").unwrap();
 
     engine.run("let x = (); x?[2] = 42").unwrap();
+
+    engine.eval::<()>("let x = (); x?[2][3]").unwrap();
+    engine.eval::<()>("let x = [1, ()]; x[1]?[2]?[3]").unwrap();
+    assert_eq!(engine.eval::<INT>("let x = [[1, 2]]; x?[0]?[1]").unwrap(), 2);
 }
 
 #[test]
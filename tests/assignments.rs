@@ -34,3 +34,45 @@ fn test_assignments_bad_lhs() {
         assert_eq!(*engine.compile("x[y].z.foo() = 42;").unwrap_err().err_type(), ParseErrorType::AssignmentToInvalidLHS(String::new()));
     }
 }
+
+#[test]
+fn test_assignments_expressions() {
+    let mut engine = Engine::new();
+
+    assert!(engine.compile("let x = 0; let y = 0; x = y = 42;").is_err());
+    assert!(engine.compile("let x = 0; let y = (x = 42);").is_err());
+    assert!(engine.compile("let x = 0; let y = abs(x = -5);").is_err());
+
+    engine.set_allow_assignment_expressions(true);
+
+    assert_eq!(engine.eval::<INT>("let x = 0; let y = 0; x = y = 21; x + y").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("let x = 1; let y = (x += 41); x * 100 + y").unwrap(), 4242);
+    assert_eq!(engine.eval::<INT>("let x = 0; let y = x = 42; y").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("let x = 0; if (x = 2) > 1 { x * 21 } else { 0 }").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("let x = 0; let y = abs(x = -5); x * 10 + y").unwrap(), -45);
+    assert_eq!(engine.eval::<INT>("fn add(a, b) { a + b } let x = 1; add(x += 1, x = 40)").unwrap(), 42);
+
+    // Assignment statements still return `()`
+    assert!(engine.eval::<()>("let x = 0; x = 42").is_ok());
+
+    #[cfg(not(feature = "no_index"))]
+    assert_eq!(
+        engine
+            .eval::<INT>(
+                "
+                    let items = [1, 2, 3];
+                    let sum = 0;
+                    let item;
+                    while (item = items.shift()) != () { sum += item; }
+                    sum
+                "
+            )
+            .unwrap(),
+        6
+    );
+
+    #[cfg(not(feature = "no_index"))]
+    assert!(matches!(engine.compile("let x = [0]; let y = (x[0] = 42);").unwrap_err().err_type(), ParseErrorType::AssignmentToInvalidLHS(..)));
+
+    assert!(matches!(engine.compile("const x = 0; let y = (x = 42);").unwrap_err().err_type(), ParseErrorType::AssignmentToConstant(..)));
+}
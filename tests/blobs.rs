@@ -114,3 +114,29 @@ fn test_blobs_write_string() {
     assert_eq!(engine.eval::<Blob>(r#"let x = blob(10, 0); write_utf8(x, 3..9, "❤❤❤❤"); x"#).unwrap(), "\0\0\0\u{2764}\u{2764}\0".as_bytes());
     assert_eq!(engine.eval::<Blob>(r#"let x = blob(10, 0); write_utf8(x, 3..7, "❤❤❤❤"); x"#).unwrap(), vec![0, 0, 0, 226, 157, 164, 226, 0, 0, 0]);
 }
+
+#[test]
+fn test_blobs_shared() {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    let data = rhai::Dynamic::from_blob_slice(&[1, 2, 3, 4, 5]);
+    let bytes = data.clone_cast::<rhai::SharedBlob>();
+    scope.push("data", data);
+
+    assert_eq!(engine.eval_with_scope::<String>(&mut scope, "type_of(data)").unwrap(), "shared_blob");
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "data.len").unwrap(), 5);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "data[1] + data[-1]").unwrap(), 7);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "let sum = 0; for x in data { sum += x } sum").unwrap(), 15);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "3 in data").unwrap());
+    assert_eq!(engine.eval_with_scope::<Blob>(&mut scope, "data.extract(1..3)").unwrap(), [2, 3]);
+    assert_eq!(engine.eval_with_scope::<Blob>(&mut scope, "let b = data.to_blob(); b[0] = 42; b").unwrap(), [42, 2, 3, 4, 5]);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "blob(5, 1).to_shared() == blob(5, 1).to_shared()").unwrap());
+    assert!(engine.run_with_scope(&mut scope, "data[0] = 42;").is_err());
+
+    // Bytes are not copied when passed around in scripts
+    let result = engine.eval_with_scope::<rhai::SharedBlob>(&mut scope, "let x = data; x").unwrap();
+    assert!(rhai::Shared::ptr_eq(&result, &bytes));
+    assert_eq!(*result, [1, 2, 3, 4, 5]);
+    assert_eq!(rhai::Dynamic::from(result).into_blob().unwrap(), [1, 2, 3, 4, 5]);
+}
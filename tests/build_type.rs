@@ -221,6 +221,88 @@ fn test_build_type_macro() {
     );
 }
 
+#[test]
+fn test_build_type_with_fields() {
+    #[derive(Debug, Clone)]
+    struct Order {
+        id: INT,
+        qty: INT,
+        note: String,
+    }
+
+    impl CustomType for Order {
+        fn build(mut builder: TypeBuilder<Self>) {
+            builder.with_name("Order").with_fn("new_order", |id: INT| Order { id, qty: 0, note: String::new() });
+
+            rhai::with_fields!(builder,
+                /// Order ID.
+                "id" => (|o: &mut Self| o.id),
+                /// Quantity ordered.
+                "qty" => (|o: &mut Self| o.qty, |o: &mut Self, v: INT| o.qty = v),
+                "note" => (|o: &mut Self| o.note.clone(), |o: &mut Self, v: String| o.note = v),
+            );
+        }
+    }
+
+    let mut engine = Engine::new();
+    engine.build_type::<Order>();
+
+    assert_eq!(engine.eval::<INT>("let o = new_order(42); o.qty = 2; o.id + o.qty").unwrap(), 44);
+    assert_eq!(engine.eval::<String>(r#"let o = new_order(1); o.note = "hello"; o.note"#).unwrap(), "hello");
+    assert!(matches!(*engine.run("let o = new_order(1); o.id = 2;").unwrap_err(), EvalAltResult::ErrorDotExpr(..)));
+
+    #[cfg(feature = "metadata")]
+    {
+        let json = engine.gen_fn_metadata_to_json(false).unwrap();
+        assert!(json.contains("/// Order ID."));
+        assert!(json.contains("/// Quantity ordered."));
+    }
+}
+
+#[cfg(not(feature = "no_module"))]
+#[test]
+fn test_build_type_statics() {
+    #[derive(Debug, Clone)]
+    struct Color(INT);
+
+    impl CustomType for Color {
+        fn build(mut builder: TypeBuilder<Self>) {
+            builder
+                .with_name("Color")
+                .with_constant("MAX", 255 as INT)
+                .with_static_fn("parse", |s: &str| Color(s.parse().unwrap_or(0)))
+                .with_static_fn("black", || Color(0))
+                .with_fn("value", |c: &mut Color| c.0);
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct Shade;
+
+    impl CustomType for Shade {
+        fn build(mut builder: TypeBuilder<Self>) {
+            builder.with_constant("LEVELS", 16 as INT);
+        }
+    }
+
+    let mut engine = Engine::new();
+    engine.build_type::<Color>().build_type::<Shade>();
+
+    assert_eq!(engine.eval::<INT>("Color::MAX").unwrap(), 255);
+    assert_eq!(engine.eval::<INT>(r#"Color::parse("42").value()"#).unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("Color::black().value()").unwrap(), 0);
+    assert_eq!(engine.eval::<INT>("Shade::LEVELS").unwrap(), 16);
+    assert!(engine.eval::<INT>(r#"parse("42").value()"#).is_err());
+
+    #[cfg(feature = "metadata")]
+    #[cfg(feature = "internals")]
+    {
+        let defs = engine.definitions().single_file();
+        assert!(defs.contains("module Color"));
+        assert!(defs.contains("fn parse("));
+    }
+}
+
 #[test]
 fn test_build_type_operators() {
     #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -248,3 +330,122 @@ fn test_build_type_operators() {
     assert!(!engine.eval::<bool>("let a = new_xyz(1); let b = new_xyz(2); a > b").unwrap());
     assert!(!engine.eval::<bool>("let a = new_xyz(1); let b = new_xyz(2); a >= b").unwrap());
 }
+
+#[test]
+fn test_build_type_missing_property() {
+    #[derive(Debug, Clone)]
+    struct Proxy {
+        prefix: String,
+        known: INT,
+    }
+
+    impl CustomType for Proxy {
+        fn build(mut builder: TypeBuilder<Self>) {
+            builder
+                .with_name("Proxy")
+                .with_get_set("known", |p: &mut Self| p.known, |p: &mut Self, v: INT| p.known = v)
+                .on_missing_property(|p, prop| match prop {
+                    "unknown" => Err(EvalAltResult::ErrorPropertyNotFound(prop.into(), Position::NONE).into()),
+                    _ => Ok(format!("{}{prop}", p.prefix).into()),
+                });
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct Plain;
+
+    let mut engine = Engine::new();
+    engine.build_type::<Proxy>();
+    engine.register_type::<Plain>();
+    engine.register_fn("proxy", || Proxy { prefix: "proxy:".into(), known: 42 });
+    engine.register_fn("plain", || Plain);
+
+    assert_eq!(engine.eval::<String>("proxy().foo").unwrap(), "proxy:foo");
+    assert_eq!(engine.eval::<INT>("let p = proxy(); p.known += 1; p.known").unwrap(), 43);
+    assert_eq!(engine.eval::<String>("let x = #{ p: proxy() }; x.p.bar").unwrap(), "proxy:bar");
+    assert_eq!(engine.eval::<INT>("proxy().bar.len()").unwrap(), 9);
+
+    let err = engine.eval::<String>("proxy().unknown").unwrap_err();
+    assert!(matches!(*err, EvalAltResult::ErrorPropertyNotFound(ref p, ..) if p == "unknown"));
+
+    // Other types are not affected
+    assert!(engine.eval::<String>("plain().foo").is_err());
+    assert!(engine.eval::<()>("let x = #{ a: 1 }; x.foo").is_ok());
+}
+
+#[test]
+fn test_build_type_builder_fn() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, Default)]
+    struct Config {
+        name: String,
+        items: Vec<INT>,
+    }
+
+    impl Clone for Config {
+        fn clone(&self) -> Self {
+            CLONES.fetch_add(1, Ordering::SeqCst);
+            Self { name: self.name.clone(), items: self.items.clone() }
+        }
+    }
+
+    impl Config {
+        fn with_name(mut self, name: &str) -> Self {
+            self.name = name.into();
+            self
+        }
+        fn with_item(mut self, item: INT) -> Self {
+            self.items.push(item);
+            self
+        }
+    }
+
+    impl CustomType for Config {
+        fn build(mut builder: TypeBuilder<Self>) {
+            builder
+                .with_name("Config")
+                .with_fn("new_config", Self::default)
+                .with_fn("len", |cfg: &mut Self| cfg.items.len() as INT)
+                .with_get("name", |cfg: &mut Self| cfg.name.clone());
+            builder.with_builder_fn("with_name", Self::with_name).unwrap();
+            builder.with_builder_fn("with_item", Self::with_item).unwrap();
+
+            assert!(builder.with_builder_fn("with name", Self::with_name).is_err());
+            assert!(builder.with_builder_fn("with_nothing", |_: INT| Self::default()).is_err());
+            assert!(builder.with_builder_fn("with_clone", |cfg: &mut Self| cfg.clone()).is_err());
+        }
+    }
+
+    let mut engine = Engine::new();
+    engine.build_type::<Config>();
+
+    let cfg = engine.eval::<Config>(r#"new_config().with_name("x").with_item(1).with_item(2)"#).unwrap();
+    assert_eq!(cfg.name, "x");
+    assert_eq!(cfg.items, [1, 2]);
+
+    CLONES.store(0, Ordering::SeqCst);
+    assert_eq!(
+        engine
+            .eval::<INT>(
+                r#"
+                    let cfg = new_config();
+                    cfg.with_name("y").with_item(1);
+                    for i in 0..100 { cfg.with_item(i); }
+                    cfg.len()
+                "#
+            )
+            .unwrap(),
+        101
+    );
+    assert_eq!(CLONES.load(Ordering::SeqCst), 0);
+
+    assert_eq!(engine.eval::<String>(r#"let cfg = new_config(); cfg.with_name("z"); cfg.name"#).unwrap(), "z");
+    assert_eq!(engine.eval::<()>(r#"let cfg = new_config(); cfg.with_name("z")"#).unwrap(), ());
+    assert_eq!(engine.eval::<String>(r#"let cfg = new_config(); with_name(cfg, "z"); cfg.name"#).unwrap(), "z");
+    assert_eq!(engine.eval::<INT>("let x = #{ cfg: new_config() }; x.cfg.with_item(1).with_item(2); x.cfg.len()").unwrap(), 2);
+
+    assert!(matches!(*engine.run(r#"const cfg = new_config(); cfg.with_name("z");"#).unwrap_err(), EvalAltResult::ErrorNonPureMethodCallOnConstant(..)));
+}
@@ -87,6 +87,42 @@ fn test_call_fn_scope() {
     assert_eq!(scope.len(), 100);
 }
 
+#[test]
+fn test_call_fn_named_args() {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    let ast = engine
+        .compile(
+            "
+                fn foo(a, b, c) { `${a}/${b}/${c}` }
+                fn foo(a, b, c, d) { `${a}/${b}/${c}/${d}` }
+                fn bar(x) { this += x; }
+            ",
+        )
+        .unwrap();
+
+    let options = CallFnOptions::new().with_named_arg("c", 3 as INT).with_named_arg("b", 2 as INT);
+    assert_eq!(engine.call_fn_with_options::<String>(options, &mut scope, &ast, "foo", (1 as INT,)).unwrap(), "1/2/3");
+
+    let options = CallFnOptions::new().with_named_arg("d", 4 as INT).with_default_arg("b", 0 as INT);
+    assert_eq!(engine.call_fn_with_options::<String>(options, &mut scope, &ast, "foo", (1 as INT,)).unwrap(), "1/0//4");
+
+    let options = CallFnOptions::new().with_named_arg("a", 1 as INT).with_named_arg("a", 42 as INT);
+    assert_eq!(engine.call_fn_with_options::<String>(options, &mut scope, &ast, "foo", ()).unwrap(), "42//");
+
+    let options = CallFnOptions::new().with_named_arg("a", 0 as INT);
+    assert!(matches!(*engine.call_fn_with_options::<String>(options, &mut scope, &ast, "foo", (1 as INT,)).unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+
+    let options = CallFnOptions::new().with_named_arg("z", 0 as INT);
+    assert!(matches!(*engine.call_fn_with_options::<String>(options, &mut scope, &ast, "foo", ()).unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+
+    let mut value = Dynamic::from(40 as INT);
+    let options = CallFnOptions::new().bind_this_ptr(&mut value).with_named_arg("x", 2 as INT);
+    engine.call_fn_with_options::<()>(options, &mut scope, &ast, "bar", ()).unwrap();
+    assert_eq!(value.as_int().unwrap(), 42);
+}
+
 struct Options {
     pub foo: bool,
     pub bar: String,
@@ -123,6 +159,54 @@ fn test_call_fn_args() {
     assert_eq!(result, "world42");
 }
 
+#[derive(FuncArgs)]
+struct DerivedOptions {
+    bar: String,
+    baz: INT,
+    scale: Option<INT>,
+    flag: Option<bool>,
+}
+
+#[test]
+fn test_call_fn_args_derive() {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    let ast = engine
+        .compile(
+            "
+                fn hello(y, z) { y + z }
+                fn hello(y, z, s) { y + z * s }
+                fn hello(y, z, s, f) { if f { `${y}!` } else { y } }
+            ",
+        )
+        .unwrap();
+
+    let options = DerivedOptions {
+        bar: "world".to_string(),
+        baz: 42,
+        scale: None,
+        flag: Some(true),
+    };
+    assert_eq!(engine.call_fn::<String>(&mut scope, &ast, "hello", options).unwrap(), "world42");
+
+    let options = DerivedOptions {
+        bar: "world".to_string(),
+        baz: 21,
+        scale: Some(2),
+        flag: None,
+    };
+    assert_eq!(engine.call_fn::<String>(&mut scope, &ast, "hello", options).unwrap(), "world42");
+
+    let options = DerivedOptions {
+        bar: "world".to_string(),
+        baz: 21,
+        scale: Some(2),
+        flag: Some(true),
+    };
+    assert_eq!(engine.call_fn::<String>(&mut scope, &ast, "hello", options).unwrap(), "world!");
+}
+
 #[test]
 fn test_call_fn_private() {
     let engine = Engine::new();
@@ -280,6 +280,47 @@ fn test_closures_sharing() {
     }
 }
 
+#[test]
+#[cfg(not(feature = "no_closure"))]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "unchecked"))]
+fn test_closures_limits() {
+    let mut engine = Engine::new();
+
+    engine.set_max_closure_captures(2);
+
+    engine.compile("let a = 1; let b = 2; let f = || a + b;").unwrap();
+
+    assert!(matches!(engine.compile("let a = 1; let b = 2; let c = 3; let f = || a + b + c;").unwrap_err().err_type(), ParseErrorType::TooManyCaptures));
+
+    engine.set_max_shared_values(3);
+
+    assert_eq!(
+        engine
+            .eval::<INT>(
+                "
+                    let fs = [];
+                    for i in 0..3 { let x = i; fs.push(|| x); }
+                    fs.len()
+                "
+            )
+            .unwrap(),
+        3
+    );
+
+    assert!(matches!(
+        *engine
+            .run(
+                "
+                    let fs = [];
+                    for i in 0..10 { let x = i; fs.push(|| x); }
+                "
+            )
+            .unwrap_err(),
+        EvalAltResult::ErrorDataTooLarge(..)
+    ));
+}
+
 #[test]
 #[cfg(not(feature = "no_closure"))]
 #[cfg(not(feature = "no_object"))]
@@ -317,6 +358,44 @@ fn test_closures_data_race() {
     ));
 }
 
+#[test]
+#[cfg(not(feature = "no_object"))]
+fn test_closures_reentrant_lock() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("apply", |context: rhai::NativeCallContext, _: &mut INT, f: FnPtr| f.call_within_context::<INT>(&context, ()));
+
+    assert_eq!(
+        engine
+            .eval::<INT>(
+                "
+                    let x = 1;
+                    let y = 41;
+                    let foo = || y + 1;
+                    x.apply(foo)
+                "
+            )
+            .unwrap(),
+        42
+    );
+
+    // `x` is locked by `apply` when the closure accesses it
+    // (under `sync`, held locks are only tracked in debug builds)
+    #[cfg(any(not(feature = "sync"), debug_assertions))]
+    assert!(matches!(
+        *engine
+            .eval::<INT>(
+                "
+                    let x = 1;
+                    let foo = || x + 1;
+                    x.apply(foo)
+                "
+            )
+            .unwrap_err(),
+        EvalAltResult::ErrorReentrantLock(ref name, ..) if name == "x"
+    ));
+}
+
 type TestStruct = Rc<RefCell<INT>>;
 
 #[test]
@@ -363,6 +442,44 @@ fn test_closures_shared_obj() {
     assert_eq!(*p1.borrow(), 42);
 }
 
+#[test]
+#[cfg(not(feature = "no_closure"))]
+#[cfg(not(feature = "no_index"))]
+fn test_closures_shared_borrow() {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    engine
+        .run_with_scope(
+            &mut scope,
+            "
+                let x = [1, 2, 3];
+                let f = || x.push(42);
+                f.call();
+            ",
+        )
+        .unwrap();
+
+    let x = scope.get("x").unwrap();
+    assert!(x.is_shared());
+
+    {
+        let array = x.try_cast_ref::<rhai::Array>().unwrap();
+        assert_eq!(array.len(), 4);
+        assert_eq!(array[3].as_int().unwrap(), 42);
+    }
+    assert!(x.try_cast_ref::<INT>().is_none());
+
+    #[cfg(not(feature = "sync"))]
+    {
+        let _guard = x.try_cast_ref::<rhai::Array>().unwrap();
+        assert!(x.clone().try_cast_mut::<rhai::Array>().is_none());
+    }
+
+    x.clone().try_cast_mut::<rhai::Array>().unwrap().clear();
+    assert_eq!(scope.get_value::<rhai::Array>("x").unwrap().len(), 0);
+}
+
 #[test]
 #[cfg(not(feature = "no_closure"))]
 fn test_closures_external() {
@@ -0,0 +1,20 @@
+#![cfg(feature = "icu")]
+use rhai::{Engine, INT};
+
+#[test]
+fn test_collate() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>(r#"compare("a", "B", "en")"#).unwrap(), -1);
+    assert_eq!(engine.eval::<INT>(r#"compare("B", "a", "en")"#).unwrap(), 1);
+    assert_eq!(engine.eval::<INT>(r#"compare("abc", "abc", "en")"#).unwrap(), 0);
+    assert_eq!(engine.eval::<INT>(r#"compare("ä", "z", "de")"#).unwrap(), -1);
+    assert_eq!(engine.eval::<INT>(r#"compare("ä", "z", "sv")"#).unwrap(), 1);
+    assert!(engine.eval::<INT>(r#"compare("a", "b", "not a locale!")"#).is_err());
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        assert_eq!(engine.eval::<String>(r#"let x = ["zebra", "Äpfel", "apple", "Zoo"]; x.sort("de"); x.to_string()"#).unwrap(), r#"["Äpfel", "apple", "zebra", "Zoo"]"#);
+        assert!(engine.eval::<()>(r#"let x = ["a", 1]; x.sort("en")"#).is_err());
+    }
+}
@@ -120,3 +120,34 @@ fn test_constant_mut() {
 
     assert!(matches!(*engine.run_with_scope(&mut scope, "MY_NUMBER.value = 42;").unwrap_err(), EvalAltResult::ErrorNonPureMethodCallOnConstant(..)));
 }
+
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_constant_freeze() {
+    let engine = Engine::new();
+
+    assert!(engine.eval::<bool>("let x = [1, 2, 3]; freeze(x); is_frozen(x)").unwrap());
+    assert!(engine.eval::<bool>("let x = freeze([1, 2, 3]); let y = x; is_frozen(y)").unwrap());
+    assert_eq!(engine.eval::<INT>("let x = freeze(#{ a: [1, 2, 3] }); x.a[1] + x.a.len()").unwrap(), 5);
+
+    assert!(matches!(*engine.run("let x = freeze([1, 2, 3]); x[1] = 42;").expect_err("expects error"), EvalAltResult::ErrorAssignmentToConstant(..)));
+    assert!(matches!(*engine.run("let x = freeze([1, 2, 3]); x.push(42);").expect_err("expects error"), EvalAltResult::ErrorNonPureMethodCallOnConstant(..)));
+    assert!(matches!(*engine.run("let x = freeze(#{ a: #{ b: 1 } }); let y = x.a; y.b = 42;").expect_err("expects error"), EvalAltResult::ErrorAssignmentToConstant(..)));
+    assert!(matches!(
+        *engine.run("let x = freeze(#{ a: 1 }); x.b = 42;").expect_err("expects error"),
+        EvalAltResult::ErrorAssignmentToConstant(x, ..) if x == "b"
+    ));
+    assert!(matches!(
+        *engine.run("fn f(m) { m.a[0] += 1; } f(freeze(#{ a: [1] }));").expect_err("expects error"),
+        EvalAltResult::ErrorInFunctionCall(.., err, _) if matches!(*err, EvalAltResult::ErrorAssignmentToConstant(..))
+    ));
+
+    let config = engine.eval::<rhai::Dynamic>("freeze(#{ a: 1 })").unwrap();
+    assert!(config.is_frozen());
+    assert!(config.clone().is_frozen());
+
+    let mut scope = Scope::new();
+    scope.push_dynamic("config", config);
+    assert!(matches!(*engine.run_with_scope(&mut scope, "config.a = 42").expect_err("expects error"), EvalAltResult::ErrorAssignmentToConstant(..)));
+}
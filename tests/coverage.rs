@@ -0,0 +1,53 @@
+#![cfg(not(feature = "no_position"))]
+use rhai::{Engine, Position};
+
+#[test]
+fn test_coverage() {
+    let mut engine = Engine::new();
+
+    assert!(engine.coverage().is_none());
+
+    engine.set_collect_coverage(true);
+
+    let mut ast = engine
+        .compile(
+            "
+                let x = 0;
+                for i in 0..3 {
+                    x += i;
+                }
+                if x > 100 {
+                    x = 0;
+                }
+            ",
+        )
+        .unwrap();
+    ast.set_source("test");
+
+    engine.run_ast(&ast).unwrap();
+
+    let mut coverage = engine.coverage().unwrap();
+    assert_eq!(coverage.hit_count(Some("test"), Position::new(2, 17)), Some(1));
+    assert_eq!(coverage.hit_count(Some("test"), Position::new(4, 23)), Some(3));
+    assert_eq!(coverage.hit_count(Some("test"), Position::new(7, 21)), None);
+
+    coverage.add_ast(&ast);
+    assert_eq!(coverage.hit_count(Some("test"), Position::new(7, 21)), Some(0));
+
+    let lines = coverage.line_hits(Some("test"));
+    assert_eq!(lines.get(&4), Some(&3));
+    assert_eq!(lines.get(&7), Some(&0));
+
+    let lcov = coverage.to_lcov();
+    assert!(lcov.starts_with("TN:\nSF:test\n"));
+    assert!(lcov.contains("DA:4,3\n"));
+    assert!(lcov.contains("DA:7,0\n"));
+    assert!(lcov.contains("LH:4\nLF:5\nend_of_record\n"));
+
+    engine.clear_coverage();
+    assert!(engine.coverage().unwrap().is_empty());
+
+    engine.set_collect_coverage(false);
+    engine.run_ast(&ast).unwrap();
+    assert!(engine.coverage().is_none());
+}
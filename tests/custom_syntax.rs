@@ -272,6 +272,61 @@ fn test_custom_syntax_scope() {
 }
 
 #[cfg(not(feature = "no_function"))]
+#[test]
+fn test_custom_syntax_declared_vars() {
+    let mut engine = Engine::new();
+
+    engine
+        .register_custom_syntax(["def", "$var$", "=", "$expr$"], false, |context, inputs| {
+            let var_name = inputs[0].get_string_value().unwrap().to_string();
+            let value = context.eval_expression_tree(&inputs[1])?;
+            context.scope_mut().push(var_name, value);
+            Ok(Dynamic::UNIT)
+        })
+        .unwrap();
+    engine.register_custom_syntax(["decl", "$var$"], false, |_, _| Ok(Dynamic::UNIT)).unwrap();
+
+    assert_eq!(engine.eval::<INT>("let x = 1; def y = x + 41; y").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("def x = 40; let y = 2; x + y").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("let x = 40; def x = x + 2; x").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("const x = 1; def x = 42; x").unwrap(), 42);
+    assert!(engine.eval::<bool>(r#"def x = 42; is_def_var("x")"#).unwrap());
+    assert!(engine.eval::<bool>("decl x; x == ()").unwrap());
+
+    engine.set_strict_variables(true);
+
+    assert_eq!(engine.eval::<INT>("def x = 42; x").unwrap(), 42);
+    assert!(matches!(engine.compile("def x = y;").unwrap_err().err_type(), ParseErrorType::VariableUndefined(v) if v == "y"));
+}
+
+#[test]
+fn test_custom_syntax_typed() {
+    let mut engine = Engine::new();
+
+    engine
+        .register_custom_syntax(["twice", "$expr:int$"], false, |context, inputs| {
+            let x = context.eval_expression_tree(&inputs[0])?.as_int().map_err(|typ| format!("expecting int, not {typ}"))?;
+            Ok(Dynamic::from_int(x * 2))
+        })
+        .unwrap();
+    engine
+        .register_custom_syntax(["quietly", "$block:()$"], false, |context, inputs| context.eval_expression_tree(&inputs[0]))
+        .unwrap();
+
+    assert_eq!(engine.eval::<INT>("twice 21").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("let x = 21; twice x").unwrap(), 42);
+    assert!(matches!(engine.compile(r#"twice "hello""#).unwrap_err().err_type(), ParseErrorType::MismatchedType(r, a) if r == "int" && a == "string"));
+
+    assert_eq!(engine.eval::<INT>("let x = 41; quietly { x += 1; } x").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("quietly {} 42").unwrap(), 42);
+    assert!(matches!(engine.compile("quietly { 42 }").unwrap_err().err_type(), ParseErrorType::MismatchedType(r, a) if r == "()" && a == "int"));
+
+    assert!(matches!(
+        engine.register_custom_syntax(["bad", "$expr:foo$"], false, |_, _| Ok(Dynamic::UNIT)).unwrap_err().err_type(),
+        ParseErrorType::BadInput(LexError::ImproperSymbol(s, ..)) if s == "$expr:foo$"
+    ));
+}
+
 #[test]
 fn test_custom_syntax_func() {
     let mut engine = Engine::new();
@@ -497,3 +552,23 @@ fn test_custom_syntax_raw_sql() {
 
     assert_eq!(engine.eval_with_scope::<String>(&mut scope, "SELECT * FROM table WHERE id = ${id}").unwrap(), "SELECT * FROM table WHERE id = ?\n123");
 }
+
+#[test]
+#[cfg(feature = "metadata")]
+#[cfg(feature = "internals")]
+fn test_custom_syntax_definitions() {
+    let mut engine = Engine::new();
+
+    engine.register_custom_operator("foo", 160).unwrap();
+    engine.register_custom_syntax(["exec", "[", "$ident$", "]", "->", "$block$"], true, |_, _| Ok(Dynamic::UNIT)).unwrap();
+    engine.register_custom_syntax_with_state_raw("hello", |_, _, _| Ok(None), false, |_, _, _| Ok(Dynamic::UNIT));
+
+    assert_eq!(
+        engine.definitions().custom_syntax(),
+        "/// Custom operator with precedence 160.\nop foo(?, ?) -> ?;\n\n/// Custom syntax: `exec [ $ident$ ] -> $block$`\nsyntax exec;\n\n/// Custom syntax.\nsyntax hello;"
+    );
+    assert!(engine.definitions().iter_files().any(|(name, ..)| name == "__custom_syntax__.d.rhai"));
+    assert!(engine.definitions().single_file().contains("syntax exec;"));
+
+    assert!(!Engine::new().definitions().iter_files().any(|(name, ..)| name == "__custom_syntax__.d.rhai"));
+}
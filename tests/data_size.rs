@@ -336,3 +336,39 @@ fn test_max_map_size() {
         4
     );
 }
+
+#[test]
+fn test_max_custom_data_size() {
+    #[derive(Clone)]
+    struct Buffer(Vec<u8>);
+
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<Buffer>("Buffer")
+        .register_fn("buffer", |size: INT| Buffer(vec![0; size as usize]))
+        .register_size_of(|buf: &Buffer| buf.0.len());
+
+    engine.run("let x = buffer(2000);").unwrap();
+
+    engine.set_max_custom_data_size(1000);
+
+    engine.run("let x = buffer(1000);").unwrap();
+    assert!(matches!(*engine.run("let x = buffer(1001);").unwrap_err(), EvalAltResult::ErrorDataTooLarge(..)));
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        engine.run("let x = [buffer(400), buffer(400)];").unwrap();
+        assert!(matches!(*engine.run("let x = [buffer(400), buffer(400)]; x.push(buffer(400));").unwrap_err(), EvalAltResult::ErrorDataTooLarge(..)));
+    }
+
+    #[cfg(not(feature = "no_object"))]
+    {
+        engine.run("let x = #{ a: buffer(400), b: buffer(400) };").unwrap();
+        assert!(matches!(*engine.run("let x = #{ a: buffer(400), b: buffer(400) }; x.c = buffer(400);").unwrap_err(), EvalAltResult::ErrorDataTooLarge(..)));
+    }
+
+    engine.set_max_custom_data_size(0);
+
+    engine.run("let x = buffer(2000);").unwrap();
+}
@@ -71,3 +71,188 @@ fn test_debugger_state() {
 
     engine.run("let x = 42;").unwrap();
 }
+
+#[test]
+#[cfg(not(feature = "no_position"))]
+fn test_debugger_inline_values() {
+    use std::sync::{Arc, RwLock};
+
+    let mut engine = Engine::new();
+    let values = Arc::new(RwLock::new(Vec::new()));
+    let values2 = values.clone();
+
+    engine.register_debugger(
+        |_, dbg| dbg,
+        move |context, _, node, _, pos| {
+            if pos.line() == Some(4) && node.is_stmt() {
+                *values2.write().unwrap() = context.inline_values(node).iter().map(|v| v.to_string()).collect::<Vec<_>>();
+            }
+            Ok(rhai::debugger::DebuggerCommand::StepInto)
+        },
+    );
+
+    engine
+        .run(
+            "
+                let x = 40;
+                let y = 2;
+                let z = x + y * x;
+                let w = z;
+            ",
+        )
+        .unwrap();
+
+    assert_eq!(*values.read().unwrap(), ["x = 40", "y = 2", "x = 40"]);
+}
+
+#[test]
+#[cfg(not(feature = "no_function"))]
+fn test_debugger_conditional_break_point_and_watch() {
+    use rhai::debugger::{BreakPoint, DebuggerCommand, DebuggerEvent};
+    use std::sync::{Arc, RwLock};
+
+    let mut engine = Engine::new();
+    let hits = Arc::new(RwLock::new(Vec::new()));
+    let hits2 = hits.clone();
+
+    engine.register_debugger(
+        |_, mut dbg| {
+            dbg.break_points_mut().push(BreakPoint::Conditional {
+                break_point: BreakPoint::AtFunctionName { name: "foo".into(), enabled: true }.into(),
+                condition: "i % 3 == 0".into(),
+            });
+            dbg.add_watch("i * 10");
+            dbg
+        },
+        move |context, event, _, _, _| {
+            if let DebuggerEvent::BreakPoint(..) = event {
+                let watch = &context.global_runtime_state().debugger().watches()[0];
+                hits2.write().unwrap().push(watch.value.clone().unwrap().unwrap().as_int().unwrap());
+            }
+            Ok(DebuggerCommand::Continue)
+        },
+    );
+
+    assert_eq!(
+        engine
+            .eval::<INT>(
+                "
+                    fn foo(x) { x + 1 }
+                    let sum = 0;
+                    for i in 0..10 { sum += foo(i); }
+                    sum
+                ",
+            )
+            .unwrap(),
+        55
+    );
+
+    assert_eq!(*hits.read().unwrap(), [0, 30, 60, 90]);
+}
+
+#[test]
+#[cfg(not(feature = "no_function"))]
+#[cfg(not(feature = "no_position"))]
+fn test_debugger_step_out() {
+    use rhai::debugger::DebuggerCommand;
+    use std::sync::{Arc, RwLock};
+
+    let mut engine = Engine::new();
+    let lines = Arc::new(RwLock::new(Vec::new()));
+    let lines2 = lines.clone();
+
+    engine.register_debugger(
+        |_, dbg| dbg,
+        move |_, _, node, _, pos| {
+            if node.is_stmt() {
+                lines2.write().unwrap().push(pos.line().unwrap());
+            }
+            match pos.line() {
+                Some(3) => Ok(DebuggerCommand::StepOut),
+                _ => Ok(DebuggerCommand::StepInto),
+            }
+        },
+    );
+
+    engine
+        .run(
+            "
+                fn foo(x) {
+                    let y = x + 1;
+                    let z = y * 2;
+                    z
+                }
+                let a = foo(1);
+                let b = a;
+            ",
+        )
+        .unwrap();
+
+    let lines = lines.read().unwrap();
+    assert!(lines.contains(&3));
+    assert!(!lines.contains(&4));
+    assert!(lines.contains(&8));
+}
+
+#[test]
+#[cfg(feature = "dap")]
+#[cfg(not(feature = "no_position"))]
+fn test_debugger_dap() {
+    use rhai::debugger::DapTransport;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, RwLock};
+
+    struct Transport(VecDeque<&'static str>, Arc<RwLock<Vec<serde_json::Value>>>);
+
+    impl DapTransport for Transport {
+        fn read_message(&mut self) -> std::io::Result<Option<String>> {
+            Ok(self.0.pop_front().map(Into::into))
+        }
+        fn write_message(&mut self, message: &str) -> std::io::Result<()> {
+            self.1.write().unwrap().push(serde_json::from_str(message).unwrap());
+            Ok(())
+        }
+    }
+
+    let output = Arc::new(RwLock::new(Vec::new()));
+
+    let requests = [
+        r#"{"seq":1,"type":"request","command":"initialize","arguments":{}}"#,
+        r#"{"seq":2,"type":"request","command":"launch","arguments":{}}"#,
+        r#"{"seq":3,"type":"request","command":"setBreakpoints","arguments":{"source":{"path":"test.rhai"},"breakpoints":[{"line":3}]}}"#,
+        r#"{"seq":4,"type":"request","command":"configurationDone"}"#,
+        r#"{"seq":5,"type":"request","command":"stackTrace","arguments":{"threadId":1}}"#,
+        r#"{"seq":6,"type":"request","command":"variables","arguments":{"variablesReference":1}}"#,
+        r#"{"seq":7,"type":"request","command":"evaluate","arguments":{"expression":"x * 2"}}"#,
+        r#"{"seq":8,"type":"request","command":"continue","arguments":{"threadId":1}}"#,
+    ];
+
+    let mut engine = Engine::new();
+    engine.register_dap_server(Transport(requests.into(), output.clone()));
+
+    let mut ast = engine
+        .compile(
+            "
+                let x = 21;
+                let y = x + 1;
+                x + y
+            ",
+        )
+        .unwrap();
+    ast.set_source("test.rhai");
+
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 43);
+
+    let output = output.read().unwrap();
+    let response = |seq: i64| output.iter().find(|m| m["request_seq"] == seq).unwrap();
+    let has_event = |name: &str| output.iter().any(|m| m["event"] == name);
+
+    assert!(has_event("initialized"));
+    assert!(has_event("stopped"));
+    assert!(has_event("terminated"));
+    assert_eq!(response(3)["body"]["breakpoints"][0]["verified"], true);
+    assert_eq!(response(5)["body"]["stackFrames"][0]["line"], 3);
+    assert_eq!(response(6)["body"]["variables"][0]["name"], "x");
+    assert_eq!(response(6)["body"]["variables"][0]["value"], "21");
+    assert_eq!(response(7)["body"]["result"], "42");
+}
@@ -0,0 +1,60 @@
+#![cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+use rhai::{Engine, EvalAltResult, ParseErrorType, Scope, INT};
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_destructuring_array() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("let [a, b] = [1, 2]; a + b").unwrap(), 3);
+    assert_eq!(engine.eval::<INT>("let [a, _, c] = [1, 2, 3]; a + c").unwrap(), 4);
+    assert_eq!(engine.eval::<INT>("let [a, ..rest] = [1, 2, 3, 4]; a + len(rest)").unwrap(), 4);
+    assert_eq!(engine.eval::<INT>("let [a, b, ..] = [1, 2, 3, 4]; a + b").unwrap(), 3);
+    assert_eq!(engine.eval::<INT>("let [a, [b, c]] = [1, [2, 3]]; a + b + c").unwrap(), 6);
+    assert_eq!(engine.eval::<INT>("let x = [1, 2]; let [a, b] = x; a = 42; x[0] + a").unwrap(), 43);
+    assert_eq!(engine.eval::<INT>("let a = 1; { let [a] = [2]; } a").unwrap(), 1);
+
+    assert!(matches!(*engine.run("let [a, b] = [1];").unwrap_err(), EvalAltResult::ErrorMismatchDataType(..)));
+    assert!(matches!(*engine.run("let [a, b] = [1, 2, 3];").unwrap_err(), EvalAltResult::ErrorMismatchDataType(..)));
+    assert!(matches!(*engine.run("let [a] = 42;").unwrap_err(), EvalAltResult::ErrorMismatchDataType(..)));
+
+    assert!(matches!(engine.compile("const [a, b] = [1, 2]; a = 3;").unwrap_err().err_type(), ParseErrorType::AssignmentToConstant(..)));
+    assert!(matches!(engine.compile("let [a, a] = [1, 2];").unwrap_err().err_type(), ParseErrorType::DuplicatedVariable(..)));
+    assert!(matches!(engine.compile("let [..a, b] = [1, 2];").unwrap_err().err_type(), ParseErrorType::MissingToken(..)));
+    assert!(matches!(engine.compile("let [a, b];").unwrap_err().err_type(), ParseErrorType::MissingToken(..)));
+}
+
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_destructuring_map() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("let #{x, y} = #{x: 1, y: 2}; x + y").unwrap(), 3);
+    assert_eq!(engine.eval::<INT>("let #{x, y, ..} = #{x: 1, y: 2, z: 3}; x + y").unwrap(), 3);
+    assert_eq!(engine.eval::<INT>(r#"let #{x: a, "y z": b} = #{x: 1, "y z": 2}; a + b"#).unwrap(), 3);
+    assert_eq!(engine.eval::<INT>("let #{x: 1, y, ..} = #{x: 1, y: 2, z: 3}; y").unwrap(), 2);
+    assert!(matches!(*engine.run("let #{x, y} = #{x: 1};").unwrap_err(), EvalAltResult::ErrorMismatchDataType(..)));
+    assert!(matches!(*engine.run("let #{x, y} = #{x: 1, y: 2, z: 3};").unwrap_err(), EvalAltResult::ErrorMismatchDataType(..)));
+    assert!(matches!(*engine.run("let #{x: 1, y, ..} = #{x: 2, y: 2};").unwrap_err(), EvalAltResult::ErrorMismatchDataType(..)));
+
+    assert!(matches!(engine.compile("let #{x, .., y} = #{x: 1, y: 2};").unwrap_err().err_type(), ParseErrorType::MissingToken(..)));
+
+    #[cfg(not(feature = "no_index"))]
+    assert_eq!(engine.eval::<INT>("let #{pos: [x, y], name} = #{pos: [1, 2], name: 3}; x + y + name").unwrap(), 6);
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_destructuring_scope() {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    engine.run_with_scope(&mut scope, "let [a, b] = [1, 2];").unwrap();
+
+    assert_eq!(scope.get_value::<INT>("a").unwrap(), 1);
+    assert_eq!(scope.get_value::<INT>("b").unwrap(), 2);
+
+    // A failed match leaves no partial bindings
+    assert!(engine.run_with_scope(&mut scope, "let [c, d] = [1];").is_err());
+    assert!(!scope.contains("c"));
+}
@@ -1,4 +1,4 @@
-use rhai::{Engine, LexError, ParseErrorType, Scope, INT};
+use rhai::{Engine, EvalAltResult, LexError, ParseErrorType, Scope, INT};
 
 #[test]
 fn test_eval() {
@@ -165,3 +165,56 @@ fn test_eval_disabled() {
         ParseErrorType::BadInput(LexError::ImproperSymbol(err, ..)) if err == "eval"
     ));
 }
+
+#[test]
+fn test_eval_full() {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.push("x", 40 as INT).push("y", "hello").push("z", 0 as INT);
+
+    let output = engine.eval_full_with_scope(
+        &mut scope,
+        r#"
+            print(y);
+            x += 2;
+            y = "hello";
+            let w = x;
+            debug(w);
+            x
+        "#,
+    );
+
+    assert_eq!(output.result.unwrap().as_int().unwrap(), 42);
+    assert_eq!(output.prints, ["hello"]);
+    assert_eq!(output.debug_messages.len(), 1);
+    assert_eq!(output.debug_messages[0].0, "42");
+    assert_eq!(output.debug_messages[0].1.line(), Some(6));
+    assert!(output.num_operations > 0);
+    assert_eq!(output.modified_variables, ["x", "w"]);
+
+    let output = engine.eval_full(r#"print("before"); let x = 1; x.foo(); print("after")"#);
+    assert!(matches!(*output.result.unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+    assert_eq!(output.prints, ["before"]);
+    assert!(output.num_operations > 0);
+    assert_eq!(output.modified_variables, ["x"]);
+
+    let output = engine.eval_full("let x = ;");
+    assert!(matches!(*output.result.unwrap_err(), EvalAltResult::ErrorParsing(..)));
+    assert!(output.prints.is_empty());
+
+    #[cfg(not(feature = "no_function"))]
+    {
+        let output = engine.eval_full(r#"fn foo() { print("inner"); 1 } print("outer"); foo() + eval("print(42); 1")"#);
+        assert_eq!(output.result.unwrap().as_int().unwrap(), 2);
+        assert_eq!(output.prints, ["outer", "inner", "42"]);
+    }
+
+    #[cfg(not(feature = "no_index"))]
+    #[cfg(not(feature = "no_function"))]
+    {
+        let output = engine.eval_full(r#"print("a"); [1, 2].map(|x| { print(x); x * 2 }); print(9)"#);
+        assert!(output.result.unwrap().is_unit());
+        assert_eq!(output.prints, ["a", "1", "2", "9"]);
+    }
+}
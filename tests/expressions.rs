@@ -108,3 +108,77 @@ fn test_expressions_eval() {
         )
         .unwrap());
 }
+
+#[test]
+fn test_expressions_to_fn() {
+    let engine = Engine::new();
+
+    let calc = engine.compile_expression_to_fn::<(INT, INT), INT>(&["x", "y"], "x * 10 + y").unwrap();
+
+    for i in 0..100 {
+        assert_eq!(calc(i, 2).unwrap(), i * 10 + 2);
+    }
+
+    let greet = engine.compile_expression_to_fn::<(String,), String>(&["name"], r#"`hello, ${name}!`"#).unwrap();
+    assert_eq!(greet("world".into()).unwrap(), "hello, world!");
+
+    let answer = engine.compile_expression_to_fn::<(), INT>(&[], "40 + 2").unwrap();
+    assert_eq!(answer().unwrap(), 42);
+
+    assert!(engine.compile_expression_to_fn::<(INT,), bool>(&["x"], "x + 1").unwrap()(1).is_err());
+    assert!(engine.compile_expression_to_fn::<(INT,), INT>(&["x"], "let y = x; y").is_err());
+}
+
+#[test]
+#[cfg(feature = "internals")]
+#[cfg(not(feature = "no_function"))]
+fn test_expressions_visitor() {
+    use rhai::visit::{walk_expr, walk_expr_mut, Visitor, VisitorMut};
+    use rhai::{Expr, Position};
+
+    struct CountCalls(Vec<String>);
+
+    impl Visitor for CountCalls {
+        fn visit_expr(&mut self, expr: &Expr) -> bool {
+            match expr {
+                Expr::FnCall(f, ..) if f.op_token.is_none() => self.0.push(f.name.to_string()),
+                #[cfg(not(feature = "no_position"))]
+                Expr::IntegerConstant(..) => assert!(!expr.position().is_none()),
+                _ => (),
+            }
+            walk_expr(self, expr)
+        }
+    }
+
+    struct Rewrite;
+
+    impl VisitorMut for Rewrite {
+        fn visit_expr_mut(&mut self, expr: &mut Expr) {
+            match expr {
+                Expr::FnCall(f, ..) if f.name == "foo" => f.name = "bar".into(),
+                Expr::IntegerConstant(1, ..) => *expr = Expr::IntegerConstant(2, Position::NONE),
+                _ => (),
+            }
+            walk_expr_mut(self, expr);
+        }
+    }
+
+    let engine = Engine::new();
+
+    let mut ast = engine.compile("fn foo(x) { x + 1 } fn bar(x) { x * 10 } fn baz(x) { foo(x) - 1 } baz(foo(4))").unwrap();
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 5);
+
+    let mut calls = CountCalls(Vec::new());
+    assert!(ast.visit(&mut calls));
+    calls.0.sort();
+    assert_eq!(calls.0, ["baz", "foo", "foo"]);
+
+    ast.visit_mut(&mut Rewrite);
+
+    let mut calls = CountCalls(Vec::new());
+    assert!(ast.visit(&mut calls));
+    calls.0.sort();
+    assert_eq!(calls.0, ["bar", "bar", "baz"]);
+
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 398);
+}
@@ -1,5 +1,5 @@
 #![cfg(not(feature = "no_float"))]
-use rhai::{Engine, FLOAT};
+use rhai::{Engine, FloatFormat, FLOAT};
 
 const EPSILON: FLOAT = 0.000_000_000_1;
 
@@ -31,6 +31,39 @@ fn test_float_parse() {
     assert!((engine.eval::<FLOAT>(r#"parse_float("9.9999")"#).unwrap() - 9.9999 as FLOAT).abs() < EPSILON);
 }
 
+#[test]
+fn test_float_format() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("to_string(1.0 / 3.0)").unwrap(), (1.0 as FLOAT / 3.0).to_string());
+    assert_eq!(engine.eval::<String>("to_string(1e20)").unwrap(), "1e20");
+    assert!(engine.eval::<FLOAT>(r#"parse_float("1_000.5")"#).is_err());
+    assert!(engine.eval::<FLOAT>(r#"parse_float("inf")"#).unwrap().is_infinite());
+
+    engine.set_float_format(
+        FloatFormat::new()
+            .with_significant_digits(3)
+            .with_scientific_thresholds(0.01, 1e6)
+            .with_separators(true)
+            .with_non_finite(false),
+    );
+
+    assert_eq!(engine.eval::<String>("to_string(1.0 / 3.0)").unwrap(), "0.333");
+    assert_eq!(engine.eval::<String>("`${2.0 / 3.0}`").unwrap(), "0.667");
+    assert_eq!(engine.eval::<String>("to_string(123456.0)").unwrap(), "123000.0");
+    assert_eq!(engine.eval::<String>("to_string(1234567.0)").unwrap(), "1.23e6");
+    assert_eq!(engine.eval::<String>("to_string(0.001234)").unwrap(), "1.23e-3");
+    assert_eq!(engine.eval::<String>("to_string(0.0)").unwrap(), "0.0");
+    assert_eq!(engine.eval::<String>("to_debug(1.0 / 3.0)").unwrap(), format!("{:?}", 1.0 as FLOAT / 3.0));
+
+    assert_eq!(engine.eval::<FLOAT>(r#"parse_float("1_000.5")"#).unwrap(), 1000.5);
+    assert_eq!(engine.eval::<FLOAT>(r#"parse_float(" -2_5e1_0 ")"#).unwrap(), -25e10);
+    assert!(engine.eval::<FLOAT>(r#"parse_float("1__000.5")"#).is_err());
+    assert!(engine.eval::<FLOAT>(r#"parse_float("_1000.5")"#).is_err());
+    assert!(engine.eval::<FLOAT>(r#"parse_float("inf")"#).is_err());
+    assert!(engine.eval::<FLOAT>(r#"parse_float("NaN")"#).is_err());
+}
+
 #[test]
 #[cfg(not(feature = "no_object"))]
 fn test_struct_with_float() {
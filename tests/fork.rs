@@ -0,0 +1,80 @@
+use rhai::{Engine, INT};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_fork() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("add", |x: INT, y: INT| x + y);
+    engine.set_allow_looping(false);
+
+    let mut fork = engine.fork();
+
+    // Registrations on the fork do not leak into the original
+    fork.register_fn("double", |x: INT| x * 2);
+
+    assert_eq!(fork.eval::<INT>("double(add(20, 1))").unwrap(), 42);
+    assert!(engine.eval::<INT>("double(add(20, 1))").is_err());
+
+    // Registrations on the original do not leak into the fork
+    engine.register_fn("triple", |x: INT| x * 3);
+
+    assert_eq!(engine.eval::<INT>("triple(add(10, 4))").unwrap(), 42);
+    assert!(fork.eval::<INT>("triple(add(10, 4))").is_err());
+
+    // Settings are copied
+    assert!(!fork.allow_looping());
+}
+
+#[test]
+fn test_fork_callbacks() {
+    let log = Arc::new(Mutex::new(Vec::<String>::new()));
+    let logger = log.clone();
+
+    let mut engine = Engine::new();
+
+    engine.on_print(move |s| logger.lock().unwrap().push(s.to_string()));
+
+    let fork = engine.fork();
+
+    fork.run(r#"print("hello")"#).unwrap();
+
+    assert_eq!(*log.lock().unwrap(), vec!["hello"]);
+}
+
+#[test]
+fn test_fork_services() {
+    #[derive(Debug, PartialEq)]
+    struct Config(INT);
+
+    let mut engine = Engine::new();
+    engine.set_service(Config(1));
+
+    let mut fork = engine.fork();
+
+    // Services are shared with the fork
+    assert_eq!(fork.service::<Config>(), Some(&Config(1)));
+
+    // Shared services cannot be modified or taken
+    assert!(engine.service_mut::<Config>().is_none());
+    assert!(fork.service_mut::<Config>().is_none());
+
+    // Replacing a service on the fork does not affect the original
+    fork.set_service(Config(2));
+    fork.service_mut::<Config>().unwrap().0 += 40;
+
+    assert_eq!(fork.service::<Config>(), Some(&Config(42)));
+    assert_eq!(engine.service::<Config>(), Some(&Config(1)));
+
+    // Services are no longer shared once the fork is dropped
+    let fork = engine.fork();
+    assert!(engine.service_mut::<Config>().is_none());
+    drop(fork);
+    engine.service_mut::<Config>().unwrap().0 = 9;
+
+    // Removing a shared service only removes it from one engine
+    let mut fork = engine.fork();
+    assert_eq!(fork.remove_service::<Config>(), None);
+    assert!(fork.service::<Config>().is_none());
+    assert_eq!(engine.remove_service::<Config>(), Some(Config(9)));
+}
@@ -1,5 +1,5 @@
 #![cfg(not(feature = "no_function"))]
-use rhai::{Dynamic, Engine, EvalAltResult, FuncRegistration, Module, NativeCallContext, ParseErrorType, Shared, INT};
+use rhai::{Dynamic, Engine, EvalAltResult, FuncRegistration, Module, NativeCallContext, ParseErrorType, Scope, Shared, INT};
 
 #[test]
 fn test_functions() {
@@ -433,6 +433,54 @@ fn test_functions_params() {
         ParseErrorType::FnDuplicatedParam(a, b) if a == "hello" && b == "x"));
 }
 
+#[test]
+fn test_functions_default_params() {
+    let engine = Engine::new();
+
+    let script = "
+        fn add(x, y = x * 2, z = 1) { x + y + z }
+    ";
+
+    assert_eq!(engine.eval::<INT>(&format!("{script} add(1)")).unwrap(), 4);
+    assert_eq!(engine.eval::<INT>(&format!("{script} add(1, 40)")).unwrap(), 42);
+    assert_eq!(engine.eval::<INT>(&format!("{script} add(1, 2, 3)")).unwrap(), 6);
+    assert!(matches!(*engine.run(&format!("{script} add()")).unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+    assert!(matches!(*engine.run(&format!("{script} add(1, 2, 3, 4)")).unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        let script = "
+            fn join(sep, ...items) {
+                let s = \"\";
+                for (x, i) in items { if i > 0 { s += sep; } s += x; }
+                s
+            }
+        ";
+
+        assert_eq!(engine.eval::<String>(&format!("{script} join(\"-\")")).unwrap(), "");
+        assert_eq!(engine.eval::<String>(&format!("{script} join(\"-\", 1)")).unwrap(), "1");
+        assert_eq!(engine.eval::<String>(&format!("{script} join(\"-\", 1, 2, 3)")).unwrap(), "1-2-3");
+        assert_eq!(engine.eval::<INT>("fn count(...x) { x.len() } count(1, 2, 3, 4, 5)").unwrap(), 5);
+    }
+
+    assert!(engine.eval::<bool>(&format!("{script} is_def_fn(\"add\", 1)")).unwrap());
+    assert!(engine.eval::<bool>(&format!("{script} is_def_fn(\"add\", 3)")).unwrap());
+    assert!(!engine.eval::<bool>(&format!("{script} is_def_fn(\"add\", 0)")).unwrap());
+    assert!(!engine.eval::<bool>(&format!("{script} is_def_fn(\"add\", 4)")).unwrap());
+    #[cfg(not(feature = "no_index"))]
+    assert!(engine.eval::<bool>("fn count(...x) { x.len() } is_def_fn(\"count\", 0) && is_def_fn(\"count\", 9)").unwrap());
+
+    // Exact number of parameters takes precedence
+    assert_eq!(engine.eval::<INT>("fn foo(x) { 1 } fn foo(x, y = 0) { 2 } foo(0) + foo(0, 0) * 10").unwrap(), 21);
+
+    // Default values must be trailing
+    assert!(matches!(engine.compile("fn foo(x = 1, y) { x }").unwrap_err().err_type(), ParseErrorType::MissingSymbol(..)));
+
+    // Rest parameter must be last
+    #[cfg(not(feature = "no_index"))]
+    assert!(matches!(engine.compile("fn foo(...x, y) { x }").unwrap_err().err_type(), ParseErrorType::MissingToken(..)));
+}
+
 #[test]
 fn test_function_pointers() {
     let engine = Engine::new();
@@ -574,3 +622,235 @@ fn test_functions_max() {
         ParseErrorType::TooManyFunctions
     ))
 }
+
+#[test]
+fn test_functions_prelude() {
+    let mut engine = Engine::new();
+
+    let prelude = engine
+        .compile(
+            "
+                const SCALE = 10;
+                let temp = 999;
+                fn scale(x) { x * SCALE }
+                fn greet() { `hello ${name()}` }
+                fn name() { \"prelude\" }
+            ",
+        )
+        .unwrap();
+
+    engine.set_prelude(&prelude).unwrap();
+    assert!(engine.has_prelude());
+
+    assert_eq!(engine.eval::<INT>("scale(4) + 2").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("fn foo() { SCALE + 1 } foo()").unwrap(), 11);
+    assert_eq!(engine.eval::<String>("greet()").unwrap(), "hello prelude");
+
+    // Scripts can override prelude functions
+    assert_eq!(engine.eval::<String>("fn name() { \"script\" } greet()").unwrap(), "hello script");
+
+    // Only constants are kept
+    assert!(engine.eval::<INT>("temp").is_err());
+
+    // Constants are recognized under Strict Variables mode
+    engine.set_strict_variables(true);
+    let ast = engine.compile("scale(SCALE)").unwrap();
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 100);
+    assert!(engine.compile("scale(temp)").is_err());
+
+    // The same AST can be called via `call_fn`
+    let ast = engine.compile("fn run(x) { scale(x) + SCALE }").unwrap();
+    assert_eq!(engine.call_fn::<INT>(&mut rhai::Scope::new(), &ast, "run", (3 as INT,)).unwrap(), 40);
+
+    engine.clear_prelude();
+    assert!(!engine.has_prelude());
+    assert!(engine.eval::<INT>("scale(4)").is_err());
+}
+
+#[test]
+fn test_functions_retain_reachable() {
+    let engine = Engine::new();
+
+    let lib = engine
+        .compile(
+            r#"
+                fn entry(x) { x.method() + call_by_name() }
+                fn method() { this * recurse(3) }
+                fn recurse(n) { if n > 0 { recurse(n - 1) } else { 1 } }
+                fn call_by_name() { let f = Fn("by_ptr"); f.call() + closure() }
+                fn by_ptr() { 10 }
+                fn closure() { let f = |x| x + from_closure(); f.call(1) }
+                fn from_closure() { 100 }
+                fn from_script() { 1000 }
+                fn unused() { unused_too() }
+                fn unused_too() { 0 }
+            "#,
+        )
+        .unwrap();
+
+    let mut ast = engine.compile("from_script()").unwrap().merge(&lib);
+    let total = ast.iter_functions().count();
+
+    ast.retain_reachable_functions(["entry"]);
+
+    let names = ast.iter_functions().map(|f| f.name).collect::<Vec<_>>();
+    assert_eq!(names.len(), total - 2);
+    assert!(!names.contains(&"unused"));
+    assert!(!names.contains(&"unused_too"));
+
+    ast.clear_statements();
+    assert_eq!(engine.call_fn::<INT>(&mut rhai::Scope::new(), &ast, "entry", (2 as INT,)).unwrap(), 113);
+
+    ast.retain_reachable_functions(["by_ptr"]);
+    assert_eq!(ast.iter_functions().map(|f| f.name).collect::<Vec<_>>(), ["by_ptr"]);
+}
+
+#[test]
+fn test_functions_call_graph() {
+    use rhai::CallGraphNode;
+
+    let engine = Engine::new();
+
+    let ast = engine
+        .compile(
+            "
+                fn area(h) { this * h }
+                fn total(list) {
+                    let sum = 0;
+                    for item in list { sum += item.area(2); }
+                    sum
+                }
+                fn recurse(n) { if n > 0 { recurse(n - 1) } }
+                fn dead() { recurse(10) }
+
+                print(total([1, 2, 3]).to_string());
+            ",
+        )
+        .unwrap();
+
+    let graph = ast.call_graph();
+    assert_eq!(graph.functions().len(), 4);
+
+    let area = CallGraphNode::Script("area".into(), 1);
+    let total = CallGraphNode::Script("total".into(), 1);
+    let recurse = CallGraphNode::Script("recurse".into(), 1);
+
+    // Method-call style call to a script function
+    assert_eq!(graph.calls_to(&area).map(|e| e.caller.clone()).collect::<Vec<_>>(), std::slice::from_ref(&total));
+
+    // Calls to native functions, with method calls counting the object as an argument
+    assert!(graph.calls_from(&CallGraphNode::Main).any(|e| e.callee == CallGraphNode::Native("to_string".into(), 1)));
+    assert!(graph.calls_from(&CallGraphNode::Main).any(|e| e.callee == CallGraphNode::Native("print".into(), 1)));
+
+    assert!(graph.calls_to(&recurse).any(|e| e.caller == recurse));
+
+    let reachable = graph.reachable_from([&CallGraphNode::Main]);
+    assert!(reachable.contains(&total));
+    assert!(reachable.contains(&area));
+    assert!(!reachable.contains(&recurse));
+
+    let unreachable = graph.unreachable_functions();
+    assert_eq!(unreachable.len(), 2);
+    assert!(unreachable.contains(&&recurse));
+}
+
+#[test]
+fn test_functions_overload_by_type() {
+    let engine = Engine::new();
+
+    let script = r#"
+        fn describe(x: int) { `int ${x}` }
+        fn describe(x: string) { `string ${x}` }
+        fn describe(x) { `other ${type_of(x)}` }
+        fn pair(x: int, y: string) { "int, string" }
+        fn pair(x: string, y: int) { "string, int" }
+    "#;
+
+    let run = |code: &str| engine.eval::<String>(&format!("{script}\n{code}"));
+
+    assert_eq!(run("describe(42)").unwrap(), "int 42");
+    assert_eq!(run(r#"describe("hello")"#).unwrap(), "string hello");
+    assert_eq!(run("describe(true)").unwrap(), "other bool");
+    assert_eq!(run(r#"pair("a", 1)"#).unwrap(), "string, int");
+    #[cfg(not(feature = "no_object"))]
+    assert_eq!(run(r#"let x = "a"; x.describe(1)"#).unwrap(), "int 1");
+    assert_eq!(run(r#"let f = Fn("pair"); f.call(1, "a")"#).unwrap(), "int, string");
+    assert_eq!(run(r#"is_def_fn("pair", 2).to_string()"#).unwrap(), "true");
+    assert!(matches!(*run("pair(1, 2)").unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+
+    let ast = engine.compile(script).unwrap();
+
+    assert_eq!(engine.call_fn::<String>(&mut Scope::new(), &ast, "describe", ("hello",)).unwrap(), "string hello");
+    assert_eq!(engine.call_fn::<String>(&mut Scope::new(), &ast, "describe", (42 as INT,)).unwrap(), "int 42");
+    assert_eq!(engine.call_fn::<String>(&mut Scope::new(), &ast, "describe", (true,)).unwrap(), "other bool");
+
+    assert!(matches!(engine.compile("fn foo(x: int) {} fn foo(x: int) {}").unwrap_err().err_type(), ParseErrorType::FnDuplicatedDefinition(..)));
+    assert!(matches!(engine.compile("fn foo(x: int, y) {}").unwrap_err().err_type(), ParseErrorType::MissingSymbol(..)));
+}
+
+#[test]
+fn test_functions_stack_sampling() {
+    let mut engine = Engine::new();
+
+    assert!(engine.stack_sampler().is_none());
+
+    engine.set_stack_sampling(true);
+
+    let sampler = engine.stack_sampler().unwrap();
+    let s = sampler.clone();
+
+    engine.register_fn("sample", move || s.sample().iter().map(|f| f.to_string()).collect::<Vec<_>>().join(";"));
+
+    let stack = engine
+        .eval::<String>(
+            "
+                fn foo(x) {
+                    bar(x)
+                }
+                fn bar(x) {
+                    sample()
+                }
+                foo(42)
+            ",
+        )
+        .unwrap();
+
+    #[cfg(not(feature = "no_position"))]
+    assert_eq!(stack, "<script> @ 8:17;foo @ 3:21;bar @ 6:21");
+    #[cfg(feature = "no_position")]
+    assert_eq!(stack, "<script>;foo;bar");
+
+    // Frames are popped on errors
+    assert!(engine.run("fn oops() { throw 42; } oops()").is_err());
+    assert_eq!(sampler.sample().len(), 1);
+}
+
+#[test]
+#[cfg(feature = "sync")]
+fn test_functions_stack_sampling_thread() {
+    let mut engine = Engine::new();
+    engine.set_stack_sampling(true);
+
+    let sampler = engine.stack_sampler().unwrap();
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    let rx = std::sync::Mutex::new(rx);
+
+    let handle = std::thread::spawn(move || {
+        engine.register_fn("wait", move || rx.lock().unwrap().recv().unwrap());
+        engine.run("fn spin() { wait(); } spin();").unwrap();
+    });
+
+    // Poll until the script is inside `spin`
+    let stack = loop {
+        let stack = sampler.sample();
+        if stack.len() == 2 {
+            break stack;
+        }
+        std::thread::yield_now();
+    };
+
+    tx.send(()).unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(stack[1].fn_name, "spin");
+}
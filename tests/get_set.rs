@@ -163,12 +163,12 @@ fn test_get_set_chain_without_write_back() {
 
     engine
         .register_type::<Inner>()
-        .register_get_set("value", |t: &mut Inner| t.value, |_: NativeCallContext, _: &mut Inner, new: INT| panic!("Inner::value setter called with {}", new))
+        .register_get_set("value", |t: &mut Inner| t.value, |_: NativeCallContext, _: &mut Inner, new: INT| -> () { panic!("Inner::value setter called with {}", new) })
         .register_type::<Outer>()
-        .register_get_set("inner", |_: NativeCallContext, t: &mut Outer| t.inner.clone(), |_: &mut Outer, new: Inner| panic!("Outer::inner setter called with {:?}", new));
+        .register_get_set("inner", |_: NativeCallContext, t: &mut Outer| t.inner.clone(), |_: &mut Outer, new: Inner| -> () { panic!("Outer::inner setter called with {:?}", new) });
 
     #[cfg(not(feature = "no_index"))]
-    engine.register_indexer_get_set(|t: &mut Outer, n: INT| Inner { value: t.inner.value * n }, |_: &mut Outer, n: INT, new: Inner| panic!("Outer::inner index setter called with {} and {:?}", n, new));
+    engine.register_indexer_get_set(|t: &mut Outer, n: INT| Inner { value: t.inner.value * n }, |_: &mut Outer, n: INT, new: Inner| -> () { panic!("Outer::inner index setter called with {} and {:?}", n, new) });
 
     assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "outer.inner.value").unwrap(), 42);
 
@@ -327,4 +327,23 @@ fn test_get_set_elvis() {
     engine.eval::<()>("let x = (); x?.foo(1,2,3)").unwrap();
     engine.eval::<()>("let x = #{a:()}; x.a?.foo.bar.baz").unwrap();
     assert_eq!(engine.eval::<String>("let x = 'x'; x?.type_of()").unwrap(), "char");
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        engine.eval::<()>("let x = (); x?.a[0]?.b()").unwrap();
+        engine.eval::<()>("let x = #{a:()}; x?.a?[0]?.b()").unwrap();
+        engine.eval::<()>("let x = #{a:[()]}; x?.a[0]?.b()").unwrap();
+        engine.eval::<()>("let x = #{a:[#{}]}; x?.a[0]?.b?.c()").unwrap();
+        engine.eval::<()>("let x = (); x?[0].a").unwrap();
+        engine.eval::<()>("let x = [(), 1]; x[0]?[1]?.a").unwrap();
+        assert_eq!(engine.eval::<INT>("let x = #{a:[#{b:42}]}; x?.a[0]?.b").unwrap(), 42);
+    }
+
+    #[cfg(not(feature = "no_function"))]
+    {
+        engine.eval::<()>("let f = (); f?.(1, 2)").unwrap();
+        engine.eval::<()>("let x = #{}; x.f?.()").unwrap();
+        assert_eq!(engine.eval::<INT>("let f = |x| x + 1; f?.(41)").unwrap(), 42);
+        assert_eq!(engine.eval::<INT>("let x = #{f: || 42}; x?.f?.()").unwrap(), 42);
+    }
 }
@@ -0,0 +1,89 @@
+use rhai::{Engine, EvalAltResult, Handle, NativeCallContext, Scope};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn make_engine(closed: &Arc<AtomicUsize>) -> Engine {
+    let mut engine = Engine::new();
+    let closed = closed.clone();
+
+    engine.register_handle::<String>("File").register_fn("open", move |ctx: NativeCallContext, path: &str| {
+        let closed = closed.clone();
+        Handle::open(&ctx, path.to_string(), move |_| {
+            closed.fetch_add(1, Ordering::SeqCst);
+        })
+    });
+    engine.register_fn("path", |file: &mut Handle<String>| file.with(|path| path.clone()));
+
+    engine
+}
+
+#[test]
+fn test_handles() {
+    let closed = Arc::new(AtomicUsize::new(0));
+    let engine = make_engine(&closed);
+
+    assert_eq!(engine.eval::<String>(r#"let f = open("a.txt"); type_of(f)"#).unwrap(), "File");
+    assert_eq!(closed.load(Ordering::SeqCst), 1);
+
+    assert_eq!(engine.eval::<String>(r#"let f = open("a.txt"); path(f)"#).unwrap(), "a.txt");
+    assert_eq!(closed.load(Ordering::SeqCst), 2);
+
+    assert!(!engine.eval::<bool>(r#"let f = open("a.txt"); let g = f; f.close(); g.is_open()"#).unwrap());
+    assert_eq!(closed.load(Ordering::SeqCst), 3);
+
+    assert!(!engine.eval::<bool>(r#"let f = open("a.txt"); f.close(); f.close()"#).unwrap());
+    assert_eq!(closed.load(Ordering::SeqCst), 4);
+
+    assert!(matches!(*engine.run(r#"let f = open("a.txt"); f.close(); path(f)"#).unwrap_err(), EvalAltResult::ErrorRuntime(..)));
+    assert_eq!(closed.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn test_handles_scope() {
+    let closed = Arc::new(AtomicUsize::new(0));
+    let engine = make_engine(&closed);
+    let mut scope = Scope::new();
+
+    engine.run_with_scope(&mut scope, r#"let f = open("a.txt");"#).unwrap();
+
+    // The evaluation run has ended, so the handle is closed even though it is still in scope
+    assert_eq!(closed.load(Ordering::SeqCst), 1);
+    let file = scope.get_value::<Handle<String>>("f").unwrap();
+    assert!(!file.is_open());
+    assert!(file.with(|path| path.len()).is_err());
+
+    // Untracked handles are only closed when closed explicitly or dropped
+    let file = Handle::with_destructor("b.txt".to_string(), {
+        let closed = closed.clone();
+        move |_| {
+            closed.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    scope.push("g", file.clone());
+
+    assert_eq!(engine.eval_with_scope::<String>(&mut scope, "path(g)").unwrap(), "b.txt");
+    assert!(file.is_open());
+    assert_eq!(closed.load(Ordering::SeqCst), 1);
+
+    drop(scope);
+    assert!(file.is_open());
+    drop(file);
+    assert_eq!(closed.load(Ordering::SeqCst), 2);
+}
+
+#[cfg(not(feature = "unchecked"))]
+#[test]
+fn test_handles_max_open() {
+    let closed = Arc::new(AtomicUsize::new(0));
+    let mut engine = make_engine(&closed);
+    engine.set_max_open_handles(2);
+
+    engine.run(r#"let f = open("a.txt"); let g = open("b.txt");"#).unwrap();
+    assert_eq!(closed.load(Ordering::SeqCst), 2);
+
+    assert!(matches!(*engine.run(r#"let f = open("a.txt"); let g = open("b.txt"); let h = open("c.txt");"#).unwrap_err(), EvalAltResult::ErrorDataTooLarge(..)));
+    assert_eq!(closed.load(Ordering::SeqCst), 4);
+
+    engine.run(r#"let f = open("a.txt"); let g = open("b.txt"); f.close(); let h = open("c.txt");"#).unwrap();
+    assert_eq!(closed.load(Ordering::SeqCst), 7);
+}
@@ -257,6 +257,74 @@ fn test_map_oop() {
     );
 }
 
+#[test]
+#[cfg(not(feature = "no_function"))]
+fn test_map_prototype() {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine
+            .eval::<INT>(
+                r#"
+                    const Base = prototype(#{
+                        get: || this.data,
+                        double: || this.data * 2
+                    });
+                    const Derived = prototype(#{
+                        "$prototype": Base,
+                        add: |x| { this.data += x; this.get() },
+                        double: || this.data * 10
+                    });
+
+                    let a = #{ data: 1 };
+                    a.set_prototype(Base);
+                    let b = #{ data: 2 };
+                    b.set_prototype(Derived);
+
+                    b.add(1) + a.double() * 100 + b.double() * 1000
+                "#,
+            )
+            .unwrap(),
+        30203
+    );
+
+    assert_eq!(engine.eval::<INT>("let obj = #{ x: 1, f: || 42 }; obj.set_prototype(#{ f: || 0 }); obj.f()").unwrap(), 42);
+    assert_eq!(engine.eval::<String>("let obj = #{}; obj.set_prototype(#{}); type_of(obj.get_prototype())").unwrap(), "Prototype");
+    assert_eq!(engine.eval::<INT>("let obj = #{}; obj.set_prototype(#{ x: 42 }); obj.get_prototype().methods().x").unwrap(), 42);
+    assert!(engine.eval::<()>("let obj = #{}; obj.set_prototype(#{ f: || 42 }); obj.clear_prototype(); obj.get_prototype()").is_ok());
+
+    assert!(matches!(
+        *engine.run("let obj = #{}; obj.set_prototype(#{ f: || 42 }); obj.clear_prototype(); obj.f()").unwrap_err(),
+        EvalAltResult::ErrorFunctionNotFound(f, ..) if f.starts_with("f (")
+    ));
+
+    let obj = engine.eval::<Dynamic>("let obj = #{}; obj.set_prototype(#{ f: || 42 }); obj").unwrap();
+    assert!(engine.implements(&obj, ["f"]));
+
+    // The prototype is not one of the properties of the object map
+    let mut scope = Scope::new();
+    engine.run_with_scope(&mut scope, "let obj = #{ a: 1, b: 2 }; obj.set_prototype(#{ f: || 42 });").unwrap();
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "obj.len()").unwrap(), 2);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "obj == #{ a: 1, b: 2 }").unwrap());
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "#{ a: 1, b: 2 } == obj").unwrap());
+    assert!(!engine.eval_with_scope::<bool>(&mut scope, "obj != #{ a: 1, b: 2 }").unwrap());
+    assert!(engine.eval::<bool>("let obj = #{}; obj.set_prototype(#{ f: || 42 }); obj.is_empty()").unwrap());
+    assert_eq!(engine.eval_with_scope::<String>(&mut scope, "obj.to_json()").unwrap(), r#"{"a":1,"b":2}"#);
+    assert!(!engine.eval_with_scope::<String>(&mut scope, "obj.to_string()").unwrap().contains("$prototype"));
+    assert!(!engine.eval_with_scope::<String>(&mut scope, "obj.to_debug()").unwrap().contains("$prototype"));
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "let #{ a, b } = obj; a + b").unwrap(), 3);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "obj.filter(|k| true).len()").unwrap(), 2);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "let x = obj; x.drain(|k| true); x.len() * 100 + x.f()").unwrap(), 42);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "let x = obj; x.retain(|k| false); x.len() * 100 + x.f()").unwrap(), 42);
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        assert_eq!(engine.eval_with_scope::<String>(&mut scope, "obj.keys().to_string()").unwrap(), r#"["a", "b"]"#);
+        assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "let n = 0; for v in obj.values() { n += v } n").unwrap(), 3);
+    }
+}
+
 #[test]
 #[cfg(feature = "internals")]
 fn test_map_missing_property_callback() {
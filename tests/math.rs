@@ -57,3 +57,84 @@ fn test_math_parse() {
     assert_eq!(engine.eval::<INT>(r#"parse_int("42", 16)"#).unwrap(), 0x42);
     assert_eq!(engine.eval::<INT>(r#"parse_int("abcdef", 16)"#).unwrap(), 0xabcdef);
 }
+
+#[test]
+#[cfg(not(feature = "unchecked"))]
+fn test_math_arithmetic_mode() {
+    use rhai::{ArithmeticMode, Scope};
+
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+    scope.push("max", INT::MAX).push("min", INT::MIN);
+
+    let mut eval = |engine: &Engine, script: &str| engine.eval_with_scope::<INT>(&mut scope, script);
+
+    assert!(eval(&engine, "max + 1").is_err());
+    assert!(eval(&engine, "-min").is_err());
+
+    engine.set_arithmetic_mode(ArithmeticMode::Wrapping);
+    assert_eq!(engine.arithmetic_mode(), ArithmeticMode::Wrapping);
+
+    assert_eq!(eval(&engine, "max + 1").unwrap(), INT::MIN);
+    assert_eq!(eval(&engine, "min - 1").unwrap(), INT::MAX);
+    assert_eq!(eval(&engine, "max * 2").unwrap(), -2);
+    assert_eq!(eval(&engine, "min / -1").unwrap(), INT::MIN);
+    assert_eq!(eval(&engine, "min % -1").unwrap(), 0);
+    assert_eq!(eval(&engine, "2 ** 64").unwrap(), 0);
+    assert_eq!(eval(&engine, "-min").unwrap(), INT::MIN);
+    assert_eq!(eval(&engine, "abs(min)").unwrap(), INT::MIN);
+    assert_eq!(eval(&engine, "let x = max; x += 2; x").unwrap(), INT::MIN + 1);
+    assert_eq!(engine.eval::<INT>(&format!("{} + 1", INT::MAX)).unwrap(), INT::MIN);
+    assert!(eval(&engine, "max / 0").is_err());
+    assert!(eval(&engine, "max % 0").is_err());
+    assert!(eval(&engine, "2 ** -1").is_err());
+
+    engine.set_arithmetic_mode(ArithmeticMode::Saturating);
+
+    assert_eq!(eval(&engine, "max + 1").unwrap(), INT::MAX);
+    assert_eq!(eval(&engine, "min - 1").unwrap(), INT::MIN);
+    assert_eq!(eval(&engine, "min * 2").unwrap(), INT::MIN);
+    assert_eq!(eval(&engine, "min / -1").unwrap(), INT::MAX);
+    assert_eq!(eval(&engine, "(-2) ** 65").unwrap(), INT::MIN);
+    assert_eq!(eval(&engine, "(-2) ** 64").unwrap(), INT::MAX);
+    assert_eq!(eval(&engine, "-min").unwrap(), INT::MAX);
+    assert_eq!(eval(&engine, "let x = min; x -= 2; x").unwrap(), INT::MIN);
+
+    #[cfg(not(feature = "only_i32"))]
+    assert_eq!(eval(&engine, "(-1) ** 5000000001").unwrap(), -1);
+
+    // Other integer types
+    #[cfg(not(feature = "only_i32"))]
+    #[cfg(not(feature = "only_i64"))]
+    {
+        let mut scope = Scope::new();
+        scope.push("x", 250_u8);
+        assert_eq!(engine.eval_with_scope::<u8>(&mut scope, "x + x").unwrap(), 255);
+        engine.set_arithmetic_mode(ArithmeticMode::Wrapping);
+        assert_eq!(engine.eval_with_scope::<u8>(&mut scope, "x + x").unwrap(), 244);
+        engine.set_arithmetic_mode(ArithmeticMode::Checked);
+        assert!(engine.eval_with_scope::<u8>(&mut scope, "x + x").is_err());
+    }
+}
+
+#[test]
+#[cfg(feature = "decimal")]
+fn test_math_decimal_format() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("2.345").round_dp_with(2, "bankers").to_string()"#).unwrap(), "2.34");
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("2.355").round_half_even(2).to_string()"#).unwrap(), "2.36");
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("2.345").round_dp_with(2, "half_up").to_string()"#).unwrap(), "2.35");
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("-2.341").round_dp_with(2, "floor").to_string()"#).unwrap(), "-2.35");
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("-2.349").round_dp_with(2, "ceiling").to_string()"#).unwrap(), "-2.34");
+    assert!(engine.eval::<String>(r#"parse_decimal("2.345").round_dp_with(2, "sideways").to_string()"#).is_err());
+
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("-1234567.891").to_formatted_string()"#).unwrap(), "-1,234,567.891");
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("123").to_formatted_string()"#).unwrap(), "123");
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("123456").to_formatted_string(".", ",")"#).unwrap(), "123.456");
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("1234567.891").to_formatted_string(" ", ",")"#).unwrap(), "1 234 567,891");
+
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("1.234.567,891", ".", ",").to_string()"#).unwrap(), "1234567.891");
+    assert_eq!(engine.eval::<String>(r#"parse_decimal("-1,234.5", ",", ".").to_string()"#).unwrap(), "-1234.5");
+    assert!(engine.eval::<String>(r#"parse_decimal("1 234,5", ",", ".").to_string()"#).is_err());
+}
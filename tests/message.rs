@@ -0,0 +1,47 @@
+#![cfg(feature = "icu")]
+#![cfg(not(feature = "no_object"))]
+use rhai::Engine;
+
+#[test]
+fn test_message_plural() {
+    let engine = Engine::new();
+
+    let script = r##"
+        let forms = #{ "=0": "no messages", one: "# message", other: "# messages" };
+        [plural(0, forms), plural(1, forms), plural(42, forms)].to_string()
+    "##;
+    assert_eq!(engine.eval::<String>(script).unwrap(), r#"["no messages", "1 message", "42 messages"]"#);
+
+    let script = r##"
+        let forms = #{ one: "# plik", few: "# pliki", many: "# plików", other: "# pliku" };
+        [plural(1, forms, "pl"), plural(3, forms, "pl"), plural(5, forms, "pl"), plural(22, forms, "pl")].to_string()
+    "##;
+    assert_eq!(engine.eval::<String>(script).unwrap(), r#"["1 plik", "3 pliki", "5 plików", "22 pliki"]"#);
+
+    assert!(engine.eval::<String>(r##"plural(2, #{ one: "#" })"##).is_err());
+}
+
+#[test]
+fn test_message_format() {
+    let engine = Engine::new();
+
+    let pattern = "{name} has {count, plural, =0 {no files} one {# file} other {# files}}.";
+
+    assert_eq!(engine.eval::<String>(&format!(r#"format_message("{pattern}", #{{ name: "Alice", count: 3 }})"#)).unwrap(), "Alice has 3 files.");
+    assert_eq!(engine.eval::<String>(&format!(r#"format_message("{pattern}", #{{ name: "Bob", count: 1 }})"#)).unwrap(), "Bob has 1 file.");
+    assert_eq!(engine.eval::<String>(&format!(r#"format_message("{pattern}", #{{ name: "Eve", count: 0 }})"#)).unwrap(), "Eve has no files.");
+
+    let pattern = "{place, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}";
+
+    assert_eq!(engine.eval::<String>(&format!(r#"format_message("{pattern}", #{{ place: 2 }}, "en")"#)).unwrap(), "2nd");
+    assert_eq!(engine.eval::<String>(&format!(r#"format_message("{pattern}", #{{ place: 13 }}, "en")"#)).unwrap(), "13th");
+
+    let pattern = "{gender, select, female {She} male {He} other {They}} replied: {n, plural, one {# reply} other {{name}''s # replies}}";
+
+    assert_eq!(engine.eval::<String>(&format!(r#"format_message("{pattern}", #{{ gender: "female", n: 5, name: "Ann" }})"#)).unwrap(), "She replied: Ann's 5 replies");
+    assert_eq!(engine.eval::<String>(r#"format_message("'{literal}' {x}", #{ x: 1 })"#).unwrap(), "{literal} 1");
+
+    assert!(engine.eval::<String>(r#"format_message("{missing}", #{})"#).is_err());
+    assert!(engine.eval::<String>(r#"format_message("{x", #{ x: 1 })"#).is_err());
+    assert!(engine.eval::<String>(r#"format_message("{x, plural, one {#}}", #{ x: 2 })"#).is_err());
+}
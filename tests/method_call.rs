@@ -133,3 +133,43 @@ fn test_method_call_typed() {
         EvalAltResult::ErrorFunctionNotFound(f, ..) if f.starts_with("foo")
     ));
 }
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_method_call_implements() {
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<TestStruct>("TestStruct")
+        .register_fn("update", TestStruct::update)
+        .register_fn("new_ts", TestStruct::new)
+        .register_get("x", |t: &mut TestStruct| t.x);
+
+    let value = rhai::Dynamic::from(TestStruct::new());
+
+    assert!(engine.implements(&value, ["update", "x"]));
+    assert!(!engine.implements(&value, ["update", "draw"]));
+    assert_eq!(engine.missing_members(&value, ["draw", "update", "resize"]), ["draw", "resize"]);
+    assert!(engine.ensure_implements(&value, ["update"]).is_ok());
+    assert!(matches!(
+        *engine.ensure_implements(&value, ["update", "draw", "resize"]).unwrap_err(),
+        EvalAltResult::ErrorMismatchDataType(need, actual, ..) if need == "implementation of `draw`, `resize`" && actual == "TestStruct"
+    ));
+
+    // Functions for other types do not count
+    assert!(!engine.implements(&rhai::Dynamic::from(42 as INT), ["update"]));
+
+    assert!(engine.eval::<bool>(r#"let x = new_ts(); implements(x, ["update", "x"])"#).unwrap());
+    assert!(!engine.eval::<bool>(r#"let x = new_ts(); x.implements(["update", "draw"])"#).unwrap());
+    assert!(engine.eval::<bool>(r#"let x = new_ts(); x.implements("update")"#).unwrap());
+    assert!(engine.eval::<bool>(r#"let obj = #{ update: |dt| dt, draw: 42 }; obj.implements(["update", "draw"])"#).unwrap());
+    assert!(!engine.eval::<bool>(r#"let obj = #{ update: |dt| dt }; implements(obj, ["update", "draw"])"#).unwrap());
+    assert!(!engine.eval::<bool>(r#"let obj = #{ update: |dt| dt }; implements(obj, ["update", 42])"#).unwrap());
+
+    #[cfg(not(feature = "no_function"))]
+    {
+        assert!(engine.eval::<bool>(r#"fn TestStruct.draw() {} let x = new_ts(); x.implements(["update", "draw"])"#).unwrap());
+        assert!(!engine.eval::<bool>(r#"fn Other.draw() {} let x = new_ts(); x.implements(["update", "draw"])"#).unwrap());
+        assert!(engine.eval::<bool>(r#"fn draw() {} let x = new_ts(); x.implements(["update", "draw"])"#).unwrap());
+    }
+}
@@ -67,3 +67,46 @@ fn test_mismatched_op_custom_type() {
             if need == "TestStruct" && actual == std::any::type_name::<INT>()
     ));
 }
+
+#[test]
+#[cfg(not(feature = "no_object"))]
+fn test_mismatched_op_type_alias() {
+    use std::sync::{Arc, RwLock};
+
+    #[derive(Debug, Clone)]
+    struct Rule;
+
+    #[derive(Debug, Clone)]
+    struct Order;
+
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<Rule>("Rule")
+        .register_type_alias::<Arc<RwLock<Rule>>, Rule>()
+        .register_type_alias::<Box<Arc<RwLock<Rule>>>, Arc<RwLock<Rule>>>()
+        .register_fn("new_rule", || Arc::new(RwLock::new(Rule)))
+        .register_fn("boxed_rule", || Box::new(Arc::new(RwLock::new(Rule))))
+        .register_fn("new_order", || Arc::new(RwLock::new(Order)));
+
+    assert_eq!(engine.eval::<String>("type_of(new_rule())").unwrap(), "Rule");
+    assert_eq!(engine.eval::<String>("type_of(boxed_rule())").unwrap(), "Rule");
+
+    assert!(matches!(
+        *engine.eval::<INT>("new_rule()").unwrap_err(),
+        EvalAltResult::ErrorMismatchOutputType(need, actual, ..) if need == std::any::type_name::<INT>() && actual == "Rule"
+    ));
+
+    // Cycles do not hang
+    engine.register_type_alias_raw("A", "B").register_type_alias_raw("B", "A");
+    assert!(["A", "B"].contains(&engine.map_type_name("A")));
+
+    engine.on_map_type_name(|name| if name.contains("Order") { Some("Order") } else { None });
+
+    assert_eq!(engine.eval::<String>("type_of(new_order())").unwrap(), "Order");
+
+    assert!(matches!(
+        *engine.eval::<INT>("new_order() + 1").unwrap_err(),
+        EvalAltResult::ErrorFunctionNotFound(f, ..) if f == format!("+ (Order, {})", std::any::type_name::<INT>())
+    ));
+}
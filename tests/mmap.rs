@@ -0,0 +1,33 @@
+#![cfg(feature = "mmap")]
+#![cfg(not(feature = "no_index"))]
+#![cfg(not(feature = "no_std"))]
+use rhai::packages::{MmapBlob, MmapPackage, Package};
+use rhai::{Blob, Engine, Scope, INT};
+
+#[test]
+fn test_mmap_blob() {
+    let path = std::env::temp_dir().join(format!("rhai-test-mmap-{}.bin", std::process::id()));
+    std::fs::write(&path, [1_u8, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+
+    let mut engine = Engine::new();
+    MmapPackage::new().register_into_engine(&mut engine);
+
+    let mut scope = Scope::new();
+    scope.push("path", path.to_string_lossy().to_string());
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "open_blob_mmap(path).len").unwrap(), 8);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "let b = open_blob_mmap(path); b[0] + b[-1]").unwrap(), 9);
+    assert!(engine.eval_with_scope::<INT>(&mut scope, "open_blob_mmap(path)[8]").is_err());
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "open_blob_mmap(path).get(8)").unwrap(), 0);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "let s = open_blob_mmap(path)[2..6]; s.len * 100 + s[1..=2][0]").unwrap(), 404);
+    assert_eq!(engine.eval_with_scope::<Blob>(&mut scope, "open_blob_mmap(path).extract(1, 3)").unwrap(), [2, 3, 4]);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "open_blob_mmap(path).parse_le_int(0, 2)").unwrap(), 0x0201);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "let sum = 0; for x in open_blob_mmap(path)[4..] { sum += x } sum").unwrap(), 26);
+    assert!(engine.eval_with_scope::<MmapBlob>(&mut scope, r#"open_blob_mmap(path + ".missing")"#).is_err());
+
+    let blob = MmapBlob::open(&path).unwrap();
+    assert_eq!(&*blob.slice(6, 2), [7, 8]);
+    drop(blob);
+
+    let _ = std::fs::remove_file(&path);
+}
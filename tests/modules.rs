@@ -1,6 +1,6 @@
 #![cfg(not(feature = "no_module"))]
 use rhai::{
-    module_resolvers::{DummyModuleResolver, StaticModuleResolver},
+    module_resolvers::{DummyModuleResolver, StaticModuleResolver, TenantModuleCache},
     Dynamic, Engine, EvalAltResult, FuncRegistration, ImmutableString, Module, ParseError, ParseErrorType, Scope, INT,
 };
 //
@@ -16,6 +16,21 @@ fn test_module() {
     assert_eq!(module.get_var_value::<INT>("answer").unwrap(), 42);
 }
 
+#[test]
+fn test_module_fn_aliases() {
+    let mut module = Module::new();
+
+    FuncRegistration::new("add")
+        .with_aliases(["plus", "sum"])
+        .in_global_namespace()
+        .set_into_module(&mut module, |x: INT, y: INT| x + y);
+
+    let mut engine = Engine::new();
+    engine.register_global_module(module.into());
+
+    assert_eq!(engine.eval::<INT>("add(1, 2) + plus(3, 4) + sum(5, 6)").unwrap(), 21);
+}
+
 #[test]
 fn test_module_syntax() {
     let engine = Engine::new();
@@ -83,6 +98,47 @@ fn test_module_sub_module() {
     assert_eq!(engine.eval::<INT>("super_inc(question::life::universe::answer)").unwrap(), 42);
 }
 
+#[test]
+fn test_module_static_nested() {
+    let mut engine = Engine::new();
+
+    let mut module = Module::new();
+    module.set_native_fn("leaf", || Ok(1 as INT));
+    engine.register_static_module("a::b::c", module.into());
+
+    // Registering a parent merges into it instead of replacing it
+    let mut module = Module::new();
+    module.set_native_fn("mid", || Ok(2 as INT));
+    module.set_var("X", 40 as INT);
+    engine.register_static_module("a::b", module.into());
+
+    let mut module = Module::new();
+    module.set_native_fn("leaf2", || Ok(3 as INT));
+    engine.register_static_module("a :: b :: c", module.into());
+
+    assert_eq!(engine.eval::<INT>("a::b::c::leaf() + a::b::mid() + a::b::c::leaf2() + a::b::X").unwrap(), 46);
+
+    // Conflicting functions are rejected, including in sub-modules
+    let mut module = Module::new();
+    module.set_native_fn("mid", || Ok(42 as INT));
+    assert_eq!(engine.try_register_static_module("a::b", module.into()).unwrap_err(), "function 'mid' is already registered in module 'a::b'");
+
+    let mut sub_module = Module::new();
+    sub_module.set_native_fn("leaf", || Ok(42 as INT));
+    let mut module = Module::new();
+    module.set_sub_module("c", sub_module);
+    assert_eq!(engine.try_register_static_module("a::b", module.into()).unwrap_err(), "function 'c::leaf' is already registered in module 'a::b'");
+
+    assert_eq!(engine.eval::<INT>("a::b::mid() + a::b::c::leaf()").unwrap(), 3);
+
+    // Functions with different signatures do not conflict
+    let mut module = Module::new();
+    module.set_native_fn("mid", |x: INT| Ok(x));
+    engine.try_register_static_module("a::b", module.into()).unwrap();
+
+    assert_eq!(engine.eval::<INT>("a::b::mid() + a::b::mid(40)").unwrap(), 42);
+}
+
 #[test]
 fn test_module_resolver() {
     let mut resolver = StaticModuleResolver::new();
@@ -585,3 +641,308 @@ fn test_module_dynamic() {
 
     assert_eq!(engine.eval::<INT>(r#"import "test" as test; test::test("test", 38);"#).unwrap(), 42);
 }
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_module_file_resolver_reload() {
+    use rhai::{module_resolvers::FileModuleResolver, ModuleResolver, Position};
+    use std::{fs, path::Path, thread, time::Duration};
+
+    // Write a file, making sure its modification time changes
+    fn write_script(path: &Path, script: &str) {
+        let orig = fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        loop {
+            fs::write(path, script).unwrap();
+            if fs::metadata(path).and_then(|m| m.modified()).ok() != orig {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    let dir = std::env::temp_dir().join(format!("rhai-test-reload-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file_path = dir.join("answer.rhai");
+    let script = r#"import "answer" as m; m::ANSWER"#;
+
+    write_script(&file_path, "export const ANSWER = 1;");
+
+    let mut resolver = FileModuleResolver::new_with_path(&dir);
+    resolver.enable_auto_reload(true);
+    let mut engine = Engine::new();
+    engine.set_module_resolver(resolver);
+
+    assert_eq!(engine.eval::<INT>(script).unwrap(), 1);
+    write_script(&file_path, "export const ANSWER = 2;");
+    assert_eq!(engine.eval::<INT>(script).unwrap(), 2);
+
+    let resolver = FileModuleResolver::new_with_path(&dir);
+    let engine = Engine::new();
+
+    resolver.resolve(&engine, None, "answer", Position::NONE).unwrap();
+    assert!(resolver.is_cached(&file_path));
+    write_script(&file_path, "export const ANSWER = 3;");
+    assert_eq!(resolver.resolve(&engine, None, "answer", Position::NONE).unwrap().get_var_value::<INT>("ANSWER").unwrap(), 2);
+
+    assert!(resolver.invalidate_path(&file_path));
+    assert!(!resolver.is_cached(&file_path));
+    assert!(!resolver.invalidate_path(&file_path));
+    assert_eq!(resolver.resolve(&engine, None, "answer", Position::NONE).unwrap().get_var_value::<INT>("ANSWER").unwrap(), 3);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_module_circular_import() {
+    use rhai::module_resolvers::FileModuleResolver;
+    use std::fs;
+
+    let dir = std::env::temp_dir().join(format!("rhai-test-circular-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.rhai"), r#"import "b" as b; export const X = 1;"#).unwrap();
+    fs::write(dir.join("b.rhai"), r#"import "c" as c; export const X = 2;"#).unwrap();
+    fs::write(dir.join("c.rhai"), "let x = 0;\nimport \"a\" as a;").unwrap();
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(FileModuleResolver::new_with_path(&dir));
+    engine.set_record_module_graph(true);
+
+    let err = engine.run(r#"import "a" as a;"#).unwrap_err();
+
+    match err.unwrap_inner() {
+        EvalAltResult::ErrorCircularImport(cycle, ..) => {
+            assert_eq!(cycle.iter().map(|(path, ..)| path.as_str()).collect::<Vec<_>>(), ["a", "b", "c", "a"]);
+            #[cfg(not(feature = "no_position"))]
+            assert_eq!(cycle[3].1, rhai::Position::new(2, 8));
+        }
+        err => panic!("wrong error: {}", err),
+    }
+
+    let graph = engine.module_graph().unwrap();
+    assert_eq!(graph.imports_of(None).collect::<Vec<_>>(), ["a"]);
+    assert_eq!(graph.importers_of("a").collect::<Vec<_>>(), ["", "c"]);
+
+    let cycle = graph.find_cycle().unwrap();
+    assert_eq!(cycle.iter().map(|&(source, path, ..)| (source, path)).collect::<Vec<_>>(), [("a", "b"), ("b", "c"), ("c", "a")]);
+
+    engine.clear_module_graph();
+    assert!(engine.module_graph().unwrap().is_empty());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_module_chained_resolver() {
+    use rhai::module_resolvers::{ChainedModuleResolver, FileModuleResolver, ModuleResolutionErrors};
+    use std::fs;
+
+    let dir = std::env::temp_dir().join(format!("rhai-test-chained-{}", std::process::id()));
+    fs::create_dir_all(dir.join("a")).unwrap();
+    fs::create_dir_all(dir.join("b")).unwrap();
+    fs::write(dir.join("a/bad.rhai"), "export const X = ;").unwrap();
+    fs::write(dir.join("b/bad.rhai"), "export const X = 1 +;").unwrap();
+    fs::write(dir.join("b/good.rhai"), "export const X = 2;").unwrap();
+
+    let mut std_modules = StaticModuleResolver::new();
+    let mut module = Module::new();
+    module.set_var("X", 1 as INT);
+    std_modules.insert("math", module);
+
+    let mut resolver = ChainedModuleResolver::new();
+    resolver.push_prefixed("std::", std_modules);
+    resolver.push_prefixed("plugins::", FileModuleResolver::new_with_path(dir.join("a")));
+    resolver.push_prefixed("plugins::", FileModuleResolver::new_with_path(dir.join("b")));
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(resolver);
+
+    assert_eq!(engine.eval::<INT>(r#"import "std::math" as m; m::X"#).unwrap(), 1);
+    assert_eq!(engine.eval::<INT>(r#"import "plugins::good" as m; m::X"#).unwrap(), 2);
+    assert!(matches!(*engine.run(r#"import "math" as m;"#).unwrap_err(), EvalAltResult::ErrorModuleNotFound(..)));
+    assert!(matches!(*engine.run(r#"import "plugins::math" as m;"#).unwrap_err(), EvalAltResult::ErrorModuleNotFound(..)));
+
+    match *engine.run(r#"import "plugins::bad" as m;"#).unwrap_err() {
+        EvalAltResult::ErrorInModule(path, err, ..) => {
+            assert_eq!(path, "plugins::bad");
+            match *err {
+                EvalAltResult::ErrorSystem(_, err) => assert_eq!(err.downcast_ref::<ModuleResolutionErrors>().unwrap().errors.len(), 2),
+                err => panic!("wrong error: {}", err),
+            }
+        }
+        err => panic!("wrong error: {}", err),
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_module_use_symbols() {
+    let mut resolver = StaticModuleResolver::new();
+    let mut module = Module::new();
+    module.set_var("answer", 42 as INT);
+    module.set_native_fn("calc", |x: INT| Ok(x + 1));
+    resolver.insert("hello", module);
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(resolver);
+
+    assert_eq!(engine.eval::<INT>(r#"import "hello" as h use { answer, calc as inc }; inc(answer) + h::calc(1)"#).unwrap(), 45);
+    assert_eq!(engine.eval::<INT>(r#"import "hello" use { answer as x }; x"#).unwrap(), 42);
+    assert_eq!(engine.eval::<INT>(r#"let answer = 1; import "hello" use { answer }; answer"#).unwrap(), 1);
+
+    assert!(matches!(
+        *engine.run(r#"import "hello" as h use { missing };"#).unwrap_err(),
+        EvalAltResult::ErrorVariableNotFound(name, ..) if name == "hello::missing"
+    ));
+    assert!(matches!(engine.compile(r#"import "hello" use { answer, answer };"#).unwrap_err().err_type(), ParseErrorType::DuplicatedVariable(..)));
+    assert!(matches!(engine.compile(r#"import "hello" use answer;"#).unwrap_err().err_type(), ParseErrorType::MissingToken(..)));
+
+    engine.set_strict_variables(true);
+
+    assert_eq!(engine.eval::<INT>(r#"import "hello" use { answer }; answer"#).unwrap(), 42);
+    assert!(engine.compile(r#"{ import "hello" use { answer }; } answer"#).is_err());
+}
+
+#[cfg(not(feature = "unchecked"))]
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_module_resource_limits() {
+    use rhai::ModuleLimits;
+
+    let engine = Engine::new();
+    let ast = engine.compile("fn work(n) { let x = 0; for i in 0..n { x += i; } x } fn spin() { loop {} }").unwrap();
+
+    let mut module = Module::eval_ast_as_new(Scope::new(), &ast, &engine).unwrap();
+    module.set_resource_limits(ModuleLimits::new().with_max_operations(500));
+    assert_eq!(module.resource_limits().unwrap().max_operations(), 500);
+
+    let mut engine = Engine::new();
+    engine.register_static_module("trusted", Module::eval_ast_as_new(Scope::new(), &ast, &engine).unwrap().into());
+    engine.register_static_module("untrusted", module.into());
+
+    assert_eq!(engine.eval::<INT>("untrusted::work(10)").unwrap(), 45);
+    assert_eq!(engine.eval::<INT>("trusted::work(1000)").unwrap(), 499500);
+
+    // The limit applies to each call, not to the host script
+    assert_eq!(engine.eval::<INT>("let x = 0; for i in 0..100 { x += untrusted::work(10); } x").unwrap(), 4500);
+
+    assert!(matches!(*engine.eval::<INT>("untrusted::work(1000)").unwrap_err(), EvalAltResult::ErrorTooManyOperations(..)));
+    assert!(matches!(*engine.run("untrusted::spin()").unwrap_err(), EvalAltResult::ErrorTooManyOperations(..)));
+
+    // Engine limits still apply
+    engine.set_max_operations(100);
+    assert!(matches!(*engine.eval::<INT>("trusted::work(1000)").unwrap_err(), EvalAltResult::ErrorTooManyOperations(..)));
+
+    #[cfg(not(feature = "no_time"))]
+    {
+        let mut module = Module::eval_ast_as_new(Scope::new(), &ast, &engine).unwrap();
+        module.set_resource_limits(ModuleLimits::new().with_max_duration(std::time::Duration::from_millis(20)));
+
+        let mut engine = Engine::new();
+        engine.register_static_module("untrusted", module.into());
+
+        assert_eq!(engine.eval::<INT>("untrusted::work(10)").unwrap(), 45);
+        assert!(matches!(*engine.run("untrusted::spin()").unwrap_err(), EvalAltResult::ErrorDeadlineExceeded(..)));
+    }
+}
+
+#[test]
+fn test_module_tenant_cache() {
+    let cache = TenantModuleCache::new();
+
+    for (tenant, answer) in [("alice", 1 as INT), ("bob", 2)] {
+        let mut module = Module::new();
+        module.set_var("answer", answer);
+
+        #[cfg(not(feature = "no_closure"))]
+        module.set_var("counter", Dynamic::from(0 as INT).into_shared());
+
+        let mut resolver = StaticModuleResolver::new();
+        resolver.insert("config", module);
+        resolver.insert("constants", Module::new());
+
+        cache.add_tenant(tenant, resolver);
+    }
+
+    assert!(cache.contains_tenant("alice"));
+    assert!(cache.engine_for(&Engine::new(), "eve").is_none());
+
+    let alice = cache.engine_for(&Engine::new(), "alice").unwrap();
+    let bob = cache.engine_for(&Engine::new(), "bob").unwrap();
+
+    alice.run(r#"import "constants" as c;"#).unwrap();
+    assert_eq!(cache.num_cached("alice"), Some(1));
+    assert_eq!(cache.num_cached("bob"), Some(0));
+
+    // Modules holding shared values are never cached
+    #[cfg(not(feature = "no_closure"))]
+    {
+        assert!(matches!(*bob.run(r#"import "config" as c;"#).unwrap_err(), EvalAltResult::ErrorInModule(..)));
+        assert_eq!(cache.num_cached("bob"), Some(0));
+    }
+
+    assert!(cache.clear_cache_for_tenant("alice"));
+    assert_eq!(cache.num_cached("alice"), Some(0));
+
+    assert!(cache.evict_tenant("alice"));
+    assert!(!cache.evict_tenant("alice"));
+    assert!(cache.num_cached("alice").is_none());
+    assert!(matches!(*alice.run(r#"import "constants" as c;"#).unwrap_err(), EvalAltResult::ErrorModuleNotFound(..)));
+
+    bob.run(r#"import "constants" as c;"#).unwrap();
+}
+
+#[cfg(feature = "metadata")]
+#[test]
+fn test_module_api_changes() {
+    use rhai::ApiChange;
+
+    fn snapshot(version: INT) -> String {
+        let mut engine = Engine::new();
+        let mut module = Module::new();
+        module.set_var("VERSION", version);
+        engine.register_fn("double", |x: INT| x * 2);
+
+        if version == 1 {
+            module.set_var("LIMIT", 10 as INT);
+            module.set_native_fn("legacy", || Ok(()));
+        } else {
+            engine.register_fn("double", |x: bool| x);
+            engine.register_fn("triple", |x: INT| x * 3);
+        }
+
+        engine.register_static_module("util", module.into());
+        engine.gen_fn_metadata_to_json(false).unwrap()
+    }
+
+    let v1 = snapshot(1);
+    let v2 = snapshot(2);
+
+    assert!(Engine::compare_fn_metadata(&v1, &v1).unwrap().is_empty());
+
+    let changes = Engine::compare_fn_metadata(&v1, &v2).unwrap();
+    let breaking = changes.iter().filter(|c| c.is_breaking()).map(ToString::to_string).collect::<Vec<_>>();
+    let additions = changes.iter().filter(|c| !c.is_breaking()).map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(breaking.len(), 3, "{:?}", breaking);
+    assert!(breaking.iter().any(|c| c == "function removed: util::legacy()"));
+    assert!(breaking.iter().any(|c| c == "constant removed: util::LIMIT"));
+    assert!(breaking.iter().any(|c| c == "constant changed: util::VERSION = 1 => 2"));
+
+    assert_eq!(additions.len(), 2, "{:?}", additions);
+    assert!(additions.iter().any(|c| c == "function added: double(_: bool) -> bool"));
+    assert!(additions.iter().any(|c| c.starts_with("function added: triple(")));
+
+    assert!(matches!(&changes[0], ApiChange::FunctionAdded(path, ..) if path.is_empty()));
+    assert!(Engine::compare_fn_metadata(&v2, &v1)
+        .unwrap()
+        .iter()
+        .any(|c| matches!(c, ApiChange::FunctionRemoved(path, sig) if path.is_empty() && sig.starts_with("triple("))));
+    assert!(Engine::compare_fn_metadata("not json", &v1).is_err());
+}
@@ -1,4 +1,4 @@
-use rhai::{Dynamic, Engine, EvalAltResult, ImmutableString, NativeCallContext, INT};
+use rhai::{Dynamic, Engine, EvalAltResult, ImmutableString, NativeCallContext, Scope, INT};
 use std::any::TypeId;
 
 #[cfg(not(feature = "no_module"))]
@@ -53,3 +53,172 @@ fn test_native_overload() {
     assert_eq!(engine.eval::<String>(r#"let x = "hello"; let y = "world"; x + y"#).unwrap(), "hello***world");
     assert_eq!(engine.eval::<String>(r#"let x = "hello"; let y = (); x + y"#).unwrap(), "hello Foo!");
 }
+
+#[test]
+fn test_native_context_service() {
+    struct Counter(std::sync::atomic::AtomicUsize);
+    struct Prefix(String);
+
+    let mut engine = Engine::new();
+
+    engine
+        .set_service(Counter(0.into()))
+        .set_service(Prefix("id-".into()))
+        .register_fn("next_id", |ctx: NativeCallContext| -> Result<String, Box<EvalAltResult>> {
+            let counter = ctx.service::<Counter>().ok_or("no counter")?;
+            let prefix = ctx.service::<Prefix>().map_or("", |p| p.0.as_str());
+            Ok(format!("{prefix}{}", counter.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst)))
+        });
+
+    assert_eq!(engine.eval::<String>("next_id(); next_id()").unwrap(), "id-1");
+
+    engine.service_mut::<Prefix>().unwrap().0 = "x".into();
+    assert_eq!(engine.eval::<String>("next_id()").unwrap(), "x2");
+
+    assert_eq!(engine.remove_service::<Prefix>().unwrap().0, "x");
+    assert!(engine.service::<Prefix>().is_none());
+    assert_eq!(engine.eval::<String>("next_id()").unwrap(), "3");
+
+    engine.remove_service::<Counter>();
+    assert!(matches!(*engine.eval::<String>("next_id()").unwrap_err(), EvalAltResult::ErrorRuntime(..)));
+}
+
+#[test]
+fn test_native_context_user_data() {
+    #[derive(Debug, Default)]
+    struct Request {
+        user: String,
+        num_lookups: INT,
+    }
+
+    let mut engine = Engine::new();
+
+    engine.register_fn("user", |ctx: NativeCallContext| ctx.user_data::<Request>().map_or_else(String::new, |r| r.user.clone()));
+
+    #[allow(deprecated)]
+    engine.on_var(|name, _, mut ctx| {
+        if name == "lookups" {
+            let request = ctx.user_data_mut::<Request>().unwrap();
+            request.num_lookups += 1;
+            Ok(Some(request.num_lookups.into()))
+        } else {
+            Ok(None)
+        }
+    });
+
+    let request = Request { user: "alice".into(), ..Default::default() };
+    let (result, request) = engine.run_with_context(request, r#"if user() != "alice" { throw "wrong user"; } let a = lookups; let b = lookups; print(a + b);"#);
+    result.unwrap();
+    let request = request.unwrap();
+    assert_eq!(request.num_lookups, 2);
+
+    assert_eq!(engine.eval::<String>("user()").unwrap(), "");
+
+    let mut scope = Scope::new();
+    let ast = engine.compile("let x = lookups * 10;").unwrap();
+    let (result, request) = engine.run_ast_with_scope_and_context(&mut scope, &ast, request);
+    result.unwrap();
+    let request = request.unwrap();
+    assert_eq!(request.num_lookups, 3);
+    assert_eq!(scope.get_value::<INT>("x").unwrap(), 30);
+
+    // The user data is handed back on errors
+    let (result, request) = engine.run_with_context(request, r#"let x = lookups; throw "oops";"#);
+    assert!(matches!(*result.unwrap_err(), EvalAltResult::ErrorRuntime(..)));
+    assert_eq!(request.unwrap().num_lookups, 4);
+
+    let (result, request) = engine.run_with_context(Request::default(), "let x = ;");
+    assert!(matches!(*result.unwrap_err(), EvalAltResult::ErrorParsing(..)));
+    assert!(request.is_some());
+
+    // The user data cannot be handed back while still shared
+    #[cfg(feature = "internals")]
+    {
+        let stored = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let store = stored.clone();
+
+        #[allow(deprecated)]
+        engine.register_fn("keep", move |ctx: NativeCallContext| store.lock().unwrap().push(ctx.store_data()));
+
+        let (result, request) = engine.run_with_context(Request::default(), "keep();");
+        result.unwrap();
+        assert!(request.is_none());
+
+        stored.lock().unwrap().clear();
+    }
+}
+
+#[test]
+fn test_native_context_undo() {
+    use std::sync::{Arc, Mutex};
+
+    let store = Arc::new(Mutex::new(Vec::<INT>::new()));
+    let mut engine = Engine::new();
+
+    let s = store.clone();
+    engine.register_fn("push", move |context: NativeCallContext, x: INT| -> Result<(), Box<EvalAltResult>> {
+        s.lock().unwrap().push(x);
+        let s = s.clone();
+        context.enlist_undo(move || {
+            s.lock().unwrap().pop();
+        })
+    });
+
+    engine.run("push(1); push(2);").unwrap();
+    assert_eq!(*store.lock().unwrap(), [1, 2]);
+
+    store.lock().unwrap().clear();
+    assert!(engine.run("push(1); push(2); throw 42;").is_err());
+    assert!(store.lock().unwrap().is_empty());
+
+    engine.run("push(1); try { push(2); push(3); throw 42; } catch { push(4); }").unwrap();
+    assert_eq!(*store.lock().unwrap(), [1, 4]);
+
+    store.lock().unwrap().clear();
+    engine.run("push(1); exit();").unwrap();
+    assert_eq!(*store.lock().unwrap(), [1]);
+}
+
+#[test]
+fn test_native_host_state() {
+    #[derive(Default)]
+    struct State {
+        total: INT,
+        calls: usize,
+    }
+
+    let mut engine = Engine::new();
+
+    engine
+        .register_fn_with_state("add", |s: &mut State, x: INT| {
+            s.total += x;
+            s.calls += 1;
+        })
+        .register_fn_with_state("total", |s: &mut State| s.total)
+        .register_fn_with_state("check", |s: &mut State, max: INT| -> Result<INT, Box<EvalAltResult>> {
+            if s.total > max {
+                Err("too large".into())
+            } else {
+                Ok(s.total)
+            }
+        });
+
+    let mut scope = Scope::new();
+    scope.set_host_state(State::default());
+    assert!(scope.has_host_state());
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "add(40); add(2); total()").unwrap(), 42);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "check(50)").unwrap(), 42);
+    assert!(engine.eval_with_scope::<INT>(&mut scope, "check(10)").is_err());
+    assert_eq!(scope.with_host_state(|s: &mut State| s.calls), Some(2));
+    assert_eq!(scope.with_host_state(|s: &mut String| s.len()), None);
+
+    // Host state of the wrong type
+    let mut scope = Scope::new();
+    scope.set_host_state(String::new());
+    assert!(engine.eval_with_scope::<INT>(&mut scope, "total()").is_err());
+
+    scope.clear_host_state();
+    assert!(!scope.has_host_state());
+    assert!(engine.eval_with_scope::<INT>(&mut scope, "total()").is_err());
+}
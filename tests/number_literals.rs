@@ -55,3 +55,56 @@ fn test_binary_literal() {
     #[cfg(not(feature = "no_float"))]
     assert!(engine.compile("0b101.101").is_err());
 }
+
+#[test]
+fn test_number_literal_separators() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("let x = 1_000_000; x").unwrap(), 1_000_000);
+    assert_eq!(engine.eval::<INT>("let x = 0x_ff_ff; x").unwrap(), 0xffff);
+    assert_eq!(engine.eval::<INT>("let x = -0x10; x").unwrap(), -16);
+    assert_eq!(engine.eval::<INT>("let x = -0b1_0000; x").unwrap(), -16);
+    assert_eq!(engine.eval::<INT>("let x = 0; for i in 0x0_0..0x0_a { x += i; } x").unwrap(), 45);
+    assert_eq!(engine.eval::<INT>("let x = 0; for i in -0o1_0..=-0o7 { x += i; } x").unwrap(), -15);
+
+    assert_eq!(
+        engine
+            .eval::<INT>(
+                "
+                    switch -0x1_0 {
+                        0x10 => 1,
+                        -0x1_0 => 2,
+                        0b1_0000..0b10_0000 => 3,
+                        _ => 4
+                    }
+                "
+            )
+            .unwrap(),
+        2
+    );
+    assert_eq!(engine.eval::<INT>("switch 0x18 { 0x10 => 1, 0b1_0000..0b10_0000 => 3, _ => 4 }").unwrap(), 3);
+}
+
+#[test]
+fn test_custom_literal_prefix() {
+    let mut engine = Engine::new();
+
+    assert!(engine.compile("0q123").is_err());
+
+    engine.register_literal_prefix('q', 4).unwrap();
+    engine.register_literal_prefix('h', 16).unwrap();
+
+    assert_eq!(engine.eval::<INT>("let x = 0q1_230; x").unwrap(), 108);
+    assert_eq!(engine.eval::<INT>("let x = 0hFF_00; x").unwrap(), 0xff00);
+    assert_eq!(engine.eval::<INT>("let x = -0h10; x").unwrap(), -16);
+    assert_eq!(engine.eval::<INT>("let x = 0; for i in 0q0..0q10 { x += i; } x").unwrap(), 6);
+    assert_eq!(engine.eval::<INT>("switch 0x1f { 0h1F => 1, _ => 2 }").unwrap(), 1);
+
+    assert!(engine.compile("0q4").is_err());
+    assert!(engine.compile("0Q1").is_err());
+
+    assert!(engine.register_literal_prefix('x', 10).is_err());
+    assert!(engine.register_literal_prefix('e', 10).is_err());
+    assert!(engine.register_literal_prefix('_', 10).is_err());
+    assert!(engine.register_literal_prefix('z', 37).is_err());
+}
@@ -1,5 +1,5 @@
 #![cfg(not(feature = "unchecked"))]
-use rhai::{Engine, EvalAltResult, INT};
+use rhai::{Engine, EvalAltResult, ProgressControl, INT};
 
 #[test]
 fn test_max_operations() {
@@ -8,11 +8,11 @@ fn test_max_operations() {
     engine.set_optimization_level(rhai::OptimizationLevel::None);
     engine.set_max_operations(500);
 
-    engine.on_progress(|count| {
-        if count % 100 == 0 {
-            println!("{count}");
+    engine.on_progress(|info| {
+        if info.operations % 100 == 0 {
+            println!("{}", info.operations);
         }
-        None
+        ProgressControl::Continue
     });
 
     engine.run("let x = 0; while x < 20 { x += 1; }").unwrap();
@@ -49,11 +49,11 @@ fn test_max_operations_functions() {
     let mut engine = Engine::new();
     engine.set_max_operations(500);
 
-    engine.on_progress(|count| {
-        if count % 100 == 0 {
-            println!("{count}");
+    engine.on_progress(|info| {
+        if info.operations % 100 == 0 {
+            println!("{}", info.operations);
         }
-        None
+        ProgressControl::Continue
     });
 
     engine
@@ -107,11 +107,11 @@ fn test_max_operations_eval() {
     let mut engine = Engine::new();
     engine.set_max_operations(500);
 
-    engine.on_progress(|count| {
-        if count % 100 == 0 {
-            println!("{count}");
+    engine.on_progress(|info| {
+        if info.operations % 100 == 0 {
+            println!("{}", info.operations);
         }
-        None
+        ProgressControl::Continue
     });
 
     assert!(matches!(
@@ -134,9 +134,153 @@ fn test_max_operations_progress() {
     engine.set_optimization_level(rhai::OptimizationLevel::None);
     engine.set_max_operations(500);
 
-    engine.on_progress(|count| if count < 100 { None } else { Some((42 as INT).into()) });
+    engine.on_progress(|info| if info.operations < 100 { ProgressControl::Continue } else { ProgressControl::Abort((42 as INT).into()) });
 
     assert!(matches!(
         *engine.run("for x in 0..500 {}").unwrap_err(),
         EvalAltResult::ErrorTerminated(x, ..) if x.as_int().unwrap() == 42));
 }
+
+#[test]
+fn test_max_operations_progress_info() {
+    use std::sync::{Arc, RwLock};
+
+    let log = Arc::new(RwLock::new(Vec::<(u64, usize)>::new()));
+    let logger = log.clone();
+
+    let mut engine = Engine::new();
+
+    engine.on_progress(move |info| {
+        let mut log = logger.write().unwrap();
+
+        if info.operations == 50 || (info.call_depth > 0 && !log.iter().any(|&(.., depth)| depth > 0)) {
+            log.push((info.operations, info.call_depth));
+        }
+
+        ProgressControl::Continue
+    });
+
+    #[cfg(not(feature = "no_function"))]
+    engine.run("fn foo() { 42 } let s = 0; for i in 0..100 { s += i; } foo()").unwrap();
+    #[cfg(feature = "no_function")]
+    engine.run("let s = 0; for i in 0..100 { s += i; }").unwrap();
+
+    let log = log.read().unwrap();
+    assert_eq!(log[0], (50, 0));
+    #[cfg(not(feature = "no_function"))]
+    assert!(matches!(log[1], (.., 1)));
+}
+
+#[cfg(not(feature = "no_time"))]
+#[test]
+fn test_deadline() {
+    use std::time::{Duration, Instant};
+
+    let mut engine = Engine::new();
+
+    engine.set_deadline(Instant::now() + Duration::from_millis(50));
+
+    assert!(matches!(*engine.run("loop {}").unwrap_err(), EvalAltResult::ErrorDeadlineExceeded(..)));
+    assert!(matches!(*engine.run("let x = 0; try { loop { x += 1; } } catch { x = -1; } x").unwrap_err(), EvalAltResult::ErrorDeadlineExceeded(..)));
+
+    engine.set_deadline(Instant::now() + Duration::from_secs(3600));
+
+    assert_eq!(engine.eval::<INT>("let x = 0; for i in 0..100 { x += i; } x").unwrap(), 4950);
+
+    engine.clear_deadline();
+
+    assert_eq!(engine.eval::<INT>("let x = 0; for i in 0..100 { x += i; } x").unwrap(), 4950);
+}
+
+#[test]
+fn test_max_operations_profile() {
+    let mut engine = Engine::new();
+    engine.set_max_operations(1000);
+    engine.set_profile_operations(true);
+
+    let script = "let x = 0;\nfor i in 0..10 {\n    x += i;\n}\n";
+    engine.run(script).unwrap();
+
+    let profile = engine.operations_profile().unwrap();
+    assert!(!profile.is_empty());
+    assert_eq!(profile.function_count(""), Some(profile.total()));
+
+    #[cfg(not(feature = "no_position"))]
+    {
+        let (source, line, ..) = profile.hot_spots(1)[0];
+        assert_eq!(source, "");
+        assert_eq!(line, 3);
+        assert_eq!(profile.iter().map(|(.., n)| n).sum::<u64>(), profile.total());
+    }
+
+    engine.clear_operations_profile();
+    assert!(engine.operations_profile().unwrap().is_empty());
+
+    #[cfg(not(feature = "no_function"))]
+    {
+        let err = engine.run("fn busy(n) { for i in 0..n {} }\nbusy(5);\nbusy(100000);").unwrap_err();
+        assert!(matches!(*err, EvalAltResult::ErrorTooManyOperations(..)));
+
+        let profile = engine.operations_profile().unwrap();
+        assert!(profile.function_count("busy").unwrap() > profile.function_count("").unwrap());
+        assert_eq!(profile.functions().map(|(.., n)| n).sum::<u64>(), profile.total());
+    }
+
+    engine.set_profile_operations(false);
+    assert!(engine.operations_profile().is_none());
+}
+
+#[test]
+fn test_max_operations_warning() {
+    use std::sync::{Arc, RwLock};
+
+    let warnings = Arc::new(RwLock::new(Vec::new()));
+    let logger = warnings.clone();
+
+    let mut engine = Engine::new();
+    engine.on_operations_warning(50, move |ops, max, _| logger.write().unwrap().push((ops, max)));
+
+    // No limit, no warning
+    engine.run("let s = 0; for i in 0..1000 { s += i; } s").unwrap();
+    assert!(warnings.read().unwrap().is_empty());
+
+    engine.set_max_operations(500);
+
+    engine.run("let x = 0; while x < 20 { x += 1; }").unwrap();
+    assert!(warnings.read().unwrap().is_empty());
+
+    // Warned once per evaluation
+    assert!(matches!(*engine.run("let s = 0; for i in 0..1000 { s += i; } s").unwrap_err(), EvalAltResult::ErrorTooManyOperations(..)));
+    assert_eq!(*warnings.read().unwrap(), [(250, 500)]);
+
+    assert!(matches!(*engine.run("let s = 0; for i in 0..1000 { s += i; } s").unwrap_err(), EvalAltResult::ErrorTooManyOperations(..)));
+    assert_eq!(*warnings.read().unwrap(), [(250, 500), (250, 500)]);
+}
+
+#[test]
+fn test_max_operations_tick() {
+    use std::sync::{Arc, RwLock};
+
+    let ticks = Arc::new(RwLock::new(Vec::new()));
+    let logger = ticks.clone();
+    let total = Arc::new(RwLock::new(0));
+    let counter = total.clone();
+
+    let mut engine = Engine::new();
+    engine.on_tick(100, move |ops| logger.write().unwrap().push(ops));
+    engine.on_progress(move |info| {
+        *counter.write().unwrap() = info.operations;
+        ProgressControl::Continue
+    });
+
+    // Ticks at every 100th operation, never at zero
+    for script in ["let x = 0; while x < 2 { x += 1; }", "let s = 0; for i in 0..1000 { s += i; } s"] {
+        ticks.write().unwrap().clear();
+        engine.run(script).unwrap();
+        let total = *total.read().unwrap();
+        assert_eq!(*ticks.read().unwrap(), (1..=total / 100).map(|n| n * 100).collect::<Vec<_>>());
+    }
+
+    assert!(*total.read().unwrap() >= 1000);
+    assert_eq!(ticks.read().unwrap().len() as u64, *total.read().unwrap() / 100);
+}
@@ -71,3 +71,73 @@ fn test_ops_custom_types() {
     assert!(!engine.eval::<bool>("let x = new_ts1(); x == ()").unwrap());
     assert!(engine.eval::<bool>("let x = new_ts1(); x != ()").unwrap());
 }
+
+#[test]
+#[cfg(not(feature = "no_object"))]
+fn test_ops_type_operators() {
+    #[derive(Debug, Clone)]
+    struct Vec2(INT, INT);
+
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<Vec2>("Vec2")
+        .register_fn("vec2", Vec2)
+        .register_get("x", |v: &mut Vec2| v.0)
+        .register_get("y", |v: &mut Vec2| v.1)
+        .register_fn("+", |_: Vec2, _: Vec2| Vec2(0, 0))
+        .register_type_operator("+", |a: Vec2, b: Vec2| Vec2(a.0 + b.0, a.1 + b.1))
+        .register_type_operator("-", |a: &mut Vec2| Vec2(-a.0, -a.1))
+        .register_type_operator("*", |a: Vec2, k: INT| Vec2(a.0 * k, a.1 * k));
+
+    assert_eq!(engine.eval::<INT>("let v = vec2(1, 2) + vec2(3, 4); v.x * 10 + v.y").unwrap(), 46);
+    assert_eq!(engine.eval::<INT>("let v = vec2(1, 2); v += vec2(3, 4); v.x * 10 + v.y").unwrap(), 46);
+    assert_eq!(engine.eval::<INT>("let v = -vec2(1, 2) * 3; v.x * 10 + v.y").unwrap(), -36);
+    assert!(engine.eval::<INT>("let v = 3 * vec2(1, 2); v.x").is_err());
+
+    #[cfg(not(feature = "no_custom_syntax"))]
+    {
+        engine.register_custom_operator("dot", 180).unwrap();
+        engine.register_type_operator("dot", |a: Vec2, b: Vec2| a.0 * b.0 + a.1 * b.1);
+
+        assert_eq!(engine.eval::<INT>("vec2(1, 2) dot vec2(3, 4) + 1").unwrap(), 12);
+        assert!(engine.eval::<INT>("1 dot 2").is_err());
+    }
+}
+
+#[test]
+#[cfg(not(feature = "no_function"))]
+#[cfg(not(feature = "no_object"))]
+fn test_ops_script_defined() {
+    #[derive(Debug, Clone)]
+    struct Vec2(INT, INT);
+
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<Vec2>("Vec2")
+        .register_fn("vec2", Vec2)
+        .register_get("x", |v: &mut Vec2| v.0)
+        .register_get("y", |v: &mut Vec2| v.1);
+
+    let script = "
+        fn +(a: Vec2, b: Vec2) { vec2(a.x + b.x, a.y + b.y) }
+        fn *(a: Vec2, k: int) { vec2(a.x * k, a.y * k) }
+        fn -(a: Vec2) { vec2(-a.x, -a.y) }
+    ";
+
+    let run = |engine: &Engine, code: &str| engine.eval::<INT>(&format!("{script}\n{code}"));
+
+    assert_eq!(run(&engine, "let v = vec2(1, 2) + vec2(3, 4); v.x * 10 + v.y").unwrap(), 46);
+    assert_eq!(run(&engine, "let v = vec2(1, 2); v += vec2(3, 4); v.x * 10 + v.y").unwrap(), 46);
+    assert_eq!(run(&engine, "let v = -vec2(1, 2) * 3; v.x * 10 + v.y").unwrap(), -36);
+    assert!(matches!(*run(&engine, "let v = 3 * vec2(1, 2); v.x").unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+
+    // Native functions and built-in operators take precedence
+    assert_eq!(engine.eval::<INT>("fn +(a, b) { 0 } 40 + 2").unwrap(), 42);
+
+    engine.register_fn("+", |a: Vec2, b: Vec2| Vec2(a.0 - b.0, a.1 - b.1));
+    assert_eq!(run(&engine, "let v = vec2(1, 2) + vec2(3, 4); v.x * 10 + v.y").unwrap(), -22);
+
+    assert!(engine.compile("fn +=(a, b) { 0 }").is_err());
+}
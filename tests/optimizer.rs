@@ -1,5 +1,7 @@
 #![cfg(not(feature = "no_optimize"))]
-use rhai::{Engine, FuncRegistration, Module, OptimizationLevel, Scope, INT};
+use rhai::{DefaultOptimizerPass, Engine, FuncRegistration, Module, OptimizationLevel, Scope, AST, INT};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[test]
 fn test_optimizer() {
@@ -198,6 +200,80 @@ fn test_optimizer_full() {
     assert_eq!(scope.get_value::<TestStruct>("FOO").unwrap().0, 42);
 }
 
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_optimizer_script_fn() {
+    let mut engine = Engine::new();
+
+    engine.set_optimization_level(OptimizationLevel::Full);
+
+    let ast = engine
+        .compile(
+            "
+                fn area(w, h) { let a = w * h; if a > 100 { 100 } else { a } }
+                area(6, 7)
+            ",
+        )
+        .unwrap();
+
+    // Make sure the call is optimized away
+    assert!(!format!("{ast:?}").contains(r#"name: "area""#));
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 42);
+
+    // Calls within functions are evaluated as well
+    let ast = engine
+        .compile(
+            "
+                fn double(x) { x * 2 }
+                fn foo() { double(21) }
+                42
+            ",
+        )
+        .unwrap();
+
+    assert!(!format!("{ast:?}").contains(r#"name: "double""#));
+
+    // Recursive functions and functions with loops are not evaluated
+    let ast = engine
+        .compile(
+            "
+                fn fib(n) { if n < 2 { n } else { fib(n - 1) + fib(n - 2) } }
+                fn sum(n) { let s = 0; for i in 0..n { s += i; } s }
+                fib(6) + sum(10)
+            ",
+        )
+        .unwrap();
+
+    let text_ast = format!("{:?}", ast.clone().clear_functions());
+    assert!(text_ast.contains(r#"name: "fib""#));
+    assert!(text_ast.contains(r#"name: "sum""#));
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 53);
+
+    // Functions larger than the threshold are not evaluated
+    engine.set_optimizer_options(rhai::OptimizerOptions::new().with_max_inline_fn_size(3));
+
+    let ast = engine.compile("fn area(w, h) { let a = w * h; if a > 100 { 100 } else { a } } area(6, 7)").unwrap();
+
+    assert!(format!("{ast:?}").contains(r#"name: "area""#));
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 42);
+
+    // Overloads with typed parameters are resolved by the types of the arguments
+    engine.set_optimizer_options(rhai::OptimizerOptions::new());
+
+    for level in [OptimizationLevel::Simple, OptimizationLevel::Full] {
+        engine.set_optimization_level(level);
+
+        let script = r#"fn f(x: int) { "int" } fn f(x: string) { "string" } f("a") + "/" + f(1)"#;
+        assert_eq!(engine.eval::<String>(script).unwrap(), "string/int");
+
+        #[cfg(not(feature = "no_float"))]
+        {
+            let script = r#"fn f(x: int) { "int" } fn f(x) { "any" } f(1.5) + "/" + f(1)"#;
+            assert_eq!(engine.eval::<String>(script).unwrap(), "any/int");
+        }
+    }
+}
+
 #[test]
 fn test_optimizer_volatile() {
     let mut engine = Engine::new();
@@ -223,3 +299,35 @@ fn test_optimizer_volatile() {
     // Make sure the call is optimized away
     assert!(!text_ast.contains(r#"name: "foo""#));
 }
+
+#[test]
+fn test_optimizer_passes() {
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::Simple);
+
+    // No passes - nothing is optimized
+    engine.set_optimizer_passes(vec![]);
+
+    let ast = engine.compile("if false { 42 } else { 123 }").unwrap();
+    assert!(format!("{ast:?}").contains("If"));
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 123);
+
+    // Custom pass after the default pass
+    engine.set_optimizer_passes(vec![Box::new(DefaultOptimizerPass)]);
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let count = counter.clone();
+
+    engine.add_optimizer_pass(move |_: &Engine, _: Option<&Scope>, ast: &mut AST, level: OptimizationLevel| {
+        assert_eq!(level, OptimizationLevel::Simple);
+        count.fetch_add(1, Ordering::Relaxed);
+        ast.clear_statements();
+    });
+
+    assert_eq!(engine.optimizer_passes().count(), 2);
+    assert_eq!(engine.optimizer_passes().next().unwrap().name(), "default");
+
+    let ast = engine.compile("if false { 42 } else { 123 }").unwrap();
+    assert!(engine.eval_ast::<()>(&ast).is_ok());
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+}
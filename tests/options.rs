@@ -1,4 +1,4 @@
-use rhai::{Engine, Scope, INT};
+use rhai::{Engine, EvalAltResult, FuncRegistration, Scope, INT};
 
 #[test]
 fn test_options_allow() {
@@ -113,3 +113,83 @@ fn test_options_strict_var() {
         assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "fn foo(z) { y + z } foo(x)").unwrap(), 42);
     }
 }
+
+#[test]
+fn test_options_deterministic() {
+    let mut engine = Engine::new();
+
+    FuncRegistration::new("roll").with_volatility(true).register_into_engine(&mut engine, || 6 as INT);
+    engine.register_fn("double", |x: INT| x * 2);
+
+    assert_eq!(engine.eval::<INT>("roll()").unwrap(), 6);
+
+    engine.set_deterministic(true);
+    assert!(engine.deterministic());
+
+    assert_eq!(engine.eval::<INT>("double(21)").unwrap(), 42);
+    assert!(matches!(*engine.eval::<INT>("roll()").unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+    #[cfg(not(feature = "no_time"))]
+    assert!(matches!(*engine.eval::<()>("timestamp()").unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+
+    engine.set_deterministic(false);
+    assert_eq!(engine.eval::<INT>("roll()").unwrap(), 6);
+}
+
+#[test]
+#[should_panic = "deterministic mode"]
+fn test_options_deterministic_register_volatile() {
+    let mut engine = Engine::new();
+    engine.set_deterministic(true);
+
+    FuncRegistration::new("roll").with_volatility(true).register_into_engine(&mut engine, || 6 as INT);
+}
+
+#[test]
+fn test_options_capabilities() {
+    let mut engine = Engine::new();
+
+    let caps = engine.capabilities();
+    assert!(!caps.version.is_empty());
+    assert_eq!(caps.features.contains(&"sync"), cfg!(feature = "sync"));
+    assert_eq!(caps.features.contains(&"unchecked"), caps.limits.is_empty());
+    assert!(caps.global_modules.iter().any(|m| m.is_standard && m.num_functions > 0));
+    assert!(!caps.strict_variables);
+    assert!(!caps.deterministic);
+    assert!(!caps.has_variable_resolver);
+
+    engine.set_strict_variables(true);
+    #[allow(deprecated)]
+    engine.on_var(|_, _, _| Ok(None));
+    #[cfg(not(feature = "no_module"))]
+    engine.register_static_module("extra", rhai::Module::new().into());
+
+    let caps = engine.capabilities();
+    assert!(caps.strict_variables);
+    assert!(caps.has_variable_resolver);
+    #[cfg(not(feature = "no_module"))]
+    assert_eq!(caps.static_modules, ["extra"]);
+}
+
+#[test]
+fn test_options_cfg_flags() {
+    let mut engine = Engine::new();
+
+    engine.set_cfg_flag("redis", true).set_cfg_flag("max_workers", 4 as INT);
+
+    assert!(engine.eval::<bool>(r#"cfg("redis")"#).unwrap());
+    assert!(!engine.eval::<bool>(r#"cfg("s3")"#).unwrap());
+    assert_eq!(engine.eval::<INT>(r#"cfg("max_workers") * 2"#).unwrap(), 8);
+
+    #[cfg(not(feature = "no_object"))]
+    {
+        assert!(engine.eval::<bool>(r#"engine_info().version != """#).unwrap());
+        assert!(engine.eval::<bool>(r#"engine_info().cfg.redis"#).unwrap());
+        assert_eq!(engine.eval::<INT>(r#"engine_info().cfg.len()"#).unwrap(), 2);
+        #[cfg(not(feature = "no_index"))]
+        assert_eq!(engine.eval::<bool>(r#""sync" in engine_info().features"#).unwrap(), cfg!(feature = "sync"));
+    }
+
+    assert!(engine.remove_cfg_flag("redis").unwrap().as_bool().unwrap());
+    assert!(engine.cfg_flag("redis").is_none());
+    assert!(!engine.eval::<bool>(r#"cfg("redis")"#).unwrap());
+}
@@ -51,3 +51,26 @@ fn test_packages_with_script() {
     assert_eq!(engine.eval::<INT>("foo(41)").unwrap(), 42);
     assert_eq!(engine.eval::<INT>("bar(40)").unwrap(), 42);
 }
+
+#[test]
+fn test_packages_filtered() {
+    let mut module = Module::new();
+    module.set_native_fn("add", |x: INT, y: INT| Ok(x + y));
+    module.set_native_fn("unsafe_reset", || Ok(()));
+    rhai::FuncRegistration::new("now").with_volatility(true).set_into_module(&mut module, || 42 as INT);
+    let module = rhai::Shared::new(module);
+
+    let mut engine = Engine::new();
+    engine.register_global_module_filtered(module.clone(), |f, is_volatile| !is_volatile && !f.name.starts_with("unsafe_"));
+
+    assert_eq!(engine.eval::<INT>("add(40, 2)").unwrap(), 42);
+    assert!(engine.run("unsafe_reset()").is_err());
+    assert!(engine.run("now()").is_err());
+
+    // The original module is untouched
+    let mut engine = Engine::new();
+    engine.register_global_module(module);
+
+    engine.run("unsafe_reset()").unwrap();
+    assert_eq!(engine.eval::<INT>("now()").unwrap(), 42);
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "catch_panics")]
+use rhai::{Engine, EvalAltResult, Position, INT};
+
+#[test]
+fn test_catch_panics() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("boom", |x: INT| -> INT {
+        if x > 0 {
+            panic!("boom at {}", x);
+        }
+        x
+    });
+
+    assert_eq!(engine.eval::<INT>("boom(0)").unwrap(), 0);
+
+    let err = engine.eval::<INT>("let x = 1;\nboom(x)").unwrap_err();
+
+    match *err {
+        EvalAltResult::ErrorInFunctionCall(ref name, .., ref inner, pos) => {
+            assert_eq!(name, "boom");
+            assert_eq!(pos, Position::new(2, 1));
+            assert!(matches!(**inner, EvalAltResult::ErrorSystem(ref msg, ref err) if msg == "Function panicked" && err.to_string() == "boom at 1"));
+        }
+        _ => panic!("unexpected error: {err}"),
+    }
+
+    // The engine is still usable after a panic
+    assert_eq!(engine.eval::<INT>("boom(0) + 42").unwrap(), 42);
+
+    // Panics can be caught by scripts
+    assert_eq!(engine.eval::<INT>("let r = 0; try { boom(1); } catch { r = 42; } r").unwrap(), 42);
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "parallel")]
+use rhai::{Engine, EvalAltResult, INT};
+use std::sync::Arc;
+
+#[test]
+fn test_parallel_spawn() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("let task = spawn(|| 40 + 2); task.join()").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("let x = 40; let task = spawn(|y| x + y, [2]); x = 0; task.join()").unwrap(), 42);
+    assert!(engine.eval::<bool>("let task = spawn(|| 42); task.is_done").unwrap());
+    assert_eq!(engine.eval::<INT>("fn foo(x) { x * 2 } let task = spawn(Fn(\"foo\"), [21]); task.join() + task.join()").unwrap(), 84);
+
+    assert!(matches!(
+        *engine.run("let task = spawn(|| throw 42); task.join()").unwrap_err(),
+        EvalAltResult::ErrorInFunctionCall(.., err, _) if matches!(*err, EvalAltResult::ErrorRuntime(ref v, ..) if v.as_int().unwrap() == 42)
+    ));
+    assert!(engine.run("let task = spawn(|| throw 42); try { task.join() } catch {} task.join()").is_err());
+}
+
+#[test]
+fn test_parallel_executor() {
+    let worker = Arc::new(Engine::new());
+    let mut engine = Engine::new();
+
+    engine.set_task_executor(move |task| {
+        let worker = worker.clone();
+        std::thread::spawn(move || task.run(&worker));
+    });
+
+    assert_eq!(
+        engine
+            .eval::<INT>(
+                "
+                    fn sum(n) { let total = 0; for x in 0..=n { total += x; } total }
+
+                    let tasks = [];
+                    for n in 0..10 { tasks.push(spawn(Fn(\"sum\"), [n * 100])); }
+
+                    let total = 0;
+                    for task in tasks { total += task.join(); }
+                    total
+                "
+            )
+            .unwrap(),
+        (0..10).map(|n| (0..=n * 100).sum::<INT>()).sum::<INT>()
+    );
+}
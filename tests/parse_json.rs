@@ -191,3 +191,109 @@ mod with_metadata {
             if msg == "parse_json (&str | ImmutableString | String)" && *pos == Position::new(2, 17)));
     }
 }
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+fn test_parse_json_stream() {
+    use rhai::StreamReader;
+
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    let input = "{\"a\": 1, \"b\": \"x\"}\n\n{\"a\": 2, \"b\": \"y\"}\r\n{\"a\": 39, \"b\": \"z\"}";
+    scope.push("input", StreamReader::new(std::io::Cursor::new(input)));
+
+    let result = engine
+        .eval_with_scope::<INT>(
+            &mut scope,
+            r#"
+                let total = 0;
+                let names = "";
+                let n = input.parse_json_stream(|rec, i| { total += rec.a * (i + 1); names += rec.b; });
+                total + n * 1000 + names.len
+            "#,
+        )
+        .unwrap();
+
+    assert_eq!(result, 1 + 4 + 117 + 3000 + 3);
+
+    scope.set_value("input", StreamReader::new(std::io::Cursor::new(input)));
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "input.parse_json_stream(|rec| rec.a < 2)").unwrap(), 2);
+}
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+fn test_parse_csv_stream() {
+    use rhai::StreamReader;
+
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    let input = "name,note\nfoo,\"hello, world\"\nbar,\"say \"\"hi\"\"\nthere\"\n";
+
+    scope.push("input", StreamReader::new(std::io::Cursor::new(input)));
+
+    assert_eq!(
+        engine
+            .eval_with_scope::<String>(&mut scope, r#"let s = ""; input.parse_csv_stream(|r| s += `${r.len()}:${r[1]}|`); s"#)
+            .unwrap(),
+        "2:note|2:hello, world|2:say \"hi\"\nthere|"
+    );
+
+    scope.set_value("input", StreamReader::new(std::io::Cursor::new(input)));
+
+    assert_eq!(
+        engine
+            .eval_with_scope::<String>(&mut scope, r#"let s = ""; let n = input.parse_csv_stream(true, |r| s += r.name); `${n}:${s}`"#)
+            .unwrap(),
+        "2:foobar"
+    );
+}
+
+#[test]
+#[cfg(not(feature = "unchecked"))]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+fn test_parse_stream_max_operations() {
+    use rhai::StreamReader;
+
+    let mut engine = Engine::new();
+    engine.set_max_operations(100);
+
+    let mut scope = Scope::new();
+    let input = "1,2,3\n".repeat(1000);
+
+    scope.push("input", StreamReader::new(std::io::Cursor::new(input)));
+
+    assert!(matches!(*engine.run_with_scope(&mut scope, "input.parse_csv_stream(|r| ())").unwrap_err(), EvalAltResult::ErrorTooManyOperations(..)));
+}
+
+#[test]
+#[cfg(not(feature = "unchecked"))]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+fn test_parse_stream_operations_written_back() {
+    use rhai::StreamReader;
+
+    let mut engine = Engine::new();
+    engine.set_max_operations(500);
+
+    let mut scope = Scope::new();
+
+    scope.push("input", StreamReader::new(std::io::Cursor::new("1\n2\n3\n")));
+    engine.run_with_scope(&mut scope, "input.parse_csv_stream(|r| { let s = 0; for i in 0..50 { s += i; } })").unwrap();
+
+    scope.set_value("input", StreamReader::new(std::io::Cursor::new("1\n2\n3\n")));
+    engine.run_with_scope(&mut scope, "let s = 0; for i in 0..100 { s += i; }").unwrap();
+
+    scope.set_value("input", StreamReader::new(std::io::Cursor::new("1\n2\n3\n")));
+    assert!(matches!(
+        *engine
+            .run_with_scope(&mut scope, "input.parse_csv_stream(|r| { let s = 0; for i in 0..50 { s += i; } }); let s = 0; for i in 0..100 { s += i; }")
+            .unwrap_err(),
+        EvalAltResult::ErrorTooManyOperations(..)
+    ));
+}
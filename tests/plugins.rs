@@ -106,6 +106,23 @@ fn test_plugins_package() {
     assert_eq!(engine.eval::<INT>("test::MYSTIC_NUMBER").unwrap(), 42);
 }
 
+#[test]
+fn test_plugins_static_module() {
+    fn make_module() -> rhai::Shared<Module> {
+        exported_static_module!(test::special_array_package)
+    }
+
+    let m1 = make_module();
+    let m2 = make_module();
+    assert!(rhai::Shared::ptr_eq(&m1, &m2));
+
+    let mut engine = Engine::new();
+    engine.register_static_module("test", m1);
+
+    assert_eq!(engine.eval::<INT>("test::MYSTIC_NUMBER").unwrap(), 42);
+    assert_eq!(engine.eval::<INT>("let a = [1, 2, 3]; test::test(a, 2)").unwrap(), 6);
+}
+
 #[test]
 fn test_plugins_parameters() {
     #[export_module]
@@ -184,3 +201,29 @@ mod handle {
         assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "world.len").unwrap(), 1);
     }
 }
+
+#[cfg(feature = "metadata")]
+mod metadata_export {
+    use rhai::plugin::*;
+    use rhai::INT;
+
+    /// Test module.
+    #[export_module(export_metadata)]
+    pub mod metadata_test_module {
+        /// Calculate the answer.
+        pub fn answer(x: INT) -> INT {
+            x + 42
+        }
+    }
+
+    #[test]
+    fn test_plugins_export_metadata() {
+        let json = std::fs::read_to_string(concat!(env!("OUT_DIR"), "/metadata_test_module.json")).unwrap();
+
+        assert!(json.contains(r#""name": "metadata_test_module""#));
+        assert!(json.contains(r#""/// Test module.""#));
+        assert!(json.contains(r#""name": "answer""#));
+        assert!(json.contains(r#""/// Calculate the answer.""#));
+        assert_eq!(metadata_test_module::answer(0), 42);
+    }
+}
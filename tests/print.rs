@@ -96,3 +96,101 @@ fn test_print_custom_type() {
         .unwrap()
         .contains(r#""e": hello: 42"#));
 }
+
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_print_pretty() {
+    let logbook = Arc::new(RwLock::new(Vec::<String>::new()));
+    let log = logbook.clone();
+
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<MyStruct>("MyStruct")
+        .register_fn("to_debug", |x: &mut MyStruct| x.to_string())
+        .register_fn("new_ts", || MyStruct { field: 42 })
+        .on_print(move |s| log.write().unwrap().push(s.to_string()));
+
+    engine.run(r#"pprint(#{ a: [1, 2], "b c": new_ts(), d: #{} })"#).unwrap();
+
+    assert_eq!(logbook.read().unwrap()[0], "#{\n  a: [\n    1,\n    2,\n  ],\n  \"b c\": <MyStruct> hello: 42,\n  d: #{},\n}");
+
+    let value = engine.eval::<Dynamic>(r#"[[[1, 2, 3]], "xxxxxxxxxx", [0, 1, 2, 3, 4]]"#).unwrap();
+    let options = rhai::PrettyPrintOptions::new().with_indent(1).with_max_depth(2).with_max_items(3).with_max_string_len(4);
+
+    assert_eq!(value.to_pretty_string(options), "[\n [\n  [... 3 items],\n ],\n \"xxxx\"... 10 chars,\n [\n  0,\n  1,\n  2,\n  ... 2 more\n ],\n]");
+
+    // The prototype of an object map is not printed
+    let value = engine.eval::<Dynamic>("let m = #{ x: 1 }; m.set_prototype(#{ f: || 0 }); m").unwrap();
+    assert_eq!(value.to_pretty_string(rhai::PrettyPrintOptions::new()), "#{\n  x: 1,\n}");
+    let value = engine.eval::<Dynamic>("let m = #{}; m.set_prototype(#{ f: || 0 }); m").unwrap();
+    assert_eq!(value.to_pretty_string(rhai::PrettyPrintOptions::new()), "#{}");
+
+    #[cfg(not(feature = "no_closure"))]
+    {
+        let value = Dynamic::from_map(Default::default()).into_shared();
+        let mut map = value.clone();
+        map.write_lock::<rhai::Map>().unwrap().insert("me".into(), value.clone());
+
+        assert_eq!(value.to_pretty_string(rhai::PrettyPrintOptions::new()), "#{\n  me: <cycle>,\n}");
+    }
+}
+
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_print_redact() {
+    let logbook = Arc::new(RwLock::new(Vec::<String>::new()));
+
+    let log1 = logbook.clone();
+    let log2 = logbook.clone();
+
+    let mut engine = Engine::new();
+
+    engine
+        .on_print(move |s| log1.write().unwrap().push(s.to_string()))
+        .on_debug(move |s, _, _| log2.write().unwrap().push(s.to_string()))
+        .on_redact(|name, value| match name {
+            Some("password") => Some("***".into()),
+            _ if value.is_string() && value.clone().into_string().unwrap().starts_with("sk-") => Some("<key>".into()),
+            _ => None,
+        });
+
+    let result = engine
+        .eval::<String>(
+            r#"
+                let user = #{ name: "bob", password: "secret", keys: ["sk-123", "pk-456"] };
+                print(user);
+                debug(user.keys);
+                pprint(user.password);
+                user.password
+            "#,
+        )
+        .unwrap();
+
+    // The value itself is not changed
+    assert_eq!(result, "secret");
+
+    let logbook = logbook.read().unwrap();
+    assert_eq!(logbook.len(), 3);
+    assert!(logbook[0].contains(r#""password": "***""#));
+    assert!(logbook[0].contains(r#""name": "bob""#));
+    assert_eq!(logbook[1], r#"["<key>", "pk-456"]"#);
+    assert_eq!(logbook[2], r#""secret""#);
+
+    let value = engine.eval::<Dynamic>(r#"#{ password: 42, nested: [#{ password: 0 }] }"#).unwrap();
+
+    assert_eq!(engine.redact_value(None, &value).to_pretty_string(rhai::PrettyPrintOptions::new()), "#{\n  nested: [\n    #{\n      password: \"***\",\n    },\n  ],\n  password: \"***\",\n}");
+
+    // The prototype of an object map is not redacted
+    let mut engine = Engine::new();
+    engine.on_redact(|name, _| name.map(|_| "***".into()));
+
+    let value = engine.eval::<Dynamic>("let m = #{ x: 1 }; m.set_prototype(#{ f: || this.x + 41 }); m").unwrap();
+
+    let mut scope = Scope::new();
+    scope.push("m", engine.redact_value(None, &value));
+
+    assert_eq!(engine.eval_with_scope::<String>(&mut scope, "m.f()").unwrap(), "***41");
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "regex")]
+use rhai::{Engine, EvalAltResult};
+
+#[test]
+fn test_regex() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>(r#"type_of(regex("a+"))"#).unwrap(), "Regex");
+    assert_eq!(engine.eval::<String>(r#"regex("a+").pattern"#).unwrap(), "a+");
+    assert!(engine.eval::<bool>(r#"regex("^\\d+$").is_match("123")"#).unwrap());
+    assert!(!engine.eval::<bool>(r#"regex("^\\d+$").is_match("abc123")"#).unwrap());
+    assert_eq!(engine.eval::<String>(r#"regex("\\d+").find("abc123def456")"#).unwrap(), "123");
+    assert!(engine.eval::<()>(r#"regex("\\d+").find("abc")"#).is_ok());
+    assert_eq!(engine.eval::<String>(r#"regex("(?<word>\\w+)@").replace("a@b@", "[${word}]")"#).unwrap(), "[a]b@");
+    assert_eq!(engine.eval::<String>(r#"regex("(?<word>\\w+)@").replace_all("a@b@", "[${word}]")"#).unwrap(), "[a][b]");
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        assert_eq!(
+            engine
+                .eval::<rhai::Array>(r#"regex("\\d+").find_all("a1b22c333")"#)
+                .unwrap()
+                .into_iter()
+                .map(|v| v.into_string().unwrap())
+                .collect::<Vec<_>>(),
+            ["1", "22", "333"]
+        );
+        assert_eq!(engine.eval::<rhai::Array>(r#"regex("\\s*,\\s*").split("a , b,c")"#).unwrap().len(), 3);
+    }
+
+    #[cfg(not(feature = "no_object"))]
+    {
+        assert_eq!(
+            engine
+                .eval::<String>(r#"let m = regex("(?<year>\\d{4})-(?<month>\\d{2})").captures("Date: 2024-05"); m.year + "/" + m.month"#)
+                .unwrap(),
+            "2024/05"
+        );
+        assert!(engine.eval::<()>(r#"regex("(?<year>\\d{4})").captures("abc")"#).is_ok());
+        assert!(engine.eval::<()>(r#"regex("(?<a>x)|(?<b>y)").captures("y").a"#).is_ok());
+
+        #[cfg(not(feature = "no_index"))]
+        assert_eq!(
+            engine
+                .eval::<String>(r#"let s = ""; for m in regex("(?<key>\\w+)=(?<value>\\w+)").captures_all("a=1, b=2") { s += m.key + m.value; } s"#)
+                .unwrap(),
+            "a1b2"
+        );
+    }
+
+    assert!(matches!(*engine.eval::<()>(r#"regex("(")"#).unwrap_err(), EvalAltResult::ErrorRuntime(..)));
+}
@@ -756,3 +756,112 @@ fn test_serde_scope() {
     assert!(scope.get_value::<bool>("y").unwrap());
     assert_eq!(scope.get_value::<String>("z").unwrap(), "serde::test_serde_scope::TestStruct");
 }
+
+#[test]
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_index"))]
+fn test_serde_de_borrowed() {
+    use rhai::serde::from_dynamic_borrowed;
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Deserialize)]
+    enum Mode {
+        Fast,
+        Safe,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Config<'a> {
+        name: &'a str,
+        #[serde(borrow)]
+        description: Cow<'a, str>,
+        #[serde(borrow)]
+        labels: BTreeMap<&'a str, &'a str>,
+        #[serde(borrow)]
+        hosts: Vec<&'a str>,
+        mode: Mode,
+    }
+
+    let engine = Engine::new();
+
+    let value = engine
+        .eval::<Dynamic>(
+            r#"
+                #{
+                    name: "server",
+                    description: "main server",
+                    labels: #{ env: "prod", region: "eu" },
+                    hosts: ["a.example.com", "b.example.com"],
+                    mode: "Safe"
+                }
+            "#,
+        )
+        .unwrap();
+
+    let config: Config = from_dynamic_borrowed(&value).unwrap();
+
+    assert_eq!(config.name, "server");
+    assert!(matches!(config.description, Cow::Borrowed("main server")));
+    assert_eq!(config.labels.len(), 2);
+    assert_eq!(config.labels["region"], "eu");
+    assert_eq!(config.hosts, ["a.example.com", "b.example.com"]);
+    assert!(matches!(config.mode, Mode::Safe));
+
+    let map = value.read_lock::<Map>().unwrap();
+    let name = map["name"].read_lock::<ImmutableString>().unwrap();
+    assert_eq!(config.name.as_ptr(), name.as_ptr());
+
+    let _ = Mode::Fast;
+}
+
+#[test]
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_index"))]
+fn test_serde_type_codec() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Point {
+        x: INT,
+        y: INT,
+    }
+
+    #[derive(Debug, Clone)]
+    struct Unknown;
+
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<Point>("Point")
+        .register_fn("point", |x: INT, y: INT| Point { x, y })
+        .register_type_codec::<Point>(
+            |p| Ok(vec![Dynamic::from(p.x), Dynamic::from(p.y)].into()),
+            |v| {
+                let a = v.into_array()?;
+                Ok(Point { x: a[0].as_int()?, y: a[1].as_int()? })
+            },
+        );
+
+    let mut scope = Scope::new();
+    scope.push_constant("origin", Point { x: 0, y: 0 });
+
+    let value = engine.eval_with_scope::<Dynamic>(&mut scope, "#{ name: \"path\", points: [origin, point(1, 2)] }").unwrap();
+
+    let encoded = engine.encode_dynamic(&value).unwrap();
+    let bytes = rmp_serde::to_vec(&encoded).unwrap();
+    let decoded = engine.decode_dynamic(rmp_serde::from_slice(&bytes).unwrap()).unwrap();
+
+    let map = decoded.cast::<Map>();
+    assert_eq!(map["name"].clone().into_string().unwrap(), "path");
+    let points = map["points"].clone().into_array().unwrap();
+    assert_eq!(points[0].clone().cast::<Point>(), Point { x: 0, y: 0 });
+    assert_eq!(points[1].clone().cast::<Point>(), Point { x: 1, y: 2 });
+
+    let encoded_scope = engine.encode_scope(&scope).unwrap();
+    let json = serde_json::to_string(&encoded_scope).unwrap();
+    let decoded_scope = engine.decode_scope(&serde_json::from_str(&json).unwrap()).unwrap();
+    assert!(decoded_scope.is_constant("origin").unwrap());
+    assert_eq!(decoded_scope.get_value::<Point>("origin").unwrap(), Point { x: 0, y: 0 });
+
+    assert!(engine.encode_dynamic(&Dynamic::from(Unknown)).is_err());
+    assert!(engine.decode_dynamic(engine.eval::<Dynamic>(r#"#{ "$type": "Unknown", "$value": 42 }"#).unwrap()).is_err());
+}
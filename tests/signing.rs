@@ -0,0 +1,75 @@
+#![cfg(feature = "signing")]
+use rhai::{Engine, EvalAltResult, ScriptSigner, INT};
+
+#[test]
+fn test_signing() {
+    // RFC 4231 test case 2
+    let signer = ScriptSigner::new("Jefe");
+    let signature = signer.sign("what do ya want for nothing?");
+
+    assert_eq!(signature, "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    assert!(signer.verify("what do ya want for nothing?", &signature.to_uppercase()));
+    assert!(!signer.verify("what do ya want for nothing!", &signature));
+    assert!(!signer.verify("what do ya want for nothing?", &signature[1..]));
+    assert!(!ScriptSigner::new("Joe").verify("what do ya want for nothing?", &signature));
+
+    // RFC 4231 test case 6 (key longer than a block)
+    let signer = ScriptSigner::new([0xaa; 131]);
+    assert_eq!(signer.sign("Test Using Larger Than Block-Size Key - Hash Key First"), "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54");
+}
+
+#[test]
+fn test_signing_compile() {
+    let signer = ScriptSigner::new("secret");
+    let script = "let x = 40; x + 2";
+    let signature = signer.sign(script);
+
+    let mut engine = Engine::new();
+
+    assert!(matches!(*engine.compile_signed(script, &signature).unwrap_err(), EvalAltResult::ErrorInvalidSignature(..)));
+
+    engine.set_script_signer(signer);
+
+    let ast = engine.compile_signed(script, &signature).unwrap();
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 42);
+
+    let err = engine.compile_signed("let x = 40; x + 3", &signature).unwrap_err();
+    assert!(matches!(*err, EvalAltResult::ErrorInvalidSignature(ref s, ..) if s == "<string>"));
+    assert!(!err.is_catchable());
+}
+
+#[cfg(not(feature = "no_module"))]
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_signing_modules() {
+    use rhai::module_resolvers::FileModuleResolver;
+    use std::fs;
+
+    let dir = std::env::temp_dir().join(format!("rhai-test-signing-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let signer = ScriptSigner::new("secret");
+    let module = "export const ANSWER = 42;";
+
+    fs::write(dir.join("good.rhai"), module).unwrap();
+    fs::write(dir.join("good.rhai.sig"), signer.sign(module)).unwrap();
+    fs::write(dir.join("bad.rhai"), "export const ANSWER = 0;").unwrap();
+    fs::write(dir.join("bad.rhai.sig"), signer.sign(module)).unwrap();
+    fs::write(dir.join("unsigned.rhai"), module).unwrap();
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(FileModuleResolver::new_with_path(&dir));
+
+    assert_eq!(engine.eval::<INT>(r#"import "unsigned" as m; m::ANSWER"#).unwrap(), 42);
+
+    engine.set_script_signer(signer);
+
+    assert_eq!(engine.eval::<INT>(r#"import "good" as m; m::ANSWER"#).unwrap(), 42);
+    assert!(engine.eval::<INT>(r#"import "bad" as m; m::ANSWER"#).is_err());
+    assert!(engine.eval::<INT>(r#"import "unsigned" as m; m::ANSWER"#).is_err());
+    assert!(matches!(*engine.compile_file(dir.join("bad.rhai")).unwrap_err(), EvalAltResult::ErrorInvalidSignature(..)));
+    assert!(engine.compile_file(dir.join("good.rhai")).is_ok());
+
+    let _ = fs::remove_dir_all(&dir);
+}
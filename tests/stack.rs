@@ -169,3 +169,31 @@ fn test_stack_overflow_ref_loop() {
 
     println!("{x:?}");
 }
+
+#[test]
+#[cfg(feature = "stack_guard")]
+#[cfg(not(feature = "no_function"))]
+fn test_stack_overflow_guard() {
+    let handle = std::thread::Builder::new()
+        .stack_size(4 * 1024 * 1024)
+        .spawn(|| {
+            let mut engine = Engine::new();
+            engine.set_max_call_levels(usize::MAX).set_max_expr_depths(0, 0).set_min_stack_remaining(512 * 1024);
+
+            let err = engine
+                .run(
+                    "
+                        fn foo(n) { if n == 0 { 0 } else { n + foo(n-1) } }
+                        foo(1000000)
+                    ",
+                )
+                .unwrap_err();
+            assert!(matches!(*err, EvalAltResult::ErrorStackOverflow(..)));
+
+            let script = format!("{}1{}", "(".repeat(100000), ")".repeat(100000));
+            assert_eq!(*engine.compile(&script).unwrap_err().0, ParseErrorType::ExprTooDeep);
+        })
+        .unwrap();
+
+    handle.join().unwrap();
+}
@@ -433,3 +433,80 @@ fn test_immutable_string() {
     let _ssx: Box<str> = x.clone().into();
     let _ssx: Box<str> = (&x).into();
 }
+
+#[test]
+fn test_string_interner() {
+    use rhai::{InternerStats, StrInterner};
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MyInterner(HashMap<(u8, String), ImmutableString>, InternerStats);
+
+    impl StrInterner for MyInterner {
+        fn intern(&mut self, category: u8, text: &str, mapper: &mut dyn FnMut(&str) -> ImmutableString) -> ImmutableString {
+            match self.0.get(&(category, text.to_string())) {
+                Some(s) => {
+                    self.1.hits += 1;
+                    s.clone()
+                }
+                None => {
+                    self.1.misses += 1;
+                    let s = mapper(text);
+                    self.0.insert((category, text.to_string()), s.clone());
+                    s
+                }
+            }
+        }
+        fn capacity(&self) -> usize {
+            usize::MAX
+        }
+        fn set_capacity(&mut self, _: usize) {}
+        fn clear(&mut self) {
+            self.0.clear();
+        }
+        fn stats(&self) -> InternerStats {
+            let mut stats = self.1;
+            stats.len = self.0.len();
+            stats
+        }
+    }
+
+    let mut engine = Engine::new();
+    engine.set_strings_interner(MyInterner::default());
+
+    let mut engine2 = Engine::new();
+    engine2.set_shared_strings_interner(engine.shared_strings_interner().unwrap());
+
+    assert_eq!(engine.eval::<INT>("let foo = 40; foo + 2").unwrap(), 42);
+
+    let stats = engine.string_interner_stats();
+    assert!(stats.len > 0);
+    assert!(stats.hits > 0);
+
+    assert_eq!(engine2.eval::<INT>("let foo = 40; foo + 2").unwrap(), 42);
+    assert!(engine2.string_interner_stats().hits > stats.hits);
+    assert_eq!(engine2.string_interner_stats().len, stats.len);
+
+    engine.set_max_strings_interned(0);
+    assert_eq!(engine.string_interner_stats(), InternerStats::default());
+}
+
+#[test]
+fn test_string_similarity() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>(r#"edit_distance("kitten", "sitting")"#).unwrap(), 3);
+    assert_eq!(engine.eval::<INT>(r#"edit_distance("", "abc")"#).unwrap(), 3);
+    assert_eq!(engine.eval::<INT>(r#"edit_distance("héllo", "hello")"#).unwrap(), 1);
+    assert_eq!(engine.eval::<INT>(r#""same".edit_distance("same")"#).unwrap(), 0);
+
+    #[cfg(not(feature = "no_float"))]
+    {
+        assert!((engine.eval::<rhai::FLOAT>(r#"similarity("martha", "marhta")"#).unwrap() - 0.9611).abs() < 0.001);
+        assert_eq!(engine.eval::<rhai::FLOAT>(r#"similarity("abc", "abc")"#).unwrap(), 1.0);
+        assert_eq!(engine.eval::<rhai::FLOAT>(r#"similarity("abc", "xyz")"#).unwrap(), 0.0);
+
+        #[cfg(not(feature = "no_index"))]
+        assert_eq!(engine.eval::<String>(r#"fuzzy_match("appel", ["apple", "apply", "maple", 42, "banana"], 0.8).to_string()"#).unwrap(), r#"["apple", "apply"]"#);
+    }
+}
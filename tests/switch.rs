@@ -305,3 +305,57 @@ fn test_switch_ranges() {
         'z'
     );
 }
+
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_switch_patterns() {
+    let engine = Engine::new();
+
+    let script = r#"
+        fn classify(value) {
+            switch value {
+                [] => "empty",
+                ["point", x, y] if x == y => `diagonal ${x}`,
+                ["point", x, y] => `point ${x},${y}`,
+                [first, ..rest] => `${first} and ${rest.len()} more`,
+                #{ kind: "circle", r } => `circle ${r}`,
+                #{ kind, .. } => `some ${kind}`,
+                42 => "answer",
+                _ => "unknown"
+            }
+        }
+    "#;
+
+    let eval = |expr: &str| engine.eval::<String>(&format!("{script} classify({expr})")).unwrap();
+
+    assert_eq!(eval("[]"), "empty");
+    assert_eq!(eval(r#"["point", 1, 1]"#), "diagonal 1");
+    assert_eq!(eval(r#"["point", 1, 2]"#), "point 1,2");
+    assert_eq!(eval(r#"["point", 1, 2, 3]"#), "point and 3 more");
+    assert_eq!(eval("[true]"), "true and 0 more");
+    assert_eq!(eval(r#"#{ kind: "circle", r: 5 }"#), "circle 5");
+    assert_eq!(eval(r#"#{ kind: "square", size: 5 }"#), "some square");
+    assert_eq!(eval("#{ size: 5 }"), "unknown");
+    assert_eq!(eval("42"), "answer");
+    assert_eq!(eval("0"), "unknown");
+
+    // Pattern variables do not leak out of the case, and shadow constants
+    assert_eq!(engine.eval::<INT>("const x = 1; let r = switch [2] { [x] => x * 10 }; r + x").unwrap(), 21);
+
+    // Literal-only patterns are still literal cases
+    assert_eq!(engine.eval::<INT>("switch [1, 2] { [1, 2] | [3, 4] => 1, [a, b] => a + b }").unwrap(), 1);
+    assert_eq!(engine.eval::<INT>("switch [3, 5] { [1, 2] | [3, 4] => 1, [a, b] => a + b }").unwrap(), 8);
+
+    // Map patterns match exactly the listed properties, unless they end with `..`
+    assert_eq!(engine.eval::<INT>("switch #{a: 1, b: 2} { #{a: 1} => 1, _ => 0 }").unwrap(), 0);
+    assert_eq!(engine.eval::<INT>("switch #{a: 1, b: 2} { #{b: 2, a: 1} => 1, _ => 0 }").unwrap(), 1);
+    assert_eq!(engine.eval::<INT>("switch #{a: 1, b: 2} { #{a: 1, ..} => 1, _ => 0 }").unwrap(), 1);
+    assert_eq!(engine.eval::<INT>("switch #{a: 1, b: 2} { #{a} => a, _ => 0 }").unwrap(), 0);
+    assert_eq!(engine.eval::<INT>("switch #{a: 1, b: 2} { #{a, b} => a + b, _ => 0 }").unwrap(), 3);
+    assert_eq!(engine.eval::<INT>("switch #{a: 1, b: 2} { #{a: 1, c, ..} => c, _ => 0 }").unwrap(), 0);
+    assert_eq!(engine.eval::<INT>("switch [#{a: 1, b: 2}] { [#{a: 1, ..}] => 1, _ => 0 }").unwrap(), 1);
+
+    assert!(matches!(engine.compile("switch x { [a] | [1] => 1 }").unwrap_err().err_type(), ParseErrorType::BadInput(..)));
+    assert!(matches!(engine.compile("switch x { [a, a] => 1 }").unwrap_err().err_type(), ParseErrorType::DuplicatedVariable(..)));
+}
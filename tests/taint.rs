@@ -0,0 +1,76 @@
+use rhai::{Engine, EvalAltResult, INT};
+
+fn make_engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine
+        .register_fn("user_input", || "O'Brien".to_string())
+        .register_fn("sql_exec", |sql: &str| sql.len() as INT)
+        .register_taint_source("user_input")
+        .register_taint_sink("sql_exec");
+
+    engine
+}
+
+fn is_taint_error(err: &EvalAltResult) -> bool {
+    matches!(err, EvalAltResult::ErrorTaintedValue(f, ..) if f == "sql_exec")
+}
+
+#[test]
+fn test_taint_propagation() {
+    let engine = make_engine();
+
+    assert!(engine.taint_tracking());
+
+    // Untainted values pass
+    assert_eq!(engine.eval::<INT>(r#"sql_exec("SELECT 1")"#).unwrap(), 8);
+
+    // Directly
+    assert!(is_taint_error(&engine.eval::<INT>("sql_exec(user_input())").unwrap_err()));
+
+    // Through variables, operators and function calls
+    for script in [
+        r#"let x = user_input(); sql_exec("SELECT " + x)"#,
+        r#"let x = user_input(); let y = "SELECT "; y += x; sql_exec(y)"#,
+        r#"let x = user_input(); sql_exec(`SELECT ${x}`)"#,
+        r#"let x = user_input(); sql_exec(x.to_upper())"#,
+        r#"let x = user_input(); let y = ""; y.append(x); sql_exec(y)"#,
+        r#"let x = user_input(); let y = ""; for c in x { y += c; } sql_exec(y)"#,
+        r#"let x = user_input(); sql_exec(x[0].to_string())"#,
+        r#"let x = user_input(); x.tag = 0; sql_exec(x)"#,
+        r#"fn wrap(s) { `(${s})` } sql_exec(wrap(user_input()))"#,
+    ] {
+        assert!(is_taint_error(&engine.eval::<INT>(script).unwrap_err()), "{}", script);
+    }
+
+    // Taint violations cannot be caught
+    assert!(is_taint_error(&engine.eval::<INT>("try { sql_exec(user_input()) } catch { 0 }").unwrap_err()));
+
+    // Unrelated values are not tainted
+    assert_eq!(engine.eval::<INT>(r#"let x = user_input(); let n = "abc"; sql_exec(n)"#).unwrap(), 3);
+
+    // Taint is independent of tags
+    assert_eq!(engine.eval::<INT>(r#"let n = "abc"; n.tag = -1; sql_exec(n)"#).unwrap(), 3);
+
+    let mut value = rhai::Dynamic::from("abc");
+    value.set_tag(-1);
+    assert!(!value.is_tainted());
+    value.set_tainted(true);
+    assert_eq!(value.tag(), -1);
+    assert!(value.clone().is_tainted());
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_taint_containers() {
+    let mut engine = make_engine();
+
+    engine.register_fn("sql_exec", |args: rhai::Array| args.len() as INT);
+
+    assert!(is_taint_error(&engine.eval::<INT>(r#"sql_exec(["SELECT ?", user_input()])"#).unwrap_err()));
+
+    engine.set_taint_tracking(false);
+
+    assert!(!engine.taint_tracking());
+    assert_eq!(engine.eval::<INT>("sql_exec(user_input())").unwrap(), 7);
+}
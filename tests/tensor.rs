@@ -0,0 +1,43 @@
+#![cfg(feature = "tensor")]
+#![cfg(not(feature = "no_float"))]
+#![cfg(not(feature = "no_index"))]
+use rhai::packages::Tensor;
+use rhai::{Engine, FLOAT, INT};
+
+#[test]
+fn test_tensor() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("type_of(tensor([1, 2]))").unwrap(), "Tensor");
+    assert_eq!(engine.eval::<String>("tensor([[1, 2, 3], [4, 5, 6]]).shape.to_string()").unwrap(), "[2, 3]");
+    assert!(engine.eval::<Tensor>("tensor([[1, 2], [3]])").is_err());
+    assert!(engine.eval::<Tensor>(r#"tensor([1, "x"])"#).is_err());
+
+    let t = engine.eval::<Tensor>("tensor([[1, 2], [3, 4]]) + tensor([10, 20])").unwrap();
+    assert_eq!(t.shape(), [2, 2]);
+    assert_eq!(t.data(), [11.0, 22.0, 13.0, 24.0]);
+
+    let t = engine.eval::<Tensor>("2 * tensor([1, 2]) - 0.5").unwrap();
+    assert_eq!(t.data(), [1.5, 3.5]);
+    assert!(engine.eval::<Tensor>("tensor([1, 2, 3]) + tensor([1, 2])").is_err());
+
+    assert_eq!(engine.eval::<String>("matmul(tensor([[1, 2], [3, 4]]), tensor([[5], [6]])).to_string()").unwrap(), "[[17.0], [39.0]]");
+    assert_eq!(engine.eval::<FLOAT>("matmul(tensor([1, 2]), tensor([3, 4])).sum()").unwrap(), 11.0);
+    assert!(engine.eval::<Tensor>("matmul(tensor([1, 2]), tensor([1, 2, 3]))").is_err());
+    assert_eq!(engine.eval::<String>("tensor([[1, 2, 3], [4, 5, 6]]).transpose().to_string()").unwrap(), "[[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]");
+    assert_eq!(engine.eval::<String>("tensor([1, 2, 3, 4, 5, 6]).reshape([3, 2])[-1].to_string()").unwrap(), "[5.0, 6.0]");
+
+    assert_eq!(engine.eval::<FLOAT>("let t = eye(3); t[1][1]").unwrap(), 1.0);
+    assert_eq!(engine.eval::<String>("let t = tensor([1, 2, 3, 4]); t[1..3].to_string()").unwrap(), "[2.0, 3.0]");
+    assert_eq!(engine.eval::<String>("let t = zeros([2, 2]); t[0] = tensor([1, 2]); t.to_string()").unwrap(), "[[1.0, 2.0], [0.0, 0.0]]");
+    assert_eq!(engine.eval::<String>("let t = zeros([3]); t[1] = 42; t.to_string()").unwrap(), "[0.0, 42.0, 0.0]");
+    assert!(engine.eval::<FLOAT>("tensor([1, 2])[2]").is_err());
+
+    assert_eq!(engine.eval::<INT>("let n = 0; for row in tensor([[1, 2], [3, 4]]) { n += row.len } n").unwrap(), 4);
+    assert_eq!(engine.eval::<FLOAT>("let s = 0.0; for x in tensor([1, 2, 3]) { s += x } s").unwrap(), 6.0);
+    assert!(engine.eval::<bool>("tensor([1, 2]) == tensor([1.0, 2.0])").unwrap());
+
+    let t = Tensor::new([2, 2], [1.0, 2.0, 3.0, 4.0]).unwrap();
+    assert_eq!(t.ndim(), 2);
+    assert!(Tensor::new([3], [1.0]).is_none());
+}
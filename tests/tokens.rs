@@ -94,3 +94,418 @@ fn test_tokens_unicode_xid_ident() {
     );
     assert!(result.is_err());
 }
+
+#[test]
+#[cfg(feature = "unicode-xid-ident")]
+fn test_tokens_unicode_identifier_policy() {
+    let mut engine = Engine::new();
+
+    assert!(engine.allow_unicode_identifiers());
+
+    // "café" written with a combining acute accent is the same as with a precomposed "é"
+    assert_eq!(engine.eval::<INT>("let cafe\u{301} = 42; café").unwrap(), 42);
+
+    // "pаypal" with a Cyrillic "а" is rejected as a mixed-script identifier
+    assert!(engine.compile("let p\u{430}ypal = 1;").is_err());
+
+    engine.set_allow_unicode_identifiers(false);
+
+    assert!(engine.compile("let café = 42;").is_err());
+    assert_eq!(engine.eval::<INT>("let cafe = 42; cafe").unwrap(), 42);
+}
+
+#[test]
+fn test_tokens_reserved_keywords() {
+    let mut engine = Engine::new();
+
+    assert!(engine.is_keyword_reserved("var"));
+    assert!(!engine.is_keyword_reserved("defer"));
+
+    assert_eq!(engine.eval::<INT>("let defer = 42; defer").unwrap(), 42);
+
+    engine.reserve_keyword("defer");
+
+    assert!(engine.is_keyword_reserved("defer"));
+    assert!(engine.compile("let defer = 42;").is_err());
+    assert!(engine.compile("fn defer() { 42 }").is_err());
+    assert!(engine.compile("fn foo(defer) { defer }").is_err());
+    assert_eq!(engine.eval::<INT>("let deferred = 42; deferred").unwrap(), 42);
+}
+
+#[cfg(not(feature = "no_std"))]
+#[test]
+fn test_tokens_reader() {
+    use std::io::Read;
+
+    /// A reader that returns one byte at a time.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self.0.split_first() {
+                Some((&b, rest)) if !buf.is_empty() => {
+                    buf[0] = b;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    let engine = Engine::new();
+
+    let script = "#!/usr/bin/env rhai\nlet x = \"héllo, 世界\";\nx.len + 33";
+    let ast = engine.compile_from_reader(Trickle(script.as_bytes())).unwrap();
+    assert_eq!(engine.eval_ast::<INT>(&ast).unwrap(), 42);
+
+    let _err = engine.compile_from_reader("\n\nlet x = ;".as_bytes()).unwrap_err();
+    #[cfg(not(feature = "no_position"))]
+    assert_eq!(_err.position().line(), Some(3));
+
+    let err = engine.compile_from_reader(&b"let x = \"\xff\";"[..]).unwrap_err();
+    assert!(matches!(*err, rhai::EvalAltResult::ErrorSystem(..)));
+}
+
+#[test]
+#[cfg(not(feature = "no_position"))]
+#[cfg(not(feature = "no_function"))]
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_index"))]
+fn test_tokens_codemod() {
+    use rhai::Codemod;
+
+    let engine = Engine::new();
+
+    let codemod = Codemod::new().rename_fn("old_sum", "sum").reorder_args("old_sum", [1, 0]).reorder_args("pad", [2, 0, 1]);
+
+    let (script, changes) = engine
+        .apply_codemod(
+            &codemod,
+            "
+                fn old_sum(a, b) { a + b }
+                let x = old_sum([1, 2], old_sum(3, 4));   // nested
+                let y = pad(|a, b| a + b, #{ a: 1, b: 2 }, (1 + 2) * 3,);
+                x.old_sum(1) + lib::old_sum(1, 2) + pad(1, 2) + old_sum()
+            ",
+        )
+        .unwrap();
+
+    assert_eq!(
+        script,
+        "
+                fn sum(a, b) { a + b }
+                let x = sum(sum(4, 3), [1, 2]);   // nested
+                let y = pad((1 + 2) * 3, |a, b| a + b, #{ a: 1, b: 2 },);
+                x.sum(1) + lib::old_sum(1, 2) + pad(1, 2) + sum()
+            "
+    );
+
+    let descriptions = changes.iter().map(|c| c.description.as_str()).collect::<Vec<_>>();
+
+    assert_eq!(
+        descriptions,
+        [
+            "renamed function definition 'old_sum' to 'sum'",
+            "renamed function 'old_sum' to 'sum'",
+            "reordered arguments of 'old_sum'",
+            "renamed function 'old_sum' to 'sum'",
+            "reordered arguments of 'old_sum'",
+            "reordered arguments of 'pad'",
+            "renamed function 'old_sum' to 'sum'",
+            "renamed function 'old_sum' to 'sum'",
+        ]
+    );
+    assert_eq!(changes[1].position.line(), Some(3));
+
+    assert!(engine.apply_codemod(&codemod, "old_sum(1,").is_err());
+}
+
+#[test]
+#[cfg(not(feature = "no_position"))]
+#[cfg(not(feature = "no_function"))]
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_closure"))]
+fn test_tokens_minify() {
+    let engine = Engine::new();
+
+    let script = r#"
+        // Sum the values of a map
+        fn total(data, offset) {
+            let result = offset;
+            for item in data.values() { result += item; }
+            let adder = |x| x + result;     // capture
+            let point = #{ result: 1, counter: 2 };
+            let counter = point.counter;
+            adder.call(counter) + point.result
+        }
+        /* the answer */
+        let total_value = total(#{ first: 10, second: 20 }, 9);
+        `${total_value}`
+    "#;
+
+    let minified = engine.minify_script(script).unwrap();
+
+    assert_eq!(engine.eval::<String>(&minified).unwrap(), engine.eval::<String>(script).unwrap());
+    assert!(minified.len() < script.len() / 2);
+
+    assert!(!minified.contains("Sum the values") && !minified.contains("answer"));
+    assert!(!minified.contains("data") && !minified.contains("offset") && !minified.contains("adder"));
+    assert!(minified.contains("fn total(") && !minified.contains("total_value"));
+    assert!(minified.contains("point.result") || minified.contains(".result"));
+
+    // Variables at global level are renamed, unless they may be provided by the host
+    let script = "let counter = 40; let step = 1; counter += step; counter + step + extra";
+    let minified = engine.minify_script(script).unwrap();
+    assert!(!minified.contains("counter") && !minified.contains("step") && minified.contains("extra"));
+
+    let mut scope = rhai::Scope::new();
+    scope.push("extra", 0 as INT);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, &minified).unwrap(), 42);
+
+    let minified = engine.minify_script("let total = total + 1; let shadow = total; shadow").unwrap();
+    assert!(minified.contains("total") && !minified.contains("shadow"));
+
+    // Functions with dynamic variable lookups are untouched
+    let minified = engine.minify_script("fn foo(value) { eval(\"value\") }").unwrap();
+    assert_eq!(minified, "fn foo(value){eval(\"value\")}");
+
+    assert!(rhai::minify("let x = ;").is_err());
+}
+
+#[test]
+#[cfg(not(feature = "no_position"))]
+fn test_tokens_classified() {
+    use rhai::TokenCategory::*;
+
+    let mut engine = Engine::new();
+    #[cfg(not(feature = "no_custom_syntax"))]
+    engine.register_custom_operator("foo", 160).unwrap();
+    engine.disable_symbol("while");
+
+    let script = "/* hello */ let x = `a${#{b: 1}.b}c${2}`;\r\nx foo 'é' + \"ü\" while // end\n";
+
+    let tokens = engine.tokenize_classified(script).into_iter().map(|t| (t.category, &script[t.span])).collect::<Vec<_>>();
+
+    #[cfg(not(feature = "no_custom_syntax"))]
+    let foo = (CustomKeyword, "foo");
+    #[cfg(feature = "no_custom_syntax")]
+    let foo = (Identifier, "foo");
+
+    assert_eq!(
+        tokens,
+        [
+            (Comment, "/* hello */"),
+            (Keyword, "let"),
+            (Identifier, "x"),
+            (Operator, "="),
+            (Interpolation, "`a$"),
+            (Interpolation, "{"),
+            (Punctuation, "#{"),
+            (Identifier, "b"),
+            (Punctuation, ":"),
+            (Number, "1"),
+            (Punctuation, "}"),
+            (Punctuation, "."),
+            (Identifier, "b"),
+            (Interpolation, "}"),
+            (Interpolation, "c$"),
+            (Interpolation, "{"),
+            (Number, "2"),
+            (Interpolation, "}"),
+            (String, "`"),
+            (Punctuation, ";"),
+            (Identifier, "x"),
+            foo,
+            (String, "'é'"),
+            (Operator, "+"),
+            (String, "\"ü\""),
+            (Reserved, "while"),
+            (Comment, "// end"),
+        ]
+    );
+
+    let tokens = engine.tokenize_classified("//! module doc\n/// doc\nfn foo() {}");
+    assert_eq!(tokens[0].category, Comment);
+    assert_eq!(tokens[0].span, 0..14);
+    assert_eq!(tokens[1].category, Comment);
+    assert_eq!(tokens[1].span, 15..22);
+    assert_eq!(tokens[1].position.line(), Some(2));
+
+    let tokens = engine.tokenize_classified("let x = \"abc");
+    assert_eq!(tokens.last().unwrap().category, Error);
+    assert_eq!(tokens.last().unwrap().position.line(), Some(1));
+}
+
+#[test]
+#[cfg(feature = "metadata")]
+#[cfg(not(feature = "no_position"))]
+#[cfg(not(feature = "no_function"))]
+#[cfg(not(feature = "no_object"))]
+fn test_tokens_signature_help() {
+    let mut engine = Engine::new();
+    engine.register_fn("clamp", |x: INT, lo: INT, hi: INT| x.max(lo).min(hi));
+
+    let script = "/// Add two numbers.\nfn add(a, b) { a + b }\nlet z = add(clamp(x, 0, 10), ";
+    let help = engine.signature_help(script, script.len()).unwrap();
+    assert_eq!(help.name, "add");
+    assert_eq!(help.active_arg, 1);
+    assert_eq!(help.signatures.len(), 1);
+    assert!(help.signatures[0].is_script);
+    assert_eq!(help.signatures[0].params, ["a", "b"]);
+    assert_eq!(help.signatures[0].comments, ["/// Add two numbers."]);
+    assert_eq!(help.signatures[0].active_param, Some(1));
+
+    let offset = script.find("0, 10").unwrap();
+    let help = engine.signature_help(script, offset).unwrap();
+    assert_eq!(help.name, "clamp");
+    assert_eq!(help.active_arg, 1);
+    assert!(!help.signatures[0].is_script);
+    assert_eq!(help.signatures[0].params.len(), 3);
+    assert_eq!(help.signatures[0].active_param, Some(1));
+
+    let script = "x.clamp(0, ";
+    let help = engine.signature_help(script, script.len()).unwrap();
+    assert!(help.is_method_call);
+    assert_eq!(help.active_arg, 1);
+    assert_eq!(help.signatures[0].active_param, Some(2));
+
+    let script = "clamp(1, 2, 3, ";
+    let help = engine.signature_help(script, script.len()).unwrap();
+    assert_eq!(help.signatures[0].active_param, None);
+
+    let script = "let x = [clamp(1, 2, 3), 4";
+    assert!(engine.signature_help(script, script.len()).is_none());
+    assert!(engine.signature_help("fn foo(a, ", 10).is_none());
+
+    #[cfg(not(feature = "no_module"))]
+    {
+        let mut module = rhai::Module::new();
+        module.set_native_fn("double", |x: INT| Ok(x * 2));
+        engine.register_static_module("math", module.into());
+
+        let script = "math::double(";
+        let help = engine.signature_help(script, script.len()).unwrap();
+        assert_eq!(help.name, "math::double");
+        assert_eq!(help.signatures.len(), 1);
+        assert_eq!(help.signatures[0].active_param, Some(0));
+
+        let script = "double(";
+        assert!(engine.signature_help(script, script.len()).unwrap().signatures.is_empty());
+    }
+}
+
+#[test]
+#[cfg(feature = "metadata")]
+#[cfg(not(feature = "no_position"))]
+fn test_tokens_completions() {
+    let mut engine = Engine::new();
+    engine.register_fn("zz_clamp", |x: INT, lo: INT, hi: INT| x.max(lo).min(hi));
+
+    let mut scope = rhai::Scope::new();
+    scope.push("zz_total", 0 as INT);
+
+    let script = "let zz_x = 1; zz_";
+    let completions = engine.completions(script, script.len(), Some(&scope));
+    assert_eq!(completions.start, 14);
+    assert_eq!(completions.candidates, ["zz_clamp", "zz_total", "zz_x"]);
+
+    let completions = engine.completions(script, script.len(), None);
+    assert_eq!(completions.candidates, ["zz_clamp", "zz_x"]);
+
+    let completions = engine.completions("whi", 3, None);
+    assert_eq!(completions.start, 0);
+    assert!(completions.candidates.iter().any(|c| c == "while"));
+
+    engine.disable_symbol("while");
+    assert!(!engine.completions("whi", 3, None).candidates.iter().any(|c| c == "while"));
+
+    // Cursor in the middle of a word
+    let completions = engine.completions("zz_clamp(1, 2, 3)", 4, None);
+    assert_eq!(completions.start, 0);
+    assert_eq!(completions.candidates, ["zz_clamp"]);
+
+    #[cfg(not(feature = "no_module"))]
+    {
+        let mut module = rhai::Module::new();
+        module.set_native_fn("double", |x: INT| Ok(x * 2));
+        module.set_native_fn("triple", |x: INT| Ok(x * 3));
+        engine.register_static_module("math", module.into());
+
+        let script = "math::d";
+        let completions = engine.completions(script, script.len(), None);
+        assert_eq!(completions.start, 6);
+        assert_eq!(completions.candidates, ["double"]);
+
+        let completions = engine.completions("math::", 6, None);
+        assert_eq!(completions.candidates, ["double", "triple"]);
+
+        assert!(engine.completions("mat", 3, None).candidates.iter().any(|c| c == "math"));
+    }
+}
+
+#[test]
+#[cfg(not(feature = "no_position"))]
+fn test_tokens_incomplete() {
+    let engine = Engine::new();
+
+    assert!(engine.is_script_incomplete("if x > 0 {"));
+    assert!(engine.is_script_incomplete("let x = [1, 2,"));
+    assert!(engine.is_script_incomplete("let x = 40 +"));
+    assert!(engine.is_script_incomplete("foo(1,\n2"));
+    assert!(!engine.is_script_incomplete(""));
+    assert!(!engine.is_script_incomplete("let x = 42;"));
+    assert!(!engine.is_script_incomplete("let x = 42; }"));
+    assert!(!engine.is_script_incomplete("let = 42; if x > 0 {"));
+}
+
+#[test]
+#[cfg(feature = "metadata")]
+#[cfg(not(feature = "no_position"))]
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_index"))]
+fn test_tokens_infer_type() {
+    let mut engine = Engine::new();
+    engine.register_fn("half", |x: INT| x / 2);
+    engine.register_fn("check", |x: INT| -> Result<bool, Box<rhai::EvalAltResult>> { Ok(x > 0) });
+
+    let int = std::any::type_name::<INT>();
+
+    let script = r#"
+        const X = 40;
+        let s = "hello";
+        let n = s.len();
+        let c = s[0];
+        let y = half(X + 2);
+        let ok = check(y) || false;
+        let v = if ok { n } else { y };
+        for i in 0..10 { let q = i < 5; }
+        let d = s.to_upper().len();
+        let w = unknown(s);
+    "#;
+
+    let infer = |word: &str| {
+        let offset = script.find(word).unwrap();
+        engine.infer_type_at(script, offset).unwrap()
+    };
+
+    let x = infer("X +");
+    assert_eq!(x.types, [int]);
+    assert_eq!(x.value.unwrap().as_int().unwrap(), 40);
+    assert!(infer("s =").value.is_none());
+    assert_eq!(infer("s =").types, ["string"]);
+    assert_eq!(infer("n =").types, [int]);
+    assert_eq!(infer("c =").types, ["char"]);
+    assert_eq!(infer("y =").types, [int]);
+    assert_eq!(infer("ok =").types, ["bool"]);
+    assert_eq!(infer("check(").types, ["bool"]);
+    assert_eq!(infer("v =").types, [int]);
+    assert_eq!(infer("i <").types, [int]);
+    assert_eq!(infer("q =").types, ["bool"]);
+    assert_eq!(infer("to_upper").types, ["string"]);
+    assert_eq!(infer("d =").types, [int]);
+    assert!(infer("w =").types.is_empty());
+
+    assert!(engine.infer_type_at("let x = ", 4).is_none());
+}
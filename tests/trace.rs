@@ -0,0 +1,56 @@
+use rhai::{Engine, Trace, TraceEvent};
+use std::sync::{Arc, RwLock};
+
+#[test]
+fn test_trace() {
+    let text = Arc::new(RwLock::new(String::new()));
+    let recorder = text.clone();
+
+    let mut engine = Engine::new();
+    engine.on_trace(move |event| {
+        let line = event.to_string() + "\n";
+        recorder.write().unwrap().push_str(&line);
+    });
+
+    #[cfg(not(feature = "no_function"))]
+    engine
+        .run(
+            r#"
+                fn greet(name) { "hello,\t" + name }
+                let x = 40;
+                x += 2;
+                let s = greet("world");
+            "#,
+        )
+        .unwrap();
+    #[cfg(feature = "no_function")]
+    engine.run("let x = 40; x += 2; let s = max(x, 0);").unwrap();
+
+    let text = text.read().unwrap().clone();
+    let parsed = Trace::parse(&text).unwrap();
+    assert_eq!(parsed.to_string(), text);
+
+    // Operators are not recorded as calls
+    let calls = parsed.events().iter().filter(|e| matches!(e, TraceEvent::Call(..))).count();
+    assert_eq!(calls, 1);
+
+    let mut replay = parsed.replay();
+    assert_eq!(replay.current(), None);
+
+    replay.step_until(|e| matches!(e, TraceEvent::Assign(name, ..) if name == "x"));
+    assert_eq!(replay.variables()["x"], "40");
+
+    replay.step_until(|e| matches!(e, TraceEvent::Assign(name, ..) if name == "x"));
+    assert_eq!(replay.variables()["x"], "42");
+
+    assert!(replay.step_until(|_| false).is_none());
+    assert_eq!(replay.index(), parsed.len());
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(replay.variables()["s"], "\"hello,\\tworld\"");
+
+    replay.seek(None);
+    assert!(replay.variables().is_empty());
+    assert!(replay.step_back().is_none());
+
+    assert_eq!(Trace::parse("S\t1:1\nX\t1:1"), Err(2));
+}
@@ -452,3 +452,33 @@ fn test_var_scope_cloning() {
     engine.run_with_scope(&mut scope, "let x = 42; print(x + foo.field);").unwrap();
     assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "let x = 42; x + foo.field").unwrap(), 43);
 }
+
+#[test]
+fn test_var_scope_speculate() {
+    let engine = Engine::new();
+
+    let mut scope = Scope::new();
+    scope.push("x", 1 as INT);
+    scope.push_constant("y", 2 as INT);
+
+    #[cfg(not(feature = "no_index"))]
+    scope.push("a", rhai::Array::new());
+
+    #[cfg(not(feature = "no_index"))]
+    let script = "x += y; a.push(x); let z = 0; if x > 2 { throw x; }";
+    #[cfg(feature = "no_index")]
+    let script = "x += y; let z = 0; if x > 2 { throw x; }";
+
+    assert!(scope.speculate(|scope| engine.run_with_scope(scope, script)).is_err());
+    assert_eq!(scope.len(), if cfg!(feature = "no_index") { 2 } else { 3 });
+    assert_eq!(scope.get_value::<INT>("x").unwrap(), 1);
+    assert_eq!(scope.is_constant("y"), Some(true));
+    assert!(!scope.contains("z"));
+    #[cfg(not(feature = "no_index"))]
+    assert!(scope.get_value::<rhai::Array>("a").unwrap().is_empty());
+
+    assert_eq!(scope.speculate(|scope| engine.eval_with_scope::<INT>(scope, "x -= y; let z = x; z")).unwrap(), -1);
+    assert_eq!(scope.get_value::<INT>("x").unwrap(), -1);
+    assert_eq!(scope.get_value::<INT>("z").unwrap(), -1);
+    assert_eq!(scope.is_constant("y"), Some(true));
+}
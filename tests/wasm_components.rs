@@ -0,0 +1,61 @@
+#![cfg(feature = "wasm-components")]
+#![cfg(not(feature = "no_index"))]
+#![cfg(not(feature = "no_object"))]
+use rhai::module_resolvers::WasmComponentModuleResolver;
+use rhai::{Engine, EvalAltResult, INT};
+
+const COMPONENT: &str = r#"
+    (component
+        (core module $m
+            (global $total (mut i32) (i32.const 0))
+            (func (export "add") (param i32 i32) (result i32)
+                local.get 0
+                local.get 1
+                i32.add)
+            (func (export "accumulate") (param i32) (result i32)
+                global.get $total
+                local.get 0
+                i32.add
+                global.set $total
+                global.get $total)
+            (func (export "is-even") (param i32) (result i32)
+                local.get 0
+                i32.const 1
+                i32.and
+                i32.eqz))
+        (core instance $i (instantiate $m))
+        (func (export "add") (param "a" s32) (param "b" s32) (result s32)
+            (canon lift (core func $i "add")))
+        (func (export "accumulate") (param "x" u8) (result u32)
+            (canon lift (core func $i "accumulate")))
+        (func (export "is-even") (param "x" s32) (result bool)
+            (canon lift (core func $i "is-even"))))
+"#;
+
+#[test]
+fn test_wasm_component_module() {
+    let resolver = WasmComponentModuleResolver::new();
+    let module = resolver.create_module(COMPONENT).unwrap();
+
+    let mut engine = Engine::new();
+    engine.register_static_module("calc", module.into());
+
+    assert_eq!(engine.eval::<INT>("calc::add(40, 2)").unwrap(), 42);
+    assert!(engine.eval::<bool>("calc::is_even(42)").unwrap());
+    assert!(!engine.eval::<bool>("calc::is_even(41)").unwrap());
+
+    // State is kept between calls
+    assert_eq!(engine.eval::<INT>("calc::accumulate(1); calc::accumulate(2)").unwrap(), 3);
+
+    assert!(matches!(*engine.eval::<INT>("calc::accumulate(256)").unwrap_err(), EvalAltResult::ErrorArithmetic(..)));
+    assert!(matches!(*engine.eval::<INT>(r#"calc::add(40, "x")"#).unwrap_err(), EvalAltResult::ErrorMismatchDataType(..)));
+    assert!(matches!(*engine.eval::<INT>("calc::add(40)").unwrap_err(), EvalAltResult::ErrorFunctionNotFound(..)));
+}
+
+#[test]
+fn test_wasm_component_module_resolver() {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(WasmComponentModuleResolver::new_with_path("no/such/path"));
+
+    assert!(matches!(*engine.run(r#"import "calc" as calc;"#).unwrap_err(), EvalAltResult::ErrorModuleNotFound(..)));
+}